@@ -192,6 +192,7 @@ pub fn run() -> sc_cli::Result<()> {
 						let jaeger_agent = None;
 						let telemetry_worker_handle = None;
 						let program_path = None;
+						let availability_pruning_config = Default::default();
 						let overseer_enable_anyways = false;
 
 						polkadot_service::new_full::<rialto_runtime::RuntimeApi, ExecutorDispatch, _>(
@@ -202,6 +203,7 @@ pub fn run() -> sc_cli::Result<()> {
 							jaeger_agent,
 							telemetry_worker_handle,
 							program_path,
+							availability_pruning_config,
 							overseer_enable_anyways,
 							overseer_gen,
 						)
@@ -510,6 +510,7 @@ construct_runtime!(
 		Paras: polkadot_runtime_parachains::paras::{Pallet, Call, Storage, Event, Config},
 		Initializer: polkadot_runtime_parachains::initializer::{Pallet, Call, Storage},
 		Dmp: polkadot_runtime_parachains::dmp::{Pallet, Call, Storage},
+		MessagingMetrics: polkadot_runtime_parachains::messaging_metrics::{Pallet, Storage},
 		Ump: polkadot_runtime_parachains::ump::{Pallet, Call, Storage, Event},
 		Hrmp: polkadot_runtime_parachains::hrmp::{Pallet, Call, Storage, Event<T>, Config},
 		SessionInfo: polkadot_runtime_parachains::session_info::{Pallet, Storage},
@@ -517,7 +518,7 @@ construct_runtime!(
 		// Parachain Onboarding Pallets
 		Registrar: polkadot_runtime_common::paras_registrar::{Pallet, Call, Storage, Event<T>},
 		Slots: polkadot_runtime_common::slots::{Pallet, Call, Storage, Event<T>},
-		ParasSudoWrapper: polkadot_runtime_common::paras_sudo_wrapper::{Pallet, Call},
+		ParasSudoWrapper: polkadot_runtime_common::paras_sudo_wrapper::{Pallet, Call, Event},
 	}
 );
 
@@ -823,6 +824,10 @@ impl_runtime_apis! {
 		{
 			polkadot_runtime_parachains::runtime_api_impl::v2::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: polkadot_primitives::v2::SessionIndex) -> Option<polkadot_primitives::v2::ExecutorParams> {
+			polkadot_runtime_parachains::runtime_api_impl::v2::session_executor_params::<Runtime>(session_index)
+		}
 	}
 
 	impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
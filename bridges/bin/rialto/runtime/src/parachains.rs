@@ -28,9 +28,10 @@ use polkadot_runtime_common::{paras_registrar, paras_sudo_wrapper, slots};
 use polkadot_runtime_parachains::{
 	configuration as parachains_configuration, dmp as parachains_dmp, hrmp as parachains_hrmp,
 	inclusion as parachains_inclusion, initializer as parachains_initializer,
-	origin as parachains_origin, paras as parachains_paras,
-	paras_inherent as parachains_paras_inherent, scheduler as parachains_scheduler,
-	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
+	messaging_metrics as parachains_messaging_metrics, origin as parachains_origin,
+	paras as parachains_paras, paras_inherent as parachains_paras_inherent,
+	scheduler as parachains_scheduler, session_info as parachains_session_info,
+	shared as parachains_shared, ump as parachains_ump,
 };
 use sp_runtime::transaction_validity::TransactionPriority;
 
@@ -55,7 +56,11 @@ impl parachains_configuration::Config for Runtime {
 	type WeightInfo = parachains_configuration::TestWeightInfo;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = parachains_dmp::TestWeightInfo;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
 	type Event = Event;
@@ -142,7 +147,9 @@ impl slots::Config for Runtime {
 	type ForceOrigin = EnsureRoot<AccountId>;
 }
 
-impl paras_sudo_wrapper::Config for Runtime {}
+impl paras_sudo_wrapper::Config for Runtime {
+	type Event = Event;
+}
 
 pub struct ZeroWeights;
 
@@ -162,6 +169,9 @@ impl polkadot_runtime_common::paras_registrar::WeightInfo for ZeroWeights {
 	fn swap() -> Weight {
 		0
 	}
+	fn register_parathread() -> Weight {
+		0
+	}
 }
 
 impl polkadot_runtime_common::slots::WeightInfo for ZeroWeights {
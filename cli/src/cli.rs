@@ -126,6 +126,18 @@ pub struct RunCmd {
 	/// commonly `127.0.0.1:4040`.
 	#[clap(long)]
 	pub pyroscope_server: Option<String>,
+
+	/// How many hours the availability store keeps finalized data. Defaults to 25.
+	///
+	/// Validators must keep the data for as long as disputes may be raised about it and refuse to
+	/// start with less, so only collators and full nodes with small disks should lower this.
+	#[clap(long, value_name = "HOURS")]
+	pub av_store_keep_finalized: Option<u64>,
+
+	/// How many minutes the availability store keeps the data of candidates which are not
+	/// included in any unfinalized block. Defaults to 60.
+	#[clap(long, value_name = "MINUTES")]
+	pub av_store_keep_unfinalized: Option<u64>,
 }
 
 #[allow(missing_docs)]
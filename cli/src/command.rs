@@ -20,7 +20,7 @@ use log::info;
 use sc_cli::{Role, RuntimeVersion, SubstrateCli};
 use service::{self, IdentifyVariant};
 use sp_core::crypto::Ss58AddressFormatRegistry;
-use std::net::ToSocketAddrs;
+use std::{net::ToSocketAddrs, time::Duration};
 
 pub use crate::error::Error;
 pub use polkadot_performance_test::PerfCheckError;
@@ -279,6 +279,14 @@ where
 		None
 	};
 
+	let mut availability_pruning_config = service::AvailabilityPruningConfig::default();
+	if let Some(hours) = cli.run.av_store_keep_finalized {
+		availability_pruning_config.keep_finalized_for = Duration::from_secs(hours * 60 * 60);
+	}
+	if let Some(minutes) = cli.run.av_store_keep_unfinalized {
+		availability_pruning_config.keep_unavailable_for = Duration::from_secs(minutes * 60);
+	}
+
 	runner.run_node_until_exit(move |config| async move {
 		let role = config.role.clone();
 
@@ -291,6 +299,7 @@ where
 				cli.run.beefy,
 				jaeger_agent,
 				None,
+				availability_pruning_config,
 				false,
 				overseer_gen,
 			)
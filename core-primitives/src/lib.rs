@@ -24,6 +24,8 @@ use parity_scale_codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use parity_util_mem::MallocSizeOf;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	generic,
 	traits::{IdentifyAccount, Verify},
@@ -65,10 +67,31 @@ pub type Hash = sp_core::H256;
 /// This type is produced by [`CandidateReceipt::hash`].
 ///
 /// This type makes it easy to enforce that a hash is a candidate hash on the type level.
+///
+/// With the `std` feature, it is serialized as a `0x`-prefixed hex string and may be parsed from
+/// one, with or without the prefix.
 #[derive(Clone, Copy, Encode, Decode, Hash, Eq, PartialEq, Default, PartialOrd, Ord, TypeInfo)]
-#[cfg_attr(feature = "std", derive(MallocSizeOf))]
+#[cfg_attr(feature = "std", derive(MallocSizeOf, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(transparent))]
 pub struct CandidateHash(pub Hash);
 
+#[cfg(feature = "std")]
+impl CandidateHash {
+	/// The first 8 hex characters of the hash, for use in logs.
+	pub fn short_id(&self) -> String {
+		self.0.as_bytes()[..4].iter().map(|b| format!("{:02x}", b)).collect()
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::str::FromStr for CandidateHash {
+	type Err = <Hash as std::str::FromStr>::Err;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.strip_prefix("0x").unwrap_or(s).parse().map(CandidateHash)
+	}
+}
+
 #[cfg(feature = "std")]
 impl std::ops::Deref for CandidateHash {
 	type Target = Hash;
@@ -135,6 +158,101 @@ pub struct InboundDownwardMessage<BlockNumber = crate::BlockNumber> {
 	pub msg: DownwardMessage,
 }
 
+impl<BlockNumber> InboundDownwardMessage<BlockNumber> {
+	/// Attach the kind of `origin` which sent this message.
+	pub fn with_origin(
+		self,
+		origin: DownwardMessageOrigin,
+	) -> VersionedInboundDownwardMessage<BlockNumber> {
+		VersionedInboundDownwardMessage::V2 { sent_at: self.sent_at, origin, msg: self.msg }
+	}
+
+	/// The hash of the payload of this message.
+	pub fn content_hash(&self) -> Hash {
+		BlakeTwo256::hash(&self.msg)
+	}
+}
+
+/// The kind of relay-chain origin which sent a downward message.
+#[derive(Encode, Decode, Clone, Copy, sp_runtime::RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(MallocSizeOf))]
+pub enum DownwardMessageOrigin {
+	/// The message was sent by runtime logic, such as a pallet.
+	#[codec(index = 0)]
+	Runtime,
+	/// The message was sent by relay-chain governance.
+	#[codec(index = 1)]
+	Governance,
+}
+
+/// An [`InboundDownwardMessage`] in one of the versions the relay chain stores it in.
+///
+/// The origin is kept next to the payload, so it cannot be forged by the sender of the payload.
+#[derive(Encode, Decode, Clone, sp_runtime::RuntimeDebug, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(MallocSizeOf))]
+pub enum VersionedInboundDownwardMessage<BlockNumber = crate::BlockNumber> {
+	/// A message which was queued without an origin.
+	#[codec(index = 0)]
+	V1(InboundDownwardMessage<BlockNumber>),
+	/// A message along with the kind of origin which sent it.
+	#[codec(index = 1)]
+	V2 {
+		/// The block number at which the message was put into the downward message queue.
+		sent_at: BlockNumber,
+		/// The kind of origin which sent the message.
+		origin: DownwardMessageOrigin,
+		/// The actual downward message.
+		msg: DownwardMessage,
+	},
+}
+
+impl<BlockNumber> VersionedInboundDownwardMessage<BlockNumber> {
+	/// The block number at which the message was put into the downward message queue.
+	pub fn sent_at(&self) -> &BlockNumber {
+		match self {
+			Self::V1(message) => &message.sent_at,
+			Self::V2 { sent_at, .. } => sent_at,
+		}
+	}
+
+	/// The kind of origin which sent the message, or `None` if it was queued without one.
+	pub fn origin(&self) -> Option<DownwardMessageOrigin> {
+		match self {
+			Self::V1(_) => None,
+			Self::V2 { origin, .. } => Some(*origin),
+		}
+	}
+
+	/// The payload of the message.
+	pub fn msg(&self) -> &DownwardMessage {
+		match self {
+			Self::V1(message) => &message.msg,
+			Self::V2 { msg, .. } => msg,
+		}
+	}
+
+	/// The hash of the payload of the message, regardless of its version.
+	pub fn content_hash(&self) -> Hash {
+		BlakeTwo256::hash(self.msg())
+	}
+
+	/// Drop the origin of the message, if any.
+	pub fn into_v1(self) -> InboundDownwardMessage<BlockNumber> {
+		match self {
+			Self::V1(message) => message,
+			Self::V2 { sent_at, msg, .. } => InboundDownwardMessage { sent_at, msg },
+		}
+	}
+}
+
+impl<BlockNumber> From<InboundDownwardMessage<BlockNumber>>
+	for VersionedInboundDownwardMessage<BlockNumber>
+{
+	fn from(message: InboundDownwardMessage<BlockNumber>) -> Self {
+		Self::V1(message)
+	}
+}
+
 /// An HRMP message seen from the perspective of a recipient.
 #[derive(Encode, Decode, Clone, sp_runtime::RuntimeDebug, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(MallocSizeOf))]
@@ -157,7 +275,157 @@ pub struct OutboundHrmpMessage<Id> {
 	pub data: sp_std::vec::Vec<u8>,
 }
 
+impl<Id> OutboundHrmpMessage<Id> {
+	/// Create a new outbound HRMP message, checking that its payload does not exceed
+	/// `max_message_size` bytes.
+	pub fn new(
+		recipient: Id,
+		data: sp_std::vec::Vec<u8>,
+		max_message_size: u32,
+	) -> Result<Self, OutboundHrmpMessagesError> {
+		let msg = Self { recipient, data };
+		msg.check_size(0, max_message_size)?;
+		Ok(msg)
+	}
+
+	fn check_size(&self, idx: u32, max_size: u32) -> Result<(), OutboundHrmpMessagesError> {
+		let msg_size = self.data.len().try_into().unwrap_or(u32::MAX);
+		if msg_size > max_size {
+			return Err(OutboundHrmpMessagesError::MessageTooLarge { idx, msg_size, max_size })
+		}
+		Ok(())
+	}
+}
+
+/// An error in the set of HRMP messages sent by a single candidate.
+#[derive(Clone, Copy, sp_runtime::RuntimeDebug, PartialEq, Eq)]
+pub enum OutboundHrmpMessagesError {
+	/// More messages were sent than permitted.
+	TooManyMessages { sent: u32, permitted: u32 },
+	/// The message at `idx` has a lower recipient than the message preceding it.
+	NotSorted { idx: u32 },
+	/// The message at `idx` has the same recipient as the message preceding it.
+	DuplicateRecipient { idx: u32 },
+	/// The payload of the message at `idx` exceeds the maximum message size.
+	MessageTooLarge { idx: u32, msg_size: u32, max_size: u32 },
+}
+
+/// Check the HRMP messages sent by a single candidate.
+///
+/// There may be at most `max_message_num` messages, each no larger than `max_message_size`
+/// bytes, sorted by ascending recipient with at most one message per recipient.
+pub fn check_outbound_hrmp_messages<Id: Ord>(
+	msgs: &[OutboundHrmpMessage<Id>],
+	max_message_num: u32,
+	max_message_size: u32,
+) -> Result<(), OutboundHrmpMessagesError> {
+	let sent = msgs.len().try_into().unwrap_or(u32::MAX);
+	if sent > max_message_num {
+		return Err(OutboundHrmpMessagesError::TooManyMessages { sent, permitted: max_message_num })
+	}
+
+	let mut last_recipient = None;
+	for (idx, msg) in msgs.iter().enumerate().map(|(idx, msg)| (idx as u32, msg)) {
+		match last_recipient {
+			Some(last) if msg.recipient == *last =>
+				return Err(OutboundHrmpMessagesError::DuplicateRecipient { idx }),
+			Some(last) if msg.recipient < *last =>
+				return Err(OutboundHrmpMessagesError::NotSorted { idx }),
+			_ => last_recipient = Some(&msg.recipient),
+		}
+		msg.check_size(idx, max_message_size)?;
+	}
+
+	Ok(())
+}
+
+/// Prepare HRMP messages for inclusion in a single candidate.
+///
+/// The messages are sorted by recipient, keeping their relative order otherwise. Only the first
+/// message to each recipient is accepted; the rest are returned separately, in order, so that
+/// they may be sent by a later candidate.
+pub fn sort_outbound_hrmp_messages<Id: Ord>(
+	mut msgs: sp_std::vec::Vec<OutboundHrmpMessage<Id>>,
+) -> (sp_std::vec::Vec<OutboundHrmpMessage<Id>>, sp_std::vec::Vec<OutboundHrmpMessage<Id>>) {
+	// Remember the original positions so that deferred messages keep their order.
+	let mut indexed: sp_std::vec::Vec<_> = msgs.drain(..).enumerate().collect();
+	indexed.sort_by(|(a_idx, a), (b_idx, b)| a.recipient.cmp(&b.recipient).then(a_idx.cmp(b_idx)));
+
+	let mut accepted: sp_std::vec::Vec<OutboundHrmpMessage<Id>> =
+		sp_std::vec::Vec::with_capacity(indexed.len());
+	let mut deferred = sp_std::vec::Vec::new();
+	for (idx, msg) in indexed {
+		match accepted.last() {
+			Some(last) if last.recipient == msg.recipient => deferred.push((idx, msg)),
+			_ => accepted.push(msg),
+		}
+	}
+	deferred.sort_by_key(|(idx, _)| *idx);
+
+	(accepted, deferred.into_iter().map(|(_, msg)| msg).collect())
+}
+
 /// `V2` primitives.
 pub mod v2 {
 	pub use super::*;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn msg(recipient: u32, len: usize) -> OutboundHrmpMessage<u32> {
+		OutboundHrmpMessage { recipient, data: vec![0; len] }
+	}
+
+	#[test]
+	fn outbound_hrmp_messages_are_checked() {
+		assert_eq!(check_outbound_hrmp_messages(&[msg(1, 4), msg(2, 4)], 2, 4), Ok(()));
+		assert_eq!(
+			check_outbound_hrmp_messages(&[msg(1, 4), msg(2, 4)], 1, 4),
+			Err(OutboundHrmpMessagesError::TooManyMessages { sent: 2, permitted: 1 }),
+		);
+		assert_eq!(
+			check_outbound_hrmp_messages(&[msg(2, 4), msg(1, 4)], 2, 4),
+			Err(OutboundHrmpMessagesError::NotSorted { idx: 1 }),
+		);
+		assert_eq!(
+			check_outbound_hrmp_messages(&[msg(1, 4), msg(1, 4)], 2, 4),
+			Err(OutboundHrmpMessagesError::DuplicateRecipient { idx: 1 }),
+		);
+		assert_eq!(
+			check_outbound_hrmp_messages(&[msg(1, 4), msg(2, 5)], 2, 4),
+			Err(OutboundHrmpMessagesError::MessageTooLarge { idx: 1, msg_size: 5, max_size: 4 }),
+		);
+		assert!(OutboundHrmpMessage::new(1, vec![0; 5], 4).is_err());
+	}
+
+	#[test]
+	fn sorting_outbound_hrmp_messages_defers_duplicates() {
+		let (accepted, deferred) = sort_outbound_hrmp_messages(vec![
+			msg(3, 1),
+			msg(1, 1),
+			msg(3, 2),
+			msg(1, 3),
+			msg(2, 1),
+		]);
+		assert_eq!(accepted, vec![msg(1, 1), msg(2, 1), msg(3, 1)]);
+		assert_eq!(deferred, vec![msg(3, 2), msg(1, 3)]);
+		assert_eq!(check_outbound_hrmp_messages(&accepted, 3, 1), Ok(()));
+	}
+
+	#[test]
+	fn candidate_hash_round_trips_through_hex() {
+		let hash = CandidateHash(Hash::repeat_byte(0xab));
+		let hex = format!("0x{}", "ab".repeat(32));
+
+		assert_eq!(hash.short_id(), "abababab");
+		assert_eq!(hex.parse::<CandidateHash>().unwrap(), hash);
+		assert_eq!(hex[2..].parse::<CandidateHash>().unwrap(), hash);
+		assert!("0xabab".parse::<CandidateHash>().is_err());
+
+		let json = serde_json::to_string(&hash).unwrap();
+		assert_eq!(json, format!("\"{}\"", hex));
+		assert_eq!(serde_json::from_str::<CandidateHash>(&json).unwrap(), hash);
+	}
+}
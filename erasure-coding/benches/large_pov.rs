@@ -0,0 +1,230 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks erasure coding of large PoVs.
+//!
+//! Before the timings, the peak heap usage of every operation is printed, which shows how much
+//! streaming the payload into the encoder and decoding straight from the systematic chunks save
+//! over materializing the payload.
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+use parity_scale_codec::{Compact, Encode};
+use polkadot_erasure_coding::*;
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	io::Read,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+const N_VALIDATORS: usize = 1000;
+const POV_SIZES: [usize; 3] = [1 << 20, 5 << 20, 10 << 20];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// keeps track of the peak amount of allocated heap memory
+struct PeakAlloc;
+
+impl PeakAlloc {
+	fn grow(by: usize) {
+		let current = CURRENT.fetch_add(by, Ordering::Relaxed) + by;
+		PEAK.fetch_max(current, Ordering::Relaxed);
+	}
+
+	fn shrink(by: usize) {
+		CURRENT.fetch_sub(by, Ordering::Relaxed);
+	}
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc(layout);
+		if !ptr.is_null() {
+			Self::grow(layout.size());
+		}
+		ptr
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		Self::shrink(layout.size());
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		let new_ptr = System.realloc(ptr, layout, new_size);
+		if !new_ptr.is_null() {
+			if new_size > layout.size() {
+				Self::grow(new_size - layout.size());
+			} else {
+				Self::shrink(layout.size() - new_size);
+			}
+		}
+		new_ptr
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+// the peak heap usage of `f` on top of what was allocated beforehand, including its result
+fn peak_heap_usage<R>(f: impl FnOnce() -> R) -> usize {
+	let base = CURRENT.load(Ordering::Relaxed);
+	PEAK.store(base, Ordering::Relaxed);
+	let result = f();
+	let peak = PEAK.load(Ordering::Relaxed) - base;
+	drop(result);
+	peak
+}
+
+fn pov(size: usize) -> Vec<u8> {
+	(0..size).map(|i| i as u8).collect()
+}
+
+fn encoded_len(size: usize) -> usize {
+	Compact(size as u32).encoded_size() + size
+}
+
+// yields the same bytes as `pov(size).encode()`, without holding them in memory
+fn pov_reader(size: usize) -> impl Read {
+	let prefix = Compact(size as u32).encode();
+	std::io::Cursor::new(prefix).chain(PovReader { pos: 0, size })
+}
+
+struct PovReader {
+	pos: usize,
+	size: usize,
+}
+
+impl Read for PovReader {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = std::cmp::min(buf.len(), self.size - self.pos);
+		for (i, byte) in buf[..len].iter_mut().enumerate() {
+			*byte = (self.pos + i) as u8;
+		}
+		self.pos += len;
+		Ok(len)
+	}
+}
+
+// the last `recovery_threshold` chunks, which are of no use to the systematic fast path
+fn regular_chunks(chunks: &[Vec<u8>]) -> impl Iterator<Item = (&[u8], usize)> {
+	let threshold = recovery_threshold(chunks.len()).unwrap();
+	chunks
+		.iter()
+		.enumerate()
+		.skip(chunks.len() - threshold)
+		.map(|(i, c)| (&c[..], i))
+}
+
+fn systematic_chunks(chunks: &[Vec<u8>]) -> impl Iterator<Item = (&[u8], usize)> {
+	let k = systematic_recovery_threshold(chunks.len()).unwrap();
+	chunks.iter().enumerate().take(k).map(|(i, c)| (&c[..], i))
+}
+
+fn report_peak_heap_usage() {
+	println!("Peak heap usage with {} validators:", N_VALIDATORS);
+	for size in POV_SIZES {
+		let pov = pov(size);
+		let chunks = obtain_chunks(N_VALIDATORS, &pov).unwrap();
+		let mib = |bytes: usize| bytes as f64 / (1 << 20) as f64;
+
+		println!("  PoV of {:.0} MiB", mib(size));
+		println!(
+			"    materialize, then obtain_chunks: {:>8.2} MiB",
+			mib(peak_heap_usage(|| {
+				let pov = self::pov(size);
+				obtain_chunks(N_VALIDATORS, &pov).unwrap()
+			})),
+		);
+		println!(
+			"    obtain_chunks_from_reader:       {:>8.2} MiB",
+			mib(peak_heap_usage(|| {
+				obtain_chunks_from_reader(N_VALIDATORS, encoded_len(size), pov_reader(size))
+					.unwrap()
+			})),
+		);
+		println!(
+			"    reconstruct:                     {:>8.2} MiB",
+			mib(peak_heap_usage(|| {
+				reconstruct::<_, Vec<u8>>(N_VALIDATORS, regular_chunks(&chunks)).unwrap()
+			})),
+		);
+		println!(
+			"    reconstruct_from_systematic:     {:>8.2} MiB",
+			mib(peak_heap_usage(|| {
+				reconstruct_from_systematic::<_, Vec<u8>>(N_VALIDATORS, systematic_chunks(&chunks))
+					.unwrap()
+			})),
+		);
+	}
+}
+
+fn encoding(c: &mut Criterion) {
+	let mut group = c.benchmark_group("encode");
+	group.sample_size(10);
+	for size in POV_SIZES {
+		group.throughput(Throughput::Bytes(size as u64));
+		let pov = pov(size);
+		group.bench_with_input(BenchmarkId::new("obtain_chunks", size), &pov, |b, pov| {
+			b.iter(|| obtain_chunks(N_VALIDATORS, pov).unwrap())
+		});
+		group.bench_with_input(
+			BenchmarkId::new("obtain_chunks_from_reader", size),
+			&size,
+			|b, &size| {
+				b.iter(|| {
+					obtain_chunks_from_reader(N_VALIDATORS, encoded_len(size), pov_reader(size))
+						.unwrap()
+				})
+			},
+		);
+	}
+	group.finish();
+}
+
+fn reconstruction(c: &mut Criterion) {
+	let mut group = c.benchmark_group("reconstruct");
+	group.sample_size(10);
+	for size in POV_SIZES {
+		group.throughput(Throughput::Bytes(size as u64));
+		let chunks = obtain_chunks(N_VALIDATORS, &pov(size)).unwrap();
+		group.bench_with_input(BenchmarkId::new("reconstruct", size), &chunks, |b, chunks| {
+			b.iter(|| reconstruct::<_, Vec<u8>>(N_VALIDATORS, regular_chunks(chunks)).unwrap())
+		});
+		group.bench_with_input(
+			BenchmarkId::new("reconstruct_from_systematic", size),
+			&chunks,
+			|b, chunks| {
+				b.iter(|| {
+					reconstruct_from_systematic::<_, Vec<u8>>(
+						N_VALIDATORS,
+						systematic_chunks(chunks),
+					)
+					.unwrap()
+				})
+			},
+		);
+	}
+	group.finish();
+}
+
+criterion_group!(benches, encoding, reconstruction);
+
+fn main() {
+	report_peak_heap_usage();
+	benches();
+	Criterion::default().configure_from_args().final_summary();
+}
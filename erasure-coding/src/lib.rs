@@ -23,6 +23,9 @@
 //! Each of n validators stores their piece of data. We assume `n = 3f + k`, `0 < k ≤ 3`.
 //! f is the maximum number of faulty validators in the system.
 //! The data is coded so any f+1 chunks can be used to reconstruct the full data.
+//!
+//! The code is systematic: the first [`systematic_recovery_threshold`] chunks hold the payload
+//! verbatim, so whoever holds all of them can recover the data without any decoding.
 
 use std::convert::TryFrom;
 
@@ -83,6 +86,9 @@ pub enum Error {
 	/// Unknown error
 	#[error("An unknown error has appeared when deriving code parameters from validator count")]
 	UnknownCodeParam,
+	/// Reading the payload to encode failed.
+	#[error("Failed to read the payload: {0:?}")]
+	ReadPayload(std::io::ErrorKind),
 }
 
 /// Obtain a threshold of chunks that should be enough to recover the data.
@@ -115,6 +121,23 @@ fn code_params(n_validators: usize) -> Result<CodeParams, Error> {
 	})
 }
 
+/// Obtain the number of chunks, starting from index 0, which hold the payload verbatim.
+///
+/// This never exceeds the [`recovery_threshold`].
+pub fn systematic_recovery_threshold(n_validators: usize) -> Result<usize, Error> {
+	code_dimensions(n_validators).map(|(_, k)| k)
+}
+
+// the number of symbols of a single encoding run and how many of them carry payload
+fn code_dimensions(n_validators: usize) -> Result<(usize, usize), Error> {
+	let _ = code_params(n_validators)?;
+
+	Ok((
+		novelpoly::next_higher_power_of_2(n_validators),
+		novelpoly::next_lower_power_of_2(recovery_threshold(n_validators)?),
+	))
+}
+
 /// Obtain erasure-coded chunks for v1 `AvailableData`, one for each validator.
 ///
 /// Works only up to 65536 validators, and `n_validators` must be non-zero.
@@ -126,19 +149,121 @@ pub fn obtain_chunks_v1(n_validators: usize, data: &AvailableData) -> Result<Vec
 ///
 /// Works only up to 65536 validators, and `n_validators` must be non-zero.
 pub fn obtain_chunks<T: Encode>(n_validators: usize, data: &T) -> Result<Vec<Vec<u8>>, Error> {
-	let params = code_params(n_validators)?;
-	let encoded = data.encode();
+	let mut encoder = ChunkEncoder::new(n_validators)?;
+	encoder.reserve(data.size_hint());
+	data.encode_to(&mut encoder);
+
+	encoder.finish()
+}
+
+/// Obtain erasure-coded chunks of everything `reader` yields, one for each validator.
+///
+/// The payload is encoded as it is read and is never held in memory as a whole. In order to
+/// reconstruct a `T` from the chunks, `reader` must yield the SCALE encoding of a `T`.
+/// `size_hint` is the expected length of the payload, used to allocate the chunks up front. It may
+/// be zero if unknown, but falling short of the actual length causes the chunks to be reallocated.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn obtain_chunks_from_reader<R: std::io::Read>(
+	n_validators: usize,
+	size_hint: usize,
+	mut reader: R,
+) -> Result<Vec<Vec<u8>>, Error> {
+	let mut encoder = ChunkEncoder::new(n_validators)?;
+	encoder.reserve(size_hint);
+	std::io::copy(&mut reader, &mut encoder).map_err(|e| Error::ReadPayload(e.kind()))?;
+
+	encoder.finish()
+}
+
+/// Erasure-codes a payload which is fed to it piece by piece.
+///
+/// Every `2 * systematic_recovery_threshold` bytes of payload form one encoding run, which is
+/// appended to the chunks as soon as it is complete. Feeding the payload through
+/// [`std::io::Write`], and hence [`parity_scale_codec::Output`], thus yields the same chunks as
+/// [`obtain_chunks`], without the need to hold the payload in memory.
+pub struct ChunkEncoder {
+	n: usize,
+	k: usize,
+	chunks: Vec<Vec<u8>>,
+	// the payload of the current encoding run
+	run: Vec<u8>,
+	payload_len: usize,
+}
+
+impl ChunkEncoder {
+	/// Create an encoder producing one chunk for each validator.
+	///
+	/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+	pub fn new(n_validators: usize) -> Result<Self, Error> {
+		let (n, k) = code_dimensions(n_validators)?;
+
+		Ok(ChunkEncoder {
+			n,
+			k,
+			chunks: vec![Vec::new(); n_validators],
+			run: Vec::with_capacity(2 * k),
+			payload_len: 0,
+		})
+	}
+
+	/// Reserve room in the chunks for `additional` more bytes of payload.
+	pub fn reserve(&mut self, additional: usize) {
+		let runs = (self.run.len() + additional + 2 * self.k - 1) / (2 * self.k);
+		for chunk in &mut self.chunks {
+			chunk.reserve(2 * runs);
+		}
+	}
+
+	/// Feed the next piece of the payload.
+	pub fn push(&mut self, mut bytes: &[u8]) {
+		self.payload_len += bytes.len();
 
-	if encoded.is_empty() {
-		return Err(Error::BadPayload)
+		while !bytes.is_empty() {
+			let take = std::cmp::min(2 * self.k - self.run.len(), bytes.len());
+			self.run.extend_from_slice(&bytes[..take]);
+			bytes = &bytes[take..];
+
+			if self.run.len() == 2 * self.k {
+				self.encode_run();
+			}
+		}
 	}
 
-	let shards = params
-		.make_encoder()
-		.encode::<WrappedShard>(&encoded[..])
-		.expect("Payload non-empty, shard sizes are uniform, and validator numbers checked; qed");
+	/// Encode what remains of the payload and yield the chunks, one for each validator.
+	pub fn finish(mut self) -> Result<Vec<Vec<u8>>, Error> {
+		if self.payload_len == 0 {
+			return Err(Error::BadPayload)
+		}
+
+		if !self.run.is_empty() {
+			self.encode_run();
+		}
+
+		Ok(self.chunks)
+	}
 
-	Ok(shards.into_iter().map(|w: WrappedShard| w.into_inner()).collect())
+	fn encode_run(&mut self) {
+		let symbols = novelpoly::f2e16::encode_sub(&self.run[..], self.n, self.k)
+			.expect("Run non-empty and no longer than 2k, code dimensions checked; qed");
+
+		for (chunk, symbol) in self.chunks.iter_mut().zip(symbols) {
+			chunk.extend_from_slice(&symbol.0.to_be_bytes());
+		}
+
+		self.run.clear();
+	}
+}
+
+impl std::io::Write for ChunkEncoder {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.push(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
 }
 
 /// Reconstruct the v1 available data from a set of chunks.
@@ -206,6 +331,68 @@ where
 	Decode::decode(&mut &payload_bytes[..]).or_else(|_e| Err(Error::BadPayload))
 }
 
+/// Reconstruct the v1 available data from its systematic chunks.
+///
+/// See [`reconstruct_from_systematic`].
+pub fn reconstruct_from_systematic_v1<'a, I: 'a>(
+	n_validators: usize,
+	chunks: I,
+) -> Result<AvailableData, Error>
+where
+	I: IntoIterator<Item = (&'a [u8], usize)>,
+{
+	reconstruct_from_systematic(n_validators, chunks)
+}
+
+/// Reconstruct decodable data from its systematic chunks.
+///
+/// Provide an iterator containing chunk data and the corresponding index. All chunks with an
+/// index below the [`systematic_recovery_threshold`] must be present, any other chunks are
+/// ignored. As these chunks hold the payload verbatim, it is decoded straight from them, without
+/// erasure decoding and without copying the payload.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn reconstruct_from_systematic<'a, I: 'a, T: Decode>(
+	n_validators: usize,
+	chunks: I,
+) -> Result<T, Error>
+where
+	I: IntoIterator<Item = (&'a [u8], usize)>,
+{
+	let k = systematic_recovery_threshold(n_validators)?;
+	let mut systematic_chunks: Vec<Option<&[u8]>> = vec![None; k];
+	let mut shard_len = None;
+	for (chunk_data, chunk_idx) in chunks.into_iter().take(n_validators) {
+		if chunk_idx >= n_validators {
+			return Err(Error::ChunkIndexOutOfBounds { chunk_index: chunk_idx, n_validators })
+		}
+
+		if chunk_idx >= k {
+			continue
+		}
+
+		let shard_len = shard_len.get_or_insert_with(|| chunk_data.len());
+
+		if *shard_len % 2 != 0 {
+			return Err(Error::UnevenLength)
+		}
+
+		if *shard_len != chunk_data.len() || *shard_len == 0 {
+			return Err(Error::NonUniformChunks)
+		}
+
+		systematic_chunks[chunk_idx] = Some(chunk_data);
+	}
+
+	let chunks = systematic_chunks
+		.into_iter()
+		.collect::<Option<Vec<_>>>()
+		.ok_or(Error::NotEnoughChunks)?;
+
+	let mut input = SystematicInput { remaining_len: k * chunks[0].len(), chunks, pos: 0 };
+	Decode::decode(&mut input).map_err(|_| Error::BadPayload)
+}
+
 /// An iterator that yields merkle branches and chunk data for all chunks to
 /// be sent to other validators.
 pub struct Branches<'a, I> {
@@ -343,6 +530,44 @@ impl<'a, I: Iterator<Item = &'a [u8]>> parity_scale_codec::Input for ShardInput<
 	}
 }
 
+// input for `codec` which draws data from the systematic chunks, in which the payload is laid out
+// as consecutive 2-byte symbols going round the chunks
+struct SystematicInput<'a> {
+	remaining_len: usize,
+	chunks: Vec<&'a [u8]>,
+	// offset of the next byte to read in the payload
+	pos: usize,
+}
+
+impl<'a> parity_scale_codec::Input for SystematicInput<'a> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, parity_scale_codec::Error> {
+		Ok(Some(self.remaining_len))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), parity_scale_codec::Error> {
+		if into.len() > self.remaining_len {
+			return Err("slice provided too big for input".into())
+		}
+
+		let k = self.chunks.len();
+		let mut read_bytes = 0;
+		while read_bytes < into.len() {
+			let (symbol, in_symbol) = (self.pos / 2, self.pos % 2);
+			let chunk = self.chunks[symbol % k];
+			let in_chunk = (symbol / k) * 2 + in_symbol;
+
+			let write_len = std::cmp::min(2 - in_symbol, into.len() - read_bytes);
+			into[read_bytes..][..write_len].copy_from_slice(&chunk[in_chunk..][..write_len]);
+
+			self.pos += write_len;
+			read_bytes += write_len;
+		}
+
+		self.remaining_len -= read_bytes;
+		Ok(())
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -378,6 +603,94 @@ mod tests {
 		assert_eq!(reconstructed, available_data);
 	}
 
+	#[test]
+	fn streamed_chunks_match_encoding_whole_payload() {
+		for n_validators in [2, 4, 10, 100, 301] {
+			for len in [1, 2, 3, 255, 1024, 4099] {
+				let payload: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+				let expected: Vec<Vec<u8>> = code_params(n_validators)
+					.unwrap()
+					.make_encoder()
+					.encode::<WrappedShard>(&payload[..])
+					.unwrap()
+					.into_iter()
+					.map(|w| w.into_inner())
+					.collect();
+
+				let mut encoder = ChunkEncoder::new(n_validators).unwrap();
+				for piece in payload.chunks(13) {
+					encoder.push(piece);
+				}
+				assert_eq!(encoder.finish().unwrap(), expected);
+
+				let from_reader =
+					obtain_chunks_from_reader(n_validators, len, &payload[..]).unwrap();
+				assert_eq!(from_reader, expected);
+			}
+		}
+	}
+
+	#[test]
+	fn encoding_empty_payload_fails() {
+		assert_eq!(obtain_chunks_from_reader(10, 0, std::io::empty()), Err(Error::BadPayload));
+	}
+
+	#[test]
+	fn systematic_recovery_threshold_is_power_of_two_below_recovery_threshold() {
+		assert_eq!(systematic_recovery_threshold(2), Ok(1));
+		assert_eq!(systematic_recovery_threshold(10), Ok(4));
+		assert_eq!(systematic_recovery_threshold(100), Ok(32));
+		assert_eq!(systematic_recovery_threshold(1000), Ok(256));
+		assert_eq!(systematic_recovery_threshold(1), Err(Error::NotEnoughValidators));
+	}
+
+	#[test]
+	fn systematic_round_trip_works() {
+		let pov = PoV { block_data: BlockData((0..255).collect()) };
+		let available_data = AvailableData { pov: pov.into(), validation_data: Default::default() };
+
+		for n_validators in [2, 3, 10, 100, 1000] {
+			let chunks = obtain_chunks(n_validators, &available_data).unwrap();
+			let k = systematic_recovery_threshold(n_validators).unwrap();
+
+			// non-systematic chunks are of no use, but do no harm either.
+			let reconstructed = reconstruct_from_systematic_v1(
+				n_validators,
+				chunks
+					.iter()
+					.enumerate()
+					.rev()
+					.take(k + 1)
+					.map(|(i, c)| (&c[..], i))
+					.chain(chunks.iter().enumerate().take(k).map(|(i, c)| (&c[..], i))),
+			)
+			.unwrap();
+
+			assert_eq!(reconstructed, available_data);
+		}
+	}
+
+	#[test]
+	fn systematic_reconstruction_needs_all_systematic_chunks() {
+		let chunks = obtain_chunks(10, &vec![5u8; 100]).unwrap();
+
+		let reconstructed: Result<Vec<u8>, _> = reconstruct_from_systematic(
+			10,
+			[(&*chunks[0], 0), (&*chunks[1], 1), (&*chunks[3], 3), (&*chunks[4], 4)]
+				.iter()
+				.cloned(),
+		);
+		assert_eq!(reconstructed, Err(Error::NotEnoughChunks));
+
+		let reconstructed: Result<Vec<u8>, _> = reconstruct_from_systematic(
+			10,
+			[(&*chunks[0], 0), (&chunks[1][..2], 1), (&*chunks[2], 2), (&*chunks[3], 3)]
+				.iter()
+				.cloned(),
+		);
+		assert_eq!(reconstructed, Err(Error::NonUniformChunks));
+	}
+
 	#[test]
 	fn reconstruct_does_not_panic_on_low_validator_count() {
 		let reconstructed = reconstruct_v1(1, [].iter().cloned());
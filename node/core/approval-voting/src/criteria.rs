@@ -466,7 +466,11 @@ pub(crate) fn check_assignment_cert(
 	let public = schnorrkel::PublicKey::from_bytes(validator_public.as_slice())
 		.map_err(|_| InvalidAssignment)?;
 
-	if claimed_core_index.0 >= config.n_cores {
+	if !assignment.kind.is_within_bounds(
+		claimed_core_index,
+		config.n_cores,
+		config.relay_vrf_modulo_samples,
+	) {
 		return Err(InvalidAssignment)
 	}
 
@@ -482,10 +486,6 @@ pub(crate) fn check_assignment_cert(
 	let &(ref vrf_output, ref vrf_proof) = &assignment.vrf;
 	match assignment.kind {
 		AssignmentCertKind::RelayVRFModulo { sample } => {
-			if sample >= config.relay_vrf_modulo_samples {
-				return Err(InvalidAssignment)
-			}
-
 			let (vrf_in_out, _) = public
 				.vrf_verify_extra(
 					relay_vrf_modulo_transcript(relay_vrf_story, sample),
@@ -503,10 +503,6 @@ pub(crate) fn check_assignment_cert(
 			}
 		},
 		AssignmentCertKind::RelayVRFDelay { core_index } => {
-			if core_index != claimed_core_index {
-				return Err(InvalidAssignment)
-			}
-
 			let (vrf_in_out, _) = public
 				.vrf_verify(
 					relay_vrf_delay_transcript(relay_vrf_story, core_index),
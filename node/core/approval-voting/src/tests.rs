@@ -2403,8 +2403,8 @@ pub async fn handle_double_assignment_import(
 				assert_eq!(candidate_index, c_index);
 			},
 			AllMessages::CandidateValidation(
-				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx),
-			) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx),
+			) if exec_kind == PvfExecKind::Approval => {
 				tx.send(Ok(ValidationResult::Valid(Default::default(), Default::default())))
 					.unwrap();
 			},
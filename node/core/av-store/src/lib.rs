@@ -73,6 +73,9 @@ const KEEP_FINALIZED_FOR: Duration = Duration::from_secs(25 * 60 * 60);
 /// The pruning interval.
 const PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 5);
 
+/// The maximum number of candidates pruned within a single database transaction.
+const PRUNING_BATCH_SIZE: usize = 500;
+
 /// Unix time wrapper with big-endian encoding.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
 struct BETimestamp(u64);
@@ -149,6 +152,39 @@ struct CandidateMeta {
 	state: State,
 	data_available: bool,
 	chunks_stored: BitVec<u8, BitOrderLsb0>,
+	// The encoded size of the available data, if stored, in bytes.
+	data_size: u64,
+	// The total encoded size of all stored chunks, in bytes.
+	chunks_size: u64,
+}
+
+// Meta information about a candidate, as written before the sizes of its data were tracked.
+//
+// Such candidates are pruned within a day, so their sizes are just taken to be zero.
+#[derive(Decode)]
+struct LegacyCandidateMeta {
+	state: State,
+	data_available: bool,
+	chunks_stored: BitVec<u8, BitOrderLsb0>,
+}
+
+impl From<LegacyCandidateMeta> for CandidateMeta {
+	fn from(meta: LegacyCandidateMeta) -> Self {
+		CandidateMeta {
+			state: meta.state,
+			data_available: meta.data_available,
+			chunks_stored: meta.chunks_stored,
+			data_size: 0,
+			chunks_size: 0,
+		}
+	}
+}
+
+impl CandidateMeta {
+	fn decode_with_legacy(raw: &[u8]) -> Result<Self, CodecError> {
+		CandidateMeta::decode(&mut &raw[..])
+			.or_else(|_| LegacyCandidateMeta::decode(&mut &raw[..]).map(Into::into))
+	}
 }
 
 fn query_inner<D: Decode>(
@@ -169,15 +205,19 @@ fn query_inner<D: Decode>(
 	}
 }
 
+// Returns the number of bytes written.
 fn write_available_data(
 	tx: &mut DBTransaction,
 	config: &Config,
 	hash: &CandidateHash,
 	available_data: &AvailableData,
-) {
+) -> u64 {
 	let key = (AVAILABLE_PREFIX, hash).encode();
+	let value = available_data.encode();
+	let size = value.len() as u64;
 
-	tx.put_vec(config.col_data, &key[..], available_data.encode());
+	tx.put_vec(config.col_data, &key[..], value);
+	size
 }
 
 fn load_available_data(
@@ -207,16 +247,20 @@ fn load_chunk(
 	query_inner(db, config.col_data, &key)
 }
 
+// Returns the number of bytes written.
 fn write_chunk(
 	tx: &mut DBTransaction,
 	config: &Config,
 	candidate_hash: &CandidateHash,
 	chunk_index: ValidatorIndex,
 	erasure_chunk: &ErasureChunk,
-) {
+) -> u64 {
 	let key = (CHUNK_PREFIX, candidate_hash, chunk_index).encode();
+	let value = erasure_chunk.encode();
+	let size = value.len() as u64;
 
-	tx.put_vec(config.col_data, &key, erasure_chunk.encode());
+	tx.put_vec(config.col_data, &key, value);
+	size
 }
 
 fn delete_chunk(
@@ -230,6 +274,16 @@ fn delete_chunk(
 	tx.delete(config.col_data, &key[..]);
 }
 
+// The total size of all stored available data and of all stored chunks, in bytes, as recorded
+// in the meta information of the candidates.
+fn measure_stored_data(db: &Arc<dyn Database>, config: &Config) -> (u64, u64) {
+	db.iter_with_prefix(config.col_meta, &META_PREFIX[..])
+		.filter_map(|(_, v)| CandidateMeta::decode_with_legacy(&v[..]).ok())
+		.fold((0, 0), |(data_size, chunks_size), meta| {
+			(data_size + meta.data_size, chunks_size + meta.chunks_size)
+		})
+}
+
 fn load_meta(
 	db: &Arc<dyn Database>,
 	config: &Config,
@@ -237,7 +291,14 @@ fn load_meta(
 ) -> Result<Option<CandidateMeta>, Error> {
 	let key = (META_PREFIX, hash).encode();
 
-	query_inner(db, config.col_meta, &key)
+	match db.get(config.col_meta, &key) {
+		Ok(Some(raw)) => Ok(Some(CandidateMeta::decode_with_legacy(&raw[..])?)),
+		Ok(None) => Ok(None),
+		Err(err) => {
+			gum::warn!(target: LOG_TARGET, ?err, "Error reading from the availability store");
+			Err(err.into())
+		},
+	}
 }
 
 fn write_meta(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash, meta: &CandidateMeta) {
@@ -392,19 +453,25 @@ impl Error {
 	}
 }
 
-/// Struct holding pruning timing configuration.
-/// The only purpose of this structure is to use different timing
-/// configurations in production and in testing.
-#[derive(Clone)]
-struct PruningConfig {
-	/// How long unavailable data should be kept.
-	keep_unavailable_for: Duration,
+/// Configuration of how long the availability store keeps data around.
+///
+/// Nodes with small disks may shorten the windows. Validators must however keep finalized data
+/// for at least as long as disputes may be raised about it, so they should stick to the defaults.
+#[derive(Debug, Clone)]
+pub struct PruningConfig {
+	/// How long the data of candidates which are not included in any unfinalized block should be
+	/// kept.
+	pub keep_unavailable_for: Duration,
 
 	/// How long finalized data should be kept.
-	keep_finalized_for: Duration,
+	pub keep_finalized_for: Duration,
 
 	/// How often to perform data pruning.
-	pruning_interval: Duration,
+	pub pruning_interval: Duration,
+
+	/// The maximum number of candidates pruned within a single database transaction. If more are
+	/// due, pruning resumes right after any pending messages are processed.
+	pub batch_size: usize,
 }
 
 impl Default for PruningConfig {
@@ -413,6 +480,7 @@ impl Default for PruningConfig {
 			keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
 			keep_finalized_for: KEEP_FINALIZED_FOR,
 			pruning_interval: PRUNING_INTERVAL,
+			batch_size: PRUNING_BATCH_SIZE,
 		}
 	}
 }
@@ -453,10 +521,21 @@ pub struct AvailabilityStoreSubsystem {
 impl AvailabilityStoreSubsystem {
 	/// Create a new `AvailabilityStoreSubsystem` with a given config on disk.
 	pub fn new(db: Arc<dyn Database>, config: Config, metrics: Metrics) -> Self {
+		Self::with_pruning_config(db, config, PruningConfig::default(), metrics)
+	}
+
+	/// Create a new `AvailabilityStoreSubsystem` with a given config on disk, which keeps data
+	/// around as long as the given pruning config prescribes.
+	pub fn with_pruning_config(
+		db: Arc<dyn Database>,
+		config: Config,
+		pruning_config: PruningConfig,
+		metrics: Metrics,
+	) -> Self {
 		Self::with_pruning_config_and_clock(
 			db,
 			config,
-			PruningConfig::default(),
+			pruning_config,
 			Box::new(SystemClock),
 			metrics,
 		)
@@ -534,6 +613,11 @@ where
 {
 	let mut next_pruning = Delay::new(subsystem.pruning_config.pruning_interval).fuse();
 
+	if subsystem.metrics.is_enabled() {
+		let (available_data, chunks) = measure_stored_data(&subsystem.db, &subsystem.config);
+		subsystem.metrics.set_stored_bytes(available_data, chunks);
+	}
+
 	loop {
 		let res = run_iteration(&mut ctx, &mut subsystem, &mut next_pruning).await;
 		match res {
@@ -592,12 +676,15 @@ where
 			}
 		}
 		_ = next_pruning => {
-			// It's important to set the delay before calling `prune_all` because an error in `prune_all`
+			// It's important to set the delay before calling `prune_batch` because an error in `prune_batch`
 			// could lead to the delay not being set again. Then we would never prune anything anymore.
 			*next_pruning = Delay::new(subsystem.pruning_config.pruning_interval).fuse();
 
 			let _timer = subsystem.metrics.time_pruning();
-			prune_all(&subsystem.db, &subsystem.config, &*subsystem.clock)?;
+			if prune_batch(subsystem)? {
+				// Prune the rest in another iteration, so that messages don't pile up meanwhile.
+				*next_pruning = Delay::new(Duration::ZERO).fuse();
+			}
 		}
 	}
 
@@ -728,6 +815,8 @@ fn note_block_backed(
 			state: State::Unavailable(now.into()),
 			data_available: false,
 			chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators],
+			data_size: 0,
+			chunks_size: 0,
 		};
 
 		let prune_at = now + pruning_config.keep_unavailable_for;
@@ -1085,7 +1174,13 @@ fn process_message(
 			subsystem.metrics.on_chunks_received(1);
 			let _timer = subsystem.metrics.time_store_chunk();
 
-			match store_chunk(&subsystem.db, &subsystem.config, candidate_hash, chunk) {
+			match store_chunk(
+				&subsystem.db,
+				&subsystem.config,
+				&subsystem.metrics,
+				candidate_hash,
+				chunk,
+			) {
 				Ok(true) => {
 					let _ = tx.send(Ok(()));
 				},
@@ -1130,6 +1225,7 @@ fn process_message(
 fn store_chunk(
 	db: &Arc<dyn Database>,
 	config: &Config,
+	metrics: &Metrics,
 	candidate_hash: CandidateHash,
 	chunk: ErasureChunk,
 ) -> Result<bool, Error> {
@@ -1140,16 +1236,18 @@ fn store_chunk(
 		None => return Ok(false), // we weren't informed of this candidate by import events.
 	};
 
-	match meta.chunks_stored.get(chunk.index.0 as usize).map(|b| *b) {
+	let chunk_size = match meta.chunks_stored.get(chunk.index.0 as usize).map(|b| *b) {
 		Some(true) => return Ok(true), // already stored.
 		Some(false) => {
 			meta.chunks_stored.set(chunk.index.0 as usize, true);
 
-			write_chunk(&mut tx, config, &candidate_hash, chunk.index, &chunk);
+			let chunk_size = write_chunk(&mut tx, config, &candidate_hash, chunk.index, &chunk);
+			meta.chunks_size += chunk_size;
 			write_meta(&mut tx, config, &candidate_hash, &meta);
+			chunk_size
 		},
 		None => return Ok(false), // out of bounds.
-	}
+	};
 
 	gum::debug!(
 		target: LOG_TARGET,
@@ -1159,6 +1257,7 @@ fn store_chunk(
 	);
 
 	db.write(tx)?;
+	metrics.on_data_stored(0, chunk_size);
 	Ok(true)
 }
 
@@ -1190,6 +1289,8 @@ fn store_available_data(
 				state: State::Unavailable(now.into()),
 				data_available: false,
 				chunks_stored: BitVec::new(),
+				data_size: 0,
+				chunks_size: 0,
 			}
 		},
 	};
@@ -1205,33 +1306,54 @@ fn store_available_data(
 		},
 	);
 
+	let mut chunks_size = 0;
 	for chunk in erasure_chunks {
-		write_chunk(&mut tx, &subsystem.config, &candidate_hash, chunk.index, &chunk);
+		chunks_size +=
+			write_chunk(&mut tx, &subsystem.config, &candidate_hash, chunk.index, &chunk);
 	}
 
+	let available_data_size =
+		write_available_data(&mut tx, &subsystem.config, &candidate_hash, &available_data);
+
+	// Chunks we have stored before got overwritten, so they must not be counted twice.
+	let overwritten_size = meta.chunks_size;
 	meta.data_available = true;
 	meta.chunks_stored = bitvec::bitvec![u8, BitOrderLsb0; 1; n_validators];
+	meta.data_size = available_data_size;
+	meta.chunks_size = chunks_size;
 
 	write_meta(&mut tx, &subsystem.config, &candidate_hash, &meta);
-	write_available_data(&mut tx, &subsystem.config, &candidate_hash, &available_data);
 
 	subsystem.db.write(tx)?;
+	subsystem.metrics.on_data_removed(0, overwritten_size);
+	subsystem.metrics.on_data_stored(available_data_size, chunks_size);
 
 	gum::debug!(target: LOG_TARGET, ?candidate_hash, "Stored data and chunks");
 
 	Ok(())
 }
 
-fn prune_all(db: &Arc<dyn Database>, config: &Config, clock: &dyn Clock) -> Result<(), Error> {
-	let now = clock.now()?;
+// Prunes up to `PruningConfig::batch_size` candidates which are due, within a single transaction.
+// Returns whether any more are due.
+fn prune_batch(subsystem: &AvailabilityStoreSubsystem) -> Result<bool, Error> {
+	let (db, config, metrics) = (&subsystem.db, &subsystem.config, &subsystem.metrics);
+	let batch_size = subsystem.pruning_config.batch_size.max(1);
+	let now = subsystem.clock.now()?;
 	let (range_start, range_end) = pruning_range(now);
 
-	let mut tx = DBTransaction::new();
-	let iter = db
+	// Take one more key than we prune, to learn whether any more are due.
+	let mut keys: Vec<_> = db
 		.iter_with_prefix(config.col_meta, &range_start[..])
-		.take_while(|(k, _)| &k[..] < &range_end[..]);
+		.take_while(|(k, _)| &k[..] < &range_end[..])
+		.map(|(k, _)| k)
+		.take(batch_size + 1)
+		.collect();
+	let more_due = keys.len() > batch_size;
+	keys.truncate(batch_size);
 
-	for (k, _v) in iter {
+	let mut tx = DBTransaction::new();
+	let (mut available_data_size, mut chunks_size) = (0, 0);
+	for k in keys {
 		tx.delete(config.col_meta, &k[..]);
 
 		let (_, candidate_hash) = match decode_pruning_key(&k[..]) {
@@ -1255,6 +1377,9 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, clock: &dyn Clock) -> Resu
 				}
 			}
 
+			available_data_size += meta.data_size;
+			chunks_size += meta.chunks_size;
+
 			// delete unfinalized block references. Pruning references don't need to be
 			// manually taken care of as we are deleting them as we go in the outer loop.
 			if let State::Unfinalized(_, blocks) = meta.state {
@@ -1272,5 +1397,6 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, clock: &dyn Clock) -> Resu
 	}
 
 	db.write(tx)?;
-	Ok(())
+	metrics.on_data_removed(available_data_size, chunks_size);
+	Ok(more_due)
 }
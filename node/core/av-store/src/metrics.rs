@@ -26,6 +26,8 @@ pub(crate) struct MetricsInner {
 	store_available_data: prometheus::Histogram,
 	store_chunk: prometheus::Histogram,
 	get_chunk: prometheus::Histogram,
+	stored_available_data_bytes: prometheus::Gauge<prometheus::U64>,
+	stored_chunks_bytes: prometheus::Gauge<prometheus::U64>,
 }
 
 /// Availability metrics.
@@ -42,7 +44,38 @@ impl Metrics {
 		}
 	}
 
-	/// Provide a timer for `prune_povs` which observes on drop.
+	/// Whether metrics are collected at all, i.e. whether it is worth measuring things for them.
+	pub(crate) fn is_enabled(&self) -> bool {
+		self.0.is_some()
+	}
+
+	/// Set the total size of the stored available data and chunks, e.g. after measuring it on
+	/// startup.
+	pub(crate) fn set_stored_bytes(&self, available_data: u64, chunks: u64) {
+		if let Some(metrics) = &self.0 {
+			metrics.stored_available_data_bytes.set(available_data);
+			metrics.stored_chunks_bytes.set(chunks);
+		}
+	}
+
+	pub(crate) fn on_data_stored(&self, available_data: u64, chunks: u64) {
+		if let Some(metrics) = &self.0 {
+			metrics.stored_available_data_bytes.add(available_data);
+			metrics.stored_chunks_bytes.add(chunks);
+		}
+	}
+
+	pub(crate) fn on_data_removed(&self, available_data: u64, chunks: u64) {
+		if let Some(metrics) = &self.0 {
+			let sub = |gauge: &prometheus::Gauge<prometheus::U64>, by: u64| {
+				gauge.set(gauge.get().saturating_sub(by))
+			};
+			sub(&metrics.stored_available_data_bytes, available_data);
+			sub(&metrics.stored_chunks_bytes, chunks);
+		}
+	}
+
+	/// Provide a timer for `prune_batch` which observes on drop.
 	pub(crate) fn time_pruning(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.pruning.start_timer())
 	}
@@ -101,7 +134,7 @@ impl metrics::Metrics for Metrics {
 			pruning: prometheus::register(
 				prometheus::Histogram::with_opts(prometheus::HistogramOpts::new(
 					"polkadot_parachain_av_store_pruning",
-					"Time spent within `av_store::prune_all`",
+					"Time spent within `av_store::prune_batch`",
 				))?,
 				registry,
 			)?,
@@ -147,6 +180,20 @@ impl metrics::Metrics for Metrics {
 				))?,
 				registry,
 			)?,
+			stored_available_data_bytes: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_parachain_av_store_stored_available_data_bytes",
+					"Total size of the available data (PoVs and validation data) in the store.",
+				)?,
+				registry,
+			)?,
+			stored_chunks_bytes: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_parachain_av_store_stored_chunks_bytes",
+					"Total size of the erasure chunks in the store.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -97,6 +97,7 @@ impl Default for TestState {
 			keep_unavailable_for: Duration::from_secs(1),
 			keep_finalized_for: Duration::from_secs(2),
 			pruning_interval: Duration::from_millis(250),
+			batch_size: 10,
 		};
 
 		let clock = TestClock { inner: Arc::new(Mutex::new(Duration::from_secs(0))) };
@@ -302,6 +303,8 @@ fn store_chunk_works() {
 					data_available: false,
 					chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators],
 					state: State::Unavailable(BETimestamp(0)),
+					data_size: 0,
+					chunks_size: 0,
 				},
 			);
 		});
@@ -380,6 +383,8 @@ fn query_chunk_checks_meta() {
 						v
 					},
 					state: State::Unavailable(BETimestamp(0)),
+					data_size: 0,
+					chunks_size: 0,
 				},
 			);
 		});
@@ -541,6 +546,8 @@ fn query_all_chunks_works() {
 						data_available: false,
 						chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators as _],
 						state: State::Unavailable(BETimestamp(0)),
+						data_size: 0,
+						chunks_size: 0,
 					},
 				);
 			});
@@ -635,6 +642,187 @@ fn stored_but_not_included_data_is_pruned() {
 	});
 }
 
+#[test]
+fn pruning_is_done_in_batches() {
+	let store = test_store();
+	let mut test_state = TestState::default();
+	test_state.pruning_config.batch_size = 1;
+
+	let subsystem = AvailabilityStoreSubsystem::with_pruning_config_and_clock(
+		store.clone(),
+		TEST_CONFIG,
+		test_state.pruning_config.clone(),
+		Box::new(test_state.clock.clone()),
+		Metrics::default(),
+	);
+
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+
+	let candidates: Vec<_> = (1..=3).map(|i| CandidateHash(Hash::repeat_byte(i))).collect();
+	for candidate_hash in &candidates {
+		store_available_data(&subsystem, *candidate_hash, 10, available_data.clone()).unwrap();
+	}
+
+	let stored = || {
+		candidates
+			.iter()
+			.filter(|c| load_meta(&store, &TEST_CONFIG, c).unwrap().is_some())
+			.count()
+	};
+
+	// Nothing is due yet.
+	assert!(!prune_batch(&subsystem).unwrap());
+	assert_eq!(stored(), 3);
+
+	test_state.clock.inc(test_state.pruning_config.keep_unavailable_for);
+
+	assert!(prune_batch(&subsystem).unwrap());
+	assert_eq!(stored(), 2);
+	assert!(prune_batch(&subsystem).unwrap());
+	assert_eq!(stored(), 1);
+	assert!(!prune_batch(&subsystem).unwrap());
+	assert_eq!(stored(), 0);
+
+	for candidate_hash in &candidates {
+		assert!(load_available_data(&store, &TEST_CONFIG, candidate_hash).unwrap().is_none());
+	}
+}
+
+#[test]
+fn stored_data_size_is_tracked() {
+	use polkadot_node_subsystem_util::metrics::{prometheus, Metrics as _};
+
+	let store = test_store();
+	let test_state = TestState::default();
+	let registry = prometheus::Registry::new();
+
+	let subsystem = AvailabilityStoreSubsystem::with_pruning_config_and_clock(
+		store.clone(),
+		TEST_CONFIG,
+		test_state.pruning_config.clone(),
+		Box::new(test_state.clock.clone()),
+		Metrics::try_register(&registry).unwrap(),
+	);
+
+	let gauges = || {
+		let gauge = |name| {
+			registry
+				.gather()
+				.into_iter()
+				.find(|family| family.get_name() == name)
+				.map(|family| family.get_metric()[0].get_gauge().get_value() as u64)
+				.unwrap()
+		};
+		(
+			gauge("polkadot_parachain_av_store_stored_available_data_bytes"),
+			gauge("polkadot_parachain_av_store_stored_chunks_bytes"),
+		)
+	};
+	// What is actually stored, as opposed to what the meta information of the candidates says.
+	let on_disk = || {
+		let total_size = |prefix: &[u8]| {
+			store
+				.iter_with_prefix(TEST_CONFIG.col_data, prefix)
+				.map(|(_, v)| v.len() as u64)
+				.sum::<u64>()
+		};
+		(total_size(&AVAILABLE_PREFIX[..]), total_size(&CHUNK_PREFIX[..]))
+	};
+
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let n_validators = 10;
+	let available_data = AvailableData {
+		pov: Arc::new(PoV { block_data: BlockData(vec![4, 5, 6]) }),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+
+	assert_eq!(measure_stored_data(&store, &TEST_CONFIG), (0, 0));
+
+	// A single chunk first, which is overwritten once the available data is stored.
+	with_tx(&store, |tx| {
+		write_meta(
+			tx,
+			&TEST_CONFIG,
+			&candidate_hash,
+			&CandidateMeta {
+				data_available: false,
+				chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators],
+				state: State::Unavailable(BETimestamp(0)),
+				data_size: 0,
+				chunks_size: 0,
+			},
+		);
+		write_pruning_key(
+			tx,
+			&TEST_CONFIG,
+			test_state.pruning_config.keep_unavailable_for,
+			&candidate_hash,
+		);
+	});
+	let chunk = ErasureChunk {
+		chunk: vec![1, 2, 3],
+		index: ValidatorIndex(1),
+		proof: Proof::try_from(vec![vec![3, 4, 5]]).unwrap(),
+	};
+	assert!(store_chunk(&store, &TEST_CONFIG, &subsystem.metrics, candidate_hash, chunk).unwrap());
+	assert_eq!(measure_stored_data(&store, &TEST_CONFIG), on_disk());
+	assert_eq!(gauges(), on_disk());
+
+	store_available_data(&subsystem, candidate_hash, n_validators, available_data.clone()).unwrap();
+	let chunks_size = (0..n_validators)
+		.map(|i| {
+			load_chunk(&store, &TEST_CONFIG, &candidate_hash, ValidatorIndex(i as _))
+				.unwrap()
+				.unwrap()
+				.encoded_size() as u64
+		})
+		.sum();
+	assert_eq!(on_disk(), (available_data.encoded_size() as u64, chunks_size));
+	assert_eq!(measure_stored_data(&store, &TEST_CONFIG), on_disk());
+	assert_eq!(gauges(), on_disk());
+
+	test_state.clock.inc(test_state.pruning_config.keep_unavailable_for);
+	assert!(!prune_batch(&subsystem).unwrap());
+
+	assert_eq!(on_disk(), (0, 0));
+	assert_eq!(measure_stored_data(&store, &TEST_CONFIG), (0, 0));
+	assert_eq!(gauges(), (0, 0));
+}
+
+#[test]
+fn meta_without_sizes_is_loaded() {
+	#[derive(Encode)]
+	struct OldCandidateMeta {
+		state: State,
+		data_available: bool,
+		chunks_stored: BitVec<u8, BitOrderLsb0>,
+	}
+
+	let store = test_store();
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let old_meta = OldCandidateMeta {
+		state: State::Unavailable(BETimestamp(0)),
+		data_available: true,
+		chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 1; 10],
+	};
+	with_tx(&store, |tx| {
+		tx.put_vec(
+			TEST_CONFIG.col_meta,
+			&(META_PREFIX, &candidate_hash).encode(),
+			old_meta.encode(),
+		)
+	});
+
+	let meta = load_meta(&store, &TEST_CONFIG, &candidate_hash).unwrap().unwrap();
+	assert!(meta.data_available);
+	assert_eq!(meta.chunks_stored, old_meta.chunks_stored);
+	assert_eq!((meta.data_size, meta.chunks_size), (0, 0));
+	assert_eq!(measure_stored_data(&store, &TEST_CONFIG), (0, 0));
+}
+
 #[test]
 fn stored_data_kept_until_finalized() {
 	let store = test_store();
@@ -31,8 +31,8 @@ use futures::{
 };
 
 use polkadot_node_primitives::{
-	AvailableData, PoV, SignedDisputeStatement, SignedFullStatement, Statement, ValidationResult,
-	BACKING_EXECUTION_TIMEOUT,
+	AvailableData, PoV, PvfExecKind, SignedDisputeStatement, SignedFullStatement, Statement,
+	ValidationResult,
 };
 use polkadot_node_subsystem_util::{
 	self as util,
@@ -387,7 +387,7 @@ async fn request_candidate_validation(
 		.send_message(CandidateValidationMessage::ValidateFromChainState(
 			candidate,
 			pov,
-			BACKING_EXECUTION_TIMEOUT,
+			PvfExecKind::Backing,
 			tx,
 		))
 		.await;
@@ -27,7 +27,8 @@ use polkadot_node_core_pvf::{
 	InvalidCandidate as WasmInvalidCandidate, PrepareError, Pvf, ValidationError, ValidationHost,
 };
 use polkadot_node_primitives::{
-	BlockData, InvalidCandidate, PoV, ValidationResult, POV_BOMB_LIMIT, VALIDATION_CODE_BOMB_LIMIT,
+	BlockData, InvalidCandidate, PoV, PvfExecKind, ValidationResult, POV_BOMB_LIMIT,
+	VALIDATION_CODE_BOMB_LIMIT,
 };
 use polkadot_node_subsystem::{
 	errors::RuntimeApiError,
@@ -41,8 +42,8 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
 use polkadot_parachain::primitives::{ValidationParams, ValidationResult as WasmValidationResult};
 use polkadot_primitives::v2::{
-	CandidateCommitments, CandidateDescriptor, Hash, OccupiedCoreAssumption,
-	PersistedValidationData, ValidationCode, ValidationCodeHash,
+	CandidateCommitments, CandidateDescriptor, ExecutorParams, Hash, OccupiedCoreAssumption,
+	PersistedValidationData, PvfExecTimeoutKind, ValidationCode, ValidationCodeHash,
 };
 
 use parity_scale_codec::Encode;
@@ -56,6 +57,8 @@ use async_trait::async_trait;
 #[cfg(test)]
 mod tests;
 
+pub use polkadot_node_core_pvf::ExecutionTimeouts;
+
 const LOG_TARGET: &'static str = "parachain::candidate-validation";
 
 /// Configuration for the candidate validation subsystem
@@ -66,6 +69,14 @@ pub struct Config {
 	/// The path to the executable which can be used for spawning PVF compilation & validation
 	/// workers.
 	pub program_path: PathBuf,
+	/// The maximum size a PoV may decompress to. Candidates with PoVs exceeding it are invalid.
+	///
+	/// Values above [`POV_BOMB_LIMIT`] are capped to it, so that no validator accepts a PoV which
+	/// would be a decompression bomb for the others.
+	pub pov_decompression_limit: usize,
+	/// The time allotted to the executions of each kind, unless the executor parameters of the
+	/// session set it.
+	pub execution_timeouts: ExecutionTimeouts,
 }
 
 /// The candidate validation subsystem.
@@ -103,6 +114,8 @@ where
 			self.pvf_metrics,
 			self.config.artifacts_cache_path,
 			self.config.program_path,
+			self.config.pov_decompression_limit.min(POV_BOMB_LIMIT),
+			self.config.execution_timeouts,
 		)
 		.map_err(|e| SubsystemError::with_origin("candidate-validation", e))
 		.boxed();
@@ -116,6 +129,8 @@ async fn run<Context>(
 	pvf_metrics: polkadot_node_core_pvf::Metrics,
 	cache_path: PathBuf,
 	program_path: PathBuf,
+	pov_decompression_limit: usize,
+	execution_timeouts: ExecutionTimeouts,
 ) -> SubsystemResult<()>
 where
 	Context: SubsystemContext<Message = CandidateValidationMessage>,
@@ -136,7 +151,7 @@ where
 				CandidateValidationMessage::ValidateFromChainState(
 					descriptor,
 					pov,
-					timeout,
+					exec_kind,
 					response_sender,
 				) => {
 					let bg = {
@@ -151,7 +166,9 @@ where
 								validation_host,
 								descriptor,
 								pov,
-								timeout,
+								exec_kind,
+								execution_timeouts,
+								pov_decompression_limit,
 								&metrics,
 							)
 							.await;
@@ -168,22 +185,31 @@ where
 					validation_code,
 					descriptor,
 					pov,
-					timeout,
+					exec_kind,
 					response_sender,
 				) => {
 					let bg = {
+						let mut sender = ctx.sender().clone();
 						let metrics = metrics.clone();
 						let validation_host = validation_host.clone();
 
 						async move {
 							let _timer = metrics.time_validate_from_exhaustive();
+							let execution_timeouts = session_execution_timeouts(
+								&mut sender,
+								descriptor.relay_parent,
+								execution_timeouts,
+							)
+							.await;
 							let res = validate_candidate_exhaustive(
 								validation_host,
 								persisted_validation_data,
 								validation_code,
 								descriptor,
 								pov,
-								timeout,
+								exec_kind,
+								execution_timeouts,
+								pov_decompression_limit,
 								&metrics,
 							)
 							.await;
@@ -260,6 +286,65 @@ where
 		})
 }
 
+/// The execution timeouts for candidates with the given relay parent.
+///
+/// These are the `defaults` of the node, overridden by the timeouts set in the executor parameters
+/// of the session. Should the parameters not be available, e.g. because the runtime does not
+/// support them yet, the defaults apply.
+async fn session_execution_timeouts<Sender>(
+	sender: &mut Sender,
+	relay_parent: Hash,
+	defaults: ExecutionTimeouts,
+) -> ExecutionTimeouts
+where
+	Sender: SubsystemSender,
+{
+	let (tx, rx) = oneshot::channel();
+	let session_index = match runtime_api_request(
+		sender,
+		relay_parent,
+		RuntimeApiRequest::SessionIndexForChild(tx),
+		rx,
+	)
+	.await
+	{
+		Ok(session_index) => session_index,
+		Err(RuntimeRequestFailed) => return defaults,
+	};
+
+	let (tx, rx) = oneshot::channel();
+	match runtime_api_request(
+		sender,
+		relay_parent,
+		RuntimeApiRequest::SessionExecutorParams(session_index, tx),
+		rx,
+	)
+	.await
+	{
+		Ok(Some(executor_params)) => execution_timeouts(defaults, &executor_params),
+		Ok(None) | Err(RuntimeRequestFailed) => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?relay_parent,
+				?session_index,
+				"No executor parameters, using the default execution timeouts",
+			);
+			defaults
+		},
+	}
+}
+
+/// The `defaults`, overridden by the execution timeouts set in the given executor parameters.
+fn execution_timeouts(defaults: ExecutionTimeouts, params: &ExecutorParams) -> ExecutionTimeouts {
+	ExecutionTimeouts {
+		backing: params.pvf_exec_timeout(PvfExecTimeoutKind::Backing).unwrap_or(defaults.backing),
+		approval: params
+			.pvf_exec_timeout(PvfExecTimeoutKind::Approval)
+			.unwrap_or(defaults.approval),
+		dispute: params.pvf_exec_timeout(PvfExecTimeoutKind::Dispute).unwrap_or(defaults.dispute),
+	}
+}
+
 async fn request_validation_code_by_hash<Sender>(
 	sender: &mut Sender,
 	relay_parent: Hash,
@@ -418,12 +503,17 @@ async fn validate_from_chain_state<Sender>(
 	validation_host: ValidationHost,
 	descriptor: CandidateDescriptor,
 	pov: Arc<PoV>,
-	timeout: Duration,
+	exec_kind: PvfExecKind,
+	execution_timeouts: ExecutionTimeouts,
+	pov_decompression_limit: usize,
 	metrics: &Metrics,
 ) -> Result<ValidationResult, ValidationFailed>
 where
 	Sender: SubsystemSender,
 {
+	let execution_timeouts =
+		session_execution_timeouts(sender, descriptor.relay_parent, execution_timeouts).await;
+
 	let (validation_data, validation_code) =
 		match find_assumed_validation_data(sender, &descriptor).await {
 			AssumptionCheckOutcome::Matches(validation_data, validation_code) =>
@@ -444,7 +534,9 @@ where
 		validation_code,
 		descriptor.clone(),
 		pov,
-		timeout,
+		exec_kind,
+		execution_timeouts,
+		pov_decompression_limit,
 		metrics,
 	)
 	.await;
@@ -475,7 +567,9 @@ async fn validate_candidate_exhaustive(
 	validation_code: ValidationCode,
 	descriptor: CandidateDescriptor,
 	pov: Arc<PoV>,
-	timeout: Duration,
+	exec_kind: PvfExecKind,
+	execution_timeouts: ExecutionTimeouts,
+	pov_decompression_limit: usize,
 	metrics: &Metrics,
 ) -> Result<ValidationResult, ValidationFailed> {
 	let _timer = metrics.time_validate_candidate_exhaustive();
@@ -511,8 +605,17 @@ async fn validate_candidate_exhaustive(
 	};
 
 	let raw_block_data =
-		match sp_maybe_compressed_blob::decompress(&pov.block_data.0, POV_BOMB_LIMIT) {
+		match sp_maybe_compressed_blob::decompress(&pov.block_data.0, pov_decompression_limit) {
 			Ok(block_data) => BlockData(block_data.to_vec()),
+			Err(sp_maybe_compressed_blob::Error::PossibleBomb) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					limit = pov_decompression_limit,
+					"PoV decompresses over the limit",
+				);
+
+				return Ok(ValidationResult::Invalid(InvalidCandidate::PoVDecompressedTooLarge))
+			},
 			Err(e) => {
 				gum::debug!(target: LOG_TARGET, err=?e, "Invalid PoV code");
 
@@ -529,7 +632,12 @@ async fn validate_candidate_exhaustive(
 	};
 
 	let result = validation_backend
-		.validate_candidate(raw_validation_code.to_vec(), timeout, params)
+		.validate_candidate(
+			raw_validation_code.to_vec(),
+			exec_kind,
+			execution_timeouts.get(exec_kind),
+			params,
+		)
 		.await;
 
 	if let Err(ref e) = result {
@@ -576,7 +684,8 @@ trait ValidationBackend {
 	async fn validate_candidate(
 		&mut self,
 		raw_validation_code: Vec<u8>,
-		timeout: Duration,
+		exec_kind: PvfExecKind,
+		execution_timeout: Duration,
 		params: ValidationParams,
 	) -> Result<WasmValidationResult, ValidationError>;
 
@@ -588,14 +697,16 @@ impl ValidationBackend for ValidationHost {
 	async fn validate_candidate(
 		&mut self,
 		raw_validation_code: Vec<u8>,
-		timeout: Duration,
+		exec_kind: PvfExecKind,
+		execution_timeout: Duration,
 		params: ValidationParams,
 	) -> Result<WasmValidationResult, ValidationError> {
 		let (tx, rx) = oneshot::channel();
 		if let Err(err) = self
 			.execute_pvf(
 				Pvf::from_code(raw_validation_code),
-				timeout,
+				exec_kind,
+				execution_timeout,
 				params.encode(),
 				polkadot_node_core_pvf::Priority::Normal,
 				tx,
@@ -19,10 +19,11 @@ use ::test_helpers::{dummy_hash, make_valid_candidate_descriptor};
 use assert_matches::assert_matches;
 use futures::executor;
 use polkadot_node_core_pvf::PrepareError;
+use polkadot_node_primitives::POV_BOMB_LIMIT;
 use polkadot_node_subsystem::messages::AllMessages;
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::reexports::SubsystemContext;
-use polkadot_primitives::v2::{HeadData, UpwardMessage};
+use polkadot_primitives::v2::{ExecutorParam, HeadData, UpwardMessage};
 use sp_core::testing::TaskExecutor;
 use sp_keyring::Sr25519Keyring;
 
@@ -359,7 +360,8 @@ impl ValidationBackend for MockValidateCandidateBackend {
 	async fn validate_candidate(
 		&mut self,
 		_raw_validation_code: Vec<u8>,
-		_timeout: Duration,
+		_exec_kind: PvfExecKind,
+		_execution_timeout: Duration,
 		_params: ValidationParams,
 	) -> Result<WasmValidationResult, ValidationError> {
 		self.result.clone()
@@ -412,7 +414,9 @@ fn candidate_validation_ok_is_ok() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	))
 	.unwrap();
@@ -461,7 +465,9 @@ fn candidate_validation_bad_return_is_invalid() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	))
 	.unwrap();
@@ -503,7 +509,9 @@ fn candidate_validation_timeout_is_internal_error() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	));
 
@@ -544,7 +552,9 @@ fn candidate_validation_code_mismatch_is_invalid() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	))
 	.unwrap();
@@ -589,7 +599,9 @@ fn compressed_code_works() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	));
 
@@ -634,7 +646,9 @@ fn code_decompression_failure_is_invalid() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	));
 
@@ -642,7 +656,7 @@ fn code_decompression_failure_is_invalid() {
 }
 
 #[test]
-fn pov_decompression_failure_is_invalid() {
+fn pov_decompression_bomb_is_too_large() {
 	let validation_data =
 		PersistedValidationData { max_pov_size: POV_BOMB_LIMIT as u32, ..Default::default() };
 	let head_data = HeadData(vec![1, 1, 1]);
@@ -680,7 +694,103 @@ fn pov_decompression_failure_is_invalid() {
 		validation_code,
 		descriptor,
 		Arc::new(pov),
-		Duration::from_secs(0),
+		PvfExecKind::Backing,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
+		&Default::default(),
+	));
+
+	assert_matches!(v, Ok(ValidationResult::Invalid(InvalidCandidate::PoVDecompressedTooLarge)));
+}
+
+#[test]
+fn pov_decompression_limit_is_configurable() {
+	let validation_data = PersistedValidationData { max_pov_size: 1024, ..Default::default() };
+	let head_data = HeadData(vec![1, 1, 1]);
+
+	let raw_block_data = vec![2u8; 1024];
+	let pov = sp_maybe_compressed_blob::compress(&raw_block_data, 1024)
+		.map(|raw| PoV { block_data: BlockData(raw) })
+		.unwrap();
+
+	let validation_code = ValidationCode(vec![2; 16]);
+
+	let descriptor = make_valid_candidate_descriptor(
+		1.into(),
+		dummy_hash(),
+		validation_data.hash(),
+		pov.hash(),
+		validation_code.hash(),
+		head_data.hash(),
+		dummy_hash(),
+		Sr25519Keyring::Alice,
+	);
+
+	let validation_result = WasmValidationResult {
+		head_data,
+		new_validation_code: None,
+		upward_messages: Vec::new(),
+		horizontal_messages: Vec::new(),
+		processed_downward_messages: 0,
+		hrmp_watermark: 0,
+	};
+
+	let validate = |pov_decompression_limit| {
+		executor::block_on(validate_candidate_exhaustive(
+			MockValidateCandidateBackend::with_hardcoded_result(Ok(validation_result.clone())),
+			validation_data.clone(),
+			validation_code.clone(),
+			descriptor.clone(),
+			Arc::new(pov.clone()),
+			PvfExecKind::Dispute,
+			ExecutionTimeouts::default(),
+			pov_decompression_limit,
+			&Default::default(),
+		))
+	};
+
+	assert_matches!(validate(1024), Ok(ValidationResult::Valid(_, _)));
+	assert_matches!(
+		validate(1023),
+		Ok(ValidationResult::Invalid(InvalidCandidate::PoVDecompressedTooLarge))
+	);
+}
+
+#[test]
+fn malformed_compressed_pov_is_invalid() {
+	let validation_data = PersistedValidationData { max_pov_size: 1024, ..Default::default() };
+	let head_data = HeadData(vec![1, 1, 1]);
+
+	// The magic prefix of a compressed blob followed by garbage.
+	let mut block_data =
+		sp_maybe_compressed_blob::compress(&[2u8; 64], 1024).unwrap()[..8].to_vec();
+	block_data.extend_from_slice(&[0xff; 32]);
+	let pov = PoV { block_data: BlockData(block_data) };
+
+	let validation_code = ValidationCode(vec![2; 16]);
+
+	let descriptor = make_valid_candidate_descriptor(
+		1.into(),
+		dummy_hash(),
+		validation_data.hash(),
+		pov.hash(),
+		validation_code.hash(),
+		head_data.hash(),
+		dummy_hash(),
+		Sr25519Keyring::Alice,
+	);
+
+	let v = executor::block_on(validate_candidate_exhaustive(
+		MockValidateCandidateBackend::with_hardcoded_result(Err(ValidationError::InternalError(
+			"unreachable".into(),
+		))),
+		validation_data,
+		validation_code,
+		descriptor,
+		Arc::new(pov),
+		PvfExecKind::Dispute,
+		ExecutionTimeouts::default(),
+		POV_BOMB_LIMIT,
 		&Default::default(),
 	));
 
@@ -702,7 +812,8 @@ impl ValidationBackend for MockPreCheckBackend {
 	async fn validate_candidate(
 		&mut self,
 		_raw_validation_code: Vec<u8>,
-		_timeout: Duration,
+		_exec_kind: PvfExecKind,
+		_execution_timeout: Duration,
 		_params: ValidationParams,
 	) -> Result<WasmValidationResult, ValidationError> {
 		unreachable!()
@@ -713,6 +824,75 @@ impl ValidationBackend for MockPreCheckBackend {
 	}
 }
 
+#[test]
+fn execution_timeouts_are_taken_from_the_executor_params() {
+	let defaults = ExecutionTimeouts::default();
+	let params = ExecutorParams::from(vec![
+		ExecutorParam::MaxMemoryPages(8192),
+		ExecutorParam::PvfExecTimeout(PvfExecTimeoutKind::Approval, 15_000),
+		ExecutorParam::PvfExecTimeout(PvfExecTimeoutKind::Dispute, 10_000),
+		ExecutorParam::PvfExecTimeout(PvfExecTimeoutKind::Dispute, 20_000),
+	]);
+
+	assert_eq!(execution_timeouts(defaults, &ExecutorParams::new()), defaults);
+	assert_eq!(
+		execution_timeouts(defaults, &params),
+		ExecutionTimeouts {
+			backing: defaults.backing,
+			approval: Duration::from_secs(15),
+			dispute: Duration::from_secs(20),
+		},
+	);
+}
+
+#[test]
+fn session_execution_timeouts_fall_back_to_the_defaults() {
+	let relay_parent = [3; 32].into();
+	let defaults = ExecutionTimeouts::default();
+	let params =
+		ExecutorParams::from(vec![ExecutorParam::PvfExecTimeout(PvfExecTimeoutKind::Backing, 500)]);
+
+	let pool = TaskExecutor::new();
+	let (mut ctx, mut ctx_handle) =
+		test_helpers::make_subsystem_context::<AllMessages, _>(pool.clone());
+
+	let timeouts_fut = async move {
+		let with_params = session_execution_timeouts(ctx.sender(), relay_parent, defaults).await;
+		let without_params = session_execution_timeouts(ctx.sender(), relay_parent, defaults).await;
+		(with_params, without_params)
+	};
+
+	let test_fut = async move {
+		for executor_params in [Some(params), None] {
+			assert_matches!(
+				ctx_handle.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					rp,
+					RuntimeApiRequest::SessionIndexForChild(tx)
+				)) => {
+					assert_eq!(rp, relay_parent);
+					let _ = tx.send(Ok(1));
+				}
+			);
+			assert_matches!(
+				ctx_handle.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					rp,
+					RuntimeApiRequest::SessionExecutorParams(1, tx)
+				)) => {
+					assert_eq!(rp, relay_parent);
+					let _ = tx.send(Ok(executor_params));
+				}
+			);
+		}
+	};
+
+	let ((with_params, without_params), ()) =
+		executor::block_on(future::join(timeouts_fut, test_fut));
+	assert_eq!(with_params, ExecutionTimeouts { backing: Duration::from_millis(500), ..defaults });
+	assert_eq!(without_params, defaults);
+}
+
 #[test]
 fn precheck_works() {
 	let relay_parent = [3; 32].into();
@@ -35,6 +35,7 @@ pub enum BackendWriteOp {
 	WriteRecentDisputes(RecentDisputes),
 	WriteCandidateVotes(SessionIndex, CandidateHash, CandidateVotes),
 	DeleteCandidateVotes(SessionIndex, CandidateHash),
+	DeleteCandidateVotesBefore(SessionIndex),
 }
 
 /// An abstraction over backend storage for the logic of this subsystem.
@@ -71,6 +72,8 @@ pub struct OverlayedBackend<'a, B: 'a> {
 	earliest_session: Option<SessionIndex>,
 	// `None` means unchanged.
 	recent_disputes: Option<RecentDisputes>,
+	// Sessions below this have all their candidate votes deleted. `None` means unchanged.
+	candidate_votes_pruned_before: Option<SessionIndex>,
 	// `None` means deleted, missing means query inner.
 	candidate_votes: HashMap<(SessionIndex, CandidateHash), Option<CandidateVotes>>,
 }
@@ -81,6 +84,7 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			inner: backend,
 			earliest_session: None,
 			recent_disputes: None,
+			candidate_votes_pruned_before: None,
 			candidate_votes: HashMap::new(),
 		}
 	}
@@ -89,6 +93,7 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 	pub fn is_empty(&self) -> bool {
 		self.earliest_session.is_none() &&
 			self.recent_disputes.is_none() &&
+			self.candidate_votes_pruned_before.is_none() &&
 			self.candidate_votes.is_empty()
 	}
 
@@ -120,6 +125,10 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			return Ok(val.clone())
 		}
 
+		if self.candidate_votes_pruned_before.map_or(false, |before| session < before) {
+			return Ok(None)
+		}
+
 		self.inner.load_candidate_votes(session, candidate_hash)
 	}
 
@@ -156,6 +165,16 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.candidate_votes.insert((session, candidate_hash), None);
 	}
 
+	/// Prepare a deletion of the candidate votes of all candidates in sessions before the
+	/// indicated one.
+	///
+	/// Any earlier writes of candidate votes in those sessions are discarded, later ones are kept.
+	pub fn delete_candidate_votes_before(&mut self, session: SessionIndex) {
+		self.candidate_votes_pruned_before =
+			Some(self.candidate_votes_pruned_before.map_or(session, |s| s.max(session)));
+		self.candidate_votes.retain(|(s, _), _| *s >= session);
+	}
+
 	/// Transform this backend into a set of write-ops to be written to the inner backend.
 	pub fn into_write_ops(self) -> impl Iterator<Item = BackendWriteOp> {
 		let earliest_session_ops = self
@@ -166,6 +185,11 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		let recent_dispute_ops =
 			self.recent_disputes.map(|d| BackendWriteOp::WriteRecentDisputes(d)).into_iter();
 
+		let pruned_votes_ops = self
+			.candidate_votes_pruned_before
+			.map(|s| BackendWriteOp::DeleteCandidateVotesBefore(s))
+			.into_iter();
+
 		let candidate_vote_ops =
 			self.candidate_votes
 				.into_iter()
@@ -174,6 +198,9 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 					None => BackendWriteOp::DeleteCandidateVotes(session, candidate),
 				});
 
-		earliest_session_ops.chain(recent_dispute_ops).chain(candidate_vote_ops)
+		earliest_session_ops
+			.chain(recent_dispute_ops)
+			.chain(pruned_votes_ops)
+			.chain(candidate_vote_ops)
 	}
 }
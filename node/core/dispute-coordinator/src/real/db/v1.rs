@@ -23,8 +23,9 @@ use polkadot_primitives::v2::{
 	ValidDisputeStatementKind, ValidatorIndex, ValidatorSignature,
 };
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use lru::LruCache;
 use parity_scale_codec::{Decode, Encode};
 
 use crate::{
@@ -40,14 +41,70 @@ const RECENT_DISPUTES_KEY: &[u8; 15] = b"recent-disputes";
 const EARLIEST_SESSION_KEY: &[u8; 16] = b"earliest-session";
 const CANDIDATE_VOTES_SUBKEY: &[u8; 15] = b"candidate-votes";
 
+/// The number of candidates the votes of which are cached.
+const CANDIDATE_VOTES_CACHE_SIZE: usize = 1024;
+
+/// The maximum number of candidates the votes of which are deleted by a single write when pruning
+/// ancient sessions, so that pruning does not hold up the import of new votes.
+const MAX_PRUNED_CANDIDATES_PER_WRITE: usize = 1024;
+
 pub struct DbBackend {
 	inner: Arc<dyn Database>,
 	config: ColumnConfiguration,
+	// The votes most recently loaded or written, `None` if there are none.
+	votes_cache: Mutex<LruCache<(SessionIndex, CandidateHash), Option<CandidateVotes>>>,
+	// The votes in sessions before this one are yet to be deleted, a batch with each write.
+	prune_votes_before: Option<SessionIndex>,
 }
 
 impl DbBackend {
 	pub fn new(db: Arc<dyn Database>, config: ColumnConfiguration) -> Self {
-		Self { inner: db, config }
+		// Votes left behind by pruning which did not finish before a restart are deleted with the
+		// next writes.
+		let prune_votes_before = load_earliest_session(&*db, &config).ok().flatten();
+		Self {
+			inner: db,
+			config,
+			votes_cache: Mutex::new(LruCache::new(CANDIDATE_VOTES_CACHE_SIZE)),
+			prune_votes_before,
+		}
+	}
+
+	fn cache_votes(
+		&self,
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		votes: Option<CandidateVotes>,
+	) {
+		if let Ok(mut cache) = self.votes_cache.lock() {
+			cache.put((session, candidate_hash), votes);
+		}
+	}
+
+	// Delete the next batch of votes in sessions before `prune_votes_before`.
+	fn prune_votes_batch(&mut self, tx: &mut DBTransaction) {
+		let before = match self.prune_votes_before {
+			Some(before) => before,
+			None => return,
+		};
+
+		// Candidate votes are keyed by session first, so the iteration only visits the votes to
+		// delete.
+		let end = candidate_votes_session_prefix(before);
+		let mut n_deleted = 0;
+		for (key, _) in self
+			.inner
+			.iter_with_prefix(self.config.col_data, CANDIDATE_VOTES_SUBKEY)
+			.take_while(|(key, _)| &key[..] < &end[..])
+			.take(MAX_PRUNED_CANDIDATES_PER_WRITE)
+		{
+			tx.delete(self.config.col_data, &key);
+			n_deleted += 1;
+		}
+
+		if n_deleted < MAX_PRUNED_CANDIDATES_PER_WRITE {
+			self.prune_votes_before = None;
+		}
 	}
 }
 
@@ -68,7 +125,23 @@ impl Backend for DbBackend {
 		session: SessionIndex,
 		candidate_hash: &CandidateHash,
 	) -> SubsystemResult<Option<CandidateVotes>> {
-		load_candidate_votes(&*self.inner, &self.config, session, candidate_hash)
+		// The votes may still be on disk while they are being pruned.
+		if self.prune_votes_before.map_or(false, |before| session < before) {
+			return Ok(None)
+		}
+
+		if let Some(votes) = self
+			.votes_cache
+			.lock()
+			.ok()
+			.and_then(|mut cache| cache.get(&(session, *candidate_hash)).cloned())
+		{
+			return Ok(votes)
+		}
+
+		let votes = load_candidate_votes(&*self.inner, &self.config, session, candidate_hash)?;
+		self.cache_votes(session, *candidate_hash, votes.clone());
+		Ok(votes)
 	}
 
 	/// Atomically writes the list of operations, with later operations taking precedence over
@@ -92,13 +165,30 @@ impl Backend for DbBackend {
 						&candidate_votes_key(session, &candidate_hash),
 						votes.encode(),
 					);
+					self.cache_votes(session, candidate_hash, Some(votes));
 				},
 				BackendWriteOp::DeleteCandidateVotes(session, candidate_hash) => {
 					tx.delete(self.config.col_data, &candidate_votes_key(session, &candidate_hash));
+					self.cache_votes(session, candidate_hash, None);
+				},
+				BackendWriteOp::DeleteCandidateVotesBefore(session) => {
+					self.prune_votes_before =
+						Some(self.prune_votes_before.map_or(session, |s| s.max(session)));
+					if let Ok(mut cache) = self.votes_cache.lock() {
+						let pruned: Vec<_> = cache
+							.iter()
+							.map(|(key, _)| *key)
+							.filter(|(s, _)| *s < session)
+							.collect();
+						for key in pruned {
+							cache.pop(&key);
+						}
+					}
 				},
 			}
 		}
 
+		self.prune_votes_batch(&mut tx);
 		self.inner.write(tx).map_err(FatalError::DbWriteFailed)
 	}
 }
@@ -114,6 +204,14 @@ fn candidate_votes_key(session: SessionIndex, candidate_hash: &CandidateHash) ->
 	buf
 }
 
+fn candidate_votes_session_prefix(session: SessionIndex) -> [u8; 15 + 4] {
+	let mut buf = [0u8; 15 + 4];
+	buf[..15].copy_from_slice(CANDIDATE_VOTES_SUBKEY);
+	buf[15..].copy_from_slice(&session.to_be_bytes());
+
+	buf
+}
+
 /// Column configuration information for the DB.
 #[derive(Debug, Clone)]
 pub struct ColumnConfiguration {
@@ -218,13 +316,16 @@ pub(crate) fn load_recent_disputes(
 /// first launch. If the on-disk data does not need to be pruned, only a single storage read
 /// will be performed.
 ///
-/// If one or more ancient sessions are pruned, all metadata on candidates within the ancient
-/// session will be deleted.
+/// Data is kept for the last `pruning_window` sessions, but never for less than the
+/// `DISPUTE_WINDOW`. If one or more ancient sessions are pruned, all metadata and votes on
+/// candidates within the ancient sessions will be deleted.
 pub(crate) fn note_current_session(
 	overlay_db: &mut OverlayedBackend<'_, impl Backend>,
 	current_session: SessionIndex,
+	pruning_window: SessionIndex,
 ) -> SubsystemResult<()> {
-	let new_earliest = current_session.saturating_sub(DISPUTE_WINDOW.get());
+	let pruning_window = std::cmp::max(pruning_window, DISPUTE_WINDOW.get());
+	let new_earliest = current_session.saturating_sub(pruning_window);
 	match overlay_db.load_earliest_session()? {
 		None => {
 			// First launch - write new-earliest.
@@ -234,6 +335,10 @@ pub(crate) fn note_current_session(
 			// Prune all data in the outdated sessions.
 			overlay_db.write_earliest_session(new_earliest);
 
+			// Clear the votes on all candidates, including undisputed ones and any left behind
+			// in sessions before `prev_earliest` by versions which only pruned disputed ones.
+			overlay_db.delete_candidate_votes_before(new_earliest);
+
 			// Clear recent disputes metadata.
 			{
 				let mut recent_disputes = overlay_db.load_recent_disputes()?.unwrap_or_default();
@@ -268,7 +373,7 @@ mod tests {
 
 	fn make_db() -> DbBackend {
 		let db = kvdb_memorydb::create(1);
-		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
+		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[0]);
 		let store = Arc::new(db);
 		let config = ColumnConfiguration { col_data: 0 };
 		DbBackend::new(store, config)
@@ -471,7 +576,7 @@ mod tests {
 		backend.write(write_ops).unwrap();
 
 		let mut overlay_db = OverlayedBackend::new(&backend);
-		note_current_session(&mut overlay_db, current_session).unwrap();
+		note_current_session(&mut overlay_db, current_session, DISPUTE_WINDOW.get()).unwrap();
 
 		assert_eq!(overlay_db.load_earliest_session().unwrap(), Some(new_earliest_session));
 
@@ -493,4 +598,148 @@ mod tests {
 			.is_some());
 		assert!(overlay_db.load_candidate_votes(very_recent, &hash_d).unwrap().is_some());
 	}
+
+	#[test]
+	fn note_current_session_prunes_undisputed_votes() {
+		let mut backend = make_db();
+
+		let hash_a = CandidateHash(Hash::repeat_byte(0x0a));
+		let hash_b = CandidateHash(Hash::repeat_byte(0x0b));
+		let hash_c = CandidateHash(Hash::repeat_byte(0x0c));
+		let hash_d = CandidateHash(Hash::repeat_byte(0x0d));
+
+		let current_session = 5 + DISPUTE_WINDOW.get();
+		// Left behind by a version which did not prune undisputed candidates.
+		let stale_session = 1;
+		let old_session = 3;
+		let recent_session = current_session - 1;
+
+		let blank_candidate_votes = || CandidateVotes {
+			candidate_receipt: dummy_candidate_receipt(dummy_hash()),
+			valid: Vec::new(),
+			invalid: Vec::new(),
+		};
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(2);
+		overlay_db.write_candidate_votes(stale_session, hash_d, blank_candidate_votes());
+		overlay_db.write_candidate_votes(old_session, hash_a, blank_candidate_votes());
+		overlay_db.write_candidate_votes(old_session, hash_b, blank_candidate_votes());
+		overlay_db.write_candidate_votes(recent_session, hash_c, blank_candidate_votes());
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		note_current_session(&mut overlay_db, current_session, DISPUTE_WINDOW.get()).unwrap();
+
+		// The overlay already reflects the pruning.
+		assert!(overlay_db.load_candidate_votes(stale_session, &hash_d).unwrap().is_none());
+		assert!(overlay_db.load_candidate_votes(old_session, &hash_a).unwrap().is_none());
+		assert!(overlay_db.load_candidate_votes(old_session, &hash_b).unwrap().is_none());
+		assert!(overlay_db.load_candidate_votes(recent_session, &hash_c).unwrap().is_some());
+
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		assert!(backend.load_candidate_votes(stale_session, &hash_d).unwrap().is_none());
+		assert!(backend.load_candidate_votes(old_session, &hash_a).unwrap().is_none());
+		assert!(backend.load_candidate_votes(old_session, &hash_b).unwrap().is_none());
+		assert!(backend.load_candidate_votes(recent_session, &hash_c).unwrap().is_some());
+	}
+
+	#[test]
+	fn note_current_session_respects_pruning_window() {
+		let mut backend = make_db();
+
+		let hash_a = CandidateHash(Hash::repeat_byte(0x0a));
+		let hash_b = CandidateHash(Hash::repeat_byte(0x0b));
+
+		let pruning_window = DISPUTE_WINDOW.get() + 4;
+		let current_session = 10 + pruning_window;
+
+		let blank_candidate_votes = || CandidateVotes {
+			candidate_receipt: dummy_candidate_receipt(dummy_hash()),
+			valid: Vec::new(),
+			invalid: Vec::new(),
+		};
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(0);
+		overlay_db.write_candidate_votes(9, hash_a, blank_candidate_votes());
+		overlay_db.write_candidate_votes(10, hash_b, blank_candidate_votes());
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		note_current_session(&mut overlay_db, current_session, pruning_window).unwrap();
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		assert_eq!(backend.load_earliest_session().unwrap(), Some(10));
+		assert!(backend.load_candidate_votes(9, &hash_a).unwrap().is_none());
+		assert!(backend.load_candidate_votes(10, &hash_b).unwrap().is_some());
+
+		// A window smaller than the dispute window does not prune any further.
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		note_current_session(&mut overlay_db, current_session, 1).unwrap();
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		assert_eq!(
+			backend.load_earliest_session().unwrap(),
+			Some(current_session - DISPUTE_WINDOW.get())
+		);
+	}
+
+	#[test]
+	fn pruning_is_spread_over_writes_and_resumed_after_restart() {
+		let mut backend = make_db();
+		let count_votes = |backend: &DbBackend| {
+			backend
+				.inner
+				.iter_with_prefix(backend.config.col_data, CANDIDATE_VOTES_SUBKEY)
+				.count()
+		};
+
+		let n_candidates = MAX_PRUNED_CANDIDATES_PER_WRITE + 5;
+		let recent_hash = CandidateHash(Hash::repeat_byte(0xff));
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(0);
+		for i in 0..n_candidates {
+			overlay_db.write_candidate_votes(
+				1,
+				CandidateHash(Hash::from_low_u64_be(i as u64)),
+				CandidateVotes {
+					candidate_receipt: dummy_candidate_receipt(dummy_hash()),
+					valid: Vec::new(),
+					invalid: Vec::new(),
+				},
+			);
+		}
+		overlay_db.write_candidate_votes(
+			2,
+			recent_hash,
+			CandidateVotes {
+				candidate_receipt: dummy_candidate_receipt(dummy_hash()),
+				valid: Vec::new(),
+				invalid: Vec::new(),
+			},
+		);
+		backend.write(overlay_db.into_write_ops()).unwrap();
+		assert_eq!(count_votes(&backend), n_candidates + 1);
+
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(2);
+		overlay_db.delete_candidate_votes_before(2);
+		backend.write(overlay_db.into_write_ops()).unwrap();
+
+		// The first batch is deleted, the rest is hidden until it is.
+		assert_eq!(count_votes(&backend), 6);
+		assert!(backend
+			.load_candidate_votes(1, &CandidateHash(Hash::from_low_u64_be(0)))
+			.unwrap()
+			.is_none());
+		assert!(backend.load_candidate_votes(2, &recent_hash).unwrap().is_some());
+
+		// A restarted backend deletes the remaining votes with its next write.
+		let mut backend = DbBackend::new(backend.inner.clone(), backend.config.clone());
+		backend.write(Vec::new()).unwrap();
+		assert_eq!(count_votes(&backend), 1);
+		assert!(backend.load_candidate_votes(2, &recent_hash).unwrap().is_some());
+	}
 }
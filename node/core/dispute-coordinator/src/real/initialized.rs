@@ -82,6 +82,7 @@ pub struct Initialized {
 	keystore: Arc<LocalKeystore>,
 	rolling_session_window: RollingSessionWindow,
 	highest_session: SessionIndex,
+	pruning_window: SessionIndex,
 	spam_slots: SpamSlots,
 	participation: Participation,
 	ordering_provider: OrderingProvider,
@@ -102,7 +103,7 @@ impl Initialized {
 		spam_slots: SpamSlots,
 		ordering_provider: OrderingProvider,
 	) -> Self {
-		let DisputeCoordinatorSubsystem { config: _, store: _, keystore, metrics } = subsystem;
+		let DisputeCoordinatorSubsystem { config, store: _, keystore, metrics } = subsystem;
 
 		let (participation_sender, participation_receiver) = mpsc::channel(1);
 		let participation = Participation::new(participation_sender);
@@ -112,6 +113,7 @@ impl Initialized {
 			keystore,
 			rolling_session_window,
 			highest_session,
+			pruning_window: config.pruning_window,
 			spam_slots,
 			ordering_provider,
 			participation,
@@ -287,7 +289,7 @@ impl Initialized {
 
 						self.highest_session = session;
 
-						db::v1::note_current_session(overlay_db, session)?;
+						db::v1::note_current_session(overlay_db, session, self.pruning_window)?;
 						self.spam_slots.prune_old(new_window_start);
 					}
 				},
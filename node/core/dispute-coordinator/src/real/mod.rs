@@ -38,7 +38,7 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_util::{
 	database::Database, rolling_session_window::RollingSessionWindow,
 };
-use polkadot_primitives::v2::{ValidatorIndex, ValidatorPair};
+use polkadot_primitives::v2::{SessionIndex, ValidatorIndex, ValidatorPair};
 
 use crate::{
 	error::{FatalResult, JfyiError, Result},
@@ -109,8 +109,12 @@ pub struct DisputeCoordinatorSubsystem {
 /// Configuration for the dispute coordinator subsystem.
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
-	/// The data column in the store to use for dispute data.
+	/// The data column in the store to use for dispute data. It must support ordered iteration.
 	pub col_data: u32,
+	/// The number of past sessions for which votes are kept in the store.
+	///
+	/// Anything below [`DISPUTE_WINDOW`] is treated as [`DISPUTE_WINDOW`].
+	pub pruning_window: SessionIndex,
 }
 
 impl Config {
@@ -260,7 +264,11 @@ impl DisputeCoordinatorSubsystem {
 		Context: SubsystemContext<Message = DisputeCoordinatorMessage>,
 	{
 		// Prune obsolete disputes:
-		db::v1::note_current_session(overlay_db, rolling_session_window.latest_session())?;
+		db::v1::note_current_session(
+			overlay_db,
+			rolling_session_window.latest_session(),
+			self.config.pruning_window,
+		)?;
 
 		let active_disputes = match overlay_db.load_recent_disputes() {
 			Ok(Some(disputes)) =>
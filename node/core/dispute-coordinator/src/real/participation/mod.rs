@@ -21,9 +21,12 @@ use futures::{
 	FutureExt, SinkExt,
 };
 
-use polkadot_node_primitives::{ValidationResult, APPROVAL_EXECUTION_TIMEOUT};
+use polkadot_node_primitives::{PvfExecKind, ValidationResult};
 use polkadot_node_subsystem::{
-	messages::{AvailabilityRecoveryMessage, AvailabilityStoreMessage, CandidateValidationMessage},
+	messages::{
+		AvailabilityRecoveryMessage, AvailabilityStoreMessage, CandidateValidationMessage,
+		RecoveryStrategy,
+	},
 	ActiveLeavesUpdate, RecoveryError, SubsystemContext, SubsystemSender,
 };
 use polkadot_node_subsystem_util::runtime::get_validation_code_by_hash;
@@ -257,7 +260,9 @@ async fn participate(
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				req.candidate_receipt().clone(),
 				req.session(),
-				None,
+				// Backers of a disputed candidate are not to be relied upon, but the systematic
+				// chunks save decoding the data.
+				RecoveryStrategy::SystematicChunksFirst,
 				recover_available_data_tx,
 			)
 			.into(),
@@ -353,9 +358,8 @@ async fn participate(
 	// Issue a request to validate the candidate with the provided exhaustive
 	// parameters
 	//
-	// We use the approval execution timeout because this is intended to
-	// be run outside of backing and therefore should be subject to the
-	// same level of leeway.
+	// Disputes get the same leeway as approvals, as this is run outside of backing too, but take
+	// precedence over them.
 	let (validation_tx, validation_rx) = oneshot::channel();
 	sender
 		.send_message(
@@ -364,7 +368,7 @@ async fn participate(
 				validation_code,
 				req.candidate_receipt().descriptor.clone(),
 				available_data.pov,
-				APPROVAL_EXECUTION_TIMEOUT,
+				PvfExecKind::Dispute,
 				validation_tx,
 			)
 			.into(),
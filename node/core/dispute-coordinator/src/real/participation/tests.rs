@@ -116,8 +116,8 @@ pub async fn participation_full_happy_path(ctx_handle: &mut VirtualOverseer) {
 	assert_matches!(
 	ctx_handle.recv().await,
 	AllMessages::CandidateValidation(
-		CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx)
-		) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+		CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx)
+		) if exec_kind == PvfExecKind::Dispute => {
 		tx.send(Ok(ValidationResult::Valid(dummy_candidate_commitments(None), PersistedValidationData::default()))).unwrap();
 	},
 	"overseer did not receive candidate validation message",
@@ -419,8 +419,8 @@ fn cast_invalid_vote_if_validation_fails_or_is_invalid() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::CandidateValidation(
-				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx)
-			) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx)
+			) if exec_kind == PvfExecKind::Dispute => {
 				tx.send(Ok(ValidationResult::Invalid(InvalidCandidate::Timeout))).unwrap();
 			},
 			"overseer did not receive candidate validation message",
@@ -457,8 +457,8 @@ fn cast_invalid_vote_if_validation_passes_but_commitments_dont_match() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::CandidateValidation(
-				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx)
-			) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx)
+			) if exec_kind == PvfExecKind::Dispute => {
 				let mut commitments = CandidateCommitments::default();
 				// this should lead to a commitments hash mismatch
 				commitments.processed_downward_messages = 42;
@@ -499,8 +499,8 @@ fn cast_valid_vote_if_validation_passes() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::CandidateValidation(
-				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx)
-			) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx)
+			) if exec_kind == PvfExecKind::Dispute => {
 				tx.send(Ok(ValidationResult::Valid(dummy_candidate_commitments(None), PersistedValidationData::default()))).unwrap();
 			},
 			"overseer did not receive candidate validation message",
@@ -538,8 +538,8 @@ fn failure_to_store_available_data_does_not_preclude_participation() {
 		assert_matches!(
 			ctx_handle.recv().await,
 			AllMessages::CandidateValidation(
-				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx)
-			) if timeout == APPROVAL_EXECUTION_TIMEOUT => {
+				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, exec_kind, tx)
+			) if exec_kind == PvfExecKind::Dispute => {
 				tx.send(Err(ValidationFailed("fail".to_string()))).unwrap();
 			},
 			"overseer did not receive candidate validation message",
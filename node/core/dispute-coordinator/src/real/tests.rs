@@ -32,7 +32,9 @@ use futures::{
 use parity_scale_codec::Encode;
 use polkadot_node_subsystem_util::database::Database;
 
-use polkadot_node_primitives::{SignedDisputeStatement, SignedFullStatement, Statement};
+use polkadot_node_primitives::{
+	SignedDisputeStatement, SignedFullStatement, Statement, DISPUTE_WINDOW,
+};
 use polkadot_node_subsystem::{
 	messages::{
 		ChainApiMessage, DisputeCoordinatorMessage, DisputeDistributionMessage,
@@ -164,9 +166,9 @@ impl Default for TestState {
 			make_keystore(vec![Sr25519Keyring::Alice.to_seed()].into_iter()).into();
 
 		let db = kvdb_memorydb::create(1);
-		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
+		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[0]);
 		let db = Arc::new(db);
-		let config = Config { col_data: 0 };
+		let config = Config { col_data: 0, pruning_window: DISPUTE_WINDOW.get() };
 
 		TestState {
 			validators: validators.into_iter().map(|(pair, _)| pair).collect(),
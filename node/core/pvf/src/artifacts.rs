@@ -14,16 +14,34 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{error::PrepareError, host::PrepareResultSender};
+use crate::{
+	error::PrepareError, executor_intf::executor_params_hash, host::PrepareResultSender, LOG_TARGET,
+};
 use always_assert::always;
 use async_std::path::{Path, PathBuf};
+use futures::StreamExt as _;
 use parity_scale_codec::{Decode, Encode};
+use polkadot_core_primitives::Hash;
 use polkadot_parachain::primitives::ValidationCodeHash;
+use sp_core::blake2_256;
 use std::{
 	collections::HashMap,
 	time::{Duration, SystemTime},
 };
 
+/// The version of the node that prepares the artifacts, including the commit it was built from.
+/// Artifacts prepared by other builds are not picked up after a restart, since the compiler may
+/// have changed in between without the crate version being bumped.
+const NODE_VERSION: &str = env!("SUBSTRATE_CLI_IMPL_VERSION");
+
+/// The checksum of the contents of a prepared artifact.
+pub type ArtifactChecksum = Hash;
+
+/// Computes the checksum of the given artifact contents.
+pub fn compute_checksum(data: &[u8]) -> ArtifactChecksum {
+	blake2_256(data).into()
+}
+
 /// A wrapper for the compiled PVF code.
 #[derive(Encode, Decode)]
 pub struct CompiledArtifact(Vec<u8>);
@@ -40,36 +58,70 @@ impl AsRef<[u8]> for CompiledArtifact {
 	}
 }
 
-/// Identifier of an artifact. Right now it only encodes a code hash of the PVF. But if we get to
-/// multiple engine implementations the artifact ID should include the engine type as well.
+/// Identifier of an artifact. Encodes a code hash of the PVF and a hash of the executor parameters
+/// it is prepared with. But if we get to multiple engine implementations the artifact ID should
+/// include the engine type as well.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ArtifactId {
 	pub(crate) code_hash: ValidationCodeHash,
+	pub(crate) executor_params_hash: Hash,
 }
 
 impl ArtifactId {
 	const PREFIX: &'static str = "wasmtime_";
 
-	/// Creates a new artifact ID with the given hash.
-	pub fn new(code_hash: ValidationCodeHash) -> Self {
-		Self { code_hash }
+	/// Creates a new artifact ID with the given hashes.
+	pub fn new(code_hash: ValidationCodeHash, executor_params_hash: Hash) -> Self {
+		Self { code_hash, executor_params_hash }
 	}
 
-	/// Tries to recover the artifact id from the given file name.
-	#[cfg(test)]
-	pub fn from_file_name(file_name: &str) -> Option<Self> {
-		use polkadot_core_primitives::Hash;
+	/// Tries to recover the artifact id, the checksum of the contents and the time the preparation
+	/// took from the given file name.
+	///
+	/// Returns `None` if the file name is malformed or the artifact was prepared by another
+	/// version of the node.
+	pub fn from_file_name(file_name: &str) -> Option<(Self, ArtifactChecksum, Duration)> {
 		use std::str::FromStr as _;
 
-		let file_name = file_name.strip_prefix(Self::PREFIX)?;
-		let code_hash = Hash::from_str(file_name).ok()?.into();
+		let file_name = file_name
+			.strip_prefix(Self::PREFIX)?
+			.strip_prefix(&format!("v{}_", NODE_VERSION))?;
+		let mut parts = file_name.split('_');
+		let code_hash = Hash::from_str(parts.next()?).ok()?.into();
+		let executor_params_hash = Hash::from_str(parts.next()?).ok()?;
+		let checksum = Hash::from_str(parts.next()?).ok()?;
+		let preparation_time = parts.next()?.strip_suffix("ms")?.parse().ok()?;
+		if parts.next().is_some() {
+			return None
+		}
 
-		Some(Self { code_hash })
+		Some((
+			Self { code_hash, executor_params_hash },
+			checksum,
+			Duration::from_millis(preparation_time),
+		))
 	}
 
-	/// Returns the expected path to this artifact given the root of the cache.
-	pub fn path(&self, cache_path: &Path) -> PathBuf {
-		let file_name = format!("{}{:#x}", Self::PREFIX, self.code_hash);
+	/// Returns the expected path to this artifact with the given checksum and preparation time
+	/// given the root of the cache.
+	///
+	/// The preparation time is part of the name so that pre-checks can still be judged by it
+	/// after a restart.
+	pub fn path(
+		&self,
+		cache_path: &Path,
+		checksum: &ArtifactChecksum,
+		preparation_time: Duration,
+	) -> PathBuf {
+		let file_name = format!(
+			"{}v{}_{:#x}_{:#x}_{:#x}_{}ms",
+			Self::PREFIX,
+			NODE_VERSION,
+			self.code_hash,
+			self.executor_params_hash,
+			checksum,
+			preparation_time.as_millis(),
+		);
 		cache_path.join(file_name)
 	}
 }
@@ -78,8 +130,8 @@ impl ArtifactId {
 ///
 /// Rationale for having this is two-fold:
 ///
-/// - While we can derive the artifact path from the artifact id, it makes sense to carry it around
-/// sometimes to avoid extra work.
+/// - While we can derive the artifact path from the artifact id and the checksum, it makes sense
+/// to carry it around sometimes to avoid extra work.
 /// - At the same time, carrying only path limiting the ability for logging.
 #[derive(Debug, Clone)]
 pub struct ArtifactPathId {
@@ -88,22 +140,27 @@ pub struct ArtifactPathId {
 }
 
 impl ArtifactPathId {
-	pub(crate) fn new(artifact_id: ArtifactId, cache_path: &Path) -> Self {
-		Self { path: artifact_id.path(cache_path), id: artifact_id }
+	pub(crate) fn new(artifact_id: ArtifactId, path: PathBuf) -> Self {
+		Self { id: artifact_id, path }
 	}
 }
 
 pub enum ArtifactState {
 	/// The artifact is ready to be used by the executor.
 	///
-	/// That means that the artifact should be accessible through the path obtained by the artifact
-	/// id (unless, it was removed externally).
+	/// That means that the artifact should be accessible through the given path (unless, it was
+	/// removed externally).
 	Prepared {
+		/// The path to the prepared artifact, which includes the checksum of its contents.
+		path: PathBuf,
 		/// The time when the artifact was the last time needed.
 		///
 		/// This is updated when we get the heads up for this artifact or when we just discover
 		/// this file.
 		last_time_needed: SystemTime,
+		/// How long the preparation took. For artifacts discovered on disk this is taken from the
+		/// file name.
+		preparation_time: Duration,
 	},
 	/// A task to prepare this artifact is scheduled.
 	Preparing { waiting_for_response: Vec<PrepareResultSender> },
@@ -118,17 +175,64 @@ pub struct Artifacts {
 }
 
 impl Artifacts {
-	/// Initialize a blank cache at the given path. This will clear everything present at the
-	/// given path, to be populated over time.
+	/// Initialize the cache at the given path, picking up the artifacts prepared before a restart.
 	///
-	/// The recognized artifacts will be filled in the table and unrecognized will be removed.
+	/// The recognized artifacts will be filled in the table and unrecognized will be removed. An
+	/// artifact is recognized if it was prepared by this version of the node with the current
+	/// executor parameters, its file name records how long the preparation took, and its contents
+	/// match the checksum in its file name. The PVFs of the removed artifacts will be prepared
+	/// again once needed.
 	pub async fn new(cache_path: &Path) -> Self {
 		// Make sure that the cache path directory and all it's parents are created.
-		// First delete the entire cache. Nodes are long-running so this should populate shortly.
-		let _ = async_std::fs::remove_dir_all(cache_path).await;
 		let _ = async_std::fs::create_dir_all(cache_path).await;
 
-		Self { artifacts: HashMap::new() }
+		let mut artifacts = HashMap::new();
+		let mut entries = match async_std::fs::read_dir(cache_path).await {
+			Ok(entries) => entries,
+			Err(err) => {
+				gum::warn!(
+					target: LOG_TARGET,
+					"failed to read the artifacts cache at {}: {:?}",
+					cache_path.display(),
+					err,
+				);
+				return Self { artifacts }
+			},
+		};
+
+		let now = SystemTime::now();
+		let mut removed = 0;
+		while let Some(entry) = entries.next().await {
+			let path = match entry {
+				Ok(entry) => entry.path(),
+				Err(_) => continue,
+			};
+
+			if let Some((artifact_id, preparation_time)) = recognize(&path).await {
+				artifacts.insert(
+					artifact_id,
+					ArtifactState::Prepared { path, last_time_needed: now, preparation_time },
+				);
+				continue
+			}
+
+			gum::debug!(target: LOG_TARGET, "removing stale artifact {}", path.display());
+			removed += 1;
+			if path.is_dir().await {
+				let _ = async_std::fs::remove_dir_all(&path).await;
+			} else {
+				let _ = async_std::fs::remove_file(&path).await;
+			}
+		}
+
+		gum::info!(
+			target: LOG_TARGET,
+			"picked up {} prepared artifacts, removed {} stale ones",
+			artifacts.len(),
+			removed,
+		);
+
+		Self { artifacts }
 	}
 
 	#[cfg(test)]
@@ -162,16 +266,29 @@ impl Artifacts {
 	/// This function must be used only for brand-new artifacts and should never be used for
 	/// replacing existing ones.
 	#[cfg(test)]
-	pub fn insert_prepared(&mut self, artifact_id: ArtifactId, last_time_needed: SystemTime) {
+	pub fn insert_prepared(
+		&mut self,
+		artifact_id: ArtifactId,
+		path: PathBuf,
+		last_time_needed: SystemTime,
+	) {
 		// See the precondition.
 		always!(self
 			.artifacts
-			.insert(artifact_id, ArtifactState::Prepared { last_time_needed })
+			.insert(
+				artifact_id,
+				ArtifactState::Prepared {
+					path,
+					last_time_needed,
+					preparation_time: Duration::ZERO,
+				}
+			)
 			.is_none());
 	}
 
-	/// Remove and retrieve the artifacts from the table that are older than the supplied Time-To-Live.
-	pub fn prune(&mut self, artifact_ttl: Duration) -> Vec<ArtifactId> {
+	/// Remove and retrieve the artifacts from the table that are older than the supplied Time-To-Live,
+	/// along with their paths.
+	pub fn prune(&mut self, artifact_ttl: Duration) -> Vec<(ArtifactId, PathBuf)> {
 		let now = SystemTime::now();
 
 		let mut to_remove = vec![];
@@ -187,37 +304,98 @@ impl Artifacts {
 			}
 		}
 
-		for artifact in &to_remove {
-			self.artifacts.remove(artifact);
-		}
-
 		to_remove
+			.into_iter()
+			.filter_map(|artifact_id| match self.artifacts.remove(&artifact_id) {
+				Some(ArtifactState::Prepared { path, .. }) => Some((artifact_id, path)),
+				_ => None,
+			})
+			.collect()
+	}
+}
+
+/// Returns the ID of the artifact at the given path and how long its preparation took if it can be
+/// picked up, i.e. it was prepared by this version of the node with the current executor
+/// parameters and is not corrupted.
+async fn recognize(path: &Path) -> Option<(ArtifactId, Duration)> {
+	if !path.is_file().await {
+		return None
+	}
+
+	let (artifact_id, checksum, preparation_time) =
+		ArtifactId::from_file_name(path.file_name()?.to_str()?)?;
+	if artifact_id.executor_params_hash != executor_params_hash() {
+		return None
 	}
+
+	let contents = async_std::fs::read(path).await.ok()?;
+	if compute_checksum(&contents) != checksum {
+		gum::warn!(target: LOG_TARGET, "checksum mismatch for artifact {}", path.display());
+		return None
+	}
+
+	Some((artifact_id, preparation_time))
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{ArtifactId, Artifacts};
-	use async_std::path::Path;
+	use super::{compute_checksum, ArtifactId, ArtifactState, Artifacts, NODE_VERSION};
+	use crate::executor_intf::executor_params_hash;
+	use async_std::path::{Path, PathBuf};
 	use sp_core::H256;
-	use std::str::FromStr;
+	use std::{str::FromStr, time::Duration};
+
+	fn fake_cache_path() -> PathBuf {
+		async_std::task::block_on(async move {
+			crate::worker_common::tmpfile("test-cache").await.unwrap()
+		})
+	}
 
 	#[test]
 	fn from_file_name() {
 		assert!(ArtifactId::from_file_name("").is_none());
 		assert!(ArtifactId::from_file_name("junk").is_none());
 
+		// The file name of an artifact prepared by this crate before the checksums were introduced.
+		assert!(ArtifactId::from_file_name(
+			"wasmtime_0x0022800000000000000000000000000000000000000000000000000000000000"
+		)
+		.is_none());
+
+		let file_name = format!(
+			"wasmtime_v{}_0x{}_0x{}_0x{}_1500ms",
+			NODE_VERSION,
+			"0022800000000000000000000000000000000000000000000000000000000000",
+			"0033900000000000000000000000000000000000000000000000000000000000",
+			"0044a00000000000000000000000000000000000000000000000000000000000",
+		);
 		assert_eq!(
-			ArtifactId::from_file_name(
-				"wasmtime_0x0022800000000000000000000000000000000000000000000000000000000000"
-			),
-			Some(ArtifactId::new(
+			ArtifactId::from_file_name(&file_name),
+			Some((
+				ArtifactId::new(
+					hex_literal::hex![
+						"0022800000000000000000000000000000000000000000000000000000000000"
+					]
+					.into(),
+					hex_literal::hex![
+						"0033900000000000000000000000000000000000000000000000000000000000"
+					]
+					.into(),
+				),
 				hex_literal::hex![
-					"0022800000000000000000000000000000000000000000000000000000000000"
+					"0044a00000000000000000000000000000000000000000000000000000000000"
 				]
-				.into()
+				.into(),
+				Duration::from_millis(1500),
 			)),
 		);
+
+		// An artifact prepared before the preparation time was recorded.
+		assert!(ArtifactId::from_file_name(file_name.strip_suffix("_1500ms").unwrap()).is_none());
+
+		// Another version of the node.
+		let file_name = file_name.replacen(NODE_VERSION, "0.0.0-other", 1);
+		assert!(ArtifactId::from_file_name(&file_name).is_none());
 	}
 
 	#[test]
@@ -225,22 +403,37 @@ mod tests {
 		let path = Path::new("/test");
 		let hash =
 			H256::from_str("1234567890123456789012345678901234567890123456789012345678901234")
-				.unwrap()
-				.into();
+				.unwrap();
+		let artifact_id = ArtifactId::new(hash.into(), H256::repeat_byte(0x01));
+		let checksum = H256::repeat_byte(0x02);
+		let preparation_time = Duration::from_millis(42);
 
 		assert_eq!(
-			ArtifactId::new(hash).path(path).to_str(),
-			Some(
-				"/test/wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234"
+			artifact_id.path(path, &checksum, preparation_time).to_str().unwrap(),
+			format!(
+				"/test/wasmtime_v{}_0x{}_0x{}_0x{}_42ms",
+				NODE_VERSION,
+				"1234567890123456789012345678901234567890123456789012345678901234",
+				"01".repeat(32),
+				"02".repeat(32),
 			),
 		);
+		assert_eq!(
+			ArtifactId::from_file_name(
+				artifact_id
+					.path(path, &checksum, preparation_time)
+					.file_name()
+					.unwrap()
+					.to_str()
+					.unwrap()
+			),
+			Some((artifact_id, checksum, preparation_time)),
+		);
 	}
 
 	#[test]
-	fn artifacts_removes_cache_on_startup() {
-		let fake_cache_path = async_std::task::block_on(async move {
-			crate::worker_common::tmpfile("test-cache").await.unwrap()
-		});
+	fn artifacts_removes_stale_cache_on_startup() {
+		let fake_cache_path = fake_cache_path();
 		let fake_artifact_path = {
 			let mut p = fake_cache_path.clone();
 			p.push("wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234");
@@ -261,4 +454,54 @@ mod tests {
 
 		std::fs::remove_dir_all(fake_cache_path).unwrap();
 	}
+
+	#[test]
+	fn artifacts_are_picked_up_on_startup() {
+		let fake_cache_path = fake_cache_path();
+		std::fs::create_dir_all(&fake_cache_path).unwrap();
+
+		let contents = b"compiled code".to_vec();
+		let checksum = compute_checksum(&contents);
+
+		// A valid artifact.
+		let valid_id = ArtifactId::new(H256::repeat_byte(0x01).into(), executor_params_hash());
+		let preparation_time = Duration::from_secs(2);
+		let valid_path = valid_id.path(&fake_cache_path, &checksum, preparation_time);
+		std::fs::write(&valid_path, &contents).unwrap();
+
+		// An artifact prepared with other executor parameters.
+		let other_params_id = ArtifactId::new(H256::repeat_byte(0x02).into(), H256::zero());
+		std::fs::write(
+			other_params_id.path(&fake_cache_path, &checksum, preparation_time),
+			&contents,
+		)
+		.unwrap();
+
+		// A corrupted artifact.
+		let corrupted_id = ArtifactId::new(H256::repeat_byte(0x03).into(), executor_params_hash());
+		std::fs::write(
+			corrupted_id.path(&fake_cache_path, &checksum, preparation_time),
+			b"corrupted",
+		)
+		.unwrap();
+
+		let p = &fake_cache_path;
+		let mut artifacts = async_std::task::block_on(async { Artifacts::new(p).await });
+
+		assert!(matches!(
+			artifacts.artifact_state_mut(&valid_id),
+			Some(ArtifactState::Prepared { path, preparation_time: time, .. })
+				if *path == valid_path && *time == preparation_time
+		));
+		assert!(artifacts.artifact_state_mut(&other_params_id).is_none());
+		assert!(artifacts.artifact_state_mut(&corrupted_id).is_none());
+
+		let remaining: Vec<_> = std::fs::read_dir(&fake_cache_path)
+			.unwrap()
+			.map(|entry| PathBuf::from(entry.unwrap().path()))
+			.collect();
+		assert_eq!(remaining, vec![valid_path]);
+
+		std::fs::remove_dir_all(fake_cache_path).unwrap();
+	}
 }
@@ -15,6 +15,10 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 //! A queue that handles requests for PVF execution.
+//!
+//! The jobs which wait for a worker are picked by the priority of their [kind][`PvfExecKind`]: the
+//! ones for disputes first, then the ones for approvals and only then the ones for backing. Jobs of
+//! the same kind are picked in the order of their arrival.
 
 use super::worker::Outcome;
 use crate::{
@@ -31,8 +35,13 @@ use futures::{
 	stream::{FuturesUnordered, StreamExt as _},
 	Future, FutureExt,
 };
+use polkadot_node_primitives::PvfExecKind;
 use slotmap::HopSlotMap;
-use std::{collections::VecDeque, fmt, time::Duration};
+use std::{
+	collections::{BTreeMap, VecDeque},
+	fmt,
+	time::Duration,
+};
 
 slotmap::new_key_type! { struct Worker; }
 
@@ -40,6 +49,7 @@ slotmap::new_key_type! { struct Worker; }
 pub enum ToQueue {
 	Enqueue {
 		artifact: ArtifactPathId,
+		exec_kind: PvfExecKind,
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		result_tx: ResultSender,
@@ -48,11 +58,31 @@ pub enum ToQueue {
 
 struct ExecuteJob {
 	artifact: ArtifactPathId,
+	exec_kind: PvfExecKind,
 	execution_timeout: Duration,
 	params: Vec<u8>,
 	result_tx: ResultSender,
 }
 
+/// The jobs which are waiting for a worker to pick them up.
+#[derive(Default)]
+struct Unscheduled(BTreeMap<PvfExecKind, VecDeque<ExecuteJob>>);
+
+impl Unscheduled {
+	fn add(&mut self, job: ExecuteJob) {
+		self.0.entry(job.exec_kind).or_default().push_back(job);
+	}
+
+	/// Take the earliest job of the kind with the highest priority.
+	fn next(&mut self) -> Option<ExecuteJob> {
+		self.0.values_mut().rev().find_map(|jobs| jobs.pop_front())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.0.values().all(|jobs| jobs.is_empty())
+	}
+}
+
 struct WorkerData {
 	idle: Option<IdleWorker>,
 	handle: WorkerHandle,
@@ -111,7 +141,7 @@ struct Queue {
 	spawn_timeout: Duration,
 
 	/// The queue of jobs that are waiting for a worker to pick up.
-	queue: VecDeque<ExecuteJob>,
+	unscheduled: Unscheduled,
 	workers: Workers,
 	mux: Mux,
 }
@@ -129,7 +159,7 @@ impl Queue {
 			program_path,
 			spawn_timeout,
 			to_queue_rx,
-			queue: VecDeque::new(),
+			unscheduled: Unscheduled::default(),
 			mux: Mux::new(),
 			workers: Workers {
 				running: HopSlotMap::with_capacity_and_key(10),
@@ -173,14 +203,15 @@ async fn purge_dead(metrics: &Metrics, workers: &mut Workers) {
 }
 
 fn handle_to_queue(queue: &mut Queue, to_queue: ToQueue) {
-	let ToQueue::Enqueue { artifact, execution_timeout, params, result_tx } = to_queue;
+	let ToQueue::Enqueue { artifact, exec_kind, execution_timeout, params, result_tx } = to_queue;
 	gum::debug!(
 		target: LOG_TARGET,
 		validation_code_hash = ?artifact.id.code_hash,
+		?exec_kind,
 		"enqueueing an artifact for execution",
 	);
 	queue.metrics.execute_enqueued();
-	let job = ExecuteJob { artifact, execution_timeout, params, result_tx };
+	let job = ExecuteJob { artifact, exec_kind, execution_timeout, params, result_tx };
 
 	if let Some(available) = queue.workers.find_available() {
 		assign(queue, available, job);
@@ -188,7 +219,7 @@ fn handle_to_queue(queue: &mut Queue, to_queue: ToQueue) {
 		if queue.workers.can_afford_one_more() {
 			spawn_extra_worker(queue);
 		}
-		queue.queue.push_back(job);
+		queue.unscheduled.add(job);
 	}
 }
 
@@ -210,7 +241,7 @@ fn handle_worker_spawned(queue: &mut Queue, idle: IdleWorker, handle: WorkerHand
 
 	gum::debug!(target: LOG_TARGET, ?worker, "execute worker spawned");
 
-	if let Some(job) = queue.queue.pop_front() {
+	if let Some(job) = queue.unscheduled.next() {
 		assign(queue, worker, job);
 	}
 }
@@ -267,7 +298,7 @@ fn handle_job_finish(
 		if let Some(data) = queue.workers.running.get_mut(worker) {
 			data.idle = Some(idle_worker);
 
-			if let Some(job) = queue.queue.pop_front() {
+			if let Some(job) = queue.unscheduled.next() {
 				assign(queue, worker, job);
 			}
 		}
@@ -277,7 +308,7 @@ fn handle_job_finish(
 			queue.metrics.execute_worker().on_retired();
 		}
 
-		if !queue.queue.is_empty() {
+		if !queue.unscheduled.is_empty() {
 			// The worker has died and we still have work we have to do. Request an extra worker.
 			//
 			// That can potentially overshoot, but that should be OK.
@@ -319,6 +350,7 @@ fn assign(queue: &mut Queue, worker: Worker, job: ExecuteJob) {
 	gum::debug!(
 		target: LOG_TARGET,
 		validation_code_hash = ?job.artifact.id,
+		exec_kind = ?job.exec_kind,
 		?worker,
 		"assigning the execute worker",
 	);
@@ -355,3 +387,53 @@ pub fn start(
 	let run = Queue::new(metrics, program_path, worker_capacity, spawn_timeout, to_queue_rx).run();
 	(to_queue_tx, run)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Pvf;
+	use async_std::path::PathBuf;
+	use futures::channel::oneshot;
+
+	// The parameters of a job are used to tell it apart.
+	fn job(id: u8, exec_kind: PvfExecKind) -> ExecuteJob {
+		let artifact_id = Pvf::from_discriminator(id as u32).as_artifact_id();
+		ExecuteJob {
+			artifact: ArtifactPathId::new(artifact_id, PathBuf::from(std::env::temp_dir())),
+			exec_kind,
+			execution_timeout: Duration::from_secs(1),
+			params: vec![id],
+			result_tx: oneshot::channel().0,
+		}
+	}
+
+	fn drain(unscheduled: &mut Unscheduled) -> Vec<u8> {
+		std::iter::from_fn(|| unscheduled.next()).map(|job| job.params[0]).collect()
+	}
+
+	#[test]
+	fn jobs_of_the_same_kind_are_picked_in_order() {
+		let mut unscheduled = Unscheduled::default();
+		for id in 0..3 {
+			unscheduled.add(job(id, PvfExecKind::Backing));
+		}
+
+		assert_eq!(drain(&mut unscheduled), vec![0, 1, 2]);
+		assert!(unscheduled.is_empty());
+	}
+
+	#[test]
+	fn backing_does_not_starve_approvals_and_disputes() {
+		let mut unscheduled = Unscheduled::default();
+		unscheduled.add(job(0, PvfExecKind::Backing));
+		unscheduled.add(job(1, PvfExecKind::Backing));
+		unscheduled.add(job(2, PvfExecKind::Approval));
+		unscheduled.add(job(3, PvfExecKind::Backing));
+		unscheduled.add(job(4, PvfExecKind::Dispute));
+		unscheduled.add(job(5, PvfExecKind::Approval));
+
+		assert!(!unscheduled.is_empty());
+		assert_eq!(drain(&mut unscheduled), vec![4, 2, 5, 0, 1, 3]);
+		assert!(unscheduled.is_empty());
+	}
+}
@@ -16,12 +16,17 @@
 
 //! Interface to the Substrate Executor
 
+use parity_scale_codec::Encode as _;
+use polkadot_core_primitives::Hash;
 use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{InvokeMethod, WasmModule as _},
 };
 use sc_executor_wasmtime::{Config, DeterministicStackLimit, Semantics};
-use sp_core::storage::{ChildInfo, TrackedStorageKey};
+use sp_core::{
+	blake2_256,
+	storage::{ChildInfo, TrackedStorageKey},
+};
 use std::any::{Any, TypeId};
 
 // Memory configuration
@@ -76,6 +81,27 @@ const CONFIG: Config = Config {
 	},
 };
 
+/// Returns the hash of the executor parameters that affect the compiled artifacts.
+///
+/// It is a part of the artifact file names, so that the artifacts prepared with different
+/// parameters are not picked up after a restart.
+pub fn executor_params_hash() -> Hash {
+	let semantics = &CONFIG.semantics;
+	let params = (
+		CONFIG.max_memory_size.map(|size| size as u64),
+		CONFIG.allow_missing_func_imports,
+		semantics.extra_heap_pages,
+		semantics.fast_instance_reuse,
+		semantics
+			.deterministic_stack_limit
+			.as_ref()
+			.map(|limit| (limit.logical_max, limit.native_stack_max)),
+		semantics.canonicalize_nans,
+		semantics.parallel_compilation,
+	);
+	blake2_256(&params.encode()).into()
+}
+
 /// Runs the prevalidation on the given code. Returns a [`RuntimeBlob`] if it succeeds.
 pub fn prevalidate(code: &[u8]) -> Result<RuntimeBlob, sc_executor_common::error::WasmError> {
 	let blob = RuntimeBlob::new(code)?;
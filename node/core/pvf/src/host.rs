@@ -24,20 +24,37 @@ use crate::{
 	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts},
 	execute,
 	metrics::Metrics,
-	prepare, PrepareResult, Priority, Pvf, ValidationError, LOG_TARGET,
+	prepare, PrepareError, PrepareResult, Priority, Pvf, ValidationError, LOG_TARGET,
 };
 use always_assert::never;
-use async_std::path::{Path, PathBuf};
+use async_std::path::PathBuf;
 use futures::{
 	channel::{mpsc, oneshot},
 	Future, FutureExt, SinkExt, StreamExt,
 };
+use polkadot_node_primitives::{
+	PvfExecKind, APPROVAL_EXECUTION_TIMEOUT, BACKING_EXECUTION_TIMEOUT,
+};
 use polkadot_parachain::primitives::ValidationResult;
 use std::{
 	collections::HashMap,
 	time::{Duration, SystemTime},
 };
 
+/// The time after which the preparation of a PVF requested for pre-checking is abandoned.
+///
+/// Pre-checking decides whether the PVF is accepted on-chain, so it must be strict to keep the
+/// outcome the same across validators.
+// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
+pub const PRECHECK_PREPARATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The time after which the preparation of a PVF requested for execution is abandoned.
+///
+/// The PVF has already passed pre-checking at this point, so it is given more leeway to not fail
+/// candidates only because the machine was under load.
+// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
+pub const LENIENT_PREPARATION_TIMEOUT: Duration = Duration::from_secs(360);
+
 /// An alias to not spell the type for the oneshot sender for the PVF execution result.
 pub(crate) type ResultSender = oneshot::Sender<Result<ValidationResult, ValidationError>>;
 
@@ -69,7 +86,8 @@ impl ValidationHost {
 			.map_err(|_| "the inner loop hung up".to_string())
 	}
 
-	/// Execute PVF with the given code, execution timeout, parameters and priority.
+	/// Execute PVF with the given code, execution timeout, parameters and priority. The kind of
+	/// the execution determines the order in which it is picked up by the execute workers.
 	/// The result of execution will be sent to the provided result sender.
 	///
 	/// This is async to accommodate the fact a possibility of back-pressure. In the vast majority of
@@ -79,13 +97,21 @@ impl ValidationHost {
 	pub async fn execute_pvf(
 		&mut self,
 		pvf: Pvf,
+		exec_kind: PvfExecKind,
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		priority: Priority,
 		result_tx: ResultSender,
 	) -> Result<(), String> {
 		self.to_host_tx
-			.send(ToHost::ExecutePvf { pvf, execution_timeout, params, priority, result_tx })
+			.send(ToHost::ExecutePvf {
+				pvf,
+				exec_kind,
+				execution_timeout,
+				params,
+				priority,
+				result_tx,
+			})
 			.await
 			.map_err(|_| "the inner loop hung up".to_string())
 	}
@@ -111,6 +137,7 @@ enum ToHost {
 	},
 	ExecutePvf {
 		pvf: Pvf,
+		exec_kind: PvfExecKind,
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		priority: Priority,
@@ -121,6 +148,39 @@ enum ToHost {
 	},
 }
 
+/// The time allotted to the execution of a PVF, depending on its [kind][`PvfExecKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionTimeouts {
+	/// The timeout for the executions done while backing a candidate.
+	pub backing: Duration,
+	/// The timeout for the executions done while approving a candidate.
+	pub approval: Duration,
+	/// The timeout for the executions done while participating in a dispute.
+	pub dispute: Duration,
+}
+
+impl ExecutionTimeouts {
+	/// Returns the timeout for the executions of the given kind.
+	pub fn get(&self, exec_kind: PvfExecKind) -> Duration {
+		match exec_kind {
+			PvfExecKind::Backing => self.backing,
+			PvfExecKind::Approval => self.approval,
+			PvfExecKind::Dispute => self.dispute,
+		}
+	}
+}
+
+impl Default for ExecutionTimeouts {
+	fn default() -> Self {
+		Self {
+			backing: BACKING_EXECUTION_TIMEOUT,
+			approval: APPROVAL_EXECUTION_TIMEOUT,
+			// Disputes are run outside of backing too, so give them the same leeway as approvals.
+			dispute: APPROVAL_EXECUTION_TIMEOUT,
+		}
+	}
+}
+
 /// Configuration for the validation host.
 pub struct Config {
 	/// The root directory where the prepared artifacts can be stored.
@@ -186,7 +246,6 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 		metrics.clone(),
 		config.prepare_workers_soft_max_num,
 		config.prepare_workers_hard_max_num,
-		config.cache_path.clone(),
 		to_prepare_pool,
 		from_prepare_pool,
 	);
@@ -208,7 +267,6 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 
 		run(
 			Inner {
-				cache_path: config.cache_path,
 				cleanup_pulse_interval: Duration::from_secs(3600),
 				artifact_ttl: Duration::from_secs(3600 * 24),
 				artifacts,
@@ -234,6 +292,7 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 /// to the given result sender.
 #[derive(Debug)]
 struct PendingExecutionRequest {
+	exec_kind: PvfExecKind,
 	execution_timeout: Duration,
 	params: Vec<u8>,
 	result_tx: ResultSender,
@@ -248,11 +307,13 @@ impl AwaitingPrepare {
 	fn add(
 		&mut self,
 		artifact_id: ArtifactId,
+		exec_kind: PvfExecKind,
 		execution_timeout: Duration,
 		params: Vec<u8>,
 		result_tx: ResultSender,
 	) {
 		self.0.entry(artifact_id).or_default().push(PendingExecutionRequest {
+			exec_kind,
 			execution_timeout,
 			params,
 			result_tx,
@@ -265,7 +326,6 @@ impl AwaitingPrepare {
 }
 
 struct Inner {
-	cache_path: PathBuf,
 	cleanup_pulse_interval: Duration,
 	artifact_ttl: Duration,
 	artifacts: Artifacts,
@@ -286,7 +346,6 @@ struct Fatal;
 
 async fn run(
 	Inner {
-		cache_path,
 		cleanup_pulse_interval,
 		artifact_ttl,
 		mut artifacts,
@@ -349,7 +408,6 @@ async fn run(
 				// will notice it.
 
 				break_if_fatal!(handle_cleanup_pulse(
-					&cache_path,
 					&mut to_sweeper_tx,
 					&mut artifacts,
 					artifact_ttl,
@@ -359,7 +417,6 @@ async fn run(
 				let to_host = break_if_fatal!(to_host.ok_or(Fatal));
 
 				break_if_fatal!(handle_to_host(
-					&cache_path,
 					&mut artifacts,
 					&mut to_prepare_queue_tx,
 					&mut to_execute_queue_tx,
@@ -381,7 +438,6 @@ async fn run(
 				// We could be eager in terms of reporting and plumb the result from the preparation
 				// worker but we don't for the sake of simplicity.
 				break_if_fatal!(handle_prepare_done(
-					&cache_path,
 					&mut artifacts,
 					&mut to_execute_queue_tx,
 					&mut awaiting_prepare,
@@ -393,7 +449,6 @@ async fn run(
 }
 
 async fn handle_to_host(
-	cache_path: &Path,
 	artifacts: &mut Artifacts,
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
@@ -404,14 +459,14 @@ async fn handle_to_host(
 		ToHost::PrecheckPvf { pvf, result_tx } => {
 			handle_precheck_pvf(artifacts, prepare_queue, pvf, result_tx).await?;
 		},
-		ToHost::ExecutePvf { pvf, execution_timeout, params, priority, result_tx } => {
+		ToHost::ExecutePvf { pvf, exec_kind, execution_timeout, params, priority, result_tx } => {
 			handle_execute_pvf(
-				cache_path,
 				artifacts,
 				prepare_queue,
 				execute_queue,
 				awaiting_prepare,
 				pvf,
+				exec_kind,
 				execution_timeout,
 				params,
 				priority,
@@ -437,9 +492,9 @@ async fn handle_precheck_pvf(
 
 	if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
 		match state {
-			ArtifactState::Prepared { last_time_needed } => {
+			ArtifactState::Prepared { last_time_needed, preparation_time, .. } => {
 				*last_time_needed = SystemTime::now();
-				let _ = result_sender.send(Ok(()));
+				let _ = result_sender.send(precheck_outcome(*preparation_time));
 			},
 			ArtifactState::Preparing { waiting_for_response } =>
 				waiting_for_response.push(result_sender),
@@ -449,19 +504,39 @@ async fn handle_precheck_pvf(
 		}
 	} else {
 		artifacts.insert_preparing(artifact_id, vec![result_sender]);
-		send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority: Priority::Normal, pvf })
-			.await?;
+		send_prepare(
+			prepare_queue,
+			prepare::ToQueue::Enqueue {
+				priority: Priority::Normal,
+				pvf,
+				preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+			},
+		)
+		.await?;
 	}
 	Ok(())
 }
 
+/// The outcome of a pre-check of a PVF which was prepared successfully within `preparation_time`.
+///
+/// The preparation may have run with the lenient timeout, so the pre-check only passes if it would
+/// have also been done within the strict one. The same holds for artifacts discovered on disk,
+/// whose preparation time is recorded in their file name.
+fn precheck_outcome(preparation_time: Duration) -> PrepareResult {
+	if preparation_time > PRECHECK_PREPARATION_TIMEOUT {
+		Err(PrepareError::TimedOut)
+	} else {
+		Ok(())
+	}
+}
+
 async fn handle_execute_pvf(
-	cache_path: &Path,
 	artifacts: &mut Artifacts,
 	prepare_queue: &mut mpsc::Sender<prepare::ToQueue>,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	awaiting_prepare: &mut AwaitingPrepare,
 	pvf: Pvf,
+	exec_kind: PvfExecKind,
 	execution_timeout: Duration,
 	params: Vec<u8>,
 	priority: Priority,
@@ -471,13 +546,14 @@ async fn handle_execute_pvf(
 
 	if let Some(state) = artifacts.artifact_state_mut(&artifact_id) {
 		match state {
-			ArtifactState::Prepared { ref mut last_time_needed } => {
+			ArtifactState::Prepared { path, last_time_needed, .. } => {
 				*last_time_needed = SystemTime::now();
 
 				send_execute(
 					execute_queue,
 					execute::ToQueue::Enqueue {
-						artifact: ArtifactPathId::new(artifact_id, cache_path),
+						artifact: ArtifactPathId::new(artifact_id, path.clone()),
+						exec_kind,
 						execution_timeout,
 						params,
 						result_tx,
@@ -486,7 +562,7 @@ async fn handle_execute_pvf(
 				.await?;
 			},
 			ArtifactState::Preparing { waiting_for_response: _ } => {
-				awaiting_prepare.add(artifact_id, execution_timeout, params, result_tx);
+				awaiting_prepare.add(artifact_id, exec_kind, execution_timeout, params, result_tx);
 			},
 			ArtifactState::FailedToProcess(error) => {
 				let _ = result_tx.send(Err(ValidationError::from(error.clone())));
@@ -496,9 +572,17 @@ async fn handle_execute_pvf(
 		// Artifact is unknown: register it and enqueue a job with the corresponding priority and
 		//
 		artifacts.insert_preparing(artifact_id.clone(), Vec::new());
-		send_prepare(prepare_queue, prepare::ToQueue::Enqueue { priority, pvf }).await?;
+		send_prepare(
+			prepare_queue,
+			prepare::ToQueue::Enqueue {
+				priority,
+				pvf,
+				preparation_timeout: LENIENT_PREPARATION_TIMEOUT,
+			},
+		)
+		.await?;
 
-		awaiting_prepare.add(artifact_id, execution_timeout, params, result_tx);
+		awaiting_prepare.add(artifact_id, exec_kind, execution_timeout, params, result_tx);
 	}
 
 	return Ok(())
@@ -529,7 +613,11 @@ async fn handle_heads_up(
 
 			send_prepare(
 				prepare_queue,
-				prepare::ToQueue::Enqueue { priority: Priority::Normal, pvf: active_pvf },
+				prepare::ToQueue::Enqueue {
+					priority: Priority::Normal,
+					pvf: active_pvf,
+					preparation_timeout: LENIENT_PREPARATION_TIMEOUT,
+				},
 			)
 			.await?;
 		}
@@ -539,13 +627,12 @@ async fn handle_heads_up(
 }
 
 async fn handle_prepare_done(
-	cache_path: &Path,
 	artifacts: &mut Artifacts,
 	execute_queue: &mut mpsc::Sender<execute::ToQueue>,
 	awaiting_prepare: &mut AwaitingPrepare,
 	from_queue: prepare::FromQueue,
 ) -> Result<(), Fatal> {
-	let prepare::FromQueue { artifact_id, result } = from_queue;
+	let prepare::FromQueue { artifact_id, result, preparation_time } = from_queue;
 
 	// Make some sanity checks and extract the current state.
 	let state = match artifacts.artifact_state_mut(&artifact_id) {
@@ -578,14 +665,17 @@ async fn handle_prepare_done(
 
 	if let ArtifactState::Preparing { waiting_for_response } = state {
 		for result_sender in waiting_for_response.drain(..) {
-			let _ = result_sender.send(result.clone());
+			let _ =
+				result_sender.send(result.clone().and_then(|_| precheck_outcome(preparation_time)));
 		}
 	}
 
 	// It's finally time to dispatch all the execution requests that were waiting for this artifact
 	// to be prepared.
 	let pending_requests = awaiting_prepare.take(&artifact_id);
-	for PendingExecutionRequest { execution_timeout, params, result_tx } in pending_requests {
+	for PendingExecutionRequest { exec_kind, execution_timeout, params, result_tx } in
+		pending_requests
+	{
 		if result_tx.is_canceled() {
 			// Preparation could've taken quite a bit of time and the requester may be not interested
 			// in execution anymore, in which case we just skip the request.
@@ -593,15 +683,19 @@ async fn handle_prepare_done(
 		}
 
 		// Don't send failed artifacts to the execution's queue.
-		if let Err(ref error) = result {
-			let _ = result_tx.send(Err(ValidationError::from(error.clone())));
-			continue
-		}
+		let artifact_path = match result {
+			Ok(ref artifact_path) => artifact_path.clone(),
+			Err(ref error) => {
+				let _ = result_tx.send(Err(ValidationError::from(error.clone())));
+				continue
+			},
+		};
 
 		send_execute(
 			execute_queue,
 			execute::ToQueue::Enqueue {
-				artifact: ArtifactPathId::new(artifact_id.clone(), cache_path),
+				artifact: ArtifactPathId::new(artifact_id.clone(), artifact_path),
+				exec_kind,
 				execution_timeout,
 				params,
 				result_tx,
@@ -611,8 +705,9 @@ async fn handle_prepare_done(
 	}
 
 	*state = match result {
-		Ok(()) => ArtifactState::Prepared { last_time_needed: SystemTime::now() },
-		Err(error) => ArtifactState::FailedToProcess(error.clone()),
+		Ok(path) =>
+			ArtifactState::Prepared { path, last_time_needed: SystemTime::now(), preparation_time },
+		Err(error) => ArtifactState::FailedToProcess(error),
 	};
 
 	Ok(())
@@ -633,7 +728,6 @@ async fn send_execute(
 }
 
 async fn handle_cleanup_pulse(
-	cache_path: &Path,
 	sweeper_tx: &mut mpsc::Sender<PathBuf>,
 	artifacts: &mut Artifacts,
 	artifact_ttl: Duration,
@@ -644,13 +738,12 @@ async fn handle_cleanup_pulse(
 		"PVF pruning: {} artifacts reached their end of life",
 		to_remove.len(),
 	);
-	for artifact_id in to_remove {
+	for (artifact_id, artifact_path) in to_remove {
 		gum::debug!(
 			target: LOG_TARGET,
 			validation_code_hash = ?artifact_id.code_hash,
 			"pruning artifact",
 		);
-		let artifact_path = artifact_id.path(cache_path);
 		sweeper_tx.send(artifact_path).await.map_err(|_| Fatal)?;
 	}
 
@@ -715,7 +808,11 @@ mod tests {
 	}
 
 	fn artifact_path(descriminator: u32) -> PathBuf {
-		artifact_id(descriminator).path(&PathBuf::from(std::env::temp_dir())).to_owned()
+		artifact_id(descriminator).path(
+			&PathBuf::from(std::env::temp_dir()),
+			&Default::default(),
+			Duration::ZERO,
+		)
 	}
 
 	struct Builder {
@@ -753,8 +850,6 @@ mod tests {
 
 	impl Test {
 		fn new(Builder { cleanup_pulse_interval, artifact_ttl, artifacts }: Builder) -> Self {
-			let cache_path = PathBuf::from(std::env::temp_dir());
-
 			let (to_host_tx, to_host_rx) = mpsc::channel(10);
 			let (to_prepare_queue_tx, to_prepare_queue_rx) = mpsc::channel(10);
 			let (from_prepare_queue_tx, from_prepare_queue_rx) = mpsc::unbounded();
@@ -765,7 +860,6 @@ mod tests {
 
 			let run = run(
 				Inner {
-					cache_path,
 					cleanup_pulse_interval,
 					artifact_ttl,
 					artifacts,
@@ -893,8 +987,8 @@ mod tests {
 		let mut builder = Builder::default();
 		builder.cleanup_pulse_interval = Duration::from_millis(100);
 		builder.artifact_ttl = Duration::from_millis(500);
-		builder.artifacts.insert_prepared(artifact_id(1), mock_now);
-		builder.artifacts.insert_prepared(artifact_id(2), mock_now);
+		builder.artifacts.insert_prepared(artifact_id(1), artifact_path(1), mock_now);
+		builder.artifacts.insert_prepared(artifact_id(2), artifact_path(2), mock_now);
 		let mut test = builder.build();
 		let mut host = test.host_handle();
 
@@ -916,6 +1010,40 @@ mod tests {
 		test.poll_ensure_to_sweeper_is_empty().await;
 	}
 
+	#[async_std::test]
+	async fn execution_timeout_is_passed_to_the_queue() {
+		let mut builder = Builder::default();
+		builder
+			.artifacts
+			.insert_prepared(artifact_id(1), artifact_path(1), SystemTime::now());
+		let mut test = builder.build();
+		let mut host = test.host_handle();
+		let timeouts = ExecutionTimeouts::default();
+
+		for exec_kind in [PvfExecKind::Backing, PvfExecKind::Approval, PvfExecKind::Dispute] {
+			let (result_tx, _result_rx) = oneshot::channel();
+			host.execute_pvf(
+				Pvf::from_discriminator(1),
+				exec_kind,
+				timeouts.get(exec_kind),
+				b"pvf1".to_vec(),
+				Priority::Normal,
+				result_tx,
+			)
+			.await
+			.unwrap();
+
+			assert_matches!(
+				test.poll_and_recv_to_execute_queue().await,
+				execute::ToQueue::Enqueue { artifact, exec_kind: kind, execution_timeout, .. } => {
+					assert_eq!(artifact.path, artifact_path(1));
+					assert_eq!(kind, exec_kind);
+					assert_eq!(execution_timeout, timeouts.get(exec_kind));
+				}
+			);
+		}
+	}
+
 	#[async_std::test]
 	async fn execute_pvf_requests() {
 		let mut test = Builder::default().build();
@@ -924,6 +1052,7 @@ mod tests {
 		let (result_tx, result_rx_pvf_1_1) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(1),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Normal,
@@ -935,6 +1064,7 @@ mod tests {
 		let (result_tx, result_rx_pvf_1_2) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(1),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Critical,
@@ -946,6 +1076,7 @@ mod tests {
 		let (result_tx, result_rx_pvf_2) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(2),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Normal,
@@ -964,7 +1095,11 @@ mod tests {
 		);
 
 		test.from_prepare_queue_tx
-			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(1),
+				result: Ok(artifact_path(1)),
+				preparation_time: Duration::ZERO,
+			})
 			.await
 			.unwrap();
 		let result_tx_pvf_1_1 = assert_matches!(
@@ -977,7 +1112,11 @@ mod tests {
 		);
 
 		test.from_prepare_queue_tx
-			.send(prepare::FromQueue { artifact_id: artifact_id(2), result: Ok(()) })
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(2),
+				result: Ok(artifact_path(2)),
+				preparation_time: Duration::ZERO,
+			})
 			.await
 			.unwrap();
 		let result_tx_pvf_2 = assert_matches!(
@@ -1019,14 +1158,20 @@ mod tests {
 		let (result_tx, result_rx) = oneshot::channel();
 		host.precheck_pvf(Pvf::from_discriminator(1), result_tx).await.unwrap();
 
-		// The queue received the prepare request.
+		// The queue received the prepare request with the strict timeout.
 		assert_matches!(
 			test.poll_and_recv_to_prepare_queue().await,
-			prepare::ToQueue::Enqueue { .. }
+			prepare::ToQueue::Enqueue { preparation_timeout, .. } => {
+				assert_eq!(preparation_timeout, PRECHECK_PREPARATION_TIMEOUT);
+			}
 		);
 		// Send `Ok` right away and poll the host.
 		test.from_prepare_queue_tx
-			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(1),
+				result: Ok(artifact_path(1)),
+				preparation_time: Duration::ZERO,
+			})
 			.await
 			.unwrap();
 		// No pending execute requests.
@@ -1050,6 +1195,7 @@ mod tests {
 			.send(prepare::FromQueue {
 				artifact_id: artifact_id(2),
 				result: Err(PrepareError::TimedOut),
+				preparation_time: PRECHECK_PREPARATION_TIMEOUT,
 			})
 			.await
 			.unwrap();
@@ -1074,6 +1220,7 @@ mod tests {
 		let (result_tx, result_rx_execute) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(1),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Critical,
@@ -1082,9 +1229,12 @@ mod tests {
 		.await
 		.unwrap();
 
+		// Preparing for the execution is lenient, even if a precheck is requested later on.
 		assert_matches!(
 			test.poll_and_recv_to_prepare_queue().await,
-			prepare::ToQueue::Enqueue { .. }
+			prepare::ToQueue::Enqueue { preparation_timeout, .. } => {
+				assert_eq!(preparation_timeout, LENIENT_PREPARATION_TIMEOUT);
+			}
 		);
 
 		let (result_tx, result_rx) = oneshot::channel();
@@ -1096,6 +1246,7 @@ mod tests {
 			.send(prepare::FromQueue {
 				artifact_id: artifact_id(1),
 				result: Err(PrepareError::TimedOut),
+				preparation_time: LENIENT_PREPARATION_TIMEOUT,
 			})
 			.await
 			.unwrap();
@@ -1106,6 +1257,46 @@ mod tests {
 			Err(ValidationError::InternalError(_))
 		);
 
+		// A lenient preparation which succeeded only after the strict timeout serves the
+		// execution, but fails the precheck, both the one which joined it and any later one.
+		let (result_tx, _result_rx_execute) = oneshot::channel();
+		host.execute_pvf(
+			Pvf::from_discriminator(3),
+			PvfExecKind::Backing,
+			TEST_EXECUTION_TIMEOUT,
+			b"pvf3".to_vec(),
+			Priority::Critical,
+			result_tx,
+		)
+		.await
+		.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_prepare_queue().await,
+			prepare::ToQueue::Enqueue { .. }
+		);
+
+		let (result_tx, result_rx) = oneshot::channel();
+		host.precheck_pvf(Pvf::from_discriminator(3), result_tx).await.unwrap();
+
+		test.from_prepare_queue_tx
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(3),
+				result: Ok(artifact_path(3)),
+				preparation_time: PRECHECK_PREPARATION_TIMEOUT + Duration::from_secs(1),
+			})
+			.await
+			.unwrap();
+		assert_matches!(
+			test.poll_and_recv_to_execute_queue().await,
+			execute::ToQueue::Enqueue { .. }
+		);
+		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Err(PrepareError::TimedOut));
+
+		let (result_tx, result_rx) = oneshot::channel();
+		host.precheck_pvf(Pvf::from_discriminator(3), result_tx).await.unwrap();
+		test.poll_ensure_to_execute_queue_is_empty().await;
+		assert_matches!(result_rx.now_or_never().unwrap().unwrap(), Err(PrepareError::TimedOut));
+
 		// Reversed case: first send multiple precheck requests, then ask for an execution.
 		let mut precheck_receivers = Vec::new();
 		for _ in 0..3 {
@@ -1117,6 +1308,7 @@ mod tests {
 		let (result_tx, _result_rx_execute) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(2),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf2".to_vec(),
 			Priority::Critical,
@@ -1130,7 +1322,11 @@ mod tests {
 			prepare::ToQueue::Enqueue { .. }
 		);
 		test.from_prepare_queue_tx
-			.send(prepare::FromQueue { artifact_id: artifact_id(2), result: Ok(()) })
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(2),
+				result: Ok(artifact_path(2)),
+				preparation_time: Duration::ZERO,
+			})
 			.await
 			.unwrap();
 		// The execute queue receives new request, preckecking is finished and we can
@@ -1152,6 +1348,7 @@ mod tests {
 		let (result_tx, result_rx) = oneshot::channel();
 		host.execute_pvf(
 			Pvf::from_discriminator(1),
+			PvfExecKind::Backing,
 			TEST_EXECUTION_TIMEOUT,
 			b"pvf1".to_vec(),
 			Priority::Normal,
@@ -1166,7 +1363,11 @@ mod tests {
 		);
 
 		test.from_prepare_queue_tx
-			.send(prepare::FromQueue { artifact_id: artifact_id(1), result: Ok(()) })
+			.send(prepare::FromQueue {
+				artifact_id: artifact_id(1),
+				result: Ok(artifact_path(1)),
+				preparation_time: Duration::ZERO,
+			})
 			.await
 			.unwrap();
 
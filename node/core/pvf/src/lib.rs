@@ -34,6 +34,12 @@
 //! All requests that depends on preparation of the same PVF are bundled together and will be executed
 //! as soon as the artifact is prepared.
 //!
+//! The prepared artifacts are kept on disk across restarts. The file name of an artifact includes the
+//! version of the node, the hash of the executor parameters, the checksum of its contents and how long
+//! its preparation took, so that pre-checks can be judged by it after a restart. On startup, the
+//! artifacts that don't match the running node or are corrupted are removed and their PVFs are
+//! prepared again once needed.
+//!
 //! # Priority
 //!
 //! PVF execution requests can specify the [priority][`Priority`] with which the given request should
@@ -46,6 +52,11 @@
 //!
 //! Priority can never go down, only up.
 //!
+//! Execution requests also carry their [kind][`polkadot_node_primitives::PvfExecKind`], i.e. whether
+//! they are done for backing, approval or a dispute, and their timeout. The requester picks the
+//! timeout by the kind, see [`ExecutionTimeouts`]. The kind also determines the order in which the
+//! waiting executions are picked up: disputes go before approvals, which go before backing.
+//!
 //! # Under the hood
 //!
 //! Under the hood, the validation host is built using a bunch of communicating processes, not
@@ -96,7 +107,10 @@ pub use error::{InvalidCandidate, PrepareError, PrepareResult, ValidationError};
 pub use priority::Priority;
 pub use pvf::Pvf;
 
-pub use host::{start, Config, ValidationHost};
+pub use host::{
+	start, Config, ExecutionTimeouts, ValidationHost, LENIENT_PREPARATION_TIMEOUT,
+	PRECHECK_PREPARATION_TIMEOUT,
+};
 pub use metrics::Metrics;
 
 pub use execute::worker_entrypoint as execute_worker_entrypoint;
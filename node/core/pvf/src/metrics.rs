@@ -155,8 +155,8 @@ impl metrics::Metrics for Metrics {
 						"Time spent in preparing PVF artifacts in seconds",
 					)
 					.buckets(vec![
-						// This is synchronized with COMPILATION_TIMEOUT=60s constant found in
-						// src/prepare/worker.rs
+						// This is synchronized with the PRECHECK_PREPARATION_TIMEOUT=60s and
+						// LENIENT_PREPARATION_TIMEOUT=360s constants found in src/host.rs
 						0.1,
 						0.5,
 						1.0,
@@ -166,6 +166,9 @@ impl metrics::Metrics for Metrics {
 						20.0,
 						30.0,
 						60.0,
+						120.0,
+						240.0,
+						360.0,
 					]),
 				)?,
 				registry,
@@ -176,8 +179,9 @@ impl metrics::Metrics for Metrics {
 						"polkadot_pvf_execution_time",
 						"Time spent in executing PVFs",
 					).buckets(vec![
-						// This is synchronized with `APPROVAL_EXECUTION_TIMEOUT`  and
-						// `BACKING_EXECUTION_TIMEOUT` constants in `node/primitives/src/lib.rs`
+						// This is synchronized with the default `ExecutionTimeouts`, i.e. the
+						// `APPROVAL_EXECUTION_TIMEOUT` and `BACKING_EXECUTION_TIMEOUT` constants in
+						// `node/primitives/src/lib.rs`
 						0.1,
 						0.25,
 						0.5,
@@ -16,7 +16,8 @@
 
 use super::worker::{self, Outcome};
 use crate::{
-	error::{PrepareError, PrepareResult},
+	artifacts::ArtifactId,
+	error::PrepareError,
 	metrics::Metrics,
 	worker_common::{IdleWorker, WorkerHandle},
 	LOG_TARGET,
@@ -61,7 +62,12 @@ pub enum ToPool {
 	///
 	/// In either case, the worker is considered busy and no further `StartWork` messages should be
 	/// sent until either `Concluded` or `Rip` message is received.
-	StartWork { worker: Worker, code: Arc<Vec<u8>>, artifact_path: PathBuf },
+	StartWork {
+		worker: Worker,
+		code: Arc<Vec<u8>>,
+		artifact_id: ArtifactId,
+		preparation_timeout: Duration,
+	},
 }
 
 /// A message sent from pool to its client.
@@ -76,9 +82,9 @@ pub enum FromPool {
 		worker: Worker,
 		/// Indicates whether the worker process was killed.
 		rip: bool,
-		/// [`Ok`] indicates that compiled artifact is successfully stored on disk.
+		/// [`Ok`] indicates that compiled artifact is successfully stored on disk at the given path.
 		/// Otherwise, an [error](PrepareError) is supplied.
-		result: PrepareResult,
+		result: Result<PathBuf, PrepareError>,
 	},
 
 	/// The given worker ceased to exist.
@@ -205,7 +211,7 @@ fn handle_to_pool(
 			metrics.prepare_worker().on_begin_spawn();
 			mux.push(spawn_worker_task(program_path.to_owned(), spawn_timeout).boxed());
 		},
-		ToPool::StartWork { worker, code, artifact_path } => {
+		ToPool::StartWork { worker, code, artifact_id, preparation_timeout } => {
 			if let Some(data) = spawned.get_mut(worker) {
 				if let Some(idle) = data.idle.take() {
 					let preparation_timer = metrics.time_preparation();
@@ -215,7 +221,8 @@ fn handle_to_pool(
 							idle,
 							code,
 							cache_path.to_owned(),
-							artifact_path,
+							artifact_id,
+							preparation_timeout,
 							preparation_timer,
 						)
 						.boxed(),
@@ -262,10 +269,12 @@ async fn start_work_task<Timer>(
 	idle: IdleWorker,
 	code: Arc<Vec<u8>>,
 	cache_path: PathBuf,
-	artifact_path: PathBuf,
+	artifact_id: ArtifactId,
+	preparation_timeout: Duration,
 	_preparation_timer: Option<Timer>,
 ) -> PoolEvent {
-	let outcome = worker::start_work(idle, code, &cache_path, artifact_path).await;
+	let outcome =
+		worker::start_work(idle, code, &cache_path, artifact_id, preparation_timeout).await;
 	PoolEvent::StartWork(worker, outcome)
 }
 
@@ -17,11 +17,16 @@
 //! A queue that handles requests for PVF preparation.
 
 use super::pool::{self, Worker};
-use crate::{artifacts::ArtifactId, metrics::Metrics, PrepareResult, Priority, Pvf, LOG_TARGET};
+use crate::{
+	artifacts::ArtifactId, error::PrepareError, metrics::Metrics, Priority, Pvf, LOG_TARGET,
+};
 use always_assert::{always, never};
 use async_std::path::PathBuf;
 use futures::{channel::mpsc, stream::StreamExt as _, Future, SinkExt};
-use std::collections::{HashMap, VecDeque};
+use std::{
+	collections::{HashMap, VecDeque},
+	time::{Duration, Instant},
+};
 
 /// A request to pool.
 #[derive(Debug)]
@@ -30,7 +35,7 @@ pub enum ToQueue {
 	///
 	/// Note that it is incorrect to enqueue the same PVF again without first receiving the
 	/// [`FromQueue`] response.
-	Enqueue { priority: Priority, pvf: Pvf },
+	Enqueue { priority: Priority, pvf: Pvf, preparation_timeout: Duration },
 }
 
 /// A response from queue.
@@ -39,9 +44,11 @@ pub struct FromQueue {
 	/// Identifier of an artifact.
 	pub(crate) artifact_id: ArtifactId,
 	/// Outcome of the PVF processing. [`Ok`] indicates that compiled artifact
-	/// is successfully stored on disk. Otherwise, an [error](crate::error::PrepareError)
+	/// is successfully stored on disk at the given path. Otherwise, an [error](PrepareError)
 	/// is supplied.
-	pub(crate) result: PrepareResult,
+	pub(crate) result: Result<PathBuf, PrepareError>,
+	/// How long the worker took to process the PVF.
+	pub(crate) preparation_time: Duration,
 }
 
 #[derive(Default)]
@@ -76,7 +83,11 @@ struct JobData {
 	/// The priority of this job. Can be bumped.
 	priority: Priority,
 	pvf: Pvf,
+	/// The time after which the preparation is abandoned and the worker is killed.
+	preparation_timeout: Duration,
 	worker: Option<Worker>,
+	/// When the job was handed to the worker.
+	started_at: Option<Instant>,
 }
 
 #[derive(Default)]
@@ -133,7 +144,6 @@ struct Queue {
 	to_pool_tx: mpsc::Sender<pool::ToPool>,
 	from_pool_rx: mpsc::UnboundedReceiver<pool::FromPool>,
 
-	cache_path: PathBuf,
 	limits: Limits,
 
 	jobs: slotmap::SlotMap<Job, JobData>,
@@ -158,7 +168,6 @@ impl Queue {
 		metrics: Metrics,
 		soft_capacity: usize,
 		hard_capacity: usize,
-		cache_path: PathBuf,
 		to_queue_rx: mpsc::Receiver<ToQueue>,
 		from_queue_tx: mpsc::UnboundedSender<FromQueue>,
 		to_pool_tx: mpsc::Sender<pool::ToPool>,
@@ -170,7 +179,6 @@ impl Queue {
 			from_queue_tx,
 			to_pool_tx,
 			from_pool_rx,
-			cache_path,
 			spawn_inflight: 0,
 			limits: Limits { hard_capacity, soft_capacity },
 			jobs: slotmap::SlotMap::with_key(),
@@ -203,18 +211,24 @@ impl Queue {
 
 async fn handle_to_queue(queue: &mut Queue, to_queue: ToQueue) -> Result<(), Fatal> {
 	match to_queue {
-		ToQueue::Enqueue { priority, pvf } => {
-			handle_enqueue(queue, priority, pvf).await?;
+		ToQueue::Enqueue { priority, pvf, preparation_timeout } => {
+			handle_enqueue(queue, priority, pvf, preparation_timeout).await?;
 		},
 	}
 	Ok(())
 }
 
-async fn handle_enqueue(queue: &mut Queue, priority: Priority, pvf: Pvf) -> Result<(), Fatal> {
+async fn handle_enqueue(
+	queue: &mut Queue,
+	priority: Priority,
+	pvf: Pvf,
+	preparation_timeout: Duration,
+) -> Result<(), Fatal> {
 	gum::debug!(
 		target: LOG_TARGET,
 		validation_code_hash = ?pvf.code_hash,
 		?priority,
+		?preparation_timeout,
 		"PVF is enqueued for preparation.",
 	);
 	queue.metrics.prepare_enqueued();
@@ -236,7 +250,13 @@ async fn handle_enqueue(queue: &mut Queue, priority: Priority, pvf: Pvf) -> Resu
 		return Ok(())
 	}
 
-	let job = queue.jobs.insert(JobData { priority, pvf, worker: None });
+	let job = queue.jobs.insert(JobData {
+		priority,
+		pvf,
+		preparation_timeout,
+		worker: None,
+		started_at: None,
+	});
 	queue.artifact_id_to_job.insert(artifact_id, job);
 
 	if let Some(available) = find_idle_worker(queue) {
@@ -281,7 +301,7 @@ async fn handle_worker_concluded(
 	queue: &mut Queue,
 	worker: Worker,
 	rip: bool,
-	result: PrepareResult,
+	result: Result<PathBuf, PrepareError>,
 ) -> Result<(), Fatal> {
 	queue.metrics.prepare_concluded();
 
@@ -339,7 +359,8 @@ async fn handle_worker_concluded(
 		"prepare worker concluded",
 	);
 
-	reply(&mut queue.from_queue_tx, FromQueue { artifact_id, result })?;
+	let preparation_time = job_data.started_at.map_or(Duration::ZERO, |at| at.elapsed());
+	reply(&mut queue.from_queue_tx, FromQueue { artifact_id, result, preparation_time })?;
 
 	// Figure out what to do with the worker.
 	if rip {
@@ -416,15 +437,20 @@ async fn assign(queue: &mut Queue, worker: Worker, job: Job) -> Result<(), Fatal
 	let job_data = &mut queue.jobs[job];
 
 	let artifact_id = job_data.pvf.as_artifact_id();
-	let artifact_path = artifact_id.path(&queue.cache_path);
 
 	job_data.worker = Some(worker);
+	job_data.started_at = Some(Instant::now());
 
 	queue.workers[worker].job = Some(job);
 
 	send_pool(
 		&mut queue.to_pool_tx,
-		pool::ToPool::StartWork { worker, code: job_data.pvf.code.clone(), artifact_path },
+		pool::ToPool::StartWork {
+			worker,
+			code: job_data.pvf.code.clone(),
+			artifact_id,
+			preparation_timeout: job_data.preparation_timeout,
+		},
 	)
 	.await?;
 
@@ -453,7 +479,6 @@ pub fn start(
 	metrics: Metrics,
 	soft_capacity: usize,
 	hard_capacity: usize,
-	cache_path: PathBuf,
 	to_pool_tx: mpsc::Sender<pool::ToPool>,
 	from_pool_rx: mpsc::UnboundedReceiver<pool::FromPool>,
 ) -> (mpsc::Sender<ToQueue>, mpsc::UnboundedReceiver<FromQueue>, impl Future<Output = ()>) {
@@ -464,7 +489,6 @@ pub fn start(
 		metrics,
 		soft_capacity,
 		hard_capacity,
-		cache_path,
 		to_queue_rx,
 		from_queue_tx,
 		to_pool_tx,
@@ -478,7 +502,7 @@ pub fn start(
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::error::PrepareError;
+	use crate::host::{LENIENT_PREPARATION_TIMEOUT, PRECHECK_PREPARATION_TIMEOUT};
 	use assert_matches::assert_matches;
 	use futures::{future::BoxFuture, FutureExt};
 	use slotmap::SlotMap;
@@ -489,6 +513,14 @@ mod tests {
 		Pvf::from_discriminator(descriminator)
 	}
 
+	fn artifact_path(descriminator: u32) -> PathBuf {
+		pvf(descriminator).as_artifact_id().path(
+			&PathBuf::from(std::env::temp_dir()),
+			&Default::default(),
+			Duration::ZERO,
+		)
+	}
+
 	async fn run_until<R>(
 		task: &mut (impl Future<Output = ()> + Unpin),
 		mut fut: (impl Future<Output = R> + Unpin),
@@ -513,7 +545,6 @@ mod tests {
 	}
 
 	struct Test {
-		_tempdir: tempfile::TempDir,
 		run: BoxFuture<'static, ()>,
 		workers: SlotMap<Worker, ()>,
 		from_pool_tx: mpsc::UnboundedSender<pool::FromPool>,
@@ -524,31 +555,15 @@ mod tests {
 
 	impl Test {
 		fn new(soft_capacity: usize, hard_capacity: usize) -> Self {
-			let tempdir = tempfile::tempdir().unwrap();
-
 			let (to_pool_tx, to_pool_rx) = mpsc::channel(10);
 			let (from_pool_tx, from_pool_rx) = mpsc::unbounded();
 
 			let workers: SlotMap<Worker, ()> = SlotMap::with_key();
 
-			let (to_queue_tx, from_queue_rx, run) = start(
-				Metrics::default(),
-				soft_capacity,
-				hard_capacity,
-				tempdir.path().to_owned().into(),
-				to_pool_tx,
-				from_pool_rx,
-			);
-
-			Self {
-				_tempdir: tempdir,
-				run: run.boxed(),
-				workers,
-				from_pool_tx,
-				to_pool_rx,
-				to_queue_tx,
-				from_queue_rx,
-			}
+			let (to_queue_tx, from_queue_rx, run) =
+				start(Metrics::default(), soft_capacity, hard_capacity, to_pool_tx, from_pool_rx);
+
+			Self { run: run.boxed(), workers, from_pool_tx, to_pool_rx, to_queue_tx, from_queue_rx }
 		}
 
 		fn send_queue(&mut self, to_queue: ToQueue) {
@@ -594,23 +609,77 @@ mod tests {
 	async fn properly_concludes() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
 		let w = test.workers.insert(());
 		test.send_from_pool(pool::FromPool::Spawned(w));
-		test.send_from_pool(pool::FromPool::Concluded { worker: w, rip: false, result: Ok(()) });
+		test.send_from_pool(pool::FromPool::Concluded {
+			worker: w,
+			rip: false,
+			result: Ok(artifact_path(1)),
+		});
 
 		assert_eq!(test.poll_and_recv_from_queue().await.artifact_id, pvf(1).as_artifact_id());
 	}
 
+	#[async_std::test]
+	async fn preparation_timeout_is_passed_to_pool() {
+		let mut test = Test::new(2, 2);
+
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(2),
+			preparation_timeout: LENIENT_PREPARATION_TIMEOUT,
+		});
+		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
+		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
+
+		test.send_from_pool(pool::FromPool::Spawned(test.workers.insert(())));
+		test.send_from_pool(pool::FromPool::Spawned(test.workers.insert(())));
+
+		assert_matches!(
+			test.poll_and_recv_to_pool().await,
+			pool::ToPool::StartWork { preparation_timeout, .. } => {
+				assert_eq!(preparation_timeout, PRECHECK_PREPARATION_TIMEOUT);
+			}
+		);
+		assert_matches!(
+			test.poll_and_recv_to_pool().await,
+			pool::ToPool::StartWork { preparation_timeout, .. } => {
+				assert_eq!(preparation_timeout, LENIENT_PREPARATION_TIMEOUT);
+			}
+		);
+	}
+
 	#[async_std::test]
 	async fn dont_spawn_over_soft_limit_unless_critical() {
 		let mut test = Test::new(2, 3);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(2) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(3) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(2),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(3),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 
 		// Receive only two spawns.
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
@@ -626,12 +695,20 @@ mod tests {
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
-		test.send_from_pool(pool::FromPool::Concluded { worker: w1, rip: false, result: Ok(()) });
+		test.send_from_pool(pool::FromPool::Concluded {
+			worker: w1,
+			rip: false,
+			result: Ok(artifact_path(1)),
+		});
 
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
 		// Enqueue a critical job.
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Critical, pvf: pvf(4) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Critical,
+			pvf: pvf(4),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 
 		// 2 out of 2 are working, but there is a critical job incoming. That means that spawning
 		// another worker is warranted.
@@ -642,14 +719,22 @@ mod tests {
 	async fn cull_unwanted() {
 		let mut test = Test::new(1, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 		let w1 = test.workers.insert(());
 		test.send_from_pool(pool::FromPool::Spawned(w1));
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
 		// Enqueue a critical job, which warrants spawning over the soft limit.
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Critical, pvf: pvf(2) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Critical,
+			pvf: pvf(2),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
 		// However, before the new worker had a chance to spawn, the first worker finishes with its
@@ -659,7 +744,11 @@ mod tests {
 		// That's a bit silly in this context, but in production there will be an entire pool up
 		// to the `soft_capacity` of workers and it doesn't matter which one to cull. Either way,
 		// we just check that edge case of an edge case works.
-		test.send_from_pool(pool::FromPool::Concluded { worker: w1, rip: false, result: Ok(()) });
+		test.send_from_pool(pool::FromPool::Concluded {
+			worker: w1,
+			rip: false,
+			result: Ok(artifact_path(1)),
+		});
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Kill(w1));
 	}
 
@@ -667,9 +756,21 @@ mod tests {
 	async fn worker_mass_die_out_doesnt_stall_queue() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(2) });
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(3) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(2),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(3),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
@@ -684,7 +785,11 @@ mod tests {
 		assert_matches!(test.poll_and_recv_to_pool().await, pool::ToPool::StartWork { .. });
 
 		// Conclude worker 1 and rip it.
-		test.send_from_pool(pool::FromPool::Concluded { worker: w1, rip: true, result: Ok(()) });
+		test.send_from_pool(pool::FromPool::Concluded {
+			worker: w1,
+			rip: true,
+			result: Ok(artifact_path(1)),
+		});
 
 		// Since there is still work, the queue requested one extra worker to spawn to handle the
 		// remaining enqueued work items.
@@ -696,7 +801,11 @@ mod tests {
 	async fn doesnt_resurrect_ripped_worker_if_no_work() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
@@ -717,7 +826,11 @@ mod tests {
 	async fn rip_for_start_work() {
 		let mut test = Test::new(2, 2);
 
-		test.send_queue(ToQueue::Enqueue { priority: Priority::Normal, pvf: pvf(1) });
+		test.send_queue(ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: pvf(1),
+			preparation_timeout: PRECHECK_PREPARATION_TIMEOUT,
+		});
 
 		assert_eq!(test.poll_and_recv_to_pool().await, pool::ToPool::Spawn);
 
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	artifacts::CompiledArtifact,
+	artifacts::{compute_checksum, ArtifactId, CompiledArtifact},
 	error::{PrepareError, PrepareResult},
 	worker_common::{
 		bytes_to_path, framed_recv, framed_send, path_to_bytes, spawn_with_program_path,
@@ -30,11 +30,12 @@ use async_std::{
 };
 use parity_scale_codec::{Decode, Encode};
 use sp_core::hexdisplay::HexDisplay;
-use std::{any::Any, panic, sync::Arc, time::Duration};
-
-/// The time period after which the preparation worker is considered unresponsive and will be killed.
-// NOTE: If you change this make sure to fix the buckets of `pvf_preparation_time` metric.
-const COMPILATION_TIMEOUT: Duration = Duration::from_secs(60);
+use std::{
+	any::Any,
+	panic,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 /// Spawns a new worker with the given program path that acts as the worker and the spawn timeout.
 ///
@@ -48,7 +49,9 @@ pub async fn spawn(
 
 pub enum Outcome {
 	/// The worker has finished the work assigned to it.
-	Concluded { worker: IdleWorker, result: PrepareResult },
+	///
+	/// If the preparation succeeded, the result holds the path of the prepared artifact.
+	Concluded { worker: IdleWorker, result: Result<PathBuf, PrepareError> },
 	/// The host tried to reach the worker but failed. This is most likely because the worked was
 	/// killed by the system.
 	Unreachable,
@@ -64,22 +67,27 @@ pub enum Outcome {
 
 /// Given the idle token of a worker and parameters of work, communicates with the worker and
 /// returns the outcome.
+///
+/// If the worker does not respond within the given preparation timeout, it is considered
+/// unresponsive and should be killed.
 pub async fn start_work(
 	worker: IdleWorker,
 	code: Arc<Vec<u8>>,
 	cache_path: &Path,
-	artifact_path: PathBuf,
+	artifact_id: ArtifactId,
+	preparation_timeout: Duration,
 ) -> Outcome {
 	let IdleWorker { mut stream, pid } = worker;
 
 	gum::debug!(
 		target: LOG_TARGET,
 		worker_pid = %pid,
-		"starting prepare for {}",
-		artifact_path.display(),
+		validation_code_hash = ?artifact_id.code_hash,
+		"starting prepare",
 	);
 
 	with_tmp_file(pid, cache_path, |tmp_file| async move {
+		let started_at = Instant::now();
 		if let Err(err) = send_request(&mut stream, code, &tmp_file).await {
 			gum::warn!(
 				target: LOG_TARGET,
@@ -97,42 +105,29 @@ pub async fn start_work(
 
 		#[derive(Debug)]
 		enum Selected {
-			Done(PrepareResult),
+			Done(Result<PathBuf, PrepareError>),
 			IoErr,
 			Deadline,
 		}
 
 		let selected =
-			match async_std::future::timeout(COMPILATION_TIMEOUT, framed_recv(&mut stream)).await {
+			match async_std::future::timeout(preparation_timeout, framed_recv(&mut stream)).await {
 				Ok(Ok(response_bytes)) => {
 					// Received bytes from worker within the time limit.
 					// By convention we expect encoded `PrepareResult`.
 					if let Ok(result) = PrepareResult::decode(&mut response_bytes.as_slice()) {
-						if result.is_ok() {
-							gum::debug!(
-								target: LOG_TARGET,
-								worker_pid = %pid,
-								"promoting WIP artifact {} to {}",
-								tmp_file.display(),
-								artifact_path.display(),
-							);
-
-							async_std::fs::rename(&tmp_file, &artifact_path)
-								.await
-								.map(|_| Selected::Done(result))
-								.unwrap_or_else(|err| {
-									gum::warn!(
-										target: LOG_TARGET,
-										worker_pid = %pid,
-										"failed to rename the artifact from {} to {}: {:?}",
-										tmp_file.display(),
-										artifact_path.display(),
-										err,
-									);
-									Selected::IoErr
-								})
-						} else {
-							Selected::Done(result)
+						match result {
+							Ok(()) => promote(
+								pid,
+								&tmp_file,
+								cache_path,
+								&artifact_id,
+								started_at.elapsed(),
+							)
+							.await
+							.map(|artifact_path| Selected::Done(Ok(artifact_path)))
+							.unwrap_or(Selected::IoErr),
+							Err(err) => Selected::Done(Err(err)),
 						}
 					} else {
 						// We received invalid bytes from the worker.
@@ -172,6 +167,55 @@ pub async fn start_work(
 	.await
 }
 
+/// Moves the artifact prepared into the given temporary file to its place in the cache, naming it
+/// after the checksum of its contents and the time its preparation took. Returns the new path of
+/// the artifact.
+async fn promote(
+	pid: u32,
+	tmp_file: &Path,
+	cache_path: &Path,
+	artifact_id: &ArtifactId,
+	preparation_time: Duration,
+) -> Option<PathBuf> {
+	let checksum = match async_std::fs::read(tmp_file).await {
+		Ok(contents) => compute_checksum(&contents),
+		Err(err) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				worker_pid = %pid,
+				"failed to read the WIP artifact {}: {:?}",
+				tmp_file.display(),
+				err,
+			);
+			return None
+		},
+	};
+	let artifact_path = artifact_id.path(cache_path, &checksum, preparation_time);
+
+	gum::debug!(
+		target: LOG_TARGET,
+		worker_pid = %pid,
+		"promoting WIP artifact {} to {}",
+		tmp_file.display(),
+		artifact_path.display(),
+	);
+
+	match async_std::fs::rename(tmp_file, &artifact_path).await {
+		Ok(()) => Some(artifact_path),
+		Err(err) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				worker_pid = %pid,
+				"failed to rename the artifact from {} to {}: {:?}",
+				tmp_file.display(),
+				artifact_path.display(),
+				err,
+			);
+			None
+		},
+	}
+}
+
 /// Create a temporary file for an artifact at the given cache path and execute the given
 /// future/closure passing the file path in.
 ///
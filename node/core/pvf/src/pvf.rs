@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::artifacts::ArtifactId;
+use crate::{artifacts::ArtifactId, executor_intf::executor_params_hash};
 use polkadot_parachain::primitives::ValidationCodeHash;
 use sp_core::blake2_256;
 use std::{fmt, sync::Arc};
@@ -51,6 +51,6 @@ impl Pvf {
 
 	/// Returns the artifact ID that corresponds to this PVF.
 	pub(crate) fn as_artifact_id(&self) -> ArtifactId {
-		ArtifactId::new(self.code_hash)
+		ArtifactId::new(self.code_hash, executor_params_hash())
 	}
 }
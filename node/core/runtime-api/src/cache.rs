@@ -22,7 +22,7 @@ use sp_consensus_babe::Epoch;
 
 use polkadot_primitives::v2::{
 	AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateEvent,
-	CommittedCandidateReceipt, CoreState, GroupRotationInfo, Hash, Id as ParaId,
+	CommittedCandidateReceipt, CoreState, ExecutorParams, GroupRotationInfo, Hash, Id as ParaId,
 	InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption, PersistedValidationData,
 	PvfCheckStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo, ValidationCode,
 	ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
@@ -40,6 +40,7 @@ const VALIDATION_CODE_CACHE_SIZE: usize = 10 * 1024 * 1024;
 const CANDIDATE_PENDING_AVAILABILITY_CACHE_SIZE: usize = 64 * 1024;
 const CANDIDATE_EVENTS_CACHE_SIZE: usize = 64 * 1024;
 const SESSION_INFO_CACHE_SIZE: usize = 64 * 1024;
+const SESSION_EXECUTOR_PARAMS_CACHE_SIZE: usize = 16 * 1024;
 const DMQ_CONTENTS_CACHE_SIZE: usize = 64 * 1024;
 const INBOUND_HRMP_CHANNELS_CACHE_SIZE: usize = 64 * 1024;
 const CURRENT_BABE_EPOCH_CACHE_SIZE: usize = 64 * 1024;
@@ -101,6 +102,7 @@ pub(crate) struct RequestResultCache {
 		MemoryLruCache<(Hash, ParaId), ResidentSizeOf<Option<CommittedCandidateReceipt>>>,
 	candidate_events: MemoryLruCache<Hash, ResidentSizeOf<Vec<CandidateEvent>>>,
 	session_info: MemoryLruCache<SessionIndex, ResidentSizeOf<SessionInfo>>,
+	session_executor_params: MemoryLruCache<SessionIndex, ResidentSizeOf<ExecutorParams>>,
 	dmq_contents:
 		MemoryLruCache<(Hash, ParaId), ResidentSizeOf<Vec<InboundDownwardMessage<BlockNumber>>>>,
 	inbound_hrmp_channels_contents: MemoryLruCache<
@@ -135,6 +137,7 @@ impl Default for RequestResultCache {
 			),
 			candidate_events: MemoryLruCache::new(CANDIDATE_EVENTS_CACHE_SIZE),
 			session_info: MemoryLruCache::new(SESSION_INFO_CACHE_SIZE),
+			session_executor_params: MemoryLruCache::new(SESSION_EXECUTOR_PARAMS_CACHE_SIZE),
 			dmq_contents: MemoryLruCache::new(DMQ_CONTENTS_CACHE_SIZE),
 			inbound_hrmp_channels_contents: MemoryLruCache::new(INBOUND_HRMP_CHANNELS_CACHE_SIZE),
 			current_babe_epoch: MemoryLruCache::new(CURRENT_BABE_EPOCH_CACHE_SIZE),
@@ -317,6 +320,18 @@ impl RequestResultCache {
 		self.session_info.insert(key, ResidentSizeOf(value));
 	}
 
+	pub(crate) fn session_executor_params(&mut self, key: SessionIndex) -> Option<&ExecutorParams> {
+		self.session_executor_params.get(&key).map(|v| &v.0)
+	}
+
+	pub(crate) fn cache_session_executor_params(
+		&mut self,
+		key: SessionIndex,
+		value: ExecutorParams,
+	) {
+		self.session_executor_params.insert(key, ResidentSizeOf(value));
+	}
+
 	pub(crate) fn dmq_contents(
 		&mut self,
 		key: (Hash, ParaId),
@@ -442,4 +457,5 @@ pub(crate) enum RequestResult {
 	SubmitPvfCheckStatement(Hash, PvfCheckStatement, ValidatorSignature, ()),
 	ValidationCodeHash(Hash, ParaId, OccupiedCoreAssumption, Option<ValidationCodeHash>),
 	Version(Hash, u32),
+	SessionExecutorParams(Hash, SessionIndex, Option<ExecutorParams>),
 }
@@ -164,6 +164,10 @@ where
 				.cache_validation_code_hash((relay_parent, para_id, assumption), hash),
 			Version(relay_parent, version) =>
 				self.requests_cache.cache_version(relay_parent, version),
+			SessionExecutorParams(_relay_parent, session_index, params) =>
+				if let Some(params) = params {
+					self.requests_cache.cache_session_executor_params(session_index, params);
+				},
 		}
 	}
 
@@ -265,6 +269,15 @@ where
 			Request::ValidationCodeHash(para, assumption, sender) =>
 				query!(validation_code_hash(para, assumption), sender)
 					.map(|sender| Request::ValidationCodeHash(para, assumption, sender)),
+			Request::SessionExecutorParams(session_index, sender) => {
+				if let Some(params) = self.requests_cache.session_executor_params(session_index) {
+					self.metrics.on_cached_request();
+					let _ = sender.send(Ok(Some(params.clone())));
+					None
+				} else {
+					Some(Request::SessionExecutorParams(session_index, sender))
+				}
+			},
 		}
 	}
 
@@ -521,6 +534,8 @@ where
 		},
 		Request::ValidationCodeHash(para, assumption, sender) =>
 			query!(ValidationCodeHash, validation_code_hash(para, assumption), ver = 2, sender),
+		Request::SessionExecutorParams(session_index, sender) =>
+			query!(SessionExecutorParams, session_executor_params(session_index), ver = 3, sender),
 	}
 }
 
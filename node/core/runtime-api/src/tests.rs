@@ -21,10 +21,11 @@ use futures::channel::oneshot;
 use polkadot_node_primitives::{BabeAllowedSlots, BabeEpoch, BabeEpochConfiguration};
 use polkadot_node_subsystem_test_helpers::make_subsystem_context;
 use polkadot_primitives::v2::{
-	AuthorityDiscoveryId, CandidateEvent, CommittedCandidateReceipt, CoreState, GroupRotationInfo,
-	Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption,
-	PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo,
-	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+	AuthorityDiscoveryId, CandidateEvent, CommittedCandidateReceipt, CoreState, ExecutorParam,
+	ExecutorParams, GroupRotationInfo, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
+	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
+	SessionIndex, SessionInfo, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 use sp_core::testing::TaskExecutor;
 use std::{
@@ -42,6 +43,7 @@ struct MockRuntimeApi {
 	validation_data: HashMap<ParaId, PersistedValidationData>,
 	session_index_for_child: SessionIndex,
 	session_info: HashMap<SessionIndex, SessionInfo>,
+	session_executor_params: HashMap<SessionIndex, ExecutorParams>,
 	validation_code: HashMap<ParaId, ValidationCode>,
 	validation_code_by_hash: HashMap<ValidationCodeHash, ValidationCode>,
 	validation_outputs_results: HashMap<ParaId, bool>,
@@ -189,6 +191,10 @@ sp_api::mock_impl_runtime_apis! {
 		) -> Option<ValidationCodeHash> {
 			self.validation_code_hash.get(&para).map(|c| c.clone())
 		}
+
+		fn session_executor_params(&self, session_index: SessionIndex) -> Option<ExecutorParams> {
+			self.session_executor_params.get(&session_index).cloned()
+		}
 	}
 
 	impl BabeApi<Block> for MockRuntimeApi {
@@ -555,6 +561,53 @@ fn requests_session_info() {
 	futures::executor::block_on(future::join(subsystem_task, test_task));
 }
 
+#[test]
+fn requests_session_executor_params() {
+	let (ctx, mut ctx_handle) = make_subsystem_context(TaskExecutor::new());
+	let mut runtime_api = MockRuntimeApi::default();
+	let session_index = 1;
+	let params = ExecutorParams::from(vec![ExecutorParam::MaxMemoryPages(2048)]);
+	runtime_api.session_executor_params.insert(session_index, params.clone());
+	let runtime_api = Arc::new(runtime_api);
+	let spawner = sp_core::testing::TaskExecutor::new();
+
+	let relay_parent = [1; 32].into();
+
+	let subsystem = RuntimeApiSubsystem::new(runtime_api.clone(), Metrics(None), spawner);
+	let subsystem_task = run(ctx, subsystem).map(|x| x.unwrap());
+	let test_task = async move {
+		let (tx, rx) = oneshot::channel();
+
+		ctx_handle
+			.send(FromOverseer::Communication {
+				msg: RuntimeApiMessage::Request(
+					relay_parent,
+					Request::SessionExecutorParams(session_index, tx),
+				),
+			})
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), Some(params));
+
+		let (tx, rx) = oneshot::channel();
+
+		ctx_handle
+			.send(FromOverseer::Communication {
+				msg: RuntimeApiMessage::Request(
+					relay_parent,
+					Request::SessionExecutorParams(session_index + 1, tx),
+				),
+			})
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), None);
+
+		ctx_handle.send(FromOverseer::Signal(OverseerSignal::Conclude)).await;
+	};
+
+	futures::executor::block_on(future::join(subsystem_task, test_task));
+}
+
 #[test]
 fn requests_validation_code() {
 	let (ctx, mut ctx_handle) = make_subsystem_context(TaskExecutor::new());
@@ -32,7 +32,7 @@ use polkadot_cli::{
 // subsystem.
 use polkadot_node_core_candidate_validation::CandidateValidationSubsystem;
 use polkadot_node_subsystem::messages::{
-	AvailabilityRecoveryMessage, CandidateValidationMessage, ValidationFailed,
+	AvailabilityRecoveryMessage, CandidateValidationMessage, RecoveryStrategy, ValidationFailed,
 };
 use polkadot_node_subsystem_util as util;
 
@@ -109,7 +109,7 @@ where
 						validation_code,
 						candidate_descriptor,
 						pov,
-						_duration,
+						_exec_kind,
 						response_sender,
 					),
 			} if pov.block_data.0.as_slice() == MALICIOUS_POV => {
@@ -127,7 +127,7 @@ where
 					CandidateValidationMessage::ValidateFromChainState(
 						candidate_descriptor,
 						pov,
-						_duration,
+						_exec_kind,
 						response_sender,
 					),
 			} if pov.block_data.0.as_slice() == MALICIOUS_POV => {
@@ -155,7 +155,7 @@ where
 									AvailabilityRecoveryMessage::RecoverAvailableData(
 										candidate_receipt,
 										session_index,
-										None,
+										RecoveryStrategy::ChunksOnly,
 										a_tx,
 									),
 								))
@@ -30,14 +30,14 @@ use polkadot_node_primitives::approval::{
 use polkadot_node_subsystem::{
 	messages::{
 		ApprovalCheckResult, ApprovalDistributionMessage, ApprovalVotingMessage,
-		AssignmentCheckResult, NetworkBridgeEvent, NetworkBridgeMessage,
+		AssignmentCheckResult, NetworkBridgeEvent, NetworkBridgeMessage, NewGossipTopology,
 	},
 	overseer, ActiveLeavesUpdate, FromOverseer, OverseerSignal, SpawnedSubsystem, SubsystemContext,
 	SubsystemError,
 };
 use polkadot_node_subsystem_util::{self as util, MIN_GOSSIP_PEERS};
 use polkadot_primitives::v2::{
-	BlockNumber, CandidateIndex, Hash, ValidatorIndex, ValidatorSignature,
+	BlockNumber, CandidateIndex, Hash, SessionIndex, ValidatorIndex, ValidatorSignature,
 };
 use std::collections::{hash_map, BTreeMap, HashMap, HashSet, VecDeque};
 
@@ -61,6 +61,13 @@ const BENEFIT_VALID_MESSAGE: Rep = Rep::BenefitMinor("Peer sent a valid message"
 const BENEFIT_VALID_MESSAGE_FIRST: Rep =
 	Rep::BenefitMinorFirst("Valid message with new information");
 
+/// The number of peers outside of the grid topology each message is sent to,
+/// which keeps messages flowing while the topology is unknown or not fully connected.
+const RANDOM_CIRCULATION: usize = 4;
+/// A message is sent to a random peer with a probability of this many out of
+/// the number of peers we are connected to.
+const RANDOM_SAMPLE_RATE: usize = MIN_GOSSIP_PEERS;
+
 /// The Approval Distribution subsystem.
 pub struct ApprovalDistribution {
 	metrics: Metrics,
@@ -110,12 +117,172 @@ struct State {
 	/// Peer view data is partially stored here, and partially inline within the [`BlockEntry`]s
 	peer_views: HashMap<PeerId, View>,
 
-	/// Track all our neighbors in the current gossip topology.
+	/// Our neighbors in the gossip topologies of the sessions of the blocks in our view.
 	/// We're not necessarily connected to all of them.
-	gossip_peers: HashSet<PeerId>,
+	topologies: HashMap<SessionIndex, SessionTopology>,
 
 	/// Tracks recently finalized blocks.
 	recent_outdated_blocks: RecentlyOutdated,
+
+	/// When to send messages more aggressively, as finality lags.
+	aggression_config: AggressionConfig,
+}
+
+/// Thresholds of the finality lag, that is how far the newest unfinalized block in our view is
+/// ahead of the oldest one, beyond which the messages of the oldest blocks are sent more
+/// aggressively.
+///
+/// Approval voting can get stuck on a single block, holding back the finality of all its
+/// descendants, so the extra bandwidth is only spent on the oldest blocks.
+#[derive(Debug, Clone)]
+struct AggressionConfig {
+	/// Level 1: validators send their own messages to all peers.
+	l1_threshold: Option<BlockNumber>,
+	/// Level 2: on top of level 1, validators forward all messages to both
+	/// dimensions of the grid.
+	l2_threshold: Option<BlockNumber>,
+	/// Every this many blocks, the messages of the unfinalized blocks are sent again
+	/// to the peers they are routed to.
+	resend_unfinalized_period: Option<BlockNumber>,
+}
+
+impl Default for AggressionConfig {
+	fn default() -> Self {
+		AggressionConfig {
+			l1_threshold: Some(13),
+			l2_threshold: Some(28),
+			resend_unfinalized_period: Some(8),
+		}
+	}
+}
+
+impl AggressionConfig {
+	/// Whether any of the thresholds is reached with a finality lag of `lag` blocks.
+	fn is_lag_relevant(&self, lag: BlockNumber) -> bool {
+		[self.l1_threshold, self.l2_threshold, self.resend_unfinalized_period]
+			.iter()
+			.flatten()
+			.any(|threshold| lag >= *threshold)
+	}
+}
+
+/// Whether to send the messages of unfinalized blocks again, when enabling aggression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resend {
+	Yes,
+	No,
+}
+
+/// Our neighbors in the gossip topology of a session, which is a grid of all the validators
+/// of the session. Each validator sends its messages to its row and column, and every
+/// validator in its row forwards them to its own column, and vice versa, so that a message
+/// reaches all validators in two hops.
+#[derive(Debug, Default)]
+struct SessionTopology {
+	/// The peers of the validators in our row.
+	peers_x: HashSet<PeerId>,
+	/// The validators in our row.
+	validator_indices_x: HashSet<ValidatorIndex>,
+	/// The peers of the validators in our column.
+	peers_y: HashSet<PeerId>,
+	/// The validators in our column.
+	validator_indices_y: HashSet<ValidatorIndex>,
+}
+
+impl From<NewGossipTopology> for SessionTopology {
+	fn from(topology: NewGossipTopology) -> Self {
+		let mut session_topology = SessionTopology::default();
+		for info in topology.our_neighbors_x.into_values() {
+			session_topology.peers_x.extend(info.peer_ids);
+			session_topology.validator_indices_x.insert(info.validator_index);
+		}
+		for info in topology.our_neighbors_y.into_values() {
+			session_topology.peers_y.extend(info.peer_ids);
+			session_topology.validator_indices_y.insert(info.validator_index);
+		}
+
+		session_topology
+	}
+}
+
+impl SessionTopology {
+	/// The peers a message originating from `validator_index` needs to be sent to.
+	fn required_routing_by_index(
+		&self,
+		validator_index: ValidatorIndex,
+		local: bool,
+	) -> RequiredRouting {
+		if local {
+			return RequiredRouting::GridXY
+		}
+
+		let grid_x = self.validator_indices_x.contains(&validator_index);
+		let grid_y = self.validator_indices_y.contains(&validator_index);
+
+		match (grid_x, grid_y) {
+			(false, false) => RequiredRouting::None,
+			(true, false) => RequiredRouting::GridY,
+			(false, true) => RequiredRouting::GridX,
+			// only possible if the validator is us.
+			(true, true) => RequiredRouting::GridXY,
+		}
+	}
+
+	fn route_to_peer(&self, required_routing: RequiredRouting, peer: &PeerId) -> bool {
+		match required_routing {
+			RequiredRouting::All => true,
+			RequiredRouting::GridX => self.peers_x.contains(peer),
+			RequiredRouting::GridY => self.peers_y.contains(peer),
+			RequiredRouting::GridXY => self.peers_x.contains(peer) || self.peers_y.contains(peer),
+			RequiredRouting::None | RequiredRouting::PendingTopology => false,
+		}
+	}
+}
+
+/// The peers a message needs to be sent to, besides the random ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RequiredRouting {
+	/// The topology of the session of the message is not known yet.
+	PendingTopology,
+	/// All peers.
+	All,
+	/// The peers of both of our grid dimensions.
+	GridXY,
+	/// The peers of our row.
+	GridX,
+	/// The peers of our column.
+	GridY,
+	/// No peers.
+	None,
+}
+
+impl RequiredRouting {
+	fn is_empty(self) -> bool {
+		matches!(self, RequiredRouting::PendingTopology | RequiredRouting::None)
+	}
+}
+
+/// Tracks the random peers a message has been sent to, on top of its required routing.
+#[derive(Debug, Default, Clone, Copy)]
+struct RandomRouting {
+	sent: usize,
+}
+
+impl RandomRouting {
+	/// Whether to send the message to one more random peer, out of `n_peers_total`.
+	fn sample(&mut self, n_peers_total: usize) -> bool {
+		if self.sent >= RANDOM_CIRCULATION || n_peers_total == 0 {
+			return false
+		}
+
+		let route = RANDOM_SAMPLE_RATE >= n_peers_total ||
+			util::gen_ratio(RANDOM_SAMPLE_RATE, n_peers_total);
+		if route {
+			self.sent += 1;
+		}
+
+		route
+	}
 }
 
 /// A short description of a validator's assignment or approval.
@@ -140,10 +307,6 @@ impl Knowledge {
 	}
 }
 
-/// The difference of our knowledge and peer's knowledge
-/// that is used to send the missing information.
-type MissingKnowledge = HashSet<MessageFingerprint>;
-
 /// Information that has been circulated to and from a peer.
 #[derive(Debug, Clone, Default)]
 struct PeerKnowledge {
@@ -168,6 +331,8 @@ struct BlockEntry {
 	number: BlockNumber,
 	/// The parent hash of the block.
 	parent_hash: Hash,
+	/// The session of the block.
+	session: SessionIndex,
 	/// Our knowledge of messages.
 	knowledge: Knowledge,
 	/// A votes entry for each candidate indexed by [`CandidateIndex`].
@@ -180,10 +345,29 @@ enum ApprovalState {
 	Approved(AssignmentCert, ValidatorSignature),
 }
 
-#[derive(Debug, Clone, Copy)]
-enum LocalSource {
-	Yes,
-	No,
+impl ApprovalState {
+	fn assignment_cert(&self) -> &AssignmentCert {
+		match self {
+			ApprovalState::Assigned(cert) => cert,
+			ApprovalState::Approved(cert, _) => cert,
+		}
+	}
+
+	fn approval_signature(&self) -> Option<&ValidatorSignature> {
+		match self {
+			ApprovalState::Assigned(_) => None,
+			ApprovalState::Approved(_, signature) => Some(signature),
+		}
+	}
+}
+
+/// The messages of a validator about a candidate, along with where to send them.
+#[derive(Debug)]
+struct MessageState {
+	approval_state: ApprovalState,
+	required_routing: RequiredRouting,
+	random_routing: RandomRouting,
+	local: bool,
 }
 
 /// Information about candidates in the context of a particular block they are included in.
@@ -191,10 +375,10 @@ enum LocalSource {
 /// if it is included by multiple blocks - this is likely the case when there are forks.
 #[derive(Debug, Default)]
 struct CandidateEntry {
-	approvals: HashMap<ValidatorIndex, (ApprovalState, LocalSource)>,
+	messages: HashMap<ValidatorIndex, MessageState>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum MessageSource {
 	Peer(PeerId),
 	Local,
@@ -207,13 +391,6 @@ impl MessageSource {
 			Self::Local => None,
 		}
 	}
-
-	fn as_local_source(&self) -> LocalSource {
-		match self {
-			Self::Local => LocalSource::Yes,
-			_ => LocalSource::No,
-		}
-	}
 }
 
 enum PendingMessage {
@@ -242,15 +419,8 @@ impl State {
 					entry.known_by.remove(&peer_id);
 				})
 			},
-			NetworkBridgeEvent::NewGossipTopology(peers) => {
-				let newly_added: Vec<PeerId> =
-					peers.difference(&self.gossip_peers).cloned().collect();
-				self.gossip_peers = peers;
-				for peer_id in newly_added {
-					if let Some(view) = self.peer_views.remove(&peer_id) {
-						self.handle_peer_view_change(ctx, metrics, peer_id, view).await;
-					}
-				}
+			NetworkBridgeEvent::NewGossipTopology(topology) => {
+				self.handle_new_session_topology(ctx, topology).await;
 			},
 			NetworkBridgeEvent::PeerViewChange(peer_id, view) => {
 				self.handle_peer_view_change(ctx, metrics, peer_id, view).await;
@@ -300,6 +470,7 @@ impl State {
 						known_by: HashMap::new(),
 						number: meta.number,
 						parent_hash: meta.parent_hash.clone(),
+						session: meta.session,
 						knowledge: Knowledge::default(),
 						candidates,
 					});
@@ -325,7 +496,8 @@ impl State {
 				let view_intersection = View::new(intersection.cloned(), view.finalized_number);
 				Self::unify_with_peer(
 					ctx,
-					&self.gossip_peers,
+					&self.topologies,
+					self.peer_views.len(),
 					metrics,
 					&mut self.blocks,
 					peer_id.clone(),
@@ -388,6 +560,42 @@ impl State {
 				}
 			}
 		}
+
+		self.enable_aggression(ctx, Resend::Yes, metrics).await;
+	}
+
+	async fn handle_new_session_topology(
+		&mut self,
+		ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
+		          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
+		topology: NewGossipTopology,
+	) {
+		let session = topology.session;
+		let topology = SessionTopology::from(topology);
+		gum::debug!(
+			target: LOG_TARGET,
+			?session,
+			peers_x = topology.peers_x.len(),
+			peers_y = topology.peers_y.len(),
+			"Received a new gossip topology",
+		);
+
+		self.topologies.insert(session, topology);
+		let topology = &self.topologies[&session];
+
+		// The messages imported before the topology was known can now be routed.
+		adjust_required_routing_and_propagate(
+			ctx,
+			&mut self.blocks,
+			&self.topologies,
+			|block_entry| block_entry.session == session,
+			|required_routing, local, validator_index| {
+				if *required_routing == RequiredRouting::PendingTopology {
+					*required_routing = topology.required_routing_by_index(*validator_index, local);
+				}
+			},
+		)
+		.await;
 	}
 
 	async fn process_incoming_peer_message(
@@ -511,7 +719,8 @@ impl State {
 
 		Self::unify_with_peer(
 			ctx,
-			&self.gossip_peers,
+			&self.topologies,
+			self.peer_views.len(),
 			metrics,
 			&mut self.blocks,
 			peer_id.clone(),
@@ -520,7 +729,13 @@ impl State {
 		.await;
 	}
 
-	fn handle_block_finalized(&mut self, finalized_number: BlockNumber) {
+	async fn handle_block_finalized(
+		&mut self,
+		ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
+		          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
+		metrics: &Metrics,
+		finalized_number: BlockNumber,
+	) {
 		// we want to prune every block up to (including) finalized_number
 		// why +1 here?
 		// split_off returns everything after the given key, including the key
@@ -535,6 +750,100 @@ impl State {
 			self.recent_outdated_blocks.note_outdated(*relay_block);
 			self.blocks.remove(relay_block);
 		});
+
+		// Keep the topologies of the sessions of the remaining blocks, and the latest one,
+		// which may have arrived before the first block of its session.
+		let latest_session = self.topologies.keys().max().copied();
+		let blocks = &self.blocks;
+		self.topologies.retain(|session, _| {
+			Some(*session) == latest_session || blocks.values().any(|b| b.session == *session)
+		});
+
+		// The remaining blocks might still lag behind enough to keep up the aggression
+		// for the new oldest ones.
+		self.enable_aggression(ctx, Resend::No, metrics).await;
+	}
+
+	/// Sends the messages of the oldest unfinalized blocks more aggressively, depending on
+	/// how far finality lags behind, and sends the messages of the unfinalized blocks again
+	/// every `resend_unfinalized_period` blocks.
+	async fn enable_aggression(
+		&mut self,
+		ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
+		          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
+		resend: Resend,
+		metrics: &Metrics,
+	) {
+		let (min_number, max_number) =
+			match (self.blocks_by_number.keys().next(), self.blocks_by_number.keys().next_back()) {
+				(Some(min), Some(max)) => (*min, *max),
+				_ => return,
+			};
+
+		let lag = max_number - min_number;
+		if !self.aggression_config.is_lag_relevant(lag) {
+			return
+		}
+
+		let config = self.aggression_config.clone();
+
+		if resend == Resend::Yes {
+			if let Some(period) = config.resend_unfinalized_period {
+				adjust_required_routing_and_propagate(
+					ctx,
+					&mut self.blocks,
+					&self.topologies,
+					|block_entry| {
+						let age = max_number - block_entry.number;
+						if age == 0 || age % period != 0 {
+							return false
+						}
+
+						// Forget what was sent, so that it is sent again.
+						for knowledge in block_entry.known_by.values_mut() {
+							knowledge.sent = Knowledge::default();
+						}
+						true
+					},
+					|_, _, _| {},
+				)
+				.await;
+			}
+		}
+
+		adjust_required_routing_and_propagate(
+			ctx,
+			&mut self.blocks,
+			&self.topologies,
+			|block_entry| block_entry.number == min_number,
+			|required_routing, local, _| {
+				if *required_routing == RequiredRouting::PendingTopology {
+					gum::debug!(
+						target: LOG_TARGET,
+						?lag,
+						"Encountered an old block pending its gossip topology",
+					);
+					return
+				}
+
+				if config.l1_threshold.map_or(false, |t| lag >= t) {
+					// The originator sends its messages to everyone.
+					if local && *required_routing != RequiredRouting::All {
+						metrics.on_aggression_l1();
+						*required_routing = RequiredRouting::All;
+					}
+				}
+
+				if config.l2_threshold.map_or(false, |t| lag >= t) {
+					// Everyone else forwards them to both of their grid dimensions.
+					if !local && *required_routing != RequiredRouting::GridXY {
+						metrics.on_aggression_l2();
+						*required_routing = RequiredRouting::GridXY;
+					}
+				}
+			},
+		)
+		.await;
 	}
 
 	async fn import_and_circulate_assignment(
@@ -693,17 +1002,49 @@ impl State {
 			}
 		}
 
-		let local_source = source.as_local_source();
+		let local = source == MessageSource::Local;
 
 		// Invariant: none of the peers except for the `source` know about the assignment.
 		metrics.on_assignment_imported();
 
+		let topology = self.topologies.get(&entry.session);
+		let required_routing = topology.map_or(RequiredRouting::PendingTopology, |t| {
+			t.required_routing_by_index(validator_index, local)
+		});
+		let mut random_routing = RandomRouting::default();
+
+		// Dispatch a ApprovalDistributionV1Message::Assignment(assignment, candidate_index)
+		// to the peers in the BlockEntry's known_by set who know about the block and are in
+		// the required routing of the assignment, and to a few random ones, excluding the
+		// peer in the source, if source has kind MessageSource::Peer.
+		//
+		// If the topology isn't known yet, the assignment is sent to the required peers
+		// once it is.
+		let n_peers_total = self.peer_views.len();
+		let source_peer = source.peer_id();
+		let peers = entry
+			.known_by
+			.keys()
+			.filter(|peer| {
+				if source_peer.as_ref() == Some(*peer) {
+					return false
+				}
+
+				topology.map_or(false, |t| t.route_to_peer(required_routing, peer)) ||
+					random_routing.sample(n_peers_total)
+			})
+			.cloned()
+			.collect::<Vec<_>>();
+
 		match entry.candidates.get_mut(claimed_candidate_index as usize) {
 			Some(candidate_entry) => {
 				// set the approval state for validator_index to Assigned
 				// unless the approval state is set already
-				candidate_entry.approvals.entry(validator_index).or_insert_with(|| {
-					(ApprovalState::Assigned(assignment.cert.clone()), local_source)
+				candidate_entry.messages.entry(validator_index).or_insert_with(|| MessageState {
+					approval_state: ApprovalState::Assigned(assignment.cert.clone()),
+					required_routing,
+					random_routing,
+					local,
 				});
 			},
 			None => {
@@ -716,20 +1057,7 @@ impl State {
 			},
 		}
 
-		// Dispatch a ApprovalDistributionV1Message::Assignment(assignment, candidate_index)
-		// to all peers in the BlockEntry's known_by set who know about the block,
-		// excluding the peer in the source, if source has kind MessageSource::Peer.
-		let maybe_peer_id = source.peer_id();
-		let mut peers = entry
-			.known_by
-			.keys()
-			.cloned()
-			.filter(|key| maybe_peer_id.as_ref().map_or(true, |id| id != key))
-			.collect::<Vec<_>>();
-
 		let assignments = vec![(assignment, claimed_candidate_index)];
-		let gossip_peers = &self.gossip_peers;
-		util::choose_random_subset(|e| gossip_peers.contains(e), &mut peers, MIN_GOSSIP_PEERS);
 
 		// Add the fingerprint of the assignment to the knowledge of each peer.
 		for peer in peers.iter() {
@@ -744,7 +1072,8 @@ impl State {
 				target: LOG_TARGET,
 				?block_hash,
 				?claimed_candidate_index,
-				?local_source,
+				?local,
+				?required_routing,
 				num_peers = peers.len(),
 				"Sending an assignment to peers",
 			);
@@ -894,23 +1223,36 @@ impl State {
 			}
 		}
 
-		let local_source = source.as_local_source();
-
 		// Invariant: none of the peers except for the `source` know about the approval.
 		metrics.on_approval_imported();
 
-		match entry.candidates.get_mut(candidate_index as usize) {
+		let required_routing = match entry.candidates.get_mut(candidate_index as usize) {
 			Some(candidate_entry) => {
 				// set the approval state for validator_index to Approved
 				// it should be in assigned state already
-				match candidate_entry.approvals.remove(&validator_index) {
-					Some((ApprovalState::Assigned(cert), _local)) => {
-						candidate_entry.approvals.insert(
+				match candidate_entry.messages.remove(&validator_index) {
+					Some(MessageState {
+						approval_state: ApprovalState::Assigned(cert),
+						required_routing,
+						random_routing,
+						local,
+					}) => {
+						candidate_entry.messages.insert(
 							validator_index,
-							(ApprovalState::Approved(cert, vote.signature.clone()), local_source),
+							MessageState {
+								approval_state: ApprovalState::Approved(
+									cert,
+									vote.signature.clone(),
+								),
+								required_routing,
+								random_routing,
+								local,
+							},
 						);
+
+						required_routing
 					},
-					Some((ApprovalState::Approved(..), _)) => {
+					Some(MessageState { approval_state: ApprovalState::Approved(..), .. }) => {
 						unreachable!(
 							"we only insert it after the fingerprint, checked the fingerprint above; qed"
 						);
@@ -924,6 +1266,8 @@ impl State {
 							?validator_index,
 							"Importing an approval we don't have an assignment for",
 						);
+
+						RequiredRouting::None
 					},
 				}
 			},
@@ -935,22 +1279,32 @@ impl State {
 					?validator_index,
 					"Expected a candidate entry on import_and_circulate_approval",
 				);
+
+				RequiredRouting::None
 			},
-		}
+		};
 
 		// Dispatch a ApprovalDistributionV1Message::Approval(vote)
-		// to all peers in the BlockEntry's known_by set who know about the block,
+		// to the peers in the BlockEntry's known_by set who know about the block and are
+		// either in the required routing of the approval or were sent its assignment by us,
 		// excluding the peer in the source, if source has kind MessageSource::Peer.
-		let maybe_peer_id = source.peer_id();
-		let mut peers = entry
+		let topology = self.topologies.get(&entry.session);
+		let source_peer = source.peer_id();
+		let assignment_fingerprint =
+			MessageFingerprint::Assignment(block_hash, candidate_index, validator_index);
+		let peers = entry
 			.known_by
-			.keys()
-			.cloned()
-			.filter(|key| maybe_peer_id.as_ref().map_or(true, |id| id != key))
-			.collect::<Vec<_>>();
+			.iter()
+			.filter(|(peer, knowledge)| {
+				if source_peer.as_ref() == Some(*peer) {
+					return false
+				}
 
-		let gossip_peers = &self.gossip_peers;
-		util::choose_random_subset(|e| gossip_peers.contains(e), &mut peers, MIN_GOSSIP_PEERS);
+				topology.map_or(false, |t| t.route_to_peer(required_routing, peer)) ||
+					knowledge.sent.contains(&assignment_fingerprint)
+			})
+			.map(|(peer, _)| peer.clone())
+			.collect::<Vec<_>>();
 
 		// Add the fingerprint of the assignment to the knowledge of each peer.
 		for peer in peers.iter() {
@@ -966,7 +1320,7 @@ impl State {
 				target: LOG_TARGET,
 				?block_hash,
 				?candidate_index,
-				?local_source,
+				?required_routing,
 				num_peers = peers.len(),
 				"Sending an approval to peers",
 			);
@@ -984,7 +1338,8 @@ impl State {
 	async fn unify_with_peer(
 		ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
 		          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
-		gossip_peers: &HashSet<PeerId>,
+		topologies: &HashMap<SessionIndex, SessionTopology>,
+		n_peers_total: usize,
 		metrics: &Metrics,
 		entries: &mut HashMap<Hash, BlockEntry>,
 		peer_id: PeerId,
@@ -992,181 +1347,93 @@ impl State {
 	) {
 		metrics.on_unify_with_peer();
 		let _timer = metrics.time_unify_with_peer();
-		let mut to_send: Vec<(Hash, MissingKnowledge)> = Vec::new();
+
+		let mut assignments = Vec::new();
+		let mut approvals = Vec::new();
+		let mut num_blocks = 0;
 
 		let view_finalized_number = view.finalized_number;
 		for head in view.into_iter() {
 			let mut block = head;
-			let interesting_blocks = std::iter::from_fn(|| {
-				// step 2.
+			loop {
 				let entry = match entries.get_mut(&block) {
 					Some(entry) if entry.number > view_finalized_number => entry,
-					_ => return None,
-				};
-				let missing_knowledge = match entry.known_by.entry(peer_id.clone()) {
-					hash_map::Entry::Occupied(e) => {
-						let missing: MissingKnowledge = entry
-							.knowledge
-							.known_messages
-							.iter()
-							.filter(|m| !e.get().contains(m))
-							.cloned()
-							.collect();
-						// step 3.
-						// We assume if peer's knowledge is complete for block N,
-						// this is also true for its ancestors.
-						// This safeguard is needed primarily in case of long finality stalls
-						// so we don't waste time in a loop for every peer.
-						if missing.is_empty() {
-							gum::trace!(
-								target: LOG_TARGET,
-								?block,
-								?peer_id,
-								"Stopping at this block, because peer knows all",
-							);
-							return None
-						}
-						missing
-					},
-					// step 4.
-					hash_map::Entry::Vacant(vacant) => {
-						let knowledge = PeerKnowledge::default();
-						vacant.insert(knowledge);
-						entry.knowledge.known_messages.clone()
-					},
+					_ => break,
 				};
-				// step 5.
-				let interesting_block = block;
-				block = entry.parent_hash.clone();
-				Some((interesting_block, missing_knowledge))
-			});
-			to_send.extend(interesting_blocks);
-		}
-
-		let is_gossip_peer = gossip_peers.contains(&peer_id);
-		let lucky = is_gossip_peer ||
-			util::gen_ratio(
-				util::MIN_GOSSIP_PEERS.saturating_sub(gossip_peers.len()),
-				util::MIN_GOSSIP_PEERS,
-			);
-		if !lucky {
-			gum::trace!(target: LOG_TARGET, ?peer_id, "Unlucky peer");
-			return
-		}
-
-		// step 6.
-		// send all assignments and approvals for all candidates in those blocks to the peer
-		Self::send_gossip_messages_to_peer(entries, ctx, peer_id, to_send).await;
-	}
-
-	async fn send_gossip_messages_to_peer(
-		entries: &mut HashMap<Hash, BlockEntry>,
-		ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
-		          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
-		peer_id: PeerId,
-		blocks: Vec<(Hash, MissingKnowledge)>,
-	) {
-		let mut assignments = Vec::new();
-		let mut approvals = Vec::new();
-		let num_blocks = blocks.len();
 
-		for (block, missing) in blocks.into_iter() {
-			let entry = match entries.get_mut(&block) {
-				Some(entry) => entry,
-				None => continue, // should be unreachable
-			};
+				// A peer in the `known_by` set of a block has already been sent all the
+				// messages of the block and of its ancestors meant for it.
+				// This safeguard is needed primarily in case of long finality stalls
+				// so we don't waste time in a loop for every peer.
+				if entry.known_by.contains_key(&peer_id) {
+					gum::trace!(
+						target: LOG_TARGET,
+						?block,
+						?peer_id,
+						"Stopping at this block, because peer knows it",
+					);
+					break
+				}
 
-			gum::trace!(
-				target: LOG_TARGET,
-				"Sending all assignments and approvals in block {} to peer {}",
-				block,
-				peer_id,
-			);
+				num_blocks += 1;
+				let topology = topologies.get(&entry.session);
+				let peer_knowledge = entry.known_by.entry(peer_id.clone()).or_default();
+
+				for (candidate_index, candidate_entry) in entry.candidates.iter_mut().enumerate() {
+					let candidate_index = candidate_index as CandidateIndex;
+					for (validator_index, message_state) in candidate_entry.messages.iter_mut() {
+						let assignment_fingerprint = MessageFingerprint::Assignment(
+							block,
+							candidate_index,
+							*validator_index,
+						);
+						let approval_fingerprint =
+							MessageFingerprint::Approval(block, candidate_index, *validator_index);
+
+						let missing_assignment = !peer_knowledge.contains(&assignment_fingerprint);
+						let missing_approval =
+							message_state.approval_state.approval_signature().is_some() &&
+								!peer_knowledge.contains(&approval_fingerprint);
+						if !missing_assignment && !missing_approval {
+							continue
+						}
 
-			for (candidate_index, candidate_entry) in entry.candidates.iter().enumerate() {
-				let candidate_index = candidate_index as u32;
-				for (validator_index, (approval_state, _is_local)) in
-					candidate_entry.approvals.iter()
-				{
-					let assignment_fingerprint = MessageFingerprint::Assignment(
-						block.clone(),
-						candidate_index,
-						validator_index.clone(),
-					);
+						// send the messages to the peer if it is in their required routing,
+						// or at random.
+						let in_topology = topology.map_or(false, |t| {
+							t.route_to_peer(message_state.required_routing, &peer_id)
+						});
+						if !in_topology && !message_state.random_routing.sample(n_peers_total) {
+							continue
+						}
 
-					match approval_state {
-						ApprovalState::Assigned(cert) => {
-							if !missing.contains(&assignment_fingerprint) {
-								gum::trace!(
-									target: LOG_TARGET,
-									?block,
-									?validator_index,
-									?candidate_index,
-									"Skipping sending known assignment",
-								);
-								continue
-							}
-							if let Some(p) = entry.known_by.get_mut(&peer_id) {
-								p.sent.insert(assignment_fingerprint);
-							}
+						if missing_assignment {
+							peer_knowledge.sent.insert(assignment_fingerprint);
 							assignments.push((
 								IndirectAssignmentCert {
-									block_hash: block.clone(),
-									validator: validator_index.clone(),
-									cert: cert.clone(),
+									block_hash: block,
+									validator: *validator_index,
+									cert: message_state.approval_state.assignment_cert().clone(),
 								},
-								candidate_index.clone(),
-							));
-						},
-						ApprovalState::Approved(assignment_cert, signature) => {
-							let fingerprint = MessageFingerprint::Approval(
-								block.clone(),
 								candidate_index,
-								validator_index.clone(),
-							);
-							if missing.contains(&assignment_fingerprint) {
-								if let Some(p) = entry.known_by.get_mut(&peer_id) {
-									p.sent.insert(assignment_fingerprint);
-								}
-								assignments.push((
-									IndirectAssignmentCert {
-										block_hash: block.clone(),
-										validator: validator_index.clone(),
-										cert: assignment_cert.clone(),
-									},
-									candidate_index.clone(),
-								));
-							} else {
-								gum::trace!(
-									target: LOG_TARGET,
-									?block,
-									?validator_index,
-									?candidate_index,
-									"Skipping sending known assignment",
-								);
-							}
-							if missing.contains(&fingerprint) {
-								if let Some(p) = entry.known_by.get_mut(&peer_id) {
-									p.sent.insert(fingerprint);
-								}
+							));
+						}
+
+						if let Some(signature) = message_state.approval_state.approval_signature() {
+							if missing_approval {
+								peer_knowledge.sent.insert(approval_fingerprint);
 								approvals.push(IndirectSignedApprovalVote {
-									block_hash: block.clone(),
-									validator: validator_index.clone(),
-									candidate_index: candidate_index.clone(),
+									block_hash: block,
+									validator: *validator_index,
+									candidate_index,
 									signature: signature.clone(),
 								});
-							} else {
-								gum::trace!(
-									target: LOG_TARGET,
-									?block,
-									?validator_index,
-									?candidate_index,
-									"Skipping sending known approval",
-								);
 							}
-						},
+						}
 					}
 				}
+
+				block = entry.parent_hash;
 			}
 		}
 
@@ -1208,6 +1475,119 @@ impl State {
 	}
 }
 
+/// Adjusts the required routing of the messages of the blocks matching `block_filter`
+/// with `routing_modifier`, and sends the messages to the peers in their required routing
+/// which don't know them yet.
+async fn adjust_required_routing_and_propagate(
+	ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
+	          + overseer::SubsystemContext<Message = ApprovalDistributionMessage>),
+	blocks: &mut HashMap<Hash, BlockEntry>,
+	topologies: &HashMap<SessionIndex, SessionTopology>,
+	block_filter: impl Fn(&mut BlockEntry) -> bool,
+	routing_modifier: impl Fn(&mut RequiredRouting, bool, &ValidatorIndex),
+) {
+	let mut peer_assignments: HashMap<PeerId, Vec<_>> = HashMap::new();
+	let mut peer_approvals: HashMap<PeerId, Vec<_>> = HashMap::new();
+
+	for (block_hash, block_entry) in blocks.iter_mut() {
+		if !block_filter(block_entry) {
+			continue
+		}
+
+		let topology = match topologies.get(&block_entry.session) {
+			Some(topology) => topology,
+			None => continue,
+		};
+
+		for (candidate_index, candidate_entry) in block_entry.candidates.iter_mut().enumerate() {
+			let candidate_index = candidate_index as CandidateIndex;
+			for (validator_index, message_state) in candidate_entry.messages.iter_mut() {
+				routing_modifier(
+					&mut message_state.required_routing,
+					message_state.local,
+					validator_index,
+				);
+
+				if message_state.required_routing.is_empty() {
+					continue
+				}
+
+				let assignment_fingerprint =
+					MessageFingerprint::Assignment(*block_hash, candidate_index, *validator_index);
+				let approval_fingerprint =
+					MessageFingerprint::Approval(*block_hash, candidate_index, *validator_index);
+
+				for (peer, peer_knowledge) in block_entry.known_by.iter_mut() {
+					if !topology.route_to_peer(message_state.required_routing, peer) {
+						continue
+					}
+
+					if !peer_knowledge.contains(&assignment_fingerprint) {
+						peer_knowledge.sent.insert(assignment_fingerprint.clone());
+						peer_assignments.entry(peer.clone()).or_default().push((
+							IndirectAssignmentCert {
+								block_hash: *block_hash,
+								validator: *validator_index,
+								cert: message_state.approval_state.assignment_cert().clone(),
+							},
+							candidate_index,
+						));
+					}
+
+					if let Some(signature) = message_state.approval_state.approval_signature() {
+						if !peer_knowledge.contains(&approval_fingerprint) {
+							peer_knowledge.sent.insert(approval_fingerprint.clone());
+							peer_approvals.entry(peer.clone()).or_default().push(
+								IndirectSignedApprovalVote {
+									block_hash: *block_hash,
+									validator: *validator_index,
+									candidate_index,
+									signature: signature.clone(),
+								},
+							);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	// the assignments go first, as peers can't import approvals without them.
+	for (peer, assignments) in peer_assignments {
+		gum::trace!(
+			target: LOG_TARGET,
+			num = assignments.len(),
+			?peer,
+			"Propagating assignments to a peer",
+		);
+
+		ctx.send_message(NetworkBridgeMessage::SendValidationMessage(
+			vec![peer],
+			protocol_v1::ValidationProtocol::ApprovalDistribution(
+				protocol_v1::ApprovalDistributionMessage::Assignments(assignments),
+			),
+		))
+		.await;
+	}
+
+	for (peer, approvals) in peer_approvals {
+		gum::trace!(
+			target: LOG_TARGET,
+			num = approvals.len(),
+			?peer,
+			"Propagating approvals to a peer",
+		);
+
+		ctx.send_message(NetworkBridgeMessage::SendValidationMessage(
+			vec![peer],
+			protocol_v1::ValidationProtocol::ApprovalDistribution(
+				protocol_v1::ApprovalDistributionMessage::Approvals(approvals),
+			),
+		))
+		.await;
+	}
+}
+
 /// Modify the reputation of a peer based on its behavior.
 async fn modify_reputation(
 	ctx: &mut (impl SubsystemContext<Message = ApprovalDistributionMessage>
@@ -1267,7 +1647,7 @@ impl ApprovalDistribution {
 				},
 				FromOverseer::Signal(OverseerSignal::BlockFinalized(_hash, number)) => {
 					gum::trace!(target: LOG_TARGET, number = %number, "finalized signal");
-					state.handle_block_finalized(number);
+					state.handle_block_finalized(&mut ctx, &self.metrics, number).await;
 				},
 				FromOverseer::Signal(OverseerSignal::Conclude) => return,
 			}
@@ -21,9 +21,10 @@ use polkadot_node_network_protocol::{our_view, view, ObservedRole};
 use polkadot_node_primitives::approval::{
 	AssignmentCertKind, VRFOutput, VRFProof, RELAY_VRF_MODULO_CONTEXT,
 };
-use polkadot_node_subsystem::messages::{AllMessages, ApprovalCheckError};
+use polkadot_node_subsystem::messages::{AllMessages, ApprovalCheckError, TopologyPeerInfo};
 use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::TimeoutExt as _;
+use polkadot_primitives::v2::AuthorityDiscoveryId;
 use std::time::Duration;
 
 type VirtualOverseer = test_helpers::TestSubsystemContextHandle<ApprovalDistributionMessage>;
@@ -156,6 +157,61 @@ fn fake_assignment_cert(block_hash: Hash, validator: ValidatorIndex) -> Indirect
 	}
 }
 
+// The peers at the positions `x` are our neighbors in our row, and those at the positions `y`
+// in our column. The position of a peer is its validator index.
+fn make_gossip_topology(
+	session: SessionIndex,
+	peers: &[PeerId],
+	x: &[usize],
+	y: &[usize],
+) -> NewGossipTopology {
+	let neighbors = |positions: &[usize]| -> HashMap<AuthorityDiscoveryId, TopologyPeerInfo> {
+		positions
+			.iter()
+			.map(|&i| {
+				let authority: AuthorityDiscoveryId =
+					sp_core::sr25519::Public::from_raw([i as u8; 32]).into();
+				let info = TopologyPeerInfo {
+					peer_ids: vec![peers[i].clone()],
+					validator_index: ValidatorIndex(i as u32),
+				};
+				(authority, info)
+			})
+			.collect()
+	};
+
+	NewGossipTopology { session, our_neighbors_x: neighbors(x), our_neighbors_y: neighbors(y) }
+}
+
+async fn send_topology(virtual_overseer: &mut VirtualOverseer, topology: NewGossipTopology) {
+	overseer_send(
+		virtual_overseer,
+		ApprovalDistributionMessage::NetworkBridgeUpdateV1(NetworkBridgeEvent::NewGossipTopology(
+			topology,
+		)),
+	)
+	.await;
+}
+
+// Returns the peers the assignments were sent to.
+async fn expect_assignments_sent(
+	virtual_overseer: &mut VirtualOverseer,
+	expected: &[(IndirectAssignmentCert, CandidateIndex)],
+) -> Vec<PeerId> {
+	assert_matches!(
+		overseer_recv(virtual_overseer).await,
+		AllMessages::NetworkBridge(NetworkBridgeMessage::SendValidationMessage(
+			peers,
+			protocol_v1::ValidationProtocol::ApprovalDistribution(
+				protocol_v1::ApprovalDistributionMessage::Assignments(assignments)
+			)
+		)) => {
+			assert_eq!(assignments, expected);
+			peers
+		}
+	)
+}
+
 async fn expect_reputation_change(
 	virtual_overseer: &mut VirtualOverseer,
 	peer_id: &PeerId,
@@ -201,6 +257,7 @@ fn try_import_the_same_assignment() {
 			number: 2,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -283,6 +340,7 @@ fn spam_attack_results_in_negative_reputation_change() {
 			number: 2,
 			candidates: vec![Default::default(); candidates_count],
 			slot: 1.into(),
+			session: 1,
 		};
 
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
@@ -365,6 +423,7 @@ fn peer_sending_us_the_same_we_just_sent_them_is_ok() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -442,6 +501,7 @@ fn import_approval_happy_path() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -528,6 +588,7 @@ fn import_approval_bad() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -605,6 +666,7 @@ fn update_our_view() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let meta_b = BlockApprovalMeta {
 			hash: hash_b,
@@ -612,6 +674,7 @@ fn update_our_view() {
 			number: 2,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let meta_c = BlockApprovalMeta {
 			hash: hash_c,
@@ -619,6 +682,7 @@ fn update_our_view() {
 			number: 3,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta_a, meta_b, meta_c]);
@@ -678,6 +742,7 @@ fn update_peer_view() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let meta_b = BlockApprovalMeta {
 			hash: hash_b,
@@ -685,6 +750,7 @@ fn update_peer_view() {
 			number: 2,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let meta_c = BlockApprovalMeta {
 			hash: hash_c,
@@ -692,6 +758,7 @@ fn update_peer_view() {
 			number: 3,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta_a, meta_b, meta_c]);
@@ -829,6 +896,7 @@ fn import_remotely_then_locally() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -913,6 +981,7 @@ fn sends_assignments_even_when_state_is_approved() {
 			number: 1,
 			candidates: vec![Default::default(); 1],
 			slot: 1.into(),
+			session: 1,
 		};
 		let msg = ApprovalDistributionMessage::NewBlocks(vec![meta]);
 		overseer_send(overseer, msg).await;
@@ -999,6 +1068,7 @@ fn race_condition_in_local_vs_remote_view_update() {
 			number: 2,
 			candidates: vec![Default::default(); candidates_count],
 			slot: 1.into(),
+			session: 1,
 		};
 
 		// This will send a peer view that is ahead of our view
@@ -1052,3 +1122,184 @@ fn race_condition_in_local_vs_remote_view_update() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn propagates_locally_generated_assignment_to_both_dimensions() {
+	let parent_hash = Hash::repeat_byte(0xFF);
+	let hash = Hash::repeat_byte(0xAA);
+	let peers: Vec<_> = (0..12).map(|_| PeerId::random()).collect();
+
+	let _ = test_harness(State::default(), |mut virtual_overseer| async move {
+		let overseer = &mut virtual_overseer;
+		for peer in &peers {
+			setup_peer_with_view(overseer, peer, view![hash]).await;
+		}
+
+		let meta = BlockApprovalMeta {
+			hash,
+			parent_hash,
+			number: 1,
+			candidates: vec![Default::default(); 1],
+			slot: 1.into(),
+			session: 1,
+		};
+		overseer_send(overseer, ApprovalDistributionMessage::NewBlocks(vec![meta])).await;
+		send_topology(overseer, make_gossip_topology(1, &peers, &[0, 1], &[2, 3])).await;
+
+		let cert = fake_assignment_cert(hash, ValidatorIndex(peers.len() as u32));
+		overseer_send(overseer, ApprovalDistributionMessage::DistributeAssignment(cert.clone(), 0))
+			.await;
+
+		// all of our neighbors, and a few random peers.
+		let recipients = expect_assignments_sent(overseer, &[(cert, 0)]).await;
+		assert!(peers[..4].iter().all(|peer| recipients.contains(peer)));
+		assert_eq!(recipients.len(), 4 + RANDOM_CIRCULATION);
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn forwards_assignment_along_the_other_dimension() {
+	let parent_hash = Hash::repeat_byte(0xFF);
+	let hash = Hash::repeat_byte(0xAA);
+	let peers: Vec<_> = (0..12).map(|_| PeerId::random()).collect();
+
+	let _ = test_harness(State::default(), |mut virtual_overseer| async move {
+		let overseer = &mut virtual_overseer;
+		for peer in &peers {
+			setup_peer_with_view(overseer, peer, view![hash]).await;
+		}
+
+		let meta = BlockApprovalMeta {
+			hash,
+			parent_hash,
+			number: 1,
+			candidates: vec![Default::default(); 1],
+			slot: 1.into(),
+			session: 1,
+		};
+		overseer_send(overseer, ApprovalDistributionMessage::NewBlocks(vec![meta])).await;
+		send_topology(overseer, make_gossip_topology(1, &peers, &[0, 1], &[2, 3])).await;
+
+		// the assignment of a validator in our row, from the validator itself.
+		let cert = fake_assignment_cert(hash, ValidatorIndex(0));
+		let msg = protocol_v1::ApprovalDistributionMessage::Assignments(vec![(cert.clone(), 0)]);
+		send_message_from_peer(overseer, &peers[0], msg).await;
+
+		assert_matches!(
+			overseer_recv(overseer).await,
+			AllMessages::ApprovalVoting(ApprovalVotingMessage::CheckAndImportAssignment(
+				assignment,
+				0u32,
+				tx,
+			)) => {
+				assert_eq!(assignment, cert);
+				tx.send(AssignmentCheckResult::Accepted).unwrap();
+			}
+		);
+		expect_reputation_change(overseer, &peers[0], BENEFIT_VALID_MESSAGE_FIRST).await;
+
+		// our column, and a few random peers.
+		let recipients = expect_assignments_sent(overseer, &[(cert, 0)]).await;
+		assert!(peers[2..4].iter().all(|peer| recipients.contains(peer)));
+		assert!(!recipients.contains(&peers[0]));
+		assert_eq!(recipients.len(), 2 + RANDOM_CIRCULATION);
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn sends_pending_messages_once_topology_is_known() {
+	let parent_hash = Hash::repeat_byte(0xFF);
+	let hash = Hash::repeat_byte(0xAA);
+	let peers: Vec<_> = (0..12).map(|_| PeerId::random()).collect();
+
+	let _ = test_harness(State::default(), |mut virtual_overseer| async move {
+		let overseer = &mut virtual_overseer;
+		for peer in &peers {
+			setup_peer_with_view(overseer, peer, view![hash]).await;
+		}
+
+		let meta = BlockApprovalMeta {
+			hash,
+			parent_hash,
+			number: 1,
+			candidates: vec![Default::default(); 1],
+			slot: 1.into(),
+			session: 1,
+		};
+		overseer_send(overseer, ApprovalDistributionMessage::NewBlocks(vec![meta])).await;
+
+		let cert = fake_assignment_cert(hash, ValidatorIndex(peers.len() as u32));
+		overseer_send(overseer, ApprovalDistributionMessage::DistributeAssignment(cert.clone(), 0))
+			.await;
+
+		// only random peers without a topology.
+		let recipients = expect_assignments_sent(overseer, &[(cert.clone(), 0)]).await;
+		assert_eq!(recipients.len(), RANDOM_CIRCULATION);
+
+		send_topology(overseer, make_gossip_topology(1, &peers, &[0, 1], &[2, 3])).await;
+
+		// the neighbors which didn't get the assignment at random get it now.
+		let mut expected: HashSet<_> =
+			peers[..4].iter().filter(|peer| !recipients.contains(peer)).cloned().collect();
+		while !expected.is_empty() {
+			let recipients = expect_assignments_sent(overseer, &[(cert.clone(), 0)]).await;
+			assert_eq!(recipients.len(), 1);
+			assert!(expected.remove(&recipients[0]));
+		}
+
+		assert!(overseer.recv().timeout(TIMEOUT).await.is_none(), "no message should be sent");
+		virtual_overseer
+	});
+}
+
+#[test]
+fn aggression_l1_sends_own_messages_of_oldest_block_to_all_peers() {
+	let l1_threshold = AggressionConfig::default().l1_threshold.unwrap();
+	let hashes: Vec<_> = (0..=l1_threshold).map(|i| Hash::repeat_byte(i as u8 + 1)).collect();
+	let peers: Vec<_> = (0..12).map(|_| PeerId::random()).collect();
+
+	let _ = test_harness(State::default(), |mut virtual_overseer| async move {
+		let overseer = &mut virtual_overseer;
+		let meta = |i: usize| BlockApprovalMeta {
+			hash: hashes[i],
+			parent_hash: if i == 0 { Hash::repeat_byte(0xFF) } else { hashes[i - 1] },
+			number: i as BlockNumber + 1,
+			candidates: vec![Default::default(); 1],
+			slot: 1.into(),
+			session: 1,
+		};
+
+		overseer_send(overseer, ApprovalDistributionMessage::NewBlocks(vec![meta(0)])).await;
+		send_topology(overseer, make_gossip_topology(1, &peers, &[0, 1], &[2, 3])).await;
+		for peer in &peers {
+			setup_peer_with_view(overseer, peer, view![hashes[0]]).await;
+		}
+
+		let cert = fake_assignment_cert(hashes[0], ValidatorIndex(peers.len() as u32));
+		overseer_send(overseer, ApprovalDistributionMessage::DistributeAssignment(cert.clone(), 0))
+			.await;
+
+		let recipients = expect_assignments_sent(overseer, &[(cert.clone(), 0)]).await;
+		assert_eq!(recipients.len(), 4 + RANDOM_CIRCULATION);
+
+		// finality lags behind by `l1_threshold` blocks now.
+		let metas = (1..hashes.len()).map(meta).collect();
+		overseer_send(overseer, ApprovalDistributionMessage::NewBlocks(metas)).await;
+
+		// the rest of the peers get our assignment on the oldest block.
+		let mut expected: HashSet<_> =
+			peers.iter().filter(|peer| !recipients.contains(peer)).cloned().collect();
+		while !expected.is_empty() {
+			let recipients = expect_assignments_sent(overseer, &[(cert.clone(), 0)]).await;
+			assert_eq!(recipients.len(), 1);
+			assert!(expected.remove(&recipients[0]));
+		}
+
+		assert!(overseer.recv().timeout(TIMEOUT).await.is_none(), "no message should be sent");
+		virtual_overseer
+	});
+}
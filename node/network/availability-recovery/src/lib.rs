@@ -37,7 +37,9 @@ use lru::LruCache;
 use rand::seq::SliceRandom;
 
 use fatality::Nested;
-use polkadot_erasure_coding::{branch_hash, branches, obtain_chunks_v1, recovery_threshold};
+use polkadot_erasure_coding::{
+	branch_hash, branches, obtain_chunks_v1, recovery_threshold, systematic_recovery_threshold,
+};
 #[cfg(not(test))]
 use polkadot_node_network_protocol::request_response::CHUNK_REQUEST_TIMEOUT;
 use polkadot_node_network_protocol::{
@@ -56,7 +58,10 @@ use polkadot_primitives::v2::{
 use polkadot_subsystem::{
 	errors::RecoveryError,
 	jaeger,
-	messages::{AvailabilityRecoveryMessage, AvailabilityStoreMessage, NetworkBridgeMessage},
+	messages::{
+		AvailabilityRecoveryMessage, AvailabilityStoreMessage, NetworkBridgeMessage,
+		RecoveryStrategy,
+	},
 	overseer::{self, Subsystem},
 	ActiveLeavesUpdate, FromOverseer, OverseerSignal, SpawnedSubsystem, SubsystemContext,
 	SubsystemError, SubsystemResult, SubsystemSender,
@@ -99,7 +104,10 @@ const TIMEOUT_START_NEW_REQUESTS: Duration = Duration::from_millis(100);
 
 /// The Availability Recovery Subsystem.
 pub struct AvailabilityRecoverySubsystem {
+	/// Whether the full data may be requested from the backing group.
 	fast_path: bool,
+	/// Whether the data may be recovered from the systematic chunks.
+	systematic_chunks: bool,
 	/// Receiver for available data requests.
 	req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 	/// Metrics for this subsystem.
@@ -113,6 +121,11 @@ struct RequestFromBackers {
 }
 
 struct RequestChunksFromValidators {
+	/// Whether only the systematic chunks are requested, each from the validator holding it.
+	///
+	/// As any of them failing to respond renders recovery impossible, failed requests are not
+	/// retried.
+	systematic: bool,
 	/// How many request have been unsuccessful so far.
 	error_count: usize,
 	/// Total number of responses that have been received.
@@ -137,6 +150,12 @@ struct RecoveryParams {
 	/// The number of pieces needed.
 	threshold: usize,
 
+	/// The number of systematic chunks, which are needed to recover without decoding.
+	systematic_threshold: usize,
+
+	/// Whether to attempt recovery from the systematic chunks before resorting to regular chunks.
+	systematic_chunks: bool,
+
 	/// A hash of the relevant candidate.
 	candidate_hash: CandidateHash,
 
@@ -149,7 +168,7 @@ struct RecoveryParams {
 
 /// Source the availability data either by means
 /// of direct request response protocol to
-/// backers (a.k.a. fast-path), or recover from chunks.
+/// backers (a.k.a. fast-path), or recover from (systematic) chunks.
 enum Source {
 	RequestFromBackers(RequestFromBackers),
 	RequestChunks(RequestChunksFromValidators),
@@ -253,6 +272,7 @@ impl RequestChunksFromValidators {
 		shuffling.shuffle(&mut rand::thread_rng());
 
 		RequestChunksFromValidators {
+			systematic: false,
 			error_count: 0,
 			total_received_responses: 0,
 			shuffling: shuffling.into(),
@@ -261,17 +281,55 @@ impl RequestChunksFromValidators {
 		}
 	}
 
+	fn new_systematic(systematic_threshold: usize) -> Self {
+		let mut shuffling: Vec<_> = (0..systematic_threshold as u32).map(ValidatorIndex).collect();
+		shuffling.shuffle(&mut rand::thread_rng());
+
+		RequestChunksFromValidators {
+			systematic: true,
+			error_count: 0,
+			total_received_responses: 0,
+			shuffling: shuffling.into(),
+			received_chunks: HashMap::new(),
+			requesting_chunks: FuturesUndead::new(),
+		}
+	}
+
+	/// Continue by requesting regular chunks from all validators, keeping the systematic chunks
+	/// received or requested so far. Validators which were asked for their systematic chunk are
+	/// not asked again.
+	fn fall_back_to_regular_chunks(&mut self, params: &RecoveryParams) -> Self {
+		let mut regular = Self::new(params.validators.len() as _);
+		let unrequested = &self.shuffling;
+		regular
+			.shuffling
+			.retain(|i| i.0 as usize >= params.systematic_threshold || unrequested.contains(i));
+		regular.received_chunks = std::mem::take(&mut self.received_chunks);
+		regular.requesting_chunks =
+			std::mem::replace(&mut self.requesting_chunks, FuturesUndead::new());
+
+		regular
+	}
+
+	fn threshold(&self, params: &RecoveryParams) -> usize {
+		if self.systematic {
+			params.systematic_threshold
+		} else {
+			params.threshold
+		}
+	}
+
 	fn is_unavailable(&self, params: &RecoveryParams) -> bool {
 		is_unavailable(
 			self.received_chunks.len(),
 			self.requesting_chunks.total_len(),
 			self.shuffling.len(),
-			params.threshold,
+			self.threshold(params),
 		)
 	}
 
 	fn can_conclude(&self, params: &RecoveryParams) -> bool {
-		self.received_chunks.len() >= params.threshold || self.is_unavailable(params)
+		self.received_chunks.len() >= self.threshold(params) || self.is_unavailable(params)
 	}
 
 	/// Desired number of parallel requests.
@@ -304,7 +362,7 @@ impl RequestChunksFromValidators {
 		params: &RecoveryParams,
 		sender: &mut impl SubsystemSender,
 	) {
-		let num_requests = self.get_desired_request_count(params.threshold);
+		let num_requests = self.get_desired_request_count(self.threshold(params));
 		let mut requests = Vec::with_capacity(num_requests - self.requesting_chunks.len());
 
 		while self.requesting_chunks.len() < num_requests {
@@ -434,12 +492,16 @@ impl RequestChunksFromValidators {
 								metrics.on_chunk_request_error();
 							}
 
-							self.shuffling.push_front(validator_index);
+							if !self.systematic {
+								self.shuffling.push_front(validator_index);
+							}
 						},
 						RequestError::Canceled(_) => {
 							metrics.on_chunk_request_error();
 
-							self.shuffling.push_front(validator_index);
+							if !self.systematic {
+								self.shuffling.push_front(validator_index);
+							}
 						},
 					}
 				},
@@ -477,6 +539,12 @@ impl RequestChunksFromValidators {
 					self.shuffling.retain(|i| !chunk_indices.contains(i));
 
 					for chunk in chunks {
+						// Other chunks are of no use for systematic recovery.
+						if self.systematic && chunk.index.0 as usize >= params.systematic_threshold
+						{
+							continue
+						}
+
 						self.received_chunks.insert(chunk.index, chunk);
 					}
 				},
@@ -500,6 +568,7 @@ impl RequestChunksFromValidators {
 					requesting = %self.requesting_chunks.len(),
 					total_requesting = %self.requesting_chunks.total_len(),
 					n_validators = %params.validators.len(),
+					systematic = %self.systematic,
 					"Data recovery is not possible",
 				);
 
@@ -513,14 +582,20 @@ impl RequestChunksFromValidators {
 			// If received_chunks has more than threshold entries, attempt to recover the data.
 			// If that fails, or a re-encoding of it doesn't match the expected erasure root,
 			// return Err(RecoveryError::Invalid)
-			if self.received_chunks.len() >= params.threshold {
+			if self.received_chunks.len() >= self.threshold(params) {
 				drop(recovery_possible);
 				let recovery_duration = metrics.time_erasure_recovery();
 
-				return match polkadot_erasure_coding::reconstruct_v1(
-					params.validators.len(),
-					self.received_chunks.values().map(|c| (&c.chunk[..], c.index.0 as usize)),
-				) {
+				let n_validators = params.validators.len();
+				let chunks =
+					self.received_chunks.values().map(|c| (&c.chunk[..], c.index.0 as usize));
+				let recovered = if self.systematic {
+					polkadot_erasure_coding::reconstruct_from_systematic_v1(n_validators, chunks)
+				} else {
+					polkadot_erasure_coding::reconstruct_v1(n_validators, chunks)
+				};
+
+				return match recovered {
 					Ok(data) => {
 						if reconstructed_data_matches_root(
 							params.validators.len(),
@@ -647,13 +722,30 @@ impl<S: SubsystemSender> RecoveryTask<S> {
 						Ok(data) => break Ok(data),
 						Err(RecoveryError::Invalid) => break Err(RecoveryError::Invalid),
 						Err(RecoveryError::Unavailable) =>
-							self.source = Source::RequestChunks(RequestChunksFromValidators::new(
-								self.params.validators.len() as _,
-							)),
+							self.source = Source::RequestChunks(if self.params.systematic_chunks {
+								RequestChunksFromValidators::new_systematic(
+									self.params.systematic_threshold,
+								)
+							} else {
+								RequestChunksFromValidators::new(self.params.validators.len() as _)
+							}),
+					}
+				},
+				Source::RequestChunks(ref mut from_all) => {
+					match from_all.run(&self.params, &mut self.sender).await {
+						Err(RecoveryError::Unavailable) if from_all.systematic => {
+							gum::debug!(
+								target: LOG_TARGET,
+								candidate_hash = ?self.params.candidate_hash,
+								"Systematic recovery failed, falling back to regular chunks",
+							);
+
+							let regular = from_all.fall_back_to_regular_chunks(&self.params);
+							self.source = Source::RequestChunks(regular);
+						},
+						result => break result,
 					}
 				},
-				Source::RequestChunks(ref mut from_all) =>
-					break from_all.run(&self.params, &mut self.sender).await,
 			}
 		}
 	}
@@ -803,6 +895,7 @@ async fn launch_recovery_task<Context>(
 	session_info: SessionInfo,
 	receipt: CandidateReceipt,
 	backing_group: Option<GroupIndex>,
+	systematic_chunks: bool,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 ) -> error::Result<()>
@@ -816,6 +909,8 @@ where
 		validator_authority_keys: session_info.discovery_keys.clone(),
 		validators: session_info.validators.clone(),
 		threshold: recovery_threshold(session_info.validators.len())?,
+		systematic_threshold: systematic_recovery_threshold(session_info.validators.len())?,
+		systematic_chunks,
 		candidate_hash,
 		erasure_root: receipt.descriptor.erasure_root,
 		metrics: metrics.clone(),
@@ -825,7 +920,11 @@ where
 		.and_then(|g| session_info.validator_groups.get(g.0 as usize))
 		.map(|group| Source::RequestFromBackers(RequestFromBackers::new(group.clone())))
 		.unwrap_or_else(|| {
-			Source::RequestChunks(RequestChunksFromValidators::new(params.validators.len() as _))
+			Source::RequestChunks(if systematic_chunks {
+				RequestChunksFromValidators::new_systematic(params.systematic_threshold)
+			} else {
+				RequestChunksFromValidators::new(params.validators.len() as _)
+			})
 		});
 
 	let recovery_task = RecoveryTask { sender: ctx.sender().clone(), params, source: phase };
@@ -856,6 +955,7 @@ async fn handle_recover<Context>(
 	receipt: CandidateReceipt,
 	session_index: SessionIndex,
 	backing_group: Option<GroupIndex>,
+	systematic_chunks: bool,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 ) -> error::Result<()>
@@ -903,6 +1003,7 @@ where
 				session_info,
 				receipt,
 				backing_group,
+				systematic_chunks,
 				response_sender,
 				metrics,
 			)
@@ -935,12 +1036,23 @@ where
 
 impl AvailabilityRecoverySubsystem {
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which starts with a fast path to
-	/// request data from backers.
+	/// request data from backers, then tries systematic chunks, as far as the requested
+	/// [`RecoveryStrategy`] allows.
 	pub fn with_fast_path(
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: true, req_receiver, metrics }
+		Self { fast_path: true, systematic_chunks: true, req_receiver, metrics }
+	}
+
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which never requests data from
+	/// backers, but tries systematic chunks first, as far as the requested [`RecoveryStrategy`]
+	/// allows.
+	pub fn with_systematic_chunks(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self { fast_path: false, systematic_chunks: true, req_receiver, metrics }
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests only chunks
@@ -948,7 +1060,7 @@ impl AvailabilityRecoverySubsystem {
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: false, req_receiver, metrics }
+		Self { fast_path: false, systematic_chunks: false, req_receiver, metrics }
 	}
 
 	async fn run<Context>(self, mut ctx: Context) -> SubsystemResult<()>
@@ -957,7 +1069,7 @@ impl AvailabilityRecoverySubsystem {
 		Context: overseer::SubsystemContext<Message = AvailabilityRecoveryMessage>,
 	{
 		let mut state = State::default();
-		let Self { fast_path, mut req_receiver, metrics } = self;
+		let Self { fast_path, systematic_chunks, mut req_receiver, metrics } = self;
 
 		loop {
 			let recv_req = req_receiver.recv(|| vec![COST_INVALID_REQUEST]).fuse();
@@ -976,7 +1088,7 @@ impl AvailabilityRecoverySubsystem {
 								AvailabilityRecoveryMessage::RecoverAvailableData(
 									receipt,
 									session_index,
-									maybe_backing_group,
+									strategy,
 									response_sender,
 								) => {
 									if let Err(e) = handle_recover(
@@ -984,7 +1096,8 @@ impl AvailabilityRecoverySubsystem {
 										&mut ctx,
 										receipt,
 										session_index,
-										maybe_backing_group.filter(|_| fast_path),
+										strategy.backing_group().filter(|_| fast_path),
+										strategy.systematic_chunks() && systematic_chunks,
 										response_sender,
 										&metrics,
 									).await {
@@ -110,6 +110,41 @@ fn test_harness_chunks_only<T: Future<Output = (VirtualOverseer, RequestResponse
 	.unwrap();
 }
 
+fn test_harness_systematic_chunks<T: Future<Output = (VirtualOverseer, RequestResponseConfig)>>(
+	test: impl FnOnce(VirtualOverseer, RequestResponseConfig) -> T,
+) {
+	let _ = env_logger::builder()
+		.is_test(true)
+		.filter(Some("polkadot_availability_recovery"), log::LevelFilter::Trace)
+		.try_init();
+
+	let pool = sp_core::testing::TaskExecutor::new();
+
+	let (context, virtual_overseer) = make_subsystem_context(pool.clone());
+
+	let (collation_req_receiver, req_cfg) = IncomingRequest::get_config_receiver();
+	let subsystem = AvailabilityRecoverySubsystem::with_systematic_chunks(
+		collation_req_receiver,
+		Metrics::new_dummy(),
+	);
+	let subsystem = subsystem.run(context);
+
+	let test_fut = test(virtual_overseer, req_cfg);
+
+	futures::pin_mut!(test_fut);
+	futures::pin_mut!(subsystem);
+
+	executor::block_on(future::join(
+		async move {
+			let (mut overseer, _req_cfg) = test_fut.await;
+			overseer_signal(&mut overseer, OverseerSignal::Conclude).await;
+		},
+		subsystem,
+	))
+	.1
+	.unwrap();
+}
+
 const TIMEOUT: Duration = Duration::from_millis(300);
 
 macro_rules! delay {
@@ -191,6 +226,10 @@ impl TestState {
 		recovery_threshold(self.validators.len()).unwrap()
 	}
 
+	fn systematic_threshold(&self) -> usize {
+		systematic_recovery_threshold(self.validators.len()).unwrap()
+	}
+
 	fn impossibility_threshold(&self) -> usize {
 		self.validators.len() - self.threshold() + 1
 	}
@@ -490,7 +529,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -527,7 +566,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				new_candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -576,7 +615,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -613,7 +652,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				new_candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -662,7 +701,7 @@ fn bad_merkle_path_leads_to_recovery_error() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -720,7 +759,7 @@ fn wrong_chunk_index_leads_to_recovery_error() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -793,7 +832,7 @@ fn invalid_erasure_coding_leads_to_invalid_error() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -842,7 +881,7 @@ fn fast_path_backing_group_recovers() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -892,7 +931,7 @@ fn no_answers_in_fast_path_causes_chunk_requests() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -931,6 +970,122 @@ fn no_answers_in_fast_path_causes_chunk_requests() {
 	});
 }
 
+#[test]
+fn systematic_chunks_are_requested_instead_of_backers() {
+	let test_state = TestState::default();
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+		let systematic_threshold = test_state.systematic_threshold();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		test_state
+			.test_chunk_requests(candidate_hash, &mut virtual_overseer, systematic_threshold, |i| {
+				assert!(i < systematic_threshold, "Only systematic chunks are requested");
+				Has::Yes
+			})
+			.await;
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
+#[test]
+fn failed_systematic_recovery_falls_back_to_regular_chunks() {
+	let test_state = TestState::default();
+
+	test_harness_systematic_chunks(|mut virtual_overseer, req_cfg| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash: test_state.current.clone(),
+				number: 1,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			})),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				RecoveryStrategy::SystematicChunksFirst,
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+		let systematic_threshold = test_state.systematic_threshold();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		// The holder of the last systematic chunk does not have it.
+		test_state
+			.test_chunk_requests(candidate_hash, &mut virtual_overseer, systematic_threshold, |i| {
+				if i + 1 < systematic_threshold {
+					Has::Yes
+				} else {
+					Has::No
+				}
+			})
+			.await;
+
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		// The systematic chunks received so far, or still in flight, count towards the threshold.
+		test_state
+			.test_chunk_requests(
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.threshold() - (systematic_threshold - 1),
+				|_| Has::Yes,
+			)
+			.await;
+
+		// Recovered data should match the original one.
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		(virtual_overseer, req_cfg)
+	});
+}
+
 #[test]
 fn task_canceled_when_receivers_dropped() {
 	let test_state = TestState::default();
@@ -954,7 +1109,7 @@ fn task_canceled_when_receivers_dropped() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -996,7 +1151,7 @@ fn chunks_retry_until_all_nodes_respond() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -1057,7 +1212,7 @@ fn not_returning_requests_wont_stall_retrieval() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -1129,7 +1284,7 @@ fn all_not_returning_requests_still_recovers_on_return() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				Some(GroupIndex(0)),
+				RecoveryStrategy::BackersFirst(GroupIndex(0)),
 				tx,
 			),
 		)
@@ -1206,7 +1361,7 @@ fn returns_early_if_we_have_the_data() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -1243,7 +1398,7 @@ fn does_not_query_local_validator() {
 			AvailabilityRecoveryMessage::RecoverAvailableData(
 				test_state.candidate.clone(),
 				test_state.session_index,
-				None,
+				RecoveryStrategy::ChunksOnly,
 				tx,
 			),
 		)
@@ -31,10 +31,13 @@ use polkadot_node_network_protocol::{
 };
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
 use polkadot_overseer::gen::{OverseerError, Subsystem};
-use polkadot_primitives::v2::{BlockNumber, Hash};
+use polkadot_primitives::v2::{AuthorityDiscoveryId, BlockNumber, Hash, ValidatorIndex};
 use polkadot_subsystem::{
 	errors::{SubsystemError, SubsystemResult},
-	messages::{AllMessages, CollatorProtocolMessage, NetworkBridgeEvent, NetworkBridgeMessage},
+	messages::{
+		AllMessages, CollatorProtocolMessage, NetworkBridgeEvent, NetworkBridgeMessage,
+		NewGossipTopology, TopologyPeerInfo,
+	},
 	overseer, ActivatedLeaf, ActiveLeavesUpdate, FromOverseer, OverseerSignal, SpawnedSubsystem,
 	SubsystemContext, SubsystemSender,
 };
@@ -45,7 +48,7 @@ use polkadot_subsystem::{
 pub use polkadot_node_network_protocol::peer_set::{peer_sets_info, IsAuthority};
 
 use std::{
-	collections::{hash_map, HashMap, HashSet},
+	collections::{hash_map, HashMap},
 	sync::Arc,
 };
 
@@ -590,30 +593,29 @@ where
 						).await;
 					}
 					NetworkBridgeMessage::NewGossipTopology {
-						our_neighbors,
+						session,
+						our_neighbors_x,
+						our_neighbors_y,
 					} => {
 						gum::debug!(
 							target: LOG_TARGET,
 							action = "NewGossipTopology",
-							neighbors = our_neighbors.len(),
+							?session,
+							neighbors_x = our_neighbors_x.len(),
+							neighbors_y = our_neighbors_y.len(),
 							"Gossip topology has changed",
 						);
 
 						let ads = &mut authority_discovery_service;
-						let mut gossip_peers = HashSet::with_capacity(our_neighbors.len());
-						for authority in our_neighbors {
-							let addr = get_peer_id_by_authority_id(
-								ads,
-								authority.clone(),
-							).await;
-
-							if let Some(peer_id) = addr {
-								gossip_peers.insert(peer_id);
-							}
-						}
+						let our_neighbors_x = resolve_topology_peers(ads, our_neighbors_x).await;
+						let our_neighbors_y = resolve_topology_peers(ads, our_neighbors_y).await;
 
 						dispatch_validation_event_to_all_unbounded(
-							NetworkBridgeEvent::NewGossipTopology(gossip_peers),
+							NetworkBridgeEvent::NewGossipTopology(NewGossipTopology {
+								session,
+								our_neighbors_x,
+								our_neighbors_y,
+							}),
 							ctx.sender(),
 						);
 					}
@@ -933,6 +935,25 @@ where
 	}
 }
 
+// Resolves the peer IDs of our neighbors in one dimension of the gossip topology.
+//
+// Neighbors without any known peer ID are kept, as their validator index still
+// determines how their messages are routed.
+async fn resolve_topology_peers<AD: validator_discovery::AuthorityDiscovery>(
+	ads: &mut AD,
+	neighbors: HashMap<AuthorityDiscoveryId, ValidatorIndex>,
+) -> HashMap<AuthorityDiscoveryId, TopologyPeerInfo> {
+	let mut peers = HashMap::with_capacity(neighbors.len());
+	for (authority, validator_index) in neighbors {
+		let peer_ids =
+			get_peer_id_by_authority_id(ads, authority.clone()).await.into_iter().collect();
+
+		peers.insert(authority, TopologyPeerInfo { peer_ids, validator_index });
+	}
+
+	peers
+}
+
 fn construct_view(
 	live_heads: impl DoubleEndedIterator<Item = Hash>,
 	finalized_number: BlockNumber,
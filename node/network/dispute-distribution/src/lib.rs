@@ -47,7 +47,11 @@ use polkadot_subsystem::{
 /// In addition the `DisputeSender` will query the coordinator for active disputes on each
 /// [`DisputeSender::update_leaves`] call and will initiate sending (start a `SendTask`) for every,
 /// to this subsystem, unknown dispute. This is to make sure, we get our vote out, even on
-/// restarts.
+/// restarts. The votes needed for those are retrieved from the coordinator in a single query.
+///
+/// Starting a `SendTask` or refreshing its sends is rate limited by `SEND_RATE_LIMIT`, so
+/// receivers don't get flooded with requests when many disputes are raised at once. Disputes
+/// waiting for the rate limit are queued, so the subsystem keeps handling messages meanwhile.
 ///
 ///	The actual work of sending and keeping track of transmission attempts to each validator for a
 ///	particular dispute are done by [`SendTask`].  The purpose of the `DisputeSender` is to keep
@@ -70,9 +74,13 @@ use self::sender::{DisputeSender, TaskFinish};
 ///	- Drop all messages from non validator nodes, for this it requires the [`AuthorityDiscovery`]
 ///	service.
 ///	- Drop messages from a node, if we are already importing a message from that node (flood).
+///	- Confirm messages from a node for a candidate, which we already confirmed to that node, right
+///	away without importing them again (duplicate).
 ///	- Drop messages from nodes, that provided us messages where the statement import failed.
 ///	- Drop any obviously invalid votes (invalid signatures for example).
 ///	- Ban peers whose votes were deemed invalid.
+///	- Lower the reputation of peers sending floods via the network bridge, or duplicates via the
+///	confirmation.
 ///
 /// For successfully imported votes, we will confirm the receipt of the message back to the sender.
 /// This way a received confirmation guarantees, that the vote has been stored to disk by the
@@ -179,7 +187,9 @@ where
 			.map_err(FatalError::SpawnTask)?;
 
 		loop {
-			let message = MuxedMessage::receive(&mut ctx, &mut self.sender_rx).await;
+			let message =
+				MuxedMessage::receive(&mut ctx, &mut self.sender_rx, &mut self.disputes_sender)
+					.await;
 			match message {
 				MuxedMessage::Subsystem(result) => {
 					let result = match result? {
@@ -200,6 +210,14 @@ where
 						.on_task_message(result.ok_or(FatalError::SenderExhausted)?)
 						.await;
 				},
+				MuxedMessage::SendReady => {
+					let result = self
+						.disputes_sender
+						.send_next(&mut ctx, &mut self.runtime)
+						.await
+						.map_err(Into::into);
+					log_error(result, "on SendReady")?;
+				},
 			}
 		}
 	}
@@ -228,7 +246,7 @@ where
 	) -> Result<()> {
 		match msg {
 			DisputeDistributionMessage::SendDispute(dispute_msg) =>
-				self.disputes_sender.start_sender(ctx, &mut self.runtime, dispute_msg).await?,
+				self.disputes_sender.start_sender(dispute_msg),
 		}
 		Ok(())
 	}
@@ -241,6 +259,8 @@ enum MuxedMessage {
 	Subsystem(FatalResult<FromOverseer<DisputeDistributionMessage>>),
 	/// Messages from spawned sender background tasks.
 	Sender(Option<TaskFinish>),
+	/// The rate limit permits starting or refreshing the next queued dispute.
+	SendReady,
 }
 
 impl MuxedMessage {
@@ -248,14 +268,17 @@ impl MuxedMessage {
 		ctx: &mut (impl SubsystemContext<Message = DisputeDistributionMessage>
 		          + overseer::SubsystemContext<Message = DisputeDistributionMessage>),
 		from_sender: &mut mpsc::Receiver<TaskFinish>,
+		disputes_sender: &mut DisputeSender,
 	) -> Self {
 		// We are only fusing here to make `select` happy, in reality we will quit if the stream
 		// ends.
 		let from_overseer = ctx.recv().fuse();
-		futures::pin_mut!(from_overseer, from_sender);
+		let send_ready = disputes_sender.ready_to_send().fuse();
+		futures::pin_mut!(from_overseer, from_sender, send_ready);
 		futures::select!(
 			msg = from_overseer => MuxedMessage::Subsystem(msg.map_err(FatalError::SubsystemReceive)),
 			msg = from_sender.next() => MuxedMessage::Sender(msg),
+			() = send_ready => MuxedMessage::SendReady,
 		)
 	}
 }
@@ -18,7 +18,7 @@ use polkadot_node_subsystem_util::{
 	metrics,
 	metrics::{
 		prometheus,
-		prometheus::{Counter, CounterVec, Opts, PrometheusError, Registry, U64},
+		prometheus::{Counter, CounterVec, Gauge, Opts, PrometheusError, Registry, U64},
 	},
 };
 
@@ -28,6 +28,12 @@ pub const SUCCEEDED: &'static str = "succeeded";
 /// Label for fail counters.
 pub const FAILED: &'static str = "failed";
 
+/// Label for requests dropped, because of another request of the same peer being imported.
+pub const FLOOD: &'static str = "flood";
+
+/// Label for requests dropped, because the peer is banned.
+pub const BANNED: &'static str = "banned";
+
 /// Dispute Distribution metrics.
 #[derive(Clone, Default)]
 pub struct Metrics(Option<MetricsInner>);
@@ -47,6 +53,12 @@ struct MetricsInner {
 	/// We both have successful imports and failed imports here.
 	imported_requests: CounterVec<U64>,
 
+	/// Number of requests dropped without import, by reason.
+	dropped_requests: CounterVec<U64>,
+
+	/// Number of requests whose votes are currently being imported.
+	pending_imports: Gauge<U64>,
+
 	/// The duration of issued dispute request to response.
 	time_dispute_request: prometheus::Histogram,
 }
@@ -78,6 +90,20 @@ impl Metrics {
 		}
 	}
 
+	/// A request got dropped without importing its votes.
+	pub fn on_dropped_request(&self, reason: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.dropped_requests.with_label_values(&[reason]).inc()
+		}
+	}
+
+	/// Set the number of requests whose votes are currently being imported.
+	pub fn on_pending_imports(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.pending_imports.set(count as u64)
+		}
+	}
+
 	/// Get a timer to time request/response duration.
 	pub fn time_dispute_request(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.time_dispute_request.start_timer())
@@ -114,6 +140,23 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			dropped_requests: prometheus::register(
+				CounterVec::new(
+					Opts::new(
+						"polkadot_parachain_dispute_distribution_dropped_requests",
+						"Total number of received requests dropped without import.",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+			pending_imports: prometheus::register(
+				Gauge::new(
+					"polkadot_parachain_dispute_distribution_pending_imports",
+					"Number of received requests whose votes are currently being imported.",
+				)?,
+				registry,
+			)?,
 			time_dispute_request: prometheus::register(
 				prometheus::Histogram::with_opts(prometheus::HistogramOpts::new(
 					"polkadot_parachain_dispute_distribution_time_dispute_request",
@@ -40,13 +40,16 @@ use polkadot_node_network_protocol::{
 };
 use polkadot_node_primitives::DISPUTE_WINDOW;
 use polkadot_node_subsystem_util::{runtime, runtime::RuntimeInfo};
+use polkadot_primitives::v2::CandidateHash;
 use polkadot_subsystem::{
-	messages::{AllMessages, DisputeCoordinatorMessage, ImportStatementsResult},
+	messages::{
+		AllMessages, DisputeCoordinatorMessage, ImportStatementsResult, NetworkBridgeMessage,
+	},
 	SubsystemSender,
 };
 
 use crate::{
-	metrics::{FAILED, SUCCEEDED},
+	metrics::{BANNED, FAILED, FLOOD, SUCCEEDED},
 	Metrics, LOG_TARGET,
 };
 
@@ -57,10 +60,18 @@ const COST_INVALID_REQUEST: Rep = Rep::CostMajor("Received message could not be
 const COST_INVALID_SIGNATURE: Rep = Rep::Malicious("Signatures were invalid.");
 const COST_INVALID_CANDIDATE: Rep = Rep::Malicious("Reported candidate was not available.");
 const COST_NOT_A_VALIDATOR: Rep = Rep::CostMajor("Reporting peer was not a validator.");
+/// Cost for sending a request while another one of the same peer is still being imported.
+pub const COST_APPARENT_FLOOD: Rep = Rep::CostMinor("Peer exceeded the rate limit.");
+/// Cost for sending a request we already confirmed to the same peer.
+pub const COST_DUPLICATE_REQUEST: Rep =
+	Rep::CostMinor("Peer sent a dispute request which was already confirmed.");
 
 /// How many statement imports we want to issue in parallel:
 pub const MAX_PARALLEL_IMPORTS: usize = 10;
 
+/// How many confirmed requests we remember for confirming them again.
+const CONFIRMED_REQUESTS_CACHE_SIZE: usize = 1000;
+
 /// State for handling incoming `DisputeRequest` messages.
 ///
 /// This is supposed to run as its own task in order to easily impose back pressure on the incoming
@@ -88,6 +99,12 @@ pub struct DisputesReceiver<Sender, AD> {
 	/// already know the peer is malicious.
 	banned_peers: LruCache<PeerId, ()>,
 
+	/// Recently confirmed requests by peer and candidate.
+	///
+	/// A peer sends us a request for the same candidate again if our confirmation got lost, so we
+	/// confirm those right away instead of importing the votes again.
+	confirmed_requests: LruCache<(PeerId, CandidateHash), ()>,
+
 	/// Log received requests.
 	metrics: Metrics,
 }
@@ -101,7 +118,7 @@ enum MuxedMessage {
 	/// - We need to make sure responses are actually sent (therefore we need to await futures
 	/// promptly).
 	/// - We need to update `banned_peers` accordingly to the result.
-	ConfirmedImport(JfyiErrorResult<(PeerId, ImportStatementsResult)>),
+	ConfirmedImport(JfyiErrorResult<(PeerId, CandidateHash, ImportStatementsResult)>),
 
 	/// A new request has arrived and should be handled.
 	NewRequest(IncomingRequest<DisputeRequest>),
@@ -156,6 +173,7 @@ where
 			// Size of MAX_PARALLEL_IMPORTS ensures we are going to immediately get rid of any
 			// malicious requests still pending in the incoming queue.
 			banned_peers: LruCache::new(MAX_PARALLEL_IMPORTS),
+			confirmed_requests: LruCache::new(CONFIRMED_REQUESTS_CACHE_SIZE),
 			metrics,
 		}
 	}
@@ -209,14 +227,38 @@ where
 			return Err(JfyiError::NotAValidator(peer).into())
 		}
 
-		// Immediately drop requests from peers that already have requests in flight or have
-		// been banned recently (flood protection):
-		if self.pending_imports.peer_is_pending(&peer) || self.banned_peers.contains(&peer) {
+		// Immediately drop requests from peers that have been banned recently:
+		if self.banned_peers.contains(&peer) {
+			gum::trace!(target: LOG_TARGET, ?peer, "Dropping message from banned peer");
+			self.metrics.on_dropped_request(BANNED);
+			return Ok(())
+		}
+
+		// Confirm again what we already confirmed, the peer likely did not get our response. As
+		// honest peers rarely need to do this, it still costs a little reputation:
+		let candidate_hash = incoming.payload.0.candidate_receipt.hash();
+		if self.confirmed_requests.contains(&(peer, candidate_hash)) {
 			gum::trace!(
 				target: LOG_TARGET,
 				?peer,
-				"Dropping message from peer (banned/pending import)"
+				?candidate_hash,
+				"Confirming already confirmed request again"
 			);
+			incoming
+				.send_outgoing_response(OutgoingResponse {
+					result: Ok(DisputeResponse::Confirmed),
+					reputation_changes: vec![COST_DUPLICATE_REQUEST],
+					sent_feedback: None,
+				})
+				.map_err(|_| JfyiError::SendResponse(peer))?;
+			return Ok(())
+		}
+
+		// Drop requests from peers that already have requests in flight (flood protection):
+		if self.pending_imports.peer_is_pending(&peer) {
+			gum::trace!(target: LOG_TARGET, ?peer, "Dropping message from peer (pending import)");
+			self.metrics.on_dropped_request(FLOOD);
+			self.report_peer(peer, COST_APPARENT_FLOOD).await;
 			return Ok(())
 		}
 
@@ -276,35 +318,48 @@ where
 			))
 			.await;
 
-		self.pending_imports.push(peer, confirmation_rx, pending_response);
+		self.pending_imports
+			.push(peer, candidate_hash, confirmation_rx, pending_response);
+		self.metrics.on_pending_imports(self.pending_imports.len());
 		Ok(())
 	}
 
 	/// Await an import and ban any misbehaving peers.
 	///
-	/// In addition we report import metrics.
+	/// Successful imports are remembered, so repeated requests can be confirmed right away. In
+	/// addition we report import metrics.
 	fn ban_bad_peer(
 		&mut self,
-		result: JfyiErrorResult<(PeerId, ImportStatementsResult)>,
+		result: JfyiErrorResult<(PeerId, CandidateHash, ImportStatementsResult)>,
 	) -> JfyiErrorResult<()> {
+		self.metrics.on_pending_imports(self.pending_imports.len());
 		match result? {
-			(_, ImportStatementsResult::ValidImport) => {
+			(peer, candidate_hash, ImportStatementsResult::ValidImport) => {
 				self.metrics.on_imported(SUCCEEDED);
+				self.confirmed_requests.put((peer, candidate_hash), ());
 			},
-			(bad_peer, ImportStatementsResult::InvalidImport) => {
+			(bad_peer, _, ImportStatementsResult::InvalidImport) => {
 				self.metrics.on_imported(FAILED);
 				self.banned_peers.put(bad_peer, ());
 			},
 		}
 		Ok(())
 	}
+
+	/// Punish a misbehaving peer, which we don't send a response to.
+	async fn report_peer(&mut self, peer: PeerId, rep: Rep) {
+		self.sender
+			.send_message(AllMessages::NetworkBridge(NetworkBridgeMessage::ReportPeer(peer, rep)))
+			.await;
+	}
 }
 
 /// Manage pending imports in a way that preserves invariants.
 struct PendingImports {
 	/// Futures in flight.
-	futures:
-		FuturesUnordered<BoxFuture<'static, (PeerId, JfyiErrorResult<ImportStatementsResult>)>>,
+	futures: FuturesUnordered<
+		BoxFuture<'static, (PeerId, CandidateHash, JfyiErrorResult<ImportStatementsResult>)>,
+	>,
 	/// Peers whose requests are currently in flight.
 	peers: HashSet<PeerId>,
 }
@@ -317,6 +372,7 @@ impl PendingImports {
 	pub fn push(
 		&mut self,
 		peer: PeerId,
+		candidate_hash: CandidateHash,
 		handled: oneshot::Receiver<ImportStatementsResult>,
 		pending_response: OutgoingResponseSender<DisputeRequest>,
 	) {
@@ -324,7 +380,7 @@ impl PendingImports {
 		self.futures.push(
 			async move {
 				let r = respond_to_request(peer, handled, pending_response).await;
-				(peer, r)
+				(peer, candidate_hash, r)
 			}
 			.boxed(),
 		)
@@ -342,14 +398,14 @@ impl PendingImports {
 }
 
 impl Stream for PendingImports {
-	type Item = JfyiErrorResult<(PeerId, ImportStatementsResult)>;
+	type Item = JfyiErrorResult<(PeerId, CandidateHash, ImportStatementsResult)>;
 	fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
 		match Pin::new(&mut self.futures).poll_next(ctx) {
 			Poll::Pending => Poll::Pending,
 			Poll::Ready(None) => Poll::Ready(None),
-			Poll::Ready(Some((peer, result))) => {
+			Poll::Ready(Some((peer, candidate_hash, result))) => {
 				self.peers.remove(&peer);
-				Poll::Ready(Some(result.map(|r| (peer, r))))
+				Poll::Ready(Some(result.map(|r| (peer, candidate_hash, r))))
 			},
 		}
 	}
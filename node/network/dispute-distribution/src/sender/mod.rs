@@ -14,14 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	time::Duration,
+};
 
-use futures::channel::{mpsc, oneshot};
+use futures::{
+	channel::{mpsc, oneshot},
+	future,
+};
+use futures_timer::Delay;
 
 use polkadot_node_network_protocol::request_response::v1::DisputeRequest;
 use polkadot_node_primitives::{CandidateVotes, DisputeMessage, SignedDisputeStatement};
 use polkadot_node_subsystem_util::runtime::RuntimeInfo;
-use polkadot_primitives::v2::{CandidateHash, DisputeStatement, Hash, SessionIndex};
+use polkadot_primitives::v2::{
+	CandidateHash, DisputeStatement, Hash, SessionIndex, ValidatorIndex,
+};
 use polkadot_subsystem::{
 	messages::{AllMessages, DisputeCoordinatorMessage},
 	ActiveLeavesUpdate, SubsystemContext,
@@ -42,6 +51,13 @@ pub use error::{Error, FatalError, JfyiError, Result};
 use self::error::JfyiErrorResult;
 use crate::{Metrics, LOG_TARGET};
 
+/// Minimum time between starting the sending of two disputes or refreshing the sends of two
+/// disputes.
+///
+/// Each of those sends requests to all validators at once, so this limits the rate of requests
+/// every single receiver has to deal with.
+pub const SEND_RATE_LIMIT: Duration = Duration::from_millis(100);
+
 /// The `DisputeSender` keeps track of all ongoing disputes we need to send statements out.
 ///
 /// For each dispute a `SendTask` is responsible for sending to the concerned validators for that
@@ -62,6 +78,15 @@ pub struct DisputeSender {
 	/// Sender to be cloned for `SendTask`s.
 	tx: mpsc::Sender<TaskFinish>,
 
+	/// Disputes waiting for their `SendTask` to be started, because of `SEND_RATE_LIMIT`.
+	pending_starts: VecDeque<DisputeRequest>,
+
+	/// Disputes waiting for their sends to be refreshed, because of `SEND_RATE_LIMIT`.
+	pending_refreshes: VecDeque<CandidateHash>,
+
+	/// Enforces `SEND_RATE_LIMIT`.
+	rate_limit: RateLimit,
+
 	/// Metrics for reporting stats about sent requests.
 	metrics: Metrics,
 }
@@ -74,36 +99,73 @@ impl DisputeSender {
 			active_sessions: HashMap::new(),
 			disputes: HashMap::new(),
 			tx,
+			pending_starts: VecDeque::new(),
+			pending_refreshes: VecDeque::new(),
+			rate_limit: RateLimit::new(),
 			metrics,
 		}
 	}
 
-	/// Create a `SendTask` for a particular new dispute.
-	pub async fn start_sender<Context: SubsystemContext>(
+	/// Queue the creation of a `SendTask` for a particular new dispute.
+	///
+	/// The `SendTask` gets created by `send_next`, once `SEND_RATE_LIMIT` permits.
+	pub fn start_sender(&mut self, msg: DisputeMessage) {
+		let req: DisputeRequest = msg.into();
+		let candidate_hash = req.0.candidate_receipt.hash();
+		if self.disputes.contains_key(&candidate_hash) ||
+			self.pending_starts
+				.iter()
+				.any(|r| r.0.candidate_receipt.hash() == candidate_hash)
+		{
+			gum::trace!(target: LOG_TARGET, ?candidate_hash, "Dispute sending already active.");
+			return
+		}
+		self.pending_starts.push_back(req);
+	}
+
+	/// Wait until the next queued dispute can be started or refreshed.
+	///
+	/// Never resolves while nothing is queued. Cancellation safe, so it can be used in `select!`.
+	pub async fn ready_to_send(&mut self) {
+		if self.pending_starts.is_empty() && self.pending_refreshes.is_empty() {
+			return future::pending().await
+		}
+		self.rate_limit.ready().await
+	}
+
+	/// Start the next queued dispute or, if there is none, refresh the sends of the next queued
+	/// one.
+	///
+	/// Should only be called once `ready_to_send` resolved, so `SEND_RATE_LIMIT` is respected.
+	pub async fn send_next<Context: SubsystemContext>(
 		&mut self,
 		ctx: &mut Context,
 		runtime: &mut RuntimeInfo,
-		msg: DisputeMessage,
 	) -> Result<()> {
-		let req: DisputeRequest = msg.into();
-		let candidate_hash = req.0.candidate_receipt.hash();
-		match self.disputes.entry(candidate_hash) {
-			Entry::Occupied(_) => {
-				gum::trace!(target: LOG_TARGET, ?candidate_hash, "Dispute sending already active.");
-				return Ok(())
-			},
-			Entry::Vacant(vacant) => {
-				let send_task = SendTask::new(
-					ctx,
-					runtime,
-					&self.active_sessions,
-					self.tx.clone(),
-					req,
-					&self.metrics,
-				)
-				.await?;
-				vacant.insert(send_task);
-			},
+		if let Some(req) = self.pending_starts.pop_front() {
+			self.rate_limit.reset();
+			let candidate_hash = req.0.candidate_receipt.hash();
+			let send_task = SendTask::new(
+				ctx,
+				runtime,
+				&self.active_sessions,
+				self.tx.clone(),
+				req,
+				&self.metrics,
+			)
+			.await?;
+			self.disputes.insert(candidate_hash, send_task);
+			return Ok(())
+		}
+
+		while let Some(candidate_hash) = self.pending_refreshes.pop_front() {
+			// Disputes might have become obsolete while waiting:
+			if let Some(dispute) = self.disputes.get_mut(&candidate_hash) {
+				self.rate_limit.reset();
+				return dispute
+					.refresh_sends(ctx, runtime, &self.active_sessions, &self.metrics)
+					.await
+			}
 		}
 		Ok(())
 	}
@@ -140,19 +202,16 @@ impl DisputeSender {
 		self.disputes
 			.retain(|candidate_hash, _| active_disputes.contains(candidate_hash));
 
-		for dispute in self.disputes.values_mut() {
-			if have_new_sessions || dispute.has_failed_sends() {
-				dispute
-					.refresh_sends(ctx, runtime, &self.active_sessions, &self.metrics)
-					.await?;
+		for (candidate_hash, dispute) in self.disputes.iter() {
+			if (have_new_sessions || dispute.has_failed_sends()) &&
+				!self.pending_refreshes.contains(candidate_hash)
+			{
+				self.pending_refreshes.push_back(*candidate_hash);
 			}
 		}
 
 		// This should only be non-empty on startup, but if not - we got you covered:
-		for dispute in unknown_disputes {
-			self.start_send_for_dispute(ctx, runtime, dispute).await?
-		}
-		Ok(())
+		self.start_send_for_disputes(ctx, runtime, unknown_disputes).await
 	}
 
 	/// Receive message from a sending task.
@@ -178,14 +237,72 @@ impl DisputeSender {
 
 	/// Call `start_sender` on all passed in disputes.
 	///
-	/// Recover necessary votes for building up `DisputeMessage` and start sending for all of them.
-	async fn start_send_for_dispute<Context: SubsystemContext>(
+	/// Recover necessary votes for building up `DisputeMessage`s and start sending for all of
+	/// them. The votes of all disputes we have voted on are fetched from the dispute coordinator
+	/// in a single query.
+	async fn start_send_for_disputes<Context: SubsystemContext>(
 		&mut self,
 		ctx: &mut Context,
 		runtime: &mut RuntimeInfo,
-		dispute: (SessionIndex, CandidateHash),
+		disputes: Vec<(SessionIndex, CandidateHash)>,
 	) -> Result<()> {
-		let (session_index, candidate_hash) = dispute;
+		let mut our_indices = HashMap::new();
+		for (session_index, candidate_hash) in disputes {
+			match self.get_our_index(ctx, runtime, session_index).await? {
+				None => {
+					gum::trace!(
+						target: LOG_TARGET,
+						"Not a validator in that session - not starting dispute sending."
+					);
+				},
+				Some(index) => {
+					our_indices.insert((session_index, candidate_hash), index);
+				},
+			}
+		}
+		if our_indices.is_empty() {
+			return Ok(())
+		}
+
+		let queries = our_indices.keys().copied().collect();
+		let mut votes: HashMap<_, _> = get_candidate_votes(ctx, queries)
+			.await?
+			.into_iter()
+			.map(|(session_index, candidate_hash, votes)| ((session_index, candidate_hash), votes))
+			.collect();
+
+		for (dispute, our_index) in our_indices {
+			let (session_index, candidate_hash) = dispute;
+			match votes.remove(&dispute) {
+				None => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?session_index,
+						?candidate_hash,
+						"No votes for active dispute?! - possible, due to race."
+					);
+				},
+				Some(votes) =>
+					self.start_send_for_dispute(ctx, runtime, dispute, our_index, votes).await?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Our validator index in the given session, if any.
+	async fn get_our_index<Context: SubsystemContext>(
+		&self,
+		ctx: &mut Context,
+		runtime: &mut RuntimeInfo,
+		session_index: SessionIndex,
+	) -> Result<Option<ValidatorIndex>> {
+		let ref_head = self.get_ref_head(session_index)?;
+		let info = runtime.get_session_info_by_index(ctx.sender(), ref_head, session_index).await?;
+		Ok(info.validator_info.our_index)
+	}
+
+	/// Find an active head, suitable for querying session information of the given session.
+	fn get_ref_head(&self, session_index: SessionIndex) -> Result<Hash> {
 		// A relay chain head is required as context for receiving session info information from runtime and
 		// storage. We will iterate `active_sessions` to find a suitable head. We assume that there is at
 		// least one active head which, by `session_index`, is at least as recent as the `dispute` passed in.
@@ -205,33 +322,21 @@ impl DisputeSender {
 				}
 			})
 			.ok_or(JfyiError::NoActiveHeads)?;
+		Ok(*ref_head)
+	}
 
-		let info = runtime
-			.get_session_info_by_index(ctx.sender(), *ref_head, session_index)
-			.await?;
-		let our_index = match info.validator_info.our_index {
-			None => {
-				gum::trace!(
-					target: LOG_TARGET,
-					"Not a validator in that session - not starting dispute sending."
-				);
-				return Ok(())
-			},
-			Some(index) => index,
-		};
-
-		let votes = match get_candidate_votes(ctx, session_index, candidate_hash).await? {
-			None => {
-				gum::debug!(
-					target: LOG_TARGET,
-					?session_index,
-					?candidate_hash,
-					"No votes for active dispute?! - possible, due to race."
-				);
-				return Ok(())
-			},
-			Some(votes) => votes,
-		};
+	/// Build the `DisputeMessage` for a dispute we voted on and start sending it.
+	async fn start_send_for_dispute<Context: SubsystemContext>(
+		&mut self,
+		ctx: &mut Context,
+		runtime: &mut RuntimeInfo,
+		dispute: (SessionIndex, CandidateHash),
+		our_index: ValidatorIndex,
+		votes: CandidateVotes,
+	) -> Result<()> {
+		let (session_index, candidate_hash) = dispute;
+		let ref_head = self.get_ref_head(session_index)?;
+		let info = runtime.get_session_info_by_index(ctx.sender(), ref_head, session_index).await?;
 
 		let our_valid_vote = votes.valid.iter().find(|(_, i, _)| *i == our_index);
 
@@ -297,7 +402,8 @@ impl DisputeSender {
 		.map_err(JfyiError::InvalidDisputeFromCoordinator)?;
 
 		// Finally, get the party started:
-		self.start_sender(ctx, runtime, message).await
+		self.start_sender(message);
+		Ok(())
 	}
 
 	/// Make active sessions correspond to currently active heads.
@@ -347,18 +453,41 @@ async fn get_active_disputes<Context: SubsystemContext>(
 	rx.await.map_err(|_| JfyiError::AskActiveDisputesCanceled)
 }
 
-/// Get all locally available dispute votes for a given dispute.
+/// Get all locally available dispute votes for the given disputes.
+///
+/// Disputes we have no votes for are missing from the result.
 async fn get_candidate_votes<Context: SubsystemContext>(
 	ctx: &mut Context,
-	session_index: SessionIndex,
-	candidate_hash: CandidateHash,
-) -> JfyiErrorResult<Option<CandidateVotes>> {
+	disputes: Vec<(SessionIndex, CandidateHash)>,
+) -> JfyiErrorResult<Vec<(SessionIndex, CandidateHash, CandidateVotes)>> {
 	let (tx, rx) = oneshot::channel();
 	ctx.send_message(AllMessages::DisputeCoordinator(
-		DisputeCoordinatorMessage::QueryCandidateVotes(vec![(session_index, candidate_hash)], tx),
+		DisputeCoordinatorMessage::QueryCandidateVotes(disputes, tx),
 	))
 	.await;
-	rx.await
-		.map(|v| v.get(0).map(|inner| inner.to_owned().2))
-		.map_err(|_| JfyiError::AskCandidateVotesCanceled)
+	rx.await.map_err(|_| JfyiError::AskCandidateVotesCanceled)
+}
+
+/// Keeps track of when the next send is allowed, so that `SEND_RATE_LIMIT` is respected.
+struct RateLimit {
+	/// `None` before the first send.
+	limit: Option<Delay>,
+}
+
+impl RateLimit {
+	fn new() -> Self {
+		Self { limit: None }
+	}
+
+	/// Wait until `SEND_RATE_LIMIT` has passed since the last `reset`.
+	async fn ready(&mut self) {
+		if let Some(limit) = &mut self.limit {
+			limit.await;
+		}
+	}
+
+	/// Note a send, so the next one has to wait for `SEND_RATE_LIMIT`.
+	fn reset(&mut self) {
+		self.limit = Some(Delay::new(SEND_RATE_LIMIT));
+	}
 }
@@ -17,7 +17,12 @@
 
 //! Subsystem unit tests
 
-use std::{collections::HashSet, sync::Arc, task::Poll, time::Duration};
+use std::{
+	collections::HashSet,
+	sync::Arc,
+	task::Poll,
+	time::{Duration, Instant},
+};
 
 use assert_matches::assert_matches;
 use futures::{
@@ -32,7 +37,7 @@ use sc_network::config::RequestResponseConfig;
 
 use polkadot_node_network_protocol::{
 	request_response::{v1::DisputeRequest, IncomingRequest},
-	PeerId,
+	PeerId, UnifiedReputationChange,
 };
 use sp_keyring::Sr25519Keyring;
 
@@ -60,7 +65,11 @@ use self::mock::{
 	MOCK_AUTHORITY_DISCOVERY, MOCK_NEXT_SESSION_INDEX, MOCK_NEXT_SESSION_INFO, MOCK_SESSION_INDEX,
 	MOCK_SESSION_INFO,
 };
-use crate::{DisputeDistributionSubsystem, Metrics, LOG_TARGET};
+use crate::{
+	receiver::{COST_APPARENT_FLOOD, COST_DUPLICATE_REQUEST},
+	sender::SEND_RATE_LIMIT,
+	DisputeDistributionSubsystem, Metrics, LOG_TARGET,
+};
 
 /// Useful mock providers.
 pub mod mock;
@@ -113,6 +122,51 @@ fn send_dispute_sends_dispute() {
 	test_harness(test);
 }
 
+#[test]
+fn send_dispute_is_rate_limited() {
+	let test = |mut handle: TestSubsystemContextHandle<DisputeDistributionMessage>, _req_cfg| async move {
+		let _ = handle_subsystem_startup(&mut handle, None).await;
+
+		let expected_receivers: HashSet<_> = MOCK_SESSION_INFO
+			.discovery_keys
+			.clone()
+			.into_iter()
+			.filter(|a| a != &Sr25519Keyring::Ferdie.public().into())
+			.collect();
+
+		let relay_parent = Hash::random();
+		let started = Instant::now();
+		for i in 0..2 {
+			let candidate = make_candidate_receipt(relay_parent);
+			let message = make_dispute_message(candidate, ALICE_INDEX, FERDIE_INDEX).await;
+			handle
+				.send(FromOverseer::Communication {
+					msg: DisputeDistributionMessage::SendDispute(message),
+				})
+				.await;
+			// Session info is only requested once:
+			if i == 0 {
+				assert_matches!(
+					handle.recv().await,
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						_,
+						RuntimeApiRequest::SessionInfo(_, tx)
+					)) => {
+						tx.send(Ok(Some(MOCK_SESSION_INFO.clone())))
+							.expect("Receiver should stay alive.");
+					}
+				);
+			}
+			check_sent_requests(&mut handle, expected_receivers.clone(), true).await;
+		}
+		// The second dispute only got sent after the rate limit:
+		assert!(started.elapsed() >= SEND_RATE_LIMIT);
+
+		conclude(&mut handle).await;
+	};
+	test_harness(test);
+}
+
 #[test]
 fn received_request_triggers_import() {
 	let test = |mut handle: TestSubsystemContextHandle<DisputeDistributionMessage>,
@@ -160,17 +214,14 @@ fn received_request_triggers_import() {
 					message.clone().into(),
 					ImportStatementsResult::ValidImport,
 					false,
-					move |_, req_tx, message| async move {
+					move |handle, req_tx, message| async move {
 						// Another request from Alice should get dropped (request already in
 						// flight):
 						{
-							let rx_response = send_network_dispute_request(
-								req_tx,
-								MOCK_AUTHORITY_DISCOVERY
-									.get_peer_id_by_authority(Sr25519Keyring::Alice),
-								message.clone(),
-							)
-							.await;
+							let alice = MOCK_AUTHORITY_DISCOVERY
+								.get_peer_id_by_authority(Sr25519Keyring::Alice);
+							let rx_response =
+								send_network_dispute_request(req_tx, alice, message.clone()).await;
 
 							assert_matches!(
 								rx_response.await,
@@ -182,17 +233,15 @@ fn received_request_triggers_import() {
 									);
 								}
 							);
+							assert_peer_reported(handle, alice, COST_APPARENT_FLOOD).await;
 						}
 						// Another request from Bob should get dropped (request already in
 						// flight):
 						{
-							let rx_response = send_network_dispute_request(
-								req_tx,
-								MOCK_AUTHORITY_DISCOVERY
-									.get_peer_id_by_authority(Sr25519Keyring::Bob),
-								message.clone(),
-							)
-							.await;
+							let bob = MOCK_AUTHORITY_DISCOVERY
+								.get_peer_id_by_authority(Sr25519Keyring::Bob);
+							let rx_response =
+								send_network_dispute_request(req_tx, bob, message.clone()).await;
 
 							assert_matches!(
 								rx_response.await,
@@ -204,6 +253,7 @@ fn received_request_triggers_import() {
 									);
 								}
 							);
+							assert_peer_reported(handle, bob, COST_APPARENT_FLOOD).await;
 						}
 					},
 				)
@@ -233,17 +283,39 @@ fn received_request_triggers_import() {
 		}
 
 		// But should work fine for Bob:
+		let bob = MOCK_AUTHORITY_DISCOVERY.get_peer_id_by_authority(Sr25519Keyring::Bob);
+		let other_candidate = make_candidate_receipt(relay_parent);
+		let other_message =
+			make_dispute_message(other_candidate.clone(), ALICE_INDEX, FERDIE_INDEX).await;
 		nested_network_dispute_request(
 			&mut handle,
 			req_tx,
-			MOCK_AUTHORITY_DISCOVERY.get_peer_id_by_authority(Sr25519Keyring::Bob),
-			message.clone().into(),
+			bob,
+			other_message.into(),
 			ImportStatementsResult::ValidImport,
 			false,
 			|_, _, _| async {},
 		)
 		.await;
 
+		// Sending an already confirmed request again gets confirmed right away, without import,
+		// but costs a little reputation:
+		{
+			let rx_response =
+				send_network_dispute_request(req_tx, bob, message.clone().into()).await;
+
+			assert_matches!(
+				rx_response.await,
+				Ok(resp) => {
+					let result = resp.result.unwrap();
+					let decoded =
+						<DisputeResponse as Decode>::decode(&mut result.as_slice()).unwrap();
+					assert!(decoded == DisputeResponse::Confirmed);
+					assert_eq!(resp.reputation_changes, vec![COST_DUPLICATE_REQUEST.into_base_rep()]);
+				}
+			);
+		}
+
 		gum::trace!(target: LOG_TARGET, "Concluding.");
 		conclude(&mut handle).await;
 	};
@@ -577,6 +649,21 @@ async fn nested_network_dispute_request<'a, F, O>(
 	);
 }
 
+/// Check that the given peer got reported to the network bridge.
+async fn assert_peer_reported(
+	handle: &mut TestSubsystemContextHandle<DisputeDistributionMessage>,
+	peer: PeerId,
+	rep: UnifiedReputationChange,
+) {
+	assert_matches!(
+		handle.recv().await,
+		AllMessages::NetworkBridge(NetworkBridgeMessage::ReportPeer(reported, reported_rep)) => {
+			assert_eq!(reported, peer);
+			assert_eq!(reported_rep, rep);
+		}
+	);
+}
+
 async fn conclude(handle: &mut TestSubsystemContextHandle<DisputeDistributionMessage>) {
 	// No more messages should be in the queue:
 	poll_fn(|ctx| {
@@ -21,7 +21,8 @@
 //! In addition to that, it creates a gossip overlay topology
 //! which limits the amount of messages sent and received
 //! to be an order of sqrt of the validators. Our neighbors
-//! in this graph will be forwarded to the network bridge with
+//! in this graph, split into the ones in our row and the ones in our column,
+//! will be forwarded to the network bridge with
 //! the `NetworkBridgeMessage::NewGossipTopology` message.
 
 use std::{
@@ -49,10 +50,12 @@ use polkadot_node_subsystem::{
 		RuntimeApiRequest,
 	},
 	overseer, ActiveLeavesUpdate, FromOverseer, OverseerSignal, SpawnedSubsystem, SubsystemContext,
-	SubsystemError, SubsystemSender,
+	SubsystemError,
 };
 use polkadot_node_subsystem_util as util;
-use polkadot_primitives::v2::{AuthorityDiscoveryId, Hash, SessionIndex};
+use polkadot_primitives::v2::{
+	AuthorityDiscoveryId, Hash, SessionIndex, SessionInfo, ValidatorIndex,
+};
 
 #[cfg(test)]
 mod tests;
@@ -234,34 +237,41 @@ where
 				self.issue_connection_request(ctx, other_authorities).await;
 
 				if is_new_session {
-					update_gossip_topology(ctx, our_index, all_authorities, relay_parent).await?;
-					self.update_authority_status_metrics(leaf, ctx.sender()).await?;
+					let session_info =
+						util::request_session_info(relay_parent, session_index, ctx.sender())
+							.await
+							.await??;
+
+					if let Some(session_info) = session_info {
+						// The topology is built over the authorities of the current session,
+						// which we are only part of if we have one of their keys.
+						if let Some(our_index) =
+							self.get_key_index_and_update_metrics(&session_info).await
+						{
+							update_gossip_topology(
+								ctx,
+								our_index,
+								session_info.discovery_keys,
+								relay_parent,
+								session_index,
+							)
+							.await?;
+						}
+					}
 				}
 			}
 		}
 		Ok(())
 	}
 
-	async fn update_authority_status_metrics(
+	/// Returns the index of our key in the discovery keys of the session, if any,
+	/// and updates the authority status metrics accordingly.
+	async fn get_key_index_and_update_metrics(
 		&mut self,
-		leaf: Hash,
-		sender: &mut impl SubsystemSender,
-	) -> Result<(), util::Error> {
-		if let Some(session_info) = util::request_session_info(
-			leaf,
-			self.last_session_index
-				.expect("Last session index is always set on every session index change"),
-			sender,
-		)
-		.await
-		.await??
-		{
-			let maybe_index = match ensure_i_am_an_authority(
-				&self.keystore,
-				&session_info.discovery_keys,
-			)
-			.await
-			{
+		session_info: &SessionInfo,
+	) -> Option<usize> {
+		let maybe_index =
+			match ensure_i_am_an_authority(&self.keystore, &session_info.discovery_keys).await {
 				Ok(index) => {
 					self.metrics.on_is_authority();
 					Some(index)
@@ -275,21 +285,21 @@ where
 				Err(_) => None,
 			};
 
-			if let Some(validator_index) = maybe_index {
-				// The subset of authorities participating in parachain consensus.
-				let parachain_validators_this_session = session_info.validators;
+		if let Some(validator_index) = maybe_index {
+			// The subset of authorities participating in parachain consensus.
+			let parachain_validators_this_session = &session_info.validators;
 
-				// First `maxValidators` entries are the parachain validators. We'll check
-				// if our index is in this set to avoid searching for the keys.
-				// https://github.com/paritytech/polkadot/blob/a52dca2be7840b23c19c153cf7e110b1e3e475f8/runtime/parachains/src/configuration.rs#L148
-				if validator_index < parachain_validators_this_session.len() {
-					self.metrics.on_is_parachain_validator();
-				} else {
-					self.metrics.on_is_not_parachain_validator();
-				}
+			// First `maxValidators` entries are the parachain validators. We'll check
+			// if our index is in this set to avoid searching for the keys.
+			// https://github.com/paritytech/polkadot/blob/a52dca2be7840b23c19c153cf7e110b1e3e475f8/runtime/parachains/src/configuration.rs#L148
+			if validator_index < parachain_validators_this_session.len() {
+				self.metrics.on_is_parachain_validator();
+			} else {
+				self.metrics.on_is_not_parachain_validator();
 			}
 		}
-		Ok(())
+
+		maybe_index
 	}
 
 	async fn issue_connection_request<Context>(
@@ -454,12 +464,16 @@ async fn ensure_i_am_an_authority(
 /// but formed randomly via BABE randomness from two epochs ago.
 /// This limits the amount of gossip peers to 2 * `sqrt(len)` and ensures the diameter of 2.
 ///
+/// The `authorities` are the discovery keys of the session, so that the position of an
+/// authority in them is its `ValidatorIndex`, which the neighbors are sent along with.
+///
 /// [web3]: https://research.web3.foundation/en/latest/polkadot/networking/3-avail-valid.html#topology
 async fn update_gossip_topology<Context>(
 	ctx: &mut Context,
 	our_index: usize,
 	authorities: Vec<AuthorityDiscoveryId>,
 	relay_parent: Hash,
+	session: SessionIndex,
 ) -> Result<(), util::Error>
 where
 	Context: SubsystemContext<Message = GossipSupportMessage>,
@@ -492,17 +506,27 @@ where
 		.position(|i| *i == our_index)
 		.expect("our_index < len; indices contains it; qed");
 
-	let neighbors = matrix_neighbors(our_shuffled_position, len);
-	let our_neighbors = neighbors.map(|i| authorities[indices[i]].clone()).collect();
+	let (row_neighbors, column_neighbors) = matrix_neighbors(our_shuffled_position, len);
+	let with_validator_index =
+		|i: usize| (authorities[indices[i]].clone(), ValidatorIndex(indices[i] as u32));
+	let our_neighbors_x = row_neighbors.map(&with_validator_index).collect();
+	let our_neighbors_y = column_neighbors.map(&with_validator_index).collect();
 
-	ctx.send_message(NetworkBridgeMessage::NewGossipTopology { our_neighbors })
-		.await;
+	ctx.send_message(NetworkBridgeMessage::NewGossipTopology {
+		session,
+		our_neighbors_x,
+		our_neighbors_y,
+	})
+	.await;
 
 	Ok(())
 }
 
-/// Compute our row and column neighbors in a matrix
-fn matrix_neighbors(our_index: usize, len: usize) -> impl Iterator<Item = usize> {
+/// Compute our row and column neighbors in a matrix, in that order.
+fn matrix_neighbors(
+	our_index: usize,
+	len: usize,
+) -> (impl Iterator<Item = usize>, impl Iterator<Item = usize>) {
 	assert!(our_index < len, "our_index is computed using `enumerate`; qed");
 
 	// e.g. for size 11 the matrix would be
@@ -520,7 +544,10 @@ fn matrix_neighbors(our_index: usize, len: usize) -> impl Iterator<Item = usize>
 	let row_neighbors = our_row * sqrt..std::cmp::min(our_row * sqrt + sqrt, len);
 	let column_neighbors = (our_column..len).step_by(sqrt);
 
-	row_neighbors.chain(column_neighbors).filter(move |i| *i != our_index)
+	(
+		row_neighbors.filter(move |i| *i != our_index),
+		column_neighbors.filter(move |i| *i != our_index),
+	)
 }
 
 impl<Context, AD> overseer::Subsystem<Context, SubsystemError> for GossipSupport<AD>
@@ -185,7 +185,38 @@ async fn overseer_recv(overseer: &mut VirtualOverseer) -> AllMessages {
 	msg
 }
 
-async fn test_neighbors(overseer: &mut VirtualOverseer) {
+fn make_session_info() -> SessionInfo {
+	// only the discovery keys matter for the gossip topology.
+	SessionInfo {
+		active_validator_indices: Vec::new(),
+		random_seed: [0u8; 32],
+		dispute_period: 6,
+		validators: Vec::new(),
+		discovery_keys: AUTHORITIES.clone(),
+		assignment_keys: Vec::new(),
+		validator_groups: Vec::new(),
+		n_cores: 1,
+		zeroth_delay_tranche_width: 0,
+		relay_vrf_modulo_samples: 1,
+		n_delay_tranches: 1,
+		no_show_slots: 1,
+		needed_approvals: 1,
+	}
+}
+
+async fn test_neighbors(overseer: &mut VirtualOverseer, hash: Hash, session: SessionIndex) {
+	assert_matches!(
+		overseer_recv(overseer).await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+			relay_parent,
+			RuntimeApiRequest::SessionInfo(s, sender),
+		)) => {
+			assert_eq!(relay_parent, hash);
+			assert_eq!(s, session);
+			sender.send(Ok(Some(make_session_info()))).unwrap();
+		}
+	);
+
 	assert_matches!(
 		overseer_recv(overseer).await,
 		AllMessages::RuntimeApi(RuntimeApiMessage::Request(
@@ -209,9 +240,20 @@ async fn test_neighbors(overseer: &mut VirtualOverseer) {
 	assert_matches!(
 		overseer_recv(overseer).await,
 		AllMessages::NetworkBridge(NetworkBridgeMessage::NewGossipTopology {
-			our_neighbors,
+			session: s,
+			our_neighbors_x,
+			our_neighbors_y,
 		}) => {
-			let mut got: Vec<_> = our_neighbors.into_iter().collect();
+			assert_eq!(s, session);
+			// a neighbor is either in our row or in our column.
+			assert!(our_neighbors_x.keys().all(|a| !our_neighbors_y.contains_key(a)));
+
+			let neighbors: Vec<_> = our_neighbors_x.into_iter().chain(our_neighbors_y).collect();
+			for (authority, validator_index) in &neighbors {
+				assert_eq!(&AUTHORITIES[validator_index.0 as usize], authority);
+			}
+
+			let mut got: Vec<_> = neighbors.into_iter().map(|(a, _)| a).collect();
 			got.sort();
 			assert_eq!(got, NEIGHBORS.clone());
 		}
@@ -257,18 +299,7 @@ fn issues_a_connection_request_on_new_session() {
 			}
 		);
 
-		test_neighbors(overseer).await;
-
-		assert_matches!(
-			overseer_recv(overseer).await,
-			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
-				relay_parent,
-				RuntimeApiRequest::SessionInfo(1, sender),
-			)) => {
-				assert_eq!(relay_parent, hash);
-				sender.send(Ok(None)).unwrap();
-			}
-		);
+		test_neighbors(overseer, hash, 1).await;
 
 		virtual_overseer
 	});
@@ -335,18 +366,7 @@ fn issues_a_connection_request_on_new_session() {
 			}
 		);
 
-		test_neighbors(overseer).await;
-
-		assert_matches!(
-			overseer_recv(overseer).await,
-			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
-				relay_parent,
-				RuntimeApiRequest::SessionInfo(2, sender),
-			)) => {
-				assert_eq!(relay_parent, hash);
-				sender.send(Ok(None)).unwrap();
-			}
-		);
+		test_neighbors(overseer, hash, 2).await;
 
 		virtual_overseer
 	});
@@ -433,18 +453,7 @@ fn issues_a_connection_request_when_last_request_was_mostly_unresolved() {
 				}
 			);
 
-			test_neighbors(overseer).await;
-
-			assert_matches!(
-				overseer_recv(overseer).await,
-				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
-					relay_parent,
-					RuntimeApiRequest::SessionInfo(1, sender),
-				)) => {
-					assert_eq!(relay_parent, hash);
-					sender.send(Ok(None)).unwrap();
-				}
-			);
+			test_neighbors(overseer, hash, 1).await;
 
 			virtual_overseer
 		})
@@ -504,18 +513,23 @@ fn issues_a_connection_request_when_last_request_was_mostly_unresolved() {
 
 #[test]
 fn test_matrix_neighbors() {
-	for (our_index, len, expected) in vec![
-		(0usize, 1usize, vec![]),
-		(1, 2, vec![0usize]),
-		(0, 9, vec![1, 2, 3, 6]),
-		(9, 10, vec![0, 3, 6]),
-		(10, 11, vec![1, 4, 7, 9]),
-		(7, 11, vec![1, 4, 6, 8, 10]),
+	for (our_index, len, expected_row, expected_column) in vec![
+		(0usize, 1usize, vec![], vec![]),
+		(1, 2, vec![], vec![0usize]),
+		(0, 9, vec![1, 2], vec![3, 6]),
+		(9, 10, vec![], vec![0, 3, 6]),
+		(10, 11, vec![9], vec![1, 4, 7]),
+		(7, 11, vec![6, 8], vec![1, 4, 10]),
 	]
 	.into_iter()
 	{
-		let mut result: Vec<_> = matrix_neighbors(our_index, len).collect();
-		result.sort();
-		assert_eq!(result, expected);
+		let (row, column) = matrix_neighbors(our_index, len);
+		let mut row_result: Vec<_> = row.collect();
+		let mut column_result: Vec<_> = column.collect();
+		row_result.sort();
+		column_result.sort();
+
+		assert_eq!(row_result, expected_row);
+		assert_eq!(column_result, expected_column);
 	}
 }
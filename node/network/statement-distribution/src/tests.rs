@@ -34,7 +34,7 @@ use polkadot_primitives_test_helpers::{
 };
 use polkadot_subsystem::{
 	jaeger,
-	messages::{RuntimeApiMessage, RuntimeApiRequest},
+	messages::{NewGossipTopology, RuntimeApiMessage, RuntimeApiRequest, TopologyPeerInfo},
 	ActivatedLeaf, LeafStatus,
 };
 use sc_keystore::LocalKeystore;
@@ -1964,12 +1964,27 @@ fn handle_multiple_seconded_statements() {
 
 		// Explicitly add all `lucky` peers to the gossip peers to ensure that neither `peerA` not `peerB`
 		// receive statements
+		let topology = NewGossipTopology {
+			session: session_index,
+			our_neighbors_x: lucky_peers
+				.iter()
+				.enumerate()
+				.map(|(i, peer)| {
+					(
+						AuthorityDiscoveryId::from(Pair::generate().0.public()),
+						TopologyPeerInfo {
+							peer_ids: vec![peer.clone()],
+							validator_index: ValidatorIndex(i as u32),
+						},
+					)
+				})
+				.collect(),
+			our_neighbors_y: HashMap::new(),
+		};
 		handle
 			.send(FromOverseer::Communication {
 				msg: StatementDistributionMessage::NetworkBridgeUpdateV1(
-					NetworkBridgeEvent::NewGossipTopology(
-						lucky_peers.iter().cloned().collect::<HashSet<_>>(),
-					),
+					NetworkBridgeEvent::NewGossipTopology(topology),
 				),
 			})
 			.await;
@@ -23,7 +23,7 @@ use futures_timer::Delay;
 use std::time::Duration;
 
 use ::test_helpers::{dummy_candidate_descriptor, dummy_hash};
-use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_node_primitives::{BlockData, PoV, PvfExecKind};
 use polkadot_node_subsystem_types::messages::{
 	CandidateBackingMessage, CandidateValidationMessage,
 };
@@ -76,7 +76,7 @@ impl Subsystem1 {
 			let msg = CandidateValidationMessage::ValidateFromChainState(
 				dummy_candidate_descriptor(dummy_hash()),
 				PoV { block_data: BlockData(Vec::new()) }.into(),
-				Default::default(),
+				PvfExecKind::Backing,
 				tx,
 			);
 			ctx.send_message(<Ctx as overseer::SubsystemContext>::AllMessages::from(msg))
@@ -21,11 +21,11 @@ use ::test_helpers::{dummy_candidate_descriptor, dummy_candidate_receipt, dummy_
 use polkadot_node_network_protocol::{PeerId, UnifiedReputationChange};
 use polkadot_node_primitives::{
 	BlockData, CollationGenerationConfig, CollationResult, DisputeMessage, InvalidDisputeVote, PoV,
-	UncheckedDisputeMessage, ValidDisputeVote,
+	PvfExecKind, UncheckedDisputeMessage, ValidDisputeVote,
 };
 use polkadot_node_subsystem_types::{
 	jaeger,
-	messages::{NetworkBridgeEvent, RuntimeApiRequest},
+	messages::{NetworkBridgeEvent, RecoveryStrategy, RuntimeApiRequest},
 	ActivatedLeaf, LeafStatus,
 };
 use polkadot_primitives::v2::{
@@ -112,7 +112,7 @@ where
 						ctx.send_message(CandidateValidationMessage::ValidateFromChainState(
 							dummy_candidate_descriptor(dummy_hash()),
 							PoV { block_data: BlockData(Vec::new()) }.into(),
-							Default::default(),
+							PvfExecKind::Backing,
 							tx,
 						))
 						.await;
@@ -795,7 +795,7 @@ fn test_candidate_validation_msg() -> CandidateValidationMessage {
 	CandidateValidationMessage::ValidateFromChainState(
 		dummy_candidate_descriptor(dummy_hash()),
 		pov,
-		Duration::default(),
+		PvfExecKind::Backing,
 		sender,
 	)
 }
@@ -846,7 +846,7 @@ fn test_availability_recovery_msg() -> AvailabilityRecoveryMessage {
 	AvailabilityRecoveryMessage::RecoverAvailableData(
 		dummy_candidate_receipt(dummy_hash()),
 		Default::default(),
-		None,
+		RecoveryStrategy::ChunksOnly,
 		sender,
 	)
 }
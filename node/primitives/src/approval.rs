@@ -19,73 +19,20 @@
 pub use sp_consensus_babe::Slot;
 pub use sp_consensus_vrf::schnorrkel::{Randomness, VRFOutput, VRFProof};
 
+pub use polkadot_primitives::v2::{
+	AssignmentCert, AssignmentCertKind, DelayTranche, RelayVRFStory, ASSIGNED_CORE_CONTEXT,
+	CORE_RANDOMNESS_CONTEXT, RELAY_VRF_DELAY_CONTEXT, RELAY_VRF_MODULO_CONTEXT,
+	RELAY_VRF_STORY_CONTEXT, TRANCHE_RANDOMNESS_CONTEXT,
+};
+
 use parity_scale_codec::{Decode, Encode};
 use polkadot_primitives::v2::{
-	BlockNumber, CandidateHash, CandidateIndex, CoreIndex, Hash, Header, ValidatorIndex,
+	BlockNumber, CandidateHash, CandidateIndex, Hash, Header, SessionIndex, ValidatorIndex,
 	ValidatorSignature,
 };
 use sp_application_crypto::ByteArray;
 use sp_consensus_babe as babe_primitives;
 
-/// Validators assigning to check a particular candidate are split up into tranches.
-/// Earlier tranches of validators check first, with later tranches serving as backup.
-pub type DelayTranche = u32;
-
-/// A static context used to compute the Relay VRF story based on the
-/// VRF output included in the header-chain.
-pub const RELAY_VRF_STORY_CONTEXT: &[u8] = b"A&V RC-VRF";
-
-/// A static context used for all relay-vrf-modulo VRFs.
-pub const RELAY_VRF_MODULO_CONTEXT: &[u8] = b"A&V MOD";
-
-/// A static context used for all relay-vrf-modulo VRFs.
-pub const RELAY_VRF_DELAY_CONTEXT: &[u8] = b"A&V DELAY";
-
-/// A static context used for transcripts indicating assigned availability core.
-pub const ASSIGNED_CORE_CONTEXT: &[u8] = b"A&V ASSIGNED";
-
-/// A static context associated with producing randomness for a core.
-pub const CORE_RANDOMNESS_CONTEXT: &[u8] = b"A&V CORE";
-
-/// A static context associated with producing randomness for a tranche.
-pub const TRANCHE_RANDOMNESS_CONTEXT: &[u8] = b"A&V TRANCHE";
-
-/// random bytes derived from the VRF submitted within the block by the
-/// block author as a credential and used as input to approval assignment criteria.
-#[derive(Debug, Clone, Encode, Decode, PartialEq)]
-pub struct RelayVRFStory(pub [u8; 32]);
-
-/// Different kinds of input data or criteria that can prove a validator's assignment
-/// to check a particular parachain.
-#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
-pub enum AssignmentCertKind {
-	/// An assignment story based on the VRF that authorized the relay-chain block where the
-	/// candidate was included combined with a sample number.
-	///
-	/// The context used to produce bytes is [`RELAY_VRF_MODULO_CONTEXT`]
-	RelayVRFModulo {
-		/// The sample number used in this cert.
-		sample: u32,
-	},
-	/// An assignment story based on the VRF that authorized the relay-chain block where the
-	/// candidate was included combined with the index of a particular core.
-	///
-	/// The context is [`RELAY_VRF_DELAY_CONTEXT`]
-	RelayVRFDelay {
-		/// The core index chosen in this cert.
-		core_index: CoreIndex,
-	},
-}
-
-/// A certification of assignment.
-#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
-pub struct AssignmentCert {
-	/// The criterion which is claimed to be met by this cert.
-	pub kind: AssignmentCertKind,
-	/// The VRF showing the criterion is met.
-	pub vrf: (VRFOutput, VRFProof),
-}
-
 /// An assignment criterion which refers to the candidate under which the assignment is
 /// relevant by block hash.
 #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
@@ -128,6 +75,8 @@ pub struct BlockApprovalMeta {
 	pub candidates: Vec<CandidateHash>,
 	/// The consensus slot of the block.
 	pub slot: Slot,
+	/// The session of the block.
+	pub session: SessionIndex,
 }
 
 /// Errors that can occur during the approvals protocol.
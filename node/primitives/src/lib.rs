@@ -76,6 +76,20 @@ pub const BACKING_EXECUTION_TIMEOUT: Duration = Duration::from_secs(2);
 /// dispute participants.
 pub const APPROVAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(6);
 
+/// The kind of work a PVF is executed for.
+///
+/// It determines the execution timeout and in which order the executions wait for a worker: the
+/// later a kind is listed, the higher its priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PvfExecKind {
+	/// Validation of a candidate which is about to be backed.
+	Backing,
+	/// Validation of a candidate by an assigned approval checker.
+	Approval,
+	/// Validation of a candidate in order to participate in a dispute about it.
+	Dispute,
+}
+
 /// Linked to `MAX_FINALITY_LAG` in relay chain selection,
 /// `MAX_HEADS_LOOK_BACK` in `approval-voting` and
 /// `MAX_BATCH_SCRAPE_ANCESTORS` in `dispute-coordinator`
@@ -224,6 +238,8 @@ pub enum InvalidCandidate {
 	CodeDecompressionFailure,
 	/// PoV does not decompress correctly.
 	PoVDecompressionFailure,
+	/// PoV decompresses to more than the allowed size.
+	PoVDecompressedTooLarge,
 	/// Validation function returned invalid data.
 	BadReturn,
 	/// Invalid relay chain parent.
@@ -28,7 +28,7 @@ use polkadot_primitives::v2::{AccountId, AccountPublic, AssignmentId, ValidatorI
 #[cfg(feature = "polkadot-native")]
 use polkadot_runtime as polkadot;
 #[cfg(feature = "polkadot-native")]
-use polkadot_runtime_constants::currency::UNITS as DOT;
+use polkadot_runtime_constants::{currency::UNITS as DOT, nomination_pools};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
 
@@ -388,6 +388,13 @@ fn polkadot_staging_testnet_config_genesis(wasm_binary: &[u8]) -> polkadot::Gene
 		},
 		paras: Default::default(),
 		xcm_pallet: Default::default(),
+		nomination_pools: polkadot::NominationPoolsConfig {
+			min_join_bond: nomination_pools::MIN_JOIN_BOND,
+			min_create_bond: nomination_pools::MIN_CREATE_BOND,
+			max_pools: Some(nomination_pools::MAX_POOLS),
+			max_members_per_pool: Some(nomination_pools::MAX_MEMBERS_PER_POOL),
+			max_members: Some(nomination_pools::MAX_MEMBERS),
+		},
 	}
 }
 
@@ -1338,6 +1345,13 @@ pub fn polkadot_testnet_genesis(
 		},
 		paras: Default::default(),
 		xcm_pallet: Default::default(),
+		nomination_pools: polkadot::NominationPoolsConfig {
+			min_join_bond: nomination_pools::MIN_JOIN_BOND,
+			min_create_bond: nomination_pools::MIN_CREATE_BOND,
+			max_pools: Some(nomination_pools::MAX_POOLS),
+			max_members_per_pool: Some(nomination_pools::MAX_MEMBERS_PER_POOL),
+			max_members: Some(nomination_pools::MAX_MEMBERS),
+		},
 	}
 }
 
@@ -53,6 +53,7 @@ use {
 pub use sp_core::traits::SpawnNamed;
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_core_av_store::PruningConfig as AvailabilityPruningConfig,
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::v2::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -232,6 +233,10 @@ pub enum Error {
 	#[error("Creating a custom database is required for validators")]
 	DatabasePathRequired,
 
+	#[cfg(feature = "full-node")]
+	#[error("Validators must keep finalized availability data for the dispute window of {0:?}")]
+	AvailabilityPruningBelowDisputeWindow(Duration),
+
 	#[cfg(feature = "full-node")]
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
@@ -678,6 +683,7 @@ pub fn new_full<RuntimeApi, ExecutorDispatch, OverseerGenerator>(
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	program_path: Option<std::path::PathBuf>,
+	availability_pruning_config: AvailabilityPruningConfig,
 	overseer_enable_anyways: bool,
 	overseer_gen: OverseerGenerator,
 ) -> Result<NewFull<Arc<FullClient<RuntimeApi, ExecutorDispatch>>>, Error>
@@ -761,6 +767,18 @@ where
 		select_chain,
 	)?;
 
+	// Validators have to be able to provide the data of candidates for as long as disputes may be
+	// raised about them.
+	if role.is_authority() {
+		let epoch_length = import_setup.2.config().epoch_length;
+		let session_duration =
+			Duration::from_millis(epoch_length.saturating_mul(slot_duration.as_millis() as u64));
+		let dispute_window = session_duration * polkadot_node_primitives::DISPUTE_WINDOW.get();
+		if availability_pruning_config.keep_finalized_for < dispute_window {
+			return Err(Error::AvailabilityPruningBelowDisputeWindow(dispute_window))
+		}
+	}
+
 	let shared_voter_state = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 
@@ -895,6 +913,8 @@ where
 			None => std::env::current_exe()?,
 			Some(p) => p,
 		},
+		pov_decompression_limit: polkadot_node_primitives::POV_BOMB_LIMIT,
+		execution_timeouts: polkadot_node_core_candidate_validation::ExecutionTimeouts::default(),
 	};
 
 	let chain_selection_config = ChainSelectionConfig {
@@ -904,6 +924,7 @@ where
 
 	let dispute_coordinator_config = DisputeCoordinatorConfig {
 		col_data: crate::parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
+		pruning_window: polkadot_node_primitives::DISPUTE_WINDOW.get(),
 	};
 
 	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
@@ -996,6 +1017,7 @@ where
 					is_collator,
 					approval_voting_config,
 					availability_config,
+					availability_pruning_config,
 					candidate_validation_config,
 					chain_selection_config,
 					dispute_coordinator_config,
@@ -1286,6 +1308,7 @@ pub fn build_full(
 	enable_beefy: bool,
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
+	availability_pruning_config: AvailabilityPruningConfig,
 	overseer_enable_anyways: bool,
 	overseer_gen: impl OverseerGen,
 ) -> Result<NewFull<Client>, Error> {
@@ -1302,6 +1325,7 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			availability_pruning_config,
 			overseer_enable_anyways,
 			overseer_gen,
 		)
@@ -1318,6 +1342,7 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			availability_pruning_config,
 			overseer_enable_anyways,
 			overseer_gen,
 		)
@@ -1334,6 +1359,7 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			availability_pruning_config,
 			overseer_enable_anyways,
 			overseer_gen,
 		)
@@ -1350,6 +1376,7 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			availability_pruning_config,
 			overseer_enable_anyways,
 			overseer_gen,
 		)
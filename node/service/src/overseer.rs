@@ -18,7 +18,9 @@ use super::{AuthorityDiscoveryApi, Block, Error, Hash, IsCollator, Registry, Spa
 use lru::LruCache;
 use polkadot_availability_distribution::IncomingRequestReceivers;
 use polkadot_node_core_approval_voting::Config as ApprovalVotingConfig;
-use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store::{
+	Config as AvailabilityConfig, PruningConfig as AvailabilityPruningConfig,
+};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::Config as ChainSelectionConfig;
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
@@ -103,6 +105,8 @@ where
 	pub approval_voting_config: ApprovalVotingConfig,
 	/// Configuration for the availability store subsystem.
 	pub availability_config: AvailabilityConfig,
+	/// How long the availability store subsystem keeps data around.
+	pub availability_pruning_config: AvailabilityPruningConfig,
 	/// Configuration for the candidate validation subsystem.
 	pub candidate_validation_config: CandidateValidationConfig,
 	/// Configuration for the chain selection subsystem.
@@ -134,6 +138,7 @@ pub fn prepared_overseer_builder<'a, Spawner, RuntimeClient>(
 		is_collator,
 		approval_voting_config,
 		availability_config,
+		availability_pruning_config,
 		candidate_validation_config,
 		chain_selection_config,
 		dispute_coordinator_config,
@@ -189,9 +194,10 @@ where
 			available_data_req_receiver,
 			Metrics::register(registry)?,
 		))
-		.availability_store(AvailabilityStoreSubsystem::new(
+		.availability_store(AvailabilityStoreSubsystem::with_pruning_config(
 			parachains_db.clone(),
 			availability_config,
+			availability_pruning_config,
 			Metrics::register(registry)?,
 		))
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
@@ -27,7 +27,14 @@ type Version = u32;
 const VERSION_FILE_NAME: &'static str = "parachain_db_version";
 
 /// Current db version.
-const CURRENT_VERSION: Version = 1;
+const CURRENT_VERSION: Version = 2;
+
+/// The kind of database the parachains DB is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseKind {
+	ParityDB,
+	RocksDB,
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -49,11 +56,15 @@ impl From<Error> for io::Error {
 }
 
 /// Try upgrading parachain's database to the current version.
-pub fn try_upgrade_db(db_path: &Path) -> Result<(), Error> {
+pub fn try_upgrade_db(db_path: &Path, db_kind: DatabaseKind) -> Result<(), Error> {
 	let is_empty = db_path.read_dir().map_or(true, |mut d| d.next().is_none());
 	if !is_empty {
-		match current_version(db_path)? {
-			0 => migrate_from_version_0_to_1(db_path)?,
+		match current_version(db_path, db_kind)? {
+			0 => {
+				migrate_from_version_0_to_1(db_path)?;
+				migrate_from_version_1_to_2(db_path, db_kind)?;
+			},
+			1 => migrate_from_version_1_to_2(db_path, db_kind)?,
 			CURRENT_VERSION => (),
 			v => return Err(Error::FutureVersion { current: CURRENT_VERSION, got: v }),
 		}
@@ -63,10 +74,15 @@ pub fn try_upgrade_db(db_path: &Path) -> Result<(), Error> {
 }
 
 /// Reads current database version from the file at given path.
-/// If the file does not exist, assumes the current version.
-fn current_version(path: &Path) -> Result<Version, Error> {
+///
+/// If the file does not exist, assumes the current version for `RocksDB`. `ParityDB` databases
+/// were not versioned before version 2, so version 1 is assumed for them.
+fn current_version(path: &Path, db_kind: DatabaseKind) -> Result<Version, Error> {
 	match fs::read_to_string(version_file_path(path)) {
-		Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(CURRENT_VERSION),
+		Err(ref err) if err.kind() == io::ErrorKind::NotFound => match db_kind {
+			DatabaseKind::RocksDB => Ok(CURRENT_VERSION),
+			DatabaseKind::ParityDB => Ok(1),
+		},
 		Err(err) => Err(err.into()),
 		Ok(content) => u32::from_str(&content).map_err(|_| Error::CorruptedVersionFile),
 	}
@@ -102,3 +118,88 @@ fn migrate_from_version_0_to_1(path: &Path) -> Result<(), Error> {
 
 	Ok(())
 }
+
+/// Migration from version 1 to version 2:
+/// * the dispute coordinator column is ordered.
+///
+/// `RocksDB` columns are always ordered, so only `ParityDB` databases change. The keys of an
+/// existing `ParityDB` column cannot be indexed in place, so the dispute coordinator column is
+/// cleared and reopened as an ordered one. The votes stored in it are lost.
+fn migrate_from_version_1_to_2(path: &Path, db_kind: DatabaseKind) -> Result<(), Error> {
+	match db_kind {
+		DatabaseKind::RocksDB => Ok(()),
+		DatabaseKind::ParityDB => paritydb_migrate_from_version_1_to_2(path),
+	}
+}
+
+fn paritydb_migrate_from_version_1_to_2(path: &Path) -> Result<(), Error> {
+	let paritydb_error = |err| super::other_io_error(format!("{:?}", err));
+
+	let metadata = match parity_db::Options::load_metadata(path).map_err(paritydb_error)? {
+		Some(metadata) => metadata,
+		// The database was not created yet.
+		None => return Ok(()),
+	};
+
+	let options = paritydb_version_2_config(path);
+	let col = super::columns::COL_DISPUTE_COORDINATOR_DATA;
+	if metadata.columns.get(col as usize) != options.columns.get(col as usize) {
+		parity_db::clear_column(path, col as u8).map_err(paritydb_error)?;
+		options.write_metadata(path, &metadata.salt).map_err(paritydb_error)?;
+	}
+
+	Ok(())
+}
+
+/// The options of the `ParityDB` database of version 2 at the given path.
+pub(super) fn paritydb_version_2_config(path: &Path) -> parity_db::Options {
+	let mut options = parity_db::Options::with_columns(path, super::columns::NUM_COLUMNS as u8);
+	for i in super::columns::ORDERED_COL {
+		options.columns[*i as usize].btree_index = true;
+	}
+
+	options
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{super::columns::*, *};
+
+	#[test]
+	fn paritydb_migrate_to_version_2_clears_the_dispute_coordinator_column() {
+		let db_dir = tempfile::tempdir().unwrap();
+		let path = db_dir.path();
+		{
+			// the columns of a `ParityDB` database before version 2.
+			let mut options = parity_db::Options::with_columns(path, NUM_COLUMNS as u8);
+			for i in [COL_AVAILABILITY_META, COL_CHAIN_SELECTION_DATA] {
+				options.columns[i as usize].btree_index = true;
+			}
+			let db = parity_db::Db::open_or_create(&options).unwrap();
+			db.commit(vec![
+				(COL_AVAILABILITY_META as u8, b"meta".to_vec(), Some(b"kept".to_vec())),
+				(COL_DISPUTE_COORDINATOR_DATA as u8, b"votes".to_vec(), Some(b"lost".to_vec())),
+			])
+			.unwrap();
+		}
+
+		try_upgrade_db(path, DatabaseKind::ParityDB).unwrap();
+		assert_eq!(current_version(path, DatabaseKind::ParityDB).unwrap(), CURRENT_VERSION);
+
+		let db = parity_db::Db::open(&paritydb_version_2_config(path)).unwrap();
+		assert_eq!(db.get(COL_AVAILABILITY_META as u8, b"meta").unwrap(), Some(b"kept".to_vec()));
+		assert_eq!(db.get(COL_DISPUTE_COORDINATOR_DATA as u8, b"votes").unwrap(), None);
+	}
+
+	#[test]
+	fn new_database_is_at_the_current_version() {
+		let db_dir = tempfile::tempdir().unwrap();
+
+		for db_kind in [DatabaseKind::ParityDB, DatabaseKind::RocksDB] {
+			let path = db_dir.path().join(format!("{:?}", db_kind));
+			fs::create_dir_all(&path).unwrap();
+			try_upgrade_db(&path, db_kind).unwrap();
+			assert_eq!(current_version(&path, db_kind).unwrap(), CURRENT_VERSION);
+		}
+	}
+}
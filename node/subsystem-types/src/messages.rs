@@ -35,28 +35,27 @@ use polkadot_node_network_protocol::{
 use polkadot_node_primitives::{
 	approval::{BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote},
 	AvailableData, BabeEpoch, BlockWeight, CandidateVotes, CollationGenerationConfig,
-	CollationSecondedSignal, DisputeMessage, ErasureChunk, PoV, SignedDisputeStatement,
-	SignedFullStatement, ValidationResult,
+	CollationSecondedSignal, DisputeMessage, ErasureChunk, PoV, PvfExecKind,
+	SignedDisputeStatement, SignedFullStatement, ValidationResult,
 };
 use polkadot_primitives::v2::{
 	AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateDescriptor, CandidateEvent,
 	CandidateHash, CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt,
-	CoreState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId,
-	InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet, OccupiedCoreAssumption,
-	PersistedValidationData, PvfCheckStatement, SessionIndex, SessionInfo,
+	CoreState, ExecutorParams, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader,
+	Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet,
+	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, SessionIndex, SessionInfo,
 	SignedAvailabilityBitfield, SignedAvailabilityBitfields, ValidationCode, ValidationCodeHash,
 	ValidatorId, ValidatorIndex, ValidatorSignature,
 };
 use polkadot_statement_table::v2::Misbehavior;
 use std::{
-	collections::{BTreeMap, HashSet},
+	collections::{BTreeMap, HashMap, HashSet},
 	sync::Arc,
-	time::Duration,
 };
 
 /// Network events as transmitted to other subsystems, wrapped in their message types.
 pub mod network_bridge_event;
-pub use network_bridge_event::NetworkBridgeEvent;
+pub use network_bridge_event::{NetworkBridgeEvent, NewGossipTopology, TopologyPeerInfo};
 
 /// Subsystem messages where each message is always bound to a relay parent.
 pub trait BoundToRelayParent {
@@ -135,8 +134,9 @@ pub enum CandidateValidationMessage {
 	ValidateFromChainState(
 		CandidateDescriptor,
 		Arc<PoV>,
-		/// Execution timeout
-		Duration,
+		/// What the candidate is validated for, which determines the execution timeout and
+		/// priority.
+		PvfExecKind,
 		oneshot::Sender<Result<ValidationResult, ValidationFailed>>,
 	),
 	/// Validate a candidate with provided, exhaustive parameters for validation.
@@ -153,8 +153,9 @@ pub enum CandidateValidationMessage {
 		ValidationCode,
 		CandidateDescriptor,
 		Arc<PoV>,
-		/// Execution timeout
-		Duration,
+		/// What the candidate is validated for, which determines the execution timeout and
+		/// priority.
+		PvfExecKind,
 		oneshot::Sender<Result<ValidationResult, ValidationFailed>>,
 	),
 	/// Try to compile the given validation code and send back
@@ -377,10 +378,23 @@ pub enum NetworkBridgeMessage {
 	},
 	/// Inform the distribution subsystems about the new
 	/// gossip network topology formed.
+	///
+	/// The only reason to have this here, is the availability of the
+	/// authority discovery service, otherwise, the `GossipSupport`
+	/// subsystem would make this all by itself.
 	NewGossipTopology {
-		/// Ids of our neighbors in the new gossip topology.
+		/// The session the topology is for.
+		session: SessionIndex,
+		/// Ids of our neighbors in the X dimension of the new gossip topology,
+		/// along with their validator indices within the session.
+		///
+		/// We're not necessarily connected to all of them, but we should.
+		our_neighbors_x: HashMap<AuthorityDiscoveryId, ValidatorIndex>,
+		/// Ids of our neighbors in the Y dimension of the new gossip topology,
+		/// along with their validator indices within the session.
+		///
 		/// We're not necessarily connected to all of them, but we should.
-		our_neighbors: HashSet<AuthorityDiscoveryId>,
+		our_neighbors_y: HashMap<AuthorityDiscoveryId, ValidatorIndex>,
 	},
 }
 
@@ -424,6 +438,41 @@ pub enum AvailabilityDistributionMessage {
 	},
 }
 
+/// The way in which available data should be recovered.
+///
+/// Each strategy starts with the cheapest source it allows and falls back to the more expensive
+/// ones: the full data from the backing group, then the systematic chunks, which need no decoding,
+/// and finally any chunks. The availability recovery subsystem may be configured to skip sources
+/// regardless of the requested strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryStrategy {
+	/// Request the full data from the given backing group first.
+	BackersFirst(GroupIndex),
+	/// Request the systematic chunks first.
+	SystematicChunksFirst,
+	/// Only recover from regular chunks.
+	ChunksOnly,
+}
+
+impl RecoveryStrategy {
+	/// The backing group to request the full data from, if any.
+	pub fn backing_group(&self) -> Option<GroupIndex> {
+		match self {
+			Self::BackersFirst(group) => Some(*group),
+			Self::SystematicChunksFirst | Self::ChunksOnly => None,
+		}
+	}
+
+	/// Whether recovering from the systematic chunks should be attempted before resorting to
+	/// regular chunks.
+	pub fn systematic_chunks(&self) -> bool {
+		match self {
+			Self::BackersFirst(_) | Self::SystematicChunksFirst => true,
+			Self::ChunksOnly => false,
+		}
+	}
+}
+
 /// Availability Recovery Message.
 #[derive(Debug, derive_more::From)]
 pub enum AvailabilityRecoveryMessage {
@@ -431,7 +480,7 @@ pub enum AvailabilityRecoveryMessage {
 	RecoverAvailableData(
 		CandidateReceipt,
 		SessionIndex,
-		Option<GroupIndex>, // Optional backing group to request from first.
+		RecoveryStrategy,
 		oneshot::Sender<Result<AvailableData, crate::errors::RecoveryError>>,
 	),
 }
@@ -682,6 +731,8 @@ pub enum RuntimeApiRequest {
 		OccupiedCoreAssumption,
 		RuntimeApiSender<Option<ValidationCodeHash>>,
 	),
+	/// Get the execution environment parameters for the given session, if stored.
+	SessionExecutorParams(SessionIndex, RuntimeApiSender<Option<ExecutorParams>>),
 }
 
 /// A message to the Runtime API subsystem.
@@ -68,6 +68,7 @@ fn main() -> Result<()> {
 							true,
 							None,
 							None,
+							Default::default(),
 							false,
 							polkadot_service::RealOverseerGen,
 						)
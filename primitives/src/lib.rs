@@ -20,3 +20,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod v2;
+
+// Staging primitives, for asynchronous backing.
+pub mod vstaging;
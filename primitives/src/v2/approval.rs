@@ -0,0 +1,103 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Approval-voting assignment criteria shared by the runtime and the node.
+
+use super::CoreIndex;
+use parity_scale_codec::{Decode, Encode};
+use primitives::RuntimeDebug;
+use sp_consensus_vrf::schnorrkel::{VRFOutput, VRFProof};
+
+/// Validators assigning to check a particular candidate are split up into tranches.
+/// Earlier tranches of validators check first, with later tranches serving as backup.
+pub type DelayTranche = u32;
+
+/// A static context used to compute the Relay VRF story based on the
+/// VRF output included in the header-chain.
+pub const RELAY_VRF_STORY_CONTEXT: &[u8] = b"A&V RC-VRF";
+
+/// A static context used for all relay-vrf-modulo VRFs.
+pub const RELAY_VRF_MODULO_CONTEXT: &[u8] = b"A&V MOD";
+
+/// A static context used for all relay-vrf-modulo VRFs.
+pub const RELAY_VRF_DELAY_CONTEXT: &[u8] = b"A&V DELAY";
+
+/// A static context used for transcripts indicating assigned availability core.
+pub const ASSIGNED_CORE_CONTEXT: &[u8] = b"A&V ASSIGNED";
+
+/// A static context associated with producing randomness for a core.
+pub const CORE_RANDOMNESS_CONTEXT: &[u8] = b"A&V CORE";
+
+/// A static context associated with producing randomness for a tranche.
+pub const TRANCHE_RANDOMNESS_CONTEXT: &[u8] = b"A&V TRANCHE";
+
+/// random bytes derived from the VRF submitted within the block by the
+/// block author as a credential and used as input to approval assignment criteria.
+#[derive(RuntimeDebug, Clone, Encode, Decode, PartialEq)]
+pub struct RelayVRFStory(pub [u8; 32]);
+
+/// Different kinds of input data or criteria that can prove a validator's assignment
+/// to check a particular parachain.
+#[derive(RuntimeDebug, Clone, Encode, Decode, PartialEq, Eq)]
+pub enum AssignmentCertKind {
+	/// An assignment story based on the VRF that authorized the relay-chain block where the
+	/// candidate was included combined with a sample number.
+	///
+	/// The context used to produce bytes is [`RELAY_VRF_MODULO_CONTEXT`]
+	RelayVRFModulo {
+		/// The sample number used in this cert.
+		sample: u32,
+	},
+	/// An assignment story based on the VRF that authorized the relay-chain block where the
+	/// candidate was included combined with the index of a particular core.
+	///
+	/// The context is [`RELAY_VRF_DELAY_CONTEXT`]
+	RelayVRFDelay {
+		/// The core index chosen in this cert.
+		core_index: CoreIndex,
+	},
+}
+
+impl AssignmentCertKind {
+	/// Check the parts of an assignment to `claimed_core_index` which do not depend on the VRF.
+	///
+	/// The claimed core must be one of the `n_cores`, a modulo sample must be one of the
+	/// `relay_vrf_modulo_samples` and a delay assignment must be to the claimed core.
+	pub fn is_within_bounds(
+		&self,
+		claimed_core_index: CoreIndex,
+		n_cores: u32,
+		relay_vrf_modulo_samples: u32,
+	) -> bool {
+		if claimed_core_index.0 >= n_cores {
+			return false
+		}
+
+		match *self {
+			AssignmentCertKind::RelayVRFModulo { sample } => sample < relay_vrf_modulo_samples,
+			AssignmentCertKind::RelayVRFDelay { core_index } => core_index == claimed_core_index,
+		}
+	}
+}
+
+/// A certification of assignment.
+#[derive(RuntimeDebug, Clone, Encode, Decode, PartialEq, Eq)]
+pub struct AssignmentCert {
+	/// The criterion which is claimed to be met by this cert.
+	pub kind: AssignmentCertKind,
+	/// The VRF showing the criterion is met.
+	pub vrf: (VRFOutput, VRFProof),
+}
@@ -0,0 +1,142 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parameters of the environment in which PVFs are executed.
+//!
+//! Validators must execute a PVF in the same environment in order to reach the same verdict, so
+//! the parameters are fixed per session and identified by their hash.
+
+use super::{BlakeTwo256, Hash, HashT};
+use parity_scale_codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use parity_util_mem::MallocSizeOf;
+use primitives::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::{ops::Deref, time::Duration, vec::Vec};
+
+/// The kind of PVF execution a timeout applies to.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, MallocSizeOf))]
+pub enum PvfExecTimeoutKind {
+	/// The execution of a candidate to back it.
+	Backing,
+	/// The execution of a candidate to approve it.
+	Approval,
+	/// The execution of a candidate to participate in a dispute about it.
+	Dispute,
+}
+
+/// A single parameter of the PVF execution environment.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, MallocSizeOf))]
+pub enum ExecutorParam {
+	/// The maximum number of 64KiB wasm memory pages the PVF may use.
+	#[codec(index = 1)]
+	MaxMemoryPages(u32),
+	/// The deterministic stack limit, in units of wasm values.
+	#[codec(index = 2)]
+	StackLogicalMax(u32),
+	/// The maximum size of the native stack, in bytes.
+	#[codec(index = 3)]
+	StackNativeMax(u32),
+	/// Canonicalize the NaN values produced by floating-point operations.
+	#[codec(index = 4)]
+	CanonicalizeNaNs,
+	/// Enable the wasm bulk memory extension.
+	#[codec(index = 5)]
+	WasmExtBulkMemory,
+	/// The time allotted to an execution of the given kind, in milliseconds.
+	#[codec(index = 6)]
+	PvfExecTimeout(PvfExecTimeoutKind, u64),
+}
+
+/// The hash of a set of [`ExecutorParams`].
+#[derive(Clone, Copy, Encode, Decode, Hash, Eq, PartialEq, PartialOrd, Ord, TypeInfo)]
+pub struct ExecutorParamsHash(Hash);
+
+impl ExecutorParamsHash {
+	/// The inner hash.
+	pub fn as_hash(&self) -> &Hash {
+		&self.0
+	}
+}
+
+impl sp_std::fmt::Debug for ExecutorParamsHash {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		write!(f, "{:?}", self.0)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ExecutorParamsHash {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+/// The parameters of the PVF execution environment for a session.
+///
+/// An empty set of parameters stands for the default environment of the node.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, MallocSizeOf))]
+pub struct ExecutorParams(Vec<ExecutorParam>);
+
+impl ExecutorParams {
+	/// Create an empty set of parameters, standing for the default environment.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The hash of the encoded parameters.
+	///
+	/// Two sets of parameters with the same hash describe the same environment, so the hash may
+	/// be used to select a matching execution worker.
+	pub fn hash(&self) -> ExecutorParamsHash {
+		ExecutorParamsHash(BlakeTwo256::hash_of(self))
+	}
+
+	/// The time allotted to an execution of the given kind, if set. Should it be set more than
+	/// once, the last value applies.
+	pub fn pvf_exec_timeout(&self, kind: PvfExecTimeoutKind) -> Option<Duration> {
+		self.0.iter().rev().find_map(|param| match param {
+			ExecutorParam::PvfExecTimeout(k, millis) if *k == kind =>
+				Some(Duration::from_millis(*millis)),
+			_ => None,
+		})
+	}
+}
+
+impl Deref for ExecutorParams {
+	type Target = Vec<ExecutorParam>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl From<&[ExecutorParam]> for ExecutorParams {
+	fn from(params: &[ExecutorParam]) -> Self {
+		Self(params.to_vec())
+	}
+}
+
+impl From<Vec<ExecutorParam>> for ExecutorParams {
+	fn from(params: Vec<ExecutorParam>) -> Self {
+		Self(params)
+	}
+}
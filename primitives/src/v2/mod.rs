@@ -31,14 +31,16 @@ pub use runtime_primitives::traits::{BlakeTwo256, Hash as HashT};
 
 // Export some core primitives.
 pub use polkadot_core_primitives::v2::{
-	AccountId, AccountIndex, AccountPublic, Balance, Block, BlockId, BlockNumber, CandidateHash,
-	ChainId, DownwardMessage, Hash, Header, InboundDownwardMessage, InboundHrmpMessage, Moment,
-	Nonce, OutboundHrmpMessage, Remark, Signature, UncheckedExtrinsic,
+	check_outbound_hrmp_messages, sort_outbound_hrmp_messages, AccountId, AccountIndex,
+	AccountPublic, Balance, Block, BlockId, BlockNumber, CandidateHash, ChainId, DownwardMessage,
+	DownwardMessageOrigin, Hash, Header, InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce,
+	OutboundHrmpMessage, OutboundHrmpMessagesError, Remark, Signature, UncheckedExtrinsic,
+	VersionedInboundDownwardMessage,
 };
 
 // Export some polkadot-parachain primitives
 pub use polkadot_parachain::primitives::{
-	HeadData, HrmpChannelId, Id, UpwardMessage, ValidationCode, ValidationCodeHash,
+	HeadData, HrmpChannelId, Id, IsSystem, UpwardMessage, ValidationCode, ValidationCodeHash,
 	LOWEST_PUBLIC_ID, LOWEST_USER_ID,
 };
 
@@ -55,6 +57,16 @@ pub use sp_staking::SessionIndex;
 mod signed;
 pub use signed::{EncodeAs, Signed, UncheckedSigned};
 
+mod approval;
+pub use approval::{
+	AssignmentCert, AssignmentCertKind, DelayTranche, RelayVRFStory, ASSIGNED_CORE_CONTEXT,
+	CORE_RANDOMNESS_CONTEXT, RELAY_VRF_DELAY_CONTEXT, RELAY_VRF_MODULO_CONTEXT,
+	RELAY_VRF_STORY_CONTEXT, TRANCHE_RANDOMNESS_CONTEXT,
+};
+
+mod executor_params;
+pub use executor_params::{ExecutorParam, ExecutorParams, ExecutorParamsHash, PvfExecTimeoutKind};
+
 mod metrics;
 pub use metrics::{
 	metric_definitions, RuntimeMetricLabel, RuntimeMetricLabelValue, RuntimeMetricLabelValues,
@@ -243,6 +255,24 @@ pub mod well_known_keys {
 		})
 	}
 
+	/// The delivery fee factor of the HRMP channel for the given identifier.
+	///
+	/// The storage entry stores a `FixedU128`. It is absent while the channel is not congested,
+	/// which means a factor of one.
+	pub fn hrmp_channel_fee_factor(channel: HrmpChannelId) -> Vec<u8> {
+		let prefix = hex!["6a0da05ca59913bc38a8630590f2627c4fa69ca2b00daf17c953949e29512ef7"];
+
+		channel.using_encoded(|channel: &[u8]| {
+			prefix
+				.as_ref()
+				.iter()
+				.chain(twox_64(channel).iter())
+				.chain(channel.iter())
+				.cloned()
+				.collect()
+		})
+	}
+
 	/// The list of inbound channels for the given para.
 	///
 	/// The storage entry stores a `Vec<ParaId>`
@@ -1647,7 +1677,7 @@ impl PvfCheckStatement {
 
 sp_api::decl_runtime_apis! {
 	/// The API for querying the state of parachains on-chain.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait ParachainHost<H: Encode + Decode = Hash, N: Encode + Decode = BlockNumber> {
 		/// Get the current validators.
 		fn validators() -> Vec<ValidatorId>;
@@ -1737,6 +1767,12 @@ sp_api::decl_runtime_apis! {
 		fn validation_code_hash(para_id: Id, assumption: OccupiedCoreAssumption)
 			-> Option<ValidationCodeHash>;
 
+		/***** Added in v3 *****/
+
+		/// Returns the execution environment parameters for the given session, if stored.
+		///
+		/// NOTE: This function is only available since parachain host version 3.
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams>;
 
 		/***** Replaced in v2 *****/
 
@@ -1850,6 +1886,69 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn dispute_statement_payloads_are_stable() {
+		// if this fails, signatures on dispute statements made by earlier releases no longer
+		// verify.
+		let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+		let parent_hash = Hash::repeat_byte(2);
+		let candidate = [1u8; 32];
+		let parent = [2u8; 32];
+		let session = 7u32.to_le_bytes();
+
+		let statements = [
+			(
+				DisputeStatement::Valid(ValidDisputeStatementKind::Explicit),
+				[&b"DISP"[..], &[1][..], &candidate[..], &session[..]].concat(),
+			),
+			(
+				DisputeStatement::Valid(ValidDisputeStatementKind::BackingSeconded(parent_hash)),
+				[&b"BKNG"[..], &[1][..], &candidate[..], &session[..], &parent[..]].concat(),
+			),
+			(
+				DisputeStatement::Valid(ValidDisputeStatementKind::BackingValid(parent_hash)),
+				[&b"BKNG"[..], &[2][..], &candidate[..], &session[..], &parent[..]].concat(),
+			),
+			(
+				DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalChecking),
+				[&b"APPR"[..], &candidate[..], &session[..]].concat(),
+			),
+			(
+				DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit),
+				[&b"DISP"[..], &[0][..], &candidate[..], &session[..]].concat(),
+			),
+		];
+
+		for (statement, expected) in statements {
+			let payload = statement.payload_data(candidate_hash, 7);
+			assert_eq!(payload, expected, "{:?}", statement);
+			// statements from one session can't be replayed in another.
+			assert_ne!(payload, statement.payload_data(candidate_hash, 8), "{:?}", statement);
+		}
+	}
+
+	#[test]
+	fn assignment_cert_kind_bounds() {
+		let modulo = AssignmentCertKind::RelayVRFModulo { sample: 1 };
+		assert!(modulo.is_within_bounds(CoreIndex(0), 1, 2));
+		assert!(!modulo.is_within_bounds(CoreIndex(0), 1, 1));
+		assert!(!modulo.is_within_bounds(CoreIndex(1), 1, 2));
+
+		let delay = AssignmentCertKind::RelayVRFDelay { core_index: CoreIndex(1) };
+		assert!(delay.is_within_bounds(CoreIndex(1), 2, 0));
+		assert!(!delay.is_within_bounds(CoreIndex(0), 2, 0));
+	}
+
+	#[test]
+	fn executor_params_hash_depends_on_params() {
+		let default = ExecutorParams::new();
+		let limited = ExecutorParams::from(&[ExecutorParam::MaxMemoryPages(2048)][..]);
+
+		assert_eq!(default.hash(), ExecutorParams::default().hash());
+		assert_ne!(default.hash(), limited.hash());
+		assert_eq!(limited.encode(), vec![4, 1, 0, 8, 0, 0]);
+	}
+
 	#[test]
 	fn collator_signature_payload_is_valid() {
 		// if this fails, collator signature verification code has to be updated.
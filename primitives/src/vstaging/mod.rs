@@ -0,0 +1,207 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Staging Primitives.
+//!
+//! These are the primitives for asynchronous backing, which are not yet part of a stable
+//! version of the primitives.
+
+// Put any primitives used by staging API functions here
+pub use crate::v2::*;
+use sp_std::prelude::*;
+
+use parity_scale_codec::{Decode, Encode};
+use primitives::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Candidate's acceptance limitations for asynchronous backing per relay parent.
+#[derive(RuntimeDebug, Copy, Clone, PartialEq, Eq, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsyncBackingParams {
+	/// The maximum number of para blocks between the para head in a relay parent
+	/// and a new candidate. Restricts nodes from building arbitrary long chains
+	/// and spamming other validators.
+	///
+	/// When async backing is disabled, the only valid value is 0.
+	pub max_candidate_depth: u32,
+	/// How many ancestors of a relay parent are allowed to build candidates on top
+	/// of.
+	///
+	/// When async backing is disabled, the only valid value is 0.
+	pub allowed_ancestry_len: u32,
+}
+
+impl AsyncBackingParams {
+	/// Whether these parameters allow candidates to be built ahead of the para head or on older
+	/// relay parents.
+	pub fn is_enabled(&self) -> bool {
+		self.max_candidate_depth > 0 || self.allowed_ancestry_len > 0
+	}
+}
+
+/// Constraints on inbound HRMP channels.
+#[derive(RuntimeDebug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct InboundHrmpLimitations<N = BlockNumber> {
+	/// An exhaustive set of all valid watermarks, sorted ascending.
+	///
+	/// It's only expected to contain block numbers at which messages were
+	/// previously sent to a para, excluding most recent head.
+	pub valid_watermarks: Vec<N>,
+}
+
+/// Constraints on outbound HRMP channels.
+#[derive(RuntimeDebug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct OutboundHrmpChannelLimitations {
+	/// The maximum bytes that can be written to the channel.
+	pub bytes_remaining: u32,
+	/// The maximum messages that can be written to the channel.
+	pub messages_remaining: u32,
+}
+
+/// Constraints on the actions that can be taken by a new parachain
+/// block. These limitations are implicitly associated with some particular
+/// parachain, which should be apparent from usage.
+#[derive(RuntimeDebug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct Constraints<N = BlockNumber> {
+	/// The minimum relay-parent number accepted under these constraints.
+	pub min_relay_parent_number: N,
+	/// The maximum Proof-of-Validity size allowed, in bytes.
+	pub max_pov_size: u32,
+	/// The maximum new validation code size allowed, in bytes.
+	pub max_code_size: u32,
+	/// The amount of UMP messages remaining.
+	pub ump_remaining: u32,
+	/// The amount of UMP bytes remaining.
+	pub ump_remaining_bytes: u32,
+	/// The maximum number of UMP messages allowed per candidate.
+	pub max_ump_num_per_candidate: u32,
+	/// Remaining DMP queue. Only includes sent-at block numbers.
+	pub dmp_remaining_messages: Vec<N>,
+	/// The limitations of all registered inbound HRMP channels.
+	pub hrmp_inbound: InboundHrmpLimitations<N>,
+	/// The limitations of all registered outbound HRMP channels.
+	pub hrmp_channels_out: Vec<(Id, OutboundHrmpChannelLimitations)>,
+	/// The maximum number of HRMP messages allowed per candidate.
+	pub max_hrmp_num_per_candidate: u32,
+	/// The required parent head-data of the parachain.
+	pub required_parent: HeadData,
+	/// The expected validation-code-hash of this parachain.
+	pub validation_code_hash: ValidationCodeHash,
+	/// The code upgrade restriction signal as-of this parachain.
+	pub upgrade_restriction: Option<UpgradeRestriction>,
+	/// The future validation code hash, if any, and at what relay-parent
+	/// number the upgrade would be minimally applied.
+	pub future_validation_code: Option<(N, ValidationCodeHash)>,
+}
+
+impl<N: PartialOrd> Constraints<N> {
+	/// The persisted validation data of a candidate built under these constraints on top of
+	/// the given relay parent, or `None` if the relay parent is older than these constraints
+	/// allow.
+	pub fn persisted_validation_data<H>(
+		&self,
+		relay_parent_number: N,
+		relay_parent_storage_root: H,
+	) -> Option<PersistedValidationData<H, N>> {
+		if relay_parent_number < self.min_relay_parent_number {
+			return None
+		}
+
+		Some(PersistedValidationData {
+			parent_head: self.required_parent.clone(),
+			relay_parent_number,
+			relay_parent_storage_root,
+			max_pov_size: self.max_pov_size,
+		})
+	}
+}
+
+/// A candidate pending availability.
+#[derive(RuntimeDebug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct CandidatePendingAvailability<H = Hash, N = BlockNumber> {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The candidate's descriptor.
+	pub descriptor: CandidateDescriptor<H>,
+	/// The commitments of the candidate.
+	pub commitments: CandidateCommitments,
+	/// The candidate's relay parent's number.
+	pub relay_parent_number: N,
+	/// The maximum Proof-of-Validity size allowed, in bytes.
+	pub max_pov_size: u32,
+}
+
+/// The per-parachain state of the backing system, including
+/// state-machine constraints and candidates pending availability.
+#[derive(RuntimeDebug, Clone, PartialEq, Encode, Decode, TypeInfo)]
+pub struct BackingState<H = Hash, N = BlockNumber> {
+	/// The state-machine constraints of the parachain.
+	pub constraints: Constraints<N>,
+	/// The candidates pending availability. These should be ordered, i.e. they should form
+	/// a sub-chain, where the first candidate builds on top of the required parent of the
+	/// constraints and each subsequent builds on top of the previous head-data.
+	pub pending_availability: Vec<CandidatePendingAvailability<H, N>>,
+}
+
+/// The number and total size of the messages waiting in a message queue.
+#[derive(RuntimeDebug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct QueueSize {
+	/// The number of messages in the queue.
+	pub message_count: u32,
+	/// The total size of the messages in the queue, in bytes.
+	pub total_bytes: u32,
+}
+
+/// The sizes of all non-empty message queues between the relay chain and the paras.
+#[derive(RuntimeDebug, Clone, Default, PartialEq, Encode, Decode, TypeInfo)]
+pub struct MessageQueueSizes {
+	/// The downward message queue of each para.
+	pub downward: Vec<(Id, QueueSize)>,
+	/// The upward message dispatch queue of each para.
+	pub upward: Vec<(Id, QueueSize)>,
+	/// The queue of each HRMP channel.
+	pub hrmp: Vec<(HrmpChannelId, QueueSize)>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The staging API for querying the state of parachains on-chain.
+	///
+	/// These methods are not yet part of a stable version of [`ParachainHost`] and may change
+	/// without notice. They should only be implemented by test networks.
+	pub trait StagingParachainHost<H: Encode + Decode = Hash, N: Encode + Decode = BlockNumber> {
+		/// Returns the state of parachain backing for a given para.
+		fn staging_para_backing_state(para_id: Id) -> Option<BackingState<H, N>>;
+
+		/// Returns candidate's acceptance limitations for asynchronous backing for a relay parent.
+		fn staging_async_backing_params() -> AsyncBackingParams;
+
+		/// Get the events concerning candidates of the given para that occurred within a block.
+		///
+		/// This is [`ParachainHost::candidate_events`] restricted to a single para.
+		fn staging_candidate_events_for_para(para_id: Id) -> Vec<CandidateEvent<H>>;
+
+		/// Get the backing votes and disputes imported on-chain within a block, if the block is
+		/// in the given session.
+		///
+		/// This is [`ParachainHost::on_chain_votes`] restricted to a single session. Like it, only
+		/// the votes of the block itself are returned, not those of its ancestors.
+		fn staging_on_chain_votes_in_session(session: SessionIndex) -> Option<ScrapedOnChainVotes<H>>;
+
+		/// Returns the number and total size of the messages waiting in every non-empty UMP, DMP
+		/// and HRMP queue.
+		fn staging_message_queue_sizes() -> MessageQueueSizes;
+	}
+}
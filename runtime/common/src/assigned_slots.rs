@@ -157,6 +157,8 @@ pub mod pallet {
 		PermanentSlotAssigned(ParaId),
 		/// A para was assigned a temporary parachain slot
 		TemporarySlotAssigned(ParaId),
+		/// The lease of a permanent parachain slot ran out, and the slot was released
+		PermanentSlotExpired(ParaId),
 	}
 
 	#[pallet::error]
@@ -478,6 +480,29 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Release permanent slots whose lease has ended by `lease_period_index`, freeing them up for
+	/// new assignments. The para itself is downgraded by the slots pallet once its lease runs out.
+	///
+	/// Returns the number of released slots.
+	fn release_expired_permanent_slots(lease_period_index: LeasePeriodOf<T>) -> u32 {
+		let expired = PermanentSlots::<T>::iter()
+			.filter(|(_, (period_begin, period_count))| {
+				period_begin.saturating_add(*period_count) <= lease_period_index
+			})
+			.map(|(para, _)| para)
+			.collect::<Vec<_>>();
+
+		for para in &expired {
+			PermanentSlots::<T>::remove(para);
+			Self::deposit_event(Event::<T>::PermanentSlotExpired(*para));
+		}
+		<PermanentSlotCount<T>>::mutate(|count| {
+			*count = count.saturating_sub(expired.len() as u32)
+		});
+
+		expired.len() as u32
+	}
+
 	/// Clear out all slot leases for both permanent & temporary slots.
 	/// The function merely calls out to `Slots::clear_all_leases`.
 	fn clear_slot_leases(origin: OriginFor<T>, id: ParaId) -> DispatchResult {
@@ -525,6 +550,8 @@ impl<T: Config> Pallet<T> {
 	/// Handles start of a lease period.
 	fn manage_lease_period_start(lease_period_index: LeasePeriodOf<T>) -> Weight {
 		// Note: leases that have ended in previous lease period, should have been cleaned in slots pallet.
+		let released = Self::release_expired_permanent_slots(lease_period_index);
+
 		if let Err(err) = Self::allocate_temporary_slot_leases(lease_period_index) {
 			log::error!(target: "assigned_slots",
 				"Allocating slots failed for lease period {:?}, with: {:?}",
@@ -532,7 +559,9 @@ impl<T: Config> Pallet<T> {
 			);
 		}
 		<T as slots::Config>::WeightInfo::force_lease() *
-			(T::MaxTemporarySlotPerLeasePeriod::get() as u64)
+			(T::MaxTemporarySlotPerLeasePeriod::get() as u64) +
+			T::DbWeight::get()
+				.reads_writes(T::MaxPermanentSlots::get() as u64 + 1, released as u64 + 1)
 	}
 }
 
@@ -882,6 +911,44 @@ mod tests {
 			// Para lease ended, downgraded back to parathread
 			assert_eq!(TestRegistrar::<Test>::is_parathread(ParaId::from(1)), true);
 			assert_eq!(Slots::already_leased(ParaId::from(1), 0, 5), false);
+
+			// The permanent slot has been released
+			assert_eq!(AssignedSlots::permanent_slot_count(), 0);
+			assert_eq!(AssignedSlots::has_permanent_slot(ParaId::from(1)), false);
+		});
+	}
+
+	#[test]
+	fn expired_perm_slot_can_be_reassigned() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			for id in 1..=3 {
+				assert_ok!(TestRegistrar::<Test>::register(
+					id,
+					ParaId::from(id as u32),
+					dummy_head_data(),
+					dummy_validation_code(),
+				));
+			}
+
+			assert_ok!(AssignedSlots::assign_perm_parachain_slot(Origin::root(), ParaId::from(1)));
+			assert_ok!(AssignedSlots::assign_perm_parachain_slot(Origin::root(), ParaId::from(2)));
+			assert_noop!(
+				AssignedSlots::assign_perm_parachain_slot(Origin::root(), ParaId::from(3)),
+				Error::<Test>::MaxPermanentSlotsExceeded
+			);
+
+			// Both leases end with lease period 2, releasing the slots at the start of period 3.
+			run_to_block(9);
+			assert_eq!(AssignedSlots::permanent_slot_count(), 0);
+			assert_eq!(AssignedSlots::permanent_slots(ParaId::from(1)), None);
+			assert_eq!(AssignedSlots::permanent_slots(ParaId::from(2)), None);
+			System::assert_has_event(
+				assigned_slots::Event::<Test>::PermanentSlotExpired(ParaId::from(1)).into(),
+			);
+
+			assert_ok!(AssignedSlots::assign_perm_parachain_slot(Origin::root(), ParaId::from(3)));
+			assert_eq!(AssignedSlots::permanent_slots(ParaId::from(3)), Some((3, 3)));
 		});
 	}
 
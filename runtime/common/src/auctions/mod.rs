@@ -18,6 +18,8 @@
 //! auctioning mechanism and for reserving balance as part of the "payment". Unreserving the balance
 //! happens elsewhere.
 
+pub mod runtime_api;
+
 use crate::{
 	slot_range::SlotRange,
 	traits::{AuctionStatus, Auctioneer, LeaseError, Leaser, Registrar},
@@ -85,6 +87,7 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// The module's configuration trait.
@@ -595,6 +598,37 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::<T>::AuctionClosed(AuctionCounter::<T>::get()));
 	}
 
+	/// A summary of the auction in progress at block `now`, if any.
+	pub fn auction_summary(
+		now: T::BlockNumber,
+	) -> Option<runtime_api::AuctionSummary<T::BlockNumber>> {
+		let (first_lease_period, ending_period_start) = AuctionInfo::<T>::get()?;
+		Some(runtime_api::AuctionSummary {
+			index: AuctionCounter::<T>::get(),
+			first_lease_period,
+			ending_period_start,
+			status: Self::auction_status(now),
+		})
+	}
+
+	/// The winners of the auction in progress if it were to close at the sample containing `now`.
+	///
+	/// Empty if `now` is not in the starting or ending period of an auction.
+	pub fn winners_at(now: T::BlockNumber) -> WinnersData<T> {
+		let offset = match Self::auction_status(now) {
+			AuctionStatus::StartingPeriod => Zero::zero(),
+			AuctionStatus::EndingPeriod(offset, _) => offset,
+			AuctionStatus::NotStarted | AuctionStatus::VrfDelay(_) => return Vec::new(),
+		};
+
+		// The winning data of a sample is only copied over once a block of the sample is
+		// initialized, so fall back to the previous sample.
+		Winning::<T>::get(offset)
+			.or_else(|| offset.checked_sub(&One::one()).and_then(Winning::<T>::get))
+			.map(Self::calculate_winners)
+			.unwrap_or_default()
+	}
+
 	/// Calculate the final winners from the winning slots.
 	///
 	/// This is a simple dynamic programming algorithm designed by Al, the original code is at:
@@ -654,7 +688,7 @@ mod tests {
 		assert_noop, assert_ok, assert_storage_noop,
 		dispatch::DispatchError::BadOrigin,
 		ord_parameter_types, parameter_types,
-		traits::{EnsureOneOf, OnFinalize, OnInitialize},
+		traits::{EnsureOneOf, OnFinalize, OnInitialize, OnRuntimeUpgrade, StorageVersion},
 	};
 	use frame_system::{EnsureRoot, EnsureSignedBy};
 	use pallet_balances;
@@ -1695,6 +1729,267 @@ mod tests {
 			assert_eq!(Winning::<Test>::iter().count(), 0);
 		});
 	}
+
+	#[test]
+	fn auction_api_reports_status_and_winners() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			assert_eq!(Auctions::auction_summary(1), None);
+			assert!(Auctions::winners_at(1).is_empty());
+
+			assert_ok!(Auctions::new_auction(Origin::signed(6), 5, 1));
+			assert_ok!(Auctions::bid(Origin::signed(1), 0.into(), 1, 1, 4, 1));
+			assert_eq!(
+				Auctions::auction_summary(1),
+				Some(runtime_api::AuctionSummary {
+					index: 1,
+					first_lease_period: 1,
+					ending_period_start: 6,
+					status: AuctionStatus::StartingPeriod,
+				})
+			);
+			assert_eq!(Auctions::winners_at(1), vec![(1, 0.into(), 1, SlotRange::ZeroThree)]);
+
+			// Once the ending period is over, the winners depend on the close point still to be
+			// drawn.
+			set_last_random(H256::zero(), 8);
+			run_to_block(9);
+			assert_eq!(Auctions::auction_summary(9).unwrap().status, AuctionStatus::VrfDelay(0));
+			assert!(Auctions::winners_at(9).is_empty());
+		});
+	}
+
+	#[test]
+	fn winning_migration_reindexes_old_slot_ranges() {
+		new_test_ext().execute_with(|| {
+			let mut old = [None; migration::OLD_SLOT_RANGE_COUNT];
+			// `ZeroZero`, `ZeroThree`, `OneTwo` and `ThreeThree` in the old layout.
+			old[0] = Some((1u64, ParaId::from(0), 10u64));
+			old[3] = Some((2, 1.into(), 20));
+			old[5] = Some((3, 2.into(), 30));
+			old[9] = Some((4, 3.into(), 40));
+			frame_support::storage::unhashed::put(&Winning::<Test>::hashed_key_for(0), &old);
+
+			assert_ok!(migration::pre_migrate::<Test>());
+			migration::MigrateToV1::<Test>::on_runtime_upgrade();
+			assert_ok!(migration::post_migrate::<Test>());
+			assert_eq!(StorageVersion::get::<Auctions>(), migration::STORAGE_VERSION);
+
+			let mut new = [None; SlotRange::SLOT_RANGE_COUNT];
+			new[SlotRange::ZeroZero as u8 as usize] = Some((1, 0.into(), 10));
+			new[SlotRange::ZeroThree as u8 as usize] = Some((2, 1.into(), 20));
+			new[SlotRange::OneTwo as u8 as usize] = Some((3, 2.into(), 30));
+			new[SlotRange::ThreeThree as u8 as usize] = Some((4, 3.into(), 40));
+			assert_eq!(Winning::<Test>::get(0), Some(new));
+
+			// The migration is not run again once the storage version is set.
+			assert_ok!(migration::pre_migrate::<Test>());
+			assert_storage_noop!(migration::MigrateToV1::<Test>::on_runtime_upgrade());
+		});
+	}
+
+	#[test]
+	fn winning_migration_leaves_entries_in_the_new_layout() {
+		new_test_ext().execute_with(|| {
+			let mut winning = [None; SlotRange::SLOT_RANGE_COUNT];
+			winning[SlotRange::SevenSeven as u8 as usize] = Some((1u64, ParaId::from(0), 10u64));
+			Winning::<Test>::insert(0, winning);
+
+			assert_ok!(migration::pre_migrate::<Test>());
+			migration::MigrateToV1::<Test>::on_runtime_upgrade();
+			assert_ok!(migration::post_migrate::<Test>());
+			assert_eq!(Winning::<Test>::get(0), Some(winning));
+		});
+	}
+
+	#[test]
+	fn winning_migration_keeps_bids_of_an_auction_in_progress() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			assert_ok!(Auctions::new_auction(Origin::signed(6), 5, 1));
+			// Ranges longer than four lease periods did not exist in the old layout.
+			assert_ok!(Auctions::bid(Origin::signed(1), 0.into(), 1, 1, 8, 5));
+			// The ending period has started, so `Winning` holds a second sample.
+			run_to_block(7);
+			assert_ok!(Auctions::bid(Origin::signed(2), 1.into(), 1, 2, 3, 6));
+			let winning = Auctions::winning(0).unwrap();
+			let ending = Auctions::winning(1).unwrap();
+
+			assert_ok!(migration::pre_migrate::<Test>());
+			migration::MigrateToV1::<Test>::on_runtime_upgrade();
+			assert_ok!(migration::post_migrate::<Test>());
+			assert_eq!(Auctions::winning(0), Some(winning));
+			assert_eq!(Auctions::winning(1), Some(ending));
+			assert_eq!(
+				Auctions::winning(1).unwrap()[SlotRange::ZeroSeven as u8 as usize],
+				Some((1, 0.into(), 5))
+			);
+		});
+	}
+}
+
+/// Migrations for chains upgrading from slot ranges of 4 lease periods.
+///
+/// Only the `Winning` entries of the auction in progress depend on the slot range layout. Entries
+/// in the old layout are re-indexed so that every bid keeps the lease periods it was placed on;
+/// entries which are already in the current layout, e.g. because bids were placed after the new
+/// code was enacted, are left as they are. An auction which is in progress during the upgrade
+/// keeps running and may take bids on the wider ranges from then on.
+pub mod migration {
+	use super::*;
+	use frame_support::{
+		storage::unhashed,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+	use parity_scale_codec::{DecodeAll, Encode};
+	use sp_std::marker::PhantomData;
+
+	/// The current storage version.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// The number of lease periods per slot range before the upgrade.
+	pub const OLD_LEASE_PERIODS_PER_SLOT: u8 = 4;
+	/// The number of slot ranges before the upgrade.
+	pub const OLD_SLOT_RANGE_COUNT: usize = 10;
+
+	type OldWinningData<T> =
+		[Option<(<T as frame_system::Config>::AccountId, ParaId, BalanceOf<T>)>;
+			OLD_SLOT_RANGE_COUNT];
+
+	/// The `SlotRange` stored at `index` of the old winning data.
+	///
+	/// The old ranges are ordered the same way as the current ones, i.e. by first and then by last
+	/// lease period.
+	fn old_slot_range(index: usize) -> Option<SlotRange> {
+		(0..OLD_LEASE_PERIODS_PER_SLOT)
+			.flat_map(|first| (first..OLD_LEASE_PERIODS_PER_SLOT).map(move |last| (first, last)))
+			.nth(index)
+			.and_then(|(first, last)| SlotRange::new_bounded(0u32, first.into(), last.into()).ok())
+	}
+
+	/// The raw `Winning` entry stored under `key`, decoded exactly as `Data`, or `None` if it
+	/// does not hold one.
+	fn decode_winning<T: Config, Data: Decode>(key: &T::BlockNumber) -> Option<Data> {
+		let raw = unhashed::get_raw(&Winning::<T>::hashed_key_for(key))?;
+		Data::decode_all(&mut &raw[..]).ok()
+	}
+
+	/// Re-index the winning data of an ongoing auction from the old 4 lease period layout into
+	/// the current `SlotRange` layout, if the storage version shows that it has not been yet.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				log::info!(target: "runtime::auctions", "Winning migration is no longer needed.");
+				return T::DbWeight::get().reads(1)
+			}
+			let weight = migrate::<T>();
+			STORAGE_VERSION.put::<Pallet<T>>();
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			post_migrate::<T>()
+		}
+	}
+
+	/// Re-index every `Winning` entry which is in the old layout. Entries which are not are left
+	/// untouched.
+	///
+	/// Use [`MigrateToV1`] rather than calling this directly, so that it is only run once.
+	pub fn migrate<T: Config>() -> Weight {
+		let keys = Winning::<T>::iter_keys().collect::<Vec<_>>();
+		let mut writes = 0u64;
+		for key in keys.iter() {
+			let old = match decode_winning::<T, OldWinningData<T>>(key) {
+				Some(old) => old,
+				None => {
+					log::warn!(
+						target: "runtime::auctions",
+						"Winning entry at {:?} is not in the old layout; leaving it as is.",
+						key,
+					);
+					continue
+				},
+			};
+			let mut new = [Pallet::<T>::EMPTY; SlotRange::SLOT_RANGE_COUNT];
+			for (index, bid) in old.into_iter().enumerate() {
+				if let Some(range) = old_slot_range(index) {
+					new[range as u8 as usize] = bid;
+				}
+			}
+			Winning::<T>::insert(key, new);
+			writes += 1;
+		}
+
+		T::DbWeight::get().reads_writes(keys.len() as u64, writes)
+	}
+
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		ensure!(
+			SlotRange::LEASE_PERIODS_PER_SLOT > OLD_LEASE_PERIODS_PER_SLOT as usize,
+			"Slot ranges were not extended, which implies no migration is needed."
+		);
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			log::info!(target: "runtime::auctions", "Winning was already migrated.");
+			return Ok(())
+		}
+
+		let (mut old, mut current) = (0, 0);
+		for key in Winning::<T>::iter_keys() {
+			// The encodings of both layouts never have the same length, so no entry is both.
+			if decode_winning::<T, OldWinningData<T>>(&key).is_some() {
+				old += 1;
+			} else if decode_winning::<T, WinningData<T>>(&key).is_some() {
+				current += 1;
+			} else {
+				return Err("Winning entry is in neither the old nor the current layout.")
+			}
+		}
+
+		if let Some((lease_period_index, _)) = AuctionInfo::<T>::get() {
+			log::info!(
+				target: "runtime::auctions",
+				"auction for lease period {:?} is in progress during the migration",
+				lease_period_index,
+			);
+		}
+		log::info!(
+			target: "runtime::auctions",
+			"winning entries to migrate: {:?}, already in the current layout: {:?}",
+			old,
+			current,
+		);
+
+		Ok(())
+	}
+
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		ensure!(
+			StorageVersion::get::<Pallet<T>>() == STORAGE_VERSION,
+			"Storage version was not updated."
+		);
+
+		for key in Winning::<T>::iter_keys() {
+			// Entries of an auction in progress may already hold bids on ranges which did not
+			// exist in the old layout, so only the layout itself is checked.
+			let new = decode_winning::<T, WinningData<T>>(&key)
+				.ok_or("Winning entry is not in the new layout.")?;
+			// Re-encoding must reproduce exactly what is stored.
+			ensure!(
+				unhashed::get_raw(&Winning::<T>::hashed_key_for(&key)) == Some(new.encode()),
+				"Winning entry was not fully migrated."
+			);
+		}
+
+		Ok(())
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
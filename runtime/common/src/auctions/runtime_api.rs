@@ -0,0 +1,61 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definitions for querying the auctions pallet.
+
+use super::AuctionIndex;
+use crate::{slot_range::SlotRange, traits::AuctionStatus};
+use parity_scale_codec::{Codec, Decode, Encode};
+use primitives::v2::Id as ParaId;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// The auction in progress, as seen at some block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AuctionSummary<BlockNumber> {
+	/// The index of the auction.
+	pub index: AuctionIndex,
+	/// The first of the lease periods being auctioned.
+	pub first_lease_period: BlockNumber,
+	/// The block at which the ending period, over which the close point is drawn, begins.
+	pub ending_period_start: BlockNumber,
+	/// The phase of the auction.
+	pub status: AuctionStatus<BlockNumber>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for following parachain slot auctions, so that front-ends can display the winners
+	/// as bidding progresses.
+	///
+	/// An auction closes at a point of its ending period which is only drawn, from on-chain VRF
+	/// randomness, after the ending period is over. The drawn sample is reported by the
+	/// `WinningOffset` event.
+	pub trait AuctionApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The auction in progress, if any.
+		fn current_auction_status() -> Option<AuctionSummary<BlockNumber>>;
+
+		/// The winners of the auction in progress if it were to close at the sample containing
+		/// `now`, along with the range each of them wins.
+		///
+		/// Empty if `now` is not in the starting or ending period of an auction.
+		fn winning_range(now: BlockNumber) -> Vec<(AccountId, ParaId, Balance, SlotRange)>;
+	}
+}
@@ -97,6 +97,20 @@ impl StatementKind {
 	}
 }
 
+/// The version of the EIP-712 domain under which typed claim signatures are made.
+///
+/// Bumping it invalidates all typed signatures made under the previous version.
+const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+
+/// The EIP-712 type of the domain under which typed claim signatures are made.
+const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version)";
+
+/// The EIP-712 type of a claim, as signed by `eth_signTypedData_v4`.
+///
+/// `dest` is the hex-encoded destination account, without a `0x` prefix, and `statement` is the
+/// text of the attested statement, or empty if there is none.
+const EIP712_CLAIM_TYPE: &[u8] = b"Claim(string dest,string statement)";
+
 impl Default for StatementKind {
 	fn default() -> Self {
 		StatementKind::Regular
@@ -157,6 +171,17 @@ impl sp_std::fmt::Debug for EcdsaSignature {
 	}
 }
 
+/// A signature over a claim, together with the scheme under which it was made.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum ClaimSignature {
+	/// A signature made by the Ethereum RPC's `personal_sign`, over the same message as is signed
+	/// for `claim_attest`.
+	PersonalSign(EcdsaSignature),
+	/// A signature made by the Ethereum RPC's `eth_signTypedData_v4`, over the EIP-712 `Claim`
+	/// message.
+	TypedData(EcdsaSignature),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -407,6 +432,55 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Make a claim to collect your DOTs by signing the statement kind it requires, under
+		/// either the `personal_sign` or the EIP-712 typed-data scheme.
+		///
+		/// This generalises `claim` and `claim_attest` to Ethereum wallets which only expose
+		/// `eth_signTypedData_v4` rather than `personal_sign`.
+		///
+		/// The dispatch origin for this call must be _None_.
+		///
+		/// Unsigned Validation:
+		/// A call to `claim_statement` is deemed valid if the signature provided matches the
+		/// message of its scheme, where `dest` is the hex-encoded `dest` account and `statement`
+		/// is the text of the given statement, if any:
+		/// - `PersonalSign`: the message signed for `claim_attest`, i.e. the configured prefix
+		///   string followed by `dest` and `statement`.
+		/// - `TypedData`: the EIP-712 `Claim(string dest,string statement)` message, under the
+		///   domain named after the configured prefix string.
+		///
+		/// The statement must match that which is expected according to your purchase arrangement.
+		///
+		/// Parameters:
+		/// - `dest`: The destination account to payout the claim.
+		/// - `ethereum_signature`: The signature of the message described above.
+		/// - `statement`: The kind of statement which is being attested to in the signature.
+		///
+		/// <weight>
+		/// The weight of this call is invariant over the input parameters.
+		/// Weight includes logic to validate unsigned `claim_statement` call.
+		///
+		/// Total Complexity: O(1)
+		/// </weight>
+		#[pallet::weight(T::WeightInfo::claim_attest())]
+		pub fn claim_statement(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			ethereum_signature: ClaimSignature,
+			statement: Option<StatementKind>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let data = dest.using_encoded(to_ascii_hex);
+			let text = statement.map_or(&[][..], StatementKind::to_text);
+			let signer = Self::eth_recover_claim(&ethereum_signature, &data, text)
+				.ok_or(Error::<T>::InvalidEthereumSignature)?;
+			ensure!(Signing::<T>::get(&signer) == statement, Error::<T>::InvalidStatement);
+
+			Self::process_claim(signer, dest)?;
+			Ok(())
+		}
+
 		/// Attest to a statement, needed to finalize the claims process.
 		///
 		/// WARNING: Insecure unless your chain includes `PrevalidateAttests` as a `SignedExtension`.
@@ -488,6 +562,17 @@ pub mod pallet {
 						Some(statement.as_slice()),
 					)
 				},
+				// <weight>
+				// The weight of this logic is included in the `claim_statement` dispatchable.
+				// </weight>
+				Call::claim_statement { dest: account, ethereum_signature, statement } => {
+					let data = account.using_encoded(to_ascii_hex);
+					let text = statement.map_or(&[][..], StatementKind::to_text);
+					(
+						Self::eth_recover_claim(&ethereum_signature, &data, text),
+						statement.map(StatementKind::to_text),
+					)
+				},
 				_ => return Err(InvalidTransaction::Call.into()),
 			};
 
@@ -554,6 +639,54 @@ impl<T: Config> Pallet<T> {
 		Some(res)
 	}
 
+	// Constructs the EIP-712 digest that `eth_signTypedData_v4` would sign for a claim of `dest`
+	// attesting to `statement`.
+	fn ethereum_typed_data_hash(dest: &[u8], statement: &[u8]) -> [u8; 32] {
+		let domain_separator = keccak_256(
+			&[
+				keccak_256(EIP712_DOMAIN_TYPE),
+				keccak_256(T::Prefix::get()),
+				keccak_256(EIP712_DOMAIN_VERSION),
+			]
+			.concat(),
+		);
+		let claim_hash = keccak_256(
+			&[keccak_256(EIP712_CLAIM_TYPE), keccak_256(dest), keccak_256(statement)].concat(),
+		);
+
+		let mut v = b"\x19\x01".to_vec();
+		v.extend_from_slice(&domain_separator);
+		v.extend_from_slice(&claim_hash);
+		keccak_256(&v)
+	}
+
+	// Attempts to recover the Ethereum address from a signature over EIP-712 typed claim data, as
+	// produced by the Ethereum RPC's `eth_signTypedData_v4`.
+	fn eth_recover_typed(
+		s: &EcdsaSignature,
+		dest: &[u8],
+		statement: &[u8],
+	) -> Option<EthereumAddress> {
+		let msg = Self::ethereum_typed_data_hash(dest, statement);
+		let mut res = EthereumAddress::default();
+		res.0
+			.copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
+		Some(res)
+	}
+
+	// Attempts to recover the Ethereum address from a signature over a claim of `dest` attesting
+	// to `statement`, under the scheme with which the signature was made.
+	fn eth_recover_claim(
+		s: &ClaimSignature,
+		dest: &[u8],
+		statement: &[u8],
+	) -> Option<EthereumAddress> {
+		match s {
+			ClaimSignature::PersonalSign(s) => Self::eth_recover(s, dest, statement),
+			ClaimSignature::TypedData(s) => Self::eth_recover_typed(s, dest, statement),
+		}
+	}
+
 	fn process_claim(signer: EthereumAddress, dest: T::AccountId) -> sp_runtime::DispatchResult {
 		let balance_due = <Claims<T>>::get(&signer).ok_or(Error::<T>::SignerHasNoClaim)?;
 
@@ -695,6 +828,21 @@ mod secp_utils {
 		r[64] = recovery_id.serialize();
 		EcdsaSignature(r)
 	}
+	pub fn typed_sig<T: Config>(
+		secret: &libsecp256k1::SecretKey,
+		what: &[u8],
+		statement: Option<StatementKind>,
+	) -> ClaimSignature {
+		let msg = <super::Pallet<T>>::ethereum_typed_data_hash(
+			&to_ascii_hex(what)[..],
+			statement.map_or(&[][..], StatementKind::to_text),
+		);
+		let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+		let mut r = [0u8; 65];
+		r[0..64].copy_from_slice(&sig.serialize()[..]);
+		r[64] = recovery_id.serialize();
+		ClaimSignature::TypedData(EcdsaSignature(r))
+	}
 }
 
 #[cfg(test)]
@@ -964,6 +1112,146 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn statement_claiming_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Claims::claim_statement(
+				Origin::none(),
+				42,
+				typed_sig::<Test>(&alice(), &42u64.encode(), None),
+				None,
+			));
+			assert_eq!(Balances::free_balance(&42), 100);
+			assert_eq!(Claims::total(), total_claims() - 100);
+
+			// A `personal_sign` signature is not valid typed data, but is valid as what it is.
+			let signature = sig::<Test>(&frank(), &43u64.encode(), &[][..]);
+			assert_noop!(
+				Claims::claim_statement(
+					Origin::none(),
+					43,
+					ClaimSignature::TypedData(signature.clone()),
+					None,
+				),
+				Error::<Test>::SignerHasNoClaim
+			);
+			assert_ok!(Claims::claim_statement(
+				Origin::none(),
+				43,
+				ClaimSignature::PersonalSign(signature),
+				None,
+			));
+			assert_eq!(Balances::free_balance(&43), 400);
+			assert_eq!(Claims::total(), total_claims() - 500);
+
+			// The statement must match the one expected for the claim.
+			assert_noop!(
+				Claims::claim_statement(
+					Origin::none(),
+					69,
+					typed_sig::<Test>(&dave(), &69u64.encode(), None),
+					None,
+				),
+				Error::<Test>::InvalidStatement
+			);
+			assert_noop!(
+				Claims::claim_statement(
+					Origin::none(),
+					69,
+					typed_sig::<Test>(&dave(), &69u64.encode(), Some(StatementKind::Saft)),
+					Some(StatementKind::Saft),
+				),
+				Error::<Test>::InvalidStatement
+			);
+			// The signed statement must match the one passed along.
+			assert_noop!(
+				Claims::claim_statement(
+					Origin::none(),
+					69,
+					typed_sig::<Test>(&dave(), &69u64.encode(), Some(StatementKind::Saft)),
+					Some(StatementKind::Regular),
+				),
+				Error::<Test>::InvalidStatement
+			);
+			assert_ok!(Claims::claim_statement(
+				Origin::none(),
+				69,
+				typed_sig::<Test>(&dave(), &69u64.encode(), Some(StatementKind::Regular)),
+				Some(StatementKind::Regular),
+			));
+			assert_eq!(Balances::free_balance(&69), 200);
+			assert_eq!(Claims::total(), total_claims() - 700);
+
+			// A `personal_sign` signature must also attest to the expected statement.
+			assert_noop!(
+				Claims::claim_statement(
+					Origin::none(),
+					70,
+					ClaimSignature::PersonalSign(sig::<Test>(&eve(), &70u64.encode(), &[][..])),
+					None,
+				),
+				Error::<Test>::InvalidStatement
+			);
+			let statement = StatementKind::Saft.to_text();
+			assert_ok!(Claims::claim_statement(
+				Origin::none(),
+				70,
+				ClaimSignature::PersonalSign(sig::<Test>(&eve(), &70u64.encode(), statement)),
+				Some(StatementKind::Saft),
+			));
+			assert_eq!(Balances::free_balance(&70), 300);
+			assert_eq!(Claims::total(), total_claims() - 1000);
+		});
+	}
+
+	#[test]
+	fn validate_unsigned_statement_works() {
+		use sp_runtime::traits::ValidateUnsigned;
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+
+		new_test_ext().execute_with(|| {
+			let call = ClaimsCall::claim_statement {
+				dest: 1,
+				ethereum_signature: typed_sig::<Test>(
+					&dave(),
+					&1u64.encode(),
+					Some(StatementKind::Regular),
+				),
+				statement: Some(StatementKind::Regular),
+			};
+			assert_eq!(
+				<Pallet<Test>>::validate_unsigned(source, &call),
+				Ok(ValidTransaction {
+					priority: 100,
+					requires: vec![],
+					provides: vec![("claims", eth(&dave())).encode()],
+					longevity: TransactionLongevity::max_value(),
+					propagate: true,
+				})
+			);
+
+			let call = ClaimsCall::claim_statement {
+				dest: 1,
+				ethereum_signature: typed_sig::<Test>(&dave(), &1u64.encode(), None),
+				statement: None,
+			};
+			assert_eq!(
+				<Pallet<Test>>::validate_unsigned(source, &call),
+				InvalidTransaction::Custom(ValidityError::InvalidStatement.into()).into(),
+			);
+
+			let call = ClaimsCall::claim_statement {
+				dest: 1,
+				ethereum_signature: typed_sig::<Test>(&bob(), &1u64.encode(), None),
+				statement: None,
+			};
+			assert_eq!(
+				<Pallet<Test>>::validate_unsigned(source, &call),
+				InvalidTransaction::Custom(ValidityError::SignerHasNoClaim.into()).into(),
+			);
+		});
+	}
+
 	#[test]
 	fn attest_claiming_works() {
 		new_test_ext().execute_with(|| {
@@ -221,6 +221,14 @@ pub mod pallet {
 		/// The maximum length for the memo attached to a crowdloan contribution.
 		type MaxMemoLength: Get<u8>;
 
+		/// The origin of contributions made by a parachain on behalf of its users, typically
+		/// through an XCM `Transact`. Yields the contributing parachain.
+		type ParaContributionOrigin: EnsureOrigin<Self::Origin, Success = ParaId>;
+
+		/// The most a single parachain may contribute to any one crowdloan.
+		#[pallet::constant]
+		type MaxParaContribution: Get<BalanceOf<Self>>;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -275,6 +283,9 @@ pub mod pallet {
 		MemoUpdated(T::AccountId, ParaId, Vec<u8>),
 		/// A parachain has been moved to `NewRaise`
 		AddedToNewRaise(ParaId),
+		/// A parachain contributed to a crowd sale from its sovereign account.
+		/// `[contributing_para, fund_index, amount]`
+		ParaContributed(ParaId, ParaId, BalanceOf<T>),
 	}
 
 	#[pallet::error]
@@ -325,6 +336,8 @@ pub mod pallet {
 		VrfDelayInProgress,
 		/// A lease period has not started yet, due to an offset in the starting block.
 		NoLeasePeriod,
+		/// The contributions of a parachain would exceed `MaxParaContribution`.
+		ParaContributionCapExceeded,
 	}
 
 	#[pallet::hooks]
@@ -510,42 +523,51 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 
-			let mut fund = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?;
-			let now = frame_system::Pallet::<T>::block_number();
-			let fund_account = Self::fund_account_id(fund.fund_index);
-			Self::ensure_crowdloan_ended(now, &fund_account, &fund)?;
-
-			let mut refund_count = 0u32;
-			// Try killing the crowdloan child trie
-			let contributions = Self::contribution_iterator(fund.fund_index);
-			// Assume everyone will be refunded.
-			let mut all_refunded = true;
-			for (who, (balance, _)) in contributions {
-				if refund_count >= T::RemoveKeysLimit::get() {
-					// Not everyone was able to be refunded this time around.
-					all_refunded = false;
-					break
-				}
-				CurrencyOf::<T>::transfer(&fund_account, &who, balance, AllowDeath)?;
-				Self::contribution_kill(fund.fund_index, &who);
-				fund.raised = fund.raised.saturating_sub(balance);
-				refund_count += 1;
-			}
-
-			// Save the changes.
-			Funds::<T>::insert(index, &fund);
-
+			let (refund_count, all_refunded) = Self::do_refund(index, T::RemoveKeysLimit::get())?;
 			if all_refunded {
-				Self::deposit_event(Event::<T>::AllRefunded(index));
 				// Refund for unused refund count.
 				Ok(Some(T::WeightInfo::refund(refund_count)).into())
 			} else {
-				Self::deposit_event(Event::<T>::PartiallyRefunded(index));
 				// No weight to refund since we did not finish the loop.
 				Ok(().into())
 			}
 		}
 
+		/// Refund up to `max_contributors` contributors of an ended crowdloan, dissolving the fund
+		/// once every contributor has been refunded.
+		///
+		/// At most `RemoveKeysLimit` contributors are refunded per call, so large crowdloans can be
+		/// refunded by calling this repeatedly.
+		///
+		/// Origin must be signed, but can come from anyone.
+		#[pallet::weight(
+			T::WeightInfo::refund((*max_contributors).min(T::RemoveKeysLimit::get()))
+				.saturating_add(T::WeightInfo::dissolve())
+		)]
+		pub fn refund_all(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] max_contributors: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let limit = max_contributors.min(T::RemoveKeysLimit::get());
+			let (refund_count, all_refunded) = Self::do_refund(index, limit)?;
+			if !all_refunded {
+				// No dissolution took place.
+				return Ok(Some(T::WeightInfo::refund(refund_count)).into())
+			}
+
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?;
+			if fund.raised.is_zero() {
+				CurrencyOf::<T>::unreserve(&fund.depositor, fund.deposit);
+				Funds::<T>::remove(index);
+				Self::deposit_event(Event::<T>::Dissolved(index));
+			}
+			Ok(Some(T::WeightInfo::refund(refund_count).saturating_add(T::WeightInfo::dissolve()))
+				.into())
+		}
+
 		/// Remove a fund after the retirement period has ended and all funds have been returned.
 		#[pallet::weight(T::WeightInfo::dissolve())]
 		pub fn dissolve(origin: OriginFor<T>, #[pallet::compact] index: ParaId) -> DispatchResult {
@@ -652,6 +674,42 @@ pub mod pallet {
 			let value = CurrencyOf::<T>::free_balance(&who);
 			Self::do_contribute(who, index, value, signature, AllowDeath)
 		}
+
+		/// Contribute to a crowd sale from the sovereign account of a parachain, e.g. on behalf of
+		/// users holding funds on that parachain.
+		///
+		/// Origin must be `ParaContributionOrigin`. The contribution is attributed to the sovereign
+		/// account of the parachain, to which it is refunded, and its total in this crowdloan may
+		/// not exceed `MaxParaContribution`. A non-empty `memo` replaces the memo of the
+		/// contribution, allowing the parachain to record who it contributed for.
+		#[pallet::weight(T::WeightInfo::contribute().saturating_add(T::WeightInfo::add_memo()))]
+		pub fn contribute_from_para(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] value: BalanceOf<T>,
+			signature: Option<MultiSignature>,
+			memo: Vec<u8>,
+		) -> DispatchResult {
+			let para = T::ParaContributionOrigin::ensure_origin(origin)?;
+			ensure!(memo.len() <= T::MaxMemoLength::get().into(), Error::<T>::MemoTooLarge);
+
+			let who: T::AccountId = para.into_account();
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?;
+			let (old_balance, _) = Self::contribution_get(fund.fund_index, &who);
+			let total = old_balance.checked_add(&value).ok_or(Error::<T>::Overflow)?;
+			ensure!(
+				total <= T::MaxParaContribution::get(),
+				Error::<T>::ParaContributionCapExceeded
+			);
+
+			Self::do_contribute(who.clone(), index, value, signature, KeepAlive)?;
+			if !memo.is_empty() {
+				Self::contribution_put(fund.fund_index, &who, &total, &memo);
+			}
+
+			Self::deposit_event(Event::<T>::ParaContributed(para, index, value));
+			Ok(())
+		}
 	}
 }
 
@@ -731,6 +789,44 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Refund up to `limit` contributors of the ended crowdloan of `index`.
+	///
+	/// Returns the number of contributors refunded and whether every contributor has now been
+	/// refunded.
+	fn do_refund(index: ParaId, limit: u32) -> Result<(u32, bool), sp_runtime::DispatchError> {
+		let mut fund = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let fund_account = Self::fund_account_id(fund.fund_index);
+		Self::ensure_crowdloan_ended(now, &fund_account, &fund)?;
+
+		let mut refund_count = 0u32;
+		// Try killing the crowdloan child trie
+		let contributions = Self::contribution_iterator(fund.fund_index);
+		// Assume everyone will be refunded.
+		let mut all_refunded = true;
+		for (who, (balance, _)) in contributions {
+			if refund_count >= limit {
+				// Not everyone was able to be refunded this time around.
+				all_refunded = false;
+				break
+			}
+			CurrencyOf::<T>::transfer(&fund_account, &who, balance, AllowDeath)?;
+			Self::contribution_kill(fund.fund_index, &who);
+			fund.raised = fund.raised.saturating_sub(balance);
+			refund_count += 1;
+		}
+
+		// Save the changes.
+		Funds::<T>::insert(index, &fund);
+
+		if all_refunded {
+			Self::deposit_event(Event::<T>::AllRefunded(index));
+		} else {
+			Self::deposit_event(Event::<T>::PartiallyRefunded(index));
+		}
+		Ok((refund_count, all_refunded))
+	}
+
 	fn do_contribute(
 		who: T::AccountId,
 		index: ParaId,
@@ -845,7 +941,7 @@ mod tests {
 
 	use frame_support::{
 		assert_noop, assert_ok, parameter_types,
-		traits::{OnFinalize, OnInitialize},
+		traits::{EnsureOrigin, OnFinalize, OnInitialize},
 	};
 	use primitives::v2::Id as ParaId;
 	use sp_core::H256;
@@ -1068,6 +1164,22 @@ mod tests {
 		pub const CrowdloanPalletId: PalletId = PalletId(*b"py/cfund");
 		pub const RemoveKeysLimit: u32 = 10;
 		pub const MaxMemoLength: u8 = 32;
+		pub const MaxParaContribution: u64 = 500;
+	}
+
+	/// Treats a signed origin as the parachain whose ID is the signer.
+	pub struct EnsureSignedAsPara;
+	impl EnsureOrigin<Origin> for EnsureSignedAsPara {
+		type Success = ParaId;
+
+		fn try_origin(o: Origin) -> Result<ParaId, Origin> {
+			frame_system::EnsureSigned::<u64>::try_origin(o).map(|who| ParaId::from(who as u32))
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> Origin {
+			Origin::signed(0)
+		}
 	}
 
 	impl Config for Test {
@@ -1079,6 +1191,8 @@ mod tests {
 		type Registrar = TestRegistrar<Test>;
 		type Auctioneer = TestAuctioneer;
 		type MaxMemoLength = MaxMemoLength;
+		type ParaContributionOrigin = EnsureSignedAsPara;
+		type MaxParaContribution = MaxParaContribution;
 		type WeightInfo = crate::crowdloan::TestWeightInfo;
 	}
 
@@ -1623,6 +1737,49 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn refund_all_pages_and_dissolves() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let account_id = Crowdloan::fund_account_id(index);
+
+			// Set up a crowdloan ending on 9
+			assert_ok!(Crowdloan::create(Origin::signed(1), para, 1000, 1, 1, 9, None));
+			for i in 1..=5 {
+				Balances::make_free_balance_be(&i, 1000);
+				assert_ok!(Crowdloan::contribute(Origin::signed(i), para, 100, None));
+			}
+
+			// Can't refund before the crowdloan it has ended
+			assert_noop!(
+				Crowdloan::refund_all(Origin::signed(1337), para, 2),
+				Error::<Test>::FundNotEnded,
+			);
+
+			// Move to the end of the crowdloan
+			run_to_block(10);
+			assert_ok!(Crowdloan::refund_all(Origin::signed(1337), para, 2));
+			assert_eq!(last_event(), super::Event::<Test>::PartiallyRefunded(para).into());
+			assert_eq!(Balances::free_balance(account_id), 300);
+			assert_ok!(Crowdloan::refund_all(Origin::signed(1337), para, 2));
+			assert_eq!(last_event(), super::Event::<Test>::PartiallyRefunded(para).into());
+			assert_eq!(Balances::free_balance(account_id), 100);
+
+			// Refunding the last contributor dissolves the fund.
+			assert_ok!(Crowdloan::refund_all(Origin::signed(1337), para, 2));
+			assert_eq!(last_event(), super::Event::<Test>::Dissolved(para).into());
+			assert_eq!(Balances::free_balance(account_id), 0);
+			assert_eq!(Crowdloan::funds(para), None);
+			for i in 2..=5 {
+				assert_eq!(Balances::free_balance(i), 1000);
+			}
+			// The deposit is returned to the depositor.
+			assert_eq!(Balances::free_balance(1), 1000 + 1);
+			assert_eq!(Balances::reserved_balance(1), 0);
+		});
+	}
+
 	#[test]
 	fn multiple_refund_works() {
 		new_test_ext().execute_with(|| {
@@ -1867,6 +2024,59 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn contribute_from_para_works() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let contributor = ParaId::from(2000);
+			let sovereign: u64 = contributor.into_account();
+			Balances::make_free_balance_be(&sovereign, 1000);
+
+			assert_ok!(Crowdloan::create(Origin::signed(1), para, 1000, 1, 4, 9, None));
+			// Can't place a memo that is too large.
+			assert_noop!(
+				Crowdloan::contribute_from_para(Origin::signed(2000), para, 49, None, vec![0; 33]),
+				Error::<Test>::MemoTooLarge,
+			);
+			// Can't contribute more than `MaxParaContribution`.
+			assert_noop!(
+				Crowdloan::contribute_from_para(Origin::signed(2000), para, 501, None, vec![]),
+				Error::<Test>::ParaContributionCapExceeded,
+			);
+
+			// The contribution is made from, and attributed to, the sovereign account.
+			assert_ok!(Crowdloan::contribute_from_para(
+				Origin::signed(2000),
+				para,
+				450,
+				None,
+				b"alice".to_vec()
+			));
+			assert_eq!(Balances::free_balance(sovereign), 550);
+			assert_eq!(Crowdloan::contribution_get(0u32, &sovereign), (450, b"alice".to_vec()));
+			assert_eq!(Crowdloan::funds(para).unwrap().raised, 450);
+			assert_eq!(
+				last_event(),
+				crowdloan::Event::<Test>::ParaContributed(contributor, para, 450).into()
+			);
+
+			// An empty memo keeps the existing one.
+			assert_ok!(Crowdloan::contribute_from_para(
+				Origin::signed(2000),
+				para,
+				50,
+				None,
+				vec![]
+			));
+			assert_eq!(Crowdloan::contribution_get(0u32, &sovereign), (500, b"alice".to_vec()));
+			// The cap applies to the total contributed by the parachain.
+			assert_noop!(
+				Crowdloan::contribute_from_para(Origin::signed(2000), para, 10, None, vec![]),
+				Error::<Test>::ParaContributionCapExceeded,
+			);
+		});
+	}
+
 	#[test]
 	fn poke_works() {
 		new_test_ext().execute_with(|| {
@@ -17,7 +17,11 @@
 //! Auxiliary `struct`/`enum`s for polkadot runtime.
 
 use crate::NegativeImbalance;
-use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use frame_support::{
+	parameter_types,
+	traits::{Currency, Get, Imbalance, OnUnbalanced},
+};
+use sp_runtime::Percent;
 
 /// Logic for the author to get a portion of fees.
 pub struct ToAuthor<R>(sp_std::marker::PhantomData<R>);
@@ -35,34 +39,62 @@ where
 	}
 }
 
-pub struct DealWithFees<R>(sp_std::marker::PhantomData<R>);
-impl<R> OnUnbalanced<NegativeImbalance<R>> for DealWithFees<R>
+parameter_types! {
+	/// The share of transaction fees going to the treasury by default, the rest going to the block
+	/// author.
+	pub const FeesToTreasury: Percent = Percent::from_percent(80);
+	/// The share of transaction tips going to the treasury by default, the rest going to the block
+	/// author.
+	pub const TipsToTreasury: Percent = Percent::from_percent(0);
+}
+
+/// Logic for splitting transaction fees and tips between the treasury and the block author.
+///
+/// `FeesShare` and `TipsShare` are the shares of the fees and of the tips respectively that go to
+/// the treasury. By default, 80% of the fees go to the treasury and 20% to the author, while tips
+/// go to the author entirely.
+pub struct DealWithFees<R, FeesShare = FeesToTreasury, TipsShare = TipsToTreasury>(
+	sp_std::marker::PhantomData<(R, FeesShare, TipsShare)>,
+);
+impl<R, FeesShare, TipsShare> OnUnbalanced<NegativeImbalance<R>>
+	for DealWithFees<R, FeesShare, TipsShare>
 where
 	R: pallet_balances::Config + pallet_treasury::Config + pallet_authorship::Config,
 	pallet_treasury::Pallet<R>: OnUnbalanced<NegativeImbalance<R>>,
 	<R as frame_system::Config>::AccountId: From<primitives::v2::AccountId>,
 	<R as frame_system::Config>::AccountId: Into<primitives::v2::AccountId>,
 	<R as frame_system::Config>::Event: From<pallet_balances::Event<R>>,
+	FeesShare: Get<Percent>,
+	TipsShare: Get<Percent>,
 {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>) {
 		if let Some(fees) = fees_then_tips.next() {
-			// for fees, 80% to treasury, 20% to author
-			let mut split = fees.ration(80, 20);
+			let (mut to_treasury, mut to_author) = split_to_treasury(fees, FeesShare::get());
 			if let Some(tips) = fees_then_tips.next() {
-				// for tips, if any, 100% to author
-				tips.merge_into(&mut split.1);
+				let (tips_to_treasury, tips_to_author) = split_to_treasury(tips, TipsShare::get());
+				tips_to_treasury.merge_into(&mut to_treasury);
+				tips_to_author.merge_into(&mut to_author);
 			}
 			use pallet_treasury::Pallet as Treasury;
-			<Treasury<R> as OnUnbalanced<_>>::on_unbalanced(split.0);
-			<ToAuthor<R> as OnUnbalanced<_>>::on_unbalanced(split.1);
+			<Treasury<R> as OnUnbalanced<_>>::on_unbalanced(to_treasury);
+			<ToAuthor<R> as OnUnbalanced<_>>::on_unbalanced(to_author);
 		}
 	}
 }
 
+/// Split `amount` into the `share` going to the treasury and the rest.
+fn split_to_treasury<R: pallet_balances::Config>(
+	amount: NegativeImbalance<R>,
+	share: Percent,
+) -> (NegativeImbalance<R>, NegativeImbalance<R>) {
+	let to_treasury = share.deconstruct() as u32;
+	amount.ration(to_treasury, 100 - to_treasury)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::{parameter_types, traits::FindAuthor, weights::DispatchClass, PalletId};
+	use frame_support::{traits::FindAuthor, weights::DispatchClass, PalletId};
 	use frame_system::limits;
 	use primitives::v2::AccountId;
 	use sp_core::H256;
@@ -200,7 +232,7 @@ mod tests {
 			assert_eq!(Balances::free_balance(Treasury::account_id()), 0);
 			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 0);
 
-			DealWithFees::on_unbalanceds(vec![fee, tip].into_iter());
+			<DealWithFees<Test>>::on_unbalanceds(vec![fee, tip].into_iter());
 
 			// Author gets 100% of tip and 20% of fee = 22
 			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 22);
@@ -208,4 +240,45 @@ mod tests {
 			assert_eq!(Balances::free_balance(Treasury::account_id()), 8);
 		});
 	}
+
+	#[test]
+	fn default_split_is_pinned() {
+		// Changing these values changes where the fees paid on every chain end up.
+		assert_eq!(FeesToTreasury::get(), Percent::from_percent(80));
+		assert_eq!(TipsToTreasury::get(), Percent::from_percent(0));
+	}
+
+	parameter_types! {
+		pub const HalfToTreasury: Percent = Percent::from_percent(50);
+		pub const AllToTreasury: Percent = Percent::from_percent(100);
+	}
+
+	#[test]
+	fn test_configured_fees_and_tip_split() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(10);
+			let tip = Balances::issue(20);
+
+			<DealWithFees<Test, HalfToTreasury, AllToTreasury>>::on_unbalanceds(
+				vec![fee, tip].into_iter(),
+			);
+
+			// Author gets 50% of fee and nothing of the tip
+			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 5);
+			// Treasury gets 50% of fee and 100% of tip
+			assert_eq!(Balances::free_balance(Treasury::account_id()), 25);
+		});
+	}
+
+	#[test]
+	fn test_fees_without_tip_split() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+
+			<DealWithFees<Test>>::on_unbalanceds(vec![fee].into_iter());
+
+			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 20);
+			assert_eq!(Balances::free_balance(Treasury::account_id()), 80);
+		});
+	}
 }
@@ -258,6 +258,7 @@ parameter_types! {
 	pub const MinContribution: Balance = 1;
 	pub const RemoveKeysLimit: u32 = 100;
 	pub const MaxMemoLength: u8 = 32;
+	pub const MaxParaContribution: Balance = 1_000_000;
 }
 
 impl crowdloan::Config for Test {
@@ -269,6 +270,8 @@ impl crowdloan::Config for Test {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type ParaContributionOrigin = origin::EnsureParachain;
+	type MaxParaContribution = MaxParaContribution;
 	type WeightInfo = crate::crowdloan::TestWeightInfo;
 }
 
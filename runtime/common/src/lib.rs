@@ -30,6 +30,7 @@ pub mod purchase;
 pub mod slot_range;
 pub mod slots;
 pub mod traits;
+pub mod treasury_payouts;
 pub mod xcm_sender;
 
 #[cfg(test)]
@@ -93,8 +94,10 @@ parameter_types! {
 
 /// Parameterized slow adjusting fee updated based on
 /// https://research.web3.foundation/en/latest/polkadot/overview/2-token-economics.html#-2.-slow-adjusting-mechanism
-pub type SlowAdjustingFeeUpdate<R> =
-	TargetedFeeAdjustment<R, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+///
+/// The multiplier never drops below `M`, which defaults to [`MinimumMultiplier`].
+pub type SlowAdjustingFeeUpdate<R, M = MinimumMultiplier> =
+	TargetedFeeAdjustment<R, TargetBlockFullness, AdjustmentVariable, M>;
 
 /// Implements the weight types for a runtime.
 /// It expects the passed runtime constants to contain a `weights` module.
@@ -208,6 +211,23 @@ impl<T: pallet_session::Config> OneSessionHandler<T::AccountId>
 	fn on_disabled(_: u32) {}
 }
 
+/// Convert a balance to an unsigned 256-bit number, use in nomination pools.
+pub struct BalanceToU256;
+impl sp_runtime::traits::Convert<primitives::v2::Balance, sp_core::U256> for BalanceToU256 {
+	fn convert(n: primitives::v2::Balance) -> sp_core::U256 {
+		n.into()
+	}
+}
+
+/// Convert an unsigned 256-bit number to balance, use in nomination pools.
+pub struct U256ToBalance;
+impl sp_runtime::traits::Convert<sp_core::U256, primitives::v2::Balance> for U256ToBalance {
+	fn convert(n: sp_core::U256) -> primitives::v2::Balance {
+		use frame_support::traits::Defensive;
+		n.try_into().defensive_unwrap_or(primitives::v2::Balance::MAX)
+	}
+}
+
 /// A reasonable benchmarking config for staking pallet.
 pub struct StakingBenchmarkingConfig;
 impl pallet_staking::BenchmarkingConfig for StakingBenchmarkingConfig {
@@ -355,4 +375,66 @@ mod multiplier_tests {
 			println!("block = {} multiplier {:?}", blocks, multiplier);
 		}
 	}
+
+	/// Apply `SlowAdjustingFeeUpdate` to `multiplier` for `blocks` blocks each consuming `weight`.
+	fn simulate(mut multiplier: Multiplier, weight: Weight, blocks: usize) -> Vec<Multiplier> {
+		let mut trace = Vec::with_capacity(blocks);
+		run_with_system_weight(weight, || {
+			for _ in 0..blocks {
+				multiplier = SlowAdjustingFeeUpdate::<Runtime>::convert(multiplier);
+				trace.push(multiplier);
+			}
+		});
+		trace
+	}
+
+	#[test]
+	fn multiplier_decreases_to_floor_on_empty_blocks() {
+		let minimum_multiplier = MinimumMultiplier::get();
+		let trace = simulate(Multiplier::one(), 0, 1_000);
+
+		// every empty block lowers the multiplier, until it reaches the floor.
+		let mut previous = Multiplier::one();
+		for next in trace.iter().copied() {
+			assert!(next >= minimum_multiplier, "{:?} < {:?}", next, minimum_multiplier);
+			assert!(next < previous || next == minimum_multiplier, "{:?} !< {:?}", next, previous);
+			previous = next;
+		}
+
+		// once at the floor, it stays there.
+		let trace = simulate(minimum_multiplier, 0, 100);
+		assert!(trace.into_iter().all(|next| next == minimum_multiplier));
+	}
+
+	#[test]
+	fn multiplier_grows_on_full_blocks() {
+		let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+		let trace = simulate(MinimumMultiplier::get(), max_normal, 1_000);
+
+		let mut previous = MinimumMultiplier::get();
+		for next in trace {
+			assert!(next > previous, "{:?} !> {:?}", next, previous);
+			previous = next;
+		}
+	}
+
+	#[test]
+	fn multiplier_is_stable_on_target_blocks() {
+		let target = TargetBlockFullness::get() *
+			BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+		let trace = simulate(Multiplier::one(), target, 1_000);
+		assert!(trace.into_iter().all(|next| next == Multiplier::one()));
+	}
+
+	#[test]
+	fn multiplier_respects_custom_floor() {
+		parameter_types! {
+			pub CustomFloor: Multiplier = Multiplier::saturating_from_rational(1, 10);
+		}
+
+		run_with_system_weight(0, || {
+			let next = SlowAdjustingFeeUpdate::<Runtime, CustomFloor>::convert(CustomFloor::get());
+			assert_eq!(next, CustomFloor::get());
+		});
+	}
 }
@@ -22,6 +22,7 @@ use frame_support::{
 	ensure,
 	pallet_prelude::Weight,
 	traits::{Currency, Get, ReservableCurrency},
+	transactional,
 };
 use frame_system::{self, ensure_root, ensure_signed};
 use primitives::v2::{HeadData, Id as ParaId, ValidationCode, LOWEST_PUBLIC_ID};
@@ -60,6 +61,7 @@ pub trait WeightInfo {
 	fn force_register() -> Weight;
 	fn deregister() -> Weight;
 	fn swap() -> Weight;
+	fn register_parathread() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -79,6 +81,9 @@ impl WeightInfo for TestWeightInfo {
 	fn swap() -> Weight {
 		0
 	}
+	fn register_parathread() -> Weight {
+		0
+	}
 }
 
 #[frame_support::pallet]
@@ -348,6 +353,37 @@ pub mod pallet {
 			NextFreeParaId::<T>::set(id + 1);
 			Ok(())
 		}
+
+		/// Reserve a Para Id and register head data and validation code for it in one call,
+		/// creating a parathread.
+		///
+		/// Once onboarded, the parathread may be scheduled by placing on-demand orders for it,
+		/// without needing a parachain slot.
+		///
+		/// ## Arguments
+		/// - `origin`: Must be called by a `Signed` origin. Becomes the manager/owner of the new para ID.
+		/// - `genesis_head`: The genesis head data of the parathread.
+		/// - `validation_code`: The initial validation code of the parathread.
+		///
+		/// ## Deposits/Fees
+		/// The origin must reserve the same deposits as for `reserve` followed by `register`.
+		///
+		/// ## Events
+		/// The `Reserved` and `Registered` events are emitted in case of success, which provide the
+		/// ID reserved for use.
+		#[pallet::weight(<T as Config>::WeightInfo::register_parathread())]
+		#[transactional]
+		pub fn register_parathread(
+			origin: OriginFor<T>,
+			genesis_head: HeadData,
+			validation_code: ValidationCode,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id = NextFreeParaId::<T>::get().max(LOWEST_PUBLIC_ID);
+			Self::do_reserve(who.clone(), None, id)?;
+			NextFreeParaId::<T>::set(id + 1);
+			Self::do_register(who, None, id, genesis_head, validation_code, true)
+		}
 	}
 }
 
@@ -883,6 +919,38 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn register_parathread_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			let para_id = LOWEST_PUBLIC_ID;
+			assert_ok!(Registrar::register_parathread(
+				Origin::signed(1),
+				test_genesis_head(32),
+				test_validation_code(32),
+			));
+			assert_eq!(NextFreeParaId::<Test>::get(), para_id + 1);
+			run_to_session(2);
+			assert!(Parachains::is_parathread(para_id));
+			assert_eq!(
+				Balances::reserved_balance(&1),
+				<Test as Config>::ParaDeposit::get() +
+					64 * <Test as Config>::DataDepositPerByte::get()
+			);
+
+			// A failed registration reserves nothing.
+			assert_noop!(
+				Registrar::register_parathread(
+					Origin::signed(1),
+					test_genesis_head(max_head_size() as usize + 1),
+					test_validation_code(32),
+				),
+				Error::<Test>::HeadDataTooLarge
+			);
+			assert_eq!(NextFreeParaId::<Test>::get(), para_id + 1);
+		});
+	}
+
 	#[test]
 	fn register_handles_basic_errors() {
 		new_test_ext().execute_with(|| {
@@ -1311,6 +1379,20 @@ mod benchmarking {
 			assert_last_event::<T>(Event::<T>::Deregistered(para).into());
 		}
 
+		register_parathread {
+			let para = LOWEST_PUBLIC_ID;
+			let genesis_head = Registrar::<T>::worst_head_data();
+			let validation_code = Registrar::<T>::worst_validation_code();
+			let caller: T::AccountId = whitelisted_caller();
+			T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		}: _(RawOrigin::Signed(caller.clone()), genesis_head, validation_code)
+		verify {
+			assert_last_event::<T>(Event::<T>::Registered(para, caller).into());
+			assert_eq!(paras::Pallet::<T>::lifecycle(para), Some(ParaLifecycle::Onboarding));
+			next_scheduled_session::<T>();
+			assert_eq!(paras::Pallet::<T>::lifecycle(para), Some(ParaLifecycle::Parathread));
+		}
+
 		swap {
 			let parathread = register_para::<T>(LOWEST_PUBLIC_ID.into());
 			let parachain = register_para::<T>((LOWEST_PUBLIC_ID + 1).into());
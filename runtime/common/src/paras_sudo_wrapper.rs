@@ -20,7 +20,7 @@ use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use parity_scale_codec::Encode;
-use primitives::v2::Id as ParaId;
+use primitives::v2::{DownwardMessageOrigin, Id as ParaId, ValidationCode};
 use runtime_parachains::{
 	configuration, dmp, hrmp,
 	paras::{self, ParaGenesisArgs},
@@ -41,6 +41,27 @@ pub mod pallet {
 	pub trait Config:
 		configuration::Config + paras::Config + dmp::Config + ump::Config + hrmp::Config
 	{
+		/// The overarching event type.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A para was scheduled to be initialized. `[para_id]`
+		ParaInitializeScheduled(ParaId),
+		/// A para was scheduled to be cleaned up. `[para_id]`
+		ParaCleanupScheduled(ParaId),
+		/// A parathread was scheduled to be upgraded to a parachain. `[para_id]`
+		ParathreadUpgradeScheduled(ParaId),
+		/// A parachain was scheduled to be downgraded to a parathread. `[para_id]`
+		ParachainDowngradeScheduled(ParaId),
+		/// A code upgrade was scheduled for a para. `[para_id]`
+		CodeUpgradeScheduled(ParaId),
+		/// A downward message was queued for a para. `[para_id]`
+		DownwardMessageQueued(ParaId),
+		/// An HRMP channel was established. `[sender, recipient]`
+		HrmpChannelEstablished(ParaId, ParaId),
 	}
 
 	#[pallet::error]
@@ -62,6 +83,9 @@ pub mod pallet {
 		CannotUpgrade,
 		/// Cannot downgrade parachain.
 		CannotDowngrade,
+		/// Could not schedule a code upgrade, either because one is already pending or because
+		/// the para already uses the given code.
+		CannotScheduleCodeUpgrade,
 	}
 
 	#[pallet::hooks]
@@ -79,6 +103,7 @@ pub mod pallet {
 			ensure_root(origin)?;
 			runtime_parachains::schedule_para_initialize::<T>(id, genesis)
 				.map_err(|_| Error::<T>::ParaAlreadyExists)?;
+			Self::deposit_event(Event::ParaInitializeScheduled(id));
 			Ok(())
 		}
 
@@ -88,6 +113,7 @@ pub mod pallet {
 			ensure_root(origin)?;
 			runtime_parachains::schedule_para_cleanup::<T>(id)
 				.map_err(|_| Error::<T>::CouldntCleanup)?;
+			Self::deposit_event(Event::ParaCleanupScheduled(id));
 			Ok(())
 		}
 
@@ -105,6 +131,7 @@ pub mod pallet {
 			);
 			runtime_parachains::schedule_parathread_upgrade::<T>(id)
 				.map_err(|_| Error::<T>::CannotUpgrade)?;
+			Self::deposit_event(Event::ParathreadUpgradeScheduled(id));
 			Ok(())
 		}
 
@@ -122,6 +149,25 @@ pub mod pallet {
 			);
 			runtime_parachains::schedule_parachain_downgrade::<T>(id)
 				.map_err(|_| Error::<T>::CannotDowngrade)?;
+			Self::deposit_event(Event::ParachainDowngradeScheduled(id));
+			Ok(())
+		}
+
+		/// Schedule a code upgrade for a para, as if it was signalled in the current block.
+		///
+		/// The para should exist, must not have an upgrade pending and must not already use
+		/// `new_code`.
+		#[pallet::weight((1_000, DispatchClass::Operational))]
+		pub fn sudo_schedule_code_upgrade(
+			origin: OriginFor<T>,
+			id: ParaId,
+			new_code: ValidationCode,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(<paras::Pallet<T>>::is_valid_para(id), Error::<T>::ParaDoesntExist);
+			runtime_parachains::schedule_code_upgrade::<T>(id, new_code)
+				.map_err(|_| Error::<T>::CannotScheduleCodeUpgrade)?;
+			Self::deposit_event(Event::CodeUpgradeScheduled(id));
 			Ok(())
 		}
 
@@ -138,11 +184,18 @@ pub mod pallet {
 			ensure_root(origin)?;
 			ensure!(<paras::Pallet<T>>::is_valid_para(id), Error::<T>::ParaDoesntExist);
 			let config = <configuration::Pallet<T>>::config();
-			<dmp::Pallet<T>>::queue_downward_message(&config, id, xcm.encode()).map_err(|e| match e
-			{
+			<dmp::Pallet<T>>::queue_downward_message_with_origin(
+				&config,
+				id,
+				DownwardMessageOrigin::Governance,
+				xcm.encode(),
+			)
+			.map_err(|e| match e {
 				dmp::QueueDownwardMessageError::ExceedsMaxMessageSize =>
-					Error::<T>::ExceedsMaxMessageSize.into(),
-			})
+					Error::<T>::ExceedsMaxMessageSize,
+			})?;
+			Self::deposit_event(Event::DownwardMessageQueued(id));
+			Ok(())
 		}
 
 		/// Forcefully establish a channel from the sender to the recipient.
@@ -166,6 +219,7 @@ pub mod pallet {
 				max_message_size,
 			)?;
 			<hrmp::Pallet<T>>::accept_open_channel(recipient, sender)?;
+			Self::deposit_event(Event::HrmpChannelEstablished(sender, recipient));
 			Ok(())
 		}
 	}
@@ -42,6 +42,7 @@ pub trait WeightInfo {
 	fn manage_lease_period_start(c: u32, t: u32) -> Weight;
 	fn clear_all_leases() -> Weight;
 	fn trigger_onboard() -> Weight;
+	fn swap_leases() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -58,6 +59,9 @@ impl WeightInfo for TestWeightInfo {
 	fn trigger_onboard() -> Weight {
 		0
 	}
+	fn swap_leases() -> Weight {
+		0
+	}
 }
 
 #[frame_support::pallet]
@@ -133,6 +137,8 @@ pub mod pallet {
 			BalanceOf<T>,
 			BalanceOf<T>,
 		),
+		/// The leases of two paras have been swapped. `[para_id, other_para_id]`
+		LeasesSwapped(ParaId, ParaId),
 	}
 
 	#[pallet::error]
@@ -141,6 +147,8 @@ pub mod pallet {
 		ParaNotOnboarding,
 		/// There was an error with the lease.
 		LeaseError,
+		/// The leases of a para cannot be swapped with itself.
+		SameParaId,
 	}
 
 	#[pallet::hooks]
@@ -217,6 +225,37 @@ pub mod pallet {
 			};
 			Ok(())
 		}
+
+		/// Swap the leases of two paras, e.g. to move a parachain's slot to a new para ID without
+		/// both managers having to call `swap` on the registrar.
+		///
+		/// Deposits stay reserved by the leasers who made them and move along with the leases.
+		/// If exactly one of the paras has a lease for the current lease period and is a
+		/// parachain while the other is a parathread, their lifecycles are swapped as well.
+		/// Everything else about the paras, including their HRMP channels, stays with their IDs.
+		///
+		/// The dispatch origin for this call must match `T::ForceOrigin`.
+		#[pallet::weight(T::WeightInfo::swap_leases())]
+		pub fn swap_leases(origin: OriginFor<T>, para: ParaId, other: ParaId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(para != other, Error::<T>::SameParaId);
+
+			let has_current_lease = |id| matches!(Leases::<T>::get(id).first(), Some(Some(_)));
+			let transition = match (has_current_lease(para), has_current_lease(other)) {
+				(true, false) => Some((para, other)),
+				(false, true) => Some((other, para)),
+				_ => None,
+			};
+			if let Some((outgoing, incoming)) = transition {
+				if T::Registrar::is_parachain(outgoing) && T::Registrar::is_parathread(incoming) {
+					T::Registrar::make_parathread(outgoing)?;
+					T::Registrar::make_parachain(incoming)?;
+				}
+			}
+
+			Self::do_swap_leases(para, other);
+			Ok(())
+		}
 	}
 }
 
@@ -302,6 +341,12 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Swap the leases of two paras.
+	fn do_swap_leases(one: ParaId, other: ParaId) {
+		Leases::<T>::mutate(one, |x| Leases::<T>::mutate(other, |y| sp_std::mem::swap(x, y)));
+		Self::deposit_event(Event::<T>::LeasesSwapped(one, other));
+	}
+
 	// Return a vector of (user, balance) for all deposits for a parachain.
 	// Useful when trying to clean up a parachain leases, as this would tell
 	// you all the balances you need to unreserve.
@@ -326,7 +371,7 @@ impl<T: Config> Pallet<T> {
 
 impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	fn on_swap(one: ParaId, other: ParaId) {
-		Leases::<T>::mutate(one, |x| Leases::<T>::mutate(other, |y| sp_std::mem::swap(x, y)))
+		Self::do_swap_leases(one, other)
 	}
 }
 
@@ -944,6 +989,49 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn swap_leases_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			for id in 1..=2 {
+				assert_ok!(TestRegistrar::<Test>::register(
+					1,
+					ParaId::from(id),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+
+			// Para 1 leases the current and next lease periods, and is onboarded.
+			assert_ok!(Slots::lease_out(1.into(), &1, 1, 0, 2));
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![1.into()]);
+
+			assert_noop!(
+				Slots::swap_leases(Origin::signed(1), 1.into(), 2.into()),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Slots::swap_leases(Origin::root(), 1.into(), 1.into()),
+				Error::<Test>::SameParaId
+			);
+
+			assert_ok!(Slots::swap_leases(Origin::root(), 1.into(), 2.into()));
+			assert!(Leases::<Test>::get(ParaId::from(1)).is_empty());
+			assert_eq!(Leases::<Test>::get(ParaId::from(2)), vec![Some((1, 1)), Some((1, 1))]);
+			// The deposit moves with the lease.
+			assert_eq!(Slots::deposit_held(1.into(), &1), 0);
+			assert_eq!(Slots::deposit_held(2.into(), &1), 1);
+			assert_eq!(Balances::reserved_balance(1), 1);
+			// And so does the parachain slot.
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![2.into()]);
+			assert_eq!(TestRegistrar::<Test>::parathreads(), vec![1.into()]);
+			assert_eq!(
+				System::events().pop().unwrap().event,
+				Event::Slots(crate::slots::Event::LeasesSwapped(1.into(), 2.into()))
+			);
+		});
+	}
+
 	#[test]
 	fn lease_period_offset_works() {
 		new_test_ext().execute_with(|| {
@@ -1117,6 +1205,27 @@ mod benchmarking {
 			}
 		}
 
+		// Worst case scenario, the lifecycles of a parachain and a parathread are swapped.
+		swap_leases {
+			// If there is an offset, we need to be on that block to be able to do lease things.
+			let now = T::LeaseOffset::get() + One::one();
+			frame_system::Pallet::<T>::set_block_number(now);
+			let (parachain, leaser) = register_a_parathread::<T>(1);
+			let (parathread, _) = register_a_parathread::<T>(2);
+			let (period_begin, _) = Slots::<T>::lease_period_index(now).unwrap();
+			let amount = T::Currency::minimum_balance();
+			Slots::<T>::lease_out(parachain, &leaser, amount, period_begin, 1u32.into())
+				.map_err(|_| "lease_out failed")?;
+			T::Registrar::execute_pending_transitions();
+			assert!(T::Registrar::is_parachain(parachain));
+		}: _(RawOrigin::Root, parachain, parathread)
+		verify {
+			assert_last_event::<T>(Event::<T>::LeasesSwapped(parachain, parathread).into());
+			T::Registrar::execute_pending_transitions();
+			assert!(T::Registrar::is_parathread(parachain));
+			assert!(T::Registrar::is_parachain(parathread));
+		}
+
 		trigger_onboard {
 			// get a parachain into a bad state where they did not onboard
 			let (para, _) = register_a_parathread::<T>(1);
@@ -20,6 +20,7 @@ use frame_support::{
 	dispatch::DispatchResult,
 	traits::{Currency, ReservableCurrency},
 };
+use parity_scale_codec::{Decode, Encode};
 use primitives::v2::{HeadData, Id as ParaId, ValidationCode};
 use sp_std::vec::*;
 
@@ -156,7 +157,7 @@ pub trait Leaser<BlockNumber> {
 }
 
 /// An enum which tracks the status of the auction system, and which phase it is in.
-#[derive(PartialEq, Debug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
 pub enum AuctionStatus<BlockNumber> {
 	/// An auction has not started yet.
 	NotStarted,
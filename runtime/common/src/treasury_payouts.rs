@@ -0,0 +1,429 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet to pay treasury spends out to beneficiaries on other chains.
+//!
+//! The treasury pallet only pays out to local accounts. With this pallet, the origins which may
+//! spend from the treasury pay the native asset out to a beneficiary on another chain through a
+//! [`Pay`] implementation, such as [`xcm_builder::PayOverXcm`]. Each payout is kept until anyone
+//! checks its status and finds that it has concluded.
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+pub use pallet::*;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::{boxed::Box, convert::TryInto};
+use xcm::{latest::prelude::*, VersionedMultiLocation};
+use xcm_builder::{LocatableAssetId, Pay, PaymentStatus};
+
+/// The index of a payout.
+pub type PayoutIndex = u32;
+
+/// A payout which has not concluded yet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Payout {
+	/// The chain on which the beneficiary is paid.
+	pub destination: MultiLocation,
+	/// The beneficiary, from the context of `destination`.
+	pub beneficiary: MultiLocation,
+	/// The amount of the native asset paid out.
+	pub amount: u128,
+	/// The identifier by which the paymaster tracks the payment.
+	pub payment_id: QueryId,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may pay out, along with the largest amount it may pay out at once.
+		type SpendOrigin: EnsureOrigin<Self::Origin, Success = u128>;
+
+		/// Makes the payments.
+		type Paymaster: Pay<
+			Balance = u128,
+			Beneficiary = MultiLocation,
+			AssetKind = LocatableAssetId,
+			Id = QueryId,
+		>;
+
+		/// The native asset of this chain, in which payouts are made.
+		#[pallet::constant]
+		type NativeAsset: Get<AssetId>;
+
+		/// The weight of making a single payment with `Paymaster`.
+		#[pallet::constant]
+		type PaymentWeight: Get<Weight>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A payout was made. `[index, destination, beneficiary, amount]`
+		Paid(PayoutIndex, MultiLocation, MultiLocation, u128),
+		/// A payout was received by its beneficiary. `[index]`
+		PaymentSucceeded(PayoutIndex),
+		/// A payout failed to reach its beneficiary. `[index]`
+		PaymentFailed(PayoutIndex),
+		/// The outcome of a payout is not known, e.g. because it was not reported in time. The
+		/// payout is forgotten. `[index]`
+		PaymentUnknown(PayoutIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The amount is more than the origin may pay out.
+		InsufficientPermission,
+		/// A location is of a version which cannot be converted to the latest one.
+		BadVersion,
+		/// The paymaster failed to make the payment.
+		PayoutError,
+		/// There is no pending payout with the given index.
+		UnknownPayout,
+		/// The payout has not concluded yet.
+		PaymentInProgress,
+	}
+
+	/// The number of payouts made so far, which is the index of the next one.
+	#[pallet::storage]
+	pub type PayoutCount<T> = StorageValue<_, PayoutIndex, ValueQuery>;
+
+	/// The payouts which have not concluded yet.
+	#[pallet::storage]
+	pub type Payouts<T> = StorageMap<_, Twox64Concat, PayoutIndex, Payout>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay `amount` of the native asset out to `beneficiary` on the chain at `destination`.
+		///
+		/// The dispatch origin must be the `SpendOrigin`, allowed to pay out at least `amount`.
+		///
+		/// The beneficiary is given from the context of `destination`.
+		//
+		// Hand-estimated: the payment itself plus the payout storage.
+		#[pallet::weight(T::PaymentWeight::get().saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
+		#[transactional]
+		pub fn pay_out(
+			origin: OriginFor<T>,
+			destination: Box<VersionedMultiLocation>,
+			beneficiary: Box<VersionedMultiLocation>,
+			#[pallet::compact] amount: u128,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			ensure!(amount <= max_amount, Error::<T>::InsufficientPermission);
+
+			let destination: MultiLocation =
+				(*destination).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let asset_kind =
+				LocatableAssetId { asset_id: T::NativeAsset::get(), location: destination.clone() };
+			let payment_id = T::Paymaster::pay(&beneficiary, asset_kind, amount)
+				.map_err(|_| Error::<T>::PayoutError)?;
+
+			let index = PayoutCount::<T>::mutate(|count| {
+				let index = *count;
+				*count = count.saturating_add(1);
+				index
+			});
+			Payouts::<T>::insert(
+				index,
+				Payout {
+					destination: destination.clone(),
+					beneficiary: beneficiary.clone(),
+					amount,
+					payment_id,
+				},
+			);
+
+			Self::deposit_event(Event::Paid(index, destination, beneficiary, amount));
+			Ok(())
+		}
+
+		/// Check whether the payout with the given `index` has concluded, and forget it if so.
+		///
+		/// The dispatch origin must be signed.
+		//
+		// Hand-estimated: the payout and the status of its payment.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn check_status(origin: OriginFor<T>, index: PayoutIndex) -> DispatchResult {
+			ensure_signed(origin)?;
+			let payout = Payouts::<T>::get(index).ok_or(Error::<T>::UnknownPayout)?;
+
+			let event = match T::Paymaster::check_payment(payout.payment_id) {
+				PaymentStatus::InProgress => return Err(Error::<T>::PaymentInProgress.into()),
+				PaymentStatus::Success => Event::PaymentSucceeded(index),
+				PaymentStatus::Failure => Event::PaymentFailed(index),
+				PaymentStatus::Unknown => Event::PaymentUnknown(index),
+			};
+
+			Payouts::<T>::remove(index);
+			Self::deposit_event(event);
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::treasury_payouts;
+	use frame_support::{assert_noop, assert_ok, parameter_types, traits::EnsureOrigin};
+	use sp_core::H256;
+	use sp_runtime::{
+		testing::Header,
+		traits::{BlakeTwo256, IdentityLookup},
+		DispatchError::BadOrigin,
+	};
+	use std::{cell::RefCell, collections::BTreeMap};
+
+	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test where
+			Block = Block,
+			NodeBlock = Block,
+			UncheckedExtrinsic = UncheckedExtrinsic,
+		{
+			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+			TreasuryPayouts: treasury_payouts::{Pallet, Call, Storage, Event},
+		}
+	);
+
+	parameter_types! {
+		pub const BlockHashCount: u32 = 250;
+	}
+	impl frame_system::Config for Test {
+		type BaseCallFilter = frame_support::traits::Everything;
+		type BlockWeights = ();
+		type BlockLength = ();
+		type DbWeight = ();
+		type Origin = Origin;
+		type Call = Call;
+		type Index = u64;
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<u64>;
+		type Header = Header;
+		type Event = Event;
+		type BlockHashCount = BlockHashCount;
+		type Version = ();
+		type PalletInfo = PalletInfo;
+		type AccountData = ();
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = ();
+		type OnSetCode = ();
+		type MaxConsumers = frame_support::traits::ConstU32<16>;
+	}
+
+	thread_local! {
+		static PAYMENTS: RefCell<BTreeMap<QueryId, (MultiLocation, LocatableAssetId, u128, PaymentStatus)>> =
+			RefCell::new(BTreeMap::new());
+	}
+
+	/// Records the payments, which are in progress until their status is set.
+	pub struct TestPaymaster;
+	impl Pay for TestPaymaster {
+		type Balance = u128;
+		type Beneficiary = MultiLocation;
+		type AssetKind = LocatableAssetId;
+		type Id = QueryId;
+		type Error = ();
+
+		fn pay(
+			who: &MultiLocation,
+			asset_kind: LocatableAssetId,
+			amount: u128,
+		) -> Result<QueryId, ()> {
+			if amount == 0 {
+				return Err(())
+			}
+			PAYMENTS.with(|p| {
+				let mut p = p.borrow_mut();
+				let id = p.len() as QueryId;
+				p.insert(id, (who.clone(), asset_kind, amount, PaymentStatus::InProgress));
+				Ok(id)
+			})
+		}
+
+		fn check_payment(id: QueryId) -> PaymentStatus {
+			PAYMENTS.with(|p| p.borrow().get(&id).map_or(PaymentStatus::Unknown, |p| p.3))
+		}
+	}
+
+	fn set_status(id: QueryId, status: PaymentStatus) {
+		PAYMENTS.with(|p| p.borrow_mut().get_mut(&id).unwrap().3 = status);
+	}
+
+	/// Root may pay out any amount, account `1` up to 100.
+	pub struct TestSpendOrigin;
+	impl EnsureOrigin<Origin> for TestSpendOrigin {
+		type Success = u128;
+		fn try_origin(o: Origin) -> Result<u128, Origin> {
+			match o.clone().into() {
+				Ok(frame_system::RawOrigin::Root) => Ok(u128::MAX),
+				Ok(frame_system::RawOrigin::Signed(1)) => Ok(100),
+				_ => Err(o),
+			}
+		}
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> Origin {
+			Origin::root()
+		}
+	}
+
+	parameter_types! {
+		pub NativeAsset: AssetId = Concrete(Here.into());
+		pub const PaymentWeight: Weight = 1_000;
+	}
+
+	impl Config for Test {
+		type Event = Event;
+		type SpendOrigin = TestSpendOrigin;
+		type Paymaster = TestPaymaster;
+		type NativeAsset = NativeAsset;
+		type PaymentWeight = PaymentWeight;
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn statemint() -> MultiLocation {
+		Parachain(1000).into()
+	}
+
+	fn alice() -> MultiLocation {
+		AccountId32 { network: NetworkId::Any, id: [1; 32] }.into()
+	}
+
+	fn pay_out(origin: Origin, amount: u128) -> DispatchResult {
+		TreasuryPayouts::pay_out(
+			origin,
+			Box::new(statemint().into()),
+			Box::new(alice().into()),
+			amount,
+		)
+	}
+
+	#[test]
+	fn pay_out_pays_the_native_asset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(pay_out(Origin::signed(1), 100));
+
+			let payout = Payouts::<Test>::get(0).unwrap();
+			assert_eq!(
+				payout,
+				Payout {
+					destination: statemint(),
+					beneficiary: alice(),
+					amount: 100,
+					payment_id: 0
+				}
+			);
+			assert_eq!(PayoutCount::<Test>::get(), 1);
+			PAYMENTS.with(|p| {
+				assert_eq!(
+					p.borrow()[&0],
+					(
+						alice(),
+						LocatableAssetId { asset_id: NativeAsset::get(), location: statemint() },
+						100,
+						PaymentStatus::InProgress
+					)
+				)
+			});
+			System::assert_last_event(Event::TreasuryPayouts(
+				crate::treasury_payouts::Event::Paid(0, statemint(), alice(), 100),
+			));
+		});
+	}
+
+	#[test]
+	fn pay_out_is_limited_by_the_origin() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(pay_out(Origin::signed(2), 1), BadOrigin);
+			assert_noop!(pay_out(Origin::signed(1), 101), Error::<Test>::InsufficientPermission);
+			assert_ok!(pay_out(Origin::root(), 1_000));
+		});
+	}
+
+	#[test]
+	fn failed_payment_is_not_recorded() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(pay_out(Origin::root(), 0), Error::<Test>::PayoutError);
+			assert_eq!(PayoutCount::<Test>::get(), 0);
+		});
+	}
+
+	#[test]
+	fn check_status_forgets_concluded_payouts() {
+		new_test_ext().execute_with(|| {
+			for _ in 0..3 {
+				assert_ok!(pay_out(Origin::root(), 10));
+			}
+
+			assert_noop!(
+				TreasuryPayouts::check_status(Origin::signed(5), 0),
+				Error::<Test>::PaymentInProgress
+			);
+
+			set_status(0, PaymentStatus::Success);
+			set_status(1, PaymentStatus::Failure);
+			set_status(2, PaymentStatus::Unknown);
+			for index in 0..3 {
+				assert_ok!(TreasuryPayouts::check_status(Origin::signed(5), index));
+				assert!(!Payouts::<Test>::contains_key(index));
+			}
+
+			let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+			assert_eq!(
+				events[events.len() - 3..],
+				[
+					Event::TreasuryPayouts(crate::treasury_payouts::Event::PaymentSucceeded(0)),
+					Event::TreasuryPayouts(crate::treasury_payouts::Event::PaymentFailed(1)),
+					Event::TreasuryPayouts(crate::treasury_payouts::Event::PaymentUnknown(2)),
+				]
+			);
+
+			assert_noop!(
+				TreasuryPayouts::check_status(Origin::signed(5), 0),
+				Error::<Test>::UnknownPayout
+			);
+		});
+	}
+}
@@ -16,34 +16,174 @@
 
 //! XCM sender for relay chain.
 
-use parity_scale_codec::Encode;
-use runtime_parachains::{configuration, dmp};
-use sp_std::marker::PhantomData;
-use xcm::latest::prelude::*;
+use frame_support::traits::Get;
+use parity_scale_codec::{Decode, Encode};
+use primitives::v2::Id as ParaId;
+use runtime_parachains::{configuration, dmp, FeeTracker};
+use sp_runtime::{traits::One, FixedPointNumber, FixedU128};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+/// Means of determining the cost of sending an XCM message to a given destination.
+pub trait PriceForMessageDelivery {
+	/// Return the assets which must be paid in order to deliver `message` to `dest`.
+	fn price_for_delivery(dest: &MultiLocation, message: &Xcm<()>) -> MultiAssets;
+}
+
+/// Delivery of messages is free.
+impl PriceForMessageDelivery for () {
+	fn price_for_delivery(_: &MultiLocation, _: &Xcm<()>) -> MultiAssets {
+		MultiAssets::new()
+	}
+}
+
+/// Delivery of any message costs the same constant price, given by `T`.
+pub struct ConstantPrice<T>(PhantomData<T>);
+impl<T: Get<MultiAssets>> PriceForMessageDelivery for ConstantPrice<T> {
+	fn price_for_delivery(_: &MultiLocation, _: &Xcm<()>) -> MultiAssets {
+		T::get()
+	}
+}
+
+/// Implementation of `PriceForMessageDelivery` which returns an exponentially increasing price for
+/// messages to congested queues.
+///
+/// The price is `(BaseFee + ByteFee * message_size) * factor`, paid in the asset `FeeAssetId`.
+/// The `factor` is given by `FeeFactor` for messages to child parachains and is one for messages
+/// to any other destination.
+pub struct ExponentialPrice<FeeAssetId, BaseFee, ByteFee, FeeFactor>(
+	PhantomData<(FeeAssetId, BaseFee, ByteFee, FeeFactor)>,
+);
+impl<FeeAssetId: Get<AssetId>, BaseFee: Get<u128>, ByteFee: Get<u128>, FeeFactor: FeeTracker>
+	PriceForMessageDelivery for ExponentialPrice<FeeAssetId, BaseFee, ByteFee, FeeFactor>
+{
+	fn price_for_delivery(dest: &MultiLocation, message: &Xcm<()>) -> MultiAssets {
+		let message_fee = (message.encoded_size() as u128).saturating_mul(ByteFee::get());
+		let fee_sum = BaseFee::get().saturating_add(message_fee);
+		let factor = match dest {
+			MultiLocation { parents: 0, interior: X1(Parachain(id)) } =>
+				FeeFactor::get_fee_factor((*id).into()),
+			_ => FixedU128::one(),
+		};
+		(FeeAssetId::get(), factor.saturating_mul_int(fee_sum)).into()
+	}
+}
 
 /// XCM sender for relay chain. It only sends downward message.
-pub struct ChildParachainRouter<T, W>(PhantomData<(T, W)>);
+///
+/// The price of delivering a message is determined by `P`.
+pub struct ChildParachainRouter<T, W, P = ()>(PhantomData<(T, W, P)>);
 
-impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion> SendXcm
-	for ChildParachainRouter<T, W>
+impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion, P: PriceForMessageDelivery>
+	SendXcm for ChildParachainRouter<T, W, P>
 {
-	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
-		let dest = dest.into();
-		match dest {
-			MultiLocation { parents: 0, interior: X1(Parachain(id)) } => {
-				// Downward message passing.
-				let versioned_xcm =
-					W::wrap_version(&dest, msg).map_err(|()| SendError::DestinationUnsupported)?;
-				let config = <configuration::Pallet<T>>::config();
-				<dmp::Pallet<T>>::queue_downward_message(
-					&config,
-					id.into(),
-					versioned_xcm.encode(),
-				)
-				.map_err(Into::<SendError>::into)?;
-				Ok(())
-			},
-			dest => Err(SendError::CannotReachDestination(dest, msg)),
+	type Ticket = (ParaId, Vec<u8>);
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(ParaId, Vec<u8>)> {
+		let d = dest.take().ok_or(SendError::MissingArgument)?;
+		let id = if let MultiLocation { parents: 0, interior: X1(Parachain(id)) } = &d {
+			*id
+		} else {
+			*dest = Some(d);
+			return Err(SendError::NotApplicable)
+		};
+
+		// Downward message passing.
+		let xcm = msg.take().ok_or(SendError::MissingArgument)?;
+		let price = P::price_for_delivery(&d, &xcm);
+		let versioned_xcm =
+			W::wrap_version(&d, xcm).map_err(|()| SendError::DestinationUnsupported)?;
+		let blob = versioned_xcm.encode();
+		let config = <configuration::Pallet<T>>::config();
+		if blob.len() as u32 > config.max_downward_message_size {
+			return Err(SendError::ExceedsMaxMessageSize)
 		}
+
+		Ok(((id.into(), blob), price))
+	}
+
+	fn deliver((id, blob): (ParaId, Vec<u8>)) -> Result<XcmHash, SendError> {
+		let hash = sp_io::hashing::blake2_256(&blob[..]);
+		let config = <configuration::Pallet<T>>::config();
+		<dmp::Pallet<T>>::queue_downward_message(&config, id, blob)
+			.map_err(Into::<SendError>::into)?;
+		Ok(hash)
+	}
+}
+
+impl<T: configuration::Config + dmp::Config, W, P> InspectMessageQueues
+	for ChildParachainRouter<T, W, P>
+{
+	fn clear_messages() {
+		<dmp::Pallet<T>>::clear_dmqs();
+	}
+
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		<dmp::Pallet<T>>::dmq_contents_all()
+			.into_iter()
+			.map(|(para_id, messages)| {
+				let decoded_messages = messages
+					.iter()
+					.filter_map(|m| VersionedXcm::<()>::decode(&mut &m.msg[..]).ok())
+					.collect();
+				let dest = MultiLocation::from(Parachain(para_id.into()));
+				(VersionedMultiLocation::from(dest), decoded_messages)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+
+	parameter_types! {
+		pub const FeeAssetId: AssetId = Concrete(Here.into());
+		pub const BaseDeliveryFee: u128 = 1_000_000_000;
+		pub const TransactionByteFee: u128 = 10_000;
+	}
+
+	struct TestFeeTracker;
+	impl FeeTracker for TestFeeTracker {
+		fn get_fee_factor(para: ParaId) -> FixedU128 {
+			if para == 2000.into() {
+				FixedU128::saturating_from_integer(2)
+			} else {
+				FixedU128::one()
+			}
+		}
+	}
+
+	type TestExponentialPrice =
+		ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, TestFeeTracker>;
+
+	#[test]
+	fn exponential_price_correct_price_calculation() {
+		let message = Xcm(vec![Trap(10)]);
+		let size = message.encoded_size() as u128;
+		let fee = BaseDeliveryFee::get() + TransactionByteFee::get() * size;
+
+		// Destinations which are not congested pay the base price.
+		let dest = Parachain(1000).into();
+		assert_eq!(
+			TestExponentialPrice::price_for_delivery(&dest, &message),
+			(FeeAssetId::get(), fee).into()
+		);
+		let dest = Parent.into();
+		assert_eq!(
+			TestExponentialPrice::price_for_delivery(&dest, &message),
+			(FeeAssetId::get(), fee).into()
+		);
+
+		// Congested destinations pay according to their fee factor.
+		let dest = Parachain(2000).into();
+		assert_eq!(
+			TestExponentialPrice::price_for_delivery(&dest, &message),
+			(FeeAssetId::get(), 2 * fee).into()
+		);
 	}
 }
@@ -38,9 +38,11 @@ pub mod currency {
 /// Time and blocks.
 pub mod time {
 	use primitives::v2::{BlockNumber, Moment};
+	use runtime_common::prod_or_fast;
+
 	pub const MILLISECS_PER_BLOCK: Moment = 6000;
 	pub const SLOT_DURATION: Moment = MILLISECS_PER_BLOCK;
-	pub const EPOCH_DURATION_IN_SLOTS: BlockNumber = 1 * HOURS;
+	pub const EPOCH_DURATION_IN_SLOTS: BlockNumber = prod_or_fast!(1 * HOURS, 1 * MINUTES);
 
 	// These time units are defined in number of blocks.
 	pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
@@ -55,16 +57,27 @@ pub mod time {
 /// Fee-related.
 pub mod fee {
 	use crate::weights::ExtrinsicBaseWeight;
-	use frame_support::weights::{
-		WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+	use frame_support::{
+		parameter_types,
+		weights::{WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 	};
 	use primitives::v2::Balance;
 	use smallvec::smallvec;
 	pub use sp_runtime::Perbill;
+	use sp_runtime::{FixedPointNumber, FixedU128};
 
 	/// The block saturation level. Fees will be updates based on this value.
 	pub const TARGET_BLOCK_FULLNESS: Perbill = Perbill::from_percent(25);
 
+	parameter_types! {
+		/// The floor of the fee multiplier. Fees never drop below this fraction of their
+		/// unadjusted value, however long the chain stays below `TARGET_BLOCK_FULLNESS`.
+		pub MinimumMultiplier: FixedU128 = FixedU128::saturating_from_rational(1, 1_000_000u128);
+	}
+
+	/// The fee charged per byte of an extrinsic's encoded length.
+	pub const TRANSACTION_BYTE_FEE: Balance = 10 * super::currency::MILLICENTS;
+
 	/// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 	/// node's balance type.
 	///
@@ -90,13 +103,74 @@ pub mod fee {
 			}]
 		}
 	}
+
+	/// Handles converting an extrinsic's encoded length, in bytes, to a fee value.
+	///
+	/// The fee is linear in the length, at [`TRANSACTION_BYTE_FEE`] per byte.
+	pub struct LengthToFee;
+	impl WeightToFeePolynomial for LengthToFee {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			smallvec![WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_parts(0),
+				coeff_integer: TRANSACTION_BYTE_FEE,
+			}]
+		}
+	}
+}
+
+/// Fast unstake.
+pub mod fast_unstake {
+	use super::currency::CENTS;
+	use primitives::v2::Balance;
+
+	/// The deposit reserved from a nominator registering for a fast unstake. It is slashed if the
+	/// nominator turns out to have been exposed in one of the eras checked.
+	pub const DEPOSIT: Balance = 100 * CENTS;
+	/// The maximum number of queued nominators checked and unstaked in a single block.
+	pub const BATCH_SIZE: u32 = 64;
+}
+
+/// System parachains.
+pub mod system_parachain {
+	use primitives::v2::{Id as ParaId, LOWEST_PUBLIC_ID};
+	use xcm::latest::prelude::*;
+
+	/// Statemine parachain ID.
+	pub const STATEMINE_ID: u32 = 1000;
+	/// Encointer parachain ID.
+	pub const ENCOINTER_ID: u32 = 1001;
+	/// Bridge Hub parachain ID.
+	pub const BRIDGE_HUB_ID: u32 = 1002;
+
+	/// Whether `id` lies in the range reserved for system parachains.
+	pub fn is_system(id: ParaId) -> bool {
+		id < LOWEST_PUBLIC_ID
+	}
+
+	/// The location of the system parachain `id`, from the context of the relay chain.
+	pub fn location(id: u32) -> MultiLocation {
+		Parachain(id).into()
+	}
+
+	/// The location of Statemine, from the context of the relay chain.
+	pub fn statemine_location() -> MultiLocation {
+		location(STATEMINE_ID)
+	}
+
+	/// The location of Encointer, from the context of the relay chain.
+	pub fn encointer_location() -> MultiLocation {
+		location(ENCOINTER_ID)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::{
 		currency::{CENTS, MILLICENTS},
-		fee::WeightToFee,
+		fee::{LengthToFee, WeightToFee, TRANSACTION_BYTE_FEE},
 	};
 	use crate::weights::ExtrinsicBaseWeight;
 	use frame_support::weights::WeightToFeePolynomial;
@@ -121,4 +195,12 @@ mod tests {
 		let y = CENTS / 10;
 		assert!(x.max(y) - x.min(y) < MILLICENTS);
 	}
+
+	#[test]
+	// This function tests that the fee for a 1KB extrinsic is correct
+	fn one_kilobyte_length_fee_is_correct() {
+		assert_eq!(LengthToFee::calc(&1), TRANSACTION_BYTE_FEE);
+		// Changing this value changes the fees charged for every extrinsic.
+		assert_eq!(LengthToFee::calc(&1024), 341_329_920);
+	}
 }
@@ -0,0 +1,80 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migrations introducing the new governance pallets.
+
+use crate::{ConvictionVoting, Referenda, Runtime, Whitelist};
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+
+/// Sets the on-chain storage version of a pallet which was added to the runtime, returning
+/// whether it had to be set.
+fn initialize_storage_version<P: GetStorageVersion + PalletInfoAccess>() -> bool {
+	let unset = StorageVersion::new(0);
+	if P::on_chain_storage_version() != unset || P::current_storage_version() == unset {
+		return false
+	}
+
+	log::info!(
+		target: "runtime::governance",
+		"setting the storage version of {} to {:?}",
+		P::name(),
+		P::current_storage_version(),
+	);
+	P::current_storage_version().put::<P>();
+	true
+}
+
+/// Initializes the pallets introduced by the new governance next to `Democracy` and the
+/// `Council`.
+///
+/// Their storage starts empty, but their storage versions have to be set: otherwise later
+/// versioned migrations of these pallets would run against storage they never wrote. Running
+/// the migration again does nothing.
+pub struct InitializeGovernance;
+impl OnRuntimeUpgrade for InitializeGovernance {
+	fn on_runtime_upgrade() -> Weight {
+		let initialized = [
+			initialize_storage_version::<ConvictionVoting>(),
+			initialize_storage_version::<Referenda>(),
+			initialize_storage_version::<Whitelist>(),
+		];
+		let writes = initialized.iter().filter(|initialized| **initialized).count();
+
+		<Runtime as frame_system::Config>::DbWeight::get()
+			.reads_writes(initialized.len() as Weight, writes as Weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			ConvictionVoting::on_chain_storage_version() ==
+				ConvictionVoting::current_storage_version(),
+			"the storage version of ConvictionVoting was not set",
+		);
+		frame_support::ensure!(
+			Referenda::on_chain_storage_version() == Referenda::current_storage_version(),
+			"the storage version of Referenda was not set",
+		);
+		frame_support::ensure!(
+			Whitelist::on_chain_storage_version() == Whitelist::current_storage_version(),
+			"the storage version of Whitelist was not set",
+		);
+		Ok(())
+	}
+}
@@ -24,9 +24,10 @@ use pallet_transaction_payment::CurrencyAdapter;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::v2::{
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
-	Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
-	SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	CoreState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
+	PersistedValidationData, ScrapedOnChainVotes, SessionInfo, Signature, UpgradeRestriction,
+	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
 };
 use runtime_common::{
 	auctions, claims, crowdloan, elections::OnChainSeqPhragmen, impl_runtime_weights,
@@ -38,7 +39,8 @@ use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, prelude::*};
 use runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, messaging_metrics as parachains_messaging_metrics,
+	origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
 	runtime_api_impl::v2 as parachains_runtime_api_impl, scheduler as parachains_scheduler,
 	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
@@ -71,7 +73,7 @@ use sp_runtime::{
 		OpaqueKeys, SaturatedConversion, Verify,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, KeyTypeId, Perbill, Percent, Permill,
+	ApplyExtrinsicResult, FixedU128, KeyTypeId, Perbill, Percent, Permill,
 };
 use sp_staking::SessionIndex;
 #[cfg(any(feature = "std", test))]
@@ -88,7 +90,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 pub use sp_runtime::BuildStorage;
 
 /// Constant values used within the runtime.
-use kusama_runtime_constants::{currency::*, fee::*, time::*};
+use kusama_runtime_constants::{currency::*, fast_unstake, fee::*, time::*};
 
 // Weights used in the runtime.
 mod weights;
@@ -99,6 +101,13 @@ mod bag_thresholds;
 // XCM configurations.
 pub mod xcm_config;
 
+// Governance configurations.
+pub mod governance;
+use governance::{
+	pallet_custom_origins, AuctionAdmin, FellowshipAdmin, GeneralAdmin, LeaseAdmin, StakingAdmin,
+	Treasurer, TreasurySpender,
+};
+
 #[cfg(test)]
 mod tests;
 
@@ -317,7 +326,7 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
+	pub const TransactionByteFee: Balance = TRANSACTION_BYTE_FEE;
 	/// This value increases the priority of `Operational` transactions by adding
 	/// a "virtual tip" that's equal to the `OperationalFeeMultiplier * final_fee`.
 	pub const OperationalFeeMultiplier: u8 = 5;
@@ -328,7 +337,7 @@ impl pallet_transaction_payment::Config for Runtime {
 	type TransactionByteFee = TransactionByteFee;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = WeightToFee;
-	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self, MinimumMultiplier>;
 }
 
 parameter_types! {
@@ -458,7 +467,10 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type BenchmarkingConfig = runtime_common::elections::BenchmarkConfig;
 	type ForceOrigin = EnsureOneOf<
 		EnsureRoot<AccountId>,
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+		EnsureOneOf<
+			pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+			StakingAdmin,
+		>,
 	>;
 	type WeightInfo = weights::pallet_election_provider_multi_phase::WeightInfo<Self>;
 	type MaxElectingVoters = MaxElectingVoters;
@@ -477,6 +489,16 @@ impl pallet_bags_list::Config for Runtime {
 	type Score = sp_npos_elections::VoteWeight;
 }
 
+impl pallet_fast_unstake::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BatchSize = frame_support::traits::ConstU32<{ fast_unstake::BATCH_SIZE }>;
+	type Deposit = frame_support::traits::ConstU128<{ fast_unstake::DEPOSIT }>;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type Staking = Staking;
+	type WeightInfo = weights::pallet_fast_unstake::WeightInfo<Runtime>;
+}
+
 fn era_payout(
 	total_staked: Balance,
 	non_gilt_issuance: Balance,
@@ -554,7 +576,10 @@ parameter_types! {
 
 type SlashCancelOrigin = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+		StakingAdmin,
+	>,
 >;
 
 impl pallet_staking::Config for Runtime {
@@ -571,7 +596,7 @@ impl pallet_staking::Config for Runtime {
 	type SessionsPerEra = SessionsPerEra;
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
-	// A majority of the council or root can cancel the slash.
+	// A majority of the council, the staking admin track or root can cancel the slash.
 	type SlashCancelOrigin = SlashCancelOrigin;
 	type SessionInterface = Self;
 	type EraPayout = EraPayout;
@@ -721,11 +746,11 @@ impl pallet_collective::Config<TechnicalCollective> for Runtime {
 
 impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type Event = Event;
-	type AddOrigin = MoreThanHalfCouncil;
-	type RemoveOrigin = MoreThanHalfCouncil;
-	type SwapOrigin = MoreThanHalfCouncil;
-	type ResetOrigin = MoreThanHalfCouncil;
-	type PrimeOrigin = MoreThanHalfCouncil;
+	type AddOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type RemoveOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type SwapOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type ResetOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type PrimeOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
 	type MembershipInitialized = TechnicalCommittee;
 	type MembershipChanged = TechnicalCommittee;
 	type MaxMembers = TechnicalMaxMembers;
@@ -753,14 +778,17 @@ parameter_types! {
 
 type ApproveOrigin = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+		Treasurer,
+	>,
 >;
 
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
 	type ApproveOrigin = ApproveOrigin;
-	type RejectOrigin = MoreThanHalfCouncil;
+	type RejectOrigin = EnsureOneOf<MoreThanHalfCouncil, Treasurer>;
 	type Event = Event;
 	type OnSlash = Treasury;
 	type ProposalBond = ProposalBond;
@@ -772,6 +800,7 @@ impl pallet_treasury::Config for Runtime {
 	type MaxApprovals = MaxApprovals;
 	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
 	type SpendFunds = Bounties;
+	type SpendOrigin = TreasurySpender;
 }
 
 parameter_types! {
@@ -970,8 +999,8 @@ impl pallet_identity::Config for Runtime {
 	type MaxAdditionalFields = MaxAdditionalFields;
 	type MaxRegistrars = MaxRegistrars;
 	type Slashed = Treasury;
-	type ForceOrigin = MoreThanHalfCouncil;
-	type RegistrarOrigin = MoreThanHalfCouncil;
+	type ForceOrigin = EnsureOneOf<MoreThanHalfCouncil, GeneralAdmin>;
+	type RegistrarOrigin = EnsureOneOf<MoreThanHalfCouncil, GeneralAdmin>;
 	type WeightInfo = weights::pallet_identity::WeightInfo<Runtime>;
 }
 
@@ -1095,6 +1124,7 @@ pub enum ProxyType {
 	CancelProxy,
 	Auction,
 	Society,
+	ParaRegistration,
 }
 
 impl Default for ProxyType {
@@ -1128,6 +1158,9 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::PhragmenElection(..) |
 				Call::TechnicalMembership(..) |
 				Call::Treasury(..) |
+				Call::ConvictionVoting(..) |
+				Call::Referenda(..) |
+				Call::Whitelist(..) |
 				Call::Bounties(..) |
 				Call::ChildBounties(..) |
 				Call::Tips(..) |
@@ -1153,10 +1186,12 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::Registrar(paras_registrar::Call::deregister {..}) |
 				// Specifically omitting Registrar `swap`
 				Call::Registrar(paras_registrar::Call::reserve {..}) |
+				Call::Registrar(paras_registrar::Call::register_parathread {..}) |
 				Call::Crowdloan(..) |
 				Call::Slots(..) |
 				Call::Auctions(..) | // Specifically omitting the entire XCM Pallet
-				Call::BagsList(..)
+				Call::BagsList(..) |
+				Call::FastUnstake(..)
 			),
 			ProxyType::Governance => matches!(
 				c,
@@ -1165,23 +1200,43 @@ impl InstanceFilter<Call> for ProxyType {
 					Call::PhragmenElection(..) |
 					Call::Treasury(..) | Call::Bounties(..) |
 					Call::Tips(..) | Call::Utility(..) |
-					Call::ChildBounties(..)
+					Call::ChildBounties(..) |
+					Call::ConvictionVoting(..) |
+					Call::Referenda(..) | Call::Whitelist(..)
 			),
 			ProxyType::Staking => {
-				matches!(c, Call::Staking(..) | Call::Session(..) | Call::Utility(..))
+				matches!(
+					c,
+					Call::Staking(..) |
+						Call::Session(..) | Call::Utility(..) |
+						Call::FastUnstake(..)
+				)
 			},
 			ProxyType::IdentityJudgement => matches!(
 				c,
 				Call::Identity(pallet_identity::Call::provide_judgement { .. }) | Call::Utility(..)
 			),
-			ProxyType::CancelProxy => {
-				matches!(c, Call::Proxy(pallet_proxy::Call::reject_announcement { .. }))
+			ProxyType::CancelProxy => match c {
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) => true,
+				// Only batches which do nothing but reject announcements.
+				Call::Utility(pallet_utility::Call::batch { calls }) |
+				Call::Utility(pallet_utility::Call::batch_all { calls }) =>
+					calls.iter().all(|call| ProxyType::CancelProxy.filter(call)),
+				_ => false,
 			},
 			ProxyType::Auction => matches!(
 				c,
 				Call::Auctions(..) | Call::Crowdloan(..) | Call::Registrar(..) | Call::Slots(..)
 			),
 			ProxyType::Society => matches!(c, Call::Society(..)),
+			ProxyType::ParaRegistration => matches!(
+				c,
+				Call::Registrar(paras_registrar::Call::reserve { .. }) |
+					Call::Registrar(paras_registrar::Call::register { .. }) |
+					Call::Registrar(paras_registrar::Call::register_parathread { .. }) |
+					Call::Registrar(paras_registrar::Call::deregister { .. }) |
+					Call::Crowdloan(..) | Call::Utility(..)
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -1250,7 +1305,11 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = weights::runtime_parachains_dmp::WeightInfo<Runtime>;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
 	type Event = Event;
@@ -1303,7 +1362,7 @@ impl slots::Config for Runtime {
 	type Registrar = Registrar;
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = ();
-	type ForceOrigin = MoreThanHalfCouncil;
+	type ForceOrigin = EnsureOneOf<MoreThanHalfCouncil, LeaseAdmin>;
 	type WeightInfo = weights::runtime_common_slots::WeightInfo<Runtime>;
 }
 
@@ -1314,6 +1373,8 @@ parameter_types! {
 	pub const RemoveKeysLimit: u32 = 1000;
 	// Allow 32 bytes for an additional memo to a crowdloan.
 	pub const MaxMemoLength: u8 = 32;
+	// The most a parachain may contribute to a crowdloan on behalf of its users.
+	pub const MaxParaContribution: Balance = 100 * GRAND;
 }
 
 impl crowdloan::Config for Runtime {
@@ -1325,6 +1386,8 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type ParaContributionOrigin = parachains_origin::EnsureParachain;
+	type MaxParaContribution = MaxParaContribution;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -1338,7 +1401,10 @@ parameter_types! {
 
 type AuctionInitiate = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+		AuctionAdmin,
+	>,
 >;
 
 impl auctions::Config for Runtime {
@@ -1418,6 +1484,9 @@ construct_runtime! {
 		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 17,
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 18,
 
+		// OpenGov stuff.
+		ConvictionVoting: pallet_conviction_voting::{Pallet, Call, Storage, Event<T>} = 20,
+		Referenda: pallet_referenda::{Pallet, Call, Storage, Event<T>} = 21,
 
 		// Claims. Usable initially.
 		Claims: claims::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 19,
@@ -1465,6 +1534,13 @@ construct_runtime! {
 		// Provides a semi-sorted list of nominators for staking.
 		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>} = 39,
 
+		// Fast unstake pallet: extension to staking.
+		FastUnstake: pallet_fast_unstake = 41,
+
+		// OpenGov stuff, continued.
+		Origins: pallet_custom_origins::{Origin} = 43,
+		Whitelist: pallet_whitelist::{Pallet, Call, Storage, Event<T>} = 44,
+
 		// Parachains pallets. Start indices at 50 to leave room.
 		ParachainsOrigin: parachains_origin::{Pallet, Origin} = 50,
 		Configuration: parachains_configuration::{Pallet, Call, Storage, Config<T>} = 51,
@@ -1479,6 +1555,7 @@ construct_runtime! {
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 60,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 61,
 		ParasDisputes: parachains_disputes::{Pallet, Call, Storage, Event<T>} = 62,
+		MessagingMetrics: parachains_messaging_metrics::{Pallet, Storage} = 63,
 
 		// Parachain Onboarding Pallets. Start indices at 70 to leave room.
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>} = 70,
@@ -1524,6 +1601,9 @@ pub type Executive = frame_executive::Executive<
 	(
 		CrowdloanIndexMigration,
 		pallet_staking::migrations::v9::InjectValidatorsIntoVoterList<Runtime>,
+		auctions::migration::MigrateToV1<Runtime>,
+		parachains_messaging_metrics::migration::SeedQueueSizes<Runtime>,
+		governance::migration::InitializeGovernance,
 	),
 >;
 /// The payload being signed in the transactions.
@@ -1562,6 +1642,7 @@ mod benches {
 		[runtime_common::slots, Slots]
 		[runtime_common::paras_registrar, Registrar]
 		[runtime_parachains::configuration, Configuration]
+		[runtime_parachains::dmp, Dmp]
 		[runtime_parachains::hrmp, Hrmp]
 		[runtime_parachains::disputes, ParasDisputes]
 		[runtime_parachains::initializer, Initializer]
@@ -1576,9 +1657,11 @@ mod benches {
 		[pallet_child_bounties, ChildBounties]
 		[pallet_collective, Council]
 		[pallet_collective, TechnicalCommittee]
+		[pallet_conviction_voting, ConvictionVoting]
 		[pallet_democracy, Democracy]
 		[pallet_elections_phragmen, PhragmenElection]
 		[pallet_election_provider_multi_phase, ElectionProviderMultiPhase]
+		[pallet_fast_unstake, FastUnstake]
 		[pallet_gilt, Gilt]
 		[pallet_identity, Identity]
 		[pallet_im_online, ImOnline]
@@ -1588,6 +1671,7 @@ mod benches {
 		[pallet_offences, OffencesBench::<Runtime>]
 		[pallet_preimage, Preimage]
 		[pallet_proxy, Proxy]
+		[pallet_referenda, Referenda]
 		[pallet_scheduler, Scheduler]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_staking, Staking]
@@ -1597,6 +1681,12 @@ mod benches {
 		[pallet_treasury, Treasury]
 		[pallet_utility, Utility]
 		[pallet_vesting, Vesting]
+		[pallet_whitelist, Whitelist]
+		// XCM
+		[pallet_xcm, XcmPallet]
+		// NOTE: Make sure you point to the individual modules below.
+		[pallet_xcm_benchmarks::fungible, XcmBalances]
+		[pallet_xcm_benchmarks::generic, XcmGeneric]
 	);
 }
 
@@ -1756,6 +1846,10 @@ sp_api::impl_runtime_apis! {
 		{
 			parachains_runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams> {
+			parachains_runtime_api_impl::session_executor_params::<Runtime>(session_index)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -1903,6 +1997,38 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_hrmp::runtime_api::HrmpApi<Block> for Runtime {
+		fn hrmp_channel_fee_factor(sender: ParaId, recipient: ParaId) -> FixedU128 {
+			Hrmp::channel_fee_factor(&HrmpChannelId { sender, recipient })
+		}
+	}
+
+	impl parachains_paras::runtime_api::ParasApi<Block> for Runtime {
+		fn upgrade_restriction_signal(para_id: ParaId) -> Option<UpgradeRestriction> {
+			Paras::upgrade_restriction_signal(para_id)
+		}
+	}
+
+	impl parachains_scheduler::runtime_api::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn upcoming_core_assignments(
+			lookahead: u32,
+		) -> Vec<(BlockNumber, parachains_scheduler::CoreAssignment)> {
+			ParaScheduler::upcoming_assignments(lookahead)
+		}
+	}
+
+	impl auctions::runtime_api::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn current_auction_status() -> Option<auctions::runtime_api::AuctionSummary<BlockNumber>> {
+			Auctions::auction_summary(System::block_number())
+		}
+
+		fn winning_range(
+			now: BlockNumber,
+		) -> Vec<(AccountId, ParaId, Balance, runtime_common::slot_range::SlotRange)> {
+			Auctions::winners_at(now)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -1915,6 +2041,66 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::runtime_api::XcmTrapApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(sp_core::H256, pallet_xcm::TrappedAssets<AccountId, Balance, BlockNumber>)> {
+			match xcm::latest::MultiLocation::try_from(origin) {
+				Ok(origin) => XcmPallet::trapped_assets(&origin),
+				Err(()) => Vec::new(),
+			}
+		}
+	}
+
+	impl pallet_xcm::runtime_api::DryRunApi<Block, Call, Event, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> pallet_xcm::runtime_api::CallDryRunEffects<Event> {
+			XcmPallet::dry_run_call::<xcm_config::XcmRouter>(origin.into(), call)
+		}
+
+		fn dry_run_xcm(
+			origin_location: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<Call>,
+		) -> Result<
+			pallet_xcm::runtime_api::XcmDryRunEffects<Event>,
+			pallet_xcm::runtime_api::DryRunError,
+		> {
+			use pallet_xcm::runtime_api::DryRunError::VersionedConversionFailed;
+			let origin_location = xcm::latest::MultiLocation::try_from(origin_location)
+				.map_err(|()| VersionedConversionFailed)?;
+			let xcm = xcm::latest::Xcm::<Call>::try_from(xcm)
+				.map_err(|()| VersionedConversionFailed)?;
+			Ok(XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin_location, xcm))
+		}
+	}
+
+	impl pallet_xcm::runtime_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets() -> Vec<xcm::VersionedMultiLocation> {
+			vec![xcm_config::KsmLocation::get().into()]
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, pallet_xcm::runtime_api::XcmPaymentApiError> {
+			use pallet_xcm::runtime_api::XcmPaymentApiError;
+			let asset = xcm::latest::MultiLocation::try_from(asset)
+				.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+			if asset != xcm_config::KsmLocation::get() {
+				return Err(XcmPaymentApiError::AssetNotFound)
+			}
+			Ok(<WeightToFee as frame_support::weights::WeightToFeePolynomial>::calc(&weight))
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, pallet_xcm::runtime_api::XcmPaymentApiError> {
+			XcmPallet::query_xcm_weight(message)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade() -> (Weight, Weight) {
@@ -1941,6 +2127,9 @@ sp_api::impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
 
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
 
@@ -1954,7 +2143,7 @@ sp_api::impl_runtime_apis! {
 			Vec<frame_benchmarking::BenchmarkBatch>,
 			sp_runtime::RuntimeString,
 		> {
-			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey};
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey, BenchmarkError};
 			// Trying to add benchmarks directly to some pallets caused cyclic dependency issues.
 			// To get around that, we separated the benchmarks into its own crate.
 			use pallet_session_benchmarking::Pallet as SessionBench;
@@ -1967,6 +2156,94 @@ sp_api::impl_runtime_apis! {
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl frame_benchmarking::baseline::Config for Runtime {}
 
+			use xcm::latest::{
+				AssetId::*, Fungibility::*, Junctions::*, MultiAsset, MultiAssets, MultiLocation,
+				Response,
+			};
+			use xcm_config::KsmLocation;
+
+			parameter_types! {
+				pub Statemine: MultiLocation = kusama_runtime_constants::system_parachain::statemine_location();
+				pub TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = Some((
+					Statemine::get(),
+					MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(KsmLocation::get()) },
+				));
+				// Kusama recognises no chains which act as reserves.
+				pub const TrustedReserve: Option<(MultiLocation, MultiAsset)> = None;
+			}
+
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(KsmLocation::get()) },
+						Statemine::get(),
+					))
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(KsmLocation::get()) },
+						Statemine::get(),
+					))
+				}
+			}
+
+			impl pallet_xcm_benchmarks::Config for Runtime {
+				type XcmConfig = xcm_config::XcmConfig;
+				type AccountIdConverter = xcm_config::SovereignAccountOf;
+				fn valid_destination() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+				fn worst_case_holding() -> MultiAssets {
+					// Kusama only knows about its native token.
+					vec![MultiAsset{
+						id: Concrete(KsmLocation::get()),
+						fun: Fungible(1_000_000 * UNITS),
+					}].into()
+				}
+			}
+
+			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
+				type TransactAsset = Balances;
+
+				type CheckedAccount = xcm_config::LocalCheckAccount;
+				type TrustedTeleporter = TrustedTeleporter;
+				type TrustedReserve = TrustedReserve;
+
+				fn get_multi_asset() -> MultiAsset {
+					MultiAsset {
+						id: Concrete(KsmLocation::get()),
+						fun: Fungible(1 * UNITS),
+					}
+				}
+			}
+
+			impl pallet_xcm_benchmarks::generic::Config for Runtime {
+				type Call = Call;
+
+				fn worst_case_response() -> (u64, Response) {
+					(0u64, Response::Version(Default::default()))
+				}
+
+				fn transact_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+
+				fn subscribe_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+
+				fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError> {
+					let origin = Statemine::get();
+					let assets: MultiAssets = (Concrete(KsmLocation::get()), 1_000 * UNITS).into();
+					let ticket = MultiLocation { parents: 0, interior: Here };
+					Ok((origin, ticket, assets))
+				}
+			}
+
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
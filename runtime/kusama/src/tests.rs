@@ -180,3 +180,62 @@ fn call_size() {
 		If the limit is too strong, maybe consider increase the limit to 300.",
 	);
 }
+
+#[test]
+fn para_registration_proxy_filters_calls() {
+	use frame_support::traits::InstanceFilter;
+
+	let para = ParaId::from(2000);
+	let who = AccountId::new([1; 32]);
+	let allowed = vec![
+		Call::Registrar(paras_registrar::Call::reserve {}),
+		Call::Registrar(paras_registrar::Call::deregister { id: para }),
+		Call::Crowdloan(crowdloan::Call::dissolve { index: para }),
+		Call::Utility(pallet_utility::Call::batch { calls: vec![] }),
+	];
+	let denied = vec![
+		Call::System(frame_system::Call::remark { remark: vec![] }),
+		Call::Balances(pallet_balances::Call::transfer { dest: who.into(), value: 1 }),
+		// Swapping a para away is left to the `Any` proxy.
+		Call::Registrar(paras_registrar::Call::swap { id: para, other: para }),
+		Call::Auctions(auctions::Call::cancel_auction {}),
+		Call::Slots(slots::Call::trigger_onboard { para }),
+		Call::Society(pallet_society::Call::unfound {}),
+		Call::Proxy(pallet_proxy::Call::remove_proxies {}),
+	];
+
+	for call in allowed {
+		assert!(ProxyType::ParaRegistration.filter(&call), "{:?} is denied", call);
+		assert!(ProxyType::NonTransfer.filter(&call), "{:?} is denied", call);
+	}
+	for call in denied {
+		assert!(!ProxyType::ParaRegistration.filter(&call), "{:?} is allowed", call);
+	}
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::ParaRegistration));
+}
+
+#[test]
+fn cancel_proxy_only_rejects_announcements() {
+	let who = AccountId::new([1; 32]);
+	let reject = Call::Proxy(pallet_proxy::Call::reject_announcement {
+		delegate: who.clone().into(),
+		call_hash: Default::default(),
+	});
+	let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+	let batch = |calls| Call::Utility(pallet_utility::Call::batch { calls });
+	let batch_all = |calls| Call::Utility(pallet_utility::Call::batch_all { calls });
+
+	for call in [reject.clone(), batch(vec![reject.clone()]), batch_all(vec![reject.clone()])] {
+		assert!(ProxyType::CancelProxy.filter(&call), "{:?} is denied", call);
+	}
+	for call in [
+		remark.clone(),
+		batch(vec![reject.clone(), remark]),
+		Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+			other_signatories: vec![who],
+			call: Box::new(reject),
+		}),
+	] {
+		assert!(!ProxyType::CancelProxy.filter(&call), "{:?} is allowed", call);
+	}
+}
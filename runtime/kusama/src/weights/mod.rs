@@ -22,9 +22,11 @@ pub mod pallet_bounties;
 pub mod pallet_child_bounties;
 pub mod pallet_collective_council;
 pub mod pallet_collective_technical_committee;
+pub mod pallet_conviction_voting;
 pub mod pallet_democracy;
 pub mod pallet_election_provider_multi_phase;
 pub mod pallet_elections_phragmen;
+pub mod pallet_fast_unstake;
 pub mod pallet_gilt;
 pub mod pallet_identity;
 pub mod pallet_im_online;
@@ -33,6 +35,7 @@ pub mod pallet_membership;
 pub mod pallet_multisig;
 pub mod pallet_preimage;
 pub mod pallet_proxy;
+pub mod pallet_referenda;
 pub mod pallet_scheduler;
 pub mod pallet_session;
 pub mod pallet_staking;
@@ -41,6 +44,8 @@ pub mod pallet_tips;
 pub mod pallet_treasury;
 pub mod pallet_utility;
 pub mod pallet_vesting;
+pub mod pallet_whitelist;
+pub mod pallet_xcm;
 pub mod runtime_common_auctions;
 pub mod runtime_common_claims;
 pub mod runtime_common_crowdloan;
@@ -48,6 +53,7 @@ pub mod runtime_common_paras_registrar;
 pub mod runtime_common_slots;
 pub mod runtime_parachains_configuration;
 pub mod runtime_parachains_disputes;
+pub mod runtime_parachains_dmp;
 pub mod runtime_parachains_hrmp;
 pub mod runtime_parachains_initializer;
 pub mod runtime_parachains_paras;
@@ -0,0 +1,124 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights for `pallet_fast_unstake`
+//!
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
+
+// Command to generate the weights:
+// ./target/production/polkadot
+// benchmark
+// --chain=kusama-dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_fast_unstake
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./file_header.txt
+// --output=./runtime/kusama/src/weights/pallet_fast_unstake.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_fast_unstake`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_fast_unstake::WeightInfo for WeightInfo<T> {
+	// Storage: FastUnstake ErasToCheckPerBlock (r:1 w:0)
+	// Storage: Staking ValidatorCount (r:1 w:0)
+	// Storage: ElectionProviderMultiPhase CurrentPhase (r:1 w:0)
+	// Storage: FastUnstake Head (r:1 w:1)
+	// Storage: FastUnstake CounterForQueue (r:1 w:0)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Staking SlashingSpans (r:1 w:0)
+	// Storage: Staking Bonded (r:1 w:1)
+	// Storage: Staking Validators (r:1 w:0)
+	// Storage: Staking Nominators (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Staking Ledger (r:0 w:1)
+	// Storage: Staking Payee (r:0 w:1)
+	/// The range of component `b` is `[1, 64]`.
+	fn on_idle_unstake(b: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((54_212_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(b as Weight)))
+	}
+	// Storage: FastUnstake ErasToCheckPerBlock (r:1 w:0)
+	// Storage: Staking ValidatorCount (r:1 w:0)
+	// Storage: ElectionProviderMultiPhase CurrentPhase (r:1 w:0)
+	// Storage: FastUnstake Head (r:1 w:1)
+	// Storage: FastUnstake Queue (r:65 w:64)
+	// Storage: FastUnstake CounterForQueue (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Staking ErasStakers (r:56 w:0)
+	/// The range of component `v` is `[1, 256]`.
+	/// The range of component `b` is `[1, 64]`.
+	fn on_idle_check(v: u32, b: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((12_385_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((44_021_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+	}
+	// Storage: FastUnstake ErasToCheckPerBlock (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: FastUnstake Queue (r:1 w:1)
+	// Storage: FastUnstake Head (r:1 w:0)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Staking Validators (r:1 w:0)
+	// Storage: Staking Nominators (r:1 w:1)
+	// Storage: Staking CounterForNominators (r:1 w:1)
+	// Storage: BagsList ListNodes (r:2 w:2)
+	// Storage: BagsList ListBags (r:1 w:1)
+	// Storage: BagsList CounterForListNodes (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: FastUnstake CounterForQueue (r:1 w:1)
+	fn register_fast_unstake() -> Weight {
+		(86_143_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: FastUnstake ErasToCheckPerBlock (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: FastUnstake Queue (r:1 w:1)
+	// Storage: FastUnstake Head (r:1 w:0)
+	// Storage: FastUnstake CounterForQueue (r:1 w:1)
+	fn deregister() -> Weight {
+		(26_702_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FastUnstake ErasToCheckPerBlock (r:0 w:1)
+	fn control() -> Weight {
+		(4_183_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
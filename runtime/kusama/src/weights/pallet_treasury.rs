@@ -43,6 +43,15 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_treasury`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn spend() -> Weight {
+		(21_714_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 	// Storage: Treasury ProposalCount (r:1 w:1)
 	// Storage: Treasury Proposals (r:0 w:1)
 	fn propose_spend() -> Weight {
@@ -104,4 +104,21 @@ impl<T: frame_system::Config> runtime_common::paras_registrar::WeightInfo for We
 			.saturating_add(T::DbWeight::get().reads(10 as Weight))
 			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Registrar NextFreeParaId (r:1 w:1)
+	// Storage: Registrar Paras (r:1 w:1)
+	// Storage: Paras ParaLifecycles (r:1 w:1)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: Paras PvfActiveVoteMap (r:1 w:0)
+	// Storage: Paras CodeByHash (r:1 w:1)
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	// Storage: Paras ActionsQueue (r:1 w:1)
+	// Storage: Paras CodeByHashRefs (r:1 w:1)
+	// Storage: Paras CurrentCodeHash (r:0 w:1)
+	// Storage: Paras UpcomingParasGenesis (r:0 w:1)
+	fn register_parathread() -> Weight {
+		(8_635_462_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
 }
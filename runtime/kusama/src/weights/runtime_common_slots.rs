@@ -86,4 +86,15 @@ impl<T: frame_system::Config> runtime_common::slots::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Slots Leases (r:2 w:2)
+	// Storage: Paras ParaLifecycles (r:2 w:2)
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	// Storage: Paras ActionsQueue (r:1 w:1)
+	// Storage: Registrar Paras (r:1 w:1)
+	fn swap_leases() -> Weight {
+		(40_496_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
 }
@@ -17,19 +17,26 @@
 //! XCM configurations for the Kusama runtime.
 
 use super::{
-	parachains_origin, AccountId, Balances, Call, CouncilCollective, Event, Origin, ParaId,
-	Runtime, WeightToFee, XcmPallet,
+	governance::{FellowshipAdmin, GeneralAdmin, StakingAdmin, Treasurer},
+	parachains_origin, weights, AccountId, AllPalletsWithSystem, Balance, Balances, BlockNumber,
+	Call, CouncilCollective, Event, Origin, ParaId, Runtime, Treasury, WeightToFee, XcmPallet,
+	DAYS,
 };
-use frame_support::{match_types, parameter_types, traits::Everything, weights::Weight};
+use frame_support::{
+	match_types, parameter_types,
+	traits::{Everything, Nothing},
+	weights::Weight,
+};
+use kusama_runtime_constants::{currency::deposit, system_parachain};
 use runtime_common::{xcm_sender, ToAuthor};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
-	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, BackingToPlurality,
+	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
+	AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom, BackingToPlurality,
 	ChildParachainAsNative, ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
 	CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds, IsChildSystemParachain, IsConcrete,
-	LocationInverter, SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
-	TakeWeightCredit, UsingComponents,
+	MintLocation, OriginToPluralityVoice, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
 };
 
 parameter_types! {
@@ -39,11 +46,14 @@ parameter_types! {
 	pub const KsmLocation: MultiLocation = Here.into();
 	/// The Kusama network ID. This is named.
 	pub const KusamaNetwork: NetworkId = NetworkId::Kusama;
-	/// Our XCM location ancestry - i.e. what, if anything, `Parent` means evaluated in our context. Since
-	/// Kusama is a top-level relay-chain, there is no ancestry.
-	pub const Ancestry: MultiLocation = Here.into();
+	/// Our location in the universe of consensus systems. Since Kusama is a top-level relay-chain
+	/// with its own consensus, it's just our network ID.
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(KusamaNetwork::get()));
 	/// The check account, which holds any native assets that have been teleported out and not back in (yet).
 	pub CheckAccount: AccountId = XcmPallet::check_account();
+	/// Our native token is minted here, so the check account tracks the amount of it which lives on
+	/// other chains.
+	pub LocalCheckAccount: (AccountId, MintLocation) = (CheckAccount::get(), MintLocation::Local);
 }
 
 /// The canonical means of converting a `MultiLocation` into an `AccountId`, used when we want to determine
@@ -69,7 +79,7 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
 	AccountId,
 	// We track our teleports in/out to keep total issuance correct.
-	CheckAccount,
+	LocalCheckAccount,
 >;
 
 /// The means that we convert an the XCM message origin location into a local dispatch origin.
@@ -101,8 +111,8 @@ pub type XcmRouter = (
 
 parameter_types! {
 	pub const Kusama: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(KsmLocation::get()) });
-	pub const KusamaForStatemine: (MultiAssetFilter, MultiLocation) = (Kusama::get(), Parachain(1000).into());
-	pub const KusamaForEncointer: (MultiAssetFilter, MultiLocation) = (Kusama::get(), Parachain(1001).into());
+	pub const KusamaForStatemine: (MultiAssetFilter, MultiLocation) = (Kusama::get(), system_parachain::statemine_location());
+	pub const KusamaForEncointer: (MultiAssetFilter, MultiLocation) = (Kusama::get(), system_parachain::encointer_location());
 }
 pub type TrustedTeleporters =
 	(xcm_builder::Case<KusamaForStatemine>, xcm_builder::Case<KusamaForEncointer>);
@@ -119,8 +129,8 @@ pub type Barrier = (
 	TakeWeightCredit,
 	// If the message is one that immediately attemps to pay for execution, then allow it.
 	AllowTopLevelPaidExecutionFrom<Everything>,
-	// Messages coming from system parachains need not pay for execution.
-	AllowUnpaidExecutionFrom<IsChildSystemParachain<ParaId>>,
+	// Messages coming from system parachains need not pay for execution, but must ask for it.
+	AllowExplicitUnpaidExecutionFrom<IsChildSystemParachain<ParaId>>,
 	// Expected responses are OK.
 	AllowKnownQueryResponses<XcmPallet>,
 	// Subscriptions for version tracking are OK.
@@ -135,7 +145,9 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = XcmPallet;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	// The weight trader piggybacks on the existing transaction-fee conversion logic.
@@ -144,10 +156,27 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 parameter_types! {
 	pub const CouncilBodyId: BodyId = BodyId::Executive;
+	// The bodies of the governance track origins are identified by the index of their track.
+	pub const StakingAdminBodyId: BodyId = BodyId::Index(10);
+	pub const TreasurerBodyId: BodyId = BodyId::Index(11);
+	pub const FellowshipAdminBodyId: BodyId = BodyId::Index(13);
+	pub const GeneralAdminBodyId: BodyId = BodyId::Index(14);
+	/// The period during which trapped assets may only be claimed by the location which trapped them.
+	pub const TrappedAssetsExpiry: BlockNumber = 28 * DAYS;
+	/// The deposit for each trap which may be swept to the treasury, covering its entry and its
+	/// share of the trapped assets' details.
+	pub const TrapDeposit: Balance = deposit(1, 128);
+	/// The treasury's location, to which expired trapped assets are swept.
+	pub TreasuryLocation: Option<MultiLocation> = Some(
+		AccountId32 { network: KusamaNetwork::get(), id: Treasury::account_id().into() }.into()
+	);
 }
 
 /// Type to convert an `Origin` type value into a `MultiLocation` value which represents an interior location
@@ -163,13 +192,24 @@ pub type LocalOriginToLocation = (
 	// And a usual Signed origin to be used in XCM as a corresponding AccountId32
 	SignedToAccountId32<Origin, AccountId, KusamaNetwork>,
 );
+
+/// Type to convert the governance track origins into a `MultiLocation` value which represents the
+/// voice of the corresponding body of this chain.
+pub type LocalPalletOriginToLocation = (
+	// StakingAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, StakingAdmin, StakingAdminBodyId>,
+	// Treasurer origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, Treasurer, TreasurerBodyId>,
+	// FellowshipAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, FellowshipAdmin, FellowshipAdminBodyId>,
+	// GeneralAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, GeneralAdmin, GeneralAdminBodyId>,
+);
+
 impl pallet_xcm::Config for Runtime {
 	type Event = Event;
-	// We don't allow any messages to be sent via the transaction yet. This is basically safe to
-	// enable, (safe the possibility of someone spamming the parachain if they're willing to pay
-	// the DOT to send from the Relay-chain). But it's useless until we bring in XCM v3 which will
-	// make `DescendOrigin` a bit more useful.
-	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, ()>;
+	// Only the governance track origins may send XCM at this point.
+	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalPalletOriginToLocation>;
 	type XcmRouter = XcmRouter;
 	// Anyone can execute XCM messages locally.
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
@@ -181,9 +221,17 @@ impl pallet_xcm::Config for Runtime {
 	// transfer.
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Origin = Origin;
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	type TreasuryLocation = TreasuryLocation;
+	type Currency = Balances;
+	type TrapDeposit = TrapDeposit;
+	type SovereignAccountOf = SovereignAccountOf;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = ();
+	type WeightInfo = weights::pallet_xcm::WeightInfo<Runtime>;
 }
@@ -22,7 +22,10 @@ use crate::shared;
 use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_PER_MILLIS};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
-use primitives::v2::{Balance, SessionIndex, MAX_CODE_SIZE, MAX_HEAD_DATA_SIZE, MAX_POV_SIZE};
+use primitives::{
+	v2::{Balance, SessionIndex, MAX_CODE_SIZE, MAX_HEAD_DATA_SIZE, MAX_POV_SIZE},
+	vstaging::AsyncBackingParams,
+};
 use sp_runtime::traits::Zero;
 use sp_std::prelude::*;
 
@@ -241,6 +244,12 @@ pub struct HostConfiguration<BlockNumber> {
 	/// This value should be greater than [`chain_availability_period`] and
 	/// [`thread_availability_period`].
 	pub minimum_validation_upgrade_delay: BlockNumber,
+	/// The parameters of asynchronous backing.
+	///
+	/// `allowed_ancestry_len` bounds how many relay-chain blocks older than the current relay
+	/// parent are accepted as relay parents of backed candidates. With the default of 0 only the
+	/// parent block is accepted.
+	pub async_backing_params: AsyncBackingParams,
 }
 
 impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber> {
@@ -289,6 +298,10 @@ impl<BlockNumber: Default + From<u32>> Default for HostConfiguration<BlockNumber
 			pvf_checking_enabled: false,
 			pvf_voting_ttl: 2u32.into(),
 			minimum_validation_upgrade_delay: 2.into(),
+			async_backing_params: AsyncBackingParams {
+				max_candidate_depth: 0,
+				allowed_ancestry_len: 0,
+			},
 		}
 	}
 }
@@ -324,6 +337,9 @@ pub enum InconsistentError<BlockNumber> {
 	ValidationUpgradeDelayIsTooLow { validation_upgrade_delay: BlockNumber },
 	/// Maximum UMP message size (`MAX_UPWARD_MESSAGE_SIZE_BOUND`) exceeded.
 	MaxUpwardMessageSizeExceeded { max_message_size: u32 },
+	/// `max_upward_message_size` exceeds `max_upward_queue_size`, so that a maximally sized
+	/// message could never be enqueued.
+	MaxUpwardMessageSizeExceedsQueueSize { max_message_size: u32, max_queue_size: u32 },
 	/// Maximum number of HRMP outbound channels exceeded.
 	MaxHrmpOutboundChannelsExceeded,
 	/// Maximum number of HRMP inbound channels exceeded.
@@ -396,6 +412,13 @@ where
 			})
 		}
 
+		if self.max_upward_message_size > self.max_upward_queue_size {
+			return Err(MaxUpwardMessageSizeExceedsQueueSize {
+				max_message_size: self.max_upward_message_size,
+				max_queue_size: self.max_upward_queue_size,
+			})
+		}
+
 		if self.hrmp_max_parachain_outbound_channels > crate::hrmp::HRMP_MAX_OUTBOUND_CHANNELS_BOUND
 		{
 			return Err(MaxHrmpOutboundChannelsExceeded)
@@ -420,6 +443,166 @@ where
 	}
 }
 
+/// The maximum number of updates which may be applied in a single call to `set_configs`.
+pub const MAX_CONFIG_UPDATES: u32 = 64;
+
+/// A change of a single member of the [`HostConfiguration`].
+///
+/// A batch of these is applied at once by `set_configs`, so that members which are constrained
+/// by each other can be changed together.
+#[derive(Clone, Encode, Decode, PartialEq, sp_core::RuntimeDebug, scale_info::TypeInfo)]
+pub enum ConfigUpdate<BlockNumber> {
+	/// Set `validation_upgrade_cooldown`.
+	ValidationUpgradeCooldown(BlockNumber),
+	/// Set `validation_upgrade_delay`.
+	ValidationUpgradeDelay(BlockNumber),
+	/// Set `code_retention_period`.
+	CodeRetentionPeriod(BlockNumber),
+	/// Set `max_code_size`.
+	MaxCodeSize(u32),
+	/// Set `max_pov_size`.
+	MaxPovSize(u32),
+	/// Set `max_head_data_size`.
+	MaxHeadDataSize(u32),
+	/// Set `parathread_cores`.
+	ParathreadCores(u32),
+	/// Set `parathread_retries`.
+	ParathreadRetries(u32),
+	/// Set `group_rotation_frequency`.
+	GroupRotationFrequency(BlockNumber),
+	/// Set `chain_availability_period`.
+	ChainAvailabilityPeriod(BlockNumber),
+	/// Set `thread_availability_period`.
+	ThreadAvailabilityPeriod(BlockNumber),
+	/// Set `scheduling_lookahead`.
+	SchedulingLookahead(u32),
+	/// Set `max_validators_per_core`.
+	MaxValidatorsPerCore(Option<u32>),
+	/// Set `max_validators`.
+	MaxValidators(Option<u32>),
+	/// Set `dispute_period`.
+	DisputePeriod(SessionIndex),
+	/// Set `dispute_post_conclusion_acceptance_period`.
+	DisputePostConclusionAcceptancePeriod(BlockNumber),
+	/// Set `dispute_max_spam_slots`.
+	DisputeMaxSpamSlots(u32),
+	/// Set `dispute_conclusion_by_time_out_period`.
+	DisputeConclusionByTimeOutPeriod(BlockNumber),
+	/// Set `no_show_slots`.
+	NoShowSlots(u32),
+	/// Set `n_delay_tranches`.
+	NDelayTranches(u32),
+	/// Set `zeroth_delay_tranche_width`.
+	ZerothDelayTrancheWidth(u32),
+	/// Set `needed_approvals`.
+	NeededApprovals(u32),
+	/// Set `relay_vrf_modulo_samples`.
+	RelayVrfModuloSamples(u32),
+	/// Set `max_upward_queue_count`.
+	MaxUpwardQueueCount(u32),
+	/// Set `max_upward_queue_size`.
+	MaxUpwardQueueSize(u32),
+	/// Set `max_downward_message_size`.
+	MaxDownwardMessageSize(u32),
+	/// Set `ump_service_total_weight`.
+	UmpServiceTotalWeight(Weight),
+	/// Set `max_upward_message_size`.
+	MaxUpwardMessageSize(u32),
+	/// Set `max_upward_message_num_per_candidate`.
+	MaxUpwardMessageNumPerCandidate(u32),
+	/// Set `hrmp_sender_deposit`.
+	HrmpSenderDeposit(Balance),
+	/// Set `hrmp_recipient_deposit`.
+	HrmpRecipientDeposit(Balance),
+	/// Set `hrmp_channel_max_capacity`.
+	HrmpChannelMaxCapacity(u32),
+	/// Set `hrmp_channel_max_total_size`.
+	HrmpChannelMaxTotalSize(u32),
+	/// Set `hrmp_max_parachain_inbound_channels`.
+	HrmpMaxParachainInboundChannels(u32),
+	/// Set `hrmp_max_parathread_inbound_channels`.
+	HrmpMaxParathreadInboundChannels(u32),
+	/// Set `hrmp_channel_max_message_size`.
+	HrmpChannelMaxMessageSize(u32),
+	/// Set `hrmp_max_parachain_outbound_channels`.
+	HrmpMaxParachainOutboundChannels(u32),
+	/// Set `hrmp_max_parathread_outbound_channels`.
+	HrmpMaxParathreadOutboundChannels(u32),
+	/// Set `hrmp_max_message_num_per_candidate`.
+	HrmpMaxMessageNumPerCandidate(u32),
+	/// Set `ump_max_individual_weight`.
+	UmpMaxIndividualWeight(Weight),
+	/// Set `pvf_checking_enabled`.
+	PvfCheckingEnabled(bool),
+	/// Set `pvf_voting_ttl`.
+	PvfVotingTtl(SessionIndex),
+	/// Set `minimum_validation_upgrade_delay`.
+	MinimumValidationUpgradeDelay(BlockNumber),
+	/// Set `async_backing_params`.
+	AsyncBackingParams(AsyncBackingParams),
+}
+
+impl<BlockNumber> ConfigUpdate<BlockNumber> {
+	/// Set the member of `config` which is targeted by this update.
+	pub fn apply(self, config: &mut HostConfiguration<BlockNumber>) {
+		use ConfigUpdate::*;
+
+		match self {
+			ValidationUpgradeCooldown(new) => config.validation_upgrade_cooldown = new,
+			ValidationUpgradeDelay(new) => config.validation_upgrade_delay = new,
+			CodeRetentionPeriod(new) => config.code_retention_period = new,
+			MaxCodeSize(new) => config.max_code_size = new,
+			MaxPovSize(new) => config.max_pov_size = new,
+			MaxHeadDataSize(new) => config.max_head_data_size = new,
+			ParathreadCores(new) => config.parathread_cores = new,
+			ParathreadRetries(new) => config.parathread_retries = new,
+			GroupRotationFrequency(new) => config.group_rotation_frequency = new,
+			ChainAvailabilityPeriod(new) => config.chain_availability_period = new,
+			ThreadAvailabilityPeriod(new) => config.thread_availability_period = new,
+			SchedulingLookahead(new) => config.scheduling_lookahead = new,
+			MaxValidatorsPerCore(new) => config.max_validators_per_core = new,
+			MaxValidators(new) => config.max_validators = new,
+			DisputePeriod(new) => config.dispute_period = new,
+			DisputePostConclusionAcceptancePeriod(new) =>
+				config.dispute_post_conclusion_acceptance_period = new,
+			DisputeMaxSpamSlots(new) => config.dispute_max_spam_slots = new,
+			DisputeConclusionByTimeOutPeriod(new) =>
+				config.dispute_conclusion_by_time_out_period = new,
+			NoShowSlots(new) => config.no_show_slots = new,
+			NDelayTranches(new) => config.n_delay_tranches = new,
+			ZerothDelayTrancheWidth(new) => config.zeroth_delay_tranche_width = new,
+			NeededApprovals(new) => config.needed_approvals = new,
+			RelayVrfModuloSamples(new) => config.relay_vrf_modulo_samples = new,
+			MaxUpwardQueueCount(new) => config.max_upward_queue_count = new,
+			MaxUpwardQueueSize(new) => config.max_upward_queue_size = new,
+			MaxDownwardMessageSize(new) => config.max_downward_message_size = new,
+			UmpServiceTotalWeight(new) => config.ump_service_total_weight = new,
+			MaxUpwardMessageSize(new) => config.max_upward_message_size = new,
+			MaxUpwardMessageNumPerCandidate(new) =>
+				config.max_upward_message_num_per_candidate = new,
+			HrmpSenderDeposit(new) => config.hrmp_sender_deposit = new,
+			HrmpRecipientDeposit(new) => config.hrmp_recipient_deposit = new,
+			HrmpChannelMaxCapacity(new) => config.hrmp_channel_max_capacity = new,
+			HrmpChannelMaxTotalSize(new) => config.hrmp_channel_max_total_size = new,
+			HrmpMaxParachainInboundChannels(new) =>
+				config.hrmp_max_parachain_inbound_channels = new,
+			HrmpMaxParathreadInboundChannels(new) =>
+				config.hrmp_max_parathread_inbound_channels = new,
+			HrmpChannelMaxMessageSize(new) => config.hrmp_channel_max_message_size = new,
+			HrmpMaxParachainOutboundChannels(new) =>
+				config.hrmp_max_parachain_outbound_channels = new,
+			HrmpMaxParathreadOutboundChannels(new) =>
+				config.hrmp_max_parathread_outbound_channels = new,
+			HrmpMaxMessageNumPerCandidate(new) => config.hrmp_max_message_num_per_candidate = new,
+			UmpMaxIndividualWeight(new) => config.ump_max_individual_weight = new,
+			PvfCheckingEnabled(new) => config.pvf_checking_enabled = new,
+			PvfVotingTtl(new) => config.pvf_voting_ttl = new,
+			MinimumValidationUpgradeDelay(new) => config.minimum_validation_upgrade_delay = new,
+			AsyncBackingParams(new) => config.async_backing_params = new,
+		}
+	}
+}
+
 pub trait WeightInfo {
 	fn set_config_with_block_number() -> Weight;
 	fn set_config_with_u32() -> Weight;
@@ -427,6 +610,7 @@ pub trait WeightInfo {
 	fn set_config_with_weight() -> Weight;
 	fn set_config_with_balance() -> Weight;
 	fn set_hrmp_open_request_ttl() -> Weight;
+	fn set_configs(n: u32) -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -449,6 +633,9 @@ impl WeightInfo for TestWeightInfo {
 	fn set_hrmp_open_request_ttl() -> Weight {
 		Weight::MAX
 	}
+	fn set_configs(_n: u32) -> Weight {
+		Weight::MAX
+	}
 }
 
 #[frame_support::pallet]
@@ -471,6 +658,8 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// The new value for a configuration parameter is invalid.
 		InvalidNewValue,
+		/// More than `MAX_CONFIG_UPDATES` updates were given at once.
+		TooManyUpdates,
 	}
 
 	/// The active configuration for the current session.
@@ -1116,6 +1305,44 @@ pub mod pallet {
 			<Self as Store>::BypassConsistencyCheck::put(new);
 			Ok(())
 		}
+
+		/// Apply a batch of configuration updates at once.
+		///
+		/// The updates are applied in order and only the resulting configuration is checked for
+		/// consistency, so that members constrained by each other can be changed together. Like
+		/// the other setters, the change takes effect at the scheduled session.
+		#[pallet::weight((
+			T::WeightInfo::set_configs(updates.len() as u32),
+			DispatchClass::Operational,
+		))]
+		pub fn set_configs(
+			origin: OriginFor<T>,
+			updates: Vec<ConfigUpdate<T::BlockNumber>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(updates.len() as u32 <= MAX_CONFIG_UPDATES, Error::<T>::TooManyUpdates);
+			Self::schedule_config_update(|config| {
+				for update in updates {
+					update.apply(config);
+				}
+			})
+		}
+
+		/// Set the asynchronous backing parameters.
+		#[pallet::weight((
+			// Using u32 here is a little bit of cheating, but that should be fine.
+			T::WeightInfo::set_config_with_option_u32(),
+			DispatchClass::Operational,
+		))]
+		pub fn set_async_backing_params(
+			origin: OriginFor<T>,
+			new: AsyncBackingParams,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::schedule_config_update(|config| {
+				config.async_backing_params = new;
+			})
+		}
 	}
 
 	#[pallet::hooks]
@@ -1142,6 +1369,8 @@ pub struct SessionChangeOutcome<BlockNumber> {
 	pub prev_config: HostConfiguration<BlockNumber>,
 	/// If new configuration was applied during the session change, this is the new configuration.
 	pub new_config: Option<HostConfiguration<BlockNumber>>,
+	/// The weight consumed by handling the session change.
+	pub weight: Weight,
 }
 
 impl<T: Config> Pallet<T> {
@@ -1157,7 +1386,7 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Returns the configuration that was actual before the session change and the configuration
 	/// that became active after the session change. If there were no scheduled changes, both will
-	/// be the same.
+	/// be the same. The outcome also carries the weight consumed.
 	pub(crate) fn initializer_on_new_session(
 		session_index: &SessionIndex,
 	) -> SessionChangeOutcome<T::BlockNumber> {
@@ -1166,7 +1395,8 @@ impl<T: Config> Pallet<T> {
 
 		// No pending configuration changes, so we're done.
 		if pending_configs.is_empty() {
-			return SessionChangeOutcome { prev_config, new_config: None }
+			let weight = T::DbWeight::get().reads(2);
+			return SessionChangeOutcome { prev_config, new_config: None, weight }
 		}
 
 		let (mut past_and_present, future) = pending_configs
@@ -1183,14 +1413,16 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let new_config = past_and_present.pop().map(|(_, config)| config);
+		let mut weight = T::DbWeight::get().reads_writes(2, 1);
 		if let Some(ref new_config) = new_config {
 			// Apply the new configuration.
 			<Self as Store>::ActiveConfig::put(new_config);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
 		}
 
 		<PendingConfigs<T>>::put(future);
 
-		SessionChangeOutcome { prev_config, new_config }
+		SessionChangeOutcome { prev_config, new_config, weight }
 	}
 
 	/// Return the session index that should be used for any future scheduled changes.
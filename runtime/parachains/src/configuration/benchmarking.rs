@@ -14,10 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::configuration::*;
+use crate::{configuration::*, shared};
 use frame_benchmarking::{benchmarks, BenchmarkError, BenchmarkResult};
 use frame_system::RawOrigin;
 use sp_runtime::traits::One;
+use sp_std::prelude::*;
 
 benchmarks! {
 	set_config_with_block_number {}: set_code_retention_period(RawOrigin::Root, One::one())
@@ -36,6 +37,35 @@ benchmarks! {
 
 	set_config_with_balance {}: set_hrmp_sender_deposit(RawOrigin::Root, 100_000_000_000)
 
+	// the worst case is updating the pending configuration of the scheduled session on top of
+	// another pending one, with updates of the widest members.
+	set_configs {
+		let n in 1 .. MAX_CONFIG_UPDATES;
+
+		let scheduled_session = shared::Pallet::<T>::scheduled_session();
+		let config = Pallet::<T>::config();
+		PendingConfigs::<T>::put(vec![
+			(scheduled_session - 1, config.clone()),
+			(scheduled_session, config),
+		]);
+		let updates = (0..n)
+			.map(|i| {
+				let deposit = 100_000_000_000 + i as Balance;
+				if i % 2 == 0 {
+					ConfigUpdate::HrmpSenderDeposit(deposit)
+				} else {
+					ConfigUpdate::HrmpRecipientDeposit(deposit)
+				}
+			})
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Root, updates)
+	verify {
+		let pending = PendingConfigs::<T>::get();
+		assert_eq!(pending.len(), 2);
+		let last_sender_deposit = (n - 1) / 2 * 2;
+		assert_eq!(pending[1].1.hrmp_sender_deposit, 100_000_000_000 + last_sender_deposit as Balance);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(Default::default()),
@@ -20,7 +20,7 @@ use frame_support::{assert_err, assert_ok};
 
 fn on_new_session(session_index: SessionIndex) -> (HostConfiguration<u32>, HostConfiguration<u32>) {
 	ParasShared::set_session_index(session_index);
-	let SessionChangeOutcome { prev_config, new_config } =
+	let SessionChangeOutcome { prev_config, new_config, .. } =
 		Configuration::initializer_on_new_session(&session_index);
 	let new_config = new_config.unwrap_or_else(|| prev_config.clone());
 	(prev_config, new_config)
@@ -263,6 +263,77 @@ fn invariants() {
 			Configuration::set_validation_upgrade_delay(Origin::root(), 0),
 			Error::<Test>::InvalidNewValue
 		);
+
+		assert_err!(
+			Configuration::set_max_upward_message_size(Origin::root(), 1),
+			Error::<Test>::InvalidNewValue
+		);
+	});
+}
+
+#[test]
+fn set_configs_checks_the_resulting_config() {
+	new_test_ext(Default::default()).execute_with(|| {
+		// each of these updates on its own leads to an inconsistent configuration.
+		assert_err!(
+			Configuration::set_chain_availability_period(Origin::root(), 12),
+			Error::<Test>::InvalidNewValue
+		);
+		assert_err!(
+			Configuration::set_max_upward_message_size(Origin::root(), 1024),
+			Error::<Test>::InvalidNewValue
+		);
+
+		assert_ok!(Configuration::set_configs(
+			Origin::root(),
+			vec![
+				ConfigUpdate::MaxUpwardMessageSize(1024),
+				ConfigUpdate::MaxUpwardQueueSize(4096),
+				ConfigUpdate::MinimumValidationUpgradeDelay(20),
+				ConfigUpdate::ChainAvailabilityPeriod(12),
+				// later updates of the same member take precedence.
+				ConfigUpdate::MaxUpwardQueueSize(2048),
+			],
+		));
+		let expected = HostConfiguration {
+			max_upward_message_size: 1024,
+			max_upward_queue_size: 2048,
+			minimum_validation_upgrade_delay: 20,
+			chain_availability_period: 12,
+			..Default::default()
+		};
+		assert_eq!(<Configuration as Store>::PendingConfigs::get(), vec![(2, expected.clone())]);
+
+		// a batch resulting in an inconsistent configuration is rejected as a whole.
+		assert_err!(
+			Configuration::set_configs(
+				Origin::root(),
+				vec![ConfigUpdate::MaxCodeSize(100), ConfigUpdate::MaxUpwardQueueSize(512)],
+			),
+			Error::<Test>::InvalidNewValue
+		);
+		assert_eq!(<Configuration as Store>::PendingConfigs::get(), vec![(2, expected.clone())]);
+
+		on_new_session(1);
+		on_new_session(2);
+		assert_eq!(Configuration::config(), expected);
+	});
+}
+
+#[test]
+fn set_configs_is_bounded_and_root_only() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let updates = vec![ConfigUpdate::MaxCodeSize(100); MAX_CONFIG_UPDATES as usize + 1];
+		assert_err!(
+			Configuration::set_configs(Origin::root(), updates),
+			Error::<Test>::TooManyUpdates
+		);
+		assert!(Configuration::set_configs(
+			Origin::signed(1),
+			vec![ConfigUpdate::MaxCodeSize(100)]
+		)
+		.is_err());
+		assert_eq!(<Configuration as Store>::PendingConfigs::get(), vec![]);
 	});
 }
 
@@ -317,7 +388,7 @@ fn setting_pending_config_members() {
 			needed_approvals: 242,
 			relay_vrf_modulo_samples: 243,
 			max_upward_queue_count: 1337,
-			max_upward_queue_size: 228,
+			max_upward_queue_size: 2280,
 			max_downward_message_size: 2048,
 			ump_service_total_weight: 20000,
 			max_upward_message_size: 448,
@@ -336,6 +407,10 @@ fn setting_pending_config_members() {
 			pvf_checking_enabled: true,
 			pvf_voting_ttl: 3,
 			minimum_validation_upgrade_delay: 20,
+			async_backing_params: AsyncBackingParams {
+				max_candidate_depth: 4,
+				allowed_ancestry_len: 3,
+			},
 		};
 
 		assert!(<Configuration as Store>::PendingConfig::get(shared::SESSION_DELAY).is_none());
@@ -500,6 +575,8 @@ fn setting_pending_config_members() {
 		Configuration::set_pvf_checking_enabled(Origin::root(), new_config.pvf_checking_enabled)
 			.unwrap();
 		Configuration::set_pvf_voting_ttl(Origin::root(), new_config.pvf_voting_ttl).unwrap();
+		Configuration::set_async_backing_params(Origin::root(), new_config.async_backing_params)
+			.unwrap();
 
 		assert_eq!(
 			<Configuration as Store>::PendingConfigs::get(),
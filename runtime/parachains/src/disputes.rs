@@ -272,8 +272,9 @@ pub trait DisputesHandler<BlockNumber: Ord> {
 	/// Called by the initializer to finalize the disputes pallet.
 	fn initializer_finalize();
 
-	/// Called by the initializer to note that a new session has started.
-	fn initializer_on_new_session(notification: &SessionChangeNotification<BlockNumber>);
+	/// Called by the initializer to note that a new session has started. Returns the weight
+	/// consumed.
+	fn initializer_on_new_session(notification: &SessionChangeNotification<BlockNumber>) -> Weight;
 }
 
 impl<BlockNumber: Ord> DisputesHandler<BlockNumber> for () {
@@ -327,7 +328,11 @@ impl<BlockNumber: Ord> DisputesHandler<BlockNumber> for () {
 
 	fn initializer_finalize() {}
 
-	fn initializer_on_new_session(_notification: &SessionChangeNotification<BlockNumber>) {}
+	fn initializer_on_new_session(
+		_notification: &SessionChangeNotification<BlockNumber>,
+	) -> Weight {
+		0
+	}
 }
 
 impl<T: Config> DisputesHandler<T::BlockNumber> for pallet::Pallet<T>
@@ -386,7 +391,9 @@ where
 		pallet::Pallet::<T>::initializer_finalize()
 	}
 
-	fn initializer_on_new_session(notification: &SessionChangeNotification<T::BlockNumber>) {
+	fn initializer_on_new_session(
+		notification: &SessionChangeNotification<T::BlockNumber>,
+	) -> Weight {
 		pallet::Pallet::<T>::initializer_on_new_session(notification)
 	}
 }
@@ -834,15 +841,17 @@ impl<T: Config> Pallet<T> {
 	/// Called by the initializer to note a new session in the disputes pallet.
 	pub(crate) fn initializer_on_new_session(
 		notification: &SessionChangeNotification<T::BlockNumber>,
-	) {
+	) -> Weight {
 		let config = <configuration::Pallet<T>>::config();
+		let mut weight = T::DbWeight::get().reads(1);
 
 		if notification.session_index <= config.dispute_period + 1 {
-			return
+			return weight
 		}
 
 		let pruning_target = notification.session_index - config.dispute_period - 1;
 
+		weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 		LastPrunedSession::<T>::mutate(|last_pruned| {
 			let to_prune = if let Some(last_pruned) = last_pruned {
 				*last_pruned + 1..=pruning_target
@@ -858,10 +867,13 @@ impl<T: Config> Pallet<T> {
 				// TODO: https://github.com/paritytech/polkadot/issues/3469
 				<Included<T>>::remove_prefix(to_prune, None);
 				SpamSlots::<T>::remove(to_prune);
+				weight = weight.saturating_add(T::DbWeight::get().writes(3));
 			}
 
 			*last_pruned = Some(pruning_target);
 		});
+
+		weight
 	}
 
 	/// Handle sets of dispute statements corresponding to 0 or more candidates.
@@ -888,6 +900,7 @@ impl<T: Config> Pallet<T> {
 			if Self::process_checked_dispute_data(
 				statement_set,
 				config.dispute_post_conclusion_acceptance_period,
+				config.dispute_max_spam_slots,
 			)? {
 				fresh.push(dispute_target);
 			}
@@ -998,11 +1011,12 @@ impl<T: Config> Pallet<T> {
 			return StatementSetFilter::RemoveAll
 		}
 
-		// Apply spam slot changes. Bail early if too many occupied.
+		// Apply spam slot changes. Votes of validators which occupy too many slots are removed.
 		let is_local = <Included<T>>::contains_key(&set.session, &set.candidate_hash);
 		if !is_local {
 			let mut spam_slots: Vec<u32> =
 				SpamSlots::<T>::get(&set.session).unwrap_or_else(|| vec![0; n_validators]);
+			let mut spamming = Vec::new();
 
 			for (validator_index, spam_slot_change) in summary.spam_slot_changes {
 				let spam_slot = spam_slots
@@ -1030,6 +1044,10 @@ impl<T: Config> Pallet<T> {
 						// by the duplicate checks above. It's only the first one which
 						// may not already have been filtered out.
 						filter.remove_index(first_index_in_set);
+
+						// The vote is not imported, so it must not occupy a spam slot either.
+						spamming.push(validator_index);
+						continue
 					}
 
 					// It's also worth noting that the `DisputeStateImporter`
@@ -1045,6 +1063,23 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 
+			// Spam slots are only incremented for unconfirmed disputes, so removing the votes
+			// above cannot affect whether the dispute is confirmed. It may however leave the
+			// dispute single-sided, in which case the whole set is rejected without occupying
+			// any spam slots.
+			if !spamming.is_empty() {
+				let mut validators_for = summary.state.validators_for.clone();
+				let mut validators_against = summary.state.validators_against.clone();
+				for ValidatorIndex(i) in spamming {
+					validators_for.set(i as usize, false);
+					validators_against.set(i as usize, false);
+				}
+
+				if validators_for.count_ones() == 0 || validators_against.count_ones() == 0 {
+					return StatementSetFilter::RemoveAll
+				}
+			}
+
 			// We write the spam slots here because sequential calls to
 			// `filter_dispute_data` have a dependency on each other.
 			//
@@ -1067,6 +1102,7 @@ impl<T: Config> Pallet<T> {
 	fn process_checked_dispute_data(
 		set: CheckedDisputeStatementSet,
 		dispute_post_conclusion_acceptance_period: T::BlockNumber,
+		max_spam_slots: u32,
 	) -> Result<bool, DispatchError> {
 		// Dispute statement sets on any dispute which concluded
 		// before this point are to be rejected.
@@ -1126,8 +1162,17 @@ impl<T: Config> Pallet<T> {
 
 		let DisputeStatementSet { session, candidate_hash, .. } = set.clone();
 
-		// we can omit spam slot checks, `fn filter_disputes_data` is
-		// always called before calling this `fn`.
+		// The spam slots were already updated by `fn filter_dispute_data`, which is always
+		// called before calling this `fn`. It never lets a validator occupy more than
+		// `max_spam_slots`, so a set which does was not filtered and is rejected.
+		if !<Included<T>>::contains_key(&session, &candidate_hash) {
+			let spam_slots = SpamSlots::<T>::get(&session).unwrap_or_default();
+			let exceeds_spam_slots = summary.spam_slot_changes.iter().any(|(v_i, change)| {
+				matches!(change, SpamSlotChange::Inc) &&
+					spam_slots.get(v_i.0 as usize).map_or(false, |slots| *slots > max_spam_slots)
+			});
+			ensure!(!exceeds_spam_slots, Error::<T>::PotentialSpam);
+		}
 
 		if fresh {
 			let is_local = <Included<T>>::contains_key(&session, &candidate_hash);
@@ -2451,3 +2451,104 @@ fn import_ignores_single_sided() {
 		assert!(statements.is_empty());
 	})
 }
+
+#[test]
+fn spamming_minority_is_limited_by_spam_slots() {
+	static ACCOUNTS: [AccountId; 7] = [0, 1, 2, 3, 4, 5, 6];
+	let dispute_max_spam_slots = 2;
+
+	let mock_genesis_config = MockGenesisConfig {
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration { dispute_max_spam_slots, ..Default::default() },
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(mock_genesis_config).execute_with(|| {
+		// We need 7 validators for the byzantine threshold to be 2
+		let validators = (0..7)
+			.map(|_| <ValidatorId as CryptoType>::Pair::generate().0)
+			.collect::<Vec<_>>();
+
+		run_to_block(3, |b| {
+			let keys = validators
+				.iter()
+				.enumerate()
+				.map(|(i, v)| (&ACCOUNTS[i], v.public()))
+				.collect::<Vec<_>>();
+			// a new session at each block
+			Some((true, b, keys.clone(), Some(keys)))
+		});
+
+		let vote = |candidate: u8, validator: u32, valid: bool| {
+			let candidate_hash = CandidateHash(sp_core::H256::repeat_byte(candidate));
+			let payload =
+				ExplicitDisputeStatement { valid, candidate_hash, session: 1 }.signing_payload();
+			let statement = if valid {
+				DisputeStatement::Valid(ValidDisputeStatementKind::Explicit)
+			} else {
+				DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit)
+			};
+			(statement, ValidatorIndex(validator), validators[validator as usize].sign(&payload))
+		};
+		let set = |candidate: u8, votes| DisputeStatementSet {
+			candidate_hash: CandidateHash(sp_core::H256::repeat_byte(candidate)),
+			session: 1,
+			statements: votes,
+		};
+
+		// Validator 6 disputes a different candidate in each set, each of which is backed by
+		// another validator.
+		let statements = vec![
+			set(1, vec![vote(1, 0, true), vote(1, 6, false)]),
+			set(2, vec![vote(2, 1, true), vote(2, 6, false)]),
+			set(3, vec![vote(3, 3, true), vote(3, 6, false)]),
+			set(4, vec![vote(4, 4, true), vote(4, 5, false), vote(4, 6, false)]),
+		];
+
+		let statements = update_spam_slots(statements);
+
+		// The third set is rejected as a whole, as it is single-sided without the vote of
+		// validator 6. Only the vote of validator 6 is removed from the fourth set.
+		assert_eq!(
+			statements,
+			vec![
+				CheckedDisputeStatementSet::unchecked_from_unchecked(set(
+					1,
+					vec![vote(1, 0, true), vote(1, 6, false)]
+				)),
+				CheckedDisputeStatementSet::unchecked_from_unchecked(set(
+					2,
+					vec![vote(2, 1, true), vote(2, 6, false)]
+				)),
+				CheckedDisputeStatementSet::unchecked_from_unchecked(set(
+					4,
+					vec![vote(4, 4, true), vote(4, 5, false)]
+				)),
+			],
+		);
+		// The rejected votes don't occupy any spam slots.
+		assert_eq!(SpamSlots::<Test>::get(1), Some(vec![1, 1, 0, 0, 1, 1, 2]));
+
+		assert_ok!(
+			Pallet::<Test>::process_checked_multi_dispute_data(statements),
+			vec![
+				(1, CandidateHash(sp_core::H256::repeat_byte(1))),
+				(1, CandidateHash(sp_core::H256::repeat_byte(2))),
+				(1, CandidateHash(sp_core::H256::repeat_byte(4))),
+			],
+		);
+
+		// A set which occupies more spam slots than allowed did not pass the filter.
+		SpamSlots::<Test>::insert(1, vec![1, 1, 1, 0, 1, 1, 3]);
+		let unfiltered = CheckedDisputeStatementSet::unchecked_from_unchecked(set(
+			3,
+			vec![vote(3, 3, true), vote(3, 6, false)],
+		));
+		assert_noop!(
+			Pallet::<Test>::process_checked_multi_dispute_data(vec![unfiltered]),
+			Error::<Test>::PotentialSpam,
+		);
+	})
+}
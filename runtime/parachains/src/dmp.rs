@@ -17,18 +17,99 @@
 use crate::{
 	configuration::{self, HostConfiguration},
 	initializer,
+	messaging_metrics::{self, MessageQueue},
+	FeeTracker,
 };
 use frame_support::pallet_prelude::*;
-use primitives::v2::{DownwardMessage, Hash, Id as ParaId, InboundDownwardMessage};
-use sp_runtime::traits::{BlakeTwo256, Hash as HashT, SaturatedConversion};
+use frame_system::pallet_prelude::BlockNumberFor;
+use parity_scale_codec::{Decode, Encode};
+use primitives::v2::{
+	DownwardMessage, DownwardMessageOrigin, Hash, Id as ParaId, InboundDownwardMessage,
+	VersionedInboundDownwardMessage,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{BlakeTwo256, CheckedDiv, Hash as HashT, One},
+	FixedPointNumber, FixedU128,
+};
 use sp_std::{fmt, prelude::*};
 use xcm::latest::SendError;
 
 pub use pallet::*;
 
+pub mod migration;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[cfg(test)]
 mod tests;
 
+/// The maximum number of messages which are appended to a single page of a downward message queue.
+pub const QUEUE_PAGE_CAPACITY: u32 = 32;
+
+/// The total size in bytes which the downward message queue of a para may hold before it is
+/// considered to be full.
+const MAX_DMQ_SIZE: u32 = 32 * 1024 * 1024;
+/// A queue is considered congested once it holds more than `1 / THRESHOLD_FACTOR` of the maximum
+/// number of maximally sized messages which fit into `MAX_DMQ_SIZE`.
+const THRESHOLD_FACTOR: u32 = 2;
+/// The base by which the delivery fee factor is multiplied (or divided) on each message which is
+/// enqueued to a congested queue (or on each pruning of a queue which is no longer congested).
+pub(crate) const EXPONENTIAL_FEE_BASE: FixedU128 = FixedU128::from_inner(1_050_000_000_000_000_000); // 1.05
+/// The increase of the fee factor for each kilobyte of a message enqueued to a congested queue.
+pub(crate) const MESSAGE_SIZE_FEE_BASE: FixedU128 = FixedU128::from_inner(1_000_000_000_000_000); // 0.001
+
+/// The index of a page of a downward message queue.
+pub type PageIndex = u32;
+
+/// The bookkeeping of the paged downward message queue of a para.
+///
+/// Messages are appended to the page at `last_page` until it holds [`QUEUE_PAGE_CAPACITY`]
+/// messages. Processed messages are pruned lazily: they are skipped by `first_page_offset` and
+/// only removed once their whole page was processed.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueueState {
+	/// The index of the first page which holds unprocessed messages.
+	pub first_page: PageIndex,
+	/// The number of processed messages at the front of the first page.
+	pub first_page_offset: u32,
+	/// The index of the page to which new messages are appended.
+	pub last_page: PageIndex,
+	/// The number of unprocessed messages in the queue.
+	pub message_count: u32,
+}
+
+/// A page of the downward message queue of a para.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct QueuePage<BlockNumber> {
+	/// The messages of this page in the order they were sent.
+	pub messages: Vec<VersionedInboundDownwardMessage<BlockNumber>>,
+	/// The head of the message queue chain after the last message of this page.
+	pub mqc_head: Hash,
+}
+
+pub trait WeightInfo {
+	fn queue_downward_message() -> Weight;
+	fn prune_dmq(p: u32) -> Weight;
+	fn sweep_queue(p: u32) -> Weight;
+}
+
+/// Fallback implementation, used by the unit tests which don't track the weight of message
+/// passing.
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn queue_downward_message() -> Weight {
+		0
+	}
+	fn prune_dmq(_p: u32) -> Weight {
+		0
+	}
+	fn sweep_queue(_p: u32) -> Weight {
+		0
+	}
+}
+
 /// An error sending a downward message.
 #[cfg_attr(test, derive(Debug))]
 pub enum QueueDownwardMessageError {
@@ -74,20 +155,44 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + configuration::Config {}
+	pub trait Config:
+		frame_system::Config + configuration::Config + messaging_metrics::Config
+	{
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_latest::<T>()
+		}
+	}
 
-	/// The downward messages addressed for a certain para.
+	/// The bookkeeping of the downward message queue of a certain para.
+	///
+	/// Invariant: present if and only if the queue holds unprocessed messages.
 	#[pallet::storage]
-	pub(crate) type DownwardMessageQueues<T: Config> = StorageMap<
+	pub(crate) type DownwardMessageQueueState<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, QueueState, ValueQuery>;
+
+	/// The pages of the downward messages addressed for a certain para.
+	///
+	/// Invariant: the pages of a para are present exactly for the indices from `first_page` to
+	/// `last_page` of its `DownwardMessageQueueState`.
+	#[pallet::storage]
+	pub(crate) type DownwardMessageQueuePages<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		ParaId,
-		Vec<InboundDownwardMessage<T::BlockNumber>>,
-		ValueQuery,
+		Twox64Concat,
+		PageIndex,
+		QueuePage<T::BlockNumber>,
 	>;
 
 	/// A mapping that stores the downward message queue MQC head for each para.
@@ -101,6 +206,38 @@ pub mod pallet {
 	pub(crate) type DownwardMessageQueueHeads<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Hash, ValueQuery>;
 
+	/// The downward message queues as they were stored before they were split into pages.
+	///
+	/// Drained into the pages over several blocks, see [`migration::migrate_legacy_queues`].
+	#[pallet::storage]
+	pub(crate) type DownwardMessageQueues<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ParaId,
+		Vec<InboundDownwardMessage<T::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// Whether `DownwardMessageQueues` may still hold queues which were not moved into pages.
+	///
+	/// While set, each queue is moved into pages before it is accessed.
+	#[pallet::storage]
+	pub(crate) type LegacyQueuesPending<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Initialization value for the delivery fee factor.
+	#[pallet::type_value]
+	pub fn InitialFactor() -> FixedU128 {
+		FixedU128::one()
+	}
+
+	/// The number to multiply the base delivery fee by for messages to a certain para.
+	///
+	/// This is increased while the para's downward message queue is congested and decreased again
+	/// once the queue is drained, down to a minimum of one.
+	#[pallet::storage]
+	pub(crate) type DeliveryFeeFactor<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, FixedU128, ValueQuery, InitialFactor>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
 }
@@ -109,7 +246,7 @@ pub mod pallet {
 impl<T: Config> Pallet<T> {
 	/// Block initialization logic, called by initializer.
 	pub(crate) fn initializer_initialize(_now: T::BlockNumber) -> Weight {
-		0
+		migration::migrate_legacy_queues::<T>(migration::LEGACY_QUEUES_PER_BLOCK)
 	}
 
 	/// Block finalization logic, called by initializer.
@@ -119,22 +256,57 @@ impl<T: Config> Pallet<T> {
 	pub(crate) fn initializer_on_new_session(
 		_notification: &initializer::SessionChangeNotification<T::BlockNumber>,
 		outgoing_paras: &[ParaId],
-	) {
-		Self::perform_outgoing_para_cleanup(outgoing_paras);
+	) -> Weight {
+		Self::perform_outgoing_para_cleanup(outgoing_paras)
 	}
 
 	/// Iterate over all paras that were noted for offboarding and remove all the data
 	/// associated with them.
-	fn perform_outgoing_para_cleanup(outgoing: &[ParaId]) {
+	fn perform_outgoing_para_cleanup(outgoing: &[ParaId]) -> Weight {
+		let mut weight: Weight = 0;
 		for outgoing_para in outgoing {
-			Self::clean_dmp_after_outgoing(outgoing_para);
+			weight = weight.saturating_add(Self::clean_dmp_after_outgoing(outgoing_para));
 		}
+		weight
 	}
 
 	/// Remove all relevant storage items for an outgoing parachain.
-	fn clean_dmp_after_outgoing(outgoing_para: &ParaId) {
-		<Self as Store>::DownwardMessageQueues::remove(outgoing_para);
+	fn clean_dmp_after_outgoing(outgoing_para: &ParaId) -> Weight {
+		let weight = Self::sweep_queue(outgoing_para);
 		<Self as Store>::DownwardMessageQueueHeads::remove(outgoing_para);
+		<Self as Store>::DeliveryFeeFactor::remove(outgoing_para);
+		weight.saturating_add(T::DbWeight::get().writes(2))
+	}
+
+	/// Remove all pages of the downward message queue of the given para, regardless of whether
+	/// their messages were processed.
+	pub(crate) fn sweep_queue(para: &ParaId) -> Weight {
+		let mut weight = 0;
+		if <Self as Store>::LegacyQueuesPending::get() {
+			<Self as Store>::DownwardMessageQueues::remove(para);
+			weight = T::DbWeight::get().writes(1);
+		}
+		let pages = <Self as Store>::DownwardMessageQueuePages::remove_prefix(para, None);
+		<Self as Store>::DownwardMessageQueueState::remove(para);
+		<messaging_metrics::Pallet<T>>::note_cleared(&MessageQueue::Downward(*para));
+		let removed = match pages {
+			sp_io::KillStorageResult::AllRemoved(n) |
+			sp_io::KillStorageResult::SomeRemaining(n) => n,
+		};
+		weight.saturating_add(<T as Config>::WeightInfo::sweep_queue(removed))
+	}
+
+	/// Move the legacy downward message queue of `para` into pages, unless all legacy queues were
+	/// moved already.
+	///
+	/// Returns the weight on top of reading `LegacyQueuesPending`, which is accounted for by the
+	/// benchmarks of the callers.
+	fn ensure_queue_migrated(para: ParaId) -> Weight {
+		if <Self as Store>::LegacyQueuesPending::get() {
+			migration::migrate_legacy_queue::<T>(para)
+		} else {
+			0
+		}
 	}
 
 	/// Enqueue a downward message to a specific recipient para.
@@ -149,25 +321,64 @@ impl<T: Config> Pallet<T> {
 		config: &HostConfiguration<T::BlockNumber>,
 		para: ParaId,
 		msg: DownwardMessage,
+	) -> Result<(), QueueDownwardMessageError> {
+		Self::queue_downward_message_with_origin(config, para, DownwardMessageOrigin::Runtime, msg)
+	}
+
+	/// Enqueue a downward message to a specific recipient para, noting the kind of `origin` which
+	/// sent it. See [`Self::queue_downward_message`].
+	///
+	/// The origin is stored alongside the message and is not part of the message queue chain.
+	pub fn queue_downward_message_with_origin(
+		config: &HostConfiguration<T::BlockNumber>,
+		para: ParaId,
+		origin: DownwardMessageOrigin,
+		msg: DownwardMessage,
 	) -> Result<(), QueueDownwardMessageError> {
 		let serialized_len = msg.len() as u32;
 		if serialized_len > config.max_downward_message_size {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
-		let inbound =
-			InboundDownwardMessage { msg, sent_at: <frame_system::Pallet<T>>::block_number() };
+		Self::ensure_queue_migrated(para);
+
+		let sent_at = <frame_system::Pallet<T>>::block_number();
 
 		// obtain the new link in the MQC and update the head.
-		<Self as Store>::DownwardMessageQueueHeads::mutate(para, |head| {
-			let new_head =
-				BlakeTwo256::hash_of(&(*head, inbound.sent_at, T::Hashing::hash_of(&inbound.msg)));
-			*head = new_head;
+		let new_head = <Self as Store>::DownwardMessageQueueHeads::mutate(para, |head| {
+			*head = BlakeTwo256::hash_of(&(*head, sent_at, T::Hashing::hash_of(&msg)));
+			*head
 		});
 
-		<Self as Store>::DownwardMessageQueues::mutate(para, |v| {
-			v.push(inbound);
-		});
+		let mut state = <Self as Store>::DownwardMessageQueueState::get(para);
+		let page = <Self as Store>::DownwardMessageQueuePages::get(para, state.last_page)
+			.filter(|page| (page.messages.len() as u32) < QUEUE_PAGE_CAPACITY);
+		if page.is_none() && state.message_count > 0 {
+			// the last page is full, start a new one.
+			state.last_page += 1;
+		}
+		let mut page = page.unwrap_or(QueuePage { messages: Vec::new(), mqc_head: new_head });
+		page.messages.push(InboundDownwardMessage { sent_at, msg }.with_origin(origin));
+		page.mqc_head = new_head;
+		<Self as Store>::DownwardMessageQueuePages::insert(para, state.last_page, page);
+
+		state.message_count += 1;
+		let q_len = state.message_count;
+		<Self as Store>::DownwardMessageQueueState::insert(para, state);
+
+		<messaging_metrics::Pallet<T>>::note_enqueued(
+			&MessageQueue::Downward(para),
+			1,
+			serialized_len,
+		);
+
+		if q_len > Self::dmq_congestion_threshold(config) {
+			let message_size_factor = FixedU128::saturating_from_integer(serialized_len / 1024)
+				.saturating_mul(MESSAGE_SIZE_FEE_BASE);
+			<Self as Store>::DeliveryFeeFactor::mutate(para, |f| {
+				*f = f.saturating_mul(EXPONENTIAL_FEE_BASE.saturating_add(message_size_factor));
+			});
+		}
 
 		Ok(())
 	}
@@ -193,18 +404,83 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Prunes the specified number of messages from the downward message queue of the given para.
+	///
+	/// Only pages all messages of which were processed are removed. The remaining pages are left
+	/// untouched.
 	pub(crate) fn prune_dmq(para: ParaId, processed_downward_messages: u32) -> Weight {
-		<Self as Store>::DownwardMessageQueues::mutate(para, |q| {
-			let processed_downward_messages = processed_downward_messages as usize;
-			if processed_downward_messages > q.len() {
-				// reaching this branch is unexpected due to the constraint established by
-				// `check_processed_downward_messages`. But better be safe than sorry.
-				q.clear();
-			} else {
-				*q = q.split_off(processed_downward_messages);
+		let mut weight = Self::ensure_queue_migrated(para);
+
+		let mut state = <Self as Store>::DownwardMessageQueueState::get(para);
+		// pruning more messages than queued is unexpected due to the constraint established by
+		// `check_processed_downward_messages`. But better be safe than sorry.
+		let mut to_prune = processed_downward_messages.min(state.message_count);
+		state.message_count -= to_prune;
+
+		// the number of pages which were read. Those which were fully processed are removed.
+		let mut visited_pages = 0;
+		if state.message_count == 0 {
+			if to_prune > 0 {
+				weight = weight.saturating_add(Self::sweep_queue(&para));
+			}
+		} else {
+			let (pruned_count, mut pruned_bytes) = (to_prune, 0u32);
+			while to_prune > 0 {
+				let page_messages =
+					<Self as Store>::DownwardMessageQueuePages::get(para, state.first_page)
+						.map_or_else(Vec::new, |page| page.messages);
+				let remaining =
+					(page_messages.len() as u32).saturating_sub(state.first_page_offset);
+				visited_pages += 1;
+
+				pruned_bytes = page_messages
+					.iter()
+					.skip(state.first_page_offset as usize)
+					.take(to_prune as usize)
+					.fold(pruned_bytes, |bytes, m| bytes.saturating_add(m.msg().len() as u32));
+
+				if to_prune < remaining {
+					state.first_page_offset += to_prune;
+					break
+				}
+
+				<Self as Store>::DownwardMessageQueuePages::remove(para, state.first_page);
+				to_prune -= remaining;
+				state.first_page += 1;
+				state.first_page_offset = 0;
+			}
+			<Self as Store>::DownwardMessageQueueState::insert(para, state.clone());
+			<messaging_metrics::Pallet<T>>::note_dequeued(
+				&MessageQueue::Downward(para),
+				pruned_count,
+				pruned_bytes,
+			);
+		}
+
+		let config = <configuration::Pallet<T>>::config();
+		if state.message_count <= Self::dmq_congestion_threshold(&config) {
+			Self::decrease_fee_factor(para);
+		}
+		weight.saturating_add(<T as Config>::WeightInfo::prune_dmq(visited_pages))
+	}
+
+	/// The number of messages above which the downward message queue of a para is congested.
+	fn dmq_congestion_threshold(config: &HostConfiguration<T::BlockNumber>) -> u32 {
+		MAX_DMQ_SIZE.checked_div(config.max_downward_message_size).unwrap_or(0) / THRESHOLD_FACTOR
+	}
+
+	/// Lower the delivery fee factor of `para` by `EXPONENTIAL_FEE_BASE`, down to a minimum of one.
+	fn decrease_fee_factor(para: ParaId) {
+		<Self as Store>::DeliveryFeeFactor::mutate_exists(para, |maybe_factor| {
+			if let Some(factor) = maybe_factor.take() {
+				let new_factor = factor
+					.checked_div(&EXPONENTIAL_FEE_BASE)
+					.unwrap_or_default()
+					.max(FixedU128::one());
+				if new_factor > FixedU128::one() {
+					*maybe_factor = Some(new_factor);
+				}
 			}
 		});
-		T::DbWeight::get().reads_writes(1, 1)
 	}
 
 	/// Returns the Head of Message Queue Chain for the given para or `None` if there is none
@@ -214,19 +490,74 @@ impl<T: Config> Pallet<T> {
 		<Self as Store>::DownwardMessageQueueHeads::get(&para)
 	}
 
+	/// Returns the head of the message queue chain after the last message of the given page of
+	/// the downward message queue of `para`, or `None` if there is no such page.
+	pub fn dmq_page_mqc_head(para: ParaId, page: PageIndex) -> Option<Hash> {
+		<Self as Store>::DownwardMessageQueuePages::get(para, page).map(|page| page.mqc_head)
+	}
+
 	/// Returns the number of pending downward messages addressed to the given para.
 	///
 	/// Returns 0 if the para doesn't have an associated downward message queue.
 	pub(crate) fn dmq_length(para: ParaId) -> u32 {
-		<Self as Store>::DownwardMessageQueues::decode_len(&para)
-			.unwrap_or(0)
-			.saturated_into::<u32>()
+		Self::ensure_queue_migrated(para);
+		<Self as Store>::DownwardMessageQueueState::get(para).message_count
 	}
 
 	/// Returns the downward message queue contents for the given para.
 	///
 	/// The most recent messages are the latest in the vector.
 	pub(crate) fn dmq_contents(recipient: ParaId) -> Vec<InboundDownwardMessage<T::BlockNumber>> {
-		<Self as Store>::DownwardMessageQueues::get(&recipient)
+		Self::dmq_versioned_contents(recipient)
+			.into_iter()
+			.map(VersionedInboundDownwardMessage::into_v1)
+			.collect()
+	}
+
+	/// Returns the downward message queue contents for the given para, along with the origins of
+	/// the messages.
+	///
+	/// The most recent messages are the latest in the vector.
+	pub fn dmq_versioned_contents(
+		recipient: ParaId,
+	) -> Vec<VersionedInboundDownwardMessage<T::BlockNumber>> {
+		Self::ensure_queue_migrated(recipient);
+		let state = <Self as Store>::DownwardMessageQueueState::get(recipient);
+		if state.message_count == 0 {
+			return Vec::new()
+		}
+
+		let mut contents = Vec::with_capacity(state.message_count as usize);
+		for index in state.first_page..=state.last_page {
+			if let Some(page) = <Self as Store>::DownwardMessageQueuePages::get(recipient, index) {
+				let skip = if index == state.first_page { state.first_page_offset } else { 0 };
+				contents.extend(page.messages.into_iter().skip(skip as usize));
+			}
+		}
+		contents
+	}
+
+	/// Returns the contents of all non-empty downward message queues, keyed by recipient para.
+	pub fn dmq_contents_all() -> Vec<(ParaId, Vec<InboundDownwardMessage<T::BlockNumber>>)> {
+		migration::migrate_legacy_queues::<T>(u32::MAX);
+		<Self as Store>::DownwardMessageQueueState::iter_keys()
+			.map(|para| (para, Self::dmq_contents(para)))
+			.collect()
+	}
+
+	/// Drop the contents of all downward message queues.
+	///
+	/// The message queue chain heads are left untouched, so this must only be used where the
+	/// resulting state is discarded, such as when dry-running a call.
+	pub fn clear_dmqs() {
+		<Self as Store>::DownwardMessageQueues::remove_all(None);
+		<Self as Store>::DownwardMessageQueuePages::remove_all(None);
+		<Self as Store>::DownwardMessageQueueState::remove_all(None);
+	}
+}
+
+impl<T: Config> FeeTracker for Pallet<T> {
+	fn get_fee_factor(para: ParaId) -> FixedU128 {
+		<Self as Store>::DeliveryFeeFactor::get(para)
 	}
 }
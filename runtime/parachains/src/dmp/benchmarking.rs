@@ -0,0 +1,75 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Pallet as Dmp, *};
+use crate::configuration::Pallet as Configuration;
+
+/// The maximum number of pages which are pruned or swept in the benchmarks.
+const MAX_PAGES: u32 = 64;
+
+fn queue_pages<T: Config>(para: ParaId, pages: u32) {
+	let config = Configuration::<T>::config();
+	let msg = vec![0u8; config.max_downward_message_size as usize];
+	for _ in 0..pages * QUEUE_PAGE_CAPACITY {
+		Dmp::<T>::queue_downward_message(&config, para, msg.clone()).unwrap();
+	}
+}
+
+frame_benchmarking::benchmarks! {
+	// worst case is appending a maximally sized message once the last page is full.
+	queue_downward_message {
+		let para = ParaId::from(1000);
+		queue_pages::<T>(para, 1);
+
+		let config = Configuration::<T>::config();
+		let msg = vec![0u8; config.max_downward_message_size as usize];
+	}: {
+		Dmp::<T>::queue_downward_message(&config, para, msg).unwrap();
+	} verify {
+		assert_eq!(Dmp::<T>::dmq_length(para), QUEUE_PAGE_CAPACITY + 1);
+	}
+
+	// the number of whole pages which are processed, leaving a single message in the queue.
+	prune_dmq {
+		let p in 0 .. MAX_PAGES;
+
+		let para = ParaId::from(1000);
+		queue_pages::<T>(para, p);
+		let config = Configuration::<T>::config();
+		Dmp::<T>::queue_downward_message(&config, para, Vec::new()).unwrap();
+	}: {
+		Dmp::<T>::prune_dmq(para, p * QUEUE_PAGE_CAPACITY);
+	} verify {
+		assert_eq!(Dmp::<T>::dmq_length(para), 1);
+	}
+
+	sweep_queue {
+		let p in 1 .. MAX_PAGES;
+
+		let para = ParaId::from(1000);
+		queue_pages::<T>(para, p);
+	}: {
+		Dmp::<T>::sweep_queue(&para);
+	} verify {
+		assert_eq!(Dmp::<T>::dmq_length(para), 0);
+	}
+}
+
+frame_benchmarking::impl_benchmark_test_suite!(
+	Dmp,
+	crate::mock::new_test_ext(Default::default()),
+	crate::mock::Test
+);
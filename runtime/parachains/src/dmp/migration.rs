@@ -0,0 +1,137 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module that is responsible for migration of storage.
+
+use crate::dmp::{Config, Pallet, QueuePage, QueueState, Store};
+use frame_support::{pallet_prelude::*, traits::StorageVersion, weights::Weight};
+use primitives::v2::Id as ParaId;
+use sp_std::prelude::*;
+
+/// The current storage version.
+///
+/// v0-v1: the downward message queues are split into pages.
+/// v1-v2: the messages in the pages are versioned, so they can carry their origin.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+/// The maximum number of legacy downward message queues which are moved into pages per block.
+pub const LEGACY_QUEUES_PER_BLOCK: u32 = 8;
+
+/// Migrates the pallet storage to the most recent version, checking and setting the `StorageVersion`.
+pub fn migrate_to_latest<T: Config>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+	if StorageVersion::get::<Pallet<T>>() < 1 {
+		weight += migrate_to_v1::<T>();
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight += T::DbWeight::get().writes(1);
+	}
+	if StorageVersion::get::<Pallet<T>>() < 2 {
+		weight += migrate_to_v2::<T>();
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight += T::DbWeight::get().writes(1);
+	}
+	weight
+}
+
+/// Start moving the messages of each `DownwardMessageQueues` entry into the first page of the
+/// queue.
+///
+/// The number of queues is unbounded, so they are not moved here. Instead, a queue is moved right
+/// before it is accessed, and the remaining ones by [`migrate_legacy_queues`] over the following
+/// blocks.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	<Pallet<T> as Store>::LegacyQueuesPending::put(true);
+	T::DbWeight::get().writes(1)
+}
+
+mod v1 {
+	use super::*;
+	use primitives::v2::{Hash, InboundDownwardMessage};
+
+	/// A page of a downward message queue, as stored by storage version 1.
+	#[derive(Decode)]
+	pub struct QueuePage<BlockNumber> {
+		pub messages: Vec<InboundDownwardMessage<BlockNumber>>,
+		pub mqc_head: Hash,
+	}
+}
+
+/// Convert the messages of all pages into the first version of `VersionedInboundDownwardMessage`,
+/// which carries no origin.
+///
+/// Each page is read and written once. Pages hold the unprocessed messages only, so their number
+/// is bounded by the queued messages which the paras did not process yet. Legacy queues which are
+/// still pending are converted when they are moved into pages.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	let mut pages: Weight = 0;
+	<Pallet<T> as Store>::DownwardMessageQueuePages::translate::<v1::QueuePage<T::BlockNumber>, _>(
+		|_, _, page| {
+			pages += 1;
+			Some(QueuePage {
+				messages: page.messages.into_iter().map(Into::into).collect(),
+				mqc_head: page.mqc_head,
+			})
+		},
+	);
+	T::DbWeight::get().reads_writes(pages, pages)
+}
+
+/// Move at most `max` of the remaining legacy downward message queues into pages, and note that
+/// the migration is done once none are left.
+pub fn migrate_legacy_queues<T: Config>(max: u32) -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+	if !<Pallet<T> as Store>::LegacyQueuesPending::get() {
+		return weight
+	}
+
+	let paras = <Pallet<T> as Store>::DownwardMessageQueues::iter_keys()
+		.take(max as usize)
+		.collect::<Vec<_>>();
+	weight = weight.saturating_add(T::DbWeight::get().reads(paras.len() as Weight + 1));
+	if (paras.len() as u32) < max {
+		<Pallet<T> as Store>::LegacyQueuesPending::kill();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	for para in paras {
+		weight = weight.saturating_add(migrate_legacy_queue::<T>(para));
+	}
+	weight
+}
+
+/// Move the messages of the legacy downward message queue of `para`, if any, into the first page
+/// of its queue.
+///
+/// The page may hold more than `QUEUE_PAGE_CAPACITY` messages, in which case new messages are
+/// appended to a new page right away. The legacy queue is read and written once, which the
+/// previous runtime did on every message sent to the para, so this fits into a block as well.
+pub fn migrate_legacy_queue<T: Config>(para: ParaId) -> Weight {
+	let messages = <Pallet<T> as Store>::DownwardMessageQueues::take(para);
+	if messages.is_empty() {
+		return T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	let state = QueueState { message_count: messages.len() as u32, ..Default::default() };
+	let mqc_head = <Pallet<T> as Store>::DownwardMessageQueueHeads::get(para);
+	<Pallet<T> as Store>::DownwardMessageQueuePages::insert(
+		para,
+		state.first_page,
+		QueuePage { messages: messages.into_iter().map(Into::into).collect(), mqc_head },
+	);
+	<Pallet<T> as Store>::DownwardMessageQueueState::insert(para, state);
+
+	T::DbWeight::get().reads_writes(2, 3)
+}
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System};
+use crate::mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System, Test};
 use hex_literal::hex;
 use parity_scale_codec::Encode;
 use primitives::v2::BlockNumber;
@@ -73,9 +73,11 @@ fn clean_dmp_works() {
 		let outgoing_paras = vec![a, b];
 		Dmp::initializer_on_new_session(&notification, &outgoing_paras);
 
-		assert!(<Dmp as Store>::DownwardMessageQueues::get(&a).is_empty());
-		assert!(<Dmp as Store>::DownwardMessageQueues::get(&b).is_empty());
-		assert!(!<Dmp as Store>::DownwardMessageQueues::get(&c).is_empty());
+		assert!(Dmp::dmq_contents(a).is_empty());
+		assert!(Dmp::dmq_contents(b).is_empty());
+		assert!(!Dmp::dmq_contents(c).is_empty());
+		assert!(!<Dmp as Store>::DownwardMessageQueuePages::contains_key(a, 0));
+		assert!(!<Dmp as Store>::DownwardMessageQueueState::contains_key(a));
 	});
 }
 
@@ -160,6 +162,124 @@ fn dmq_pruning() {
 	});
 }
 
+#[test]
+fn dmq_pages_are_filled_and_pruned() {
+	let a = ParaId::from(1312);
+	let msg = |i: u32| i.encode();
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		for i in 0..2 * QUEUE_PAGE_CAPACITY + 1 {
+			queue_downward_message(a, msg(i)).unwrap();
+			if i == QUEUE_PAGE_CAPACITY - 1 {
+				assert_eq!(Dmp::dmq_page_mqc_head(a, 0), Some(Dmp::dmq_mqc_head(a)));
+			}
+		}
+		let state = <Dmp as Store>::DownwardMessageQueueState::get(a);
+		assert_eq!((state.first_page, state.last_page), (0, 2));
+		assert_eq!(Dmp::dmq_length(a), 2 * QUEUE_PAGE_CAPACITY + 1);
+		assert_eq!(Dmp::dmq_page_mqc_head(a, 2), Some(Dmp::dmq_mqc_head(a)));
+
+		// processed messages are skipped, but their page is kept until all of it is processed.
+		Dmp::prune_dmq(a, 1);
+		assert!(<Dmp as Store>::DownwardMessageQueuePages::contains_key(a, 0));
+		assert_eq!(Dmp::dmq_contents(a)[0].msg, msg(1));
+
+		Dmp::prune_dmq(a, QUEUE_PAGE_CAPACITY);
+		assert!(!<Dmp as Store>::DownwardMessageQueuePages::contains_key(a, 0));
+		let state = <Dmp as Store>::DownwardMessageQueueState::get(a);
+		assert_eq!((state.first_page, state.first_page_offset), (1, 1));
+		let contents = Dmp::dmq_contents(a);
+		assert_eq!(contents.len(), QUEUE_PAGE_CAPACITY as usize);
+		assert_eq!(contents[0].msg, msg(QUEUE_PAGE_CAPACITY + 1));
+
+		// once everything is processed, the queue is gone.
+		Dmp::prune_dmq(a, QUEUE_PAGE_CAPACITY);
+		assert_eq!(Dmp::dmq_length(a), 0);
+		assert!(Dmp::dmq_contents(a).is_empty());
+		assert!(!<Dmp as Store>::DownwardMessageQueueState::contains_key(a));
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::iter_prefix(a).count(), 0);
+
+		// the queue starts over at the first page, but the message queue chain continues.
+		let head = Dmp::dmq_mqc_head(a);
+		queue_downward_message(a, msg(0)).unwrap();
+		assert_eq!(Dmp::dmq_length(a), 1);
+		assert!(Dmp::dmq_mqc_head(a) != head);
+		assert_eq!(Dmp::dmq_page_mqc_head(a, 0), Some(Dmp::dmq_mqc_head(a)));
+	});
+}
+
+#[test]
+fn migrate_to_v1_moves_queues_into_pages() {
+	use frame_support::traits::StorageVersion;
+
+	let a = ParaId::from(1312);
+	let b = ParaId::from(228);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		let messages = (0..QUEUE_PAGE_CAPACITY + 2)
+			.map(|i| InboundDownwardMessage { sent_at: 1, msg: i.encode() })
+			.collect::<Vec<_>>();
+		let head = Hash::repeat_byte(1);
+		<Dmp as Store>::DownwardMessageQueues::insert(a, messages.clone());
+		<Dmp as Store>::DownwardMessageQueues::insert(b, Vec::new());
+		<Dmp as Store>::DownwardMessageQueueHeads::insert(a, head);
+		StorageVersion::new(0).put::<Dmp>();
+
+		migration::migrate_to_latest::<Test>();
+		assert!(<Dmp as Store>::LegacyQueuesPending::get());
+		assert_eq!(StorageVersion::get::<Dmp>(), migration::STORAGE_VERSION);
+
+		// the queues are moved over the next block.
+		run_to_block(1, None);
+		assert!(!<Dmp as Store>::LegacyQueuesPending::get());
+		assert_eq!(<Dmp as Store>::DownwardMessageQueues::iter_keys().count(), 0);
+
+		assert_eq!(Dmp::dmq_contents(a), messages);
+		assert_eq!(Dmp::dmq_length(a), QUEUE_PAGE_CAPACITY + 2);
+		assert_eq!(Dmp::dmq_page_mqc_head(a, 0), Some(head));
+		assert!(!<Dmp as Store>::DownwardMessageQueueState::contains_key(b));
+
+		// new messages go to a new page, as the migrated one is over capacity.
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		assert_eq!(<Dmp as Store>::DownwardMessageQueueState::get(a).last_page, 1);
+		Dmp::prune_dmq(a, QUEUE_PAGE_CAPACITY + 2);
+		assert_eq!(Dmp::dmq_contents(a)[0].msg, vec![1, 2, 3].encode());
+	});
+}
+
+#[test]
+fn legacy_queues_are_moved_on_access_and_in_batches() {
+	let paras = (0..migration::LEGACY_QUEUES_PER_BLOCK + 2)
+		.map(ParaId::from)
+		.collect::<Vec<_>>();
+	let a = paras[0];
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		let messages = vec![InboundDownwardMessage { sent_at: 1, msg: vec![1] }];
+		for para in &paras {
+			<Dmp as Store>::DownwardMessageQueues::insert(para, messages.clone());
+		}
+		migration::migrate_to_v1::<Test>();
+
+		// a queue which is accessed before it is migrated is moved right away.
+		queue_downward_message(a, vec![2]).unwrap();
+		assert!(!<Dmp as Store>::DownwardMessageQueues::contains_key(a));
+		assert_eq!(Dmp::dmq_length(a), 2);
+		assert_eq!(Dmp::dmq_contents(a)[0], messages[0]);
+
+		// the remaining queues take two blocks.
+		run_to_block(1, None);
+		assert_eq!(<Dmp as Store>::DownwardMessageQueues::iter_keys().count(), 1);
+		assert!(<Dmp as Store>::LegacyQueuesPending::get());
+		run_to_block(2, None);
+		assert!(!<Dmp as Store>::LegacyQueuesPending::get());
+
+		for para in &paras[1..] {
+			assert_eq!(Dmp::dmq_contents(*para), messages);
+		}
+	});
+}
+
 #[test]
 fn queue_downward_message_critical() {
 	let a = ParaId::from(1312);
@@ -201,3 +321,101 @@ fn verify_dmq_mqc_head_is_externally_accessible() {
 		);
 	});
 }
+
+#[test]
+fn fee_factor_follows_queue_congestion() {
+	let a = ParaId::from(1312);
+	let mut genesis = default_genesis_config();
+	genesis.configuration.config.max_downward_message_size = 1 << 20;
+
+	new_test_ext(genesis).execute_with(|| {
+		let threshold = Dmp::dmq_congestion_threshold(&Configuration::config());
+		assert_eq!(threshold, 16);
+		for _ in 0..threshold {
+			queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		}
+		assert_eq!(Dmp::get_fee_factor(a), FixedU128::one());
+
+		// The queue is now congested, so the fee factor starts to increase.
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		assert_eq!(Dmp::get_fee_factor(a), EXPONENTIAL_FEE_BASE);
+
+		// Larger messages increase it more quickly.
+		queue_downward_message(a, vec![0; 4 * 1024]).unwrap();
+		let size_factor =
+			FixedU128::saturating_from_integer(4).saturating_mul(MESSAGE_SIZE_FEE_BASE);
+		let expected =
+			EXPONENTIAL_FEE_BASE.saturating_mul(EXPONENTIAL_FEE_BASE.saturating_add(size_factor));
+		assert_eq!(Dmp::get_fee_factor(a), expected);
+
+		// Once the queue is drained, the fee factor decreases back to one.
+		Dmp::prune_dmq(a, threshold + 2);
+		assert!(Dmp::get_fee_factor(a) < expected);
+		assert!(Dmp::get_fee_factor(a) > FixedU128::one());
+		Dmp::prune_dmq(a, 0);
+		Dmp::prune_dmq(a, 0);
+		assert_eq!(Dmp::get_fee_factor(a), FixedU128::one());
+		assert!(!<Dmp as Store>::DeliveryFeeFactor::contains_key(a));
+	});
+}
+
+#[test]
+fn queued_messages_carry_their_origin() {
+	let a = ParaId::from(1312);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		Dmp::queue_downward_message_with_origin(
+			&Configuration::config(),
+			a,
+			DownwardMessageOrigin::Governance,
+			vec![1, 2, 3],
+		)
+		.unwrap();
+
+		let contents = Dmp::dmq_versioned_contents(a);
+		assert_eq!(contents[0].origin(), Some(DownwardMessageOrigin::Runtime));
+		assert_eq!(contents[1].origin(), Some(DownwardMessageOrigin::Governance));
+		assert_eq!(contents[0].content_hash(), contents[1].content_hash());
+
+		// the origin is not exposed to the para.
+		assert_eq!(
+			Dmp::dmq_contents(a),
+			contents.into_iter().map(|m| m.into_v1()).collect::<Vec<_>>(),
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v2_versions_the_queued_messages() {
+	use frame_support::{storage::unhashed, traits::StorageVersion};
+
+	let a = ParaId::from(1312);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		let messages = vec![
+			InboundDownwardMessage { sent_at: 1, msg: vec![1] },
+			InboundDownwardMessage { sent_at: 2, msg: vec![2, 3] },
+		];
+		let head = Hash::repeat_byte(1);
+		// a page as stored by storage version 1.
+		unhashed::put(
+			&<Dmp as Store>::DownwardMessageQueuePages::hashed_key_for(a, 0),
+			&(messages.clone(), head),
+		);
+		<Dmp as Store>::DownwardMessageQueueState::insert(
+			a,
+			QueueState { message_count: 2, total_bytes: 3, ..Default::default() },
+		);
+		StorageVersion::new(1).put::<Dmp>();
+
+		migration::migrate_to_latest::<Test>();
+		assert_eq!(StorageVersion::get::<Dmp>(), migration::STORAGE_VERSION);
+
+		let contents = Dmp::dmq_versioned_contents(a);
+		assert_eq!(contents, messages.clone().into_iter().map(Into::into).collect::<Vec<_>>());
+		assert!(contents.iter().all(|m| m.origin().is_none()));
+		assert_eq!(Dmp::dmq_contents(a), messages);
+		assert_eq!(Dmp::dmq_page_mqc_head(a, 0), Some(head));
+	});
+}
@@ -16,17 +16,24 @@
 
 use crate::{
 	configuration::{self, HostConfiguration},
-	dmp, ensure_parachain, initializer, paras,
+	dmp, ensure_parachain, initializer,
+	messaging_metrics::{self, MessageQueue},
+	paras,
 };
 use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use primitives::v2::{
-	Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage, OutboundHrmpMessage,
-	SessionIndex,
+	check_outbound_hrmp_messages, Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage,
+	IsSystem, OutboundHrmpMessage, OutboundHrmpMessagesError, SessionIndex,
 };
 use scale_info::TypeInfo;
-use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash as HashT, UniqueSaturatedInto};
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, BlakeTwo256, CheckedDiv, Hash as HashT, One, UniqueSaturatedInto,
+	},
+	FixedPointNumber, FixedU128,
+};
 use sp_std::{
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 	fmt, mem,
@@ -43,6 +50,12 @@ pub const HRMP_MAX_INBOUND_CHANNELS_BOUND: u32 = 128;
 /// Same as [`HRMP_MAX_INBOUND_CHANNELS_BOUND`], but for outbound channels.
 pub const HRMP_MAX_OUTBOUND_CHANNELS_BOUND: u32 = 128;
 
+/// A channel is considered congested once it holds more than `1 / THRESHOLD_FACTOR` of either its
+/// message capacity or its total size.
+const THRESHOLD_FACTOR: u32 = 2;
+
+pub mod runtime_api;
+
 #[cfg(test)]
 pub(crate) mod tests;
 
@@ -58,6 +71,7 @@ pub trait WeightInfo {
 	fn force_process_hrmp_close(c: u32) -> Weight;
 	fn hrmp_cancel_open_request(c: u32) -> Weight;
 	fn clean_open_channel_requests(c: u32) -> Weight;
+	fn establish_system_channel() -> Weight;
 }
 
 /// A weight info that is only suitable for testing.
@@ -88,6 +102,9 @@ impl WeightInfo for TestWeightInfo {
 	fn clean_open_channel_requests(_: u32) -> Weight {
 		Weight::MAX
 	}
+	fn establish_system_channel() -> Weight {
+		Weight::MAX
+	}
 }
 
 /// A description of a request to open an HRMP channel.
@@ -158,6 +175,7 @@ pub enum OutboundHrmpAcceptanceErr {
 	MoreMessagesThanPermitted { sent: u32, permitted: u32 },
 	NotSorted { idx: u32 },
 	NoSuchChannel { idx: u32, channel_id: HrmpChannelId },
+	ChannelClosing { idx: u32, channel_id: HrmpChannelId },
 	MaxMessageSizeExceeded { idx: u32, msg_size: u32, max_size: u32 },
 	TotalSizeExceeded { idx: u32, total_size: u32, limit: u32 },
 	CapacityExceeded { idx: u32, count: u32, limit: u32 },
@@ -206,6 +224,11 @@ impl fmt::Debug for OutboundHrmpAcceptanceErr {
 				"the HRMP message at index {} is sent to a non existent channel {:?}->{:?}",
 				idx, channel_id.sender, channel_id.recipient,
 			),
+			ChannelClosing { idx, channel_id } => write!(
+				fmt,
+				"the HRMP message at index {} is sent to a channel {:?}->{:?} which is being closed",
+				idx, channel_id.sender, channel_id.recipient,
+			),
 			MaxMessageSizeExceeded { idx, msg_size, max_size } => write!(
 				fmt,
 				"the HRMP message at index {} exceeds the negotiated channel maximum message size ({} > {})",
@@ -236,7 +259,11 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config:
-		frame_system::Config + configuration::Config + paras::Config + dmp::Config
+		frame_system::Config
+		+ configuration::Config
+		+ paras::Config
+		+ dmp::Config
+		+ messaging_metrics::Config
 	{
 		/// The outer event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -269,6 +296,16 @@ pub mod pallet {
 		OpenChannelAccepted(ParaId, ParaId),
 		/// HRMP channel closed. `[by_parachain, channel_id]`
 		ChannelClosed(ParaId, HrmpChannelId),
+		/// The closure of an HRMP channel was enacted while it still held messages. The channel
+		/// no longer accepts messages and is removed once the recipient processed the remaining
+		/// ones. `[channel_id]`
+		ChannelDraining(HrmpChannelId),
+		/// An HRMP channel was removed and the deposits were returned.
+		/// `[channel_id, sender_deposit, recipient_deposit]`
+		ChannelRemoved(HrmpChannelId, Balance, Balance),
+		/// An HRMP channel between two system parachains was established without deposits.
+		/// `[sender, recipient, max_capacity, max_message_size]`
+		SystemChannelEstablished(ParaId, ParaId, u32, u32),
 	}
 
 	#[pallet::error]
@@ -311,6 +348,9 @@ pub mod pallet {
 		OpenHrmpChannelAlreadyConfirmed,
 		/// The provided witness data is wrong.
 		WrongWitness,
+		/// Either the sender or the recipient of a system channel is not a system parachain, or the
+		/// origin is neither Root nor one of them.
+		ChannelCreationNotAuthorized,
 	}
 
 	/// The set of pending HRMP open channel requests.
@@ -358,6 +398,20 @@ pub mod pallet {
 	pub type HrmpCloseChannelRequestsList<T: Config> =
 		StorageValue<_, Vec<HrmpChannelId>, ValueQuery>;
 
+	/// A set of HRMP channels which were closed while still holding messages. Such a channel does
+	/// not accept new messages, and is removed, returning the deposits, once the recipient
+	/// processed all of its messages.
+	///
+	/// Invariant:
+	/// - each channel in this set is present in `HrmpChannels` and has non-empty contents.
+	///
+	/// This set was introduced without a storage migration. Under the previous rules a channel
+	/// was removed as soon as its close request was processed, so no channel could be draining
+	/// and the set starts out empty. Close requests still queued from before are processed under
+	/// the new rules at the next session change.
+	#[pallet::storage]
+	pub type HrmpDrainingChannels<T: Config> = StorageMap<_, Twox64Concat, HrmpChannelId, ()>;
+
 	/// The HRMP watermark associated with each para.
 	/// Invariant:
 	/// - each para `P` used here as a key should satisfy `Paras::is_valid_para(P)` within a session.
@@ -404,6 +458,22 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Initialization value for the delivery fee factor.
+	#[pallet::type_value]
+	pub fn InitialFactor() -> FixedU128 {
+		FixedU128::one()
+	}
+
+	/// The number to multiply the base delivery fee by for messages sent over a certain channel.
+	///
+	/// This is increased while the channel is congested and decreased again once the recipient
+	/// processed enough messages, down to a minimum of one.
+	///
+	/// Invariant: cannot be set if the corresponding channel in `HrmpChannels` is `None`.
+	#[pallet::storage]
+	pub type HrmpChannelFeeFactor<T: Config> =
+		StorageMap<_, Twox64Concat, HrmpChannelId, FixedU128, ValueQuery, InitialFactor>;
+
 	/// Maintains a mapping that can be used to answer the question: What paras sent a message at
 	/// the given block number for a given receiver. Invariants:
 	/// - The inner `Vec<ParaId>` is never empty.
@@ -577,6 +647,46 @@ pub mod pallet {
 			Self::deposit_event(Event::OpenChannelCanceled(origin, channel_id));
 			Ok(())
 		}
+
+		/// Establish a channel between two system parachains right away, skipping the
+		/// request/accept handshake.
+		///
+		/// The channel is sized according to the maximum capacity and message size allowed by the
+		/// relay-chain configuration, and neither party has to leave a deposit.
+		///
+		/// The origin must be either Root or one of the two parachains. The call is free if it
+		/// succeeds.
+		#[pallet::weight(<T as Config>::WeightInfo::establish_system_channel())]
+		pub fn establish_system_channel(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			recipient: ParaId,
+		) -> DispatchResultWithPostInfo {
+			if ensure_root(origin.clone()).is_err() {
+				let origin = ensure_parachain(<T as Config>::Origin::from(origin))?;
+				ensure!(
+					origin == sender || origin == recipient,
+					Error::<T>::ChannelCreationNotAuthorized,
+				);
+			}
+			ensure!(
+				sender.is_system() && recipient.is_system(),
+				Error::<T>::ChannelCreationNotAuthorized,
+			);
+
+			let config = <configuration::Pallet<T>>::config();
+			let max_capacity = config.hrmp_channel_max_capacity;
+			let max_message_size = config.hrmp_channel_max_message_size;
+			Self::open_system_channel(sender, recipient, max_capacity, max_message_size)?;
+
+			Self::deposit_event(Event::SystemChannelEstablished(
+				sender,
+				recipient,
+				max_capacity,
+				max_message_size,
+			));
+			Ok(Pays::No.into())
+		}
 	}
 }
 
@@ -627,7 +737,7 @@ impl<T: Config> Pallet<T> {
 			outgoing_paras.len() as u32
 		))
 		.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_close(
-			outgoing_paras.len() as u32
+			outgoing_paras.len() as u32,
 		))
 	}
 
@@ -701,7 +811,7 @@ impl<T: Config> Pallet<T> {
 				if !outgoing.contains(&req_id.recipient) {
 					T::Currency::unreserve(
 						&req_id.recipient.into_account(),
-						config.hrmp_recipient_deposit.unique_saturated_into(),
+						Self::recipient_deposit(config, &req_id).unique_saturated_into(),
 					);
 				}
 				Self::decrease_accepted_channel_request_count(req_id.recipient);
@@ -764,7 +874,7 @@ impl<T: Config> Pallet<T> {
 						&channel_id,
 						HrmpChannel {
 							sender_deposit: request.sender_deposit,
-							recipient_deposit: config.hrmp_recipient_deposit,
+							recipient_deposit: Self::recipient_deposit(config, &channel_id),
 							max_capacity: request.max_capacity,
 							max_total_size: request.max_total_size,
 							max_message_size: request.max_message_size,
@@ -797,12 +907,24 @@ impl<T: Config> Pallet<T> {
 		<Self as Store>::HrmpOpenChannelRequestsList::put(open_req_channels);
 	}
 
-	/// Iterate over all close channel requests unconditionally closing the channels.
+	/// Iterate over all close channel requests and close the channels.
+	///
+	/// Channels which still hold messages are not removed right away, but stop accepting new
+	/// messages and are removed once drained by the recipient.
 	fn process_hrmp_close_channel_requests() {
 		let close_reqs = <Self as Store>::HrmpCloseChannelRequestsList::take();
 		for condemned_ch_id in close_reqs {
 			<Self as Store>::HrmpCloseChannelRequests::remove(&condemned_ch_id);
-			Self::close_hrmp_channel(&condemned_ch_id);
+
+			let is_drained = <Self as Store>::HrmpChannelContents::decode_len(&condemned_ch_id)
+				.unwrap_or_default() ==
+				0;
+			if is_drained || !<Self as Store>::HrmpChannels::contains_key(&condemned_ch_id) {
+				Self::close_hrmp_channel(&condemned_ch_id);
+			} else {
+				<Self as Store>::HrmpDrainingChannels::insert(&condemned_ch_id, ());
+				Self::deposit_event(Event::ChannelDraining(condemned_ch_id));
+			}
 		}
 	}
 
@@ -824,9 +946,17 @@ impl<T: Config> Pallet<T> {
 				&channel_id.recipient.into_account(),
 				recipient_deposit.unique_saturated_into(),
 			);
+			Self::deposit_event(Event::ChannelRemoved(
+				channel_id.clone(),
+				sender_deposit,
+				recipient_deposit,
+			));
 		}
 
 		<Self as Store>::HrmpChannelContents::remove(channel_id);
+		<Self as Store>::HrmpDrainingChannels::remove(channel_id);
+		<Self as Store>::HrmpChannelFeeFactor::remove(channel_id);
+		<messaging_metrics::Pallet<T>>::note_cleared(&MessageQueue::Hrmp(channel_id.clone()));
 
 		<Self as Store>::HrmpEgressChannelsIndex::mutate(&channel_id.sender, |v| {
 			if let Ok(i) = v.binary_search(&channel_id.recipient) {
@@ -893,27 +1023,26 @@ impl<T: Config> Pallet<T> {
 		sender: ParaId,
 		out_hrmp_msgs: &[OutboundHrmpMessage<ParaId>],
 	) -> Result<(), OutboundHrmpAcceptanceErr> {
-		if out_hrmp_msgs.len() as u32 > config.hrmp_max_message_num_per_candidate {
-			return Err(OutboundHrmpAcceptanceErr::MoreMessagesThanPermitted {
-				sent: out_hrmp_msgs.len() as u32,
-				permitted: config.hrmp_max_message_num_per_candidate,
-			})
-		}
-
-		let mut last_recipient = None::<ParaId>;
+		// the messages must be sorted in ascending order and there must be no two messages sent
+		// to the same recipient. The size limit is negotiated per channel and checked below.
+		check_outbound_hrmp_messages(
+			out_hrmp_msgs,
+			config.hrmp_max_message_num_per_candidate,
+			u32::MAX,
+		)
+		.map_err(|e| match e {
+			OutboundHrmpMessagesError::TooManyMessages { sent, permitted } =>
+				OutboundHrmpAcceptanceErr::MoreMessagesThanPermitted { sent, permitted },
+			OutboundHrmpMessagesError::NotSorted { idx } |
+			OutboundHrmpMessagesError::DuplicateRecipient { idx } =>
+				OutboundHrmpAcceptanceErr::NotSorted { idx },
+			OutboundHrmpMessagesError::MessageTooLarge { idx, msg_size, max_size } =>
+				OutboundHrmpAcceptanceErr::MaxMessageSizeExceeded { idx, msg_size, max_size },
+		})?;
 
 		for (idx, out_msg) in
 			out_hrmp_msgs.iter().enumerate().map(|(idx, out_msg)| (idx as u32, out_msg))
 		{
-			match last_recipient {
-				// the messages must be sorted in ascending order and there must be no two messages sent
-				// to the same recipient. Thus we can check that every recipient is strictly greater than
-				// the previous one.
-				Some(last_recipient) if out_msg.recipient <= last_recipient =>
-					return Err(OutboundHrmpAcceptanceErr::NotSorted { idx }),
-				_ => last_recipient = Some(out_msg.recipient),
-			}
-
 			let channel_id = HrmpChannelId { sender, recipient: out_msg.recipient };
 
 			let channel = match <Self as Store>::HrmpChannels::get(&channel_id) {
@@ -921,6 +1050,10 @@ impl<T: Config> Pallet<T> {
 				None => return Err(OutboundHrmpAcceptanceErr::NoSuchChannel { channel_id, idx }),
 			};
 
+			if <Self as Store>::HrmpDrainingChannels::contains_key(&channel_id) {
+				return Err(OutboundHrmpAcceptanceErr::ChannelClosing { channel_id, idx })
+			}
+
 			let msg_size = out_msg.data.len() as u32;
 			if msg_size > channel.max_message_size {
 				return Err(OutboundHrmpAcceptanceErr::MaxMessageSizeExceeded {
@@ -990,21 +1123,37 @@ impl<T: Config> Pallet<T> {
 					leftover.push(msg);
 				}
 			}
-			if !leftover.is_empty() {
+			let is_drained = leftover.is_empty();
+			if !is_drained {
 				<Self as Store>::HrmpChannelContents::insert(&channel_id, leftover);
 			} else {
 				<Self as Store>::HrmpChannelContents::remove(&channel_id);
 			}
 
 			// update the channel metadata.
-			<Self as Store>::HrmpChannels::mutate(&channel_id, |channel| {
-				if let Some(ref mut channel) = channel {
+			let is_congested = <Self as Store>::HrmpChannels::mutate(&channel_id, |channel| {
+				channel.as_mut().map_or(false, |channel| {
 					channel.msg_count -= pruned_cnt as u32;
 					channel.total_size -= pruned_size as u32;
-				}
+					Self::is_congested(channel)
+				})
 			});
+			if !is_congested {
+				Self::decrease_fee_factor(&channel_id);
+			}
 
-			weight += T::DbWeight::get().reads_writes(2, 2);
+			weight += <messaging_metrics::Pallet<T>>::note_dequeued(
+				&MessageQueue::Hrmp(channel_id.clone()),
+				pruned_cnt as u32,
+				pruned_size as u32,
+			);
+			weight += T::DbWeight::get().reads_writes(4, 3);
+
+			// a closed channel is only removed once the recipient processed all of its messages.
+			if is_drained && <Self as Store>::HrmpDrainingChannels::contains_key(&channel_id) {
+				Self::close_hrmp_channel(&channel_id);
+				weight += T::DbWeight::get().reads_writes(3, 6);
+			}
 		}
 
 		<Self as Store>::HrmpWatermarks::insert(&recipient, new_hrmp_watermark);
@@ -1034,6 +1183,10 @@ impl<T: Config> Pallet<T> {
 					continue
 				},
 			};
+			if <Self as Store>::HrmpDrainingChannels::contains_key(&channel_id) {
+				// the channel was closed since acceptance of this candidate.
+				continue
+			}
 
 			let inbound = InboundHrmpMessage { sent_at: now, data: out_msg.data };
 
@@ -1050,6 +1203,24 @@ impl<T: Config> Pallet<T> {
 			));
 			channel.mqc_head = Some(new_head);
 
+			if Self::is_congested(&channel) {
+				let message_size_factor =
+					FixedU128::saturating_from_integer(inbound.data.len() as u128 / 1024)
+						.saturating_mul(dmp::MESSAGE_SIZE_FEE_BASE);
+				<Self as Store>::HrmpChannelFeeFactor::mutate(&channel_id, |f| {
+					*f = f.saturating_mul(
+						dmp::EXPONENTIAL_FEE_BASE.saturating_add(message_size_factor),
+					);
+				});
+				weight += T::DbWeight::get().reads_writes(1, 1);
+			}
+
+			weight += <messaging_metrics::Pallet<T>>::note_enqueued(
+				&MessageQueue::Hrmp(channel_id.clone()),
+				1,
+				inbound.data.len() as u32,
+			);
+
 			<Self as Store>::HrmpChannels::insert(&channel_id, channel);
 			<Self as Store>::HrmpChannelContents::append(&channel_id, inbound);
 
@@ -1084,6 +1255,38 @@ impl<T: Config> Pallet<T> {
 		weight
 	}
 
+	/// Whether the channel holds more than `1 / THRESHOLD_FACTOR` of its message capacity or of
+	/// its total size.
+	fn is_congested(channel: &HrmpChannel) -> bool {
+		channel.msg_count > channel.max_capacity / THRESHOLD_FACTOR ||
+			channel.total_size > channel.max_total_size / THRESHOLD_FACTOR
+	}
+
+	/// Lower the delivery fee factor of the channel by `EXPONENTIAL_FEE_BASE`, down to a minimum
+	/// of one.
+	fn decrease_fee_factor(channel_id: &HrmpChannelId) {
+		<Self as Store>::HrmpChannelFeeFactor::mutate_exists(channel_id, |maybe_factor| {
+			if let Some(factor) = maybe_factor.take() {
+				let new_factor = factor
+					.checked_div(&dmp::EXPONENTIAL_FEE_BASE)
+					.unwrap_or_default()
+					.max(FixedU128::one());
+				if new_factor > FixedU128::one() {
+					*maybe_factor = Some(new_factor);
+				}
+			}
+		});
+	}
+
+	/// Returns the factor by which the delivery fee for messages sent over the given channel
+	/// should be multiplied.
+	///
+	/// The factor is greater than one only while the channel is congested, and is one for
+	/// channels which don't exist.
+	pub fn channel_fee_factor(channel_id: &HrmpChannelId) -> FixedU128 {
+		<Self as Store>::HrmpChannelFeeFactor::get(channel_id)
+	}
+
 	/// Initiate opening a channel from a parachain to a given recipient with given channel
 	/// parameters.
 	///
@@ -1136,10 +1339,9 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::OpenHrmpChannelLimitExceeded,
 		);
 
-		T::Currency::reserve(
-			&origin.into_account(),
-			config.hrmp_sender_deposit.unique_saturated_into(),
-		)?;
+		let sender_deposit =
+			if Self::is_system_channel(&channel_id) { 0 } else { config.hrmp_sender_deposit };
+		T::Currency::reserve(&origin.into_account(), sender_deposit.unique_saturated_into())?;
 
 		// mutating storage directly now -- shall not bail henceforth.
 
@@ -1149,7 +1351,7 @@ impl<T: Config> Pallet<T> {
 			HrmpOpenChannelRequest {
 				confirmed: false,
 				_age: 0,
-				sender_deposit: config.hrmp_sender_deposit,
+				sender_deposit,
 				max_capacity: proposed_max_capacity,
 				max_message_size: proposed_max_message_size,
 				max_total_size: config.hrmp_channel_max_total_size,
@@ -1183,6 +1385,111 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Open a deposit-free channel between two system parachains immediately.
+	///
+	/// Performs the same checks as [`init_open_channel`](Self::init_open_channel) and
+	/// [`accept_open_channel`](Self::accept_open_channel) combined, except that there must not be
+	/// a pending open request for the channel either.
+	fn open_system_channel(
+		sender: ParaId,
+		recipient: ParaId,
+		max_capacity: u32,
+		max_message_size: u32,
+	) -> DispatchResult {
+		ensure!(sender != recipient, Error::<T>::OpenHrmpChannelToSelf);
+		ensure!(
+			<paras::Pallet<T>>::is_valid_para(sender) &&
+				<paras::Pallet<T>>::is_valid_para(recipient),
+			Error::<T>::OpenHrmpChannelInvalidRecipient,
+		);
+		ensure!(max_capacity > 0, Error::<T>::OpenHrmpChannelZeroCapacity);
+		ensure!(max_message_size > 0, Error::<T>::OpenHrmpChannelZeroMessageSize);
+
+		let channel_id = HrmpChannelId { sender, recipient };
+		ensure!(
+			<Self as Store>::HrmpChannels::get(&channel_id).is_none(),
+			Error::<T>::OpenHrmpChannelAlreadyExists,
+		);
+		ensure!(
+			<Self as Store>::HrmpOpenChannelRequests::get(&channel_id).is_none(),
+			Error::<T>::OpenHrmpChannelAlreadyRequested,
+		);
+
+		let config = <configuration::Pallet<T>>::config();
+		let (outbound_limit, inbound_limit) = (
+			if <paras::Pallet<T>>::is_parathread(sender) {
+				config.hrmp_max_parathread_outbound_channels
+			} else {
+				config.hrmp_max_parachain_outbound_channels
+			},
+			if <paras::Pallet<T>>::is_parathread(recipient) {
+				config.hrmp_max_parathread_inbound_channels
+			} else {
+				config.hrmp_max_parachain_inbound_channels
+			},
+		);
+		let egress_cnt =
+			<Self as Store>::HrmpEgressChannelsIndex::decode_len(&sender).unwrap_or(0) as u32;
+		let open_req_cnt = <Self as Store>::HrmpOpenChannelRequestCount::get(&sender);
+		ensure!(
+			egress_cnt + open_req_cnt < outbound_limit,
+			Error::<T>::OpenHrmpChannelLimitExceeded,
+		);
+		let ingress_cnt =
+			<Self as Store>::HrmpIngressChannelsIndex::decode_len(&recipient).unwrap_or(0) as u32;
+		let accepted_cnt = <Self as Store>::HrmpAcceptedChannelRequestCount::get(&recipient);
+		ensure!(
+			ingress_cnt + accepted_cnt < inbound_limit,
+			Error::<T>::AcceptHrmpChannelLimitExceeded,
+		);
+
+		// mutating storage directly now -- shall not bail henceforth.
+
+		<Self as Store>::HrmpChannels::insert(
+			&channel_id,
+			HrmpChannel {
+				sender_deposit: 0,
+				recipient_deposit: 0,
+				max_capacity,
+				max_total_size: config.hrmp_channel_max_total_size,
+				max_message_size,
+				msg_count: 0,
+				total_size: 0,
+				mqc_head: None,
+			},
+		);
+		<Self as Store>::HrmpIngressChannelsIndex::mutate(&recipient, |v| {
+			if let Err(i) = v.binary_search(&sender) {
+				v.insert(i, sender);
+			}
+		});
+		<Self as Store>::HrmpEgressChannelsIndex::mutate(&sender, |v| {
+			if let Err(i) = v.binary_search(&recipient) {
+				v.insert(i, recipient);
+			}
+		});
+
+		Ok(())
+	}
+
+	/// Whether the given channel connects two system parachains, in which case neither side has
+	/// to leave a deposit for it.
+	fn is_system_channel(channel_id: &HrmpChannelId) -> bool {
+		channel_id.sender.is_system() && channel_id.recipient.is_system()
+	}
+
+	/// The deposit the recipient of the given channel leaves when accepting it.
+	fn recipient_deposit(
+		config: &HostConfiguration<T::BlockNumber>,
+		channel_id: &HrmpChannelId,
+	) -> Balance {
+		if Self::is_system_channel(channel_id) {
+			0
+		} else {
+			config.hrmp_recipient_deposit
+		}
+	}
+
 	/// Accept a pending open channel request from the given sender.
 	///
 	/// Basically the same as [`hrmp_accept_open_channel`](Pallet::hrmp_accept_open_channel) but
@@ -1211,7 +1518,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Currency::reserve(
 			&origin.into_account(),
-			config.hrmp_recipient_deposit.unique_saturated_into(),
+			Self::recipient_deposit(&config, &channel_id).unique_saturated_into(),
 		)?;
 
 		// persist the updated open channel request and then increment the number of accepted
@@ -1281,9 +1588,11 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::CloseHrmpChannelDoesntExist,
 		);
 
-		// check that there is no outstanding close request for this channel
+		// check that there is no outstanding close request for this channel and that it is not
+		// being drained already.
 		ensure!(
-			<Self as Store>::HrmpCloseChannelRequests::get(&channel_id).is_none(),
+			<Self as Store>::HrmpCloseChannelRequests::get(&channel_id).is_none() &&
+				!<Self as Store>::HrmpDrainingChannels::contains_key(&channel_id),
 			Error::<T>::CloseHrmpChannelAlreadyUnderway,
 		);
 
@@ -1356,6 +1665,46 @@ impl<T: Config> Pallet<T> {
 
 		inbound_hrmp_channels_contents
 	}
+
+	/// Returns the block numbers at which messages were sent to the given recipient, which are
+	/// valid HRMP watermarks for its next candidate in addition to the most recent block.
+	pub(crate) fn valid_watermarks(recipient: ParaId) -> Vec<T::BlockNumber> {
+		<Self as Store>::HrmpChannelDigests::get(&recipient)
+			.into_iter()
+			.map(|(block_no, _)| block_no)
+			.collect()
+	}
+
+	/// Returns the number of messages and bytes that may still be sent over each of the outbound
+	/// channels of the given sender, keyed by recipient.
+	///
+	/// Channels which are being drained do not accept any new messages.
+	pub(crate) fn outbound_remaining_capacity(sender: ParaId) -> Vec<(ParaId, (u32, u32))> {
+		let recipients = <Self as Store>::HrmpEgressChannelsIndex::get(&sender);
+		let mut remaining = Vec::with_capacity(recipients.len());
+
+		for recipient in recipients {
+			let channel_id = HrmpChannelId { sender, recipient };
+			let channel = match <Self as Store>::HrmpChannels::get(&channel_id) {
+				Some(channel) => channel,
+				None => continue,
+			};
+
+			if <Self as Store>::HrmpDrainingChannels::contains_key(&channel_id) {
+				remaining.push((recipient, (0, 0)));
+			} else {
+				remaining.push((
+					recipient,
+					(
+						channel.max_capacity.saturating_sub(channel.msg_count),
+						channel.max_total_size.saturating_sub(channel.total_size),
+					),
+				));
+			}
+		}
+
+		remaining
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -1474,6 +1823,17 @@ impl<T: Config> Pallet<T> {
 			assert!(!contents.is_empty());
 		}
 
+		// A draining channel is still open and only exists until its contents are processed.
+		for (draining_channel, _) in <Self as Store>::HrmpDrainingChannels::iter() {
+			assert!(<Self as Store>::HrmpChannels::contains_key(&draining_channel));
+			assert!(<Self as Store>::HrmpChannelContents::contains_key(&draining_channel));
+		}
+
+		for (channel, factor) in <Self as Store>::HrmpChannelFeeFactor::iter() {
+			assert!(<Self as Store>::HrmpChannels::contains_key(&channel));
+			assert!(factor > FixedU128::one());
+		}
+
 		// Senders and recipients must be onboarded. Otherwise, all channels associated with them
 		// are removed.
 		assert_contains_only_onboarded(
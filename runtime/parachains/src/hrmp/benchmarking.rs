@@ -132,6 +132,8 @@ where
 const PREFIX_0: u32 = 10_000;
 const PREFIX_1: u32 = PREFIX_0 * 2;
 const MAX_UNIQUE_CHANNELS: u32 = 128;
+/// A para that is not a system parachain, and so has to leave deposits for its channels.
+const USER_PARA: u32 = 2_000;
 
 static_assertions::const_assert!(MAX_UNIQUE_CHANNELS < PREFIX_0);
 static_assertions::const_assert!(HRMP_MAX_INBOUND_CHANNELS_BOUND < PREFIX_0);
@@ -141,10 +143,10 @@ frame_benchmarking::benchmarks! {
 	where_clause { where <T as frame_system::Config>::Origin: From<crate::Origin> }
 
 	hrmp_init_open_channel {
-		let sender_id: ParaId = 1u32.into();
-		let sender_origin: crate::Origin = 1u32.into();
+		let sender_id: ParaId = USER_PARA.into();
+		let sender_origin: crate::Origin = USER_PARA.into();
 
-		let recipient_id: ParaId = 2u32.into();
+		let recipient_id: ParaId = (USER_PARA + 1).into();
 
 		// make sure para is registered, and has enough balance.
 		let deposit: BalanceOf<T> = Configuration::<T>::config().hrmp_sender_deposit.unique_saturated_into();
@@ -162,7 +164,7 @@ frame_benchmarking::benchmarks! {
 
 	hrmp_accept_open_channel {
 		let [(sender, _), (recipient, recipient_origin)] =
-			establish_para_connection::<T>(1, 2, ParachainSetupStep::Requested);
+			establish_para_connection::<T>(USER_PARA, USER_PARA + 1, ParachainSetupStep::Requested);
 	}: _(recipient_origin, sender)
 	verify {
 		assert_last_event::<T>(Event::<T>::OpenChannelAccepted(sender, recipient).into());
@@ -170,7 +172,7 @@ frame_benchmarking::benchmarks! {
 
 	hrmp_close_channel {
 		let [(sender, sender_origin), (recipient, _)] =
-			establish_para_connection::<T>(1, 2, ParachainSetupStep::Established);
+			establish_para_connection::<T>(USER_PARA, USER_PARA + 1, ParachainSetupStep::Established);
 		let channel_id = HrmpChannelId { sender, recipient };
 	}: _(sender_origin, channel_id.clone())
 	verify {
@@ -270,7 +272,7 @@ frame_benchmarking::benchmarks! {
 		}
 
 		let [(sender, sender_origin), (recipient, _)] =
-			establish_para_connection::<T>(1, 2, ParachainSetupStep::Requested);
+			establish_para_connection::<T>(USER_PARA, USER_PARA + 1, ParachainSetupStep::Requested);
 		assert_eq!(HrmpOpenChannelRequestsList::<T>::decode_len().unwrap_or_default() as u32, c + 1);
 		let channel_id = HrmpChannelId { sender, recipient };
 	}: _(sender_origin, channel_id, c + 1)
@@ -296,6 +298,23 @@ frame_benchmarking::benchmarks! {
 	} verify {
 		assert_eq!(HrmpOpenChannelRequestsList::<T>::decode_len().unwrap_or_default() as u32, 0);
 	}
+
+	establish_system_channel {
+		let sender: ParaId = 1u32.into();
+		let recipient: ParaId = 2u32.into();
+		register_parachain_with_balance::<T>(sender, 0u32.into());
+		register_parachain_with_balance::<T>(recipient, 0u32.into());
+	}: _(frame_system::RawOrigin::Root, sender, recipient)
+	verify {
+		let config = Configuration::<T>::config();
+		assert_last_event::<T>(Event::<T>::SystemChannelEstablished(
+			sender,
+			recipient,
+			config.hrmp_channel_max_capacity,
+			config.hrmp_channel_max_message_size,
+		).into());
+		assert!(HrmpChannels::<T>::contains_key(&HrmpChannelId { sender, recipient }));
+	}
 }
 
 frame_benchmarking::impl_benchmark_test_suite!(
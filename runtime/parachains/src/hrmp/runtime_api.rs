@@ -0,0 +1,33 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definitions for querying the HRMP pallet.
+
+use primitives::v2::Id as ParaId;
+use sp_runtime::FixedU128;
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying the congestion of HRMP channels, so that the router of a sending
+	/// parachain can price the delivery of its messages accordingly.
+	pub trait HrmpApi {
+		/// The factor by which the delivery fee for messages sent from `sender` to `recipient`
+		/// should be multiplied.
+		///
+		/// This grows exponentially while the channel is congested and is one otherwise, or if
+		/// there is no such channel.
+		fn hrmp_channel_fee_factor(sender: ParaId, recipient: ParaId) -> FixedU128;
+	}
+}
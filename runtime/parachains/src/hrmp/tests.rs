@@ -48,7 +48,7 @@ fn run_to_block(to: BlockNumber, new_session: Option<Vec<BlockNumber>>) {
 				&notification.new_config,
 				notification.validators.clone(),
 			);
-			let outgoing_paras = Paras::initializer_on_new_session(&notification);
+			let (outgoing_paras, _) = Paras::initializer_on_new_session(&notification);
 			Hrmp::initializer_on_new_session(&notification, &outgoing_paras);
 		}
 
@@ -263,6 +263,92 @@ fn send_recv_messages() {
 	});
 }
 
+#[test]
+fn remaining_capacity_and_watermarks_follow_sent_messages() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_channel_max_message_size = 20;
+	genesis.hrmp_channel_max_total_size = 20;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+
+		run_to_block(5, Some(vec![4, 5]));
+		Hrmp::init_open_channel(para_a, para_b, 2, 20).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+
+		run_to_block(6, Some(vec![6]));
+		assert_eq!(Hrmp::outbound_remaining_capacity(para_a), vec![(para_b, (2, 20))]);
+		assert!(Hrmp::outbound_remaining_capacity(para_b).is_empty());
+		assert!(Hrmp::valid_watermarks(para_b).is_empty());
+
+		let msgs = vec![OutboundHrmpMessage { recipient: para_b, data: b"knock".to_vec() }];
+		let _ = Hrmp::queue_outbound_hrmp(para_a, msgs);
+		assert_eq!(Hrmp::outbound_remaining_capacity(para_a), vec![(para_b, (1, 15))]);
+		assert_eq!(Hrmp::valid_watermarks(para_b), vec![6]);
+
+		// A channel being drained accepts no more messages.
+		Hrmp::close_channel(para_b, HrmpChannelId { sender: para_a, recipient: para_b }).unwrap();
+		run_to_block(8, Some(vec![8]));
+		assert_eq!(Hrmp::outbound_remaining_capacity(para_a), vec![(para_b, (0, 0))]);
+
+		let _ = Hrmp::prune_hrmp(para_b, 6);
+		assert!(Hrmp::valid_watermarks(para_b).is_empty());
+		assert!(Hrmp::outbound_remaining_capacity(para_a).is_empty());
+	});
+}
+
+#[test]
+fn fee_factor_follows_channel_congestion() {
+	use primitives::v2::well_known_keys;
+
+	let para_a = 32.into();
+	let para_b = 64.into();
+	let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+	let base = dmp::EXPONENTIAL_FEE_BASE;
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_channel_max_capacity = 4;
+	genesis.hrmp_max_message_num_per_candidate = 4;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+
+		run_to_block(5, Some(vec![4, 5]));
+		Hrmp::init_open_channel(para_a, para_b, 4, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		run_to_block(6, Some(vec![6]));
+		assert_eq!(Hrmp::channel_fee_factor(&channel_id), FixedU128::one());
+
+		// The third message exceeds half of the capacity of the channel.
+		let msg = || OutboundHrmpMessage { recipient: para_b, data: vec![1, 2] };
+		let _ = Hrmp::queue_outbound_hrmp(para_a, vec![msg(), msg(), msg()]);
+		assert_eq!(Hrmp::channel_fee_factor(&channel_id), base);
+
+		run_to_block(7, None);
+		let _ = Hrmp::queue_outbound_hrmp(para_a, vec![msg()]);
+		assert_eq!(Hrmp::channel_fee_factor(&channel_id), base * base);
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// The sending parachain can find the factor in the relay-chain state.
+		let raw_factor =
+			sp_io::storage::get(&well_known_keys::hrmp_channel_fee_factor(channel_id.clone()))
+				.expect("the channel is congested, so its fee factor must be stored");
+		assert_eq!(FixedU128::decode(&mut &raw_factor[..]).unwrap(), base * base);
+
+		// Once the channel is no longer congested, the factor decreases with each pruning.
+		run_to_block(8, None);
+		let _ = Hrmp::prune_hrmp(para_b, 6);
+		assert_eq!(Hrmp::channel_fee_factor(&channel_id), base);
+		let _ = Hrmp::prune_hrmp(para_b, 7);
+		assert_eq!(Hrmp::channel_fee_factor(&channel_id), FixedU128::one());
+		assert!(!<Hrmp as Store>::HrmpChannelFeeFactor::contains_key(&channel_id));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
 #[test]
 fn hrmp_mqc_head_fixture() {
 	let para_a = 2000.into();
@@ -447,8 +533,8 @@ fn verify_externally_accessible() {
 
 #[test]
 fn charging_deposits() {
-	let para_a = 32.into();
-	let para_b = 64.into();
+	let para_a = 2032.into();
+	let para_b = 2064.into();
 
 	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
 		register_parachain_with_balance(para_a, 0);
@@ -477,8 +563,8 @@ fn charging_deposits() {
 
 #[test]
 fn refund_deposit_on_normal_closure() {
-	let para_a = 32.into();
-	let para_b = 64.into();
+	let para_a = 2032.into();
+	let para_b = 2064.into();
 
 	let mut genesis = GenesisConfigBuilder::default();
 	genesis.hrmp_sender_deposit = 20;
@@ -502,10 +588,66 @@ fn refund_deposit_on_normal_closure() {
 	});
 }
 
+#[test]
+fn refund_deposit_after_draining_closed_channel() {
+	let para_a = 2032.into();
+	let para_b = 2064.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain_with_balance(para_a, 100);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		run_to_block(8, Some(vec![8]));
+
+		// A sends a message to B and B closes the channel before processing it.
+		let msgs = vec![OutboundHrmpMessage { recipient: para_b, data: b"knock".to_vec() }];
+		let config = Configuration::config();
+		assert!(Hrmp::check_outbound_hrmp(&config, para_a, &msgs).is_ok());
+		let _ = Hrmp::queue_outbound_hrmp(para_a, msgs.clone());
+		let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+		Hrmp::close_channel(para_b, channel_id.clone()).unwrap();
+
+		// After the session change the channel no longer accepts messages, but it is kept
+		// around, along with the deposits, until the pending message is processed.
+		run_to_block(10, Some(vec![10]));
+		assert!(channel_exists(para_a, para_b));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event ==
+				MockEvent::Hrmp(Event::ChannelDraining(channel_id.clone()))));
+		assert!(matches!(
+			Hrmp::check_outbound_hrmp(&config, para_a, &msgs),
+			Err(OutboundHrmpAcceptanceErr::ChannelClosing { idx: 0, .. })
+		));
+		assert_eq!(
+			Hrmp::close_channel(para_a, channel_id.clone()),
+			Err(Error::<Test>::CloseHrmpChannelAlreadyUnderway)
+		);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account()), 80);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_b.into_account()), 95);
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// Once B processed the message the channel is removed and the deposits are returned.
+		run_to_block(11, None);
+		let _ = Hrmp::prune_hrmp(para_b, 8);
+		assert!(!channel_exists(para_a, para_b));
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account()), 100);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_b.into_account()), 110);
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::ChannelRemoved(channel_id.clone(), 20, 15))));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
 #[test]
 fn refund_deposit_on_offboarding() {
-	let para_a = 32.into();
-	let para_b = 64.into();
+	let para_a = 2032.into();
+	let para_b = 2064.into();
 
 	let mut genesis = GenesisConfigBuilder::default();
 	genesis.hrmp_sender_deposit = 20;
@@ -538,8 +680,8 @@ fn refund_deposit_on_offboarding() {
 
 #[test]
 fn no_dangling_open_requests() {
-	let para_a = 32.into();
-	let para_b = 64.into();
+	let para_a = 2032.into();
+	let para_b = 2064.into();
 
 	let mut genesis = GenesisConfigBuilder::default();
 	genesis.hrmp_sender_deposit = 20;
@@ -573,8 +715,8 @@ fn no_dangling_open_requests() {
 
 #[test]
 fn cancel_pending_open_channel_request() {
-	let para_a = 32.into();
-	let para_b = 64.into();
+	let para_a = 2032.into();
+	let para_b = 2064.into();
 
 	let mut genesis = GenesisConfigBuilder::default();
 	genesis.hrmp_sender_deposit = 20;
@@ -599,3 +741,96 @@ fn cancel_pending_open_channel_request() {
 		Hrmp::assert_storage_consistency_exhaustive();
 	});
 }
+
+#[test]
+fn establish_system_channel_works() {
+	let para_a = 1.into();
+	let para_a_origin: crate::Origin = 1.into();
+	let para_b = 3.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		// Both parachains have no funds to leave deposits with.
+		register_parachain_with_balance(para_a, 0);
+		register_parachain_with_balance(para_b, 0);
+		run_to_block(5, Some(vec![4, 5]));
+
+		assert_ok!(Hrmp::establish_system_channel(para_a_origin.into(), para_a, para_b));
+		Hrmp::assert_storage_consistency_exhaustive();
+		// The channel is open right away, without waiting for a session change.
+		assert!(channel_exists(para_a, para_b));
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::SystemChannelEstablished(para_a, para_b, 2, 8))));
+
+		assert_noop!(
+			Hrmp::establish_system_channel(crate::mock::Origin::root(), para_a, para_b),
+			Error::<Test>::OpenHrmpChannelAlreadyExists,
+		);
+
+		let channel = <Hrmp as Store>::HrmpChannels::get(&HrmpChannelId {
+			sender: para_a,
+			recipient: para_b,
+		})
+		.unwrap();
+		assert_eq!((channel.sender_deposit, channel.recipient_deposit), (0, 0));
+
+		// Closing the channel doesn't touch any balance.
+		Hrmp::close_channel(para_b, HrmpChannelId { sender: para_a, recipient: para_b }).unwrap();
+		run_to_block(8, Some(vec![8]));
+		assert!(!channel_exists(para_a, para_b));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn establish_system_channel_checks_origin_and_paras() {
+	let system_para = 1.into();
+	let system_para_origin: crate::Origin = 1.into();
+	let other_system_para = 3.into();
+	let other_system_para_origin: crate::Origin = 3.into();
+	let user_para = 2000.into();
+	let user_para_origin: crate::Origin = 2000.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(system_para);
+		register_parachain(other_system_para);
+		register_parachain(user_para);
+		run_to_block(5, Some(vec![4, 5]));
+
+		assert_noop!(
+			Hrmp::establish_system_channel(
+				crate::mock::Origin::signed(1),
+				system_para,
+				other_system_para
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Hrmp::establish_system_channel(user_para_origin.into(), system_para, other_system_para),
+			Error::<Test>::ChannelCreationNotAuthorized,
+		);
+		assert_noop!(
+			Hrmp::establish_system_channel(
+				system_para_origin.clone().into(),
+				system_para,
+				user_para
+			),
+			Error::<Test>::ChannelCreationNotAuthorized,
+		);
+		assert_noop!(
+			Hrmp::establish_system_channel(crate::mock::Origin::root(), user_para, system_para),
+			Error::<Test>::ChannelCreationNotAuthorized,
+		);
+		assert_noop!(
+			Hrmp::establish_system_channel(system_para_origin.into(), system_para, 5.into()),
+			Error::<Test>::OpenHrmpChannelInvalidRecipient,
+		);
+
+		// Either of the two system parachains may ask for the channel.
+		assert_ok!(Hrmp::establish_system_channel(
+			other_system_para_origin.into(),
+			system_para,
+			other_system_para
+		));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
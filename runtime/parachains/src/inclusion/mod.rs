@@ -21,8 +21,11 @@
 //! to included.
 
 use crate::{
-	configuration, disputes, dmp, hrmp, paras, paras_inherent::DisputedBitfield,
-	scheduler::CoreAssignment, shared, ump,
+	configuration, disputes, dmp, hrmp, paras,
+	paras_inherent::DisputedBitfield,
+	scheduler::CoreAssignment,
+	shared::{self, AllowedRelayParentsTracker},
+	ump,
 };
 use bitvec::{order::Lsb0 as BitOrderLsb0, vec::BitVec};
 use frame_support::pallet_prelude::*;
@@ -30,11 +33,11 @@ use parity_scale_codec::{Decode, Encode};
 use primitives::v2::{
 	AvailabilityBitfield, BackedCandidate, CandidateCommitments, CandidateDescriptor,
 	CandidateHash, CandidateReceipt, CommittedCandidateReceipt, CoreIndex, GroupIndex, Hash,
-	HeadData, Id as ParaId, SigningContext, UncheckedSignedAvailabilityBitfields, ValidatorId,
-	ValidatorIndex, ValidityAttestation,
+	HeadData, Id as ParaId, SessionIndex, SigningContext, UncheckedSignedAvailabilityBitfields,
+	ValidatorId, ValidatorIndex, ValidityAttestation,
 };
 use scale_info::TypeInfo;
-use sp_runtime::{traits::One, DispatchError};
+use sp_runtime::DispatchError;
 use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 pub use pallet::*;
@@ -115,6 +118,14 @@ impl<H, N> CandidatePendingAvailability<H, N> {
 		&self.descriptor
 	}
 
+	/// Get the block number of the relay-parent of the receipt.
+	pub(crate) fn relay_parent_number(&self) -> N
+	where
+		N: Clone,
+	{
+		self.relay_parent_number.clone()
+	}
+
 	#[cfg(any(feature = "runtime-benchmarks", test))]
 	pub(crate) fn new(
 		core: CoreIndex,
@@ -206,12 +217,12 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// A candidate was backed. `[candidate, head_data]`
+		/// A candidate was backed. `[candidate, head_data, core_index, group_index]`
 		CandidateBacked(CandidateReceipt<T::Hash>, HeadData, CoreIndex, GroupIndex),
-		/// A candidate was included. `[candidate, head_data]`
+		/// A candidate was included. `[candidate, head_data, core_index, group_index]`
 		CandidateIncluded(CandidateReceipt<T::Hash>, HeadData, CoreIndex, GroupIndex),
-		/// A candidate timed out. `[candidate, head_data]`
-		CandidateTimedOut(CandidateReceipt<T::Hash>, HeadData, CoreIndex),
+		/// A candidate timed out. `[candidate, head_data, core_index, group_index]`
+		CandidateTimedOut(CandidateReceipt<T::Hash>, HeadData, CoreIndex, GroupIndex),
 	}
 
 	#[pallet::error]
@@ -222,7 +233,7 @@ pub mod pallet {
 		UnsortedOrDuplicateDisputeStatementSet,
 		/// Backed candidates are out of order (core index) or contain duplicates.
 		UnsortedOrDuplicateBackedCandidates,
-		/// A different relay parent was provided compared to the on-chain stored one.
+		/// A backed candidate's relay parent is not one of the allowed relay parents.
 		UnexpectedRelayParent,
 		/// Availability bitfield has unexpected size.
 		WrongBitfieldSize,
@@ -248,8 +259,8 @@ pub mod pallet {
 		PrematureCodeUpgrade,
 		/// Output code is too large
 		NewCodeTooLarge,
-		/// Candidate not in parent context.
-		CandidateNotInParentContext,
+		/// The candidate's relay parent is not one of the allowed relay parents.
+		DisallowedRelayParent,
 		/// Invalid group index in core assignment.
 		InvalidGroupIndex,
 		/// Insufficient (non-majority) backing.
@@ -312,12 +323,14 @@ impl<T: Config> Pallet<T> {
 	/// Handle an incoming session change.
 	pub(crate) fn initializer_on_new_session(
 		_notification: &crate::initializer::SessionChangeNotification<T::BlockNumber>,
-	) {
+	) -> Weight {
 		// unlike most drain methods, drained elements are not cleared on `Drop` of the iterator
 		// and require consumption.
-		for _ in <PendingAvailabilityCommitments<T>>::drain() {}
-		for _ in <PendingAvailability<T>>::drain() {}
-		for _ in <AvailabilityBitfields<T>>::drain() {}
+		let drained = <PendingAvailabilityCommitments<T>>::drain().count() +
+			<PendingAvailability<T>>::drain().count() +
+			<AvailabilityBitfields<T>>::drain().count();
+
+		T::DbWeight::get().reads_writes(drained as Weight, drained as Weight)
 	}
 
 	/// Extract the freed cores based on cores that became available.
@@ -460,13 +473,13 @@ impl<T: Config> Pallet<T> {
 		Ok(freed_cores)
 	}
 
-	/// Process candidates that have been backed. Provide the relay storage root, a set of candidates
-	/// and scheduled cores.
+	/// Process candidates that have been backed. Provide the relay parents the candidates may be
+	/// built on, a set of candidates and scheduled cores.
 	///
 	/// Both should be sorted ascending by core index, and the candidates should be a subset of
 	/// scheduled cores. If these conditions are not met, the execution of the function fails.
 	pub(crate) fn process_candidates<GV>(
-		parent_storage_root: T::Hash,
+		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, T::BlockNumber>,
 		candidates: Vec<BackedCandidate<T::Hash>>,
 		scheduled: Vec<CoreAssignment>,
 		group_validators: GV,
@@ -481,13 +494,10 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let validators = shared::Pallet::<T>::active_validator_keys();
-		let parent_hash = <frame_system::Pallet<T>>::parent_hash();
+		let session_index = shared::Pallet::<T>::session_index();
 
-		// At the moment we assume (and in fact enforce, below) that the relay-parent is always one
-		// before of the block where we include a candidate (i.e. this code path).
 		let now = <frame_system::Pallet<T>>::block_number();
-		let relay_parent_number = now - One::one();
-		let check_ctx = CandidateCheckContext::<T>::new(now, relay_parent_number);
+		let check_ctx = CandidateCheckContext::<T>::new(now);
 
 		// Collect candidate receipts with backers.
 		let mut candidate_receipt_with_backing_validator_indices =
@@ -509,9 +519,6 @@ impl<T: Config> Pallet<T> {
 				Ok(())
 			};
 
-			let signing_context =
-				SigningContext { parent_hash, session_index: shared::Pallet::<T>::session_index() };
-
 			// We combine an outer loop over candidates with an inner loop over the scheduled,
 			// where each iteration of the outer loop picks up at the position
 			// in scheduled just after the past iteration left off.
@@ -525,9 +532,10 @@ impl<T: Config> Pallet<T> {
 			'next_backed_candidate: for (candidate_idx, backed_candidate) in
 				candidates.iter().enumerate()
 			{
-				match check_ctx.verify_backed_candidate(
-					parent_hash,
-					parent_storage_root,
+				let relay_parent = backed_candidate.descriptor().relay_parent;
+				let relay_parent_number = match check_ctx.verify_backed_candidate(
+					allowed_relay_parents,
+					session_index,
 					candidate_idx,
 					backed_candidate,
 				)? {
@@ -536,7 +544,7 @@ impl<T: Config> Pallet<T> {
 							target: LOG_TARGET,
 							"Failed to create PVD for candidate {} on relay parent {:?}",
 							candidate_idx,
-							parent_hash,
+							relay_parent,
 						);
 						// We don't want to error out here because it will
 						// brick the relay-chain. So we return early without
@@ -544,7 +552,10 @@ impl<T: Config> Pallet<T> {
 						return Ok(ProcessedCandidates::default())
 					},
 					Ok(rpn) => rpn,
-				}
+				};
+
+				// The candidate is signed in the context of its own relay parent.
+				let signing_context = SigningContext { parent_hash: relay_parent, session_index };
 
 				let para_id = backed_candidate.descriptor().para_id;
 				let mut backers = bitvec::bitvec![u8, BitOrderLsb0; 0; validators.len()];
@@ -624,6 +635,7 @@ impl<T: Config> Pallet<T> {
 							assignment.core,
 							backers,
 							assignment.group_idx,
+							relay_parent_number,
 						));
 						continue 'next_backed_candidate
 					}
@@ -645,8 +657,8 @@ impl<T: Config> Pallet<T> {
 
 		// one more sweep for actually writing to storage.
 		let core_indices =
-			core_indices_and_backers.iter().map(|&(ref c, _, _)| c.clone()).collect();
-		for (candidate, (core, backers, group)) in
+			core_indices_and_backers.iter().map(|&(ref c, _, _, _)| c.clone()).collect();
+		for (candidate, (core, backers, group, relay_parent_number)) in
 			candidates.into_iter().zip(core_indices_and_backers)
 		{
 			let para_id = candidate.descriptor().para_id;
@@ -698,10 +710,11 @@ impl<T: Config> Pallet<T> {
 		// `relay_parent_number` is equal to `now`.
 		let now = <frame_system::Pallet<T>>::block_number();
 		let relay_parent_number = now;
-		let check_ctx = CandidateCheckContext::<T>::new(now, relay_parent_number);
+		let check_ctx = CandidateCheckContext::<T>::new(now);
 
 		if let Err(err) = check_ctx.check_validation_outputs(
 			para_id,
+			relay_parent_number,
 			&validation_outputs.head_data,
 			&validation_outputs.new_validation_code,
 			validation_outputs.processed_downward_messages,
@@ -827,6 +840,7 @@ impl<T: Config> Pallet<T> {
 					candidate,
 					commitments.head_data,
 					pending.core,
+					pending.backing_group,
 				));
 			}
 		}
@@ -938,7 +952,6 @@ impl<BlockNumber> AcceptanceCheckErr<BlockNumber> {
 pub(crate) struct CandidateCheckContext<T: Config> {
 	config: configuration::HostConfiguration<T::BlockNumber>,
 	now: T::BlockNumber,
-	relay_parent_number: T::BlockNumber,
 }
 
 /// An error indicating that creating Persisted Validation Data failed
@@ -946,34 +959,40 @@ pub(crate) struct CandidateCheckContext<T: Config> {
 pub(crate) struct FailedToCreatePVD;
 
 impl<T: Config> CandidateCheckContext<T> {
-	pub(crate) fn new(now: T::BlockNumber, relay_parent_number: T::BlockNumber) -> Self {
-		Self { config: <configuration::Pallet<T>>::config(), now, relay_parent_number }
+	pub(crate) fn new(now: T::BlockNumber) -> Self {
+		Self { config: <configuration::Pallet<T>>::config(), now }
 	}
 
 	/// Execute verification of the candidate.
 	///
 	/// Assures:
-	///  * correct expected relay parent reference
+	///  * the relay parent is one of the `allowed_relay_parents` of `session_index`
 	///  * collator signature check passes
 	///  * code hash of commitments matches current code hash
 	///  * para head in the descriptor and commitments match
+	///
+	/// Returns the number of the candidate's relay parent.
 	pub(crate) fn verify_backed_candidate(
 		&self,
-		parent_hash: <T as frame_system::Config>::Hash,
-		parent_storage_root: T::Hash,
+		allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, T::BlockNumber>,
+		session_index: SessionIndex,
 		candidate_idx: usize,
 		backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>,
-	) -> Result<Result<(), FailedToCreatePVD>, Error<T>> {
+	) -> Result<Result<T::BlockNumber, FailedToCreatePVD>, Error<T>> {
 		let para_id = backed_candidate.descriptor().para_id;
-		let now = <frame_system::Pallet<T>>::block_number();
-		let relay_parent_number = now - One::one();
+		let relay_parent = backed_candidate.descriptor().relay_parent;
+
+		// we require that the candidate is built on one of the allowed relay parents.
+		let (relay_parent_storage_root, relay_parent_number) = allowed_relay_parents
+			.acquire_info(relay_parent, session_index)
+			.ok_or(Error::<T>::DisallowedRelayParent)?;
 
 		{
 			// this should never fail because the para is registered
 			let persisted_validation_data = match crate::util::make_persisted_validation_data::<T>(
 				para_id,
 				relay_parent_number,
-				parent_storage_root,
+				relay_parent_storage_root,
 			) {
 				Some(l) => l,
 				None => return Ok(Err(FailedToCreatePVD)),
@@ -987,11 +1006,6 @@ impl<T: Config> CandidateCheckContext<T> {
 			);
 		}
 
-		// we require that the candidate is in the context of the parent block.
-		ensure!(
-			backed_candidate.descriptor().relay_parent == parent_hash,
-			Error::<T>::CandidateNotInParentContext,
-		);
 		ensure!(
 			backed_candidate.descriptor().check_collator_signature().is_ok(),
 			Error::<T>::NotCollatorSigned,
@@ -1013,6 +1027,7 @@ impl<T: Config> CandidateCheckContext<T> {
 
 		if let Err(err) = self.check_validation_outputs(
 			para_id,
+			relay_parent_number,
 			&backed_candidate.candidate.commitments.head_data,
 			&backed_candidate.candidate.commitments.new_validation_code,
 			backed_candidate.candidate.commitments.processed_downward_messages,
@@ -1029,7 +1044,7 @@ impl<T: Config> CandidateCheckContext<T> {
 			);
 			Err(err.strip_into_dispatch_err::<T>())?;
 		};
-		Ok(Ok(()))
+		Ok(Ok(relay_parent_number))
 	}
 
 	/// Check the given outputs after candidate validation on whether it passes the acceptance
@@ -1037,6 +1052,7 @@ impl<T: Config> CandidateCheckContext<T> {
 	fn check_validation_outputs(
 		&self,
 		para_id: ParaId,
+		relay_parent_number: T::BlockNumber,
 		head_data: &HeadData,
 		new_validation_code: &Option<primitives::v2::ValidationCode>,
 		processed_downward_messages: u32,
@@ -1064,7 +1080,7 @@ impl<T: Config> CandidateCheckContext<T> {
 		// check if the candidate passes the messaging acceptance criteria
 		<dmp::Pallet<T>>::check_processed_downward_messages(para_id, processed_downward_messages)?;
 		<ump::Pallet<T>>::check_upward_messages(&self.config, para_id, upward_messages)?;
-		<hrmp::Pallet<T>>::check_hrmp_watermark(para_id, self.relay_parent_number, hrmp_watermark)?;
+		<hrmp::Pallet<T>>::check_hrmp_watermark(para_id, relay_parent_number, hrmp_watermark)?;
 		<hrmp::Pallet<T>>::check_outbound_hrmp(&self.config, para_id, horizontal_messages)?;
 
 		Ok(())
@@ -19,8 +19,8 @@ use crate::{
 	configuration::HostConfiguration,
 	initializer::SessionChangeNotification,
 	mock::{
-		new_test_ext, Configuration, MockGenesisConfig, ParaInclusion, Paras, ParasShared, System,
-		Test,
+		assert_last_event, new_test_ext, Configuration, Event as MockEvent, MockGenesisConfig,
+		ParaInclusion, Paras, ParasShared, System, Test,
 	},
 	paras::ParaGenesisArgs,
 	paras_inherent::DisputedBitfield,
@@ -30,6 +30,10 @@ use assert_matches::assert_matches;
 use frame_support::assert_noop;
 use futures::executor::block_on;
 use keyring::Sr25519Keyring;
+use polkadot_primitives_test_helpers::{
+	dummy_candidate_receipt, dummy_collator, dummy_collator_signature, dummy_hash,
+	dummy_validation_code,
+};
 use primitives::v2::{
 	BlockNumber, CandidateCommitments, CandidateDescriptor, CollatorId,
 	CompactStatement as Statement, Hash, SignedAvailabilityBitfield, SignedStatement,
@@ -39,10 +43,6 @@ use primitives::v2::{
 use sc_keystore::LocalKeystore;
 use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
 use std::sync::Arc;
-use test_helpers::{
-	dummy_candidate_receipt, dummy_collator, dummy_collator_signature, dummy_hash,
-	dummy_validation_code,
-};
 
 fn default_config() -> HostConfiguration<BlockNumber> {
 	let mut config = HostConfiguration::default();
@@ -294,6 +294,18 @@ impl TestCandidateBuilder {
 	}
 }
 
+/// The allowed relay parents of candidates built on top of the parent block, as noted by the
+/// paras inherent.
+fn default_allowed_relay_parent_tracker() -> AllowedRelayParentsTracker<Hash, BlockNumber> {
+	let mut allowed = AllowedRelayParentsTracker::default();
+
+	let relay_parent = System::parent_hash();
+	let parent_number = System::block_number().saturating_sub(1);
+
+	allowed.update(relay_parent, Hash::zero(), ParasShared::session_index(), parent_number, 1);
+	allowed
+}
+
 pub(crate) fn make_vdata_hash(para_id: ParaId) -> Option<Hash> {
 	let relay_parent_number = <frame_system::Pallet<Test>>::block_number() - 1;
 	let persisted_validation_data = crate::util::make_persisted_validation_data::<Test>(
@@ -336,7 +348,7 @@ fn collect_pending_cleans_up_pending() {
 			CandidatePendingAvailability {
 				core: CoreIndex::from(1),
 				hash: default_candidate.hash(),
-				descriptor: default_candidate.descriptor,
+				descriptor: default_candidate.descriptor.clone(),
 				availability_votes: default_availability_votes(),
 				relay_parent_number: 0,
 				backed_in_number: 0,
@@ -344,7 +356,10 @@ fn collect_pending_cleans_up_pending() {
 				backing_group: GroupIndex::from(1),
 			},
 		);
-		PendingAvailabilityCommitments::<Test>::insert(chain_b, default_candidate.commitments);
+		PendingAvailabilityCommitments::<Test>::insert(
+			chain_b,
+			default_candidate.commitments.clone(),
+		);
 
 		run_to_block(5, |_| None);
 
@@ -353,8 +368,19 @@ fn collect_pending_cleans_up_pending() {
 		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_some());
 		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_b).is_some());
 
+		let timed_out_candidate = CandidateReceipt {
+			descriptor: default_candidate.descriptor.clone(),
+			commitments_hash: default_candidate.commitments.hash(),
+		};
+
 		ParaInclusion::collect_pending(|core, _since| core == CoreIndex::from(0));
 
+		assert_last_event(MockEvent::ParaInclusion(Event::CandidateTimedOut(
+			timed_out_candidate,
+			default_candidate.commitments.head_data.clone(),
+			CoreIndex::from(0),
+			GroupIndex::from(0),
+		)));
 		assert!(<PendingAvailability<Test>>::get(&chain_a).is_none());
 		assert!(<PendingAvailability<Test>>::get(&chain_b).is_some());
 		assert!(<PendingAvailabilityCommitments<Test>>::get(&chain_a).is_none());
@@ -982,7 +1008,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_b_assignment.clone()],
 					&group_validators,
@@ -1037,7 +1063,7 @@ fn candidate_checks() {
 			// out-of-order manifests as unscheduled.
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed_b, backed_a],
 					vec![chain_a_assignment.clone(), chain_b_assignment.clone()],
 					&group_validators,
@@ -1070,7 +1096,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1079,7 +1105,7 @@ fn candidate_checks() {
 			);
 		}
 
-		// candidate not in parent context.
+		// candidate not built on an allowed relay parent.
 		{
 			let wrong_parent_hash = Hash::repeat_byte(222);
 			assert!(System::parent_hash() != wrong_parent_hash);
@@ -1105,12 +1131,12 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
 				),
-				Error::<Test>::CandidateNotInParentContext
+				Error::<Test>::DisallowedRelayParent
 			);
 		}
 
@@ -1140,7 +1166,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![
 						chain_a_assignment.clone(),
@@ -1182,7 +1208,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![thread_a_assignment.clone()],
 					&group_validators,
@@ -1232,7 +1258,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1272,7 +1298,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1316,7 +1342,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1350,7 +1376,7 @@ fn candidate_checks() {
 
 			assert_eq!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1385,7 +1411,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1420,7 +1446,7 @@ fn candidate_checks() {
 
 			assert_noop!(
 				ParaInclusion::process_candidates(
-					Default::default(),
+					&default_allowed_relay_parent_tracker(),
 					vec![backed],
 					vec![chain_a_assignment.clone()],
 					&group_validators,
@@ -1585,7 +1611,7 @@ fn backing_works() {
 			core_indices: occupied_cores,
 			candidate_receipt_with_backing_validator_indices,
 		} = ParaInclusion::process_candidates(
-			Default::default(),
+			&default_allowed_relay_parent_tracker(),
 			backed_candidates.clone(),
 			vec![
 				chain_a_assignment.clone(),
@@ -1786,7 +1812,7 @@ fn can_include_candidate_with_ok_code_upgrade() {
 
 		let ProcessedCandidates { core_indices: occupied_cores, .. } =
 			ParaInclusion::process_candidates(
-				Default::default(),
+				&default_allowed_relay_parent_tracker(),
 				vec![backed_a],
 				vec![chain_a_assignment.clone()],
 				&group_validators,
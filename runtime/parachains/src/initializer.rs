@@ -72,6 +72,25 @@ impl<BlockNumber: Default + From<u32>> Default for SessionChangeNotification<Blo
 	}
 }
 
+/// The parachains modules notified of a session change, in the order in which they are notified.
+///
+/// Later modules may rely on the state left behind by earlier ones, e.g. the scheduler assigns
+/// cores to the paras onboarded by the paras module and the session info module records the
+/// validator groups formed by the scheduler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionChangeHandler {
+	Configuration,
+	Shared,
+	Paras,
+	Scheduler,
+	Inclusion,
+	SessionInfo,
+	Disputes,
+	Dmp,
+	Ump,
+	Hrmp,
+}
+
 #[derive(Encode, Decode, TypeInfo)]
 struct BufferedSessionChange {
 	validators: Vec<ValidatorId>,
@@ -143,6 +162,13 @@ pub mod pallet {
 	pub(super) type BufferedSessionChanges<T: Config> =
 		StorageValue<_, Vec<BufferedSessionChange>, ValueQuery>;
 
+	/// The weight consumed by the last session change.
+	///
+	/// A session change is only applied at the end of the block, so this is the weight reserved
+	/// for it in `on_initialize`.
+	#[pallet::storage]
+	pub(super) type LastSessionChangeWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(now: T::BlockNumber) -> Weight {
@@ -167,9 +193,19 @@ pub mod pallet {
 				ump::Pallet::<T>::initializer_initialize(now) +
 				hrmp::Pallet::<T>::initializer_initialize(now);
 
+			// A session change buffered earlier in this block is applied in `on_finalize`, when
+			// its weight can no longer be accounted for. Reserve the weight of the last one.
+			let session_change_weight = if BufferedSessionChanges::<T>::exists() {
+				LastSessionChangeWeight::<T>::get()
+			} else {
+				0
+			};
+
 			HasInitialized::<T>::set(Some(()));
 
 			total_weight
+				.saturating_add(session_change_weight)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
 		}
 
 		fn on_finalize(now: T::BlockNumber) {
@@ -192,7 +228,8 @@ pub mod pallet {
 			if let Some(BufferedSessionChange { session_index, validators, queued }) =
 				BufferedSessionChanges::<T>::take().pop()
 			{
-				Self::apply_new_session(session_index, validators, queued);
+				let handled = Self::apply_new_session(session_index, validators, queued);
+				LastSessionChangeWeight::<T>::put(Self::session_change_weight(&handled));
 			}
 
 			HasInitialized::<T>::take();
@@ -220,11 +257,17 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Notify all parachains modules of the session change.
+	///
+	/// Returns the notified modules in the order they were notified, along with the weight each
+	/// of them consumed.
 	fn apply_new_session(
 		session_index: SessionIndex,
 		all_validators: Vec<ValidatorId>,
 		queued: Vec<ValidatorId>,
-	) {
+	) -> Vec<(SessionChangeHandler, Weight)> {
+		let mut handled = Vec::with_capacity(10);
+
 		let random_seed = {
 			let mut buf = [0u8; 32];
 			// TODO: audit usage of randomness API
@@ -235,16 +278,18 @@ impl<T: Config> Pallet<T> {
 			buf
 		};
 
-		let configuration::SessionChangeOutcome { prev_config, new_config } =
+		let configuration::SessionChangeOutcome { prev_config, new_config, weight } =
 			configuration::Pallet::<T>::initializer_on_new_session(&session_index);
+		handled.push((SessionChangeHandler::Configuration, weight));
 		let new_config = new_config.unwrap_or_else(|| prev_config.clone());
 
-		let validators = shared::Pallet::<T>::initializer_on_new_session(
+		let (validators, weight) = shared::Pallet::<T>::initializer_on_new_session(
 			session_index,
 			random_seed.clone(),
 			&new_config,
 			all_validators,
 		);
+		handled.push((SessionChangeHandler::Shared, weight));
 
 		let notification = SessionChangeNotification {
 			validators,
@@ -255,14 +300,44 @@ impl<T: Config> Pallet<T> {
 			session_index,
 		};
 
-		let outgoing_paras = paras::Pallet::<T>::initializer_on_new_session(&notification);
-		scheduler::Pallet::<T>::initializer_on_new_session(&notification);
-		inclusion::Pallet::<T>::initializer_on_new_session(&notification);
-		session_info::Pallet::<T>::initializer_on_new_session(&notification);
-		T::DisputesHandler::initializer_on_new_session(&notification);
-		dmp::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras);
-		ump::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras);
-		hrmp::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras);
+		let (outgoing_paras, weight) =
+			paras::Pallet::<T>::initializer_on_new_session(&notification);
+		handled.push((SessionChangeHandler::Paras, weight));
+		handled.push((
+			SessionChangeHandler::Scheduler,
+			scheduler::Pallet::<T>::initializer_on_new_session(&notification),
+		));
+		handled.push((
+			SessionChangeHandler::Inclusion,
+			inclusion::Pallet::<T>::initializer_on_new_session(&notification),
+		));
+		handled.push((
+			SessionChangeHandler::SessionInfo,
+			session_info::Pallet::<T>::initializer_on_new_session(&notification),
+		));
+		handled.push((
+			SessionChangeHandler::Disputes,
+			T::DisputesHandler::initializer_on_new_session(&notification),
+		));
+		handled.push((
+			SessionChangeHandler::Dmp,
+			dmp::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras),
+		));
+		handled.push((
+			SessionChangeHandler::Ump,
+			ump::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras),
+		));
+		handled.push((
+			SessionChangeHandler::Hrmp,
+			hrmp::Pallet::<T>::initializer_on_new_session(&notification, &outgoing_paras),
+		));
+
+		handled
+	}
+
+	/// The total weight consumed by a session change.
+	fn session_change_weight(handled: &[(SessionChangeHandler, Weight)]) -> Weight {
+		handled.iter().fold(0 as Weight, |acc, (_, weight)| acc.saturating_add(*weight))
 	}
 
 	/// Should be called when a new session occurs. Buffers the session notification to be applied
@@ -305,6 +380,27 @@ impl<T: Config> Pallet<T> {
 	{
 		Self::on_new_session(changed, session_index, validators, queued)
 	}
+
+	/// Apply a session change to all parachains modules right away, instead of buffering it until
+	/// the end of the block. If `queued` is `None`, the `validators` are considered queued.
+	///
+	/// This allows integration tests to drive session changes without `pallet_session`. Returns
+	/// the total weight consumed, which is also registered for the current block.
+	#[cfg(any(test, feature = "test-helpers"))]
+	pub fn test_helpers_apply_new_session(
+		session_index: SessionIndex,
+		validators: Vec<ValidatorId>,
+		queued: Option<Vec<ValidatorId>>,
+	) -> Weight {
+		let queued = queued.unwrap_or_else(|| validators.clone());
+		let handled = Self::apply_new_session(session_index, validators, queued);
+		let weight = Self::session_change_weight(&handled);
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			weight,
+			frame_support::weights::DispatchClass::Mandatory,
+		);
+		weight
+	}
 }
 
 impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
@@ -16,10 +16,11 @@
 
 use super::*;
 use crate::mock::{
-	new_test_ext, Configuration, Dmp, Initializer, MockGenesisConfig, Paras, SessionInfo, System,
+	new_test_ext, Configuration, Dmp, Initializer, MockGenesisConfig, Paras, ParasShared,
+	SessionInfo, System,
 };
+use polkadot_primitives_test_helpers::dummy_validation_code;
 use primitives::v2::{HeadData, Id as ParaId};
-use test_helpers::dummy_validation_code;
 
 use frame_support::{
 	assert_ok,
@@ -129,3 +130,67 @@ fn scheduled_cleanup_performed() {
 		assert!(!Dmp::dmq_contents(c).is_empty());
 	});
 }
+
+#[test]
+fn session_change_notifies_modules_in_order() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let handled = Initializer::apply_new_session(1, vec![], vec![]);
+
+		assert_eq!(
+			handled.iter().map(|(handler, _)| *handler).collect::<Vec<_>>(),
+			vec![
+				SessionChangeHandler::Configuration,
+				SessionChangeHandler::Shared,
+				SessionChangeHandler::Paras,
+				SessionChangeHandler::Scheduler,
+				SessionChangeHandler::Inclusion,
+				SessionChangeHandler::SessionInfo,
+				SessionChangeHandler::Disputes,
+				SessionChangeHandler::Dmp,
+				SessionChangeHandler::Ump,
+				SessionChangeHandler::Hrmp,
+			],
+		);
+	});
+}
+
+#[test]
+fn session_change_weight_is_reserved_on_initialize() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let new_session = |index| {
+			Initializer::on_new_session(
+				false,
+				index,
+				Vec::new().into_iter(),
+				Some(Vec::new().into_iter()),
+			)
+		};
+
+		// the session pallet buffers the change before the initializer is initialized.
+		new_session(1);
+		Initializer::on_initialize(1);
+		Initializer::on_finalize(1);
+		let session_change_weight = <Initializer as Store>::LastSessionChangeWeight::get();
+		assert!(session_change_weight > 0);
+
+		let without_change = Initializer::on_initialize(2);
+		Initializer::on_finalize(2);
+
+		new_session(2);
+		let with_change = Initializer::on_initialize(3);
+		assert_eq!(with_change, without_change + session_change_weight);
+		Initializer::on_finalize(3);
+	});
+}
+
+#[test]
+fn test_helpers_apply_new_session_applies_immediately() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let weight = Initializer::test_helpers_apply_new_session(1, vec![], None);
+
+		assert!(weight > 0);
+		assert!(<Initializer as Store>::BufferedSessionChanges::get().is_empty());
+		assert_eq!(ParasShared::session_index(), 1);
+		assert!(SessionInfo::session_info(1).is_some());
+	});
+}
@@ -29,7 +29,9 @@ pub mod dmp;
 pub mod hrmp;
 pub mod inclusion;
 pub mod initializer;
+pub mod messaging_metrics;
 pub mod metrics;
+pub mod on_demand;
 pub mod origin;
 pub mod paras;
 pub mod paras_inherent;
@@ -50,7 +52,16 @@ mod mock;
 
 pub use origin::{ensure_parachain, Origin};
 pub use paras::ParaLifecycle;
-use primitives::v2::Id as ParaId;
+use primitives::v2::{Id as ParaId, ValidationCode};
+use sp_runtime::FixedU128;
+
+/// Trait for tracking message delivery fees on a transport protocol.
+pub trait FeeTracker {
+	/// Return the factor by which the delivery fee for messages to `para` should be multiplied.
+	///
+	/// The factor is greater than one only while the queue to `para` is congested.
+	fn get_fee_factor(para: ParaId) -> FixedU128;
+}
 
 /// Schedule a para to be initialized at the start of the next session with the given genesis data.
 ///
@@ -69,6 +80,24 @@ pub fn schedule_para_cleanup<T: paras::Config>(id: primitives::v2::Id) -> Result
 	<paras::Pallet<T>>::schedule_para_cleanup(id).map_err(|_| ())
 }
 
+/// Schedule a code upgrade for a para, as if it was signalled in the current block.
+///
+/// Fails if the para doesn't exist, already has an upgrade pending or is already validated by
+/// `new_code`.
+pub fn schedule_code_upgrade<T: paras::Config>(
+	id: ParaId,
+	new_code: ValidationCode,
+) -> Result<(), ()> {
+	if !paras::Pallet::<T>::can_schedule_code_upgrade(id, &new_code) {
+		return Err(())
+	}
+
+	let config = configuration::Pallet::<T>::config();
+	let now = frame_system::Pallet::<T>::block_number();
+	paras::Pallet::<T>::schedule_code_upgrade(id, new_code, now, &config);
+	Ok(())
+}
+
 /// Schedule a parathread to be upgraded to a parachain.
 pub fn schedule_parathread_upgrade<T: paras::Config>(id: ParaId) -> Result<(), ()> {
 	paras::Pallet::<T>::schedule_parathread_upgrade(id).map_err(|_| ())
@@ -0,0 +1,151 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The messaging metrics module keeps track of the depth of the message queues between the relay
+//! chain and the paras.
+//!
+//! The DMP, UMP and HRMP modules report every message they enqueue or dequeue, so the number and
+//! the total size of the messages waiting in a queue can be read without iterating the queue
+//! itself.
+//!
+//! When the module is added to a running chain, [`migration::SeedQueueSizes`] counts the messages
+//! which were queued before.
+
+use frame_support::pallet_prelude::*;
+use primitives::vstaging::{HrmpChannelId, Id as ParaId, MessageQueueSizes, QueueSize};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+
+pub mod migration;
+
+#[cfg(test)]
+mod tests;
+
+/// A message queue between the relay chain and the paras.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum MessageQueue {
+	/// The downward message queue of a para.
+	Downward(ParaId),
+	/// The upward message dispatch queue of a para.
+	Upward(ParaId),
+	/// The queue of an HRMP channel.
+	Hrmp(HrmpChannelId),
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	/// The messages waiting in the downward message queue of each para.
+	#[pallet::storage]
+	#[pallet::getter(fn downward_queue_size)]
+	pub(super) type DownwardQueueSizes<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, QueueSize, ValueQuery>;
+
+	/// The messages waiting in the upward message dispatch queue of each para.
+	#[pallet::storage]
+	#[pallet::getter(fn upward_queue_size)]
+	pub(super) type UpwardQueueSizes<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, QueueSize, ValueQuery>;
+
+	/// The messages waiting in the queue of each HRMP channel.
+	#[pallet::storage]
+	#[pallet::getter(fn hrmp_queue_size)]
+	pub(super) type HrmpQueueSizes<T: Config> =
+		StorageMap<_, Twox64Concat, HrmpChannelId, QueueSize, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Note that `count` messages of `bytes` total size were appended to `queue`.
+	pub(crate) fn note_enqueued(queue: &MessageQueue, count: u32, bytes: u32) -> Weight {
+		Self::mutate_queue_size(queue, |size| {
+			size.message_count = size.message_count.saturating_add(count);
+			size.total_bytes = size.total_bytes.saturating_add(bytes);
+		})
+	}
+
+	/// Note that `count` messages of `bytes` total size were removed from `queue`.
+	pub(crate) fn note_dequeued(queue: &MessageQueue, count: u32, bytes: u32) -> Weight {
+		Self::mutate_queue_size(queue, |size| {
+			size.message_count = size.message_count.saturating_sub(count);
+			size.total_bytes = size.total_bytes.saturating_sub(bytes);
+		})
+	}
+
+	/// Note that all messages were removed from `queue`, e.g. because its para was offboarded or
+	/// the channel closed.
+	pub(crate) fn note_cleared(queue: &MessageQueue) -> Weight {
+		match queue {
+			MessageQueue::Downward(para) => <Self as Store>::DownwardQueueSizes::remove(para),
+			MessageQueue::Upward(para) => <Self as Store>::UpwardQueueSizes::remove(para),
+			MessageQueue::Hrmp(channel) => <Self as Store>::HrmpQueueSizes::remove(channel),
+		}
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Returns the number and total size of the messages waiting in `queue`.
+	pub fn queue_size(queue: &MessageQueue) -> QueueSize {
+		match queue {
+			MessageQueue::Downward(para) => Self::downward_queue_size(para),
+			MessageQueue::Upward(para) => Self::upward_queue_size(para),
+			MessageQueue::Hrmp(channel) => Self::hrmp_queue_size(channel),
+		}
+	}
+
+	/// Returns the sizes of all non-empty message queues.
+	pub fn message_queue_sizes() -> MessageQueueSizes {
+		MessageQueueSizes {
+			downward: <Self as Store>::DownwardQueueSizes::iter().collect(),
+			upward: <Self as Store>::UpwardQueueSizes::iter().collect(),
+			hrmp: <Self as Store>::HrmpQueueSizes::iter().collect(),
+		}
+	}
+
+	fn mutate_queue_size(queue: &MessageQueue, f: impl FnOnce(&mut QueueSize)) -> Weight {
+		// Empty queues are not kept in the storage, so that `message_queue_sizes` only returns
+		// non-empty ones.
+		let mutate = |maybe_size: &mut Option<QueueSize>| {
+			let mut size = maybe_size.take().unwrap_or_default();
+			f(&mut size);
+			if size.message_count > 0 {
+				*maybe_size = Some(size);
+			}
+		};
+
+		match queue {
+			MessageQueue::Downward(para) =>
+				<Self as Store>::DownwardQueueSizes::mutate_exists(para, mutate),
+			MessageQueue::Upward(para) =>
+				<Self as Store>::UpwardQueueSizes::mutate_exists(para, mutate),
+			MessageQueue::Hrmp(channel) =>
+				<Self as Store>::HrmpQueueSizes::mutate_exists(channel, mutate),
+		}
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
@@ -0,0 +1,108 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module that is responsible for migration of storage.
+
+use super::*;
+use crate::{dmp, hrmp, ump};
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+
+/// The current storage version.
+///
+/// v0-v1: the counters are seeded with the messages which were queued before the module was
+/// added.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// Seeds the counters with the messages waiting in the DMP, UMP and HRMP queues when the module is
+/// added to a running chain, and sets the storage version.
+///
+/// From then on the queues report every message they enqueue or dequeue, so the counters match
+/// the queues. Chains which start with the module have nothing to seed, as the genesis sets the
+/// storage version already.
+///
+/// This must run before any message is queued under the new runtime, which holds for the
+/// migrations passed to the executive. The DMP storage is migrated to its latest version first,
+/// so that its pages can be read.
+pub struct SeedQueueSizes<T>(PhantomData<T>);
+impl<T: dmp::Config + ump::Config + hrmp::Config> OnRuntimeUpgrade for SeedQueueSizes<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+			log::warn!(target: "runtime::messaging_metrics", "SeedQueueSizes should be removed.");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let weight = dmp::migration::migrate_to_latest::<T>();
+		let (mut reads, mut writes) = (1, 1);
+
+		// Legacy downward queues are only moved into pages, which changes neither their messages
+		// nor the counters, so they are counted alongside the paged ones.
+		let mut downward = BTreeMap::<ParaId, QueueSize>::new();
+		for (para, messages) in dmp::DownwardMessageQueues::<T>::iter() {
+			reads += 1;
+			for message in messages {
+				note_message(downward.entry(para).or_default(), message.msg.len());
+			}
+		}
+		for para in dmp::DownwardMessageQueueState::<T>::iter_keys() {
+			let messages = <dmp::Pallet<T>>::dmq_contents(para);
+			reads += 2 + messages.len() as Weight;
+			for message in messages {
+				note_message(downward.entry(para).or_default(), message.msg.len());
+			}
+		}
+		for (para, size) in downward {
+			writes += 1;
+			<Pallet<T> as Store>::DownwardQueueSizes::insert(para, size);
+		}
+
+		for (para, (message_count, total_bytes)) in ump::RelayDispatchQueueSize::<T>::iter() {
+			reads += 1;
+			if message_count > 0 {
+				writes += 1;
+				let size = QueueSize { message_count, total_bytes };
+				<Pallet<T> as Store>::UpwardQueueSizes::insert(para, size);
+			}
+		}
+
+		for (channel_id, channel) in hrmp::HrmpChannels::<T>::iter() {
+			reads += 1;
+			if channel.msg_count > 0 {
+				writes += 1;
+				let size =
+					QueueSize { message_count: channel.msg_count, total_bytes: channel.total_size };
+				<Pallet<T> as Store>::HrmpQueueSizes::insert(channel_id, size);
+			}
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+		weight.saturating_add(T::DbWeight::get().reads_writes(reads, writes))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+			"the messaging metrics storage version was not set",
+		);
+		Ok(())
+	}
+}
+
+fn note_message(size: &mut QueueSize, len: usize) {
+	size.message_count = size.message_count.saturating_add(1);
+	size.total_bytes = size.total_bytes.saturating_add(len as u32);
+}
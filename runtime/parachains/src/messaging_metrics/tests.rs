@@ -0,0 +1,127 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::{new_test_ext, Configuration, Dmp, MessagingMetrics, MockGenesisConfig};
+use frame_support::assert_ok;
+
+fn size(message_count: u32, total_bytes: u32) -> QueueSize {
+	QueueSize { message_count, total_bytes }
+}
+
+#[test]
+fn counters_track_enqueued_and_dequeued_messages() {
+	let para = ParaId::from(100);
+	let channel = HrmpChannelId { sender: ParaId::from(100), recipient: ParaId::from(200) };
+
+	new_test_ext(Default::default()).execute_with(|| {
+		MessagingMetrics::note_enqueued(&MessageQueue::Upward(para), 3, 30);
+		MessagingMetrics::note_enqueued(&MessageQueue::Upward(para), 1, 5);
+		MessagingMetrics::note_enqueued(&MessageQueue::Hrmp(channel.clone()), 2, 8);
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Upward(para)), size(4, 35));
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Hrmp(channel.clone())), size(2, 8));
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Downward(para)), size(0, 0));
+
+		MessagingMetrics::note_dequeued(&MessageQueue::Upward(para), 2, 20);
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Upward(para)), size(2, 15));
+
+		MessagingMetrics::note_cleared(&MessageQueue::Hrmp(channel.clone()));
+		assert_eq!(
+			MessagingMetrics::message_queue_sizes(),
+			MessageQueueSizes { downward: vec![], upward: vec![(para, size(2, 15))], hrmp: vec![] },
+		);
+	});
+}
+
+#[test]
+fn empty_queues_are_not_kept() {
+	let para = ParaId::from(100);
+
+	new_test_ext(Default::default()).execute_with(|| {
+		MessagingMetrics::note_enqueued(&MessageQueue::Upward(para), 1, 10);
+		// Dequeueing more than was counted saturates at zero.
+		MessagingMetrics::note_dequeued(&MessageQueue::Upward(para), 2, 20);
+
+		assert!(!UpwardQueueSizes::<crate::mock::Test>::contains_key(para));
+		assert_eq!(MessagingMetrics::message_queue_sizes(), MessageQueueSizes::default());
+	});
+}
+
+#[test]
+fn downward_messages_are_counted() {
+	let para = ParaId::from(100);
+	let genesis = MockGenesisConfig {
+		configuration: crate::configuration::GenesisConfig {
+			config: crate::configuration::HostConfiguration {
+				max_downward_message_size: 1024,
+				..Default::default()
+			},
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis).execute_with(|| {
+		let config = Configuration::config();
+		assert_ok!(Dmp::queue_downward_message(&config, para, vec![1, 2, 3]));
+		assert_ok!(Dmp::queue_downward_message(&config, para, vec![4, 5]));
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Downward(para)), size(2, 5));
+
+		Dmp::prune_dmq(para, 1);
+		assert_eq!(MessagingMetrics::queue_size(&MessageQueue::Downward(para)), size(1, 2));
+
+		Dmp::prune_dmq(para, 1);
+		assert_eq!(MessagingMetrics::message_queue_sizes(), MessageQueueSizes::default());
+	});
+}
+
+#[test]
+fn migration_seeds_the_counters_from_the_queues() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	let para = ParaId::from(100);
+	let genesis = MockGenesisConfig {
+		configuration: crate::configuration::GenesisConfig {
+			config: crate::configuration::HostConfiguration {
+				max_downward_message_size: 1024,
+				..Default::default()
+			},
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis).execute_with(|| {
+		let config = Configuration::config();
+		assert_ok!(Dmp::queue_downward_message(&config, para, vec![1, 2, 3]));
+		assert_ok!(Dmp::queue_downward_message(&config, para, vec![4, 5]));
+		crate::ump::RelayDispatchQueueSize::<crate::mock::Test>::insert(para, (2, 7));
+
+		// Pretend the module was just added to the chain, after the messages were queued.
+		let _ = DownwardQueueSizes::<crate::mock::Test>::remove_all(None);
+		StorageVersion::new(0).put::<MessagingMetrics>();
+
+		migration::SeedQueueSizes::<crate::mock::Test>::on_runtime_upgrade();
+
+		assert_eq!(MessagingMetrics::on_chain_storage_version(), migration::STORAGE_VERSION);
+		assert_eq!(
+			MessagingMetrics::message_queue_sizes(),
+			MessageQueueSizes {
+				downward: vec![(para, size(2, 5))],
+				upward: vec![(para, size(2, 7))],
+				hrmp: vec![],
+			},
+		);
+	});
+}
@@ -17,8 +17,8 @@
 //! Mocks for all the traits.
 
 use crate::{
-	configuration, disputes, dmp, hrmp, inclusion, initializer, origin, paras, paras_inherent,
-	scheduler, session_info, shared,
+	configuration, disputes, dmp, hrmp, inclusion, initializer, messaging_metrics, on_demand,
+	origin, paras, paras_inherent, scheduler, session_info, shared,
 	ump::{self, MessageId, UmpSink},
 	ParaId,
 };
@@ -64,6 +64,8 @@ frame_support::construct_runtime!(
 		Dmp: dmp,
 		Ump: ump,
 		Hrmp: hrmp,
+		MessagingMetrics: messaging_metrics,
+		OnDemand: on_demand,
 		ParachainsOrigin: origin,
 		SessionInfo: session_info,
 		Disputes: disputes,
@@ -219,7 +221,11 @@ impl crate::paras::Config for Test {
 	type NextSessionRotation = TestNextSessionRotation;
 }
 
-impl crate::dmp::Config for Test {}
+impl crate::dmp::Config for Test {
+	type WeightInfo = crate::dmp::TestWeightInfo;
+}
+
+impl crate::messaging_metrics::Config for Test {}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -291,6 +297,17 @@ impl crate::disputes::PunishValidators for Test {
 
 impl crate::scheduler::Config for Test {}
 
+parameter_types! {
+	pub const BaseSpotPrice: Balance = 100;
+}
+
+impl crate::on_demand::Config for Test {
+	type Event = Event;
+	type Currency = pallet_balances::Pallet<Test>;
+	type BaseSpotPrice = BaseSpotPrice;
+	type WeightInfo = crate::on_demand::TestWeightInfo;
+}
+
 impl crate::inclusion::Config for Test {
 	type Event = Event;
 	type DisputesHandler = Disputes;
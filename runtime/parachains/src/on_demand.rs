@@ -0,0 +1,163 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The on-demand module for parathreads.
+//!
+//! Parathreads are registered paras without a lease, and therefore without a dedicated
+//! availability core. This module lets anyone buy a single block's worth of execution for such a
+//! para by placing a spot order, which is turned into a claim on the scheduler's parathread
+//! queue.
+//!
+//! The spot price grows with the depth of the parathread queue: an order placed on an empty queue
+//! costs `BaseSpotPrice`, while an order taking the last free slot costs close to twice as much.
+
+use crate::{configuration, paras, scheduler};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, WithdrawReasons},
+};
+use frame_system::pallet_prelude::*;
+use primitives::v2::{CollatorId, Id as ParaId, ParathreadClaim};
+use sp_runtime::{traits::Saturating, Perbill};
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+
+pub trait WeightInfo {
+	fn place_order() -> Weight;
+}
+
+/// A weight info that is only suitable for testing.
+pub struct TestWeightInfo;
+
+impl WeightInfo for TestWeightInfo {
+	fn place_order() -> Weight {
+		Weight::MAX
+	}
+}
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + configuration::Config + paras::Config + scheduler::Config
+	{
+		/// The outer event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which spot orders are paid. Payments are burned.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The price of an order placed on an empty parathread queue.
+		#[pallet::constant]
+		type BaseSpotPrice: Get<BalanceOf<Self>>;
+
+		/// Something that provides the weight of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A spot order was placed and queued for scheduling. `[para_id, who, spot_price]`
+		OrderPlaced(ParaId, T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The para is not a parathread.
+		NotParathread,
+		/// The parathread queue is full, or there are no parathread cores.
+		QueueFull,
+		/// The para already has a claim queued or assigned to a core.
+		AlreadyQueued,
+		/// The current spot price is higher than the maximum amount the caller is willing to pay.
+		SpotPriceHigherThanMaxAmount,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Buy a single block's worth of execution for the parathread `para_id`.
+		///
+		/// The caller pays the current spot price, which must not exceed `max_amount`. The
+		/// resulting claim may only be backed by candidates from `collator`.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			max_amount: BalanceOf<T>,
+			para_id: ParaId,
+			collator: CollatorId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<paras::Pallet<T>>::is_parathread(para_id), Error::<T>::NotParathread);
+			ensure!(
+				!<scheduler::Pallet<T>>::has_parathread_claim(para_id),
+				Error::<T>::AlreadyQueued
+			);
+
+			let (queue_len, queue_max_size) = <scheduler::Pallet<T>>::parathread_queue_depth();
+			ensure!(queue_len < queue_max_size, Error::<T>::QueueFull);
+
+			let spot_price = Self::spot_price(queue_len, queue_max_size);
+			ensure!(spot_price <= max_amount, Error::<T>::SpotPriceHigherThanMaxAmount);
+
+			// The imbalance is dropped, burning the payment.
+			let _ = T::Currency::withdraw(
+				&who,
+				spot_price,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			<scheduler::Pallet<T>>::add_parathread_claim(ParathreadClaim(para_id, collator));
+
+			Self::deposit_event(Event::<T>::OrderPlaced(para_id, who, spot_price));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The price of an order placed on a parathread queue holding `queue_len` out of at most
+	/// `queue_max_size` claims.
+	///
+	/// This is `BaseSpotPrice` on an empty queue and grows linearly with the queue depth.
+	pub fn spot_price(queue_len: u32, queue_max_size: u32) -> BalanceOf<T> {
+		let base = T::BaseSpotPrice::get();
+		base.saturating_add(Perbill::from_rational(queue_len, queue_max_size.max(1)) * base)
+	}
+
+	/// The price of an order placed right now.
+	pub fn current_spot_price() -> BalanceOf<T> {
+		let (queue_len, queue_max_size) = <scheduler::Pallet<T>>::parathread_queue_depth();
+		Self::spot_price(queue_len, queue_max_size)
+	}
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{Pallet as OnDemand, *};
+use crate::{
+	configuration::Pallet as Configuration,
+	paras::{Pallet as Paras, ParachainsCache},
+	scheduler::Pallet as Scheduler,
+};
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_core::sr25519;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+fn register_parathread<T: Config>(id: ParaId) {
+	let mut parachains = ParachainsCache::new();
+	Paras::<T>::initialize_para_now(
+		&mut parachains,
+		id,
+		&crate::paras::ParaGenesisArgs {
+			parachain: false,
+			genesis_head: vec![1].into(),
+			validation_code: vec![1].into(),
+		},
+	);
+}
+
+fn collator() -> CollatorId {
+	CollatorId::from(sr25519::Public::from_raw([42u8; 32]))
+}
+
+benchmarks! {
+	// The order takes the last free slot of a full parathread queue.
+	place_order {
+		let mut config = Configuration::<T>::config();
+		config.parathread_cores = config.parathread_cores.max(1);
+		config.scheduling_lookahead = config.scheduling_lookahead.max(1);
+		Configuration::<T>::force_set_active_config(config);
+
+		let (_, queue_max_size) = Scheduler::<T>::parathread_queue_depth();
+		for i in 1..queue_max_size {
+			let id = ParaId::from(1_000 + i);
+			register_parathread::<T>(id);
+			Scheduler::<T>::add_parathread_claim(ParathreadClaim(id, collator()));
+		}
+		assert_eq!(Scheduler::<T>::parathread_queue_depth(), (queue_max_size - 1, queue_max_size));
+
+		let para_id = ParaId::from(1_000);
+		register_parathread::<T>(para_id);
+
+		let caller: T::AccountId = account("caller", 0, 0);
+		let spot_price = OnDemand::<T>::current_spot_price();
+		T::Currency::make_free_balance_be(
+			&caller,
+			spot_price.saturating_mul(2u32.into()).saturating_add(T::Currency::minimum_balance()),
+		);
+	}: _(RawOrigin::Signed(caller.clone()), spot_price, para_id, collator())
+	verify {
+		assert!(Scheduler::<T>::has_parathread_claim(para_id));
+		assert_last_event::<T>(Event::<T>::OrderPlaced(para_id, caller, spot_price).into());
+	}
+}
+
+frame_benchmarking::impl_benchmark_test_suite!(
+	OnDemand,
+	crate::mock::new_test_ext(Default::default()),
+	crate::mock::Test
+);
@@ -0,0 +1,187 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+use frame_support::{assert_noop, assert_ok, traits::Currency as _};
+use keyring::Sr25519Keyring;
+
+use crate::{
+	configuration::HostConfiguration,
+	mock::{
+		assert_last_event, new_test_ext, Balances, Event as MockEvent, MockGenesisConfig, OnDemand,
+		Origin, Paras, ParasShared, Scheduler, System, Test,
+	},
+	paras::ParaGenesisArgs,
+};
+
+fn schedule_blank_para(id: ParaId, is_chain: bool) {
+	assert_ok!(Paras::schedule_para_initialize(
+		id,
+		ParaGenesisArgs {
+			genesis_head: Vec::new().into(),
+			validation_code: vec![1, 2, 3].into(),
+			parachain: is_chain,
+		}
+	));
+}
+
+/// Advance to the next session, onboarding all scheduled paras.
+fn run_to_next_session() {
+	let b = System::block_number();
+
+	Scheduler::initializer_finalize();
+	Paras::initializer_finalize(b);
+
+	let notification = crate::initializer::SessionChangeNotification {
+		session_index: ParasShared::scheduled_session(),
+		..Default::default()
+	};
+	Paras::initializer_on_new_session(&notification);
+	Scheduler::initializer_on_new_session(&notification);
+
+	System::on_finalize(b);
+	System::on_initialize(b + 1);
+	System::set_block_number(b + 1);
+}
+
+fn genesis_config(parathread_cores: u32, scheduling_lookahead: u32) -> MockGenesisConfig {
+	MockGenesisConfig {
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				parathread_cores,
+				scheduling_lookahead,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	}
+}
+
+fn collator() -> CollatorId {
+	CollatorId::from(Sr25519Keyring::Alice.public())
+}
+
+fn balance(who: u64) -> u128 {
+	Balances::free_balance(&who)
+}
+
+#[test]
+fn spot_price_grows_with_queue_depth() {
+	new_test_ext(Default::default()).execute_with(|| {
+		assert_eq!(OnDemand::spot_price(0, 6), 100);
+		assert_eq!(OnDemand::spot_price(3, 6), 150);
+		assert_eq!(OnDemand::spot_price(6, 6), 200);
+		// Without any parathread cores the base price applies.
+		assert_eq!(OnDemand::spot_price(0, 0), 100);
+	});
+}
+
+#[test]
+fn place_order_works() {
+	let thread_a = ParaId::from(10);
+	let thread_b = ParaId::from(11);
+
+	new_test_ext(genesis_config(3, 2)).execute_with(|| {
+		schedule_blank_para(thread_a, false);
+		schedule_blank_para(thread_b, false);
+		run_to_next_session();
+
+		Balances::make_free_balance_be(&1, 1_000);
+		assert_eq!(OnDemand::current_spot_price(), 100);
+
+		assert_ok!(OnDemand::place_order(Origin::signed(1), 100, thread_a, collator()));
+		assert_eq!(balance(1), 900);
+		assert!(Scheduler::has_parathread_claim(thread_a));
+		assert_eq!(Scheduler::parathread_queue_depth(), (1, 6));
+		assert_last_event(MockEvent::OnDemand(Event::OrderPlaced(thread_a, 1, 100)));
+
+		// The price now reflects the deeper queue.
+		let spot_price = OnDemand::current_spot_price();
+		assert_eq!(spot_price, OnDemand::spot_price(1, 6));
+		assert!(spot_price > 100);
+
+		assert_ok!(OnDemand::place_order(Origin::signed(1), spot_price, thread_b, collator()));
+		assert_eq!(balance(1), 900 - spot_price);
+		assert_eq!(Scheduler::parathread_queue_depth(), (2, 6));
+	});
+}
+
+#[test]
+fn place_order_fails_for_non_parathreads() {
+	let chain = ParaId::from(10);
+	let unknown = ParaId::from(11);
+
+	new_test_ext(genesis_config(3, 2)).execute_with(|| {
+		schedule_blank_para(chain, true);
+		run_to_next_session();
+		Balances::make_free_balance_be(&1, 1_000);
+
+		assert_noop!(
+			OnDemand::place_order(Origin::signed(1), 1_000, chain, collator()),
+			Error::<Test>::NotParathread,
+		);
+		assert_noop!(
+			OnDemand::place_order(Origin::signed(1), 1_000, unknown, collator()),
+			Error::<Test>::NotParathread,
+		);
+	});
+}
+
+#[test]
+fn place_order_checks_queue_and_price() {
+	let thread_a = ParaId::from(10);
+	let thread_b = ParaId::from(11);
+
+	new_test_ext(genesis_config(1, 1)).execute_with(|| {
+		schedule_blank_para(thread_a, false);
+		schedule_blank_para(thread_b, false);
+		run_to_next_session();
+		Balances::make_free_balance_be(&1, 1_000);
+
+		assert_noop!(
+			OnDemand::place_order(Origin::signed(1), 99, thread_a, collator()),
+			Error::<Test>::SpotPriceHigherThanMaxAmount,
+		);
+
+		assert_ok!(OnDemand::place_order(Origin::signed(1), 100, thread_a, collator()));
+		assert_noop!(
+			OnDemand::place_order(Origin::signed(1), 1_000, thread_a, collator()),
+			Error::<Test>::AlreadyQueued,
+		);
+		assert_noop!(
+			OnDemand::place_order(Origin::signed(1), 1_000, thread_b, collator()),
+			Error::<Test>::QueueFull,
+		);
+		assert_eq!(balance(1), 900);
+	});
+}
+
+#[test]
+fn place_order_requires_funds() {
+	let thread = ParaId::from(10);
+
+	new_test_ext(genesis_config(3, 2)).execute_with(|| {
+		schedule_blank_para(thread, false);
+		run_to_next_session();
+		Balances::make_free_balance_be(&1, 50);
+
+		assert!(OnDemand::place_order(Origin::signed(1), 100, thread, collator()).is_err());
+		assert!(!Scheduler::has_parathread_claim(thread));
+		assert_eq!(balance(1), 50);
+	});
+}
@@ -16,6 +16,7 @@
 
 //! Declaration of the parachain specific origin and a pallet that hosts it.
 
+use frame_support::traits::EnsureOrigin;
 use primitives::v2::Id as ParaId;
 use sp_runtime::traits::BadOrigin;
 use sp_std::result;
@@ -34,6 +35,25 @@ where
 	}
 }
 
+/// Ensure that the origin represents a parachain, e.g. one dispatching an XCM `Transact` with
+/// the native origin kind. Yields the parachain ID.
+pub struct EnsureParachain;
+impl<OuterOrigin> EnsureOrigin<OuterOrigin> for EnsureParachain
+where
+	OuterOrigin: Into<result::Result<Origin, OuterOrigin>> + From<Origin>,
+{
+	type Success = ParaId;
+
+	fn try_origin(o: OuterOrigin) -> result::Result<ParaId, OuterOrigin> {
+		o.into().map(|Origin::Parachain(id)| id)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		OuterOrigin::from(Origin::Parachain(0.into()))
+	}
+}
+
 /// There is no way to register an origin type in `construct_runtime` without a pallet the origin
 /// belongs to.
 ///
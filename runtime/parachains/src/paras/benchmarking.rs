@@ -134,6 +134,16 @@ benchmarks! {
 		assert_last_event::<T>(Event::ActionQueued(para_id, next_session).into());
 	}
 
+	force_set_upgrade_cooldown {
+		let para_id = ParaId::from(1000);
+		ParaLifecycles::<T>::insert(&para_id, ParaLifecycle::Parachain);
+		let cooldown = T::BlockNumber::from(1000u32);
+	}: _(RawOrigin::Root, para_id, Some(cooldown))
+	verify {
+		assert_eq!(<Pallet<T> as Store>::UpgradeCooldownOverrides::get(&para_id), Some(cooldown));
+		assert_last_event::<T>(Event::UpgradeCooldownSet(para_id).into());
+	}
+
 	add_trusted_validation_code {
 		let c in 1 .. MAX_CODE_SIZE;
 		let new_code = ValidationCode(vec![0; c as usize]);
@@ -134,6 +134,8 @@ pub(crate) mod benchmarking;
 #[cfg(test)]
 pub(crate) mod tests;
 
+pub mod runtime_api;
+
 pub use pallet::*;
 
 const LOG_TARGET: &str = "runtime::paras";
@@ -402,6 +404,7 @@ pub trait WeightInfo {
 	fn force_schedule_code_upgrade(c: u32) -> Weight;
 	fn force_note_new_head(s: u32) -> Weight;
 	fn force_queue_action() -> Weight;
+	fn force_set_upgrade_cooldown() -> Weight;
 	fn add_trusted_validation_code(c: u32) -> Weight;
 	fn poke_unused_validation_code() -> Weight;
 
@@ -429,6 +432,9 @@ impl WeightInfo for TestWeightInfo {
 	fn force_queue_action() -> Weight {
 		Weight::MAX
 	}
+	fn force_set_upgrade_cooldown() -> Weight {
+		Weight::MAX
+	}
 	fn add_trusted_validation_code(_c: u32) -> Weight {
 		Weight::MAX
 	}
@@ -506,6 +512,12 @@ pub mod pallet {
 		/// The given validation code was rejected by the PVF pre-checking vote.
 		/// `code_hash` `para_id`
 		PvfCheckRejected(ValidationCodeHash, ParaId),
+		/// The para is signalled that it must not signal another code upgrade. `para_id`
+		UpgradeRestrictionSignalSet(ParaId),
+		/// The para is signalled that it may signal a code upgrade again. `para_id`
+		UpgradeRestrictionSignalCleared(ParaId),
+		/// The cooldown between code upgrades of a para has been set. `para_id`
+		UpgradeCooldownSet(ParaId),
 	}
 
 	#[pallet::error]
@@ -617,6 +629,7 @@ pub mod pallet {
 	///
 	/// Corresponding code can be retrieved with [`CodeByHash`].
 	#[pallet::storage]
+	#[pallet::getter(fn future_code_hash)]
 	pub(super) type FutureCodeHash<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, ValidationCodeHash>;
 
@@ -653,6 +666,12 @@ pub mod pallet {
 	pub(super) type UpgradeCooldowns<T: Config> =
 		StorageValue<_, Vec<(ParaId, T::BlockNumber)>, ValueQuery>;
 
+	/// The cooldown between code upgrades of a para, for the paras whose cooldown differs from
+	/// the `validation_upgrade_cooldown` of the host configuration.
+	#[pallet::storage]
+	pub(super) type UpgradeCooldownOverrides<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, T::BlockNumber>;
+
 	/// The list of upcoming code upgrades. Each item is a pair of which para performs a code
 	/// upgrade and at which relay-chain block it is expected at.
 	///
@@ -976,6 +995,28 @@ pub mod pallet {
 				Ok(Some(<T as Config>::WeightInfo::include_pvf_check_statement()).into())
 			}
 		}
+
+		/// Set the cooldown between code upgrades of a para, overriding the
+		/// `validation_upgrade_cooldown` of the host configuration. `None` makes the para use the
+		/// configured cooldown again.
+		///
+		/// Only the upgrades scheduled after this call are affected.
+		#[pallet::weight(<T as Config>::WeightInfo::force_set_upgrade_cooldown())]
+		pub fn force_set_upgrade_cooldown(
+			origin: OriginFor<T>,
+			para: ParaId,
+			cooldown: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(ParaLifecycles::<T>::contains_key(&para), Error::<T>::NotRegistered);
+			match cooldown {
+				Some(cooldown) =>
+					<Self as Store>::UpgradeCooldownOverrides::insert(&para, cooldown),
+				None => <Self as Store>::UpgradeCooldownOverrides::remove(&para),
+			}
+			Self::deposit_event(Event::UpgradeCooldownSet(para));
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -1068,13 +1109,15 @@ impl<T: Config> Pallet<T> {
 
 	/// Called by the initializer to note that a new session has started.
 	///
-	/// Returns the list of outgoing paras from the actions queue.
+	/// Returns the list of outgoing paras from the actions queue and the weight consumed.
 	pub(crate) fn initializer_on_new_session(
 		notification: &SessionChangeNotification<T::BlockNumber>,
-	) -> Vec<ParaId> {
-		let outgoing_paras = Self::apply_actions_queue(notification.session_index);
-		Self::groom_ongoing_pvf_votes(&notification.new_config, notification.validators.len());
-		outgoing_paras
+	) -> (Vec<ParaId>, Weight) {
+		let (outgoing_paras, actions_weight) =
+			Self::apply_actions_queue(notification.session_index);
+		let votes_weight =
+			Self::groom_ongoing_pvf_votes(&notification.new_config, notification.validators.len());
+		(outgoing_paras, actions_weight.saturating_add(votes_weight))
 	}
 
 	/// The validation code of live para.
@@ -1099,15 +1142,19 @@ impl<T: Config> Pallet<T> {
 	// The final state of any para after the actions queue should be as a
 	// parachain, parathread, or not registered. (stable states)
 	//
-	// Returns the list of outgoing paras from the actions queue.
-	fn apply_actions_queue(session: SessionIndex) -> Vec<ParaId> {
+	// Returns the list of outgoing paras from the actions queue and the weight consumed.
+	fn apply_actions_queue(session: SessionIndex) -> (Vec<ParaId>, Weight) {
 		let actions = ActionsQueue::<T>::take(session);
 		let mut parachains = ParachainsCache::new();
 		let now = <frame_system::Pallet<T>>::block_number();
 		let mut outgoing = Vec::new();
 
+		// The actions queue and the parachains list.
+		let mut weight = T::DbWeight::get().reads_writes(2, 2);
+
 		for para in actions {
 			let lifecycle = ParaLifecycles::<T>::get(&para);
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
 			match lifecycle {
 				None | Some(ParaLifecycle::Parathread) | Some(ParaLifecycle::Parachain) => { /* Nothing to do... */
 				},
@@ -1115,16 +1162,19 @@ impl<T: Config> Pallet<T> {
 					if let Some(genesis_data) = <Self as Store>::UpcomingParasGenesis::take(&para) {
 						Self::initialize_para_now(&mut parachains, para, &genesis_data);
 					}
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 5));
 				},
 				// Upgrade a parathread to a parachain
 				Some(ParaLifecycle::UpgradingParathread) => {
 					parachains.add(para);
 					ParaLifecycles::<T>::insert(&para, ParaLifecycle::Parachain);
+					weight = weight.saturating_add(T::DbWeight::get().writes(1));
 				},
 				// Downgrade a parachain to a parathread
 				Some(ParaLifecycle::DowngradingParachain) => {
 					parachains.remove(para);
 					ParaLifecycles::<T>::insert(&para, ParaLifecycle::Parathread);
+					weight = weight.saturating_add(T::DbWeight::get().writes(1));
 				},
 				// Offboard a parathread or parachain from the system
 				Some(ParaLifecycle::OffboardingParachain) |
@@ -1135,16 +1185,24 @@ impl<T: Config> Pallet<T> {
 					<Self as Store>::FutureCodeUpgrades::remove(&para);
 					<Self as Store>::UpgradeGoAheadSignal::remove(&para);
 					<Self as Store>::UpgradeRestrictionSignal::remove(&para);
+					<Self as Store>::UpgradeCooldownOverrides::remove(&para);
 					ParaLifecycles::<T>::remove(&para);
 					let removed_future_code_hash = <Self as Store>::FutureCodeHash::take(&para);
 					if let Some(removed_future_code_hash) = removed_future_code_hash {
-						Self::decrease_code_ref(&removed_future_code_hash);
+						weight = weight
+							.saturating_add(Self::decrease_code_ref(&removed_future_code_hash));
 					}
 
 					let removed_code_hash = <Self as Store>::CurrentCodeHash::take(&para);
 					if let Some(removed_code_hash) = removed_code_hash {
-						Self::note_past_code(para, now, now, removed_code_hash);
+						weight = weight.saturating_add(Self::note_past_code(
+							para,
+							now,
+							now,
+							removed_code_hash,
+						));
 					}
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 8));
 
 					outgoing.push(para);
 				},
@@ -1170,12 +1228,13 @@ impl<T: Config> Pallet<T> {
 					.filter(|&(ref para, _)| !outgoing.contains(para))
 					.collect();
 			});
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
 		}
 
 		// Persist parachains into the storage explicitly.
 		drop(parachains);
 
-		return outgoing
+		(outgoing, weight)
 	}
 
 	// note replacement of the code of para with given `id`, which occured in the
@@ -1293,9 +1352,10 @@ impl<T: Config> Pallet<T> {
 
 		// reserve weight for `initializer_finalize`:
 		// - 1 read and 1 write for `UpgradeCooldowns::mutate`.
-		// - 1 write per expired cooldown.
+		// - 1 read and 1 write per expired cooldown.
 		weight += T::DbWeight::get().reads_writes(1, 1);
-		weight += T::DbWeight::get().reads(cooldowns_expired as u64);
+		weight +=
+			T::DbWeight::get().reads_writes(cooldowns_expired as u64, cooldowns_expired as u64);
 
 		weight
 	}
@@ -1306,8 +1366,11 @@ impl<T: Config> Pallet<T> {
 	fn process_scheduled_upgrade_cooldowns(now: T::BlockNumber) {
 		<Self as Store>::UpgradeCooldowns::mutate(
 			|upgrade_cooldowns: &mut Vec<(ParaId, T::BlockNumber)>| {
-				for &(para, _) in upgrade_cooldowns.iter().take_while(|&(_, at)| at <= &now) {
-					<Self as Store>::UpgradeRestrictionSignal::remove(&para);
+				let num = upgrade_cooldowns.iter().take_while(|&(_, at)| at <= &now).count();
+				for (para, _) in upgrade_cooldowns.drain(..num) {
+					if <Self as Store>::UpgradeRestrictionSignal::take(&para).is_some() {
+						Self::deposit_event(Event::UpgradeRestrictionSignalCleared(para));
+					}
 				}
 			},
 		);
@@ -1647,6 +1710,14 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Whether a code upgrade to `new_code` can be scheduled for the given para, i.e. the para
+	/// exists, has no upgrade pending and is not already validated by `new_code`.
+	pub(crate) fn can_schedule_code_upgrade(id: ParaId, new_code: &ValidationCode) -> bool {
+		Self::is_valid_para(id) &&
+			!FutureCodeHash::<T>::contains_key(&id) &&
+			CurrentCodeHash::<T>::get(&id) != Some(new_code.hash())
+	}
+
 	/// Schedule a future code upgrade of the given parachain.
 	///
 	/// If the new code is not known, then the PVF pre-checking will be started for that validation
@@ -1705,9 +1776,12 @@ impl<T: Config> Pallet<T> {
 		weight += T::DbWeight::get().writes(2);
 		FutureCodeHash::<T>::insert(&id, &code_hash);
 		UpgradeRestrictionSignal::<T>::insert(&id, UpgradeRestriction::Present);
+		Self::deposit_event(Event::UpgradeRestrictionSignalSet(id));
 
-		weight += T::DbWeight::get().reads_writes(1, 1);
-		let next_possible_upgrade_at = relay_parent_number + cfg.validation_upgrade_cooldown;
+		weight += T::DbWeight::get().reads_writes(2, 1);
+		let cooldown = <Self as Store>::UpgradeCooldownOverrides::get(&id)
+			.unwrap_or(cfg.validation_upgrade_cooldown);
+		let next_possible_upgrade_at = relay_parent_number + cooldown;
 		<Self as Store>::UpgradeCooldowns::mutate(|upgrade_cooldowns| {
 			let insert_idx = upgrade_cooldowns
 				.binary_search_by_key(&next_possible_upgrade_at, |&(_, b)| b)
@@ -1893,6 +1967,14 @@ impl<T: Config> Pallet<T> {
 		ParaLifecycles::<T>::get(&id)
 	}
 
+	/// Returns the upgrade restriction signalled to the para, if any.
+	///
+	/// While this is `Some`, the para must not signal a code upgrade, e.g. because the cooldown
+	/// after its previous upgrade has not yet expired.
+	pub fn upgrade_restriction_signal(id: ParaId) -> Option<UpgradeRestriction> {
+		UpgradeRestrictionSignal::<T>::get(&id)
+	}
+
 	/// Returns whether the given ID refers to a valid para.
 	///
 	/// Paras that are onboarding or offboarding are not included.
@@ -0,0 +1,30 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definitions for querying the paras pallet.
+
+use primitives::v2::{Id as ParaId, UpgradeRestriction};
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying the upgrade state of paras, so that collators know whether they may
+	/// include a code upgrade in the next block.
+	pub trait ParasApi {
+		/// The upgrade restriction currently signalled to `para_id`, if any.
+		///
+		/// While this is `Some`, a candidate of the para must not signal a code upgrade.
+		fn upgrade_restriction_signal(para_id: ParaId) -> Option<UpgradeRestriction>;
+	}
+}
@@ -17,11 +17,11 @@
 use super::*;
 use frame_support::{assert_err, assert_ok, assert_storage_noop};
 use keyring::Sr25519Keyring;
+use polkadot_primitives_test_helpers::{dummy_head_data, dummy_validation_code};
 use primitives::v2::{BlockNumber, ValidatorId, PARACHAIN_KEY_TYPE_ID};
 use sc_keystore::LocalKeystore;
 use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
 use std::sync::Arc;
-use test_helpers::{dummy_head_data, dummy_validation_code};
 
 use crate::{
 	configuration::HostConfiguration,
@@ -121,6 +121,15 @@ impl EventValidator {
 		Self { events: Vec::new() }
 	}
 
+	fn upgrade_restricted(&mut self, id: ParaId) -> &mut Self {
+		self.events.push(frame_system::EventRecord {
+			phase: frame_system::Phase::Initialization,
+			event: Event::UpgradeRestrictionSignalSet(id).into(),
+			topics: vec![],
+		});
+		self
+	}
+
 	fn started(&mut self, code: &ValidationCode, id: ParaId) -> &mut Self {
 		self.events.push(frame_system::EventRecord {
 			phase: frame_system::Phase::Initialization,
@@ -576,6 +585,46 @@ fn code_upgrade_applied_after_delay_even_when_late() {
 	});
 }
 
+#[test]
+fn schedule_code_upgrade_checks_para_state() {
+	let original_code = ValidationCode(vec![1, 2, 3]);
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			parachain: true,
+			genesis_head: dummy_head_data(),
+			validation_code: original_code.clone(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration { pvf_checking_enabled: false, ..Default::default() },
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let new_code = ValidationCode(vec![4, 5, 6]);
+		run_to_block(2, None);
+
+		// Unknown paras and upgrades to the current code are rejected.
+		assert!(crate::schedule_code_upgrade::<Test>(ParaId::from(1), new_code.clone()).is_err());
+		assert!(crate::schedule_code_upgrade::<Test>(para_id, original_code.clone()).is_err());
+		assert!(<Paras as Store>::FutureCodeHash::get(&para_id).is_none());
+
+		assert_ok!(crate::schedule_code_upgrade::<Test>(para_id, new_code.clone()));
+		assert_eq!(<Paras as Store>::FutureCodeHash::get(&para_id), Some(new_code.hash()));
+
+		// Only one upgrade may be pending at a time.
+		assert!(crate::schedule_code_upgrade::<Test>(para_id, ValidationCode(vec![7])).is_err());
+		assert_eq!(<Paras as Store>::FutureCodeHash::get(&para_id), Some(new_code.hash()));
+	});
+}
+
 #[test]
 fn submit_code_change_when_not_allowed_is_err() {
 	let code_retention_period = 10;
@@ -686,6 +735,10 @@ fn upgrade_restriction_elapsed_doesnt_mean_can_upgrade() {
 			<Paras as Store>::UpgradeRestrictionSignal::get(&para_id),
 			Some(UpgradeRestriction::Present),
 		);
+		assert_eq!(Paras::upgrade_restriction_signal(para_id), Some(UpgradeRestriction::Present));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::UpgradeRestrictionSignalSet(para_id).into()));
 		assert_eq!(
 			<Paras as Store>::FutureCodeUpgrades::get(&para_id),
 			Some(0 + validation_upgrade_delay)
@@ -694,6 +747,11 @@ fn upgrade_restriction_elapsed_doesnt_mean_can_upgrade() {
 
 		run_to_block(31, None);
 		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&para_id).is_none());
+		assert!(Paras::upgrade_restriction_signal(para_id).is_none());
+		assert!(<Paras as Store>::UpgradeCooldowns::get().is_empty());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::UpgradeRestrictionSignalCleared(para_id).into()));
 
 		// Note the para still cannot upgrade the validation code.
 		assert!(!Paras::can_upgrade_validation_code(para_id));
@@ -707,6 +765,72 @@ fn upgrade_restriction_elapsed_doesnt_mean_can_upgrade() {
 	});
 }
 
+#[test]
+fn upgrade_cooldown_can_be_set_per_para() {
+	let validation_upgrade_delay = 5;
+	let validation_upgrade_cooldown = 10;
+
+	let paras = vec![0u32, 1]
+		.into_iter()
+		.map(|id| {
+			let args = ParaGenesisArgs {
+				parachain: true,
+				genesis_head: dummy_head_data(),
+				validation_code: vec![1, 2, 3].into(),
+			};
+			(id.into(), args)
+		})
+		.collect();
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				validation_upgrade_delay,
+				validation_upgrade_cooldown,
+				pvf_checking_enabled: false,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let (a, b): (ParaId, ParaId) = (0u32.into(), 1u32.into());
+
+		run_to_block(1, None);
+		assert_ok!(Paras::force_set_upgrade_cooldown(Origin::root(), a, Some(20)));
+		assert_eq!(<Paras as Store>::UpgradeCooldownOverrides::get(&a), Some(20));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::UpgradeCooldownSet(a).into()));
+		assert_err!(
+			Paras::force_set_upgrade_cooldown(Origin::root(), 2u32.into(), Some(20)),
+			Error::<Test>::NotRegistered,
+		);
+
+		Paras::schedule_code_upgrade(a, ValidationCode(vec![4, 5, 6]), 0, &Configuration::config());
+		Paras::schedule_code_upgrade(b, ValidationCode(vec![7, 8, 9]), 0, &Configuration::config());
+		assert_eq!(<Paras as Store>::UpgradeCooldowns::get(), vec![(b, 10), (a, 20)]);
+
+		// Clearing the override does not affect the upgrade which is already scheduled.
+		assert_ok!(Paras::force_set_upgrade_cooldown(Origin::root(), a, None));
+		assert!(<Paras as Store>::UpgradeCooldownOverrides::get(&a).is_none());
+		assert_eq!(<Paras as Store>::UpgradeCooldowns::get(), vec![(b, 10), (a, 20)]);
+
+		run_to_block(11, None);
+		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&b).is_none());
+		assert_eq!(
+			<Paras as Store>::UpgradeRestrictionSignal::get(&a),
+			Some(UpgradeRestriction::Present),
+		);
+
+		run_to_block(21, None);
+		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&a).is_none());
+	});
+}
+
 #[test]
 fn full_parachain_cleanup_storage() {
 	let code_retention_period = 20;
@@ -1093,6 +1217,7 @@ fn pvf_check_coalescing_onboarding_and_upgrade() {
 		// Verify that the required events were emitted.
 		EventValidator::new()
 			.started(&validation_code, b)
+			.upgrade_restricted(a)
 			.started(&validation_code, a)
 			.accepted(&validation_code, b)
 			.accepted(&validation_code, a)
@@ -1209,7 +1334,11 @@ fn pvf_check_upgrade_reject() {
 		assert!(<Paras as Store>::FutureCodeHash::get(&a).is_none());
 
 		// Verify that the required events were emitted.
-		EventValidator::new().started(&new_code, a).rejected(&new_code, a).check();
+		EventValidator::new()
+			.upgrade_restricted(a)
+			.started(&new_code, a)
+			.rejected(&new_code, a)
+			.check();
 	});
 }
 
@@ -1559,6 +1688,7 @@ fn add_trusted_validation_code_insta_approval() {
 
 		// Verify that the required events were emitted.
 		EventValidator::new()
+			.upgrade_restricted(para_id)
 			.started(&validation_code, para_id)
 			.accepted(&validation_code, para_id)
 			.check();
@@ -29,7 +29,8 @@ use crate::{
 	initializer,
 	metrics::METRICS,
 	scheduler::{self, CoreAssignment, FreedReason},
-	shared, ump, ParaId,
+	shared::{self, AllowedRelayParentsTracker},
+	ump, ParaId,
 };
 use bitvec::prelude::BitVec;
 use frame_support::{
@@ -337,6 +338,15 @@ impl<T: Config> Pallet<T> {
 
 		let current_session = <shared::Pallet<T>>::session_index();
 
+		// Candidates backed from now on may be built on top of the parent block.
+		let allowed_relay_parents = <shared::Pallet<T>>::add_allowed_relay_parent(
+			parent_hash,
+			*parent_header.state_root(),
+			current_session,
+			now - One::one(),
+			<configuration::Pallet<T>>::config().async_backing_params.allowed_ancestry_len,
+		);
+
 		let max_block_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
 
 		METRICS.on_before_filter(candidates_weight + bitfields_weight + disputes_weight);
@@ -500,7 +510,8 @@ impl<T: Config> Pallet<T> {
 
 		let scheduled = <scheduler::Pallet<T>>::scheduled();
 		assure_sanity_backed_candidates::<T, _>(
-			parent_hash,
+			&allowed_relay_parents,
+			current_session,
 			&backed_candidates,
 			move |_candidate_index: usize, backed_candidate: &BackedCandidate<T::Hash>| -> bool {
 				<T>::DisputesHandler::concluded_invalid(current_session, backed_candidate.hash())
@@ -512,12 +523,11 @@ impl<T: Config> Pallet<T> {
 		METRICS.on_candidates_sanitized(backed_candidates.len() as u64);
 
 		// Process backed candidates according to scheduled cores.
-		let parent_storage_root = parent_header.state_root().clone();
 		let inclusion::ProcessedCandidates::<<T::Header as HeaderT>::Hash> {
 			core_indices: occupied,
 			candidate_receipt_with_backing_validator_indices,
 		} = <inclusion::Pallet<T>>::process_candidates(
-			parent_storage_root,
+			&allowed_relay_parents,
 			backed_candidates,
 			scheduled,
 			<scheduler::Pallet<T>>::group_validators,
@@ -707,12 +717,19 @@ impl<T: Config> Pallet<T> {
 
 			let scheduled = <scheduler::Pallet<T>>::scheduled();
 
-			let relay_parent_number = now - One::one();
-			let parent_storage_root = parent_header.state_root().clone();
+			// Like in `enter`, the parent block is an allowed relay parent of the candidates.
+			let allowed_relay_parents = &<shared::Pallet<T>>::add_allowed_relay_parent(
+				parent_hash,
+				*parent_header.state_root(),
+				current_session,
+				now - One::one(),
+				config.async_backing_params.allowed_ancestry_len,
+			);
 
-			let check_ctx = CandidateCheckContext::<T>::new(now, relay_parent_number);
+			let check_ctx = CandidateCheckContext::<T>::new(now);
 			let backed_candidates = sanitize_backed_candidates::<T, _>(
-				parent_hash,
+				allowed_relay_parents,
+				current_session,
 				backed_candidates,
 				move |candidate_idx: usize,
 				      backed_candidate: &BackedCandidate<<T as frame_system::Config>::Hash>|
@@ -724,7 +741,7 @@ impl<T: Config> Pallet<T> {
 							// That way we avoid possible duplicate checks while assuring all
 							// backed candidates fine to pass on.
 							check_ctx
-								.verify_backed_candidate(parent_hash, parent_storage_root, candidate_idx, backed_candidate)
+								.verify_backed_candidate(allowed_relay_parents, current_session, candidate_idx, backed_candidate)
 								.is_err()
 				},
 				&scheduled[..],
@@ -1102,12 +1119,16 @@ pub(crate) fn assure_sanity_bitfields<T: crate::inclusion::Config>(
 /// `candidate_has_concluded_invalid_dispute` must return `true` if the candidate
 /// is disputed, false otherwise. The passed `usize` is the candidate index.
 ///
+/// Candidates which are not built on one of the `allowed_relay_parents` of `session_index` are
+/// filtered out as well.
+///
 /// The returned `Vec` is sorted according to the occupied core index.
 fn sanitize_backed_candidates<
 	T: crate::inclusion::Config,
 	F: FnMut(usize, &BackedCandidate<T::Hash>) -> bool,
 >(
-	relay_parent: T::Hash,
+	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, T::BlockNumber>,
+	session_index: SessionIndex,
 	mut backed_candidates: Vec<BackedCandidate<T::Hash>>,
 	mut candidate_has_concluded_invalid_dispute_or_is_invalid: F,
 	scheduled: &[CoreAssignment],
@@ -1125,16 +1146,16 @@ fn sanitize_backed_candidates<
 
 	// Assure the backed candidate's `ParaId`'s core is free.
 	// This holds under the assumption that `Scheduler::schedule` is called _before_.
-	// Also checks the candidate references the correct relay parent.
+	// Also checks the candidate references an allowed relay parent.
 
 	backed_candidates.retain(|backed_candidate| {
 		let desc = backed_candidate.descriptor();
-		desc.relay_parent == relay_parent &&
+		allowed_relay_parents.acquire_info(desc.relay_parent, session_index).is_some() &&
 			scheduled_paras_to_core_idx.get(&desc.para_id).is_some()
 	});
 
 	// Sort the `Vec` last, once there is a guarantee that these
-	// `BackedCandidates` references the expected relay chain parent,
+	// `BackedCandidates` references an allowed relay chain parent,
 	// but more importantly are scheduled for a free core.
 	// This both avoids extra work for obviously invalid candidates,
 	// but also allows this to be done in place.
@@ -1152,7 +1173,8 @@ pub(crate) fn assure_sanity_backed_candidates<
 	T: crate::inclusion::Config,
 	F: FnMut(usize, &BackedCandidate<T::Hash>) -> bool,
 >(
-	relay_parent: T::Hash,
+	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, T::BlockNumber>,
+	session_index: SessionIndex,
 	backed_candidates: &[BackedCandidate<T::Hash>],
 	mut candidate_has_concluded_invalid_dispute_or_is_invalid: F,
 	scheduled: &[CoreAssignment],
@@ -1165,9 +1187,9 @@ pub(crate) fn assure_sanity_backed_candidates<
 		}
 		// Assure the backed candidate's `ParaId`'s core is free.
 		// This holds under the assumption that `Scheduler::schedule` is called _before_.
-		// Also checks the candidate references the correct relay parent.
+		// Also checks the candidate references an allowed relay parent.
 		let desc = backed_candidate.descriptor();
-		if desc.relay_parent != relay_parent {
+		if allowed_relay_parents.acquire_info(desc.relay_parent, session_index).is_none() {
 			return Err(Error::<T>::UnexpectedRelayParent)
 		}
 	}
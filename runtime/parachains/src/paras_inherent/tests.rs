@@ -1147,6 +1147,15 @@ mod sanitizers {
 		let keystore = Arc::new(keystore) as SyncCryptoStorePtr;
 		let signing_context = SigningContext { parent_hash: relay_parent, session_index };
 
+		let mut allowed_relay_parents = AllowedRelayParentsTracker::default();
+		allowed_relay_parents.update(
+			relay_parent,
+			Hash::zero(),
+			session_index,
+			RELAY_PARENT_NUM,
+			1,
+		);
+
 		let validators = vec![
 			keyring::Sr25519Keyring::Alice,
 			keyring::Sr25519Keyring::Bob,
@@ -1219,7 +1228,8 @@ mod sanitizers {
 		// happy path
 		assert_eq!(
 			sanitize_backed_candidates::<Test, _>(
-				relay_parent,
+				&allowed_relay_parents,
+				session_index,
 				backed_candidates.clone(),
 				has_concluded_invalid,
 				scheduled
@@ -1231,7 +1241,8 @@ mod sanitizers {
 		{
 			let scheduled = &[][..];
 			assert!(sanitize_backed_candidates::<Test, _>(
-				relay_parent,
+				&allowed_relay_parents,
+				session_index,
 				backed_candidates.clone(),
 				has_concluded_invalid,
 				scheduled
@@ -1241,9 +1252,29 @@ mod sanitizers {
 
 		// relay parent mismatch
 		{
-			let relay_parent = Hash::repeat_byte(0xFA);
+			let mut allowed_relay_parents = AllowedRelayParentsTracker::default();
+			allowed_relay_parents.update(
+				Hash::repeat_byte(0xFA),
+				Hash::zero(),
+				session_index,
+				RELAY_PARENT_NUM,
+				1,
+			);
+			assert!(sanitize_backed_candidates::<Test, _>(
+				&allowed_relay_parents,
+				session_index,
+				backed_candidates.clone(),
+				has_concluded_invalid,
+				scheduled
+			)
+			.is_empty());
+		}
+
+		// relay parent of another session
+		{
 			assert!(sanitize_backed_candidates::<Test, _>(
-				relay_parent,
+				&allowed_relay_parents,
+				session_index + 1,
 				backed_candidates.clone(),
 				has_concluded_invalid,
 				scheduled
@@ -1267,7 +1298,8 @@ mod sanitizers {
 				|_idx: usize, candidate: &BackedCandidate| set.contains(&candidate.hash());
 			assert_eq!(
 				sanitize_backed_candidates::<Test, _>(
-					relay_parent,
+					&allowed_relay_parents,
+					session_index,
 					backed_candidates.clone(),
 					has_concluded_invalid,
 					scheduled
@@ -96,7 +96,7 @@ mod tests {
 
 			let pubkeys = validator_pubkeys(&validators);
 
-			let shuffled_pubkeys =
+			let (shuffled_pubkeys, _) =
 				ParasShared::initializer_on_new_session(1, [1; 32], &config, pubkeys);
 
 			assert_eq!(
@@ -18,6 +18,8 @@
 //!
 //! These are exposed as different modules using different sets of primitives.
 //! At the moment there is only a v2 module and it is not completely clear how migration
-//! to a v2 would be done.
+//! to a v2 would be done. The vstaging module implements the staging API, which is not yet part
+//! of any stable version.
 
 pub mod v2;
+pub mod vstaging;
@@ -23,10 +23,11 @@ use crate::{
 };
 use primitives::v2::{
 	AuthorityDiscoveryId, CandidateEvent, CommittedCandidateReceipt, CoreIndex, CoreOccupied,
-	CoreState, GroupIndex, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage,
-	InboundHrmpMessage, OccupiedCore, OccupiedCoreAssumption, PersistedValidationData,
-	PvfCheckStatement, ScheduledCore, ScrapedOnChainVotes, SessionIndex, SessionInfo,
-	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+	CoreState, ExecutorParams, GroupIndex, GroupRotationInfo, Hash, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, OccupiedCore, OccupiedCoreAssumption,
+	PersistedValidationData, PvfCheckStatement, ScheduledCore, ScrapedOnChainVotes, SessionIndex,
+	SessionInfo, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 use sp_runtime::traits::One;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
@@ -335,7 +336,7 @@ where
 				CandidateEvent::CandidateBacked(c, h, core, group),
 			RawEvent::<T>::CandidateIncluded(c, h, core, group) =>
 				CandidateEvent::CandidateIncluded(c, h, core, group),
-			RawEvent::<T>::CandidateTimedOut(c, h, core) =>
+			RawEvent::<T>::CandidateTimedOut(c, h, core, _group) =>
 				CandidateEvent::CandidateTimedOut(c, h, core),
 			RawEvent::<T>::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
 		})
@@ -399,3 +400,10 @@ where
 		<paras::Pallet<T>>::current_code_hash(&para_id)
 	})
 }
+
+/// Implementation for the `session_executor_params` function of the runtime API.
+pub fn session_executor_params<T: session_info::Config>(
+	session_index: SessionIndex,
+) -> Option<ExecutorParams> {
+	<session_info::Pallet<T>>::session_executor_params(session_index)
+}
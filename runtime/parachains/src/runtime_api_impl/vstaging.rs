@@ -0,0 +1,227 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Put implementations of functions from staging APIs here.
+
+use crate::{
+	configuration, dmp, hrmp, inclusion, initializer, messaging_metrics, paras, paras_inherent,
+	runtime_api_impl::v2, shared, ump,
+};
+use primitives::vstaging::{
+	AsyncBackingParams, BackingState, CandidateEvent, CandidatePendingAvailability, Constraints,
+	Id as ParaId, InboundHrmpLimitations, MessageQueueSizes, OutboundHrmpChannelLimitations,
+	ScrapedOnChainVotes, SessionIndex,
+};
+use sp_std::prelude::*;
+
+/// Implementation for the `staging_para_backing_state` function of the staging runtime API.
+pub fn backing_state<T: initializer::Config>(
+	para_id: ParaId,
+) -> Option<BackingState<T::Hash, T::BlockNumber>> {
+	let config = <configuration::Pallet<T>>::config();
+
+	// Candidates of the next block may be built on top of this block or on one of the allowed
+	// ancestors.
+	let min_relay_parent_number = <shared::Pallet<T>>::allowed_relay_parents()
+		.hypothetical_earliest_block_number(
+			<frame_system::Pallet<T>>::block_number(),
+			<shared::Pallet<T>>::session_index(),
+			config.async_backing_params.allowed_ancestry_len,
+		);
+
+	let required_parent = <paras::Pallet<T>>::para_head(para_id)?;
+	let validation_code_hash = <paras::Pallet<T>>::current_code_hash(para_id)?;
+
+	let upgrade_restriction = <paras::Pallet<T>>::upgrade_restriction_signal(para_id);
+	let future_validation_code = <paras::Pallet<T>>::future_code_upgrade_at(para_id)
+		.zip(<paras::Pallet<T>>::future_code_hash(para_id));
+
+	let (ump_msg_count, ump_total_bytes) = ump::RelayDispatchQueueSize::<T>::get(para_id);
+	let ump_remaining = config.max_upward_queue_count.saturating_sub(ump_msg_count);
+	let ump_remaining_bytes = config.max_upward_queue_size.saturating_sub(ump_total_bytes);
+
+	let dmp_remaining_messages = <dmp::Pallet<T>>::dmq_contents(para_id)
+		.into_iter()
+		.map(|msg| msg.sent_at)
+		.collect();
+
+	let hrmp_inbound =
+		InboundHrmpLimitations { valid_watermarks: <hrmp::Pallet<T>>::valid_watermarks(para_id) };
+	let hrmp_channels_out = <hrmp::Pallet<T>>::outbound_remaining_capacity(para_id)
+		.into_iter()
+		.map(|(para, (messages_remaining, bytes_remaining))| {
+			(para, OutboundHrmpChannelLimitations { messages_remaining, bytes_remaining })
+		})
+		.collect();
+
+	let constraints = Constraints {
+		min_relay_parent_number,
+		max_pov_size: config.max_pov_size,
+		max_code_size: config.max_code_size,
+		ump_remaining,
+		ump_remaining_bytes,
+		max_ump_num_per_candidate: config.max_upward_message_num_per_candidate,
+		dmp_remaining_messages,
+		hrmp_inbound,
+		hrmp_channels_out,
+		max_hrmp_num_per_candidate: config.hrmp_max_message_num_per_candidate,
+		required_parent,
+		validation_code_hash,
+		upgrade_restriction,
+		future_validation_code,
+	};
+
+	// At most one candidate of a para is pending availability at a time, but the API is ready
+	// for a chain of them.
+	let pending_availability = <inclusion::Pallet<T>>::pending_availability(para_id)
+		.zip(inclusion::PendingAvailabilityCommitments::<T>::get(para_id))
+		.map(|(pending, commitments)| CandidatePendingAvailability {
+			candidate_hash: pending.candidate_hash(),
+			descriptor: pending.candidate_descriptor().clone(),
+			commitments,
+			relay_parent_number: pending.relay_parent_number(),
+			max_pov_size: constraints.max_pov_size,
+		})
+		.into_iter()
+		.collect();
+
+	Some(BackingState { constraints, pending_availability })
+}
+
+/// Implementation for the `staging_async_backing_params` function of the staging runtime API.
+pub fn async_backing_params<T: initializer::Config>() -> AsyncBackingParams {
+	<configuration::Pallet<T>>::config().async_backing_params
+}
+
+/// Implementation for the `staging_candidate_events_for_para` function of the staging runtime API.
+pub fn candidate_events_for_para<T, F>(
+	para_id: ParaId,
+	extract_event: F,
+) -> Vec<CandidateEvent<T::Hash>>
+where
+	T: initializer::Config,
+	F: Fn(<T as frame_system::Config>::Event) -> Option<inclusion::Event<T>>,
+{
+	v2::candidate_events::<T, F>(extract_event)
+		.into_iter()
+		.filter(|event| {
+			let receipt = match event {
+				CandidateEvent::CandidateBacked(receipt, ..) |
+				CandidateEvent::CandidateIncluded(receipt, ..) |
+				CandidateEvent::CandidateTimedOut(receipt, ..) => receipt,
+			};
+			receipt.descriptor.para_id == para_id
+		})
+		.collect()
+}
+
+/// Implementation for the `staging_on_chain_votes_in_session` function of the staging runtime API.
+pub fn on_chain_votes_in_session<T: paras_inherent::Config>(
+	session: SessionIndex,
+) -> Option<ScrapedOnChainVotes<T::Hash>> {
+	v2::on_chain_votes::<T>().filter(|votes| votes.session == session)
+}
+
+/// Implementation for the `staging_message_queue_sizes` function of the staging runtime API.
+pub fn message_queue_sizes<T: messaging_metrics::Config>() -> MessageQueueSizes {
+	<messaging_metrics::Pallet<T>>::message_queue_sizes()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Event as MockEvent, System, Test};
+	use polkadot_primitives_test_helpers::dummy_candidate_receipt;
+	use primitives::v2::{CoreIndex, GroupIndex, HeadData};
+	use sp_core::H256;
+
+	fn extract_event(event: MockEvent) -> Option<inclusion::Event<Test>> {
+		match event {
+			MockEvent::ParaInclusion(event) => Some(event),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn candidate_events_for_para_filters_by_para() {
+		let (a, b) = (ParaId::from(1000), ParaId::from(2000));
+		let receipt = |para_id| {
+			let mut receipt = dummy_candidate_receipt(H256::zero());
+			receipt.descriptor.para_id = para_id;
+			receipt
+		};
+
+		new_test_ext(Default::default()).execute_with(|| {
+			// events are not stored in the genesis block.
+			System::set_block_number(1);
+			for (para_id, core) in [(a, 0), (b, 1), (a, 2)] {
+				System::deposit_event(inclusion::Event::<Test>::CandidateBacked(
+					receipt(para_id),
+					HeadData::default(),
+					CoreIndex(core),
+					GroupIndex(0),
+				));
+			}
+			System::deposit_event(inclusion::Event::<Test>::CandidateTimedOut(
+				receipt(b),
+				HeadData::default(),
+				CoreIndex(1),
+				GroupIndex(0),
+			));
+
+			let events_a = candidate_events_for_para::<Test, _>(a, extract_event);
+			assert_eq!(
+				events_a,
+				vec![
+					CandidateEvent::CandidateBacked(
+						receipt(a),
+						HeadData::default(),
+						CoreIndex(0),
+						GroupIndex(0),
+					),
+					CandidateEvent::CandidateBacked(
+						receipt(a),
+						HeadData::default(),
+						CoreIndex(2),
+						GroupIndex(0),
+					),
+				],
+			);
+			assert_eq!(candidate_events_for_para::<Test, _>(b, extract_event).len(), 2);
+			assert!(
+				candidate_events_for_para::<Test, _>(ParaId::from(3000), extract_event).is_empty()
+			);
+		});
+	}
+
+	#[test]
+	fn on_chain_votes_in_session_filters_by_session() {
+		new_test_ext(Default::default()).execute_with(|| {
+			assert_eq!(on_chain_votes_in_session::<Test>(0), None);
+
+			let votes = ScrapedOnChainVotes {
+				session: 2,
+				backing_validators_per_candidate: Vec::new(),
+				disputes: Vec::new(),
+			};
+			paras_inherent::OnChainVotes::<Test>::put(votes.clone());
+
+			assert_eq!(on_chain_votes_in_session::<Test>(2), Some(votes));
+			assert_eq!(on_chain_votes_in_session::<Test>(1), None);
+			assert_eq!(on_chain_votes_in_session::<Test>(3), None);
+		});
+	}
+}
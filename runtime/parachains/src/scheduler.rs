@@ -51,6 +51,8 @@ pub use pallet::*;
 #[cfg(test)]
 mod tests;
 
+pub mod runtime_api;
+
 /// A queued parathread entry, pre-assigned to a core.
 #[derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(test, derive(PartialEq, Debug))]
@@ -71,12 +73,37 @@ pub struct ParathreadClaimQueue {
 impl ParathreadClaimQueue {
 	/// Queue a parathread entry to be processed.
 	///
-	/// Provide the entry and the number of parathread cores, which must be greater than 0.
-	fn enqueue_entry(&mut self, entry: ParathreadEntry, n_parathread_cores: u32) {
-		let core_offset = self.next_core_offset;
-		self.next_core_offset = (self.next_core_offset + 1) % n_parathread_cores;
+	/// Provide the entry, the number of parathread cores, which must be greater than 0, and the
+	/// maximum number of entries which may be queued on a single core. The entry is assigned to
+	/// the next core in turn whose queue is not full.
+	///
+	/// Returns `false` without queueing the entry if the queues of all cores are full.
+	fn enqueue_entry(
+		&mut self,
+		entry: ParathreadEntry,
+		n_parathread_cores: u32,
+		max_per_core: u32,
+	) -> bool {
+		let core_offset = (0..n_parathread_cores)
+			.map(|i| (self.next_core_offset + i) % n_parathread_cores)
+			.find(|core_offset| self.len_on_core(*core_offset) < max_per_core as usize);
+
+		match core_offset {
+			Some(core_offset) => {
+				self.next_core_offset = (core_offset + 1) % n_parathread_cores;
+				self.queue.push(QueuedParathread { claim: entry, core_offset });
+				true
+			},
+			None => false,
+		}
+	}
 
-		self.queue.push(QueuedParathread { claim: entry, core_offset })
+	/// Queue a parathread entry ahead of all other entries with the given core offset.
+	///
+	/// This is used for entries which were already occupying the core, so it is not subject to
+	/// the bound on the number of entries queued on a single core.
+	fn bump_entry(&mut self, entry: ParathreadEntry, core_offset: u32) {
+		self.queue.insert(0, QueuedParathread { claim: entry, core_offset })
 	}
 
 	/// Take next queued entry with given core offset, if any.
@@ -87,8 +114,20 @@ impl ParathreadClaimQueue {
 
 	/// Get the next queued entry with given core offset, if any.
 	fn get_next_on_core(&self, core_offset: u32) -> Option<&ParathreadEntry> {
-		let pos = self.queue.iter().position(|queued| queued.core_offset == core_offset);
-		pos.map(|i| &self.queue[i].claim)
+		self.iter_on_core(core_offset).next()
+	}
+
+	/// Iterate over the queued entries with given core offset, in the order they will be taken.
+	fn iter_on_core(&self, core_offset: u32) -> impl Iterator<Item = &ParathreadEntry> {
+		self.queue
+			.iter()
+			.filter(move |queued| queued.core_offset == core_offset)
+			.map(|queued| &queued.claim)
+	}
+
+	/// The number of queued entries with given core offset.
+	fn len_on_core(&self, core_offset: u32) -> usize {
+		self.iter_on_core(core_offset).count()
 	}
 }
 
@@ -179,6 +218,7 @@ pub mod pallet {
 	///
 	/// The number of queued claims is bounded at the `scheduling_lookahead`
 	/// multiplied by the number of parathread multiplexer cores. Reasonably, 10 * 50 = 500.
+	/// New claims are only mapped onto a core with fewer than `scheduling_lookahead` claims queued.
 	#[pallet::storage]
 	pub(crate) type ParathreadQueue<T> = StorageValue<_, ParathreadClaimQueue, ValueQuery>;
 
@@ -235,11 +275,13 @@ impl<T: Config> Pallet<T> {
 	/// Called by the initializer to note that a new session has started.
 	pub(crate) fn initializer_on_new_session(
 		notification: &SessionChangeNotification<T::BlockNumber>,
-	) {
+	) -> Weight {
 		let &SessionChangeNotification { ref validators, ref new_config, .. } = notification;
 		let config = new_config;
 
 		let mut thread_queue = ParathreadQueue::<T>::get();
+		// Every queued claim is checked against the paras pallet once.
+		let n_queued = thread_queue.queue.len() as Weight;
 		let n_parachains = <paras::Pallet<T>>::parachains().len() as u32;
 		let n_cores = core::cmp::max(
 			n_parachains + config.parathread_cores,
@@ -340,13 +382,29 @@ impl<T: Config> Pallet<T> {
 
 		let now = <frame_system::Pallet<T>>::block_number() + One::one();
 		<SessionStartBlock<T>>::set(now);
+
+		T::DbWeight::get().reads_writes(4 + n_queued, 5)
+	}
+
+	/// The number of parathread claims currently queued, along with the maximum number of claims
+	/// the queue may hold under the active configuration.
+	pub(crate) fn parathread_queue_depth() -> (u32, u32) {
+		let config = <configuration::Pallet<T>>::config();
+		let queue_max_size = config.parathread_cores.saturating_mul(config.scheduling_lookahead);
+
+		(ParathreadQueue::<T>::get().queue.len() as u32, queue_max_size)
+	}
+
+	/// Whether a claim for the given parathread is queued or currently assigned to a core.
+	pub(crate) fn has_parathread_claim(para_id: ParaId) -> bool {
+		ParathreadClaimIndex::<T>::get().binary_search(&para_id).is_ok()
 	}
 
 	/// Add a parathread claim to the queue. If there is a competing claim in the queue or currently
-	/// assigned to a core, this call will fail. This call will also fail if the queue is full.
+	/// assigned to a core, this call will fail. This call will also fail if the queue is full, or
+	/// if the queue of every parathread core already holds `scheduling_lookahead` claims.
 	///
 	/// Fails if the claim does not correspond to any live parathread.
-	#[allow(unused)]
 	pub fn add_parathread_claim(claim: ParathreadClaim) {
 		if !<paras::Pallet<T>>::is_parathread(claim.0) {
 			return
@@ -362,21 +420,28 @@ impl<T: Config> Pallet<T> {
 
 			let para_id = claim.0;
 
-			let competes_with_another =
-				ParathreadClaimIndex::<T>::mutate(|index| match index.binary_search(&para_id) {
-					Ok(_) => true,
-					Err(i) => {
-						index.insert(i, para_id);
-						false
-					},
-				});
+			ParathreadClaimIndex::<T>::mutate(|index| {
+				// a competing claim is already queued or assigned to a core.
+				let i = match index.binary_search(&para_id) {
+					Ok(_) => return,
+					Err(i) => i,
+				};
 
-			if competes_with_another {
-				return
-			}
+				let entry = ParathreadEntry { claim, retries: 0 };
+				if queue.enqueue_entry(entry, config.parathread_cores, config.scheduling_lookahead)
+				{
+					index.insert(i, para_id);
+				}
+			})
+		})
+	}
 
-			let entry = ParathreadEntry { claim, retries: 0 };
-			queue.enqueue_entry(entry, config.parathread_cores);
+	/// Remove a parathread from the claim index, opening it up for further claims.
+	fn remove_parathread_claim(para_id: ParaId) {
+		ParathreadClaimIndex::<T>::mutate(|index| {
+			if let Ok(i) = index.binary_search(&para_id) {
+				index.remove(i);
+			}
 		})
 	}
 
@@ -384,6 +449,7 @@ impl<T: Config> Pallet<T> {
 	/// for them being freed. The list is assumed to be sorted in ascending order by core index.
 	pub(crate) fn free_cores(just_freed_cores: impl IntoIterator<Item = (CoreIndex, FreedReason)>) {
 		let config = <configuration::Pallet<T>>::config();
+		let n_parachains = <paras::Pallet<T>>::parachains().len() as u32;
 
 		AvailabilityCores::<T>::mutate(|cores| {
 			for (freed_index, freed_reason) in just_freed_cores {
@@ -396,17 +462,22 @@ impl<T: Config> Pallet<T> {
 								FreedReason::Concluded => {
 									// After a parathread candidate has successfully been included,
 									// open it up for further claims!
-									ParathreadClaimIndex::<T>::mutate(|index| {
-										if let Ok(i) = index.binary_search(&entry.claim.0) {
-											index.remove(i);
-										}
-									})
+									Self::remove_parathread_claim(entry.claim.0)
+								},
+								FreedReason::TimedOut if config.parathread_cores == 0 => {
+									// defensive: there is no core left to retry the claim on.
+									Self::remove_parathread_claim(entry.claim.0)
 								},
 								FreedReason::TimedOut => {
 									// If a parathread candidate times out, it's not the collator's fault,
-									// so we don't increment retries.
+									// so we don't increment retries. Instead, the claim is bumped to the
+									// front of the queue of its core, so that it is retried as soon as
+									// the core is free again.
+									let core_offset = freed_index.0.saturating_sub(n_parachains) %
+										config.parathread_cores;
+
 									ParathreadQueue::<T>::mutate(|queue| {
-										queue.enqueue_entry(entry, config.parathread_cores);
+										queue.bump_entry(entry, core_offset);
 									})
 								},
 							}
@@ -705,42 +776,88 @@ impl<T: Config> Pallet<T> {
 	/// occupied and the candidate occupying it became available.
 	///
 	/// For parachains, this is always the ID of the parachain and no specified collator.
-	/// For parathreads, this is based on the next item in the `ParathreadQueue` assigned to that
-	/// core, or if there isn't one, the claim that is currently occupying the core, as long
-	/// as the claim's retries would not exceed the limit. Otherwise None.
+	/// For parathreads, this is the claim that is currently occupying the core, as it is bumped to
+	/// the front of the queue after timing out, or if there isn't one, the next item in the
+	/// `ParathreadQueue` assigned to that core. Otherwise None.
 	pub(crate) fn next_up_on_time_out(core: CoreIndex) -> Option<ScheduledCore> {
 		let parachains = <paras::Pallet<T>>::parachains();
 		if (core.0 as usize) < parachains.len() {
 			Some(ScheduledCore { para_id: parachains[core.0 as usize], collator: None })
 		} else {
-			let queue = ParathreadQueue::<T>::get();
-
-			// This is the next scheduled para on this core.
-			let core_offset = (core.0 as usize - parachains.len()) as u32;
-			queue
-				.get_next_on_core(core_offset)
-				.map(|entry| ScheduledCore {
-					para_id: entry.claim.0,
-					collator: Some(entry.claim.1.clone()),
+			let cores = AvailabilityCores::<T>::get();
+
+			// This is the claim currently occupying the core.
+			cores
+				.get(core.0 as usize)
+				.and_then(|c| c.as_ref())
+				.and_then(|o| {
+					match o {
+						CoreOccupied::Parathread(entry) => Some(ScheduledCore {
+							para_id: entry.claim.0,
+							collator: Some(entry.claim.1.clone()),
+						}),
+						CoreOccupied::Parachain => None, // defensive; not possible.
+					}
 				})
 				.or_else(|| {
-					// Or, if none, the claim currently occupying the core,
-					// as it would be put back on the queue after timing out.
-					let cores = AvailabilityCores::<T>::get();
-					cores.get(core.0 as usize).and_then(|c| c.as_ref()).and_then(|o| {
-						match o {
-							CoreOccupied::Parathread(entry) => Some(ScheduledCore {
-								para_id: entry.claim.0,
-								collator: Some(entry.claim.1.clone()),
-							}),
-							CoreOccupied::Parachain => None, // defensive; not possible.
-						}
+					// Or, if none, the next scheduled para on this core.
+					let queue = ParathreadQueue::<T>::get();
+					let core_offset = (core.0 as usize - parachains.len()) as u32;
+					queue.get_next_on_core(core_offset).map(|entry| ScheduledCore {
+						para_id: entry.claim.0,
+						collator: Some(entry.claim.1.clone()),
 					})
 				})
 		}
 	}
 
+	/// The assignments of cores expected for each of the next `lookahead` blocks, paired with the
+	/// number of the block they are expected at. `lookahead` is capped at `scheduling_lookahead`.
+	///
+	/// Parachain cores are always assigned to their parachain. The claims queued on a parathread
+	/// core are expected to be assigned one per block, in order, which is the earliest they can be
+	/// assigned if every candidate before them becomes available in time. This lets collators of
+	/// parathreads know when to build.
+	pub fn upcoming_assignments(lookahead: u32) -> Vec<(T::BlockNumber, CoreAssignment)> {
+		let config = <configuration::Pallet<T>>::config();
+		let now = <frame_system::Pallet<T>>::block_number();
+		let parachains = <paras::Pallet<T>>::parachains();
+		let queue = ParathreadQueue::<T>::get();
+		let n_cores = AvailabilityCores::<T>::get().len();
+
+		let mut assignments = Vec::new();
+		for ahead in 0..lookahead.min(config.scheduling_lookahead) {
+			let at = now + (ahead + 1).into();
+
+			for core_index in 0..n_cores {
+				let core = CoreIndex(core_index as u32);
+				let group_idx = match Self::group_assigned_to_core(core, at) {
+					Some(group_idx) => group_idx,
+					None => continue,
+				};
+
+				let (para_id, kind) = if core_index < parachains.len() {
+					(parachains[core_index], AssignmentKind::Parachain)
+				} else {
+					let core_offset = (core_index - parachains.len()) as u32;
+					match queue.iter_on_core(core_offset).nth(ahead as usize) {
+						Some(entry) => (
+							entry.claim.0,
+							AssignmentKind::Parathread(entry.claim.1.clone(), entry.retries),
+						),
+						None => continue,
+					}
+				};
+
+				assignments.push((at, CoreAssignment { core, para_id, kind, group_idx }));
+			}
+		}
+
+		assignments
+	}
+
 	// Free all scheduled cores and return parathread claims to queue, with retries incremented.
+	// Claims beyond retries, or which no longer fit in the queue, are dropped.
 	pub(crate) fn clear() {
 		let config = <configuration::Pallet<T>>::config();
 		ParathreadQueue::<T>::mutate(|queue| {
@@ -755,8 +872,15 @@ impl<T: Config> Pallet<T> {
 						retries: retries + 1,
 					};
 
-					if entry.retries <= config.parathread_retries {
-						queue.enqueue_entry(entry, config.parathread_cores);
+					let requeued = entry.retries <= config.parathread_retries &&
+						queue.enqueue_entry(
+							entry,
+							config.parathread_cores,
+							config.scheduling_lookahead,
+						);
+
+					if !requeued {
+						Self::remove_parathread_claim(core_assignment.para_id);
 					}
 				}
 			}
@@ -0,0 +1,38 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definitions for querying the scheduler pallet.
+
+use super::CoreAssignment;
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying upcoming core assignments, so that collators know when to build a
+	/// block for their para.
+	pub trait SchedulerApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The assignments of cores expected for each of the next `lookahead` blocks, paired with
+		/// the number of the block they are expected at.
+		///
+		/// `lookahead` is capped at the `scheduling_lookahead` of the active configuration. Queued
+		/// parathread claims are expected to be assigned one per block, which is the earliest
+		/// they can be assigned.
+		fn upcoming_core_assignments(lookahead: u32) -> Vec<(BlockNumber, CoreAssignment)>;
+	}
+}
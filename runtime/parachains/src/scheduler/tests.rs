@@ -255,6 +255,7 @@ fn session_change_prunes_cores_beyond_retries_and_those_from_non_live_parathread
 					retries: max_parathread_retries + 1,
 				},
 				4,
+				2,
 			);
 
 			// Will not be pruned.
@@ -264,18 +265,21 @@ fn session_change_prunes_cores_beyond_retries_and_those_from_non_live_parathread
 					retries: max_parathread_retries,
 				},
 				4,
+				2,
 			);
 
 			// Will not be pruned.
 			queue.enqueue_entry(
 				ParathreadEntry { claim: ParathreadClaim(thread_c, collator.clone()), retries: 0 },
 				4,
+				2,
 			);
 
 			// Will be pruned: not a live parathread.
 			queue.enqueue_entry(
 				ParathreadEntry { claim: ParathreadClaim(thread_d, collator.clone()), retries: 0 },
 				4,
+				2,
 			);
 
 			queue
@@ -686,7 +690,7 @@ fn schedule_schedules_including_just_freed() {
 				scheduled[2],
 				CoreAssignment {
 					core: CoreIndex(3),
-					para_id: thread_e,
+					para_id: thread_c,
 					kind: AssignmentKind::Parathread(collator.clone(), 0),
 					group_idx: GroupIndex(3),
 				}
@@ -709,16 +713,16 @@ fn schedule_schedules_including_just_freed() {
 			// thread A claim should have been wiped, but thread C claim should remain.
 			assert_eq!(index, vec![thread_b, thread_c, thread_d, thread_e]);
 
-			// Although C was descheduled, the core `4`  was occupied so C goes back on the queue.
+			// C timed out, so it was bumped ahead of the claim on E, which remains queued.
 			assert_eq!(parathread_queue.queue.len(), 1);
 			assert_eq!(
 				parathread_queue.queue[0],
 				QueuedParathread {
 					claim: ParathreadEntry {
-						claim: ParathreadClaim(thread_c, collator.clone()),
-						retries: 0, // retries not incremented by timeout - validators' fault.
+						claim: ParathreadClaim(thread_e, collator.clone()),
+						retries: 0,
 					},
-					core_offset: 2, // reassigned to next core. thread_e claim was on offset 1.
+					core_offset: 1,
 				}
 			);
 		}
@@ -948,6 +952,9 @@ fn parathread_claims_are_pruned_after_retries() {
 
 		run_to_block(2 + max_retries + 1, |_| None);
 		assert_eq!(Scheduler::scheduled().len(), 0);
+
+		// the pruned claims no longer prevent further claims.
+		assert!(ParathreadClaimIndex::<Test>::get().is_empty());
 	});
 }
 
@@ -1449,3 +1456,140 @@ fn parathread_claims_are_pruned_after_deregistration() {
 		assert_eq!(Scheduler::scheduled().len(), 1);
 	});
 }
+
+#[test]
+fn parathread_claims_are_bounded_per_core() {
+	let collator = CollatorId::from(Sr25519Keyring::Alice.public());
+	let entry = |id: u32| ParathreadEntry {
+		claim: ParathreadClaim(ParaId::from(id), collator.clone()),
+		retries: 0,
+	};
+
+	let mut queue = ParathreadClaimQueue::default();
+
+	// one claim per core.
+	assert!(queue.enqueue_entry(entry(1), 2, 1));
+	assert!(queue.enqueue_entry(entry(2), 2, 1));
+	assert!(!queue.enqueue_entry(entry(3), 2, 1));
+	assert_eq!(queue.len_on_core(0), 1);
+	assert_eq!(queue.len_on_core(1), 1);
+
+	// bumped claims go ahead of the others on their core, regardless of the bound.
+	queue.bump_entry(entry(4), 1);
+	assert_eq!(queue.iter_on_core(1).cloned().collect::<Vec<_>>(), vec![entry(4), entry(2)]);
+	assert!(!queue.enqueue_entry(entry(3), 2, 1));
+
+	// as soon as a core has room, new claims are mapped onto it.
+	assert_eq!(queue.take_next_on_core(0), Some(entry(1)));
+	assert!(queue.enqueue_entry(entry(3), 2, 1));
+	assert_eq!(queue.get_next_on_core(0), Some(&entry(3)));
+	assert_eq!(queue.next_core_offset, 1);
+}
+
+#[test]
+fn timed_out_claims_are_bumped_and_upcoming_assignments_follow_the_queue() {
+	let mut config = default_config();
+	config.parathread_cores = 1;
+
+	let genesis_config = MockGenesisConfig {
+		configuration: crate::configuration::GenesisConfig {
+			config: config.clone(),
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	let chain_a = ParaId::from(1);
+	let thread_a = ParaId::from(2);
+	let thread_b = ParaId::from(3);
+	let thread_c = ParaId::from(4);
+
+	let collator = CollatorId::from(Sr25519Keyring::Alice.public());
+	let chain_assignment = |at: BlockNumber| {
+		(
+			at,
+			CoreAssignment {
+				core: CoreIndex(0),
+				para_id: chain_a,
+				kind: AssignmentKind::Parachain,
+				group_idx: GroupIndex(0),
+			},
+		)
+	};
+	let thread_assignment = |at: BlockNumber, para_id: ParaId| {
+		(
+			at,
+			CoreAssignment {
+				core: CoreIndex(1),
+				para_id,
+				kind: AssignmentKind::Parathread(collator.clone(), 0),
+				group_idx: GroupIndex(1),
+			},
+		)
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		schedule_blank_para(chain_a, true);
+		schedule_blank_para(thread_a, false);
+		schedule_blank_para(thread_b, false);
+		schedule_blank_para(thread_c, false);
+
+		// start a new session to activate, 2 validators for 2 cores.
+		run_to_block(1, |number| match number {
+			1 => Some(SessionChangeNotification {
+				new_config: config.clone(),
+				validators: vec![
+					ValidatorId::from(Sr25519Keyring::Alice.public()),
+					ValidatorId::from(Sr25519Keyring::Eve.public()),
+				],
+				..Default::default()
+			}),
+			_ => None,
+		});
+
+		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
+		Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone()));
+
+		// the lookahead is capped at `scheduling_lookahead`.
+		assert_eq!(
+			Scheduler::upcoming_assignments(5),
+			vec![
+				chain_assignment(2),
+				thread_assignment(2, thread_a),
+				chain_assignment(3),
+				thread_assignment(3, thread_b),
+			],
+		);
+
+		run_to_block(2, |_| None);
+		Scheduler::occupied(&[CoreIndex(0), CoreIndex(1)]);
+		Scheduler::add_parathread_claim(ParathreadClaim(thread_c, collator.clone()));
+		assert_eq!(
+			Scheduler::upcoming_assignments(2),
+			vec![
+				chain_assignment(3),
+				thread_assignment(3, thread_b),
+				chain_assignment(4),
+				thread_assignment(4, thread_c),
+			],
+		);
+
+		// the claim occupying the core would be retried first after timing out.
+		assert_eq!(
+			Scheduler::next_up_on_time_out(CoreIndex(1)).unwrap(),
+			ScheduledCore { para_id: thread_a, collator: Some(collator.clone()) }
+		);
+
+		Scheduler::free_cores(vec![(CoreIndex(1), FreedReason::TimedOut)]);
+		assert_eq!(
+			Scheduler::upcoming_assignments(2),
+			vec![
+				chain_assignment(3),
+				thread_assignment(3, thread_a),
+				chain_assignment(4),
+				thread_assignment(4, thread_b),
+			],
+		);
+		assert_eq!(ParathreadClaimIndex::<Test>::get(), vec![thread_a, thread_b, thread_c]);
+	});
+}
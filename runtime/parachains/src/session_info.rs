@@ -24,7 +24,9 @@ use crate::{
 	util::{take_active_subset, take_active_subset_and_inactive},
 };
 use frame_support::{pallet_prelude::*, traits::OneSessionHandler};
-use primitives::v2::{AssignmentId, AuthorityDiscoveryId, SessionIndex, SessionInfo};
+use primitives::v2::{
+	AssignmentId, AuthorityDiscoveryId, ExecutorParams, SessionIndex, SessionInfo,
+};
 use sp_std::vec::Vec;
 
 pub use pallet::*;
@@ -75,6 +77,13 @@ pub mod pallet {
 	#[pallet::getter(fn session_info)]
 	pub(crate) type Sessions<T: Config> = StorageMap<_, Identity, SessionIndex, SessionInfo>;
 
+	/// Executor parameter set for a given session index.
+	/// Has an entry for every session in `Sessions`.
+	#[pallet::storage]
+	#[pallet::getter(fn session_executor_params)]
+	pub(crate) type SessionExecutorParams<T: Config> =
+		StorageMap<_, Identity, SessionIndex, ExecutorParams>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_runtime_upgrade() -> Weight {
@@ -100,8 +109,11 @@ impl<T: Config> Pallet<T> {
 	/// Handle an incoming session change.
 	pub(crate) fn initializer_on_new_session(
 		notification: &crate::initializer::SessionChangeNotification<T::BlockNumber>,
-	) {
+	) -> Weight {
 		let config = <configuration::Pallet<T>>::config();
+		// The configuration, the keys, the active set, the validator groups, the cores and the
+		// earliest stored session.
+		let mut weight = T::DbWeight::get().reads(7);
 
 		let dispute_period = config.dispute_period;
 
@@ -126,9 +138,12 @@ impl<T: Config> Pallet<T> {
 			core::cmp::max(new_earliest_stored_session, old_earliest_stored_session);
 		// remove all entries from `Sessions` from the previous value up to the new value
 		// avoid a potentially heavy loop when introduced on a live chain
+		weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 		if old_earliest_stored_session != 0 || Sessions::<T>::get(0).is_some() {
 			for idx in old_earliest_stored_session..new_earliest_stored_session {
 				Sessions::<T>::remove(&idx);
+				SessionExecutorParams::<T>::remove(&idx);
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
 			}
 			// update `EarliestStoredSession` based on `config.dispute_period`
 			EarliestStoredSession::<T>::set(new_earliest_stored_session);
@@ -153,6 +168,10 @@ impl<T: Config> Pallet<T> {
 			dispute_period,
 		};
 		Sessions::<T>::insert(&new_session_index, &new_session_info);
+		// no parameters are configured yet, so every session runs in the default environment.
+		SessionExecutorParams::<T>::insert(&new_session_index, ExecutorParams::default());
+
+		weight.saturating_add(T::DbWeight::get().writes(2))
 	}
 
 	/// Called by the initializer to initialize the session info pallet.
@@ -106,6 +106,8 @@ fn session_pruning_is_based_on_dispute_period() {
 		assert!(Sessions::<Test>::get(7).is_none());
 		assert!(Sessions::<Test>::get(8).is_some());
 		assert!(Sessions::<Test>::get(9).is_some());
+		assert!(SessionExecutorParams::<Test>::get(7).is_none());
+		assert_eq!(SessionExecutorParams::<Test>::get(8), Some(ExecutorParams::default()));
 
 		// changing `dispute_period` works
 		let dispute_period = 5;
@@ -21,7 +21,8 @@
 
 use frame_support::pallet_prelude::*;
 use primitives::v2::{SessionIndex, ValidatorId, ValidatorIndex};
-use sp_std::vec::Vec;
+use sp_runtime::traits::AtLeast32BitUnsigned;
+use sp_std::{collections::vec_deque::VecDeque, vec::Vec};
 
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_chacha::ChaCha20Rng;
@@ -38,6 +39,75 @@ pub(crate) const SESSION_DELAY: SessionIndex = 2;
 #[cfg(test)]
 mod tests;
 
+/// The relay parents that backed candidates may currently be built on.
+///
+/// This is a ring buffer of the most recent relay-chain blocks, each with the session in which
+/// candidates built on top of it are backed. Only the `max_ancestry_len` most recent blocks
+/// before the latest one are kept.
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AllowedRelayParentsTracker<Hash, BlockNumber> {
+	/// The relay parent hashes with their state roots and sessions, oldest first.
+	buffer: VecDeque<(Hash, Hash, SessionIndex)>,
+	/// The number of the most recent relay parent, if any.
+	latest_number: BlockNumber,
+}
+
+impl<Hash: PartialEq + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	AllowedRelayParentsTracker<Hash, BlockNumber>
+{
+	/// Add a new relay parent, pruning the ones which are now older than `max_ancestry_len`
+	/// blocks.
+	///
+	/// `number` must be the number of the block following the previously added relay parent.
+	pub(crate) fn update(
+		&mut self,
+		relay_parent: Hash,
+		state_root: Hash,
+		session: SessionIndex,
+		number: BlockNumber,
+		max_ancestry_len: u32,
+	) {
+		// The most recent relay parent is always allowed.
+		let buffer_size_limit = max_ancestry_len as usize + 1;
+
+		self.buffer.push_back((relay_parent, state_root, session));
+		self.latest_number = number;
+		while self.buffer.len() > buffer_size_limit {
+			let _ = self.buffer.pop_front();
+		}
+	}
+
+	/// Get the state root and the number of `relay_parent`, if candidates backed in `session`
+	/// may be built on it.
+	pub fn acquire_info(
+		&self,
+		relay_parent: Hash,
+		session: SessionIndex,
+	) -> Option<(Hash, BlockNumber)> {
+		let pos = self.buffer.iter().position(|(hash, _, entry_session)| {
+			*hash == relay_parent && *entry_session == session
+		})?;
+		let age = (self.buffer.len() - 1) - pos;
+		let number = self.latest_number - BlockNumber::from(age as u32);
+
+		Some((self.buffer[pos].1, number))
+	}
+
+	/// The number of the oldest relay parent candidates backed in `session` may be built on once
+	/// the block `now` has been added.
+	pub fn hypothetical_earliest_block_number(
+		&self,
+		now: BlockNumber,
+		session: SessionIndex,
+		max_ancestry_len: u32,
+	) -> BlockNumber {
+		let same_session =
+			self.buffer.iter().rev().take_while(|(_, _, s)| *s == session).count() as u32;
+
+		now - max_ancestry_len.min(same_session).into()
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -68,6 +138,12 @@ pub mod pallet {
 	#[pallet::getter(fn active_validator_keys)]
 	pub(super) type ActiveValidatorKeys<T: Config> = StorageValue<_, Vec<ValidatorId>, ValueQuery>;
 
+	/// The relay parents that backed candidates may currently be built on.
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_relay_parents)]
+	pub(crate) type AllowedRelayParents<T: Config> =
+		StorageValue<_, AllowedRelayParentsTracker<T::Hash, T::BlockNumber>, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
 }
@@ -83,13 +159,13 @@ impl<T: Config> Pallet<T> {
 
 	/// Called by the initializer to note that a new session has started.
 	///
-	/// Returns the list of outgoing paras from the actions queue.
+	/// Returns the shuffled active validators of the new session and the weight consumed.
 	pub(crate) fn initializer_on_new_session(
 		session_index: SessionIndex,
 		random_seed: [u8; 32],
 		new_config: &HostConfiguration<T::BlockNumber>,
 		all_validators: Vec<ValidatorId>,
-	) -> Vec<ValidatorId> {
+	) -> (Vec<ValidatorId>, Weight) {
 		CurrentSessionIndex::<T>::set(session_index);
 		let mut rng: ChaCha20Rng = SeedableRng::from_seed(random_seed);
 
@@ -110,7 +186,24 @@ impl<T: Config> Pallet<T> {
 		ActiveValidatorIndices::<T>::set(shuffled_indices);
 		ActiveValidatorKeys::<T>::set(active_validator_keys.clone());
 
-		active_validator_keys
+		(active_validator_keys, T::DbWeight::get().writes(3))
+	}
+
+	/// Note `relay_parent`, the block `number`, as a relay parent candidates backed in `session`
+	/// may be built on, keeping at most `max_ancestry_len` older relay parents around.
+	///
+	/// Returns the updated set of allowed relay parents.
+	pub(crate) fn add_allowed_relay_parent(
+		relay_parent: T::Hash,
+		state_root: T::Hash,
+		session: SessionIndex,
+		number: T::BlockNumber,
+		max_ancestry_len: u32,
+	) -> AllowedRelayParentsTracker<T::Hash, T::BlockNumber> {
+		AllowedRelayParents::<T>::mutate(|tracker| {
+			tracker.update(relay_parent, state_root, session, number, max_ancestry_len);
+			tracker.clone()
+		})
 	}
 
 	/// Return the session index that should be used for any future scheduled changes.
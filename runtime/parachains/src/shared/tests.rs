@@ -20,6 +20,7 @@ use crate::{
 	mock::{new_test_ext, MockGenesisConfig, ParasShared},
 };
 use keyring::Sr25519Keyring;
+use primitives::v2::{BlockNumber, Hash};
 
 fn validator_pubkeys(val_ids: &[Sr25519Keyring]) -> Vec<ValidatorId> {
 	val_ids.iter().map(|v| v.public().into()).collect()
@@ -41,7 +42,7 @@ fn sets_and_shuffles_validators() {
 	let pubkeys = validator_pubkeys(&validators);
 
 	new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-		let validators = ParasShared::initializer_on_new_session(1, [1; 32], &config, pubkeys);
+		let (validators, _) = ParasShared::initializer_on_new_session(1, [1; 32], &config, pubkeys);
 
 		assert_eq!(
 			validators,
@@ -85,7 +86,7 @@ fn sets_truncates_and_shuffles_validators() {
 	let pubkeys = validator_pubkeys(&validators);
 
 	new_test_ext(MockGenesisConfig::default()).execute_with(|| {
-		let validators = ParasShared::initializer_on_new_session(1, [1; 32], &config, pubkeys);
+		let (validators, _) = ParasShared::initializer_on_new_session(1, [1; 32], &config, pubkeys);
 
 		assert_eq!(validators, validator_pubkeys(&[Sr25519Keyring::Ferdie, Sr25519Keyring::Bob,]));
 
@@ -97,3 +98,64 @@ fn sets_truncates_and_shuffles_validators() {
 		);
 	});
 }
+
+#[test]
+fn tracker_earliest_block_number() {
+	let mut tracker = AllowedRelayParentsTracker::<Hash, BlockNumber>::default();
+
+	// Test it on an empty tracker.
+	let now: u32 = 1;
+	let max_ancestry_len = 5;
+	assert_eq!(tracker.hypothetical_earliest_block_number(now, 0, max_ancestry_len), now);
+
+	// Push a single block into the tracker, suppose max capacity is 1.
+	let max_ancestry_len = 0;
+	tracker.update(Hash::zero(), Hash::zero(), 0, 0, max_ancestry_len);
+	assert_eq!(tracker.hypothetical_earliest_block_number(now, 0, max_ancestry_len), now);
+
+	// Test a greater capacity.
+	let max_ancestry_len = 4;
+	let now = 4;
+	for i in 1..now {
+		tracker.update(Hash::zero(), Hash::zero(), 0, i, max_ancestry_len);
+		assert_eq!(tracker.hypothetical_earliest_block_number(i + 1, 0, max_ancestry_len), 0);
+	}
+
+	// Capacity exceeded.
+	tracker.update(Hash::zero(), Hash::zero(), 0, now, max_ancestry_len);
+	assert_eq!(tracker.hypothetical_earliest_block_number(now + 1, 0, max_ancestry_len), 1);
+
+	// Relay parents of a previous session don't count.
+	assert_eq!(tracker.hypothetical_earliest_block_number(now + 1, 1, max_ancestry_len), now + 1);
+}
+
+#[test]
+fn tracker_acquire_info() {
+	let mut tracker = AllowedRelayParentsTracker::<Hash, BlockNumber>::default();
+	let max_ancestry_len = 2;
+
+	// (relay_parent, state_root, session) pairs.
+	let blocks = &[
+		(Hash::repeat_byte(0), Hash::repeat_byte(10), 0),
+		(Hash::repeat_byte(1), Hash::repeat_byte(11), 0),
+		(Hash::repeat_byte(2), Hash::repeat_byte(12), 1),
+	];
+
+	let (relay_parent, state_root, session) = blocks[0];
+	tracker.update(relay_parent, state_root, session, 0, max_ancestry_len);
+	assert_eq!(tracker.acquire_info(relay_parent, session), Some((state_root, 0)));
+	assert!(tracker.acquire_info(relay_parent, session + 1).is_none());
+
+	let (relay_parent, state_root, session) = blocks[1];
+	tracker.update(relay_parent, state_root, session, 1, max_ancestry_len);
+	let (relay_parent, state_root, session) = blocks[2];
+	tracker.update(relay_parent, state_root, session, 2, max_ancestry_len);
+	for (block_num, (rp, state_root, session)) in blocks.iter().enumerate() {
+		assert_eq!(tracker.acquire_info(*rp, *session), Some((*state_root, block_num as u32)));
+	}
+
+	// The oldest block is pruned once the capacity is exceeded.
+	tracker.update(Hash::repeat_byte(3), Hash::repeat_byte(13), 1, 3, max_ancestry_len);
+	assert!(tracker.acquire_info(blocks[0].0, blocks[0].2).is_none());
+	assert_eq!(tracker.acquire_info(blocks[1].0, blocks[1].2), Some((blocks[1].1, 1)));
+}
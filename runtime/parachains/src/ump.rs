@@ -17,14 +17,12 @@
 use crate::{
 	configuration::{self, HostConfiguration},
 	initializer,
+	messaging_metrics::{self, MessageQueue},
 };
 use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
 use frame_system::pallet_prelude::*;
 use primitives::v2::{Id as ParaId, UpwardMessage};
-use sp_std::{
-	collections::btree_map::BTreeMap, convert::TryFrom, fmt, marker::PhantomData, mem, prelude::*,
-};
-use xcm::latest::Outcome;
+use sp_std::{collections::btree_map::BTreeMap, fmt, marker::PhantomData, mem, prelude::*};
 
 pub use pallet::*;
 
@@ -98,50 +96,39 @@ fn upward_message_id(data: &[u8]) -> MessageId {
 impl<XcmExecutor: xcm::latest::ExecuteXcm<C::Call>, C: Config> UmpSink for XcmSink<XcmExecutor, C> {
 	fn process_upward_message(
 		origin: ParaId,
-		mut data: &[u8],
+		data: &[u8],
 		max_weight: Weight,
 	) -> Result<Weight, (MessageId, Weight)> {
-		use parity_scale_codec::DecodeLimit;
-		use xcm::{
-			latest::{Error as XcmError, Junction, Xcm},
-			VersionedXcm,
+		use xcm::latest::Junction;
+		use xcm_executor::{
+			traits::{ProcessMessage, ProcessMessageError},
+			ProcessXcmMessage,
 		};
 
 		let id = upward_message_id(&data[..]);
-		let maybe_msg_and_weight = VersionedXcm::<C::Call>::decode_all_with_depth_limit(
-			xcm::MAX_XCM_DECODE_DEPTH,
-			&mut data,
-		)
-		.map(|xcm| {
-			(
-				Xcm::<C::Call>::try_from(xcm),
-				// NOTE: We are overestimating slightly here.
-				// The benchmark is timing this whole function with different message sizes and a NOOP extrinsic to
-				// measure the size-dependent weight. But as we use the weight funtion **in** the benchmarked funtion we
-				// are taking call and control-flow overhead into account twice.
-				<C as Config>::WeightInfo::process_upward_message(data.len() as u32),
-			)
-		});
-		match maybe_msg_and_weight {
-			Err(_) => {
+		// NOTE: We are overestimating slightly here.
+		// The benchmark is timing this whole function with different message sizes and a NOOP extrinsic to
+		// measure the size-dependent weight. But as we use the weight funtion **in** the benchmarked funtion we
+		// are taking call and control-flow overhead into account twice.
+		let weight_used = <C as Config>::WeightInfo::process_upward_message(data.len() as u32);
+		let xcm_junction = Junction::Parachain(origin.into());
+		match ProcessXcmMessage::<Junction, XcmExecutor, C::Call>::process_message(
+			data,
+			xcm_junction,
+			max_weight,
+		) {
+			Err(ProcessMessageError::BadFormat) => {
 				Pallet::<C>::deposit_event(Event::InvalidFormat(id));
 				Ok(0)
 			},
-			Ok((Err(()), weight_used)) => {
+			Err(ProcessMessageError::Unsupported) => {
 				Pallet::<C>::deposit_event(Event::UnsupportedVersion(id));
 				Ok(weight_used)
 			},
-			Ok((Ok(xcm_message), weight_used)) => {
-				let xcm_junction = Junction::Parachain(origin.into());
-				let outcome = XcmExecutor::execute_xcm(xcm_junction, xcm_message, max_weight);
-				match outcome {
-					Outcome::Error(XcmError::WeightLimitReached(required)) => Err((id, required)),
-					outcome => {
-						let outcome_weight = outcome.weight_used();
-						Pallet::<C>::deposit_event(Event::ExecutedUpward(id, outcome));
-						Ok(weight_used.saturating_add(outcome_weight))
-					},
-				}
+			Err(ProcessMessageError::Overweight(required)) => Err((id, required)),
+			Ok((success, weight)) => {
+				Pallet::<C>::deposit_event(Event::Processed { id, origin, success, weight });
+				Ok(weight_used.saturating_add(weight))
 			},
 		}
 	}
@@ -216,7 +203,9 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + configuration::Config {
+	pub trait Config:
+		frame_system::Config + configuration::Config + messaging_metrics::Config
+	{
 		/// The aggregate event.
 		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -247,9 +236,17 @@ pub mod pallet {
 		/// Upward message is unsupported version of XCM.
 		/// \[ id \]
 		UnsupportedVersion(MessageId),
-		/// Upward message executed with the given outcome.
-		/// \[ id, outcome \]
-		ExecutedUpward(MessageId, Outcome),
+		/// Upward message was processed. `success` is true only if its execution was complete.
+		Processed {
+			/// The id of the message.
+			id: MessageId,
+			/// The para which sent the message.
+			origin: ParaId,
+			/// Whether the execution of the message was complete.
+			success: bool,
+			/// The weight used in executing the message.
+			weight: Weight,
+		},
 		/// The weight limit for handling upward messages was reached.
 		/// \[ id, remaining, required \]
 		WeightExhausted(MessageId, Weight, Weight),
@@ -398,6 +395,7 @@ impl<T: Config> Pallet<T> {
 	pub(crate) fn clean_ump_after_outgoing(outgoing_para: &ParaId) -> Weight {
 		<Self as Store>::RelayDispatchQueueSize::remove(outgoing_para);
 		<Self as Store>::RelayDispatchQueues::remove(outgoing_para);
+		<messaging_metrics::Pallet<T>>::note_cleared(&MessageQueue::Upward(*outgoing_para));
 
 		// Remove the outgoing para from the `NeedsDispatch` list and from
 		// `NextDispatchRoundStartWith`.
@@ -494,6 +492,12 @@ impl<T: Config> Pallet<T> {
 				}
 			});
 
+			weight += <messaging_metrics::Pallet<T>>::note_enqueued(
+				&MessageQueue::Upward(para),
+				extra_count,
+				extra_size,
+			);
+
 			// NOTE: The actual computation is not accounted for. It should be benchmarked.
 			weight += T::DbWeight::get().reads_writes(3, 3);
 
@@ -671,11 +675,17 @@ impl QueueCache {
 				// remove the entries altogether.
 				RelayDispatchQueues::<T>::remove(&para);
 				RelayDispatchQueueSize::<T>::remove(&para);
+				<messaging_metrics::Pallet<T>>::note_cleared(&MessageQueue::Upward(para));
 			} else if entry.consumed_count > 0 {
 				RelayDispatchQueues::<T>::insert(&para, &entry.queue[entry.consumed_count..]);
 				let count = (entry.queue.len() - entry.consumed_count) as u32;
 				let size = entry.total_size.saturating_sub(entry.consumed_size as u32);
 				RelayDispatchQueueSize::<T>::insert(&para, (count, size));
+				<messaging_metrics::Pallet<T>>::note_dequeued(
+					&MessageQueue::Upward(para),
+					entry.consumed_count as u32,
+					entry.consumed_size as u32,
+				);
 			}
 		}
 	}
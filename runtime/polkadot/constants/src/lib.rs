@@ -35,14 +35,35 @@ pub mod currency {
 	pub const fn deposit(items: u32, bytes: u32) -> Balance {
 		items as Balance * 20 * DOLLARS + (bytes as Balance) * 100 * MILLICENTS
 	}
+
+	/// The deposit held for an account with `num_proxies` proxies.
+	pub const fn proxy_deposit(num_proxies: u32) -> Balance {
+		// One storage item; key size 32, value size 8; and 33 bytes for each proxy.
+		deposit(1, 8) + deposit(0, 33) * num_proxies as Balance
+	}
+
+	/// The deposit held for a multisig operation requiring `threshold` approvals.
+	pub const fn multisig_deposit(threshold: u32) -> Balance {
+		// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes; and 32
+		// bytes for each approval.
+		deposit(1, 88) + deposit(0, 32) * threshold as Balance
+	}
+
+	/// The deposit held for an identity with `fields` additional fields.
+	pub const fn identity_deposit(fields: u32) -> Balance {
+		// One storage item of 258 bytes; and 66 bytes for each additional field.
+		deposit(1, 258) + deposit(0, 66) * fields as Balance
+	}
 }
 
 /// Time and blocks.
 pub mod time {
 	use primitives::v2::{BlockNumber, Moment};
+	use runtime_common::prod_or_fast;
+
 	pub const MILLISECS_PER_BLOCK: Moment = 6000;
 	pub const SLOT_DURATION: Moment = MILLISECS_PER_BLOCK;
-	pub const EPOCH_DURATION_IN_SLOTS: BlockNumber = 4 * HOURS;
+	pub const EPOCH_DURATION_IN_SLOTS: BlockNumber = prod_or_fast!(4 * HOURS, 1 * MINUTES);
 
 	// These time units are defined in number of blocks.
 	pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
@@ -57,16 +78,27 @@ pub mod time {
 /// Fee-related.
 pub mod fee {
 	use crate::weights::ExtrinsicBaseWeight;
-	use frame_support::weights::{
-		WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+	use frame_support::{
+		parameter_types,
+		weights::{WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial},
 	};
 	use primitives::v2::Balance;
 	use smallvec::smallvec;
 	pub use sp_runtime::Perbill;
+	use sp_runtime::{FixedPointNumber, FixedU128};
 
 	/// The block saturation level. Fees will be updates based on this value.
 	pub const TARGET_BLOCK_FULLNESS: Perbill = Perbill::from_percent(25);
 
+	parameter_types! {
+		/// The floor of the fee multiplier. Fees never drop below this fraction of their
+		/// unadjusted value, however long the chain stays below `TARGET_BLOCK_FULLNESS`.
+		pub MinimumMultiplier: FixedU128 = FixedU128::saturating_from_rational(1, 1_000_000u128);
+	}
+
+	/// The fee charged per byte of an extrinsic's encoded length.
+	pub const TRANSACTION_BYTE_FEE: Balance = 10 * super::currency::MILLICENTS;
+
 	/// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 	/// node's balance type.
 	///
@@ -92,13 +124,113 @@ pub mod fee {
 			}]
 		}
 	}
+
+	/// Handles converting an extrinsic's encoded length, in bytes, to a fee value.
+	///
+	/// The fee is linear in the length, at [`TRANSACTION_BYTE_FEE`] per byte.
+	pub struct LengthToFee;
+	impl WeightToFeePolynomial for LengthToFee {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			smallvec![WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_parts(0),
+				coeff_integer: TRANSACTION_BYTE_FEE,
+			}]
+		}
+	}
+}
+
+/// Bounties and child bounties.
+pub mod bounties {
+	use super::currency::DOLLARS;
+	use primitives::v2::Balance;
+	use sp_runtime::Permill;
+
+	/// The share of the curator fee which a curator reserves as a deposit.
+	pub const CURATOR_DEPOSIT_MULTIPLIER: Permill = Permill::from_percent(50);
+	/// The minimum deposit of a curator.
+	pub const CURATOR_DEPOSIT_MIN: Balance = 10 * DOLLARS;
+	/// The maximum deposit of a curator.
+	pub const CURATOR_DEPOSIT_MAX: Balance = 200 * DOLLARS;
+
+	/// The deposit reserved by the curator of a bounty or a child bounty paying a curator `fee`.
+	///
+	/// This is the computation performed by the bounties pallet with the constants above.
+	pub fn curator_deposit(fee: Balance) -> Balance {
+		(CURATOR_DEPOSIT_MULTIPLIER * fee).clamp(CURATOR_DEPOSIT_MIN, CURATOR_DEPOSIT_MAX)
+	}
+}
+
+/// Nomination pools.
+pub mod nomination_pools {
+	use super::currency::UNITS;
+	use primitives::v2::Balance;
+
+	/// The minimum bond of a member joining a pool.
+	pub const MIN_JOIN_BOND: Balance = 1 * UNITS;
+	/// The minimum bond of the depositor creating a pool.
+	pub const MIN_CREATE_BOND: Balance = 500 * UNITS;
+	/// The maximum number of pools that may exist.
+	pub const MAX_POOLS: u32 = 64;
+	/// The maximum number of members of a single pool.
+	pub const MAX_MEMBERS_PER_POOL: u32 = 1_024;
+	/// The maximum number of pool members across all pools.
+	pub const MAX_MEMBERS: u32 = MAX_POOLS * MAX_MEMBERS_PER_POOL;
+}
+
+/// Fast unstake.
+pub mod fast_unstake {
+	use super::currency::UNITS;
+	use primitives::v2::Balance;
+
+	/// The deposit reserved from a nominator registering for a fast unstake. It is slashed if the
+	/// nominator turns out to have been exposed in one of the eras checked.
+	pub const DEPOSIT: Balance = 1 * UNITS;
+	/// The maximum number of queued nominators checked and unstaked in a single block.
+	pub const BATCH_SIZE: u32 = 16;
+}
+
+/// System parachains.
+pub mod system_parachain {
+	use primitives::v2::{Id as ParaId, LOWEST_PUBLIC_ID};
+	use xcm::latest::prelude::*;
+
+	/// Statemint parachain ID.
+	pub const STATEMINT_ID: u32 = 1000;
+	/// Collectives parachain ID.
+	pub const COLLECTIVES_ID: u32 = 1001;
+	/// Bridge Hub parachain ID.
+	pub const BRIDGE_HUB_ID: u32 = 1002;
+
+	/// Whether `id` lies in the range reserved for system parachains.
+	pub fn is_system(id: ParaId) -> bool {
+		id < LOWEST_PUBLIC_ID
+	}
+
+	/// The location of the system parachain `id`, from the context of the relay chain.
+	pub fn location(id: u32) -> MultiLocation {
+		Parachain(id).into()
+	}
+
+	/// The location of Statemint, from the context of the relay chain.
+	pub fn statemint_location() -> MultiLocation {
+		location(STATEMINT_ID)
+	}
+
+	/// The location of the Bridge Hub, from the context of the relay chain.
+	pub fn bridge_hub_location() -> MultiLocation {
+		location(BRIDGE_HUB_ID)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::{
-		currency::{CENTS, DOLLARS, MILLICENTS},
-		fee::WeightToFee,
+		bounties::{curator_deposit, CURATOR_DEPOSIT_MAX, CURATOR_DEPOSIT_MIN},
+		currency::{identity_deposit, multisig_deposit, proxy_deposit, CENTS, DOLLARS, MILLICENTS},
+		fee::{LengthToFee, WeightToFee, TRANSACTION_BYTE_FEE},
 	};
 	use crate::weights::ExtrinsicBaseWeight;
 	use frame_support::weights::WeightToFeePolynomial;
@@ -123,4 +255,33 @@ mod tests {
 		let y = CENTS / 10;
 		assert!(x.max(y) - x.min(y) < MILLICENTS);
 	}
+
+	#[test]
+	// This function tests that the fee for a 1KB extrinsic is correct
+	fn one_kilobyte_length_fee_is_correct() {
+		assert_eq!(LengthToFee::calc(&1), TRANSACTION_BYTE_FEE);
+		// Changing this value changes the fees charged for every extrinsic.
+		assert_eq!(LengthToFee::calc(&1024), 1_024_000_000);
+	}
+
+	#[test]
+	// This function tests that the curator deposit is half the fee, within its bounds
+	fn curator_deposit_is_correct() {
+		assert_eq!(curator_deposit(0), CURATOR_DEPOSIT_MIN);
+		assert_eq!(curator_deposit(20 * DOLLARS), CURATOR_DEPOSIT_MIN);
+		assert_eq!(curator_deposit(100 * DOLLARS), 50 * DOLLARS);
+		assert_eq!(curator_deposit(400 * DOLLARS), CURATOR_DEPOSIT_MAX);
+		assert_eq!(curator_deposit(u128::MAX), CURATOR_DEPOSIT_MAX);
+	}
+
+	#[test]
+	// This function tests that the named deposits are correct
+	fn named_deposits_are_correct() {
+		// 20 DOT for the storage item, plus 1 mDOT per byte.
+		assert_eq!(proxy_deposit(0), 200_080_000_000);
+		assert_eq!(proxy_deposit(1), 200_410_000_000);
+		assert_eq!(multisig_deposit(2), 201_520_000_000);
+		assert_eq!(identity_deposit(1), 203_240_000_000);
+		assert_eq!(proxy_deposit(0), 20 * DOLLARS + 8 * 100 * MILLICENTS);
+	}
 }
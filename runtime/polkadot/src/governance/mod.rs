@@ -0,0 +1,112 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! New governance configurations for the Polkadot runtime.
+//!
+//! Governance is moving from the Democracy pallet and the Council to referenda voted on with
+//! conviction voting, where each referendum is decided on a track specific to the origin it
+//! dispatches with. The migration happens in stages:
+//!
+//! 1. The new pallets are introduced next to `Democracy`, `Council` and `TechnicalCommittee`.
+//!    [`migration::InitializeGovernance`] sets their on-chain storage versions, since pallets
+//!    added to a live chain start without one. Ongoing referenda and proposals run to completion
+//!    under the old rules, and locks from Democracy votes expire on their own.
+//! 2. Privileged calls accept the track origins next to the council: the `EnsureOrigin`s of the
+//!    runtime are extended with the origins of the custom origins pallet, and the treasury may
+//!    be spent from by [`TreasurySpender`].
+//! 3. Once no referenda are left in Democracy, the old pallets are removed from the runtime
+//!    together with their storage.
+//!
+//! Until a fellowship exists, whitelisting calls for the whitelisted caller track is done by a
+//! majority of the Technical Committee.
+
+use super::*;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, EnsureOneOf},
+};
+use frame_system::EnsureRootWithSuccess;
+
+mod origins;
+pub use origins::{
+	pallet_custom_origins, AuctionAdmin, FellowshipAdmin, GeneralAdmin, LeaseAdmin,
+	ReferendumCanceller, ReferendumKiller, Spender, StakingAdmin, Treasurer, WhitelistedCaller,
+};
+mod tracks;
+pub use tracks::TracksInfo;
+pub mod migration;
+
+parameter_types! {
+	pub VoteLockingPeriod: BlockNumber = prod_or_fast!(28 * DAYS, 1, "DOT_VOTE_LOCKING_PERIOD");
+}
+
+impl pallet_conviction_voting::Config for Runtime {
+	type WeightInfo = weights::pallet_conviction_voting::WeightInfo<Runtime>;
+	type Event = Event;
+	type Currency = Balances;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxVotes = ConstU32<512>;
+	type MaxTurnout = frame_support::traits::TotalIssuanceOf<Balances, Self::AccountId>;
+	type Polls = Referenda;
+}
+
+parameter_types! {
+	pub const AlarmInterval: BlockNumber = 1;
+	pub const SubmissionDeposit: Balance = 1 * DOLLARS;
+	pub UndecidingTimeout: BlockNumber = prod_or_fast!(14 * DAYS, 1 * MINUTES, "DOT_UNDECIDING_TIMEOUT");
+}
+
+parameter_types! {
+	pub const MaxBalance: Balance = Balance::max_value();
+}
+/// The origin allowed to spend from the treasury, returning the largest amount it may spend.
+pub type TreasurySpender = EnsureOneOf<EnsureRootWithSuccess<AccountId, MaxBalance>, Spender>;
+
+impl origins::pallet_custom_origins::Config for Runtime {}
+
+/// The origin allowed to whitelist calls for the whitelisted caller track.
+pub type WhitelistOrigin = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
+>;
+
+impl pallet_whitelist::Config for Runtime {
+	type WeightInfo = weights::pallet_whitelist::WeightInfo<Runtime>;
+	type Event = Event;
+	type Call = Call;
+	type WhitelistOrigin = WhitelistOrigin;
+	type DispatchWhitelistedOrigin = EnsureOneOf<EnsureRoot<AccountId>, WhitelistedCaller>;
+	type PreimageProvider = Preimage;
+}
+
+impl pallet_referenda::Config for Runtime {
+	type WeightInfo = weights::pallet_referenda::WeightInfo<Runtime>;
+	type Call = Call;
+	type Event = Event;
+	type Scheduler = Scheduler;
+	type Currency = Balances;
+	type SubmitOrigin = frame_system::EnsureSigned<AccountId>;
+	type CancelOrigin = EnsureOneOf<EnsureRoot<AccountId>, ReferendumCanceller>;
+	type KillOrigin = EnsureOneOf<EnsureRoot<AccountId>, ReferendumKiller>;
+	type Slash = Treasury;
+	type Votes = pallet_conviction_voting::VotesOf<Runtime>;
+	type Tally = pallet_conviction_voting::TallyOf<Runtime>;
+	type SubmissionDeposit = SubmissionDeposit;
+	type MaxQueued = ConstU32<100>;
+	type UndecidingTimeout = UndecidingTimeout;
+	type AlarmInterval = AlarmInterval;
+	type Tracks = TracksInfo;
+}
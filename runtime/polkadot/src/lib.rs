@@ -23,14 +23,15 @@
 use pallet_transaction_payment::CurrencyAdapter;
 use runtime_common::{
 	auctions, claims, crowdloan, elections::OnChainSeqPhragmen, impl_runtime_weights,
-	impls::DealWithFees, paras_registrar, prod_or_fast, slots, BlockHashCount, BlockLength,
-	CurrencyToVote, SlowAdjustingFeeUpdate,
+	impls::DealWithFees, paras_registrar, prod_or_fast, slots, treasury_payouts, BlockHashCount,
+	BlockLength, CurrencyToVote, SlowAdjustingFeeUpdate,
 };
 
 use runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, messaging_metrics as parachains_messaging_metrics,
+	origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
 	runtime_api_impl::v2 as parachains_runtime_api_impl, scheduler as parachains_scheduler,
 	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
@@ -58,9 +59,10 @@ use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::v2::{
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
-	Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
-	SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	CoreState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
+	PersistedValidationData, ScrapedOnChainVotes, SessionInfo, Signature, UpgradeRestriction,
+	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
 };
 use sp_core::OpaqueMetadata;
 use sp_runtime::{
@@ -72,7 +74,7 @@ use sp_runtime::{
 		OpaqueKeys, SaturatedConversion, Verify, Zero,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, KeyTypeId, Perbill, Percent, Permill,
+	ApplyExtrinsicResult, FixedU128, KeyTypeId, Perbill, Percent, Permill,
 };
 use sp_staking::SessionIndex;
 use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, prelude::*};
@@ -90,7 +92,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 pub use sp_runtime::BuildStorage;
 
 /// Constant values used within the runtime.
-use polkadot_runtime_constants::{currency::*, fee::*, time::*};
+use polkadot_runtime_constants::{bounties, currency::*, fast_unstake, fee::*, time::*};
 
 // Weights used in the runtime.
 mod weights;
@@ -99,6 +101,13 @@ mod bag_thresholds;
 
 pub mod xcm_config;
 
+// Governance configurations.
+pub mod governance;
+use governance::{
+	pallet_custom_origins, AuctionAdmin, FellowshipAdmin, GeneralAdmin, LeaseAdmin, StakingAdmin,
+	Treasurer, TreasurySpender,
+};
+
 impl_runtime_weights!(polkadot_runtime_constants);
 
 // Make the WASM binary available.
@@ -144,6 +153,9 @@ impl Contains<Call> for BaseFilter {
 			Call::TechnicalCommittee(_) |
 			Call::TechnicalMembership(_) |
 			Call::Treasury(_) |
+			Call::ConvictionVoting(_) |
+			Call::Referenda(_) |
+			Call::Whitelist(_) |
 			Call::PhragmenElection(_) |
 			Call::System(_) |
 			Call::Scheduler(_) |
@@ -182,6 +194,9 @@ impl Contains<Call> for BaseFilter {
 			Call::Auctions(_) |
 			Call::Crowdloan(_) |
 			Call::BagsList(_) |
+			Call::NominationPools(_) |
+			Call::FastUnstake(_) |
+			Call::TreasuryPayouts(_) |
 			Call::XcmPallet(_) => true,
 			// All pallets are allowed, but exhaustive match is defensive
 			// in the case of adding new pallets.
@@ -362,7 +377,7 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
+	pub const TransactionByteFee: Balance = TRANSACTION_BYTE_FEE;
 	/// This value increases the priority of `Operational` transactions by adding
 	/// a "virtual tip" that's equal to the `OperationalFeeMultiplier * final_fee`.
 	pub const OperationalFeeMultiplier: u8 = 5;
@@ -373,7 +388,7 @@ impl pallet_transaction_payment::Config for Runtime {
 	type TransactionByteFee = TransactionByteFee;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = WeightToFee;
-	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self, MinimumMultiplier>;
 }
 
 parameter_types! {
@@ -501,7 +516,10 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type BenchmarkingConfig = runtime_common::elections::BenchmarkConfig;
 	type ForceOrigin = EnsureOneOf<
 		EnsureRoot<AccountId>,
-		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+		EnsureOneOf<
+			pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+			StakingAdmin,
+		>,
 	>;
 	type WeightInfo = weights::pallet_election_provider_multi_phase::WeightInfo<Self>;
 	type MaxElectingVoters = MaxElectingVoters;
@@ -520,6 +538,16 @@ impl pallet_bags_list::Config for Runtime {
 	type Score = sp_npos_elections::VoteWeight;
 }
 
+impl pallet_fast_unstake::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BatchSize = frame_support::traits::ConstU32<{ fast_unstake::BATCH_SIZE }>;
+	type Deposit = frame_support::traits::ConstU128<{ fast_unstake::DEPOSIT }>;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type Staking = Staking;
+	type WeightInfo = weights::pallet_fast_unstake::WeightInfo<Runtime>;
+}
+
 // TODO #6469: This shouldn't be static, but a lazily cached value, not built unless needed, and
 // re-built in case input parameters have changed. The `ideal_stake` should be determined by the
 // amount of parachain slots being bid on: this should be around `(75 - 25.min(slots / 4))%`.
@@ -551,7 +579,10 @@ parameter_types! {
 
 type SlashCancelOrigin = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+		StakingAdmin,
+	>,
 >;
 
 impl pallet_staking::Config for Runtime {
@@ -566,7 +597,7 @@ impl pallet_staking::Config for Runtime {
 	type SessionsPerEra = SessionsPerEra;
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
-	// A super-majority of the council can cancel the slash.
+	// A super-majority of the council or the staking admin track can cancel the slash.
 	type SlashCancelOrigin = SlashCancelOrigin;
 	type SessionInterface = Self;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
@@ -581,10 +612,30 @@ impl pallet_staking::Config for Runtime {
 	type WeightInfo = weights::pallet_staking::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const PoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	pub const MinPointsToBalance: u32 = 10;
+}
+
+impl pallet_nomination_pools::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = weights::pallet_nomination_pools::WeightInfo<Self>;
+	type Currency = Balances;
+	type BalanceToU256 = runtime_common::BalanceToU256;
+	type U256ToBalance = runtime_common::U256ToBalance;
+	type StakingInterface = Staking;
+	type PostUnbondingPoolsWindow = frame_support::traits::ConstU32<4>;
+	type MaxMetadataLen = frame_support::traits::ConstU32<256>;
+	// we use the same number of allowed unlocking chunks as with staking.
+	type MaxUnbonding = <Self as pallet_staking::Config>::MaxUnlockingChunks;
+	type PalletId = PoolsPalletId;
+	type MinPointsToBalance = MinPointsToBalance;
+}
+
 parameter_types! {
 	// Minimum 4 CENTS/byte
-	pub const BasicDeposit: Balance = deposit(1, 258);
-	pub const FieldDeposit: Balance = deposit(0, 66);
+	pub const BasicDeposit: Balance = identity_deposit(0);
+	pub const FieldDeposit: Balance = identity_deposit(1) - identity_deposit(0);
 	pub const SubAccountDeposit: Balance = deposit(1, 53);
 	pub const MaxSubAccounts: u32 = 100;
 	pub const MaxAdditionalFields: u32 = 100;
@@ -601,8 +652,8 @@ impl pallet_identity::Config for Runtime {
 	type MaxAdditionalFields = MaxAdditionalFields;
 	type MaxRegistrars = MaxRegistrars;
 	type Slashed = Treasury;
-	type ForceOrigin = MoreThanHalfCouncil;
-	type RegistrarOrigin = MoreThanHalfCouncil;
+	type ForceOrigin = EnsureOneOf<MoreThanHalfCouncil, GeneralAdmin>;
+	type RegistrarOrigin = EnsureOneOf<MoreThanHalfCouncil, GeneralAdmin>;
 	type WeightInfo = weights::pallet_identity::WeightInfo<Runtime>;
 }
 
@@ -753,11 +804,11 @@ impl pallet_collective::Config<TechnicalCollective> for Runtime {
 
 impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type Event = Event;
-	type AddOrigin = MoreThanHalfCouncil;
-	type RemoveOrigin = MoreThanHalfCouncil;
-	type SwapOrigin = MoreThanHalfCouncil;
-	type ResetOrigin = MoreThanHalfCouncil;
-	type PrimeOrigin = MoreThanHalfCouncil;
+	type AddOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type RemoveOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type SwapOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type ResetOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
+	type PrimeOrigin = EnsureOneOf<MoreThanHalfCouncil, FellowshipAdmin>;
 	type MembershipInitialized = TechnicalCommittee;
 	type MembershipChanged = TechnicalCommittee;
 	type MaxMembers = TechnicalMaxMembers;
@@ -785,14 +836,17 @@ parameter_types! {
 
 type ApproveOrigin = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+		Treasurer,
+	>,
 >;
 
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
 	type ApproveOrigin = ApproveOrigin;
-	type RejectOrigin = MoreThanHalfCouncil;
+	type RejectOrigin = EnsureOneOf<MoreThanHalfCouncil, Treasurer>;
 	type Event = Event;
 	type OnSlash = Treasury;
 	type ProposalBond = ProposalBond;
@@ -804,6 +858,15 @@ impl pallet_treasury::Config for Runtime {
 	type SpendFunds = Bounties;
 	type MaxApprovals = MaxApprovals;
 	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
+	type SpendOrigin = TreasurySpender;
+}
+
+impl treasury_payouts::Config for Runtime {
+	type Event = Event;
+	type SpendOrigin = TreasurySpender;
+	type Paymaster = xcm_config::TreasuryPaymaster;
+	type NativeAsset = xcm_config::TreasuryPaymentAsset;
+	type PaymentWeight = xcm_config::TreasuryPaymentMaxWeight;
 }
 
 parameter_types! {
@@ -811,9 +874,9 @@ parameter_types! {
 	pub const BountyDepositPayoutDelay: BlockNumber = 8 * DAYS;
 	pub const BountyUpdatePeriod: BlockNumber = 90 * DAYS;
 	pub const MaximumReasonLength: u32 = 16384;
-	pub const CuratorDepositMultiplier: Permill = Permill::from_percent(50);
-	pub const CuratorDepositMin: Balance = 10 * DOLLARS;
-	pub const CuratorDepositMax: Balance = 200 * DOLLARS;
+	pub const CuratorDepositMultiplier: Permill = bounties::CURATOR_DEPOSIT_MULTIPLIER;
+	pub const CuratorDepositMin: Balance = bounties::CURATOR_DEPOSIT_MIN;
+	pub const CuratorDepositMax: Balance = bounties::CURATOR_DEPOSIT_MAX;
 	pub const BountyValueMinimum: Balance = 10 * DOLLARS;
 }
 
@@ -1010,10 +1073,8 @@ impl pallet_utility::Config for Runtime {
 }
 
 parameter_types! {
-	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
-	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
+	pub const DepositBase: Balance = multisig_deposit(0);
+	pub const DepositFactor: Balance = multisig_deposit(1) - multisig_deposit(0);
 	pub const MaxSignatories: u16 = 100;
 }
 
@@ -1028,10 +1089,8 @@ impl pallet_multisig::Config for Runtime {
 }
 
 parameter_types! {
-	// One storage item; key size 32, value size 8; .
-	pub const ProxyDepositBase: Balance = deposit(1, 8);
-	// Additional storage item size of 33 bytes.
-	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const ProxyDepositBase: Balance = proxy_deposit(0);
+	pub const ProxyDepositFactor: Balance = proxy_deposit(1) - proxy_deposit(0);
 	pub const MaxProxies: u16 = 32;
 	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
 	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
@@ -1061,6 +1120,7 @@ pub enum ProxyType {
 	IdentityJudgement = 5,
 	CancelProxy = 6,
 	Auction = 7,
+	ParaRegistration = 8,
 }
 
 #[cfg(test)]
@@ -1092,6 +1152,93 @@ mod proxy_type_tests {
 		}
 		assert!(ProxyType::decode(&mut &OldProxyType::SudoBalances.encode()[..]).is_err());
 	}
+
+	fn para_registration_calls() -> Vec<Call> {
+		let para = ParaId::from(2000);
+		vec![
+			Call::Registrar(paras_registrar::Call::reserve {}),
+			Call::Registrar(paras_registrar::Call::register_parathread {
+				genesis_head: vec![].into(),
+				validation_code: vec![].into(),
+			}),
+			Call::Registrar(paras_registrar::Call::deregister { id: para }),
+			Call::Crowdloan(crowdloan::Call::dissolve { index: para }),
+			Call::Utility(pallet_utility::Call::batch { calls: vec![] }),
+		]
+	}
+
+	fn other_calls() -> Vec<Call> {
+		let para = ParaId::from(2000);
+		let who = AccountId::new([1; 32]);
+		vec![
+			Call::System(frame_system::Call::remark { remark: vec![] }),
+			Call::Balances(pallet_balances::Call::transfer { dest: who.clone().into(), value: 1 }),
+			// Swapping a para away is left to the `Any` proxy.
+			Call::Registrar(paras_registrar::Call::swap { id: para, other: para }),
+			Call::Auctions(auctions::Call::cancel_auction {}),
+			Call::Slots(slots::Call::trigger_onboard { para }),
+			Call::Staking(pallet_staking::Call::chill {}),
+			Call::Proxy(pallet_proxy::Call::remove_proxies {}),
+			Call::Proxy(pallet_proxy::Call::reject_announcement {
+				delegate: who.into(),
+				call_hash: Default::default(),
+			}),
+		]
+	}
+
+	#[test]
+	fn para_registration_proxy_filters_calls() {
+		for call in para_registration_calls() {
+			assert!(ProxyType::ParaRegistration.filter(&call), "{:?} is denied", call);
+		}
+		for call in other_calls() {
+			assert!(!ProxyType::ParaRegistration.filter(&call), "{:?} is allowed", call);
+		}
+	}
+
+	#[test]
+	fn para_registration_proxy_is_covered_by_non_transfer() {
+		for call in para_registration_calls() {
+			assert!(ProxyType::NonTransfer.filter(&call), "{:?} is denied", call);
+		}
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::ParaRegistration));
+		assert!(!ProxyType::ParaRegistration.is_superset(&ProxyType::NonTransfer));
+	}
+
+	#[test]
+	fn cancel_proxy_filters_calls() {
+		let who = AccountId::new([1; 32]);
+		let reject = Call::Proxy(pallet_proxy::Call::reject_announcement {
+			delegate: who.clone().into(),
+			call_hash: Default::default(),
+		});
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let batch = |calls| Call::Utility(pallet_utility::Call::batch { calls });
+		let batch_all = |calls| Call::Utility(pallet_utility::Call::batch_all { calls });
+		let multisig = Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+			other_signatories: vec![who],
+			call: Box::new(reject.clone()),
+		});
+
+		for call in [
+			reject.clone(),
+			batch(vec![reject.clone(), reject.clone()]),
+			batch_all(vec![reject.clone()]),
+			batch(vec![batch_all(vec![reject.clone()])]),
+		] {
+			assert!(ProxyType::CancelProxy.filter(&call), "{:?} is denied", call);
+		}
+		for call in [
+			Call::Proxy(pallet_proxy::Call::remove_proxies {}),
+			remark.clone(),
+			batch(vec![reject.clone(), remark.clone()]),
+			batch_all(vec![remark]),
+			multisig,
+			Call::Utility(pallet_utility::Call::as_derivative { index: 0, call: Box::new(reject) }),
+		] {
+			assert!(!ProxyType::CancelProxy.filter(&call), "{:?} is allowed", call);
+		}
+	}
 }
 
 impl Default for ProxyType {
@@ -1125,6 +1272,9 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::PhragmenElection(..) |
 				Call::TechnicalMembership(..) |
 				Call::Treasury(..) |
+				Call::ConvictionVoting(..) |
+				Call::Referenda(..) |
+				Call::Whitelist(..) |
 				Call::Bounties(..) |
 				Call::ChildBounties(..) |
 				Call::Tips(..) |
@@ -1140,34 +1290,58 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::Registrar(paras_registrar::Call::deregister {..}) |
 				// Specifically omitting Registrar `swap`
 				Call::Registrar(paras_registrar::Call::reserve {..}) |
+				Call::Registrar(paras_registrar::Call::register_parathread {..}) |
 				Call::Crowdloan(..) |
 				Call::Slots(..) |
 				Call::Auctions(..) | // Specifically omitting the entire XCM Pallet
-				Call::BagsList(..)
+				Call::BagsList(..) |
+				Call::NominationPools(..) |
+				Call::FastUnstake(..)
 			),
 			ProxyType::Governance => matches!(
 				c,
 				Call::Democracy(..) |
 					Call::Council(..) | Call::TechnicalCommittee(..) |
 					Call::PhragmenElection(..) |
-					Call::Treasury(..) | Call::Bounties(..) |
-					Call::Tips(..) | Call::Utility(..) |
-					Call::ChildBounties(..)
+					Call::Treasury(..) | Call::TreasuryPayouts(..) |
+					Call::Bounties(..) | Call::Tips(..) | Call::Utility(..) |
+					Call::ChildBounties(..) |
+					Call::ConvictionVoting(..) |
+					Call::Referenda(..) | Call::Whitelist(..)
 			),
 			ProxyType::Staking => {
-				matches!(c, Call::Staking(..) | Call::Session(..) | Call::Utility(..))
+				matches!(
+					c,
+					Call::Staking(..) |
+						Call::Session(..) | Call::Utility(..) |
+						Call::NominationPools(..) |
+						Call::FastUnstake(..)
+				)
 			},
 			ProxyType::IdentityJudgement => matches!(
 				c,
 				Call::Identity(pallet_identity::Call::provide_judgement { .. }) | Call::Utility(..)
 			),
-			ProxyType::CancelProxy => {
-				matches!(c, Call::Proxy(pallet_proxy::Call::reject_announcement { .. }))
+			ProxyType::CancelProxy => match c {
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) => true,
+				// Only batches which do nothing but reject announcements.
+				Call::Utility(pallet_utility::Call::batch { calls }) |
+				Call::Utility(pallet_utility::Call::batch_all { calls }) =>
+					calls.iter().all(|call| ProxyType::CancelProxy.filter(call)),
+				_ => false,
 			},
 			ProxyType::Auction => matches!(
 				c,
 				Call::Auctions(..) | Call::Crowdloan(..) | Call::Registrar(..) | Call::Slots(..)
 			),
+			ProxyType::ParaRegistration => matches!(
+				c,
+				Call::Registrar(paras_registrar::Call::reserve { .. }) |
+					Call::Registrar(paras_registrar::Call::register { .. }) |
+					Call::Registrar(paras_registrar::Call::register_parathread { .. }) |
+					Call::Registrar(paras_registrar::Call::deregister { .. }) |
+					Call::Crowdloan(..) | Call::Utility(..)
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -1236,7 +1410,11 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = parachains_ump::TestWeightInfo;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = weights::runtime_parachains_dmp::WeightInfo<Runtime>;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
 	type Event = Event;
@@ -1298,7 +1476,7 @@ impl slots::Config for Runtime {
 	type Registrar = Registrar;
 	type LeasePeriod = LeasePeriod;
 	type LeaseOffset = LeaseOffset;
-	type ForceOrigin = MoreThanHalfCouncil;
+	type ForceOrigin = EnsureOneOf<MoreThanHalfCouncil, LeaseAdmin>;
 	type WeightInfo = weights::runtime_common_slots::WeightInfo<Runtime>;
 }
 
@@ -1312,6 +1490,8 @@ parameter_types! {
 	pub const RemoveKeysLimit: u32 = 1000;
 	// Allow 32 bytes for an additional memo to a crowdloan.
 	pub const MaxMemoLength: u8 = 32;
+	// The most a parachain may contribute to a crowdloan on behalf of its users.
+	pub const MaxParaContribution: Balance = 1_000_000 * DOLLARS;
 }
 
 impl crowdloan::Config for Runtime {
@@ -1323,6 +1503,8 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type ParaContributionOrigin = parachains_origin::EnsureParachain;
+	type MaxParaContribution = MaxParaContribution;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -1336,7 +1518,10 @@ parameter_types! {
 
 type AuctionInitiate = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+	EnsureOneOf<
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>,
+		AuctionAdmin,
+	>,
 >;
 
 impl auctions::Config for Runtime {
@@ -1389,6 +1574,11 @@ construct_runtime! {
 		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 18,
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 19,
 
+		// OpenGov stuff.
+		ConvictionVoting: pallet_conviction_voting::{Pallet, Call, Storage, Event<T>} = 20,
+		Referenda: pallet_referenda::{Pallet, Call, Storage, Event<T>} = 21,
+		Origins: pallet_custom_origins::{Origin} = 22,
+		Whitelist: pallet_whitelist::{Pallet, Call, Storage, Event<T>} = 23,
 
 		// Claims. Usable initially.
 		Claims: claims::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 24,
@@ -1419,6 +1609,15 @@ construct_runtime! {
 		// Provides a semi-sorted list of nominators for staking.
 		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>} = 37,
 
+		// Nomination pools: extension to staking.
+		NominationPools: pallet_nomination_pools::{Pallet, Call, Storage, Event<T>, Config<T>} = 39,
+
+		// Fast unstake pallet: extension to staking.
+		FastUnstake: pallet_fast_unstake = 40,
+
+		// Treasury payouts to other chains: extension to the treasury.
+		TreasuryPayouts: treasury_payouts::{Pallet, Call, Storage, Event} = 41,
+
 		// Parachains pallets. Start indices at 50 to leave room.
 		ParachainsOrigin: parachains_origin::{Pallet, Origin} = 50,
 		Configuration: parachains_configuration::{Pallet, Call, Storage, Config<T>} = 51,
@@ -1433,6 +1632,7 @@ construct_runtime! {
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 60,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 61,
 		ParasDisputes: parachains_disputes::{Pallet, Call, Storage, Event<T>} = 62,
+		MessagingMetrics: parachains_messaging_metrics::{Pallet, Storage} = 63,
 
 		// Parachain Onboarding Pallets. Start indices at 70 to leave room.
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>} = 70,
@@ -1480,6 +1680,10 @@ pub type Executive = frame_executive::Executive<
 		FixCouncilDepositMigration,
 		CrowdloanIndexMigration,
 		pallet_staking::migrations::v9::InjectValidatorsIntoVoterList<Runtime>,
+		auctions::migration::MigrateToV1<Runtime>,
+		parachains_messaging_metrics::migration::SeedQueueSizes<Runtime>,
+		governance::migration::InitializeGovernance,
+		InitializeNominationPools,
 	),
 >;
 /// The payload being signed in transactions.
@@ -1503,6 +1707,44 @@ impl OnRuntimeUpgrade for CrowdloanIndexMigration {
 	}
 }
 
+/// Sets the configuration of the nomination pools, which were added to the runtime after genesis.
+///
+/// Outside of the genesis config the minimum bonds and the maximum pool and member counts would
+/// be left unset, so no pool could be created. Once the pallet has a storage version or a
+/// configuration, nothing is changed, so later changes by governance are kept.
+pub struct InitializeNominationPools;
+impl OnRuntimeUpgrade for InitializeNominationPools {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		use frame_support::traits::{GetStorageVersion, StorageVersion};
+		use polkadot_runtime_constants::nomination_pools::*;
+
+		if NominationPools::on_chain_storage_version() != StorageVersion::new(0) ||
+			pallet_nomination_pools::MinCreateBond::<Runtime>::exists()
+		{
+			log::warn!(target: "runtime::polkadot", "InitializeNominationPools should be removed.");
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(2)
+		}
+
+		pallet_nomination_pools::MinJoinBond::<Runtime>::put(MIN_JOIN_BOND);
+		pallet_nomination_pools::MinCreateBond::<Runtime>::put(MIN_CREATE_BOND);
+		pallet_nomination_pools::MaxPools::<Runtime>::put(MAX_POOLS);
+		pallet_nomination_pools::MaxPoolMembersPerPool::<Runtime>::put(MAX_MEMBERS_PER_POOL);
+		pallet_nomination_pools::MaxPoolMembers::<Runtime>::put(MAX_MEMBERS);
+		NominationPools::current_storage_version().put::<NominationPools>();
+
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(2, 6)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			pallet_nomination_pools::MinCreateBond::<Runtime>::exists(),
+			"the nomination pools are not configured",
+		);
+		Ok(())
+	}
+}
+
 /// A migration struct to fix some deposits in the council election pallet.
 ///
 /// See more details here: https://github.com/paritytech/polkadot/issues/4160
@@ -1713,6 +1955,7 @@ mod benches {
 		[runtime_common::paras_registrar, Registrar]
 		[runtime_parachains::configuration, Configuration]
 		[runtime_parachains::disputes, ParasDisputes]
+		[runtime_parachains::dmp, Dmp]
 		[runtime_parachains::initializer, Initializer]
 		[runtime_parachains::paras, Paras]
 		[runtime_parachains::paras_inherent, ParaInherent]
@@ -1725,17 +1968,21 @@ mod benches {
 		[pallet_child_bounties, ChildBounties]
 		[pallet_collective, Council]
 		[pallet_collective, TechnicalCommittee]
+		[pallet_conviction_voting, ConvictionVoting]
 		[pallet_democracy, Democracy]
 		[pallet_elections_phragmen, PhragmenElection]
 		[pallet_election_provider_multi_phase, ElectionProviderMultiPhase]
+		[pallet_fast_unstake, FastUnstake]
 		[pallet_identity, Identity]
 		[pallet_im_online, ImOnline]
 		[pallet_indices, Indices]
 		[pallet_membership, TechnicalMembership]
 		[pallet_multisig, Multisig]
+		[pallet_nomination_pools, NominationPoolsBench::<Runtime>]
 		[pallet_offences, OffencesBench::<Runtime>]
 		[pallet_preimage, Preimage]
 		[pallet_proxy, Proxy]
+		[pallet_referenda, Referenda]
 		[pallet_scheduler, Scheduler]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_staking, Staking]
@@ -1745,6 +1992,12 @@ mod benches {
 		[pallet_treasury, Treasury]
 		[pallet_utility, Utility]
 		[pallet_vesting, Vesting]
+		[pallet_whitelist, Whitelist]
+		// XCM
+		[pallet_xcm, XcmPallet]
+		// NOTE: Make sure you point to the individual modules below.
+		[pallet_xcm_benchmarks::fungible, XcmBalances]
+		[pallet_xcm_benchmarks::generic, XcmGeneric]
 	);
 }
 
@@ -1904,6 +2157,10 @@ sp_api::impl_runtime_apis! {
 		{
 			parachains_runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams> {
+			parachains_runtime_api_impl::session_executor_params::<Runtime>(session_index)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -2051,6 +2308,38 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_hrmp::runtime_api::HrmpApi<Block> for Runtime {
+		fn hrmp_channel_fee_factor(sender: ParaId, recipient: ParaId) -> FixedU128 {
+			Hrmp::channel_fee_factor(&HrmpChannelId { sender, recipient })
+		}
+	}
+
+	impl parachains_paras::runtime_api::ParasApi<Block> for Runtime {
+		fn upgrade_restriction_signal(para_id: ParaId) -> Option<UpgradeRestriction> {
+			Paras::upgrade_restriction_signal(para_id)
+		}
+	}
+
+	impl parachains_scheduler::runtime_api::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn upcoming_core_assignments(
+			lookahead: u32,
+		) -> Vec<(BlockNumber, parachains_scheduler::CoreAssignment)> {
+			ParaScheduler::upcoming_assignments(lookahead)
+		}
+	}
+
+	impl auctions::runtime_api::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn current_auction_status() -> Option<auctions::runtime_api::AuctionSummary<BlockNumber>> {
+			Auctions::auction_summary(System::block_number())
+		}
+
+		fn winning_range(
+			now: BlockNumber,
+		) -> Vec<(AccountId, ParaId, Balance, runtime_common::slot_range::SlotRange)> {
+			Auctions::winners_at(now)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -2063,6 +2352,66 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::runtime_api::XcmTrapApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(sp_core::H256, pallet_xcm::TrappedAssets<AccountId, Balance, BlockNumber>)> {
+			match xcm::latest::MultiLocation::try_from(origin) {
+				Ok(origin) => XcmPallet::trapped_assets(&origin),
+				Err(()) => Vec::new(),
+			}
+		}
+	}
+
+	impl pallet_xcm::runtime_api::DryRunApi<Block, Call, Event, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> pallet_xcm::runtime_api::CallDryRunEffects<Event> {
+			XcmPallet::dry_run_call::<xcm_config::XcmRouter>(origin.into(), call)
+		}
+
+		fn dry_run_xcm(
+			origin_location: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<Call>,
+		) -> Result<
+			pallet_xcm::runtime_api::XcmDryRunEffects<Event>,
+			pallet_xcm::runtime_api::DryRunError,
+		> {
+			use pallet_xcm::runtime_api::DryRunError::VersionedConversionFailed;
+			let origin_location = xcm::latest::MultiLocation::try_from(origin_location)
+				.map_err(|()| VersionedConversionFailed)?;
+			let xcm = xcm::latest::Xcm::<Call>::try_from(xcm)
+				.map_err(|()| VersionedConversionFailed)?;
+			Ok(XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin_location, xcm))
+		}
+	}
+
+	impl pallet_xcm::runtime_api::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets() -> Vec<xcm::VersionedMultiLocation> {
+			vec![xcm_config::DotLocation::get().into()]
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: xcm::VersionedMultiLocation,
+		) -> Result<u128, pallet_xcm::runtime_api::XcmPaymentApiError> {
+			use pallet_xcm::runtime_api::XcmPaymentApiError;
+			let asset = xcm::latest::MultiLocation::try_from(asset)
+				.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+			if asset != xcm_config::DotLocation::get() {
+				return Err(XcmPaymentApiError::AssetNotFound)
+			}
+			Ok(<WeightToFee as frame_support::weights::WeightToFeePolynomial>::calc(&weight))
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<Weight, pallet_xcm::runtime_api::XcmPaymentApiError> {
+			XcmPallet::query_xcm_weight(message)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade() -> (Weight, Weight) {
@@ -2087,9 +2436,13 @@ sp_api::impl_runtime_apis! {
 
 			use pallet_session_benchmarking::Pallet as SessionBench;
 			use pallet_offences_benchmarking::Pallet as OffencesBench;
+			use pallet_nomination_pools_benchmarking::Pallet as NominationPoolsBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
 
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
 
@@ -2103,19 +2456,109 @@ sp_api::impl_runtime_apis! {
 			Vec<frame_benchmarking::BenchmarkBatch>,
 			sp_runtime::RuntimeString,
 		> {
-			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey};
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey, BenchmarkError};
 			// Trying to add benchmarks directly to some pallets caused cyclic dependency issues.
 			// To get around that, we separated the benchmarks into its own crate.
 			use pallet_session_benchmarking::Pallet as SessionBench;
 			use pallet_offences_benchmarking::Pallet as OffencesBench;
+			use pallet_nomination_pools_benchmarking::Pallet as NominationPoolsBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
 
 			impl pallet_session_benchmarking::Config for Runtime {}
 			impl pallet_offences_benchmarking::Config for Runtime {}
+			impl pallet_nomination_pools_benchmarking::Config for Runtime {}
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl frame_benchmarking::baseline::Config for Runtime {}
 
+			use xcm::latest::{
+				AssetId::*, Fungibility::*, Junctions::*, MultiAsset, MultiAssets, MultiLocation,
+				Response,
+			};
+			use xcm_config::DotLocation;
+
+			parameter_types! {
+				pub Statemint: MultiLocation = polkadot_runtime_constants::system_parachain::statemint_location();
+				pub TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = Some((
+					Statemint::get(),
+					MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(DotLocation::get()) },
+				));
+				// Polkadot recognises no chains which act as reserves.
+				pub const TrustedReserve: Option<(MultiLocation, MultiAsset)> = None;
+			}
+
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(DotLocation::get()) },
+						Statemint::get(),
+					))
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(DotLocation::get()) },
+						Statemint::get(),
+					))
+				}
+			}
+
+			impl pallet_xcm_benchmarks::Config for Runtime {
+				type XcmConfig = xcm_config::XcmConfig;
+				type AccountIdConverter = xcm_config::SovereignAccountOf;
+				fn valid_destination() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemint::get())
+				}
+				fn worst_case_holding() -> MultiAssets {
+					// Polkadot only knows about its native token.
+					vec![MultiAsset{
+						id: Concrete(DotLocation::get()),
+						fun: Fungible(1_000_000 * UNITS),
+					}].into()
+				}
+			}
+
+			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
+				type TransactAsset = Balances;
+
+				type CheckedAccount = xcm_config::LocalCheckAccount;
+				type TrustedTeleporter = TrustedTeleporter;
+				type TrustedReserve = TrustedReserve;
+
+				fn get_multi_asset() -> MultiAsset {
+					MultiAsset {
+						id: Concrete(DotLocation::get()),
+						fun: Fungible(1 * UNITS),
+					}
+				}
+			}
+
+			impl pallet_xcm_benchmarks::generic::Config for Runtime {
+				type Call = Call;
+
+				fn worst_case_response() -> (u64, Response) {
+					(0u64, Response::Version(Default::default()))
+				}
+
+				fn transact_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemint::get())
+				}
+
+				fn subscribe_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemint::get())
+				}
+
+				fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError> {
+					let origin = Statemint::get();
+					let assets: MultiAssets = (Concrete(DotLocation::get()), 1_000 * UNITS).into();
+					let ticket = MultiLocation { parents: 0, interior: Here };
+					Ok((origin, ticket, assets))
+				}
+			}
+
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
@@ -2147,7 +2590,7 @@ mod test_fees {
 	use frame_support::weights::{GetDispatchInfo, WeightToFeePolynomial};
 	use keyring::Sr25519Keyring::Charlie;
 	use pallet_transaction_payment::Multiplier;
-	use runtime_common::MinimumMultiplier;
+	use polkadot_runtime_constants::fee::MinimumMultiplier;
 	use separator::Separatable;
 	use sp_runtime::{assert_eq_error_rate, FixedPointNumber};
 
@@ -2297,4 +2740,24 @@ mod test {
 			If the limit is too strong, maybe consider increase the limit",
 		);
 	}
+
+	#[test]
+	fn nomination_pool_locations_convert_to_reward_accounts() {
+		use xcm::latest::prelude::*;
+		use xcm_config::{nomination_pool_location, NominationPoolRewardAccount};
+		use xcm_executor::traits::Convert;
+
+		let location = nomination_pool_location(7);
+		assert_eq!(location, MultiLocation::new(0, X2(PalletInstance(39), GeneralIndex(7))));
+		assert_eq!(
+			NominationPoolRewardAccount::convert(location),
+			Ok(NominationPools::create_reward_account(7)),
+		);
+
+		// Other pallets and out of range pool ids are not pools.
+		let other_pallet = MultiLocation::new(0, X2(PalletInstance(38), GeneralIndex(7)));
+		assert!(NominationPoolRewardAccount::convert(other_pallet).is_err());
+		let too_large = MultiLocation::new(0, X2(PalletInstance(39), GeneralIndex(u128::MAX)));
+		assert!(NominationPoolRewardAccount::convert(too_large).is_err());
+	}
 }
@@ -0,0 +1,114 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights for `pallet_conviction_voting`
+//!
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
+
+// Command to generate the weights:
+// ./target/production/polkadot
+// benchmark
+// --chain=polkadot-dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_conviction_voting
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./file_header.txt
+// --output=./runtime/polkadot/src/weights/pallet_conviction_voting.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_conviction_voting`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_conviction_voting::WeightInfo for WeightInfo<T> {
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: ConvictionVoting VotingFor (r:1 w:1)
+	// Storage: ConvictionVoting ClassLocksFor (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn vote_new() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: ConvictionVoting VotingFor (r:1 w:1)
+	// Storage: ConvictionVoting ClassLocksFor (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn vote_existing() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: ConvictionVoting VotingFor (r:1 w:1)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn remove_vote() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: ConvictionVoting VotingFor (r:1 w:1)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:0)
+	fn remove_other_vote() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConvictionVoting VotingFor (r:2 w:2)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: ConvictionVoting ClassLocksFor (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn delegate(r: u32, ) -> Weight {
+		(56_000_000 as Weight)
+			.saturating_add((27_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: ConvictionVoting VotingFor (r:2 w:2)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn undelegate(r: u32, ) -> Weight {
+		(37_000_000 as Weight)
+			.saturating_add((27_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: ConvictionVoting VotingFor (r:1 w:1)
+	// Storage: ConvictionVoting ClassLocksFor (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn unlock() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
@@ -0,0 +1,258 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights for `pallet_nomination_pools`
+//!
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
+
+// Command to generate the weights:
+// ./target/production/polkadot
+// benchmark
+// --chain=polkadot-dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_nomination_pools
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./file_header.txt
+// --output=./runtime/polkadot/src/weights/pallet_nomination_pools.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_nomination_pools`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_nomination_pools::WeightInfo for WeightInfo<T> {
+	// Storage: NominationPools MinJoinBond (r:1 w:0)
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:0)
+	// Storage: System Account (r:2 w:1)
+	// Storage: NominationPools MaxPoolMembersPerPool (r:1 w:0)
+	// Storage: NominationPools MaxPoolMembers (r:1 w:0)
+	// Storage: NominationPools CounterForPoolMembers (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: BagsList ListNodes (r:3 w:3)
+	// Storage: BagsList ListBags (r:2 w:2)
+	fn join() -> Weight {
+		(132_648_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: System Account (r:3 w:2)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: BagsList ListNodes (r:3 w:3)
+	// Storage: BagsList ListBags (r:2 w:2)
+	fn bond_extra_transfer() -> Weight {
+		(127_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: BagsList ListNodes (r:3 w:3)
+	// Storage: BagsList ListBags (r:2 w:2)
+	fn bond_extra_reward() -> Weight {
+		(141_286_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn claim_payout() -> Weight {
+		(52_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: System Account (r:2 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking Nominators (r:1 w:0)
+	// Storage: Staking MinNominatorBond (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: BagsList ListNodes (r:3 w:3)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: BagsList ListBags (r:2 w:2)
+	// Storage: NominationPools SubPoolsStorage (r:1 w:1)
+	// Storage: NominationPools CounterForSubPoolsStorage (r:1 w:1)
+	fn unbond() -> Weight {
+		(128_830_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	fn pool_withdraw_unbonded(s: u32, ) -> Weight {
+		(44_531_000 as Weight)
+			.saturating_add((50_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools SubPoolsStorage (r:1 w:1)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: NominationPools CounterForPoolMembers (r:1 w:1)
+	fn withdraw_unbonded_update(s: u32, ) -> Weight {
+		(87_316_000 as Weight)
+			.saturating_add((53_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: NominationPools SubPoolsStorage (r:1 w:1)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking SlashingSpans (r:1 w:0)
+	// Storage: Staking Validators (r:1 w:0)
+	// Storage: Staking Nominators (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: NominationPools CounterForPoolMembers (r:1 w:1)
+	// Storage: NominationPools ReversePoolIdLookup (r:1 w:1)
+	// Storage: NominationPools CounterForReversePoolIdLookup (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: NominationPools CounterForRewardPools (r:1 w:1)
+	// Storage: NominationPools CounterForSubPoolsStorage (r:1 w:1)
+	// Storage: NominationPools Metadata (r:1 w:1)
+	// Storage: NominationPools CounterForBondedPools (r:1 w:1)
+	// Storage: Staking Payee (r:0 w:1)
+	fn withdraw_unbonded_kill(_s: u32, ) -> Weight {
+		(143_962_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: Staking MinNominatorBond (r:1 w:0)
+	// Storage: NominationPools MinCreateBond (r:1 w:0)
+	// Storage: NominationPools MinJoinBond (r:1 w:0)
+	// Storage: NominationPools MaxPools (r:1 w:0)
+	// Storage: NominationPools CounterForBondedPools (r:1 w:1)
+	// Storage: NominationPools PoolMembers (r:1 w:1)
+	// Storage: NominationPools LastPoolId (r:1 w:1)
+	// Storage: NominationPools MaxPoolMembers (r:1 w:0)
+	// Storage: NominationPools CounterForPoolMembers (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Staking Ledger (r:1 w:1)
+	// Storage: Staking Bonded (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Staking HistoryDepth (r:1 w:0)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: NominationPools RewardPools (r:1 w:1)
+	// Storage: NominationPools CounterForRewardPools (r:1 w:1)
+	// Storage: NominationPools ReversePoolIdLookup (r:1 w:1)
+	// Storage: NominationPools CounterForReversePoolIdLookup (r:1 w:1)
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: Staking Payee (r:0 w:1)
+	fn create() -> Weight {
+		(127_102_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking MinNominatorBond (r:1 w:0)
+	// Storage: Staking Nominators (r:1 w:1)
+	// Storage: Staking MaxNominatorsCount (r:1 w:0)
+	// Storage: Staking Validators (r:2 w:0)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: Staking Bonded (r:1 w:0)
+	// Storage: BagsList ListNodes (r:1 w:1)
+	// Storage: BagsList ListBags (r:1 w:1)
+	// Storage: BagsList CounterForListNodes (r:1 w:1)
+	// Storage: Staking CounterForNominators (r:1 w:1)
+	fn nominate(n: u32, ) -> Weight {
+		(50_724_000 as Weight)
+			.saturating_add((2_224_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	// Storage: Staking Ledger (r:1 w:0)
+	fn set_state() -> Weight {
+		(27_486_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:0)
+	// Storage: NominationPools Metadata (r:1 w:1)
+	// Storage: NominationPools CounterForMetadata (r:1 w:1)
+	fn set_metadata(n: u32, ) -> Weight {
+		(14_912_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NominationPools MinJoinBond (r:0 w:1)
+	// Storage: NominationPools MaxPoolMembers (r:0 w:1)
+	// Storage: NominationPools MaxPoolMembersPerPool (r:0 w:1)
+	// Storage: NominationPools MinCreateBond (r:0 w:1)
+	// Storage: NominationPools MaxPools (r:0 w:1)
+	fn set_configs() -> Weight {
+		(6_581_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:1)
+	fn update_roles() -> Weight {
+		(22_317_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NominationPools BondedPools (r:1 w:0)
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking Validators (r:1 w:0)
+	// Storage: Staking Nominators (r:1 w:1)
+	// Storage: Staking CounterForNominators (r:1 w:1)
+	// Storage: BagsList ListNodes (r:1 w:1)
+	// Storage: BagsList ListBags (r:1 w:1)
+	// Storage: BagsList CounterForListNodes (r:1 w:1)
+	fn chill() -> Weight {
+		(49_193_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
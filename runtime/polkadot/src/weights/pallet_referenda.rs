@@ -0,0 +1,250 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights for `pallet_referenda`
+//!
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
+
+// Command to generate the weights:
+// ./target/production/polkadot
+// benchmark
+// --chain=polkadot-dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_referenda
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./file_header.txt
+// --output=./runtime/polkadot/src/weights/pallet_referenda.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_referenda`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_referenda::WeightInfo for WeightInfo<T> {
+	// Storage: Referenda ReferendumCount (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Referenda ReferendumInfoFor (r:0 w:1)
+	fn submit() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn place_decision_deposit_preparing() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	fn place_decision_deposit_queued() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	fn place_decision_deposit_not_queued() -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn place_decision_deposit_passing() -> Weight {
+		(64_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn place_decision_deposit_failing() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	fn refund_decision_deposit() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn cancel() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn kill() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda TrackQueue (r:1 w:0)
+	// Storage: Referenda DecidingCount (r:1 w:1)
+	fn one_fewer_deciding_queue_empty() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn one_fewer_deciding_failing() -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn one_fewer_deciding_passing() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_requeued_insertion() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_requeued_slide() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_queued() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:0)
+	// Storage: Referenda TrackQueue (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_not_queued() -> Weight {
+		(59_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_no_deposit() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_preparing() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	fn nudge_referendum_timed_out() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_begin_deciding_failing() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Referenda DecidingCount (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_begin_deciding_passing() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_begin_confirming() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_end_confirming() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_continue_not_confirming() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_continue_confirming() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:2 w:2)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	fn nudge_referendum_approved() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Referenda ReferendumInfoFor (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn nudge_referendum_rejected() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -92,4 +92,15 @@ impl<T: frame_system::Config> runtime_parachains::configuration::WeightInfo for
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Configuration PendingConfigs (r:1 w:1)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: Configuration BypassConsistencyCheck (r:1 w:0)
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	fn set_configs(n: u32, ) -> Weight {
+		(10_412_000 as Weight)
+			.saturating_add((131_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,82 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Weights for `runtime_parachains::dmp`
+//!
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
+
+// Command to generate the weights:
+// ./target/production/polkadot
+// benchmark
+// --chain=polkadot-dev
+// --steps=50
+// --repeat=20
+// --pallet=runtime_parachains::dmp
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./file_header.txt
+// --output=./runtime/polkadot/src/weights/runtime_parachains_dmp.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `runtime_parachains::dmp`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> runtime_parachains::dmp::WeightInfo for WeightInfo<T> {
+	// Storage: Dmp LegacyQueuesPending (r:1 w:0)
+	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueState (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueuePages (r:1 w:1)
+	// Storage: MessagingMetrics DownwardQueueSizes (r:1 w:1)
+	fn queue_downward_message() -> Weight {
+		(15_218_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dmp LegacyQueuesPending (r:1 w:0)
+	// Storage: Dmp DownwardMessageQueueState (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueuePages (r:1 w:1)
+	// Storage: MessagingMetrics DownwardQueueSizes (r:1 w:1)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: Dmp DeliveryFeeFactor (r:1 w:1)
+	fn prune_dmq(p: u32, ) -> Weight {
+		(9_874_000 as Weight)
+			.saturating_add((2_366_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Dmp LegacyQueuesPending (r:1 w:0)
+	// Storage: Dmp DownwardMessageQueuePages (r:0 w:1)
+	// Storage: Dmp DownwardMessageQueueState (r:0 w:1)
+	// Storage: MessagingMetrics DownwardQueueSizes (r:0 w:1)
+	fn sweep_queue(p: u32, ) -> Weight {
+		(7_102_000 as Weight)
+			.saturating_add((1_212_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+}
@@ -17,23 +17,30 @@
 //! XCM configuration for Polkadot.
 
 use super::{
-	parachains_origin, AccountId, Balances, Call, CouncilCollective, Event, Origin, ParaId,
-	Runtime, WeightToFee, XcmPallet,
+	governance::{FellowshipAdmin, GeneralAdmin, StakingAdmin, Treasurer},
+	parachains_origin, weights, AccountId, AllPalletsWithSystem, Balance, Balances, BlockNumber,
+	Call, CouncilCollective, Event, NominationPools, Origin, ParaId, Runtime, Treasury,
+	WeightToFee, XcmPallet, DAYS,
 };
 use frame_support::{
 	match_types, parameter_types,
-	traits::{Everything, Nothing},
+	traits::{Everything, Nothing, PalletInfoAccess},
 	weights::Weight,
 };
+use pallet_nomination_pools::PoolId;
+use polkadot_runtime_constants::{currency::deposit, system_parachain};
 use runtime_common::{xcm_sender, ToAuthor};
+use sp_std::prelude::*;
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, BackingToPlurality, ChildParachainAsNative,
 	ChildParachainConvertsVia, CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds,
-	IsConcrete, LocationInverter, SignedAccountId32AsNative, SignedToAccountId32,
+	IsConcrete, MintLocation, NetworkExportTable, OriginToPluralityVoice, PayOverXcm,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignPaidRemoteExporter,
 	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
 };
+use xcm_executor::traits::Convert;
 
 parameter_types! {
 	/// The location of the DOT token, from the context of this chain. Since this token is native to this
@@ -42,11 +49,14 @@ parameter_types! {
 	pub const DotLocation: MultiLocation = Here.into();
 	/// The Polkadot network ID. This is named.
 	pub const PolkadotNetwork: NetworkId = NetworkId::Polkadot;
-	/// Our XCM location ancestry - i.e. what, if anything, `Parent` means evaluated in our context. Since
-	/// Polkadot is a top-level relay-chain, there is no ancestry.
-	pub const Ancestry: MultiLocation = Here.into();
+	/// Our location in the universe of consensus systems. Since Polkadot is a top-level relay-chain
+	/// with its own consensus, it's just our network ID.
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(PolkadotNetwork::get()));
 	/// The check account, which holds any native assets that have been teleported out and not back in (yet).
 	pub CheckAccount: AccountId = XcmPallet::check_account();
+	/// Our native token is minted here, so the check account tracks the amount of it which lives on
+	/// other chains.
+	pub LocalCheckAccount: (AccountId, MintLocation) = (CheckAccount::get(), MintLocation::Local);
 }
 
 /// The canonical means of converting a `MultiLocation` into an `AccountId`, used when we want to determine
@@ -58,6 +68,43 @@ pub type SovereignAccountOf = (
 	AccountId32Aliases<PolkadotNetwork, AccountId>,
 );
 
+/// The location of the nomination pool `id`, from the context of this chain.
+///
+/// A pool is addressed as a `GeneralIndex` under the instance of the nomination pools pallet, so
+/// that parachains can target it like any other account of this chain.
+pub fn nomination_pool_location(id: PoolId) -> MultiLocation {
+	MultiLocation::new(
+		0,
+		X2(PalletInstance(NominationPools::index() as u8), GeneralIndex(id as u128)),
+	)
+}
+
+/// Converts the location of a nomination pool, as given by `nomination_pool_location`, into the
+/// reward account of the pool.
+///
+/// Assets deposited there are paid out to the members of the pool as rewards.
+pub struct NominationPoolRewardAccount;
+impl Convert<MultiLocation, AccountId> for NominationPoolRewardAccount {
+	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
+		match location {
+			MultiLocation { parents: 0, interior: X2(PalletInstance(index), GeneralIndex(id)) }
+				if index as usize == NominationPools::index() =>
+				match PoolId::try_from(id) {
+					Ok(id) => Ok(NominationPools::create_reward_account(id)),
+					Err(_) => Err(location),
+				},
+			_ => Err(location),
+		}
+	}
+}
+
+/// The means of converting a `MultiLocation` into the `AccountId` holding assets on its behalf.
+///
+/// On top of the sovereign accounts, the reward accounts of the nomination pools can be targeted by
+/// the location of their pool. Pool locations are never the origin of an XCM, so they are
+/// deliberately not part of `SovereignAccountOf`.
+pub type LocationToAccountId = (SovereignAccountOf, NominationPoolRewardAccount);
+
 /// Our asset transactor. This is what allows us to interact with the runtime assets from the point of
 /// view of XCM-only concepts like `MultiLocation` and `MultiAsset`.
 ///
@@ -68,11 +115,11 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	// Use this currency when it is a fungible asset matching the given location or name:
 	IsConcrete<DotLocation>,
 	// We can convert the MultiLocations with our converter above:
-	SovereignAccountOf,
+	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
 	AccountId,
 	// We track our teleports in/out to keep total issuance correct.
-	CheckAccount,
+	LocalCheckAccount,
 >;
 
 /// The means that we convert an XCM origin `MultiLocation` into the runtime's `Origin` type for
@@ -98,16 +145,31 @@ parameter_types! {
 	pub const MaxInstructions: u32 = 100;
 }
 
+parameter_types! {
+	/// The bridged networks which may be reached from here, together with the location of the
+	/// bridge through which each is reached and the fee, if any, which the bridge charges for
+	/// exporting a message. The Bridge Hub is a system parachain and exports for free.
+	pub BridgeTable: Vec<(NetworkId, MultiLocation, Option<MultiAsset>)> = vec![
+		(NetworkId::Kusama, system_parachain::bridge_hub_location(), None),
+	];
+}
+
 /// The XCM router. When we want to send an XCM message, we use this type. It amalgamates all of our
 /// individual routers.
 pub type XcmRouter = (
-	// Only one router so far - use DMP to communicate with child parachains.
+	// Use DMP to communicate with child parachains.
 	xcm_sender::ChildParachainRouter<Runtime, XcmPallet>,
+	// Messages for bridged networks are wrapped in `ExportMessage` and sent on to the bridge.
+	SovereignPaidRemoteExporter<
+		NetworkExportTable<BridgeTable>,
+		xcm_sender::ChildParachainRouter<Runtime, XcmPallet>,
+		UniversalLocation,
+	>,
 );
 
 parameter_types! {
 	pub const Polkadot: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(DotLocation::get()) });
-	pub const PolkadotForStatemint: (MultiAssetFilter, MultiLocation) = (Polkadot::get(), Parachain(1000).into());
+	pub const PolkadotForStatemint: (MultiAssetFilter, MultiLocation) = (Polkadot::get(), system_parachain::statemint_location());
 }
 
 /// Polkadot Relay recognizes/respects the Statemint chain as a teleporter.
@@ -140,7 +202,9 @@ impl xcm_executor::Config for XcmConfig {
 	// Polkadot Relay recognises no chains which act as reserves.
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = XcmPallet;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	// The weight trader piggybacks on the existing transaction-fee conversion logic.
@@ -149,10 +213,57 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	// Messages are exported to bridged networks by the Bridge Hub, not by the relay chain itself;
+	// see `XcmRouter`.
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+}
+
+parameter_types! {
+	/// The location of the treasury account, from which the treasury pays out over XCM.
+	pub TreasuryAccountLocation: MultiLocation =
+		AccountId32 { network: PolkadotNetwork::get(), id: Treasury::account_id().into() }.into();
+	/// The asset in which the treasury pays out over XCM.
+	pub TreasuryPaymentAsset: AssetId = Concrete(DotLocation::get());
+	/// The number of blocks within which the outcome of a treasury payout should be reported.
+	pub const TreasuryPaymentTimeout: BlockNumber = 1 * DAYS;
+	/// The weight of withdrawing a treasury payout and sending it on its way.
+	pub TreasuryPaymentMaxWeight: Weight = 2 * BaseXcmWeight::get();
 }
 
+/// Pays treasury spends out to beneficiaries on parachains.
+///
+/// Payouts of DOT to Statemint are teleported, all others are reserve transferred to their
+/// destination. The outcome of each payout is reported back to the XCM pallet, through which the
+/// status of the payout may be tracked.
+pub type TreasuryPaymaster = PayOverXcm<
+	TreasuryAccountLocation,
+	xcm_executor::XcmExecutor<XcmConfig>,
+	Call,
+	XcmPallet,
+	TreasuryPaymentTimeout,
+	TrustedTeleporters,
+	UniversalLocation,
+	TreasuryPaymentMaxWeight,
+>;
+
 parameter_types! {
 	pub const CouncilBodyId: BodyId = BodyId::Executive;
+	// The bodies of the governance track origins are identified by the index of their track.
+	pub const StakingAdminBodyId: BodyId = BodyId::Index(10);
+	pub const TreasurerBodyId: BodyId = BodyId::Index(11);
+	pub const FellowshipAdminBodyId: BodyId = BodyId::Index(13);
+	pub const GeneralAdminBodyId: BodyId = BodyId::Index(14);
+	/// The period during which trapped assets may only be claimed by the location which trapped them.
+	pub const TrappedAssetsExpiry: BlockNumber = 28 * DAYS;
+	/// The deposit for each trap which may be swept to the treasury, covering its entry and its
+	/// share of the trapped assets' details.
+	pub const TrapDeposit: Balance = deposit(1, 128);
+	/// The treasury's location, to which expired trapped assets are swept.
+	pub TreasuryLocation: Option<MultiLocation> = Some(
+		AccountId32 { network: PolkadotNetwork::get(), id: Treasury::account_id().into() }.into()
+	);
 	// We are conservative with the XCM version we advertize.
 	pub const AdvertisedXcmVersion: u32 = 2;
 }
@@ -171,10 +282,23 @@ pub type LocalOriginToLocation = (
 	SignedToAccountId32<Origin, AccountId, PolkadotNetwork>,
 );
 
+/// Type to convert the governance track origins into a `MultiLocation` value which represents the
+/// voice of the corresponding body of this chain.
+pub type LocalPalletOriginToLocation = (
+	// StakingAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, StakingAdmin, StakingAdminBodyId>,
+	// Treasurer origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, Treasurer, TreasurerBodyId>,
+	// FellowshipAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, FellowshipAdmin, FellowshipAdminBodyId>,
+	// GeneralAdmin origin to be used in XCM as a corresponding Plurality `MultiLocation` value.
+	OriginToPluralityVoice<Origin, GeneralAdmin, GeneralAdminBodyId>,
+);
+
 impl pallet_xcm::Config for Runtime {
 	type Event = Event;
-	// Not much use in sending XCM at this point.
-	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, ()>; // == Deny All
+	// Only the governance track origins may send XCM at this point.
+	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalPalletOriginToLocation>;
 	type XcmRouter = XcmRouter;
 	// Anyone can execute XCM messages locally...
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
@@ -184,9 +308,17 @@ impl pallet_xcm::Config for Runtime {
 	type XcmTeleportFilter = Everything; // == Allow All
 	type XcmReserveTransferFilter = Everything; // == Allow All
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Origin = Origin;
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = AdvertisedXcmVersion;
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	type TreasuryLocation = TreasuryLocation;
+	type Currency = Balances;
+	type TrapDeposit = TrapDeposit;
+	type SovereignAccountOf = SovereignAccountOf;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = ();
+	type WeightInfo = weights::pallet_xcm::WeightInfo<Runtime>;
 }
@@ -36,10 +36,11 @@ use pallet_transaction_payment::{CurrencyAdapter, FeeDetails, RuntimeDispatchInf
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::v2::{
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
-	Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement,
-	ScrapedOnChainVotes, SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId,
-	ValidatorIndex, ValidatorSignature,
+	CoreState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
+	PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionInfo, Signature,
+	UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 use runtime_common::{
 	assigned_slots, auctions, crowdloan, impl_runtime_weights, impls::ToAuthor, paras_registrar,
@@ -66,7 +67,8 @@ use sp_version::RuntimeVersion;
 use runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, messaging_metrics as parachains_messaging_metrics,
+	on_demand as parachains_on_demand, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, scheduler as parachains_scheduler,
 	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
 };
@@ -148,6 +150,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		auctions::migration::MigrateToV1<Runtime>,
+		parachains_messaging_metrics::migration::SeedQueueSizes<Runtime>,
+	),
 >;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -211,7 +217,7 @@ construct_runtime! {
 		Auctions: auctions::{Pallet, Call, Storage, Event<T>},
 		Crowdloan: crowdloan::{Pallet, Call, Storage, Event<T>},
 		Slots: slots::{Pallet, Call, Storage, Event<T>},
-		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call},
+		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call, Event},
 		AssignedSlots: assigned_slots::{Pallet, Call, Storage, Event<T>},
 
 		// Sudo
@@ -238,6 +244,12 @@ construct_runtime! {
 		BridgeRococoMessagesDispatch: pallet_bridge_dispatch::{Pallet, Event<T>} = 45,
 		BridgeWococoMessagesDispatch: pallet_bridge_dispatch::<Instance1>::{Pallet, Event<T>} = 46,
 
+		// On-demand orders for parathreads.
+		OnDemand: parachains_on_demand = 47,
+
+		// Sizes of the message queues between the relay chain and the paras.
+		MessagingMetrics: parachains_messaging_metrics = 48,
+
 		// A "council"
 		Collective: pallet_collective = 80,
 		Membership: pallet_membership = 81,
@@ -612,7 +624,11 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = weights::runtime_parachains_dmp::WeightInfo<Runtime>;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
 	type Event = Event;
@@ -627,13 +643,26 @@ impl parachains_paras_inherent::Config for Runtime {
 
 impl parachains_scheduler::Config for Runtime {}
 
+parameter_types! {
+	pub const OnDemandBaseSpotPrice: Balance = 1 * DOLLARS;
+}
+
+impl parachains_on_demand::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BaseSpotPrice = OnDemandBaseSpotPrice;
+	type WeightInfo = weights::runtime_parachains_on_demand::WeightInfo<Runtime>;
+}
+
 impl parachains_initializer::Config for Runtime {
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = weights::runtime_parachains_initializer::WeightInfo<Runtime>;
 }
 
-impl paras_sudo_wrapper::Config for Runtime {}
+impl paras_sudo_wrapper::Config for Runtime {
+	type Event = Event;
+}
 
 parameter_types! {
 	pub const PermanentSlotLeasePeriodLength: u32 = 365;
@@ -897,6 +926,8 @@ parameter_types! {
 	pub const RemoveKeysLimit: u32 = 500;
 	// Allow 32 bytes for an additional memo to a crowdloan.
 	pub const MaxMemoLength: u8 = 32;
+	// The most a parachain may contribute to a crowdloan on behalf of its users.
+	pub const MaxParaContribution: Balance = 1_000_000 * DOLLARS;
 }
 
 impl crowdloan::Config for Runtime {
@@ -908,6 +939,8 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type ParaContributionOrigin = parachains_origin::EnsureParachain;
+	type MaxParaContribution = MaxParaContribution;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -957,6 +990,7 @@ pub enum ProxyType {
 	Any,
 	CancelProxy,
 	Auction,
+	ParaRegistration,
 }
 impl Default for ProxyType {
 	fn default() -> Self {
@@ -967,8 +1001,13 @@ impl InstanceFilter<Call> for ProxyType {
 	fn filter(&self, c: &Call) -> bool {
 		match self {
 			ProxyType::Any => true,
-			ProxyType::CancelProxy => {
-				matches!(c, Call::Proxy(pallet_proxy::Call::reject_announcement { .. }))
+			ProxyType::CancelProxy => match c {
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) => true,
+				// Only batches which do nothing but reject announcements.
+				Call::Utility(pallet_utility::Call::batch { calls }) |
+				Call::Utility(pallet_utility::Call::batch_all { calls }) =>
+					calls.iter().all(|call| ProxyType::CancelProxy.filter(call)),
+				_ => false,
 			},
 			ProxyType::Auction => matches!(
 				c,
@@ -977,6 +1016,14 @@ impl InstanceFilter<Call> for ProxyType {
 					Call::Registrar { .. } |
 					Call::Multisig(..) | Call::Slots { .. }
 			),
+			ProxyType::ParaRegistration => matches!(
+				c,
+				Call::Registrar(paras_registrar::Call::reserve { .. }) |
+					Call::Registrar(paras_registrar::Call::register { .. }) |
+					Call::Registrar(paras_registrar::Call::register_parathread { .. }) |
+					Call::Registrar(paras_registrar::Call::deregister { .. }) |
+					Call::Crowdloan(..) | Call::Utility(..)
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -1065,8 +1112,10 @@ mod benches {
 		[runtime_common::paras_registrar, Registrar]
 		[runtime_common::slots, Slots]
 		[runtime_parachains::configuration, Configuration]
+		[runtime_parachains::dmp, Dmp]
 		[runtime_parachains::hrmp, Hrmp]
 		[runtime_parachains::initializer, Initializer]
+		[runtime_parachains::on_demand, OnDemand]
 		[runtime_parachains::paras, Paras]
 		[runtime_parachains::disputes, ParasDisputes]
 		[runtime_parachains::paras_inherent, ParaInherent]
@@ -1083,6 +1132,11 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_timestamp, Timestamp]
 		[pallet_utility, Utility]
+		// XCM
+		[pallet_xcm, XcmPallet]
+		// NOTE: Make sure you point to the individual modules below.
+		[pallet_xcm_benchmarks::fungible, XcmBalances]
+		[pallet_xcm_benchmarks::generic, XcmGeneric]
 	);
 }
 
@@ -1239,6 +1293,48 @@ sp_api::impl_runtime_apis! {
 		{
 			runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams> {
+			runtime_api_impl::session_executor_params::<Runtime>(session_index)
+		}
+	}
+
+	impl primitives::vstaging::StagingParachainHost<Block, Hash, BlockNumber> for Runtime {
+		fn staging_para_backing_state(
+			para_id: ParaId,
+		) -> Option<primitives::vstaging::BackingState<Hash, BlockNumber>> {
+			runtime_parachains::runtime_api_impl::vstaging::backing_state::<Runtime>(para_id)
+		}
+
+		fn staging_async_backing_params() -> primitives::vstaging::AsyncBackingParams {
+			runtime_parachains::runtime_api_impl::vstaging::async_backing_params::<Runtime>()
+		}
+
+		fn staging_candidate_events_for_para(para_id: ParaId) -> Vec<CandidateEvent<Hash>> {
+			runtime_parachains::runtime_api_impl::vstaging::candidate_events_for_para::<Runtime, _>(
+				para_id,
+				|ev| {
+					match ev {
+						Event::ParaInclusion(ev) => {
+							Some(ev)
+						}
+						_ => None,
+					}
+				},
+			)
+		}
+
+		fn staging_on_chain_votes_in_session(
+			session: SessionIndex,
+		) -> Option<ScrapedOnChainVotes<Hash>> {
+			runtime_parachains::runtime_api_impl::vstaging::on_chain_votes_in_session::<Runtime>(
+				session,
+			)
+		}
+
+		fn staging_message_queue_sizes() -> primitives::vstaging::MessageQueueSizes {
+			runtime_parachains::runtime_api_impl::vstaging::message_queue_sizes::<Runtime>()
+		}
 	}
 
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
@@ -1477,6 +1573,38 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_hrmp::runtime_api::HrmpApi<Block> for Runtime {
+		fn hrmp_channel_fee_factor(sender: ParaId, recipient: ParaId) -> FixedU128 {
+			Hrmp::channel_fee_factor(&HrmpChannelId { sender, recipient })
+		}
+	}
+
+	impl parachains_paras::runtime_api::ParasApi<Block> for Runtime {
+		fn upgrade_restriction_signal(para_id: ParaId) -> Option<UpgradeRestriction> {
+			Paras::upgrade_restriction_signal(para_id)
+		}
+	}
+
+	impl parachains_scheduler::runtime_api::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn upcoming_core_assignments(
+			lookahead: u32,
+		) -> Vec<(BlockNumber, parachains_scheduler::CoreAssignment)> {
+			ParaScheduler::upcoming_assignments(lookahead)
+		}
+	}
+
+	impl auctions::runtime_api::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn current_auction_status() -> Option<auctions::runtime_api::AuctionSummary<BlockNumber>> {
+			Auctions::auction_summary(System::block_number())
+		}
+
+		fn winning_range(
+			now: BlockNumber,
+		) -> Vec<(AccountId, ParaId, Balance, runtime_common::slot_range::SlotRange)> {
+			Auctions::winners_at(now)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -1489,6 +1617,41 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::runtime_api::XcmTrapApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(sp_core::H256, pallet_xcm::TrappedAssets<AccountId, Balance, BlockNumber>)> {
+			match xcm::latest::MultiLocation::try_from(origin) {
+				Ok(origin) => XcmPallet::trapped_assets(&origin),
+				Err(()) => Vec::new(),
+			}
+		}
+	}
+
+	impl pallet_xcm::runtime_api::DryRunApi<Block, Call, Event, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> pallet_xcm::runtime_api::CallDryRunEffects<Event> {
+			XcmPallet::dry_run_call::<xcm_config::XcmRouter>(origin.into(), call)
+		}
+
+		fn dry_run_xcm(
+			origin_location: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<Call>,
+		) -> Result<
+			pallet_xcm::runtime_api::XcmDryRunEffects<Event>,
+			pallet_xcm::runtime_api::DryRunError,
+		> {
+			use pallet_xcm::runtime_api::DryRunError::VersionedConversionFailed;
+			let origin_location = xcm::latest::MultiLocation::try_from(origin_location)
+				.map_err(|()| VersionedConversionFailed)?;
+			let xcm = xcm::latest::Xcm::<Call>::try_from(xcm)
+				.map_err(|()| VersionedConversionFailed)?;
+			Ok(XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin_location, xcm))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1501,6 +1664,9 @@ sp_api::impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
 
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
 
@@ -1514,13 +1680,101 @@ sp_api::impl_runtime_apis! {
 			Vec<frame_benchmarking::BenchmarkBatch>,
 			sp_runtime::RuntimeString,
 		> {
-			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey};
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey, BenchmarkError};
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
 
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl frame_benchmarking::baseline::Config for Runtime {}
 
+			use xcm::latest::{
+				AssetId::*, Fungibility::*, Junction::Parachain, Junctions::*, MultiAsset, MultiAssets,
+				MultiLocation, Response,
+			};
+			use xcm_config::RocLocation;
+
+			parameter_types! {
+				pub Statemine: MultiLocation = Parachain(1000).into();
+				pub TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = Some((
+					Statemine::get(),
+					MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(RocLocation::get()) },
+				));
+				// Rococo recognises no chains which act as reserves.
+				pub const TrustedReserve: Option<(MultiLocation, MultiAsset)> = None;
+			}
+
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(RocLocation::get()) },
+						Statemine::get(),
+					))
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(RocLocation::get()) },
+						Statemine::get(),
+					))
+				}
+			}
+
+			impl pallet_xcm_benchmarks::Config for Runtime {
+				type XcmConfig = xcm_config::XcmConfig;
+				type AccountIdConverter = xcm_config::SovereignAccountOf;
+				fn valid_destination() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+				fn worst_case_holding() -> MultiAssets {
+					// Rococo only knows about its native token.
+					vec![MultiAsset{
+						id: Concrete(RocLocation::get()),
+						fun: Fungible(1_000_000 * UNITS),
+					}].into()
+				}
+			}
+
+			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
+				type TransactAsset = Balances;
+
+				type CheckedAccount = xcm_config::LocalCheckAccount;
+				type TrustedTeleporter = TrustedTeleporter;
+				type TrustedReserve = TrustedReserve;
+
+				fn get_multi_asset() -> MultiAsset {
+					MultiAsset {
+						id: Concrete(RocLocation::get()),
+						fun: Fungible(1 * UNITS),
+					}
+				}
+			}
+
+			impl pallet_xcm_benchmarks::generic::Config for Runtime {
+				type Call = Call;
+
+				fn worst_case_response() -> (u64, Response) {
+					(0u64, Response::Version(Default::default()))
+				}
+
+				fn transact_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+
+				fn subscribe_origin() -> Result<MultiLocation, BenchmarkError> {
+					Ok(Statemine::get())
+				}
+
+				fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError> {
+					let origin = Statemine::get();
+					let assets: MultiAssets = (Concrete(RocLocation::get()), 1_000 * UNITS).into();
+					let ticket = MultiLocation { parents: 0, interior: Here };
+					Ok((origin, ticket, assets))
+				}
+			}
+
+			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
+			type XcmGeneric = pallet_xcm_benchmarks::generic::Pallet::<Runtime>;
+
 			let mut batches = Vec::<BenchmarkBatch>::new();
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
@@ -17,8 +17,8 @@
 //! XCM configuration for Rococo.
 
 use super::{
-	parachains_origin, AccountId, Balances, Call, Event, Origin, ParaId, Runtime, WeightToFee,
-	XcmPallet,
+	parachains_origin, weights, AccountId, AllPalletsWithSystem, Balances, BlockNumber, Call,
+	Event, Origin, ParaId, Runtime, WeightToFee, XcmPallet, DAYS,
 };
 use frame_support::{
 	parameter_types,
@@ -31,15 +31,19 @@ use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom, BackingToPlurality,
 	ChildParachainAsNative, ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
-	CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds, IsConcrete, LocationInverter,
+	CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds, IsConcrete, MintLocation,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, UsingComponents,
 };
 
 parameter_types! {
 	pub const RocLocation: MultiLocation = Here.into();
 	pub const RococoNetwork: NetworkId = NetworkId::Polkadot;
-	pub const Ancestry: MultiLocation = Here.into();
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(RococoNetwork::get()));
 	pub CheckAccount: AccountId = XcmPallet::check_account();
+	/// Our native token is minted here, so the check account tracks the amount of it which lives on
+	/// other chains.
+	pub LocalCheckAccount: (AccountId, MintLocation) = (CheckAccount::get(), MintLocation::Local);
+	pub const TrappedAssetsExpiry: BlockNumber = 7 * DAYS;
 }
 
 pub type SovereignAccountOf =
@@ -55,7 +59,7 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
 	AccountId,
 	// It's a native asset so we keep track of the teleports to maintain total issuance.
-	CheckAccount,
+	LocalCheckAccount,
 >;
 
 type LocalOriginConverter = (
@@ -126,7 +130,9 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = XcmPallet;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	type Trader = UsingComponents<WeightToFee, RocLocation, AccountId, Balances, ToAuthor<Runtime>>;
@@ -134,6 +140,9 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 parameter_types! {
@@ -162,9 +171,18 @@ impl pallet_xcm::Config for Runtime {
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Origin = Origin;
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	// No treasury, so expired trapped assets stay trapped and no deposit is taken for them.
+	type TreasuryLocation = ();
+	type Currency = Balances;
+	type TrapDeposit = ();
+	type SovereignAccountOf = SovereignAccountOf;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = ();
+	type WeightInfo = weights::pallet_xcm::WeightInfo<Runtime>;
 }
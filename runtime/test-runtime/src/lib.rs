@@ -27,7 +27,8 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use polkadot_runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, messaging_metrics as parachains_messaging_metrics,
+	origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, runtime_api_impl::v2 as runtime_impl,
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared, ump as parachains_ump,
@@ -46,10 +47,10 @@ use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use polkadot_runtime_parachains::reward_points::RewardValidatorsWithEraPoints;
 use primitives::v2::{
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash as HashT, Id as ParaId, InboundDownwardMessage,
-	InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData,
-	ScrapedOnChainVotes, SessionInfo as SessionInfoData, Signature, ValidationCode,
-	ValidationCodeHash, ValidatorId, ValidatorIndex,
+	CoreState, ExecutorParams, GroupRotationInfo, Hash as HashT, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
+	PersistedValidationData, ScrapedOnChainVotes, SessionInfo as SessionInfoData, Signature,
+	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
 };
 use runtime_common::{
 	claims, impl_runtime_weights, paras_sudo_wrapper, BlockHashCount, BlockLength,
@@ -503,7 +504,11 @@ impl parachains_paras::Config for Runtime {
 	type NextSessionRotation = Babe;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = parachains_dmp::TestWeightInfo;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -521,6 +526,7 @@ parameter_types! {
 	pub const BaseXcmWeight: frame_support::weights::Weight = 1_000;
 	pub const AnyNetwork: xcm::latest::NetworkId = xcm::latest::NetworkId::Any;
 	pub const MaxInstructions: u32 = 100;
+	pub const TrappedAssetsExpiry: BlockNumber = DAYS;
 }
 
 pub type LocalOriginToLocation = xcm_builder::SignedToAccountId32<Origin, AccountId, AnyNetwork>;
@@ -530,7 +536,7 @@ impl pallet_xcm::Config for Runtime {
 	// is `XcmExecutor`, which will be used in unit tests located in xcm-executor.
 	type Event = Event;
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
-	type LocationInverter = xcm_config::InvertNothing;
+	type UniversalLocation = xcm_config::UniversalLocation;
 	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
 	type Weigher = xcm_builder::FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	type XcmRouter = xcm_config::DoNothingRouter;
@@ -542,6 +548,15 @@ impl pallet_xcm::Config for Runtime {
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	// No treasury, so expired trapped assets stay trapped and no deposit is taken for them.
+	type TreasuryLocation = ();
+	type Currency = Balances;
+	type TrapDeposit = ();
+	type SovereignAccountOf = xcm_builder::AccountId32Aliases<AnyNetwork, AccountId>;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = ();
+	type WeightInfo = pallet_xcm::TestWeightInfo;
 }
 
 impl parachains_hrmp::Config for Runtime {
@@ -553,7 +568,9 @@ impl parachains_hrmp::Config for Runtime {
 
 impl parachains_scheduler::Config for Runtime {}
 
-impl paras_sudo_wrapper::Config for Runtime {}
+impl paras_sudo_wrapper::Config for Runtime {
+	type Event = Event;
+}
 
 impl parachains_origin::Config for Runtime {}
 
@@ -683,12 +700,13 @@ construct_runtime! {
 		Paras: parachains_paras::{Pallet, Call, Storage, Event},
 		ParasShared: parachains_shared::{Pallet, Call, Storage},
 		Scheduler: parachains_scheduler::{Pallet, Storage},
-		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call},
+		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call, Event},
 		ParasOrigin: parachains_origin::{Pallet, Origin},
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage},
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>},
 		Ump: parachains_ump::{Pallet, Call, Storage, Event},
 		Dmp: parachains_dmp::{Pallet, Call, Storage},
+		MessagingMetrics: parachains_messaging_metrics::{Pallet, Storage},
 		Xcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		ParasDisputes: parachains_disputes::{Pallet, Storage, Event<T>},
 
@@ -894,6 +912,10 @@ sp_api::impl_runtime_apis! {
 		{
 			runtime_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams> {
+			runtime_impl::session_executor_params::<Runtime>(session_index)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
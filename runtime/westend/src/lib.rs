@@ -37,10 +37,11 @@ use pallet_transaction_payment::{CurrencyAdapter, FeeDetails, RuntimeDispatchInf
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::v2::{
 	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage,
-	Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement,
-	ScrapedOnChainVotes, SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId,
-	ValidatorIndex, ValidatorSignature,
+	CoreState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
+	PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionInfo, Signature,
+	UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature,
 };
 use runtime_common::{
 	assigned_slots, auctions, crowdloan, elections::OnChainSeqPhragmen, impl_runtime_weights,
@@ -50,7 +51,8 @@ use runtime_common::{
 use runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, messaging_metrics as parachains_messaging_metrics,
+	on_demand as parachains_on_demand, origin as parachains_origin, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
 	runtime_api_impl::v2 as parachains_runtime_api_impl, scheduler as parachains_scheduler,
 	session_info as parachains_session_info, shared as parachains_shared, ump as parachains_ump,
@@ -66,7 +68,7 @@ use sp_runtime::{
 		OpaqueKeys, SaturatedConversion, Verify,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, KeyTypeId, Perbill,
+	ApplyExtrinsicResult, FixedU128, KeyTypeId, Perbill,
 };
 use sp_staking::SessionIndex;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
@@ -706,6 +708,7 @@ pub enum ProxyType {
 	IdentityJudgement,
 	CancelProxy,
 	Auction,
+	ParaRegistration,
 }
 impl Default for ProxyType {
 	fn default() -> Self {
@@ -751,6 +754,7 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::Registrar(paras_registrar::Call::deregister{..}) |
 				// Specifically omitting Registrar `swap`
 				Call::Registrar(paras_registrar::Call::reserve{..}) |
+				Call::Registrar(paras_registrar::Call::register_parathread {..}) |
 				Call::Crowdloan(..) |
 				Call::Slots(..) |
 				Call::Auctions(..) | // Specifically omitting the entire XCM Pallet
@@ -770,13 +774,26 @@ impl InstanceFilter<Call> for ProxyType {
 				c,
 				Call::Identity(pallet_identity::Call::provide_judgement { .. }) | Call::Utility(..)
 			),
-			ProxyType::CancelProxy => {
-				matches!(c, Call::Proxy(pallet_proxy::Call::reject_announcement { .. }))
+			ProxyType::CancelProxy => match c {
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) => true,
+				// Only batches which do nothing but reject announcements.
+				Call::Utility(pallet_utility::Call::batch { calls }) |
+				Call::Utility(pallet_utility::Call::batch_all { calls }) =>
+					calls.iter().all(|call| ProxyType::CancelProxy.filter(call)),
+				_ => false,
 			},
 			ProxyType::Auction => matches!(
 				c,
 				Call::Auctions(..) | Call::Crowdloan(..) | Call::Registrar(..) | Call::Slots(..)
 			),
+			ProxyType::ParaRegistration => matches!(
+				c,
+				Call::Registrar(paras_registrar::Call::reserve { .. }) |
+					Call::Registrar(paras_registrar::Call::register { .. }) |
+					Call::Registrar(paras_registrar::Call::register_parathread { .. }) |
+					Call::Registrar(paras_registrar::Call::deregister { .. }) |
+					Call::Crowdloan(..) | Call::Utility(..)
+			),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -845,7 +862,11 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type WeightInfo = weights::runtime_parachains_dmp::WeightInfo<Runtime>;
+}
+
+impl parachains_messaging_metrics::Config for Runtime {}
 
 impl parachains_hrmp::Config for Runtime {
 	type Event = Event;
@@ -860,13 +881,26 @@ impl parachains_paras_inherent::Config for Runtime {
 
 impl parachains_scheduler::Config for Runtime {}
 
+parameter_types! {
+	pub const OnDemandBaseSpotPrice: Balance = 1 * UNITS;
+}
+
+impl parachains_on_demand::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BaseSpotPrice = OnDemandBaseSpotPrice;
+	type WeightInfo = weights::runtime_parachains_on_demand::WeightInfo<Runtime>;
+}
+
 impl parachains_initializer::Config for Runtime {
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = weights::runtime_parachains_initializer::WeightInfo<Runtime>;
 }
 
-impl paras_sudo_wrapper::Config for Runtime {}
+impl paras_sudo_wrapper::Config for Runtime {
+	type Event = Event;
+}
 
 parameter_types! {
 	pub const PermanentSlotLeasePeriodLength: u32 = 26;
@@ -930,6 +964,8 @@ parameter_types! {
 	pub const RemoveKeysLimit: u32 = 500;
 	// Allow 32 bytes for an additional memo to a crowdloan.
 	pub const MaxMemoLength: u8 = 32;
+	// The most a parachain may contribute to a crowdloan on behalf of its users.
+	pub const MaxParaContribution: Balance = 100 * GRAND;
 }
 
 impl crowdloan::Config for Runtime {
@@ -941,6 +977,8 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type ParaContributionOrigin = parachains_origin::EnsureParachain;
+	type MaxParaContribution = MaxParaContribution;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -1039,11 +1077,13 @@ construct_runtime! {
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 51,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 52,
 		ParasDisputes: parachains_disputes::{Pallet, Call, Storage, Event<T>} = 53,
+		OnDemand: parachains_on_demand::{Pallet, Call, Event<T>} = 54,
+		MessagingMetrics: parachains_messaging_metrics::{Pallet, Storage} = 55,
 
 		// Parachain Onboarding Pallets. Start indices at 60 to leave room.
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>, Config} = 60,
 		Slots: slots::{Pallet, Call, Storage, Event<T>} = 61,
-		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call} = 62,
+		ParasSudoWrapper: paras_sudo_wrapper::{Pallet, Call, Event} = 62,
 		Auctions: auctions::{Pallet, Call, Storage, Event<T>} = 63,
 		Crowdloan: crowdloan::{Pallet, Call, Storage, Event<T>} = 64,
 		AssignedSlots: assigned_slots::{Pallet, Call, Storage, Event<T>} = 65,
@@ -1086,6 +1126,8 @@ pub type Executive = frame_executive::Executive<
 	(
 		CrowdloanIndexMigration,
 		pallet_staking::migrations::v9::InjectValidatorsIntoVoterList<Runtime>,
+		auctions::migration::MigrateToV1<Runtime>,
+		parachains_messaging_metrics::migration::SeedQueueSizes<Runtime>,
 	),
 >;
 /// The payload being signed in transactions.
@@ -1125,8 +1167,10 @@ mod benches {
 		[runtime_common::slots, Slots]
 		[runtime_parachains::configuration, Configuration]
 		[runtime_parachains::disputes, ParasDisputes]
+		[runtime_parachains::dmp, Dmp]
 		[runtime_parachains::hrmp, Hrmp]
 		[runtime_parachains::initializer, Initializer]
+		[runtime_parachains::on_demand, OnDemand]
 		[runtime_parachains::paras, Paras]
 		[runtime_parachains::paras_inherent, ParaInherent]
 		[runtime_parachains::ump, Ump]
@@ -1149,6 +1193,7 @@ mod benches {
 		[pallet_utility, Utility]
 		[pallet_vesting, Vesting]
 		// XCM
+		[pallet_xcm, XcmPallet]
 		// NOTE: Make sure you point to the individual modules below.
 		[pallet_xcm_benchmarks::fungible, XcmBalances]
 		[pallet_xcm_benchmarks::generic, XcmGeneric]
@@ -1311,6 +1356,48 @@ sp_api::impl_runtime_apis! {
 		{
 			parachains_runtime_api_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn session_executor_params(session_index: SessionIndex) -> Option<ExecutorParams> {
+			parachains_runtime_api_impl::session_executor_params::<Runtime>(session_index)
+		}
+	}
+
+	impl primitives::vstaging::StagingParachainHost<Block, Hash, BlockNumber> for Runtime {
+		fn staging_para_backing_state(
+			para_id: ParaId,
+		) -> Option<primitives::vstaging::BackingState<Hash, BlockNumber>> {
+			runtime_parachains::runtime_api_impl::vstaging::backing_state::<Runtime>(para_id)
+		}
+
+		fn staging_async_backing_params() -> primitives::vstaging::AsyncBackingParams {
+			runtime_parachains::runtime_api_impl::vstaging::async_backing_params::<Runtime>()
+		}
+
+		fn staging_candidate_events_for_para(para_id: ParaId) -> Vec<CandidateEvent<Hash>> {
+			runtime_parachains::runtime_api_impl::vstaging::candidate_events_for_para::<Runtime, _>(
+				para_id,
+				|ev| {
+					match ev {
+						Event::ParaInclusion(ev) => {
+							Some(ev)
+						}
+						_ => None,
+					}
+				},
+			)
+		}
+
+		fn staging_on_chain_votes_in_session(
+			session: SessionIndex,
+		) -> Option<ScrapedOnChainVotes<Hash>> {
+			runtime_parachains::runtime_api_impl::vstaging::on_chain_votes_in_session::<Runtime>(
+				session,
+			)
+		}
+
+		fn staging_message_queue_sizes() -> primitives::vstaging::MessageQueueSizes {
+			runtime_parachains::runtime_api_impl::vstaging::message_queue_sizes::<Runtime>()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -1458,6 +1545,38 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl parachains_hrmp::runtime_api::HrmpApi<Block> for Runtime {
+		fn hrmp_channel_fee_factor(sender: ParaId, recipient: ParaId) -> FixedU128 {
+			Hrmp::channel_fee_factor(&HrmpChannelId { sender, recipient })
+		}
+	}
+
+	impl parachains_paras::runtime_api::ParasApi<Block> for Runtime {
+		fn upgrade_restriction_signal(para_id: ParaId) -> Option<UpgradeRestriction> {
+			Paras::upgrade_restriction_signal(para_id)
+		}
+	}
+
+	impl parachains_scheduler::runtime_api::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn upcoming_core_assignments(
+			lookahead: u32,
+		) -> Vec<(BlockNumber, parachains_scheduler::CoreAssignment)> {
+			ParaScheduler::upcoming_assignments(lookahead)
+		}
+	}
+
+	impl auctions::runtime_api::AuctionApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn current_auction_status() -> Option<auctions::runtime_api::AuctionSummary<BlockNumber>> {
+			Auctions::auction_summary(System::block_number())
+		}
+
+		fn winning_range(
+			now: BlockNumber,
+		) -> Vec<(AccountId, ParaId, Balance, runtime_common::slot_range::SlotRange)> {
+			Auctions::winners_at(now)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -1470,6 +1589,41 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::runtime_api::XcmTrapApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(sp_core::H256, pallet_xcm::TrappedAssets<AccountId, Balance, BlockNumber>)> {
+			match xcm::latest::MultiLocation::try_from(origin) {
+				Ok(origin) => XcmPallet::trapped_assets(&origin),
+				Err(()) => Vec::new(),
+			}
+		}
+	}
+
+	impl pallet_xcm::runtime_api::DryRunApi<Block, Call, Event, OriginCaller> for Runtime {
+		fn dry_run_call(
+			origin: OriginCaller,
+			call: Call,
+		) -> pallet_xcm::runtime_api::CallDryRunEffects<Event> {
+			XcmPallet::dry_run_call::<xcm_config::XcmRouter>(origin.into(), call)
+		}
+
+		fn dry_run_xcm(
+			origin_location: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<Call>,
+		) -> Result<
+			pallet_xcm::runtime_api::XcmDryRunEffects<Event>,
+			pallet_xcm::runtime_api::DryRunError,
+		> {
+			use pallet_xcm::runtime_api::DryRunError::VersionedConversionFailed;
+			let origin_location = xcm::latest::MultiLocation::try_from(origin_location)
+				.map_err(|()| VersionedConversionFailed)?;
+			let xcm = xcm::latest::Xcm::<Call>::try_from(xcm)
+				.map_err(|()| VersionedConversionFailed)?;
+			Ok(XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin_location, xcm))
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade() -> (frame_support::weights::Weight, frame_support::weights::Weight) {
@@ -1523,8 +1677,8 @@ sp_api::impl_runtime_apis! {
 			impl frame_system_benchmarking::Config for Runtime {}
 
 			use xcm::latest::{
-				AssetId::*, Fungibility::*, Junctions::*, MultiAsset, MultiAssets, MultiLocation,
-				Response,
+				AssetId::*, Fungibility::*, Junction, Junctions::*, MultiAsset, MultiAssets,
+				MultiLocation, Response,
 			};
 			use xcm_config::{Westmint, WndLocation};
 
@@ -1557,7 +1711,7 @@ sp_api::impl_runtime_apis! {
 			impl pallet_xcm_benchmarks::fungible::Config for Runtime {
 				type TransactAsset = Balances;
 
-				type CheckedAccount = xcm_config::CheckAccount;
+				type CheckedAccount = xcm_config::LocalCheckAccount;
 				type TrustedTeleporter = TrustedTeleporter;
 				type TrustedReserve = TrustedReserve;
 
@@ -1569,6 +1723,22 @@ sp_api::impl_runtime_apis! {
 				}
 			}
 
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(WndLocation::get()) },
+						Westmint::get(),
+					))
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+					Some((
+						MultiAsset { fun: Fungible(1 * UNITS), id: Concrete(WndLocation::get()) },
+						Westmint::get(),
+					))
+				}
+			}
+
 			impl pallet_xcm_benchmarks::generic::Config for Runtime {
 				type Call = Call;
 
@@ -1590,6 +1760,11 @@ sp_api::impl_runtime_apis! {
 					let ticket = MultiLocation { parents: 0, interior: Here };
 					Ok((origin, ticket, assets))
 				}
+
+				fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError> {
+					// Westend doesn't alias any universal origins.
+					Err(BenchmarkError::Skip)
+				}
 			}
 
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
@@ -1610,8 +1785,10 @@ sp_api::impl_runtime_apis! {
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da95ecffd7b6c0f78751baa9d281e0bfa3a6d6f646c70792f74727372790000000000000000000000000000000000000000").to_vec().into(),
 				// Dmp DownwardMessageQueueHeads
 				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0c4d7fefc408aac59dbfe80a72ac8e3ce5").to_vec().into(),
-				// Dmp DownwardMessageQueues
-				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0ca95dac46c07a40d91506e7637ec4ba57").to_vec().into(),
+				// Dmp DownwardMessageQueueState
+				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0c61f093ef11ca2e53cbacd774c008a141").to_vec().into(),
+				// Dmp DownwardMessageQueuePages
+				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0c34d894ec6fadffc214525dde845b9027").to_vec().into(),
 				// Configuration ActiveConfig
 				hex_literal::hex!("06de3d8a54d27e44a9d5ce189618f22db4b49d95320d9021994c850f25b8e385").to_vec().into(),
 			];
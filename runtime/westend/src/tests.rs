@@ -59,8 +59,8 @@ fn sanity_check_teleport_assets_weight() {
 	// so this test will certainly ensure that this problem does not occur.
 	use frame_support::dispatch::GetDispatchInfo;
 	let weight = pallet_xcm::Call::<Runtime>::teleport_assets {
-		dest: Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::here())),
-		beneficiary: Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::here())),
+		dest: Box::new(xcm::VersionedMultiLocation::V3(MultiLocation::here())),
+		beneficiary: Box::new(xcm::VersionedMultiLocation::V3(MultiLocation::here())),
 		assets: Box::new((Concrete(MultiLocation::here()), Fungible(200_000)).into()),
 		fee_asset_item: 0,
 	}
@@ -69,3 +69,29 @@ fn sanity_check_teleport_assets_weight() {
 
 	assert!(weight * 50 < BlockWeights::get().max_block);
 }
+
+#[test]
+fn cancel_proxy_only_rejects_announcements() {
+	let who = AccountId::new([1; 32]);
+	let reject = Call::Proxy(pallet_proxy::Call::reject_announcement {
+		delegate: who.clone().into(),
+		call_hash: Default::default(),
+	});
+	let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+	let batch = |calls| Call::Utility(pallet_utility::Call::batch { calls });
+	let batch_all = |calls| Call::Utility(pallet_utility::Call::batch_all { calls });
+
+	for call in [reject.clone(), batch(vec![reject.clone()]), batch_all(vec![reject.clone()])] {
+		assert!(ProxyType::CancelProxy.filter(&call), "{:?} is denied", call);
+	}
+	for call in [
+		remark.clone(),
+		batch(vec![reject.clone(), remark]),
+		Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+			other_signatories: vec![who],
+			call: Box::new(reject),
+		}),
+	] {
+		assert!(!ProxyType::CancelProxy.filter(&call), "{:?} is allowed", call);
+	}
+}
@@ -31,14 +31,17 @@ pub mod pallet_staking;
 pub mod pallet_timestamp;
 pub mod pallet_utility;
 pub mod pallet_vesting;
+pub mod pallet_xcm;
 pub mod runtime_common_auctions;
 pub mod runtime_common_crowdloan;
 pub mod runtime_common_paras_registrar;
 pub mod runtime_common_slots;
 pub mod runtime_parachains_configuration;
 pub mod runtime_parachains_disputes;
+pub mod runtime_parachains_dmp;
 pub mod runtime_parachains_hrmp;
 pub mod runtime_parachains_initializer;
+pub mod runtime_parachains_on_demand;
 pub mod runtime_parachains_paras;
 pub mod runtime_parachains_paras_inherent;
 pub mod runtime_parachains_ump;
@@ -149,4 +149,18 @@ impl<T: frame_system::Config> runtime_parachains::hrmp::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Paras ParaLifecycles (r:2 w:0)
+	// Storage: Hrmp HrmpChannels (r:1 w:1)
+	// Storage: Hrmp HrmpOpenChannelRequests (r:1 w:0)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: Hrmp HrmpEgressChannelsIndex (r:1 w:1)
+	// Storage: Hrmp HrmpOpenChannelRequestCount (r:1 w:0)
+	// Storage: Hrmp HrmpIngressChannelsIndex (r:1 w:1)
+	// Storage: Hrmp HrmpAcceptedChannelRequestCount (r:1 w:0)
+	fn establish_system_channel() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 }
@@ -97,6 +97,14 @@ impl<T: frame_system::Config> runtime_parachains::paras::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// NOTE: Hand-estimated, not benchmarked. Regenerate this weight on reference hardware.
+	// Storage: Paras ParaLifecycles (r:1 w:0)
+	// Storage: Paras UpgradeCooldownOverrides (r:0 w:1)
+	fn force_set_upgrade_cooldown() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: Paras PvfActiveVoteMap (r:1 w:0)
 	// Storage: Paras CodeByHash (r:1 w:1)
 	fn add_trusted_validation_code(c: u32, ) -> Weight {
@@ -14,13 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Autogenerated weights for `pallet_xcm_benchmarks::fungible`
+//! Weights for `pallet_xcm_benchmarks::fungible`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2022-03-08, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("westend-dev"), DB CACHE: 1024
+//! THESE WEIGHTS ARE HAND-ESTIMATED PLACEHOLDERS. They were not generated by the benchmark CLI.
+//! They count the storage accesses noted on each function and estimate the execution time from
+//! comparable extrinsics. Replace them with benchmarked weights by running the command below on
+//! reference hardware.
 
-// Executed Command:
+// Command to generate the weights:
 // target/production/polkadot
 // benchmark
 // --chain=westend-dev
@@ -47,13 +48,13 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo<T> {
 	// Storage: System Account (r:1 w:1)
 	pub(crate) fn withdraw_asset() -> Weight {
-		(20_308_000 as Weight)
+		(21_353_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: System Account (r:2 w:2)
 	pub(crate) fn transfer_asset() -> Weight {
-		(32_193_000 as Weight)
+		(33_013_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
@@ -61,26 +62,29 @@ impl<T: frame_system::Config> WeightInfo<T> {
 	// Storage: XcmPallet SupportedVersion (r:1 w:0)
 	// Storage: XcmPallet VersionDiscoveryQueue (r:1 w:1)
 	// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
 	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
-	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueState (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueuePages (r:1 w:1)
 	pub(crate) fn transfer_reserve_asset() -> Weight {
-		(50_731_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+		(54_556_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: Benchmark Override (r:0 w:0)
 	pub(crate) fn reserve_asset_deposited() -> Weight {
 		(2_000_000_000_000 as Weight)
+
 	}
 	// Storage: System Account (r:1 w:1)
 	pub(crate) fn receive_teleported_asset() -> Weight {
-		(19_622_000 as Weight)
+		(19_853_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: System Account (r:1 w:1)
 	pub(crate) fn deposit_asset() -> Weight {
-		(22_433_000 as Weight)
+		(22_415_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
@@ -88,22 +92,26 @@ impl<T: frame_system::Config> WeightInfo<T> {
 	// Storage: XcmPallet SupportedVersion (r:1 w:0)
 	// Storage: XcmPallet VersionDiscoveryQueue (r:1 w:1)
 	// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
 	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
-	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueState (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueuePages (r:1 w:1)
 	pub(crate) fn deposit_reserve_asset() -> Weight {
-		(41_765_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(46_593_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 	// Storage: System Account (r:1 w:1)
 	// Storage: XcmPallet SupportedVersion (r:1 w:0)
 	// Storage: XcmPallet VersionDiscoveryQueue (r:1 w:1)
 	// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
 	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
-	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueState (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueuePages (r:1 w:1)
 	pub(crate) fn initiate_teleport() -> Weight {
-		(41_204_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(47_557_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 }
@@ -181,6 +181,22 @@ enum ValidityVote<Signature: Eq + Clone> {
 	Valid(Signature),
 }
 
+impl<Signature: Eq + Clone> ValidityVote<Signature> {
+	fn into_attestation(self) -> ValidityAttestation<Signature> {
+		match self {
+			Self::Issued(s) => ValidityAttestation::Implicit(s),
+			Self::Valid(s) => ValidityAttestation::Explicit(s),
+		}
+	}
+
+	fn from_attestation(attestation: ValidityAttestation<Signature>) -> Self {
+		match attestation {
+			ValidityAttestation::Implicit(s) => Self::Issued(s),
+			ValidityAttestation::Explicit(s) => Self::Valid(s),
+		}
+	}
+}
+
 /// A summary of import of a statement.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Summary<Digest, Group> {
@@ -223,6 +239,33 @@ pub struct AttestedCandidate<Group, Candidate, AuthorityId, Signature> {
 	pub validity_votes: Vec<(AuthorityId, ValidityAttestation<Signature>)>,
 }
 
+/// A proposed candidate, along with the validity votes on it, as kept in a [`TableSnapshot`].
+#[derive(Clone, PartialEq, Decode, Encode)]
+pub struct CandidateSnapshot<Candidate, AuthorityId, Signature> {
+	/// The candidate data.
+	pub candidate: Candidate,
+	/// Validity votes on the candidate, including the implicit vote of each authority which
+	/// seconded it.
+	pub validity_votes: Vec<(AuthorityId, ValidityAttestation<Signature>)>,
+}
+
+/// The state of a [`Table`], from which it can be restored, e.g. after a restart.
+#[derive(Clone, PartialEq, Decode, Encode)]
+pub struct TableSnapshot<Candidate, Digest, AuthorityId, Signature> {
+	/// The digest of the candidate proposed by each authority, along with its signature.
+	pub proposals: Vec<(AuthorityId, Digest, Signature)>,
+	/// The proposed candidates.
+	pub candidates: Vec<CandidateSnapshot<Candidate, AuthorityId, Signature>>,
+}
+
+/// Type alias for a table snapshot corresponding to context type.
+pub type TableSnapshotFor<Ctx> = TableSnapshot<
+	<Ctx as Context>::Candidate,
+	<Ctx as Context>::Digest,
+	<Ctx as Context>::AuthorityId,
+	<Ctx as Context>::Signature,
+>;
+
 /// Stores votes and data about a candidate.
 pub struct CandidateData<Ctx: Context> {
 	group_id: Ctx::GroupId,
@@ -366,6 +409,71 @@ impl<Ctx: Context> Table<Ctx> {
 		self.detected_misbehavior.drain().into()
 	}
 
+	/// Take a snapshot of the proposed candidates and of the validity votes on them.
+	///
+	/// Detected misbehavior is not part of the snapshot.
+	pub fn snapshot(&self) -> TableSnapshotFor<Ctx> {
+		let proposals = self
+			.authority_data
+			.iter()
+			.filter_map(|(authority, data)| {
+				data.proposal
+					.as_ref()
+					.map(|(digest, sig)| (authority.clone(), digest.clone(), sig.clone()))
+			})
+			.collect();
+
+		let candidates = self
+			.candidate_votes
+			.values()
+			.map(|data| CandidateSnapshot {
+				candidate: data.candidate.clone(),
+				validity_votes: data
+					.validity_votes
+					.iter()
+					.map(|(a, v)| (a.clone(), v.clone().into_attestation()))
+					.collect(),
+			})
+			.collect();
+
+		TableSnapshot { proposals, candidates }
+	}
+
+	/// Restore a table from a snapshot taken with [`Table::snapshot`].
+	///
+	/// Statements imported into the restored table are judged against the restored proposals
+	/// and votes, just as they would have been by the original table. Proposals of candidates
+	/// missing from the snapshot are dropped.
+	pub fn restore(snapshot: TableSnapshotFor<Ctx>) -> Self {
+		let TableSnapshot { proposals, candidates } = snapshot;
+
+		let mut candidate_votes = HashMap::new();
+		for CandidateSnapshot { candidate, validity_votes } in candidates {
+			candidate_votes.entry(Ctx::candidate_digest(&candidate)).or_insert_with(|| {
+				CandidateData {
+					group_id: Ctx::candidate_group(&candidate),
+					candidate,
+					validity_votes: validity_votes
+						.into_iter()
+						.map(|(a, v)| (a, ValidityVote::from_attestation(v)))
+						.collect(),
+				}
+			});
+		}
+
+		// The existence proof of `import_candidate` requires every proposal to have its
+		// candidate votes entry.
+		let authority_data = proposals
+			.into_iter()
+			.filter(|(_, digest, _)| candidate_votes.contains_key(digest))
+			.map(|(authority, digest, sig)| {
+				(authority, AuthorityData { proposal: Some((digest, sig)) })
+			})
+			.collect();
+
+		Table { authority_data, detected_misbehavior: HashMap::new(), candidate_votes }
+	}
+
 	fn import_candidate(
 		&mut self,
 		context: &Ctx,
@@ -936,4 +1044,99 @@ mod tests {
 		assert_eq!(summary.group_id, GroupId(2));
 		assert_eq!(summary.validity_votes, 2);
 	}
+
+	#[test]
+	fn table_can_be_restored_from_snapshot() {
+		let context = TestContext {
+			authorities: {
+				let mut map = HashMap::new();
+				map.insert(AuthorityId(1), GroupId(2));
+				map.insert(AuthorityId(2), GroupId(2));
+				map.insert(AuthorityId(3), GroupId(2));
+				map
+			},
+		};
+
+		let mut table = create();
+		let candidate = SignedStatement {
+			statement: Statement::Seconded(Candidate(2, 100)),
+			signature: Signature(1),
+			sender: AuthorityId(1),
+		};
+		let vote = SignedStatement {
+			statement: Statement::Valid(Digest(100)),
+			signature: Signature(2),
+			sender: AuthorityId(2),
+		};
+		table.import_statement(&context, candidate);
+		table.import_statement(&context, vote.clone());
+
+		let mut restored = Table::<TestContext>::restore(table.snapshot());
+
+		assert_eq!(restored.get_candidate(&Digest(100)), Some(&Candidate(2, 100)));
+		assert_eq!(
+			restored.attested_candidate(&Digest(100), &context).map(|a| a.validity_votes.len()),
+			Some(2),
+		);
+
+		// Votes from before the restart are not counted twice.
+		assert!(restored.import_statement(&context, vote).is_none());
+
+		// Proposals from before the restart are still binding.
+		restored.import_statement(
+			&context,
+			SignedStatement {
+				statement: Statement::Seconded(Candidate(2, 999)),
+				signature: Signature(1),
+				sender: AuthorityId(1),
+			},
+		);
+		assert_eq!(
+			restored.detected_misbehavior[&AuthorityId(1)][0],
+			Misbehavior::MultipleCandidates(MultipleCandidates {
+				first: (Candidate(2, 100), Signature(1)),
+				second: (Candidate(2, 999), Signature(1)),
+			})
+		);
+
+		let summary = restored
+			.import_statement(
+				&context,
+				SignedStatement {
+					statement: Statement::Valid(Digest(100)),
+					signature: Signature(3),
+					sender: AuthorityId(3),
+				},
+			)
+			.expect("new vote to give summary");
+		assert_eq!(summary.validity_votes, 3);
+	}
+
+	#[test]
+	fn restoring_drops_proposals_of_unknown_candidates() {
+		let context = TestContext {
+			authorities: {
+				let mut map = HashMap::new();
+				map.insert(AuthorityId(1), GroupId(2));
+				map
+			},
+		};
+
+		let snapshot = TableSnapshot {
+			proposals: vec![(AuthorityId(1), Digest(100), Signature(1))],
+			candidates: vec![],
+		};
+		let mut table = Table::<TestContext>::restore(snapshot);
+
+		table.import_statement(
+			&context,
+			SignedStatement {
+				statement: Statement::Seconded(Candidate(2, 999)),
+				signature: Signature(1),
+				sender: AuthorityId(1),
+			},
+		);
+		assert!(!table.detected_misbehavior.contains_key(&AuthorityId(1)));
+		assert_eq!(table.get_candidate(&Digest(999)), Some(&Candidate(2, 999)));
+	}
 }
@@ -48,6 +48,14 @@ pub mod v2 {
 	/// A summary of import of a statement.
 	pub type Summary = generic::Summary<CandidateHash, Id>;
 
+	/// A snapshot of the state of a statement table.
+	pub type TableSnapshot = generic::TableSnapshot<
+		CommittedCandidateReceipt,
+		CandidateHash,
+		ValidatorIndex,
+		ValidatorSignature,
+	>;
+
 	impl<'a> From<&'a Statement> for PrimitiveStatement {
 		fn from(s: &'a Statement) -> PrimitiveStatement {
 			match *s {
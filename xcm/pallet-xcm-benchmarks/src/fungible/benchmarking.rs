@@ -131,7 +131,7 @@ benchmarks_instance_pallet! {
 		let (trusted_teleporter, teleportable_asset) = T::TrustedTeleporter::get()
 			.ok_or(BenchmarkError::Skip)?;
 
-		if let Some(checked_account) = T::CheckedAccount::get() {
+		if let Some((checked_account, _)) = T::CheckedAccount::get() {
 			T::TransactAsset::mint_into(
 				&checked_account,
 				<
@@ -219,8 +219,18 @@ benchmarks_instance_pallet! {
 		// Add our asset to the holding.
 		holding.push(asset.clone());
 
-		// Checked account starts at zero
-		assert!(T::CheckedAccount::get().map_or(true, |c| T::TransactAsset::balance(&c).is_zero()));
+		if let Some((checked_account, _)) = T::CheckedAccount::get() {
+			// Fund the checked account so that the teleport may go out whether the asset is
+			// minted here or must have been teleported in before.
+			T::TransactAsset::mint_into(
+				&checked_account,
+				<
+					T::TransactAsset
+					as
+					Inspect<T::AccountId>
+				>::Balance::max_value() / 2u32.into(),
+			)?;
+		}
 
 		let mut executor = new_executor::<T>(Default::default());
 		executor.holding = holding.into();
@@ -233,9 +243,16 @@ benchmarks_instance_pallet! {
 	}: {
 		executor.execute(xcm)?;
 	} verify {
-		if let Some(checked_account) = T::CheckedAccount::get() {
-			// teleport checked account should have received some asset.
-			assert!(!T::TransactAsset::balance(&checked_account).is_zero());
+		if let Some((checked_account, _)) = T::CheckedAccount::get() {
+			// teleport checked account should have been credited or debited.
+			assert_ne!(
+				T::TransactAsset::balance(&checked_account),
+				<
+					T::TransactAsset
+					as
+					Inspect<T::AccountId>
+				>::Balance::max_value() / 2u32.into(),
+			);
 		}
 	}
 
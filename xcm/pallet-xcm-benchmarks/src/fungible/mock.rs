@@ -18,7 +18,10 @@
 
 use crate::{fungible as xcm_balances_benchmark, mock::*};
 use frame_benchmarking::BenchmarkError;
-use frame_support::{parameter_types, traits::Everything};
+use frame_support::{
+	parameter_types,
+	traits::{Everything, Nothing},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -26,7 +29,7 @@ use sp_runtime::{
 	BuildStorage,
 };
 use xcm::latest::prelude::*;
-use xcm_builder::AllowUnpaidExecutionFrom;
+use xcm_builder::{AllowUnpaidExecutionFrom, MintLocation};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -134,7 +137,9 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = ();
 	type IsReserve = TrustedReserves;
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = xcm_builder::LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = ();
 	type Barrier = AllowUnpaidExecutionFrom<Everything>;
 	type Weigher = xcm_builder::FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type Trader = xcm_builder::FixedRateOfFungible<WeightPrice, ()>;
@@ -142,6 +147,9 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = ();
 	type AssetClaims = ();
 	type SubscriptionService = ();
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 impl crate::Config for Test {
@@ -162,7 +170,7 @@ pub type TrustedTeleporters = (xcm_builder::Case<TeleConcreteFung>,);
 pub type TrustedReserves = (xcm_builder::Case<RsrvConcreteFung>,);
 
 parameter_types! {
-	pub const CheckedAccount: Option<u64> = Some(100);
+	pub const CheckedAccount: Option<(u64, MintLocation)> = Some((100, MintLocation::Local));
 	pub const ChildTeleporter: MultiLocation = Parachain(1000).into();
 	pub const TrustedTeleporter: Option<(MultiLocation, MultiAsset)> = Some((
 		ChildTeleporter::get(),
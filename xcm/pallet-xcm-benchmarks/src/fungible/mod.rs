@@ -32,8 +32,9 @@ pub mod pallet {
 		/// This is useful for testing and checking.
 		type TransactAsset: frame_support::traits::fungible::Mutate<Self::AccountId>;
 
-		/// The account used to check assets being teleported.
-		type CheckedAccount: Get<Option<Self::AccountId>>;
+		/// The account used to check assets being teleported, along with where those assets are
+		/// minted.
+		type CheckedAccount: Get<Option<(Self::AccountId, xcm_builder::MintLocation)>>;
 
 		/// A trusted location which we allow teleports from, and the asset we allow to teleport.
 		type TrustedTeleporter: Get<Option<(xcm::latest::MultiLocation, xcm::latest::MultiAsset)>>;
@@ -18,12 +18,13 @@ use super::*;
 use crate::{new_executor, XcmCallOf};
 use codec::Encode;
 use frame_benchmarking::{benchmarks, BenchmarkError};
-use frame_support::dispatch::GetDispatchInfo;
+use frame_support::{dispatch::GetDispatchInfo, traits::PalletInfoAccess, weights::WeightMeter};
 use sp_std::vec;
 use xcm::{
 	latest::{prelude::*, MultiAssets},
 	DoubleEncoded,
 };
+use xcm_executor::ExecutorError;
 
 benchmarks! {
 	query_holding {
@@ -228,6 +229,8 @@ benchmarks! {
 		<T::XcmConfig as xcm_executor::Config>::AssetTrap::drop_assets(
 			&origin,
 			assets.clone().into(),
+			&XcmContext { origin: Some(origin.clone()), message_id: [0; 32], topic: None },
+			&mut WeightMeter::max_limit(),
 		);
 
 		// Assets should be in the trap now.
@@ -294,6 +297,140 @@ benchmarks! {
 		assert!(!<T::XcmConfig as xcm_executor::Config>::SubscriptionService::is_subscribed(&origin));
 	}
 
+	unpaid_execution {
+		let origin = MultiLocation::parent();
+		let mut executor = new_executor::<T>(origin.clone());
+		let instruction = Instruction::<XcmCallOf<T>>::UnpaidExecution {
+			weight_limit: WeightLimit::Unlimited,
+			check_origin: Some(origin),
+		};
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	}
+
+	burn_asset {
+		let holding = T::worst_case_holding();
+		let assets = holding.clone();
+		let mut executor = new_executor::<T>(Default::default());
+		executor.holding = holding.into();
+		let instruction = Instruction::<XcmCallOf<T>>::BurnAsset(assets);
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		assert!(executor.holding.is_empty());
+	}
+
+	expect_origin {
+		let mut executor = new_executor::<T>(Default::default());
+		let instruction = Instruction::<XcmCallOf<T>>::ExpectOrigin(Some(MultiLocation::parent()));
+		let xcm = Xcm(vec![instruction]);
+		let mut _result = Ok(());
+	} : {
+		_result = executor.execute(xcm);
+	} verify {
+		assert!(matches!(
+			_result,
+			Err(ExecutorError { xcm_error: XcmError::ExpectationFalse, .. })
+		));
+	}
+
+	expect_error {
+		let mut executor = new_executor::<T>(Default::default());
+		executor.error = Some((3u32, XcmError::Overflow));
+		let instruction = Instruction::<XcmCallOf<T>>::ExpectError(None);
+		let xcm = Xcm(vec![instruction]);
+		let mut _result = Ok(());
+	} : {
+		_result = executor.execute(xcm);
+	} verify {
+		assert!(matches!(
+			_result,
+			Err(ExecutorError { xcm_error: XcmError::ExpectationFalse, .. })
+		));
+	}
+
+	query_pallet {
+		let query_id = Default::default();
+		let destination = T::valid_destination().map_err(|_| BenchmarkError::Skip)?;
+		let max_weight = Default::default();
+		let mut executor = new_executor::<T>(Default::default());
+		let instruction = Instruction::<XcmCallOf<T>>::QueryPallet {
+			module_name: b"frame_system".to_vec(),
+			response_info: QueryResponseInfo { destination, query_id, max_weight },
+		};
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	}
+
+	expect_pallet {
+		type System<T> = frame_system::Pallet<T>;
+		let crate_version = <System<T> as PalletInfoAccess>::crate_version();
+		let mut executor = new_executor::<T>(Default::default());
+		let instruction = Instruction::<XcmCallOf<T>>::ExpectPallet {
+			index: <System<T> as PalletInfoAccess>::index() as u32,
+			name: <System<T> as PalletInfoAccess>::name().as_bytes().to_vec(),
+			module_name: <System<T> as PalletInfoAccess>::module_name().as_bytes().to_vec(),
+			crate_major: crate_version.major as u32,
+			min_crate_minor: crate_version.minor as u32,
+		};
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	}
+
+	report_transact_status {
+		let query_id = Default::default();
+		let destination = T::valid_destination().map_err(|_| BenchmarkError::Skip)?;
+		let max_weight = Default::default();
+		let mut executor = new_executor::<T>(Default::default());
+		executor.transact_status = MaybeErrorCode::Error(b"MyError".to_vec());
+		let instruction = Instruction::<XcmCallOf<T>>::ReportTransactStatus(QueryResponseInfo {
+			query_id,
+			destination,
+			max_weight,
+		});
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	}
+
+	clear_transact_status {
+		let mut executor = new_executor::<T>(Default::default());
+		executor.transact_status = MaybeErrorCode::Error(b"MyError".to_vec());
+		let instruction = Instruction::<XcmCallOf<T>>::ClearTransactStatus;
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		assert_eq!(executor.transact_status, MaybeErrorCode::Success);
+	}
+
+	set_topic {
+		let mut executor = new_executor::<T>(Default::default());
+		let instruction = Instruction::<XcmCallOf<T>>::SetTopic([1; 32]);
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		assert_eq!(executor.topic, Some([1; 32]));
+	}
+
+	universal_origin {
+		let (origin, alias) = T::universal_alias()?;
+		let mut executor = new_executor::<T>(origin);
+		let instruction = Instruction::<XcmCallOf<T>>::UniversalOrigin(alias.clone());
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		use frame_support::traits::Get;
+		let universal_location = <T::XcmConfig as xcm_executor::Config>::UniversalLocation::get();
+		assert_eq!(executor.origin, Some(X1(alias).relative_to(&universal_location)));
+	}
+
 	initiate_reserve_withdraw {
 		let holding = T::worst_case_holding();
 		let assets_filter = MultiAssetFilter::Definite(holding.clone());
@@ -105,7 +105,9 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = AlwaysSignedByDefault<Origin>;
 	type IsReserve = AllAssetLocationsPass;
 	type IsTeleporter = ();
-	type LocationInverter = xcm_builder::LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = ();
 	type Barrier = AllowUnpaidExecutionFrom<Everything>;
 	type Weigher = xcm_builder::FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type Trader = xcm_builder::FixedRateOfFungible<WeightPrice, ()>;
@@ -113,6 +115,9 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = TestAssetTrap;
 	type AssetClaims = TestAssetTrap;
 	type SubscriptionService = TestSubscriptionService;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Everything;
 }
 
 impl crate::Config for Test {
@@ -150,6 +155,10 @@ impl generic::Config for Test {
 		let ticket = MultiLocation { parents: 0, interior: X1(GeneralIndex(0)) };
 		Ok((Default::default(), ticket, assets))
 	}
+
+	fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError> {
+		Ok((Here.into(), GlobalConsensus(Kusama)))
+	}
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
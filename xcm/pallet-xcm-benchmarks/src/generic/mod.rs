@@ -8,7 +8,7 @@ mod mock;
 pub mod pallet {
 	use frame_benchmarking::BenchmarkError;
 	use frame_support::{dispatch::Dispatchable, pallet_prelude::Encode, weights::GetDispatchInfo};
-	use xcm::latest::{MultiAssets, MultiLocation, Response};
+	use xcm::latest::{Junction, MultiAssets, MultiLocation, Response};
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config + crate::Config {
@@ -32,6 +32,12 @@ pub mod pallet {
 
 		/// Return an origin, ticket, and assets that can be trapped and claimed.
 		fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError>;
+
+		/// An origin and the child of the Universal Ancestor which it may alias through
+		/// `UniversalOrigin`.
+		///
+		/// If set to `Err`, benchmarks which rely on a universal alias will be skipped.
+		fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError>;
 	}
 
 	#[pallet::pallet]
@@ -21,8 +21,12 @@ use xcm_executor::traits::FilterAssetLocation;
 // An xcm sender/receiver akin to > /dev/null
 pub struct DevNull;
 impl xcm::opaque::latest::SendXcm for DevNull {
-	fn send_xcm(_: impl Into<MultiLocation>, _: Xcm<()>) -> SendResult {
-		Ok(())
+	type Ticket = ();
+	fn validate(_: &mut Option<MultiLocation>, _: &mut Option<Xcm<()>>) -> SendResult<()> {
+		Ok(((), MultiAssets::new()))
+	}
+	fn deliver(_: ()) -> Result<XcmHash, SendError> {
+		Ok([0; 32])
 	}
 }
 
@@ -51,7 +55,8 @@ impl xcm_executor::traits::Convert<MultiLocation, u64> for AccountIdConverter {
 }
 
 parameter_types! {
-	pub Ancestry: MultiLocation = Junction::Parachain(101).into();
+	pub UniversalLocation: InteriorMultiLocation =
+		Junctions::X2(Junction::GlobalConsensus(NetworkId::Any), Junction::Parachain(101));
 	pub UnitWeightCost: Weight = 10;
 	pub WeightPrice: (AssetId, u128) = (Concrete(Here.into()), 1_000_000);
 }
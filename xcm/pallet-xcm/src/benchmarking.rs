@@ -0,0 +1,116 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM pallet's asset transfer extrinsics.
+
+use super::*;
+use frame_benchmarking::{benchmarks, BenchmarkError, BenchmarkResult};
+use frame_support::weights::Weight;
+use sp_runtime::traits::Bounded;
+
+/// Runtime-specific parameters required to benchmark the XCM pallet.
+///
+/// The assets given must be of the pallet's `Currency`, which is used to fund the origin so that
+/// the transfer succeeds.
+pub trait Config: crate::Config {
+	/// An asset which the `ExecuteXcmOrigin`'s successful origin may teleport, along with the
+	/// destination to which it may be teleported. `None` if teleports are not supported.
+	fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)>;
+
+	/// An asset which the `ExecuteXcmOrigin`'s successful origin may reserve-transfer, along with
+	/// the destination to which it may be transferred. `None` if reserve transfers are not
+	/// supported.
+	fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)>;
+}
+
+fn unbenchmarkable() -> BenchmarkError {
+	BenchmarkError::Override(BenchmarkResult::from_weight(Weight::max_value()))
+}
+
+/// Fund the account of `location` generously, returning it along with its balance.
+fn fund<T: Config>(
+	location: &MultiLocation,
+) -> Result<(T::AccountId, BalanceOf<T>), BenchmarkError> {
+	let who = T::SovereignAccountOf::convert_ref(location).map_err(|()| unbenchmarkable())?;
+	let balance = BalanceOf::<T>::max_value() / 2u32.into();
+	T::Currency::make_free_balance_be(&who, balance);
+	Ok((who, balance))
+}
+
+/// Ensure that the transfer from `who` was successful: its assets were withdrawn and none of them
+/// were trapped for want of a successful execution.
+fn assert_transferred<T: Config>(who: &T::AccountId, balance: BalanceOf<T>) {
+	assert!(T::Currency::free_balance(who) < balance);
+	assert!(AssetTraps::<T>::iter().next().is_none());
+}
+
+benchmarks! {
+	limited_teleport_assets {
+		let (asset, dest) = T::teleportable_asset_and_dest().ok_or(BenchmarkError::Skip)?;
+		let send_origin = T::ExecuteXcmOrigin::successful_origin();
+		let origin_location = T::ExecuteXcmOrigin::try_origin(send_origin.clone())
+			.map_err(|_| unbenchmarkable())?;
+		if !T::XcmTeleportFilter::contains(&(origin_location.clone(), vec![asset.clone()])) {
+			return Err(unbenchmarkable())
+		}
+		let (who, balance) = fund::<T>(&origin_location)?;
+		let beneficiary: MultiLocation = AccountId32 { network: Any, id: [0u8; 32] }.into();
+		let assets: MultiAssets = asset.into();
+	}: {
+		Pallet::<T>::limited_teleport_assets(
+			send_origin,
+			Box::new(dest.into()),
+			Box::new(beneficiary.into()),
+			Box::new(assets.into()),
+			0,
+			Unlimited,
+		)?;
+	}
+	verify {
+		assert_transferred::<T>(&who, balance);
+	}
+
+	limited_reserve_transfer_assets {
+		let (asset, dest) = T::reserve_transferable_asset_and_dest().ok_or(BenchmarkError::Skip)?;
+		let send_origin = T::ExecuteXcmOrigin::successful_origin();
+		let origin_location = T::ExecuteXcmOrigin::try_origin(send_origin.clone())
+			.map_err(|_| unbenchmarkable())?;
+		if !T::XcmReserveTransferFilter::contains(&(origin_location.clone(), vec![asset.clone()])) {
+			return Err(unbenchmarkable())
+		}
+		let (who, balance) = fund::<T>(&origin_location)?;
+		let beneficiary: MultiLocation = AccountId32 { network: Any, id: [0u8; 32] }.into();
+		let assets: MultiAssets = asset.into();
+	}: {
+		Pallet::<T>::limited_reserve_transfer_assets(
+			send_origin,
+			Box::new(dest.into()),
+			Box::new(beneficiary.into()),
+			Box::new(assets.into()),
+			0,
+			Unlimited,
+		)?;
+	}
+	verify {
+		assert_transferred::<T>(&who, balance);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext_with_balances(Vec::new()),
+		crate::mock::Test
+	);
+}
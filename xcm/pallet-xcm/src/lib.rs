@@ -18,16 +18,22 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 #[cfg(test)]
 mod mock;
+pub mod runtime_api;
 #[cfg(test)]
 mod tests;
 
 use codec::{Decode, Encode, EncodeLike};
-use frame_support::traits::{Contains, EnsureOrigin, Get, OriginTrait};
+use frame_support::{
+	traits::{Contains, Currency, EnsureOrigin, Get, OriginTrait, ReservableCurrency},
+	weights::Weight,
+};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{BadOrigin, Saturating},
+	traits::{BadOrigin, One, SaturatedConversion, Saturating},
 	RuntimeDebug,
 };
 use sp_std::{
@@ -39,26 +45,47 @@ use sp_std::{
 	vec,
 };
 use xcm::prelude::*;
-use xcm_executor::traits::ConvertOrigin;
+use xcm_executor::traits::{Convert, ConvertOrigin};
 
 use frame_support::PalletId;
 pub use pallet::*;
 
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn limited_teleport_assets() -> Weight;
+	fn limited_reserve_transfer_assets() -> Weight;
+}
+
+/// The weights used before the extrinsics of this pallet were benchmarked.
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn limited_teleport_assets() -> Weight {
+		100_000_000
+	}
+	fn limited_reserve_transfer_assets() -> Weight {
+		100_000_000
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use crate::runtime_api::{CallDryRunEffects, XcmDryRunEffects, XcmPaymentApiError};
 	use frame_support::{
 		dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
 		pallet_prelude::*,
-		parameter_types,
+		parameter_types, transactional,
+		weights::WeightMeter,
 	};
 	use frame_system::{pallet_prelude::*, Config as SysConfig};
 	use sp_core::H256;
 	use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, BlockNumberProvider, Hash};
 	use xcm_executor::{
 		traits::{
-			ClaimAssets, DropAssets, InvertLocation, OnResponse, VersionChangeNotifier,
-			WeightBounds,
+			ticket_version, version_ticket, CheckSuspension, ClaimAssets, DropAssets, OnResponse,
+			QueryHandler, QueryResponseStatus, VersionChangeNotifier, WeightBounds,
 		},
 		Assets,
 	};
@@ -107,8 +134,8 @@ pub mod pallet {
 		/// Means of measuring the weight consumed by an XCM message locally.
 		type Weigher: WeightBounds<<Self as SysConfig>::Call>;
 
-		/// Means of inverting a location.
-		type LocationInverter: InvertLocation;
+		/// This chain's universal location, as with the XCM executor's `UniversalLocation`.
+		type UniversalLocation: Get<InteriorMultiLocation>;
 
 		/// The outer `Origin` type.
 		type Origin: From<Origin> + From<<Self as SysConfig>::Origin>;
@@ -124,6 +151,36 @@ pub mod pallet {
 		/// The latest supported version that we advertise. Generally just set it to
 		/// `pallet_xcm::CurrentXcmVersion`.
 		type AdvertisedXcmVersion: Get<XcmVersion>;
+
+		/// The number of blocks for which trapped assets may only be claimed by their origin. After
+		/// this period, anyone may sweep them to `TreasuryLocation`.
+		type TrappedAssetsExpiry: Get<Self::BlockNumber>;
+
+		/// The location to which expired trapped assets are swept, or `None` if they may never be
+		/// swept.
+		type TreasuryLocation: Get<Option<MultiLocation>>;
+
+		/// The currency in which the deposits for sweepable asset traps are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for each trap which may be swept to `TreasuryLocation`. Assets
+		/// trapped by an origin which cannot pay it may only ever be claimed by that origin.
+		type TrapDeposit: Get<BalanceOf<Self>>;
+
+		/// How to convert the origin of an execution which trapped some assets into the account
+		/// from which the trap's deposit is reserved.
+		type SovereignAccountOf: Convert<MultiLocation, Self::AccountId>;
+
+		/// Our filter of the messages which may be sent with `send`, given their origin and
+		/// destination.
+		type XcmSendFilter: Contains<(MultiLocation, MultiLocation)>;
+
+		/// The rate at which any single origin other than `Here` may send messages with `send`, or
+		/// `None` if it is unlimited.
+		type SendRateLimit: Get<Option<SendRateLimit>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	/// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
@@ -216,6 +273,30 @@ pub mod pallet {
 		///
 		/// \[ location, query ID \]
 		NotifyTargetMigrationFail(VersionedMultiLocation, QueryId),
+		/// Execution of incoming XCM messages has been suspended, either for all origins (`None`)
+		/// or only for the given location.
+		///
+		/// \[ location \]
+		ExecutionSuspended(Option<MultiLocation>),
+		/// Execution of incoming XCM messages has been resumed, either for all origins (`None`) or
+		/// only for the given location.
+		///
+		/// \[ location \]
+		ExecutionResumed(Option<MultiLocation>),
+		/// Some assets have been claimed from an asset trap.
+		///
+		/// \[ hash, origin, assets \]
+		AssetsClaimed(H256, MultiLocation, VersionedMultiAssets),
+		/// Some expired trapped assets are being swept to the treasury.
+		///
+		/// \[ hash, origin, assets \]
+		TrappedAssetsSwept(H256, MultiLocation, VersionedMultiAssets),
+		/// A query did not receive a response before its timeout. If it had no notification
+		/// registered, it remains available as timed out until taken with `take_timeout`;
+		/// otherwise it has been removed.
+		///
+		/// \[ id \]
+		QueryTimedOut(QueryId),
 	}
 
 	#[pallet::origin]
@@ -263,6 +344,65 @@ pub mod pallet {
 		NoSubscription,
 		/// The location is invalid since it already has a subscription from us.
 		AlreadySubscribed,
+		/// There are no trapped assets under the given hash.
+		UnknownTrap,
+		/// The trapped assets have not yet expired.
+		TrapNotExpired,
+		/// Trapped assets may not be swept since there is no treasury location.
+		NoTreasury,
+		/// The trapped assets could not be claimed into the treasury.
+		SweepFailed,
+		/// The fees for delivering the message could not be paid.
+		FeesNotMet,
+		/// The origin has exceeded its allowance for sending messages.
+		Throttled,
+	}
+
+	/// The details of assets trapped under a particular hash.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct TrappedAssets<AccountId, Balance, BlockNumber> {
+		/// The location from whose execution the assets were trapped.
+		pub origin: VersionedMultiLocation,
+		/// The trapped assets, in the version in which they were trapped.
+		pub assets: VersionedMultiAssets,
+		/// The traps of these assets which may be swept to the treasury, in the order in which
+		/// they were made.
+		pub entries: Vec<TrapEntry<AccountId, Balance, BlockNumber>>,
+	}
+
+	/// A single trap of some assets which may be swept to the treasury once expired.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct TrapEntry<AccountId, Balance, BlockNumber> {
+		/// The block after which the assets may be swept to the treasury by anyone.
+		pub expiry: BlockNumber,
+		/// The account from which `deposit` is reserved.
+		pub depositor: AccountId,
+		/// The deposit reserved for the trap, returned once it is claimed or swept.
+		pub deposit: Balance,
+	}
+
+	/// A token bucket limit on the messages which a single origin may send.
+	#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct SendRateLimit {
+		/// The maximum number of messages which may be sent in a burst.
+		pub max_messages: u32,
+		/// The maximum number of encoded message bytes which may be sent in a burst.
+		pub max_bytes: u32,
+		/// The number of messages by which an origin's allowance is replenished each block.
+		pub messages_per_block: u32,
+		/// The number of bytes by which an origin's allowance is replenished each block.
+		pub bytes_per_block: u32,
+	}
+
+	/// The remaining allowance of an origin under `SendRateLimit`.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct SendAllowance<BlockNumber> {
+		/// The number of messages which may still be sent.
+		pub messages: u32,
+		/// The number of encoded message bytes which may still be sent.
+		pub bytes: u32,
+		/// The block at which the allowance was last updated.
+		pub updated: BlockNumber,
 	}
 
 	/// The status of a query.
@@ -278,6 +418,8 @@ pub mod pallet {
 		VersionNotifier { origin: VersionedMultiLocation, is_active: bool },
 		/// A response has been received.
 		Ready { response: VersionedResponse, at: BlockNumber },
+		/// No response was received before the query's timeout; it was expired at block `at`.
+		Timeout { at: BlockNumber },
 	}
 
 	#[derive(Copy, Clone)]
@@ -316,6 +458,12 @@ pub mod pallet {
 	pub(super) type Queries<T: Config> =
 		StorageMap<_, Blake2_128Concat, QueryId, QueryStatus<T::BlockNumber>, OptionQuery>;
 
+	/// The pending queries indexed by the block in which they should be expired if no response
+	/// has been received by then.
+	#[pallet::storage]
+	pub(super) type QueryTimeouts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, QueryId, (), OptionQuery>;
+
 	/// The existing asset traps.
 	///
 	/// Key is the blake2 256 hash of (origin, versioned `MultiAssets`) pair. Value is the number of
@@ -324,6 +472,32 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The details of the existing asset traps, keyed as in `AssetTraps`.
+	///
+	/// Every trap has its origin and assets recorded here, but only the traps whose deposit could
+	/// be reserved have a sweepable entry, so there are never more entries than the count in
+	/// `AssetTraps`.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_trap_details)]
+	pub(super) type AssetTrapDetails<T: Config> = StorageMap<
+		_,
+		Identity,
+		H256,
+		TrappedAssets<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The allowances of the origins which have sent messages under `Config::SendRateLimit`. An
+	/// origin with no entry has its full allowance.
+	#[pallet::storage]
+	pub(super) type SendAllowances<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		VersionedMultiLocation,
+		SendAllowance<T::BlockNumber>,
+		OptionQuery,
+	>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -388,6 +562,16 @@ pub mod pallet {
 	pub(super) type CurrentMigration<T: Config> =
 		StorageValue<_, VersionMigrationStage, OptionQuery>;
 
+	/// Whether the execution of all incoming XCM messages is suspended.
+	#[pallet::storage]
+	pub(super) type XcmExecutionSuspended<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Locations from which the execution of incoming XCM messages is suspended, regardless of
+	/// `XcmExecutionSuspended`.
+	#[pallet::storage]
+	pub(super) type SuspendedOrigins<T: Config> =
+		StorageMap<_, Blake2_128Concat, VersionedMultiLocation, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		/// The default version to encode outgoing XCM messages with.
@@ -410,8 +594,8 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-			let mut weight_used = 0;
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight_used = Self::expire_queries(n);
 			if let Some(migration) = CurrentMigration::<T>::get() {
 				// Consume 10% of block at most
 				let max_weight = T::BlockWeights::get().max_block / 10;
@@ -463,11 +647,31 @@ pub mod pallet {
 				origin_location.clone().try_into().map_err(|_| Error::<T>::InvalidOrigin)?;
 			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
 			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
-
-			Self::send_xcm(interior, dest.clone(), message.clone()).map_err(|e| match e {
-				SendError::CannotReachDestination(..) => Error::<T>::Unreachable,
+			ensure!(
+				T::XcmSendFilter::contains(&(origin_location.clone(), dest.clone())),
+				Error::<T>::Filtered
+			);
+			let allowance =
+				Self::charge_send_allowance(&origin_location, message.encoded_size() as u32)
+					.map_err(|()| Error::<T>::Throttled)?;
+
+			Self::send_xcm_paid(
+				interior,
+				dest.clone(),
+				message.clone(),
+				Some(origin_location.clone()),
+			)
+			.map_err(|e| match e {
+				SendError::NotApplicable | SendError::Unroutable => Error::<T>::Unreachable,
+				SendError::Fees => Error::<T>::FeesNotMet,
 				_ => Error::<T>::SendFailure,
 			})?;
+			if let Some(allowance) = allowance {
+				SendAllowances::<T>::insert(
+					LatestVersionedMultiLocation(&origin_location),
+					allowance,
+				);
+			}
 			Self::deposit_event(Event::Sent(origin_location, dest, message));
 			Ok(())
 		}
@@ -475,8 +679,11 @@ pub mod pallet {
 		/// Teleport some assets from the local chain to some destination chain.
 		///
 		/// Fee payment on the destination side is made from the asset in the `assets` vector of
-		/// index `fee_asset_item`. The weight limit for fees is not provided and thus is unlimited,
-		/// with all fees taken as needed from the asset.
+		/// index `fee_asset_item`. The weight limit for fees is not provided, so it is estimated
+		/// using the local `Weigher`, which may not match the destination's own weights.
+		///
+		/// NOTE: Deprecated in favour of `limited_teleport_assets`, which takes an explicit weight
+		/// limit.
 		///
 		/// - `origin`: Must be capable of withdrawing the `assets` and executing XCM.
 		/// - `dest`: Destination context for the assets. Will typically be `X2(Parent, Parachain(..))` to send
@@ -497,7 +704,9 @@ pub mod pallet {
 						WithdrawAsset(assets),
 						InitiateTeleport { assets: Wild(All), dest, xcm: Xcm(vec![]) },
 					]);
-					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| 100_000_000 + w)
+					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| {
+						T::WeightInfo::limited_teleport_assets().saturating_add(w)
+					})
 				},
 				_ => Weight::max_value(),
 			}
@@ -516,8 +725,11 @@ pub mod pallet {
 		/// chain and forward a notification XCM.
 		///
 		/// Fee payment on the destination side is made from the asset in the `assets` vector of
-		/// index `fee_asset_item`. The weight limit for fees is not provided and thus is unlimited,
-		/// with all fees taken as needed from the asset.
+		/// index `fee_asset_item`. The weight limit for fees is not provided, so it is estimated
+		/// using the local `Weigher`, which may not match the destination's own weights.
+		///
+		/// NOTE: Deprecated in favour of `limited_reserve_transfer_assets`, which takes an explicit
+		/// weight limit.
 		///
 		/// - `origin`: Must be capable of withdrawing the `assets` and executing XCM.
 		/// - `dest`: Destination context for the assets. Will typically be `X2(Parent, Parachain(..))` to send
@@ -535,7 +747,9 @@ pub mod pallet {
 					let mut message = Xcm(vec![
 						TransferReserveAsset { assets, dest, xcm: Xcm(vec![]) }
 					]);
-					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| 100_000_000 + w)
+					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| {
+						T::WeightInfo::limited_reserve_transfer_assets().saturating_add(w)
+					})
 				},
 				_ => Weight::max_value(),
 			}
@@ -697,7 +911,9 @@ pub mod pallet {
 					let mut message = Xcm(vec![
 						TransferReserveAsset { assets, dest, xcm: Xcm(vec![]) }
 					]);
-					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| 100_000_000 + w)
+					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| {
+						T::WeightInfo::limited_reserve_transfer_assets().saturating_add(w)
+					})
 				},
 				_ => Weight::max_value(),
 			}
@@ -747,7 +963,9 @@ pub mod pallet {
 						WithdrawAsset(assets),
 						InitiateTeleport { assets: Wild(All), dest, xcm: Xcm(vec![]) },
 					]);
-					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| 100_000_000 + w)
+					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| {
+						T::WeightInfo::limited_teleport_assets().saturating_add(w)
+					})
 				},
 				_ => Weight::max_value(),
 			}
@@ -769,9 +987,315 @@ pub mod pallet {
 				Some(weight_limit),
 			)
 		}
+
+		/// Execute an XCM message as though it originated from an arbitrary location.
+		///
+		/// The message is granted `max_weight` of weight credit, so it passes any barrier which
+		/// accepts pre-paid weight (e.g. `TakeWeightCredit`) without buying execution.
+		///
+		/// - `origin`: Must be Root.
+		/// - `location`: The location as which the message should be executed.
+		/// - `message`: The message to execute.
+		/// - `max_weight`: No more than this amount of weight will be used in its execution.
+		#[pallet::weight(max_weight.saturating_add(100_000_000u64))]
+		pub fn force_execute(
+			origin: OriginFor<T>,
+			location: Box<VersionedMultiLocation>,
+			message: Box<VersionedXcm<<T as SysConfig>::Call>>,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let location: MultiLocation =
+				(*location).try_into().map_err(|()| Error::<T>::BadLocation)?;
+			let message = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let outcome = T::XcmExecutor::execute_with_origin(location, message, max_weight);
+			let result = Ok(Some(outcome.weight_used().saturating_add(100_000_000)).into());
+			Self::deposit_event(Event::Attempted(outcome));
+			result
+		}
+
+		/// Suspend the execution of incoming XCM messages.
+		///
+		/// - `origin`: Must be Root.
+		/// - `maybe_location`: The location whose messages should no longer be executed, or `None`
+		///   to suspend execution for all locations.
+		#[pallet::weight(100_000_000u64)]
+		pub fn force_suspend_execution(
+			origin: OriginFor<T>,
+			maybe_location: Option<Box<VersionedMultiLocation>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let maybe_location = Self::maybe_latest_location(maybe_location)?;
+			match maybe_location {
+				Some(ref location) =>
+					SuspendedOrigins::<T>::insert(LatestVersionedMultiLocation(location), ()),
+				None => XcmExecutionSuspended::<T>::put(true),
+			}
+			Self::deposit_event(Event::ExecutionSuspended(maybe_location));
+			Ok(())
+		}
+
+		/// Resume the execution of incoming XCM messages previously suspended with
+		/// `force_suspend_execution`.
+		///
+		/// - `origin`: Must be Root.
+		/// - `maybe_location`: The location whose messages may be executed again, or `None` to lift
+		///   the suspension for all locations. Lifting the global suspension does not affect
+		///   locations which were suspended individually.
+		#[pallet::weight(100_000_000u64)]
+		pub fn force_resume_execution(
+			origin: OriginFor<T>,
+			maybe_location: Option<Box<VersionedMultiLocation>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let maybe_location = Self::maybe_latest_location(maybe_location)?;
+			match maybe_location {
+				Some(ref location) =>
+					SuspendedOrigins::<T>::remove(LatestVersionedMultiLocation(location)),
+				None => XcmExecutionSuspended::<T>::kill(),
+			}
+			Self::deposit_event(Event::ExecutionResumed(maybe_location));
+			Ok(())
+		}
+
+		/// Claim assets trapped by a previous execution from the origin's location and deposit
+		/// them to `beneficiary`.
+		///
+		/// - `origin`: Must be capable of executing XCM; its location must be the one whose
+		///   execution trapped the assets.
+		/// - `assets`: The trapped assets, in the XCM version in which they were trapped.
+		/// - `beneficiary`: The location to which the claimed assets should be deposited.
+		#[pallet::weight({
+			let assets_version = assets.identify_version();
+			match (*assets.clone()).try_into() {
+				Ok(assets) => {
+					let ticket = Pallet::<T>::claim_ticket(assets_version);
+					let mut message = Pallet::<T>::claim_message(assets, ticket, Here.into());
+					T::Weigher::weight(&mut message).map_or(Weight::max_value(), |w| 100_000_000 + w)
+				},
+				_ => Weight::max_value(),
+			}
+		})]
+		pub fn claim_assets(
+			origin: OriginFor<T>,
+			assets: Box<VersionedMultiAssets>,
+			beneficiary: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let ticket = Self::claim_ticket(assets.identify_version());
+			let assets: MultiAssets = (*assets).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			Self::do_claim_assets(origin_location, assets, ticket, beneficiary)?;
+			Ok(())
+		}
+
+		/// Sweep the earliest trap of some expired trapped assets to the treasury, returning its
+		/// deposit.
+		///
+		/// - `origin`: Must be Signed; anyone may sweep expired assets.
+		/// - `hash`: The hash under which the assets are trapped.
+		#[pallet::weight(200_000_000u64)]
+		#[transactional]
+		pub fn sweep_trapped_assets(origin: OriginFor<T>, hash: H256) -> DispatchResult {
+			ensure_signed(origin)?;
+			let beneficiary = T::TreasuryLocation::get().ok_or(Error::<T>::NoTreasury)?;
+			let trap = AssetTrapDetails::<T>::get(hash).ok_or(Error::<T>::UnknownTrap)?;
+			let entry = trap.entries.first().cloned().ok_or(Error::<T>::UnknownTrap)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > entry.expiry, Error::<T>::TrapNotExpired);
+			let trap_origin: MultiLocation =
+				trap.origin.try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let ticket = Self::claim_ticket(trap.assets.identify_version());
+			let assets: MultiAssets =
+				trap.assets.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			// Claiming releases the earliest entries only while there are more entries than traps,
+			// so it releases the swept entry itself only if every trap has an entry.
+			let released_by_claim = trap.entries.len() >= AssetTraps::<T>::get(hash) as usize;
+			let outcome = Self::do_claim_assets(trap_origin.clone(), assets, ticket, beneficiary)?;
+			outcome.ensure_complete().map_err(|_| Error::<T>::SweepFailed)?;
+			if !released_by_claim {
+				AssetTrapDetails::<T>::mutate(hash, |maybe_trap| {
+					if let Some(trap) = maybe_trap {
+						trap.entries.remove(0);
+					}
+				});
+				T::Currency::unreserve(&entry.depositor, entry.deposit);
+			}
+			Self::deposit_event(Event::TrappedAssetsSwept(hash, trap_origin, trap.assets));
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// The assets trapped by the execution of messages from `origin`, along with the hash
+		/// under which each is trapped.
+		///
+		/// NOTE: This iterates over all trapped assets and is intended for off-chain use only.
+		pub fn trapped_assets(
+			origin: &MultiLocation,
+		) -> Vec<(H256, TrappedAssets<T::AccountId, BalanceOf<T>, T::BlockNumber>)> {
+			AssetTrapDetails::<T>::iter()
+				.filter(|(_, trap)| {
+					MultiLocation::try_from(trap.origin.clone()).map_or(false, |o| &o == origin)
+				})
+				.collect()
+		}
+
+		/// Dispatch `call` from `origin`, returning its result along with the events it emitted
+		/// and the messages it queued for delivery in `Router`.
+		///
+		/// NOTE: This changes state and must only be called where that state is discarded, such as
+		/// in the implementation of `runtime_api::DryRunApi`.
+		pub fn dry_run_call<Router: InspectMessageQueues>(
+			origin: <T as Config>::Origin,
+			call: <T as Config>::Call,
+		) -> CallDryRunEffects<<T as SysConfig>::Event> {
+			Router::clear_messages();
+			frame_system::Pallet::<T>::reset_events();
+			let execution_result = call.dispatch(origin);
+			CallDryRunEffects {
+				execution_result,
+				emitted_events: Self::take_emitted_events(),
+				forwarded_xcms: Router::get_messages(),
+			}
+		}
+
+		/// Execute `xcm` as if it were received from `origin_location`, returning its outcome
+		/// along with the events it emitted and the messages it queued for delivery in `Router`.
+		///
+		/// NOTE: This changes state and must only be called where that state is discarded, such as
+		/// in the implementation of `runtime_api::DryRunApi`.
+		pub fn dry_run_xcm<Router: InspectMessageQueues>(
+			origin_location: MultiLocation,
+			xcm: Xcm<<T as SysConfig>::Call>,
+		) -> XcmDryRunEffects<<T as SysConfig>::Event> {
+			Router::clear_messages();
+			frame_system::Pallet::<T>::reset_events();
+			let execution_result =
+				T::XcmExecutor::execute_xcm(origin_location, xcm, Weight::max_value());
+			XcmDryRunEffects {
+				execution_result,
+				emitted_events: Self::take_emitted_events(),
+				forwarded_xcms: Router::get_messages(),
+			}
+		}
+
+		/// The weight of executing `message` on this chain, as given by `Config::Weigher`.
+		pub fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+			let message = Xcm::<()>::try_from(message)
+				.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+			let mut message: Xcm<<T as SysConfig>::Call> = message.into();
+			T::Weigher::weight(&mut message).map_err(|()| XcmPaymentApiError::WeightNotComputable)
+		}
+
+		fn take_emitted_events() -> Vec<<T as SysConfig>::Event> {
+			frame_system::Pallet::<T>::read_events_no_consensus()
+				.into_iter()
+				.map(|record| record.event)
+				.collect()
+		}
+
+		/// The allowance which `origin` would have left under `Config::SendRateLimit` after sending
+		/// a message of `len` encoded bytes.
+		///
+		/// Returns `Ok(None)` if `origin` is not rate limited and `Err` if its allowance does not
+		/// cover the message.
+		fn charge_send_allowance(
+			origin: &MultiLocation,
+			len: u32,
+		) -> Result<Option<SendAllowance<T::BlockNumber>>, ()> {
+			let limit = match T::SendRateLimit::get() {
+				Some(limit) if origin != &MultiLocation::here() => limit,
+				_ => return Ok(None),
+			};
+			let now = frame_system::Pallet::<T>::block_number();
+			let (messages, bytes) =
+				match SendAllowances::<T>::get(LatestVersionedMultiLocation(origin)) {
+					Some(SendAllowance { messages, bytes, updated }) => {
+						let elapsed: u32 = now.saturating_sub(updated).saturated_into();
+						(
+							messages
+								.saturating_add(elapsed.saturating_mul(limit.messages_per_block))
+								.min(limit.max_messages),
+							bytes
+								.saturating_add(elapsed.saturating_mul(limit.bytes_per_block))
+								.min(limit.max_bytes),
+						)
+					},
+					None => (limit.max_messages, limit.max_bytes),
+				};
+			let messages = messages.checked_sub(1).ok_or(())?;
+			let bytes = bytes.checked_sub(len).ok_or(())?;
+			Ok(Some(SendAllowance { messages, bytes, updated: now }))
+		}
+
+		/// The `ClaimAsset` ticket which identifies assets trapped in the given XCM version.
+		fn claim_ticket(assets_version: XcmVersion) -> MultiLocation {
+			version_ticket(assets_version)
+		}
+
+		/// The message which claims the trapped `assets` and deposits them into `beneficiary`.
+		fn claim_message(
+			assets: MultiAssets,
+			ticket: MultiLocation,
+			beneficiary: MultiLocation,
+		) -> Xcm<<T as SysConfig>::Call> {
+			let max_assets = assets.len() as u32;
+			Xcm(vec![
+				ClaimAsset { assets, ticket },
+				DepositAsset { assets: Wild(All), max_assets, beneficiary },
+			])
+		}
+
+		fn do_claim_assets(
+			origin_location: MultiLocation,
+			assets: MultiAssets,
+			ticket: MultiLocation,
+			beneficiary: MultiLocation,
+		) -> Result<Outcome, Error<T>> {
+			let message = Self::claim_message(assets, ticket, beneficiary);
+			let outcome = Self::execute_local(origin_location, message)?;
+			Self::deposit_event(Event::Attempted(outcome.clone()));
+			Ok(outcome)
+		}
+
+		/// Execute a locally-built `message` as though it originated from `origin_location`,
+		/// crediting it with exactly the weight which the executor reckons it needs.
+		fn execute_local(
+			origin_location: MultiLocation,
+			message: Xcm<<T as SysConfig>::Call>,
+		) -> Result<Outcome, Error<T>> {
+			let hash = message.using_encoded(sp_io::hashing::blake2_256);
+			let pre =
+				T::XcmExecutor::prepare(message).map_err(|_| Error::<T>::UnweighableMessage)?;
+			let weight = pre.weight_of();
+			Ok(T::XcmExecutor::execute(origin_location, pre, hash, weight))
+		}
+
+		/// The entry under which a trap by `origin` may be swept once expired, if there is a
+		/// treasury and the deposit for it can be reserved from the origin's sovereign account.
+		fn sweepable_trap_entry(
+			origin: &MultiLocation,
+		) -> Option<TrapEntry<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+			T::TreasuryLocation::get()?;
+			let depositor = T::SovereignAccountOf::convert_ref(origin).ok()?;
+			let deposit = T::TrapDeposit::get();
+			T::Currency::reserve(&depositor, deposit).ok()?;
+			let expiry = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::TrappedAssetsExpiry::get());
+			Some(TrapEntry { expiry, depositor, deposit })
+		}
+
+		fn maybe_latest_location(
+			maybe_location: Option<Box<VersionedMultiLocation>>,
+		) -> Result<Option<MultiLocation>, Error<T>> {
+			maybe_location
+				.map(|l| MultiLocation::try_from(*l).map_err(|()| Error::<T>::BadLocation))
+				.transpose()
+		}
+
 		fn do_reserve_transfer_assets(
 			origin: OriginFor<T>,
 			dest: Box<VersionedMultiLocation>,
@@ -790,12 +1314,12 @@ pub mod pallet {
 			let value = (origin_location, assets.drain());
 			ensure!(T::XcmReserveTransferFilter::contains(&value), Error::<T>::Filtered);
 			let (origin_location, assets) = value;
-			let ancestry = T::LocationInverter::ancestry();
+			let context = T::UniversalLocation::get();
 			let fees = assets
 				.get(fee_asset_item as usize)
 				.ok_or(Error::<T>::Empty)?
 				.clone()
-				.reanchored(&dest, &ancestry)
+				.reanchored(&dest, &context)
 				.map_err(|_| Error::<T>::CannotReanchor)?;
 			let max_assets = assets.len() as u32;
 			let assets: MultiAssets = assets.into();
@@ -820,11 +1344,8 @@ pub mod pallet {
 				BuyExecution { fees, weight_limit },
 				DepositAsset { assets: Wild(All), max_assets, beneficiary },
 			]);
-			let mut message = Xcm(vec![TransferReserveAsset { assets, dest, xcm }]);
-			let weight =
-				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
-			let outcome =
-				T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
+			let message = Xcm(vec![TransferReserveAsset { assets, dest, xcm }]);
+			let outcome = Self::execute_local(origin_location, message)?;
 			Self::deposit_event(Event::Attempted(outcome));
 			Ok(())
 		}
@@ -847,12 +1368,12 @@ pub mod pallet {
 			let value = (origin_location, assets.drain());
 			ensure!(T::XcmTeleportFilter::contains(&value), Error::<T>::Filtered);
 			let (origin_location, assets) = value;
-			let ancestry = T::LocationInverter::ancestry();
+			let context = T::UniversalLocation::get();
 			let fees = assets
 				.get(fee_asset_item as usize)
 				.ok_or(Error::<T>::Empty)?
 				.clone()
-				.reanchored(&dest, &ancestry)
+				.reanchored(&dest, &context)
 				.map_err(|_| Error::<T>::CannotReanchor)?;
 			let max_assets = assets.len() as u32;
 			let assets: MultiAssets = assets.into();
@@ -877,12 +1398,9 @@ pub mod pallet {
 				BuyExecution { fees, weight_limit },
 				DepositAsset { assets: Wild(All), max_assets, beneficiary },
 			]);
-			let mut message =
+			let message =
 				Xcm(vec![WithdrawAsset(assets), InitiateTeleport { assets: Wild(All), dest, xcm }]);
-			let weight =
-				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
-			let outcome =
-				T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
+			let outcome = Self::execute_local(origin_location, message)?;
 			Self::deposit_event(Event::Attempted(outcome));
 			Ok(())
 		}
@@ -961,8 +1479,8 @@ pub mod pallet {
 					};
 					let response = Response::Version(xcm_version);
 					let message = Xcm(vec![QueryResponse { query_id, response, max_weight }]);
-					let event = match T::XcmRouter::send_xcm(new_key.clone(), message) {
-						Ok(()) => {
+					let event = match send_xcm::<T::XcmRouter>(new_key.clone(), message) {
+						Ok(_) => {
 							let value = (query_id, max_weight, xcm_version);
 							VersionNotifyTargets::<T>::insert(XCM_VERSION, key, value);
 							Event::VersionChangeNotified(new_key, xcm_version)
@@ -1008,8 +1526,8 @@ pub mod pallet {
 							let response = Response::Version(xcm_version);
 							let message =
 								Xcm(vec![QueryResponse { query_id, response, max_weight }]);
-							let event = match T::XcmRouter::send_xcm(new_key.clone(), message) {
-								Ok(()) => {
+							let event = match send_xcm::<T::XcmRouter>(new_key.clone(), message) {
+								Ok(_) => {
 									VersionNotifyTargets::<T>::insert(
 										XCM_VERSION,
 										versioned_key,
@@ -1042,9 +1560,9 @@ pub mod pallet {
 				q.saturating_inc();
 				r
 			});
-			// TODO #3735: Correct weight.
-			let instruction = SubscribeVersion { query_id, max_response_weight: 0 };
-			T::XcmRouter::send_xcm(dest, Xcm(vec![instruction]))?;
+			let max_response_weight = Self::version_notify_response_weight();
+			let instruction = SubscribeVersion { query_id, max_response_weight };
+			send_xcm::<T::XcmRouter>(dest, Xcm(vec![instruction]))?;
 			VersionNotifiers::<T>::insert(XCM_VERSION, &versioned_dest, query_id);
 			let query_status =
 				QueryStatus::VersionNotifier { origin: versioned_dest, is_active: false };
@@ -1052,31 +1570,54 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// The weight of handling a version notification in `on_response`: the query is read, and
+		/// both it and the supported version of the notifier are written.
+		fn version_notify_response_weight() -> Weight {
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
 		/// Request that `dest` ceases informing us of its version.
 		pub fn unrequest_version_notify(dest: impl Into<MultiLocation>) -> XcmResult {
 			let dest = dest.into();
 			let versioned_dest = LatestVersionedMultiLocation(&dest);
 			let query_id = VersionNotifiers::<T>::take(XCM_VERSION, versioned_dest)
 				.ok_or(XcmError::InvalidLocation)?;
-			T::XcmRouter::send_xcm(dest.clone(), Xcm(vec![UnsubscribeVersion]))?;
+			send_xcm::<T::XcmRouter>(dest.clone(), Xcm(vec![UnsubscribeVersion]))?;
 			Queries::<T>::remove(query_id);
 			Ok(())
 		}
 
 		/// Relay an XCM `message` from a given `interior` location in this context to a given `dest`
-		/// location. A null `dest` is not handled.
+		/// location. A null `dest` is not handled. Any price for delivery is paid by this chain.
 		pub fn send_xcm(
+			interior: impl Into<Junctions>,
+			dest: impl Into<MultiLocation>,
+			message: Xcm<()>,
+		) -> Result<XcmHash, SendError> {
+			Self::send_xcm_paid(interior, dest, message, None)
+		}
+
+		/// Relay an XCM `message` from a given `interior` location in this context to a given `dest`
+		/// location, charging the price for delivery to `maybe_fee_payer` if it is `Some`.
+		fn send_xcm_paid(
 			interior: impl Into<Junctions>,
 			dest: impl Into<MultiLocation>,
 			mut message: Xcm<()>,
-		) -> Result<(), SendError> {
+			maybe_fee_payer: Option<MultiLocation>,
+		) -> Result<XcmHash, SendError> {
 			let interior = interior.into();
 			let dest = dest.into();
 			if interior != Junctions::Here {
 				message.0.insert(0, DescendOrigin(interior))
 			};
 			log::trace!(target: "xcm::send_xcm", "dest: {:?}, message: {:?}", &dest, &message);
-			T::XcmRouter::send_xcm(dest, message)
+			let (ticket, price) = validate_send::<T::XcmRouter>(dest, message)?;
+			if let Some(fee_payer) = maybe_fee_payer {
+				if !price.is_none() {
+					T::XcmExecutor::charge_fees(fee_payer, price).map_err(|_| SendError::Fees)?;
+				}
+			}
+			T::XcmRouter::deliver(ticket)
 		}
 
 		pub fn check_account() -> T::AccountId {
@@ -1089,9 +1630,15 @@ pub mod pallet {
 			maybe_notify: Option<(u8, u8)>,
 			timeout: T::BlockNumber,
 		) -> u64 {
+			// The query may be expired in the first block after `timeout`, but never in a block
+			// whose `on_initialize` has already run.
+			let expiry = timeout
+				.saturating_add(One::one())
+				.max(frame_system::Pallet::<T>::block_number().saturating_add(One::one()));
 			QueryCounter::<T>::mutate(|q| {
 				let r = *q;
 				q.saturating_inc();
+				QueryTimeouts::<T>::insert(expiry, r, ());
 				Queries::<T>::insert(
 					r,
 					QueryStatus::Pending {
@@ -1122,7 +1669,8 @@ pub mod pallet {
 			timeout: T::BlockNumber,
 		) -> Result<QueryId, XcmError> {
 			let responder = responder.into();
-			let dest = T::LocationInverter::invert_location(&responder)
+			let dest = T::UniversalLocation::get()
+				.invert_target(&responder)
 				.map_err(|()| XcmError::MultiLocationNotInvertible)?;
 			let query_id = Self::new_query(responder, timeout);
 			let report_error = Xcm(vec![ReportError { dest, query_id, max_response_weight: 0 }]);
@@ -1157,7 +1705,8 @@ pub mod pallet {
 			timeout: T::BlockNumber,
 		) -> Result<(), XcmError> {
 			let responder = responder.into();
-			let dest = T::LocationInverter::invert_location(&responder)
+			let dest = T::UniversalLocation::get()
+				.invert_target(&responder)
 				.map_err(|()| XcmError::MultiLocationNotInvertible)?;
 			let notify: <T as Config>::Call = notify.into();
 			let max_response_weight = notify.get_dispatch_info().weight;
@@ -1200,14 +1749,57 @@ pub mod pallet {
 			}
 		}
 
+		/// Attempt to remove a query which has timed out without a response, returning the block
+		/// at which it was expired.
+		///
+		/// Returns `None` if the query has not (yet) timed out.
+		pub fn take_timeout(query_id: QueryId) -> Option<T::BlockNumber> {
+			if let Some(QueryStatus::Timeout { at }) = Queries::<T>::get(query_id) {
+				Queries::<T>::remove(query_id);
+				Some(at)
+			} else {
+				None
+			}
+		}
+
+		/// Expire all queries due to time out in block `now` which are still pending.
+		///
+		/// Queries without a notification are kept as `QueryStatus::Timeout` so that their owner
+		/// may observe the timeout; those with a notification are removed since nobody will ever
+		/// take them. Returns the weight used.
+		fn expire_queries(now: T::BlockNumber) -> Weight {
+			let mut weight_used = 0;
+			for (query_id, ()) in QueryTimeouts::<T>::drain_prefix(now) {
+				weight_used.saturating_accrue(T::DbWeight::get().reads_writes(2, 1));
+				match Queries::<T>::get(query_id) {
+					Some(QueryStatus::Pending { maybe_notify, timeout, .. }) if timeout < now => {
+						if maybe_notify.is_some() {
+							Queries::<T>::remove(query_id);
+						} else {
+							Queries::<T>::insert(query_id, QueryStatus::Timeout { at: now });
+						}
+						weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+						Self::deposit_event(Event::QueryTimedOut(query_id));
+					},
+					// Already answered or taken.
+					_ => {},
+				}
+			}
+			weight_used
+		}
+
 		/// Note that a particular destination to whom we would like to send a message is unknown
-		/// and queue it for version discovery.
+		/// and queue it for version discovery, unless we are already awaiting its version.
 		fn note_unknown_version(dest: &MultiLocation) {
 			log::trace!(
 				target: "xcm::pallet_xcm::note_unknown_version",
 				"XCM version is unknown for destination: {:?}",
 				dest,
 			);
+			if VersionNotifiers::<T>::contains_key(XCM_VERSION, LatestVersionedMultiLocation(dest))
+			{
+				return
+			}
 			let versioned_dest = VersionedMultiLocation::from(dest.clone());
 			VersionDiscoveryQueue::<T>::mutate(|q| {
 				if let Some(index) = q.iter().position(|i| &i.0 == &versioned_dest) {
@@ -1242,6 +1834,43 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> QueryHandler for Pallet<T> {
+		type BlockNumber = T::BlockNumber;
+
+		fn new_query(responder: impl Into<MultiLocation>, timeout: T::BlockNumber) -> QueryId {
+			Self::do_new_query(responder, None, timeout)
+		}
+
+		fn report_outcome(
+			message: &mut Xcm<()>,
+			responder: impl Into<MultiLocation>,
+			timeout: T::BlockNumber,
+		) -> Result<QueryId, XcmError> {
+			// Resolves to the inherent function of the same name.
+			Self::report_outcome(message, responder, timeout)
+		}
+
+		fn take_response(query_id: QueryId) -> QueryResponseStatus<T::BlockNumber> {
+			match Queries::<T>::get(query_id) {
+				Some(QueryStatus::Ready { response, at }) => match response.try_into() {
+					Ok(response) => {
+						Queries::<T>::remove(query_id);
+						Self::deposit_event(Event::ResponseTaken(query_id));
+						QueryResponseStatus::Ready { response, at }
+					},
+					Err(_) => QueryResponseStatus::UnexpectedVersion,
+				},
+				Some(QueryStatus::Pending { timeout, .. }) =>
+					QueryResponseStatus::Pending { timeout },
+				Some(QueryStatus::Timeout { at }) => {
+					Queries::<T>::remove(query_id);
+					QueryResponseStatus::TimedOut { at }
+				},
+				Some(QueryStatus::VersionNotifier { .. }) | None => QueryResponseStatus::NotFound,
+			}
+		}
+	}
+
 	impl<T: Config> VersionChangeNotifier for Pallet<T> {
 		/// Start notifying `location` should the XCM version of this chain change.
 		///
@@ -1259,7 +1888,7 @@ pub mod pallet {
 			let xcm_version = T::AdvertisedXcmVersion::get();
 			let response = Response::Version(xcm_version);
 			let instruction = QueryResponse { query_id, response, max_weight };
-			T::XcmRouter::send_xcm(dest.clone(), Xcm(vec![instruction]))?;
+			send_xcm::<T::XcmRouter>(dest.clone(), Xcm(vec![instruction]))?;
 
 			let value = (query_id, max_weight, xcm_version);
 			VersionNotifyTargets::<T>::insert(XCM_VERSION, versioned_dest, value);
@@ -1280,17 +1909,53 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> CheckSuspension for Pallet<T> {
+		fn is_suspended<Call>(
+			origin: &MultiLocation,
+			_message: &Xcm<Call>,
+			_max_weight: Weight,
+		) -> bool {
+			XcmExecutionSuspended::<T>::get() ||
+				SuspendedOrigins::<T>::contains_key(LatestVersionedMultiLocation(origin))
+		}
+	}
+
 	impl<T: Config> DropAssets for Pallet<T> {
-		fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight {
+		fn drop_assets(
+			origin: &MultiLocation,
+			assets: Assets,
+			_context: &XcmContext,
+			meter: &mut WeightMeter,
+		) {
 			if assets.is_empty() {
-				return 0
+				return
+			}
+			// `AssetTraps` and `AssetTrapDetails` are each read and written, as is the depositor's
+			// account if a deposit is reserved for a sweepable entry.
+			if !meter.check_accrue(T::DbWeight::get().reads_writes(3, 3)) {
+				log::warn!(
+					target: "runtime::xcm",
+					"Not enough weight left to trap assets dropped by {:?}: {:?}",
+					origin,
+					assets,
+				);
+				return
 			}
 			let versioned = VersionedMultiAssets::from(MultiAssets::from(assets));
 			let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
 			AssetTraps::<T>::mutate(hash, |n| *n += 1);
+			let maybe_entry = Self::sweepable_trap_entry(origin);
+			AssetTrapDetails::<T>::mutate(hash, |maybe_trap| {
+				maybe_trap
+					.get_or_insert_with(|| TrappedAssets {
+						origin: origin.clone().into(),
+						assets: versioned.clone(),
+						entries: Vec::new(),
+					})
+					.entries
+					.extend(maybe_entry)
+			});
 			Self::deposit_event(Event::AssetsTrapped(hash, origin.clone(), versioned));
-			// TODO #3735: Put the real weight in there.
-			0
 		}
 	}
 
@@ -1300,22 +1965,39 @@ pub mod pallet {
 			ticket: &MultiLocation,
 			assets: &MultiAssets,
 		) -> bool {
-			let mut versioned = VersionedMultiAssets::from(assets.clone());
-			match (ticket.parents, &ticket.interior) {
-				(0, X1(GeneralIndex(i))) =>
-					versioned = match versioned.into_version(*i as u32) {
-						Ok(v) => v,
-						Err(()) => return false,
-					},
-				(0, Here) => (),
-				_ => return false,
+			let version = match ticket_version(ticket) {
+				Some(version) => version,
+				None => return false,
+			};
+			let versioned = match VersionedMultiAssets::from(assets.clone()).into_version(version) {
+				Ok(v) => v,
+				Err(()) => return false,
 			};
-			let hash = BlakeTwo256::hash_of(&(origin, versioned));
-			match AssetTraps::<T>::get(hash) {
+			let hash = BlakeTwo256::hash_of(&(origin, &versioned));
+			let remaining = match AssetTraps::<T>::get(hash) {
 				0 => return false,
-				1 => AssetTraps::<T>::remove(hash),
-				n => AssetTraps::<T>::insert(hash, n - 1),
-			}
+				1 => {
+					AssetTraps::<T>::remove(hash);
+					0
+				},
+				n => {
+					AssetTraps::<T>::insert(hash, n - 1);
+					n - 1
+				},
+			};
+			// Keep no more sweepable entries than there are traps, releasing the earliest first.
+			AssetTrapDetails::<T>::mutate_exists(hash, |maybe_trap| {
+				if let Some(trap) = maybe_trap {
+					while trap.entries.len() > remaining as usize {
+						let entry = trap.entries.remove(0);
+						T::Currency::unreserve(&entry.depositor, entry.deposit);
+					}
+					if remaining == 0 {
+						*maybe_trap = None;
+					}
+				}
+			});
+			Self::deposit_event(Event::AssetsClaimed(hash, origin.clone(), versioned));
 			return true
 		}
 	}
@@ -1342,6 +2024,8 @@ pub mod pallet {
 					Response::Version(v),
 					Some(QueryStatus::VersionNotifier { origin: expected_origin, is_active }),
 				) => {
+					// Only the query has been read so far.
+					let read_weight = T::DbWeight::get().reads(1);
 					let origin: MultiLocation = match expected_origin.try_into() {
 						Ok(o) if &o == origin => o,
 						Ok(o) => {
@@ -1350,7 +2034,7 @@ pub mod pallet {
 								query_id,
 								Some(o),
 							));
-							return 0
+							return read_weight
 						},
 						_ => {
 							Self::deposit_event(Event::InvalidResponder(
@@ -1358,11 +2042,12 @@ pub mod pallet {
 								query_id,
 								None,
 							));
-							// TODO #3735: Correct weight for this.
-							return 0
+							return read_weight
 						},
 					};
-					// TODO #3735: Check max_weight is correct.
+					// `max_weight` is not enforced here: handling a notification costs a small,
+					// fixed weight, and notifiers subscribed before we budgeted for it reply with
+					// a zero `max_weight`.
 					if !is_active {
 						Queries::<T>::insert(
 							query_id,
@@ -1379,7 +2064,7 @@ pub mod pallet {
 						v,
 					);
 					Self::deposit_event(Event::SupportedVersionChanged(origin, v));
-					0
+					Self::version_notify_response_weight()
 				},
 				(response, Some(QueryStatus::Pending { responder, maybe_notify, .. })) => {
 					let responder = match MultiLocation::try_from(responder) {
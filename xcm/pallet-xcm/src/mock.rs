@@ -14,19 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::{construct_runtime, parameter_types, traits::Everything, weights::Weight};
+use codec::Encode;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing},
+	weights::Weight,
+};
 use polkadot_parachain::primitives::Id as ParaId;
 use polkadot_runtime_parachains::origin;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
 pub use sp_std::{cell::RefCell, fmt::Debug, marker::PhantomData};
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, Case, ChildParachainAsNative, ChildParachainConvertsVia,
 	ChildSystemParachainAsSuperuser, CurrencyAdapter as XcmCurrencyAdapter, FixedRateOfFungible,
-	FixedWeightBounds, IsConcrete, LocationInverter, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	FixedWeightBounds, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit,
 };
 use xcm_executor::XcmExecutor;
 
@@ -147,23 +152,61 @@ pub(crate) fn take_sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
 /// Sender that never returns error, always sends
 pub struct TestSendXcm;
 impl SendXcm for TestSendXcm {
-	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
-		SENT_XCM.with(|q| q.borrow_mut().push((dest.into(), msg)));
-		Ok(())
+	type Ticket = (MultiLocation, Xcm<()>);
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(MultiLocation, Xcm<()>)> {
+		let pair = (dest.take().unwrap(), msg.take().unwrap());
+		Ok((pair, DeliveryPrice::get()))
+	}
+	fn deliver(pair: (MultiLocation, Xcm<()>)) -> Result<XcmHash, SendError> {
+		let hash = pair.1.using_encoded(sp_io::hashing::blake2_256);
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok(hash)
+	}
+}
+impl InspectMessageQueues for TestSendXcm {
+	fn clear_messages() {
+		SENT_XCM.with(|q| q.borrow_mut().clear());
+	}
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		let mut messages: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> = Vec::new();
+		for (dest, msg) in sent_xcm() {
+			let (dest, msg) = (VersionedMultiLocation::from(dest), VersionedXcm::from(msg));
+			match messages.iter_mut().find(|(d, _)| d == &dest) {
+				Some((_, msgs)) => msgs.push(msg),
+				None => messages.push((dest, vec![msg])),
+			}
+		}
+		messages
 	}
 }
 /// Sender that returns error if `X8` junction and stops routing
 pub struct TestSendXcmErrX8;
 impl SendXcm for TestSendXcmErrX8 {
-	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
-		let dest = dest.into();
-		if dest.len() == 8 {
+	type Ticket = (MultiLocation, Xcm<()>);
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<(MultiLocation, Xcm<()>)> {
+		if dest.as_ref().ok_or(SendError::MissingArgument)?.len() == 8 {
 			Err(SendError::Transport("Destination location full"))
 		} else {
-			SENT_XCM.with(|q| q.borrow_mut().push((dest, msg)));
-			Ok(())
+			Err(SendError::NotApplicable)
 		}
 	}
+	fn deliver(pair: (MultiLocation, Xcm<()>)) -> Result<XcmHash, SendError> {
+		let hash = pair.1.using_encoded(sp_io::hashing::blake2_256);
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok(hash)
+	}
+}
+impl InspectMessageQueues for TestSendXcmErrX8 {
+	fn clear_messages() {}
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		Vec::new()
+	}
 }
 
 parameter_types! {
@@ -218,7 +261,7 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 	pub const RelayLocation: MultiLocation = Here.into();
 	pub const AnyNetwork: NetworkId = NetworkId::Any;
-	pub Ancestry: MultiLocation = Here.into();
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(AnyNetwork::get()));
 	pub UnitWeightCost: Weight = 1_000;
 }
 
@@ -257,7 +300,9 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = Case<TrustedAssets>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = XcmPallet;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	type Trader = FixedRateOfFungible<CurrencyPerSecond, ()>;
@@ -265,29 +310,48 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, AnyNetwork>;
 
 parameter_types! {
 	pub static AdvertisedXcmVersion: pallet_xcm::XcmVersion = 2;
+	pub static DeliveryPrice: MultiAssets = MultiAssets::new();
+	pub static SendLimit: Option<pallet_xcm::SendRateLimit> = None;
+	pub const TrappedAssetsExpiry: u64 = 10;
+	pub const TrapDeposit: Balance = 5;
+	pub TreasuryLocation: Option<MultiLocation> =
+		Some(Junction::AccountId32 { network: Any, id: [9u8; 32] }.into());
 }
 
+pub type XcmRouter = (TestSendXcmErrX8, TestSendXcm);
+
 impl pallet_xcm::Config for Test {
 	type Event = Event;
 	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
-	type XcmRouter = (TestSendXcmErrX8, TestSendXcm);
+	type XcmRouter = XcmRouter;
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<Origin, LocalOriginToLocation>;
 	type XcmExecuteFilter = Everything;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Origin = Origin;
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = AdvertisedXcmVersion;
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	type TreasuryLocation = TreasuryLocation;
+	type Currency = Balances;
+	type TrapDeposit = TrapDeposit;
+	type SovereignAccountOf = SovereignAccountOf;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = SendLimit;
+	type WeightInfo = pallet_xcm::TestWeightInfo;
 }
 
 impl origin::Config for Test {}
@@ -306,6 +370,17 @@ pub(crate) fn last_events(n: usize) -> Vec<Event> {
 	System::events().into_iter().map(|e| e.event).rev().take(n).rev().collect()
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_xcm::benchmarking::Config for Test {
+	fn teleportable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+		Some(((Here, 10).into(), Parachain(1000).into()))
+	}
+
+	fn reserve_transferable_asset_and_dest() -> Option<(MultiAsset, MultiLocation)> {
+		Some(((Here, 10).into(), Parachain(1000).into()))
+	}
+}
+
 pub(crate) fn buy_execution<C>(fees: impl Into<MultiAsset>) -> Instruction<C> {
 	use xcm::latest::prelude::*;
 	BuyExecution { fees: fees.into(), weight_limit: Unlimited }
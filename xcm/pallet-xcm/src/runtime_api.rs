@@ -0,0 +1,119 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definitions for querying the XCM pallet.
+
+use crate::TrappedAssets;
+use codec::{Codec, Decode, Encode};
+use frame_support::{dispatch::DispatchResultWithPostInfo, weights::Weight};
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use xcm::{latest::Outcome, VersionedMultiLocation, VersionedXcm};
+
+/// The effects of dry-running a call.
+#[derive(Encode, Decode, RuntimeDebug)]
+pub struct CallDryRunEffects<Event> {
+	/// The result of dispatching the call.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// The events which were emitted while dispatching the call.
+	pub emitted_events: Vec<Event>,
+	/// The messages which would be sent to other consensus systems, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// The effects of dry-running an incoming XCM.
+#[derive(Encode, Decode, RuntimeDebug)]
+pub struct XcmDryRunEffects<Event> {
+	/// The outcome of executing the message.
+	pub execution_result: Outcome,
+	/// The events which were emitted while executing the message.
+	pub emitted_events: Vec<Event>,
+	/// The messages which would be sent to other consensus systems, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// Reasons for which an XCM payment query could not be answered.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum XcmPaymentApiError {
+	/// A versioned argument could not be converted into the latest XCM version.
+	VersionedConversionFailed,
+	/// The weight of the message could not be computed.
+	WeightNotComputable,
+	/// The given asset is not accepted in payment for execution.
+	AssetNotFound,
+}
+
+/// Reasons for which a dry run could not be performed.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum DryRunError {
+	/// A versioned argument could not be converted into the latest XCM version.
+	VersionedConversionFailed,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying assets trapped by failed XCM executions, so that wallets may recover
+	/// them with the XCM pallet's `claim_assets` extrinsic.
+	pub trait XcmTrapApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The assets trapped by the execution of messages from `origin`, along with the hash
+		/// under which each is trapped.
+		fn trapped_assets(
+			origin: VersionedMultiLocation,
+		) -> Vec<(H256, TrappedAssets<AccountId, Balance, BlockNumber>)>;
+	}
+
+	/// The API for dry-running calls and incoming messages, so that wallets can show their
+	/// effects, including the messages they would forward, before anything is signed.
+	///
+	/// The state changes of a dry run are never committed.
+	pub trait DryRunApi<Call, Event, OriginCaller>
+	where
+		Call: Codec,
+		Event: Codec,
+		OriginCaller: Codec,
+	{
+		/// Dry-run `call` dispatched from `origin`.
+		fn dry_run_call(origin: OriginCaller, call: Call) -> CallDryRunEffects<Event>;
+
+		/// Dry-run the execution of `xcm` as if it were received from `origin_location`.
+		fn dry_run_xcm(
+			origin_location: VersionedMultiLocation,
+			xcm: VersionedXcm<Call>,
+		) -> Result<XcmDryRunEffects<Event>, DryRunError>;
+	}
+
+	/// The API for pricing the execution of XCMs on this chain.
+	///
+	/// Assets are identified by the concrete location of their `AssetId`.
+	pub trait XcmPaymentApi {
+		/// The assets which are accepted in payment for the execution of XCMs.
+		fn query_acceptable_payment_assets() -> Vec<VersionedMultiLocation>;
+
+		/// The amount of `asset` which must be paid for `weight` of execution.
+		fn query_weight_to_asset_fee(
+			weight: Weight,
+			asset: VersionedMultiLocation,
+		) -> Result<u128, XcmPaymentApiError>;
+
+		/// The weight of executing `message` on this chain.
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError>;
+	}
+}
@@ -15,19 +15,28 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, AssetTraps, CurrentMigration, Error, LatestVersionedMultiLocation, Queries,
-	QueryStatus, VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets,
+	mock::*, runtime_api::XcmPaymentApiError, AssetTrapDetails, AssetTraps, CurrentMigration,
+	Error, LatestVersionedMultiLocation, Queries, QueryStatus, QueryTimeouts, SendAllowance,
+	SendAllowances, SendRateLimit, TrapEntry, TrappedAssets, VersionDiscoveryQueue,
+	VersionNotifiers, VersionNotifyTargets,
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{Currency, Hooks},
+	traits::{Currency, Hooks, ReservableCurrency},
+	weights::WeightMeter,
 };
 use polkadot_parachain::primitives::{AccountIdConversion, Id as ParaId};
-use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_runtime::traits::{BlakeTwo256, Dispatchable, Hash};
 use std::convert::TryInto;
 use xcm::prelude::*;
 use xcm_builder::AllowKnownQueryResponses;
-use xcm_executor::{traits::ShouldExecute, XcmExecutor};
+use xcm_executor::{
+	traits::{
+		ticket_version, version_ticket, AnyVersionTicket, ClaimAssets, DropAssets, Properties,
+		QueryHandler, QueryResponseStatus, ShouldExecute,
+	},
+	XcmExecutor,
+};
 
 const ALICE: AccountId = AccountId::new([0u8; 32]);
 const BOB: AccountId = AccountId::new([1u8; 32]);
@@ -95,6 +104,45 @@ fn report_outcome_notify_works() {
 	});
 }
 
+/// Test that a notification heavier than the weight budgeted by the response is not dispatched.
+#[test]
+fn report_outcome_notify_should_not_exceed_budgeted_weight() {
+	let mut message = Xcm(vec![ClearOrigin]);
+	let call = pallet_test_notifier::Call::notification_received {
+		query_id: 0,
+		response: Default::default(),
+	};
+	let notify = Call::TestNotifier(call);
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		XcmPallet::report_outcome_notify(&mut message, Parachain(PARA_ID).into(), notify, 100)
+			.unwrap();
+		assert_eq!(
+			message.0[0],
+			SetAppendix(Xcm(vec![ReportError {
+				query_id: 0,
+				dest: Parent.into(),
+				max_response_weight: 1_000_000
+			}]))
+		);
+
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(
+			Parachain(PARA_ID).into(),
+			Xcm(vec![QueryResponse {
+				query_id: 0,
+				response: Response::ExecutionResult(None),
+				max_weight: 999_999,
+			}]),
+			1_000_000_000,
+		);
+		assert_eq!(r, Outcome::Complete(1_000));
+		assert_eq!(
+			last_event(),
+			Event::XcmPallet(crate::Event::NotifyOverweight(0, 4, 2, 1_000_000, 999_999))
+		);
+		assert_eq!(Queries::<Test>::iter().collect::<Vec<_>>(), vec![]);
+	});
+}
+
 #[test]
 fn report_outcome_works() {
 	let balances =
@@ -144,6 +192,103 @@ fn report_outcome_works() {
 	});
 }
 
+#[test]
+fn stale_queries_should_time_out() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let notify = Call::TestNotifier(pallet_test_notifier::Call::notification_received {
+			query_id: 1,
+			response: Default::default(),
+		});
+		let plain = XcmPallet::new_query(Parachain(PARA_ID), 10);
+		let notified = XcmPallet::new_notify_query(Parachain(PARA_ID), notify, 10);
+		let answered = XcmPallet::new_query(Parachain(PARA_ID), 10);
+
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(
+			Parachain(PARA_ID).into(),
+			Xcm(vec![QueryResponse {
+				query_id: answered,
+				response: Response::ExecutionResult(None),
+				max_weight: 0,
+			}]),
+			1_000_000_000,
+		);
+		assert_eq!(r, Outcome::Complete(1_000));
+
+		// Nothing expires until the block after the timeout.
+		XcmPallet::on_initialize(10);
+		assert!(matches!(XcmPallet::query(plain), Some(QueryStatus::Pending { .. })));
+		assert_eq!(XcmPallet::take_timeout(plain), None);
+
+		XcmPallet::on_initialize(11);
+		let events = last_events(2);
+		assert!(events.contains(&Event::XcmPallet(crate::Event::QueryTimedOut(plain))));
+		assert!(events.contains(&Event::XcmPallet(crate::Event::QueryTimedOut(notified))));
+		assert_eq!(XcmPallet::query(plain), Some(QueryStatus::Timeout { at: 11 }));
+		assert_eq!(XcmPallet::query(notified), None);
+		assert!(matches!(XcmPallet::query(answered), Some(QueryStatus::Ready { .. })));
+		assert_eq!(QueryTimeouts::<Test>::iter().count(), 0);
+
+		// A late response is no longer accepted.
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(
+			Parachain(PARA_ID).into(),
+			Xcm(vec![QueryResponse {
+				query_id: plain,
+				response: Response::ExecutionResult(None),
+				max_weight: 0,
+			}]),
+			1_000_000_000,
+		);
+		assert_eq!(r, Outcome::Error(XcmError::Barrier));
+		assert_eq!(XcmPallet::take_response(plain), None);
+
+		assert_eq!(XcmPallet::take_timeout(plain), Some(11));
+		assert_eq!(XcmPallet::query(plain), None);
+		assert_eq!(XcmPallet::take_response(answered), Some((Response::ExecutionResult(None), 1)));
+	});
+}
+
+#[test]
+fn query_handler_tracks_query_status() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let answered = <XcmPallet as QueryHandler>::new_query(Parachain(PARA_ID), 10);
+		let unanswered = <XcmPallet as QueryHandler>::new_query(Parachain(PARA_ID), 10);
+		assert_eq!(
+			<XcmPallet as QueryHandler>::take_response(answered),
+			QueryResponseStatus::Pending { timeout: 10 }
+		);
+
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(
+			Parachain(PARA_ID).into(),
+			Xcm(vec![QueryResponse {
+				query_id: answered,
+				response: Response::ExecutionResult(None),
+				max_weight: 0,
+			}]),
+			1_000_000_000,
+		);
+		assert_eq!(r, Outcome::Complete(1_000));
+		XcmPallet::on_initialize(11);
+
+		assert_eq!(
+			<XcmPallet as QueryHandler>::take_response(answered),
+			QueryResponseStatus::Ready { response: Response::ExecutionResult(None), at: 1 }
+		);
+		assert_eq!(
+			<XcmPallet as QueryHandler>::take_response(unanswered),
+			QueryResponseStatus::TimedOut { at: 11 }
+		);
+		// Taking a response removes the query.
+		assert_eq!(
+			<XcmPallet as QueryHandler>::take_response(answered),
+			QueryResponseStatus::NotFound
+		);
+		assert_eq!(
+			<XcmPallet as QueryHandler>::take_response(unanswered),
+			QueryResponseStatus::NotFound
+		);
+	});
+}
+
 /// Test sending an `XCM` message (`XCM::ReserveAssetDeposit`)
 ///
 /// Asserts that the expected message is sent and the event is emitted
@@ -180,6 +325,123 @@ fn send_works() {
 	});
 }
 
+/// Test that dry-running `send` reports the message it would forward without needing it to be
+/// delivered beforehand.
+#[test]
+fn dry_run_send_reports_forwarded_message() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: MultiLocation =
+			AccountId32 { network: AnyNetwork::get(), id: ALICE.into() }.into();
+		let message = Xcm(vec![ClearOrigin]);
+		let call = Call::XcmPallet(crate::Call::send {
+			dest: Box::new(RelayLocation::get().into()),
+			message: Box::new(VersionedXcm::from(message.clone())),
+		});
+		// A message sent before the dry run is not part of its effects.
+		assert_ok!(send_xcm::<XcmRouter>(Parachain(PARA_ID).into(), Xcm(vec![Trap(0)])));
+
+		let effects = XcmPallet::dry_run_call::<XcmRouter>(Origin::signed(ALICE), call);
+		assert_ok!(effects.execution_result);
+		let forwarded = Xcm(vec![DescendOrigin(sender.clone().try_into().unwrap()), ClearOrigin]);
+		assert_eq!(
+			effects.forwarded_xcms,
+			vec![(Here.into(), vec![VersionedXcm::from(forwarded)])]
+		);
+		assert_eq!(
+			effects.emitted_events.last(),
+			Some(&Event::XcmPallet(crate::Event::Sent(sender, RelayLocation::get(), message)))
+		);
+	});
+}
+
+/// Test that the weight of a message is reported as the configured weigher computes it.
+#[test]
+fn query_xcm_weight_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let message = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin, ClearOrigin]));
+		assert_eq!(XcmPallet::query_xcm_weight(message), Ok(2 * BaseXcmWeight::get()));
+
+		let too_long = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin; 101]));
+		assert_eq!(
+			XcmPallet::query_xcm_weight(too_long),
+			Err(XcmPaymentApiError::WeightNotComputable)
+		);
+	});
+}
+
+/// Test that `send` is throttled once the origin has exhausted its allowance, and that the
+/// allowance is replenished over time.
+#[test]
+fn send_is_rate_limited() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		SendLimit::set(Some(SendRateLimit {
+			max_messages: 2,
+			max_bytes: 1_000,
+			messages_per_block: 1,
+			bytes_per_block: 1_000,
+		}));
+		let alice: MultiLocation =
+			AccountId32 { network: AnyNetwork::get(), id: ALICE.into() }.into();
+		let send = |who: AccountId| {
+			Call::XcmPallet(crate::Call::send {
+				dest: Box::new(RelayLocation::get().into()),
+				message: Box::new(VersionedXcm::from(Xcm(vec![ClearOrigin]))),
+			})
+			.dispatch(Origin::signed(who))
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+
+		assert_ok!(send(ALICE));
+		assert_ok!(send(ALICE));
+		let events = System::events().len();
+		assert_eq!(send(ALICE), Err(Error::<Test>::Throttled.into()));
+		// A throttled message is neither sent nor recorded.
+		assert_eq!(System::events().len(), events);
+		assert_eq!(sent_xcm().len(), 2);
+		// Other origins have their own allowance.
+		assert_ok!(send(BOB));
+
+		System::set_block_number(2);
+		assert_ok!(send(ALICE));
+		assert_eq!(
+			SendAllowances::<Test>::get(LatestVersionedMultiLocation(&alice)),
+			Some(SendAllowance { messages: 0, bytes: 998, updated: 2 })
+		);
+		assert_eq!(sent_xcm().len(), 4);
+	});
+}
+
+/// Test that the price for delivering a message is paid by the origin of `send`.
+///
+/// Asserts that `send` fails with `Error::FeesNotMet` when the origin cannot pay the price.
+#[test]
+fn send_charges_delivery_fees() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let message = Xcm(vec![ClearOrigin]);
+		let versioned_dest: Box<VersionedMultiLocation> = Box::new(RelayLocation::get().into());
+		let versioned_message = Box::new(VersionedXcm::from(message));
+
+		DeliveryPrice::set((Here, SEND_AMOUNT).into());
+		assert_ok!(XcmPallet::send(
+			Origin::signed(ALICE),
+			versioned_dest.clone(),
+			versioned_message.clone()
+		));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		assert_eq!(sent_xcm().len(), 1);
+
+		DeliveryPrice::set((Here, INITIAL_BALANCE).into());
+		assert_noop!(
+			XcmPallet::send(Origin::signed(ALICE), versioned_dest, versioned_message),
+			crate::Error::<Test>::FeesNotMet
+		);
+	});
+}
+
 /// Test that sending an `XCM` message fails when the `XcmRouter` blocks the
 /// matching message format
 ///
@@ -499,6 +761,103 @@ fn execute_withdraw_to_deposit_works() {
 	});
 }
 
+#[test]
+fn force_execute_works_as_arbitrary_location() {
+	let balances = vec![(ParaId::from(PARA_ID).into_account(), INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let weight = 2 * BaseXcmWeight::get();
+		let dest: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: BOB.into() }.into();
+		let location: MultiLocation = Parachain(PARA_ID).into();
+		let message = Xcm(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: dest },
+		]);
+		assert_noop!(
+			XcmPallet::force_execute(
+				Origin::signed(ALICE),
+				Box::new(location.clone().into()),
+				Box::new(VersionedXcm::from(message.clone())),
+				weight
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(XcmPallet::force_execute(
+			Origin::root(),
+			Box::new(location.into()),
+			Box::new(VersionedXcm::from(message)),
+			weight
+		));
+		assert_eq!(
+			Balances::total_balance(&ParaId::from(PARA_ID).into_account()),
+			INITIAL_BALANCE - SEND_AMOUNT
+		);
+		assert_eq!(Balances::total_balance(&BOB), SEND_AMOUNT);
+		assert_eq!(
+			last_event(),
+			Event::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
+		);
+	});
+}
+
+#[test]
+fn execution_can_be_suspended_and_resumed() {
+	let balances = vec![(ParaId::from(PARA_ID).into_account(), INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let message = || {
+			Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				DepositAsset {
+					assets: All.into(),
+					max_assets: 1,
+					beneficiary: Parachain(1).into(),
+				},
+			])
+		};
+		let weight = 3 * BaseXcmWeight::get();
+		let para = MultiLocation::from(Parachain(PARA_ID));
+
+		assert_noop!(
+			XcmPallet::force_suspend_execution(Origin::signed(ALICE), None),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+
+		// Suspending a single origin only affects that origin.
+		assert_ok!(XcmPallet::force_suspend_execution(
+			Origin::root(),
+			Some(Box::new(para.clone().into())),
+		));
+		assert_eq!(
+			last_event(),
+			Event::XcmPallet(crate::Event::ExecutionSuspended(Some(para.clone())))
+		);
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(para.clone(), message(), weight);
+		assert_eq!(r, Outcome::Error(XcmError::Barrier));
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(Parachain(PARA_ID + 1), message(), weight);
+		assert_ne!(r, Outcome::Error(XcmError::Barrier));
+
+		// Lifting the global suspension keeps the per-origin suspension in place.
+		assert_ok!(XcmPallet::force_suspend_execution(Origin::root(), None));
+		assert_ok!(XcmPallet::force_resume_execution(Origin::root(), None));
+		assert_eq!(last_event(), Event::XcmPallet(crate::Event::ExecutionResumed(None)));
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(para.clone(), message(), weight);
+		assert_eq!(r, Outcome::Error(XcmError::Barrier));
+
+		assert_ok!(XcmPallet::force_resume_execution(
+			Origin::root(),
+			Some(Box::new(para.clone().into())),
+		));
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(para.clone(), message(), weight);
+		assert_eq!(r, Outcome::Complete(weight));
+
+		// A global suspension affects everyone.
+		assert_ok!(XcmPallet::force_suspend_execution(Origin::root(), None));
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(para, message(), weight);
+		assert_eq!(r, Outcome::Error(XcmError::Barrier));
+	});
+}
+
 /// Test drop/claim assets.
 #[test]
 fn trapped_assets_can_be_claimed() {
@@ -577,6 +936,212 @@ fn trapped_assets_can_be_claimed() {
 	});
 }
 
+/// Claim tickets are interpreted by version, so assets trapped in an older XCM version can still
+/// be claimed.
+#[test]
+fn trapped_assets_can_be_claimed_across_versions() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let assets: MultiAssets = (Here, SEND_AMOUNT).into();
+		// Assets trapped while XCM v0 was the latest version are identified in that version.
+		let vma = VersionedMultiAssets::from(assets.clone()).into_version(0).unwrap();
+		let hash = BlakeTwo256::hash_of(&(source.clone(), vma));
+		AssetTraps::<Test>::insert(hash, 3);
+
+		assert_eq!(ticket_version(&Here.into()), Some(XCM_VERSION));
+		assert_eq!(ticket_version(&GeneralIndex(0).into()), Some(0));
+		assert_eq!(ticket_version(&GeneralIndex(u32::MAX as u128 + 1).into()), None);
+		assert_eq!(ticket_version(&Parent.into()), None);
+		assert_eq!(version_ticket(0), GeneralIndex(0).into());
+		assert_eq!(version_ticket(XCM_VERSION), Here.into());
+
+		// The pallet only matches a ticket of the version in which the assets were trapped...
+		assert!(!<XcmPallet as ClaimAssets>::claim_assets(&source, &Here.into(), &assets));
+		assert!(!<XcmPallet as ClaimAssets>::claim_assets(
+			&source,
+			&GeneralIndex(1).into(),
+			&assets
+		));
+		assert!(<XcmPallet as ClaimAssets>::claim_assets(
+			&source,
+			&GeneralIndex(0).into(),
+			&assets
+		));
+		assert_eq!(AssetTraps::<Test>::get(hash), 2);
+
+		// ...while wrapped in `AnyVersionTicket` any canonical ticket will do.
+		type AnyTicket = AnyVersionTicket<XcmPallet>;
+		assert!(AnyTicket::claim_assets(&source, &Here.into(), &assets));
+		assert!(AnyTicket::claim_assets(&source, &GeneralIndex(1).into(), &assets));
+		assert_eq!(AssetTraps::<Test>::get(hash), 0);
+		assert!(!AnyTicket::claim_assets(&source, &Here.into(), &assets));
+
+		// Non-canonical tickets never match.
+		AssetTraps::<Test>::insert(hash, 1);
+		assert!(!AnyTicket::claim_assets(&source, &Parent.into(), &assets));
+		assert!(!AnyTicket::claim_assets(
+			&source,
+			&GeneralIndex(u32::MAX as u128 + 1).into(),
+			&assets
+		));
+		assert_eq!(AssetTraps::<Test>::get(hash), 1);
+	});
+}
+
+#[test]
+fn trapped_assets_can_be_claimed_and_swept_by_extrinsic() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let dest: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: BOB.into() }.into();
+		let trap_assets = || {
+			assert_ok!(XcmPallet::execute(
+				Origin::signed(ALICE),
+				Box::new(VersionedXcm::from(Xcm(vec![
+					WithdrawAsset((Here, SEND_AMOUNT).into()),
+					buy_execution((Here, SEND_AMOUNT)),
+					Trap(0),
+				]))),
+				3 * BaseXcmWeight::get()
+			));
+		};
+		let vma = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), vma.clone()));
+
+		let expiries = || {
+			AssetTrapDetails::<Test>::get(hash)
+				.map_or(vec![], |t| t.entries.into_iter().map(|e| e.expiry).collect::<Vec<_>>())
+		};
+
+		trap_assets();
+		assert_eq!(AssetTraps::<Test>::get(hash), 1);
+		assert_eq!(expiries(), vec![11]);
+		assert_eq!(Balances::reserved_balance(&ALICE), TrapDeposit::get());
+
+		// The trapped assets can be looked up by their origin.
+		let trapped = XcmPallet::trapped_assets(&source);
+		assert_eq!(trapped.len(), 1);
+		assert_eq!(trapped[0].0, hash);
+		assert_eq!(trapped[0].1.assets, vma);
+		assert!(XcmPallet::trapped_assets(&dest).is_empty());
+
+		// The trapping origin may claim its assets to any beneficiary, getting its deposit back.
+		assert_ok!(XcmPallet::claim_assets(
+			Origin::signed(ALICE),
+			Box::new(vma.clone()),
+			Box::new(dest.clone().into()),
+		));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(AssetTraps::<Test>::get(hash), 0);
+		assert!(AssetTrapDetails::<Test>::get(hash).is_none());
+
+		// Once claimed, the assets cannot be swept.
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(Origin::signed(BOB), hash),
+			Error::<Test>::UnknownTrap
+		);
+
+		// Each trap of the same assets keeps its own expiry and deposit.
+		trap_assets();
+		System::set_block_number(5);
+		trap_assets();
+		assert_eq!(AssetTraps::<Test>::get(hash), 2);
+		assert_eq!(expiries(), vec![11, 15]);
+		assert_eq!(Balances::reserved_balance(&ALICE), 2 * TrapDeposit::get());
+
+		// Nobody may sweep the assets before they expire.
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(Origin::signed(BOB), hash),
+			Error::<Test>::TrapNotExpired
+		);
+
+		// Only the expired trap is swept.
+		System::set_block_number(12);
+		assert_ok!(XcmPallet::sweep_trapped_assets(Origin::signed(BOB), hash));
+		let treasury = AccountId::new([9u8; 32]);
+		assert_eq!(Balances::total_balance(&treasury), SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::get(hash), 1);
+		assert_eq!(expiries(), vec![15]);
+		assert_eq!(Balances::reserved_balance(&ALICE), TrapDeposit::get());
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(Origin::signed(BOB), hash),
+			Error::<Test>::TrapNotExpired
+		);
+
+		System::set_block_number(16);
+		assert_ok!(XcmPallet::sweep_trapped_assets(Origin::signed(BOB), hash));
+		assert_eq!(Balances::total_balance(&treasury), 2 * SEND_AMOUNT);
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(AssetTraps::<Test>::get(hash), 0);
+		assert!(AssetTrapDetails::<Test>::get(hash).is_none());
+	});
+}
+
+#[test]
+fn traps_without_deposit_are_listed_but_not_sweepable() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		// The sovereign account of this parachain has no funds to reserve a deposit from.
+		let source: MultiLocation = Parachain(2000).into();
+		let assets = MultiAssets::from((Here, SEND_AMOUNT));
+		let vma = VersionedMultiAssets::from(assets.clone());
+		let hash = BlakeTwo256::hash_of(&(source.clone(), vma.clone()));
+		<XcmPallet as DropAssets>::drop_assets(
+			&source,
+			assets.into(),
+			&XcmContext { origin: Some(source.clone()), message_id: [0; 32], topic: None },
+			&mut WeightMeter::max_limit(),
+		);
+
+		let trapped = XcmPallet::trapped_assets(&source);
+		assert_eq!(trapped.len(), 1);
+		assert_eq!(trapped[0].0, hash);
+		assert_eq!(trapped[0].1.assets, vma);
+		assert!(trapped[0].1.entries.is_empty());
+
+		System::set_block_number(100);
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(Origin::signed(ALICE), hash),
+			Error::<Test>::UnknownTrap
+		);
+	});
+}
+
+#[test]
+fn failed_sweep_changes_nothing() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let vma = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), vma.clone()));
+		assert_ok!(Balances::reserve(&ALICE, TrapDeposit::get()));
+		// A sweepable entry for which there is no trap to claim from.
+		AssetTrapDetails::<Test>::insert(
+			hash,
+			TrappedAssets {
+				origin: source.into(),
+				assets: vma,
+				entries: vec![TrapEntry {
+					expiry: 1,
+					depositor: ALICE,
+					deposit: TrapDeposit::get(),
+				}],
+			},
+		);
+
+		System::set_block_number(2);
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(Origin::signed(ALICE), hash),
+			Error::<Test>::SweepFailed
+		);
+	});
+}
+
 #[test]
 fn fake_latest_versioned_multilocation_works() {
 	use codec::Encode;
@@ -623,7 +1188,7 @@ fn basic_subscription_works() {
 			&remote,
 			&mut message,
 			weight,
-			&mut 0
+			&mut Properties { weight_credit: 0, message_id: None },
 		));
 	});
 }
@@ -784,9 +1349,9 @@ fn subscription_side_upgrades_work_with_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -813,9 +1378,9 @@ fn subscription_side_upgrades_work_without_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -843,20 +1408,20 @@ fn subscriber_side_subscription_works() {
 		assert_eq!(take_sent_xcm(), vec![]);
 
 		// This message cannot be sent to a v1 remote.
-		let v2_msg = Xcm::<()>(vec![Trap(0)]);
-		assert_eq!(XcmPallet::wrap_version(&remote, v2_msg.clone()), Err(()));
+		let v3_msg = Xcm::<()>(vec![Trap(0)]);
+		assert_eq!(XcmPallet::wrap_version(&remote, v3_msg.clone()), Err(()));
 
 		let message = Xcm(vec![
-			// Remote upgraded to XCM v2
-			QueryResponse { query_id: 0, max_weight: 0, response: Response::Version(2) },
+			// Remote upgraded to XCM v3
+			QueryResponse { query_id: 0, max_weight: 0, response: Response::Version(3) },
 		]);
 		let r = XcmExecutor::<XcmConfig>::execute_xcm(remote.clone(), message, weight);
 		assert_eq!(r, Outcome::Complete(weight));
 
-		// This message can now be sent to remote as it's v2.
+		// This message can now be sent to remote as it's v3.
 		assert_eq!(
-			XcmPallet::wrap_version(&remote, v2_msg.clone()),
-			Ok(VersionedXcm::from(v2_msg))
+			XcmPallet::wrap_version(&remote, v3_msg.clone()),
+			Ok(VersionedXcm::from(v3_msg))
 		);
 	});
 }
@@ -875,17 +1440,17 @@ fn auto_subscription_works() {
 			query_id: 1,
 			response: xcm::v1::Response::Assets(vec![].into()),
 		};
-		let v2_msg = Xcm::<()>(vec![Trap(0)]);
+		let v3_msg = Xcm::<()>(vec![Trap(0)]);
 		assert_eq!(
 			XcmPallet::wrap_version(&remote0, v1_msg.clone()),
 			Ok(VersionedXcm::from(v1_msg.clone())),
 		);
-		assert_eq!(XcmPallet::wrap_version(&remote0, v2_msg.clone()), Err(()));
+		assert_eq!(XcmPallet::wrap_version(&remote0, v3_msg.clone()), Err(()));
 		let expected = vec![(remote0.clone().into(), 2)];
 		assert_eq!(VersionDiscoveryQueue::<Test>::get().into_inner(), expected);
 
-		assert_eq!(XcmPallet::wrap_version(&remote0, v2_msg.clone()), Err(()));
-		assert_eq!(XcmPallet::wrap_version(&remote1, v2_msg.clone()), Err(()));
+		assert_eq!(XcmPallet::wrap_version(&remote0, v3_msg.clone()), Err(()));
+		assert_eq!(XcmPallet::wrap_version(&remote1, v3_msg.clone()), Err(()));
 		let expected = vec![(remote0.clone().into(), 3), (remote1.clone().into(), 1)];
 		assert_eq!(VersionDiscoveryQueue::<Test>::get().into_inner(), expected);
 
@@ -898,20 +1463,20 @@ fn auto_subscription_works() {
 			)]
 		);
 
-		// Assume remote0 is working ok and XCM version 2.
+		// Assume remote0 is working ok and XCM version 3.
 
 		let weight = BaseXcmWeight::get();
 		let message = Xcm(vec![
-			// Remote supports XCM v2
-			QueryResponse { query_id: 0, max_weight: 0, response: Response::Version(2) },
+			// Remote supports XCM v3
+			QueryResponse { query_id: 0, max_weight: 0, response: Response::Version(3) },
 		]);
 		let r = XcmExecutor::<XcmConfig>::execute_xcm(remote0.clone(), message, weight);
 		assert_eq!(r, Outcome::Complete(weight));
 
-		// This message can now be sent to remote0 as it's v2.
+		// This message can now be sent to remote0 as it's v3.
 		assert_eq!(
-			XcmPallet::wrap_version(&remote0, v2_msg.clone()),
-			Ok(VersionedXcm::from(v2_msg.clone()))
+			XcmPallet::wrap_version(&remote0, v3_msg.clone()),
+			Ok(VersionedXcm::from(v3_msg.clone()))
 		);
 
 		XcmPallet::on_initialize(2);
@@ -933,9 +1498,44 @@ fn auto_subscription_works() {
 		let r = XcmExecutor::<XcmConfig>::execute_xcm(remote1.clone(), message, weight);
 		assert_eq!(r, Outcome::Complete(weight));
 
-		// v2 messages cannot be sent to remote1...
+		// v3 messages cannot be sent to remote1...
 		assert_eq!(XcmPallet::wrap_version(&remote1, v1_msg.clone()), Ok(VersionedXcm::V1(v1_msg)));
-		assert_eq!(XcmPallet::wrap_version(&remote1, v2_msg.clone()), Err(()));
+		assert_eq!(XcmPallet::wrap_version(&remote1, v3_msg.clone()), Err(()));
+	})
+}
+
+/// A destination whose version we have already requested should not be queued for discovery again.
+#[test]
+fn pending_subscription_should_not_be_requeued() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let remote = Parachain(1000).into();
+		let msg = Xcm::<()>(vec![Trap(0)]);
+		// Unknown destinations get the safe XCM version.
+		let v2_msg = xcm::v2::Xcm::<()>(vec![xcm::v2::Instruction::Trap(0)]);
+
+		assert_eq!(
+			XcmPallet::wrap_version(&remote, msg.clone()),
+			Ok(VersionedXcm::from(v2_msg.clone()))
+		);
+		XcmPallet::on_initialize(1);
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				remote.clone(),
+				Xcm(vec![SubscribeVersion { query_id: 0, max_response_weight: 0 }]),
+			)]
+		);
+		assert_eq!(VersionDiscoveryQueue::<Test>::get().into_inner(), vec![]);
+
+		// No response yet, so the version is still unknown, but we are already waiting for it.
+		assert_eq!(
+			XcmPallet::wrap_version(&remote, msg.clone()),
+			Ok(VersionedXcm::from(v2_msg.clone()))
+		);
+		assert_eq!(VersionDiscoveryQueue::<Test>::get().into_inner(), vec![]);
+
+		XcmPallet::on_initialize(2);
+		assert_eq!(take_sent_xcm(), vec![]);
 	})
 }
 
@@ -989,9 +1589,9 @@ fn subscription_side_upgrades_work_with_multistage_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -20,7 +20,9 @@ use proc_macro::TokenStream;
 
 mod v0;
 mod v1;
+mod v3;
 mod weight_info;
+mod weights_from_benchmarks;
 
 #[proc_macro]
 pub fn impl_conversion_functions_for_multilocation_v0(input: TokenStream) -> TokenStream {
@@ -36,7 +38,46 @@ pub fn impl_conversion_functions_for_multilocation_v1(input: TokenStream) -> Tok
 		.into()
 }
 
+#[proc_macro]
+pub fn impl_conversion_functions_for_multilocation_v3(input: TokenStream) -> TokenStream {
+	v3::multilocation::generate_conversion_functions(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
 #[proc_macro_derive(XcmWeightInfoTrait)]
 pub fn derive_xcm_weight_info(item: TokenStream) -> TokenStream {
 	weight_info::derive(item)
 }
+
+/// Complete an `XcmWeightInfo` implementation from the output of `pallet-xcm-benchmarks`.
+///
+/// Each instruction is weighed by the benchmark of the same name: fungible benchmarks are scaled
+/// by the instruction's assets with `weigh_assets`, generic ones are taken as they are, and
+/// instructions without a benchmark weigh `Weight::MAX`. Methods given in the annotated `impl`
+/// block are kept as they are. The benchmark output files, relative to the crate root, must be
+/// declared as modules named after them alongside the `impl` block.
+///
+/// ```ignore
+/// #[xcm::weights_from_benchmarks(
+///     runtime = Runtime,
+///     fungible = "src/weights/xcm/pallet_xcm_benchmarks_fungible.rs",
+///     generic = "src/weights/xcm/pallet_xcm_benchmarks_generic.rs",
+///     weigh_assets = weigh_multi_assets,
+/// )]
+/// impl<Call> XcmWeightInfo<Call> for RuntimeXcmWeight<Call> {}
+/// ```
+#[proc_macro_attribute]
+pub fn weights_from_benchmarks(attr: TokenStream, item: TokenStream) -> TokenStream {
+	weights_from_benchmarks::attribute(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn impl_weights_from_benchmarks(input: TokenStream) -> TokenStream {
+	weights_from_benchmarks::implement(input.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
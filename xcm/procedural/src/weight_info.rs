@@ -47,11 +47,22 @@ pub fn derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
 					});
 				quote::quote!(fn #snake_cased_ident( #(#ref_fields)* ) -> Weight;)
 			});
+			let methods = methods.collect::<Vec<_>>();
 
+			// The signatures are also handed to `weights_from_benchmarks`, which cannot see this
+			// enum from the runtime crates, by way of a callback macro.
 			let res = quote::quote! {
 				pub trait XcmWeightInfo #generics {
 					#(#methods)*
 				}
+
+				#[doc(hidden)]
+				#[macro_export]
+				macro_rules! __xcm_weight_info_signatures {
+					([$($callback:tt)*] $($input:tt)*) => {
+						$($callback)*! { $($input)* signatures { #(#methods)* } }
+					};
+				}
 			};
 			res.into()
 		},
@@ -0,0 +1,279 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::{collections::BTreeMap, path::PathBuf};
+use syn::{
+	parse::{Parse, ParseStream},
+	Error, Ident, LitStr, Result, Token,
+};
+
+mod keyword {
+	syn::custom_keyword!(runtime);
+	syn::custom_keyword!(fungible);
+	syn::custom_keyword!(generic);
+	syn::custom_keyword!(weigh_assets);
+	syn::custom_keyword!(config);
+	syn::custom_keyword!(implementation);
+	syn::custom_keyword!(signatures);
+}
+
+/// The arguments of the `weights_from_benchmarks` attribute.
+struct Config {
+	/// The runtime with which the benchmarked weight functions are instantiated.
+	runtime: syn::Type,
+	/// The output of the `pallet_xcm_benchmarks::fungible` benchmarks.
+	fungible: LitStr,
+	/// The output of the `pallet_xcm_benchmarks::generic` benchmarks.
+	generic: LitStr,
+	/// The method which scales a fungible benchmark by the assets of an instruction.
+	weigh_assets: Ident,
+}
+
+impl Parse for Config {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let (mut runtime, mut fungible, mut generic, mut weigh_assets) = (None, None, None, None);
+		while !input.is_empty() {
+			let lookahead = input.lookahead1();
+			if lookahead.peek(keyword::runtime) {
+				input.parse::<keyword::runtime>()?;
+				input.parse::<Token![=]>()?;
+				runtime = Some(input.parse()?);
+			} else if lookahead.peek(keyword::fungible) {
+				input.parse::<keyword::fungible>()?;
+				input.parse::<Token![=]>()?;
+				fungible = Some(input.parse()?);
+			} else if lookahead.peek(keyword::generic) {
+				input.parse::<keyword::generic>()?;
+				input.parse::<Token![=]>()?;
+				generic = Some(input.parse()?);
+			} else if lookahead.peek(keyword::weigh_assets) {
+				input.parse::<keyword::weigh_assets>()?;
+				input.parse::<Token![=]>()?;
+				weigh_assets = Some(input.parse()?);
+			} else {
+				return Err(lookahead.error())
+			}
+			if !input.is_empty() {
+				input.parse::<Token![,]>()?;
+			}
+		}
+		let missing = |name| Error::new(Span::call_site(), format!("Missing argument `{}`", name));
+		Ok(Self {
+			runtime: runtime.ok_or_else(|| missing("runtime"))?,
+			fungible: fungible.ok_or_else(|| missing("fungible"))?,
+			generic: generic.ok_or_else(|| missing("generic"))?,
+			weigh_assets: weigh_assets.ok_or_else(|| missing("weigh_assets"))?,
+		})
+	}
+}
+
+/// The input of the worker macro, as assembled by `__xcm_weight_info_signatures`.
+struct Input {
+	config: Config,
+	implementation: syn::ItemImpl,
+	signatures: Vec<syn::TraitItemMethod>,
+}
+
+impl Parse for Input {
+	fn parse(input: ParseStream) -> Result<Self> {
+		input.parse::<keyword::config>()?;
+		let content;
+		syn::braced!(content in input);
+		let config = content.parse()?;
+
+		input.parse::<keyword::implementation>()?;
+		let content;
+		syn::braced!(content in input);
+		let implementation = content.parse()?;
+
+		input.parse::<keyword::signatures>()?;
+		let content;
+		syn::braced!(content in input);
+		let mut signatures = Vec::new();
+		while !content.is_empty() {
+			signatures.push(content.parse()?);
+		}
+
+		Ok(Self { config, implementation, signatures })
+	}
+}
+
+pub fn attribute(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	// Parse everything up front so that errors point at the user's code rather than at the
+	// expansion of the callback.
+	syn::parse2::<Config>(attr.clone())?;
+	syn::parse2::<syn::ItemImpl>(item.clone())?;
+
+	Ok(quote! {
+		::xcm::__xcm_weight_info_signatures! {
+			[::xcm::__impl_weights_from_benchmarks]
+			config { #attr }
+			implementation { #item }
+		}
+	})
+}
+
+pub fn implement(input: TokenStream) -> Result<TokenStream> {
+	let Input { config, mut implementation, signatures } = syn::parse2(input)?;
+	let Config { runtime, fungible, generic, weigh_assets } = config;
+	let (fungible_path, fungible_weights) = benchmarks(&fungible)?;
+	let (generic_path, generic_weights) = benchmarks(&generic)?;
+	let fungible_module = module_name(&fungible)?;
+	let generic_module = module_name(&generic)?;
+
+	let mut overrides = BTreeMap::new();
+	for item in implementation.items.drain(..) {
+		match item {
+			syn::ImplItem::Method(method) => {
+				let name = method.sig.ident.clone();
+				if !signatures.iter().any(|s| s.sig.ident == name) {
+					let msg = format!("`{}` does not weigh any XCM instruction", name);
+					return Err(Error::new(name.span(), msg))
+				}
+				overrides.insert(name.to_string(), method);
+			},
+			item => return Err(Error::new_spanned(item, "Only methods may be given by hand")),
+		}
+	}
+
+	for signature in signatures {
+		let name = signature.sig.ident.clone();
+		if let Some(method) = overrides.remove(&name.to_string()) {
+			implementation.items.push(syn::ImplItem::Method(method));
+			continue
+		}
+
+		let mut sig = signature.sig;
+		let assets = sig.inputs.iter().position(is_assets);
+		let body = if let Some(takes_components) = fungible_weights.get(&name.to_string()) {
+			check_components(*takes_components, &name, &fungible)?;
+			let weight = quote!(#fungible_module::WeightInfo::<#runtime>::#name());
+			match assets {
+				Some(_) => quote!(assets.#weigh_assets(#weight)),
+				None => weight,
+			}
+		} else if let Some(takes_components) = generic_weights.get(&name.to_string()) {
+			check_components(*takes_components, &name, &generic)?;
+			quote!(#generic_module::WeightInfo::<#runtime>::#name())
+		} else {
+			// Instructions without a benchmark cannot be executed at any cost.
+			quote!(Weight::MAX)
+		};
+		let uses_assets = assets.filter(|_| fungible_weights.contains_key(&name.to_string()));
+
+		for (index, input) in sig.inputs.iter_mut().enumerate() {
+			if let syn::FnArg::Typed(pat_type) = input {
+				*pat_type.pat = if Some(index) == uses_assets {
+					syn::parse_quote!(assets)
+				} else {
+					syn::parse_quote!(_)
+				};
+			}
+		}
+		implementation.items.push(syn::parse_quote!(#sig { #body }));
+	}
+
+	Ok(quote! {
+		const _: () = {
+			use ::xcm::{latest::*, DoubleEncoded};
+
+			// Regenerate whenever the benchmark output changes.
+			const _: &[u8] = include_bytes!(#fungible_path);
+			const _: &[u8] = include_bytes!(#generic_path);
+
+			#implementation
+		};
+	})
+}
+
+/// Read the weight functions out of the benchmark output at `path`, along with whether each of
+/// them takes any components.
+fn benchmarks(path: &LitStr) -> Result<(String, BTreeMap<String, bool>)> {
+	let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+		.map_err(|e| Error::new(path.span(), format!("Cannot locate the crate: {}", e)))?;
+	let full_path = PathBuf::from(manifest_dir).join(path.value());
+	let content = std::fs::read_to_string(&full_path).map_err(|e| {
+		Error::new(path.span(), format!("Cannot read `{}`: {}", full_path.display(), e))
+	})?;
+	let file = syn::parse_file(&content).map_err(|e| {
+		Error::new(path.span(), format!("Cannot parse `{}`: {}", full_path.display(), e))
+	})?;
+
+	let weights = file
+		.items
+		.iter()
+		.filter_map(|item| match item {
+			syn::Item::Impl(item) if item.trait_.is_none() => Some(item),
+			_ => None,
+		})
+		.filter(|item| match &*item.self_ty {
+			syn::Type::Path(p) => p.path.segments.last().map_or(false, |s| s.ident == "WeightInfo"),
+			_ => false,
+		})
+		.flat_map(|item| item.items.iter())
+		.filter_map(|item| match item {
+			syn::ImplItem::Method(method) =>
+				Some((method.sig.ident.to_string(), !method.sig.inputs.is_empty())),
+			_ => None,
+		})
+		.collect();
+
+	Ok((full_path.display().to_string(), weights))
+}
+
+/// The module into which the benchmark output at `path` is expected to be declared, i.e. its file
+/// name.
+fn module_name(path: &LitStr) -> Result<Ident> {
+	PathBuf::from(path.value())
+		.file_stem()
+		.and_then(|stem| stem.to_str())
+		.and_then(|stem| syn::parse_str(stem).ok())
+		.ok_or_else(|| Error::new(path.span(), "The file name must be a valid module name"))
+}
+
+fn check_components(takes_components: bool, name: &Ident, path: &LitStr) -> Result<()> {
+	if takes_components {
+		let msg = format!(
+			"The benchmark of `{}` in `{}` takes components and must be weighed by hand",
+			name,
+			path.value(),
+		);
+		return Err(Error::new(path.span(), msg))
+	}
+	Ok(())
+}
+
+/// Whether the given argument holds the assets which an instruction operates on.
+fn is_assets(input: &syn::FnArg) -> bool {
+	let ty = match input {
+		syn::FnArg::Typed(pat_type) => &*pat_type.ty,
+		syn::FnArg::Receiver(_) => return false,
+	};
+	let ty = match ty {
+		syn::Type::Reference(r) => &*r.elem,
+		ty => ty,
+	};
+	match ty {
+		syn::Type::Path(p) => p
+			.path
+			.segments
+			.last()
+			.map_or(false, |s| s.ident == "MultiAssets" || s.ident == "MultiAssetFilter"),
+		_ => false,
+	}
+}
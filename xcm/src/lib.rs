@@ -35,14 +35,19 @@ use scale_info::TypeInfo;
 pub mod v0;
 pub mod v1;
 pub mod v2;
+pub mod v3;
 
 pub mod latest {
-	pub use super::v2::*;
+	pub use super::v3::*;
 }
 
 mod double_encoded;
 pub use double_encoded::DoubleEncoded;
 
+#[doc(hidden)]
+pub use xcm_procedural::impl_weights_from_benchmarks as __impl_weights_from_benchmarks;
+pub use xcm_procedural::weights_from_benchmarks;
+
 /// Maximum nesting level for XCM decoding.
 pub const MAX_XCM_DECODE_DEPTH: u32 = 8;
 
@@ -69,6 +74,12 @@ pub trait IntoVersion: Sized {
 	}
 }
 
+/// Identify the version of XCM in which a versioned value is expressed.
+pub trait IdentifyVersion {
+	/// The version of XCM which `self` is expressed in.
+	fn identify_version(&self) -> Version;
+}
+
 /// A single `MultiLocation` value, together with its version code.
 #[derive(Derivative, Encode, Decode, TypeInfo)]
 #[derivative(Clone(bound = ""), Eq(bound = ""), PartialEq(bound = ""), Debug(bound = ""))]
@@ -77,6 +88,8 @@ pub trait IntoVersion: Sized {
 pub enum VersionedMultiLocation {
 	V0(v0::MultiLocation),
 	V1(v1::MultiLocation),
+	#[codec(index = 3)]
+	V3(v3::MultiLocation),
 }
 
 impl IntoVersion for VersionedMultiLocation {
@@ -84,20 +97,37 @@ impl IntoVersion for VersionedMultiLocation {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
 }
 
+impl IdentifyVersion for VersionedMultiLocation {
+	fn identify_version(&self) -> Version {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+			Self::V3(_) => 3,
+		}
+	}
+}
+
 impl From<v0::MultiLocation> for VersionedMultiLocation {
 	fn from(x: v0::MultiLocation) -> Self {
 		VersionedMultiLocation::V0(x)
 	}
 }
 
-impl<T: Into<v1::MultiLocation>> From<T> for VersionedMultiLocation {
+impl From<v1::MultiLocation> for VersionedMultiLocation {
+	fn from(x: v1::MultiLocation) -> Self {
+		VersionedMultiLocation::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiLocation>> From<T> for VersionedMultiLocation {
 	fn from(x: T) -> Self {
-		VersionedMultiLocation::V1(x.into())
+		VersionedMultiLocation::V3(x.into())
 	}
 }
 
@@ -108,6 +138,7 @@ impl TryFrom<VersionedMultiLocation> for v0::MultiLocation {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -119,6 +150,19 @@ impl TryFrom<VersionedMultiLocation> for v1::MultiLocation {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiLocation> for v3::MultiLocation {
+	type Error = ();
+	fn try_from(x: VersionedMultiLocation) -> Result<Self, ()> {
+		use VersionedMultiLocation::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -132,6 +176,7 @@ pub enum VersionedResponse {
 	V0(v0::Response),
 	V1(v1::Response),
 	V2(v2::Response),
+	V3(v3::Response),
 }
 
 impl IntoVersion for VersionedResponse {
@@ -140,11 +185,23 @@ impl IntoVersion for VersionedResponse {
 			0 => Self::V0(self.try_into()?),
 			1 => Self::V1(self.try_into()?),
 			2 => Self::V2(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
 }
 
+impl IdentifyVersion for VersionedResponse {
+	fn identify_version(&self) -> Version {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+			Self::V2(_) => 2,
+			Self::V3(_) => 3,
+		}
+	}
+}
+
 impl From<v0::Response> for VersionedResponse {
 	fn from(x: v0::Response) -> Self {
 		VersionedResponse::V0(x)
@@ -157,9 +214,15 @@ impl From<v1::Response> for VersionedResponse {
 	}
 }
 
-impl<T: Into<v2::Response>> From<T> for VersionedResponse {
+impl From<v2::Response> for VersionedResponse {
+	fn from(x: v2::Response) -> Self {
+		VersionedResponse::V2(x)
+	}
+}
+
+impl<T: Into<v3::Response>> From<T> for VersionedResponse {
 	fn from(x: T) -> Self {
-		VersionedResponse::V2(x.into())
+		VersionedResponse::V3(x.into())
 	}
 }
 
@@ -171,6 +234,7 @@ impl TryFrom<VersionedResponse> for v0::Response {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
 			V2(x) => VersionedResponse::V1(x.try_into()?).try_into(),
+			V3(x) => VersionedResponse::V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -183,6 +247,7 @@ impl TryFrom<VersionedResponse> for v1::Response {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
 			V2(x) => x.try_into(),
+			V3(x) => VersionedResponse::V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -195,6 +260,20 @@ impl TryFrom<VersionedResponse> for v2::Response {
 			V0(x) => VersionedResponse::V1(x.try_into()?).try_into(),
 			V1(x) => x.try_into(),
 			V2(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedResponse> for v3::Response {
+	type Error = ();
+	fn try_from(x: VersionedResponse) -> Result<Self, ()> {
+		use VersionedResponse::*;
+		match x {
+			V0(x) => VersionedResponse::V2(V0(x).try_into()?).try_into(),
+			V1(x) => VersionedResponse::V2(V1(x).try_into()?).try_into(),
+			V2(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -207,6 +286,8 @@ impl TryFrom<VersionedResponse> for v2::Response {
 pub enum VersionedMultiAsset {
 	V0(v0::MultiAsset),
 	V1(v1::MultiAsset),
+	#[codec(index = 3)]
+	V3(v3::MultiAsset),
 }
 
 impl IntoVersion for VersionedMultiAsset {
@@ -214,20 +295,37 @@ impl IntoVersion for VersionedMultiAsset {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
 }
 
+impl IdentifyVersion for VersionedMultiAsset {
+	fn identify_version(&self) -> Version {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+			Self::V3(_) => 3,
+		}
+	}
+}
+
 impl From<v0::MultiAsset> for VersionedMultiAsset {
 	fn from(x: v0::MultiAsset) -> Self {
 		VersionedMultiAsset::V0(x)
 	}
 }
 
-impl<T: Into<v1::MultiAsset>> From<T> for VersionedMultiAsset {
+impl From<v1::MultiAsset> for VersionedMultiAsset {
+	fn from(x: v1::MultiAsset) -> Self {
+		VersionedMultiAsset::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiAsset>> From<T> for VersionedMultiAsset {
 	fn from(x: T) -> Self {
-		VersionedMultiAsset::V1(x.into())
+		VersionedMultiAsset::V3(x.into())
 	}
 }
 
@@ -238,6 +336,7 @@ impl TryFrom<VersionedMultiAsset> for v0::MultiAsset {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -249,6 +348,19 @@ impl TryFrom<VersionedMultiAsset> for v1::MultiAsset {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiAsset> for v3::MultiAsset {
+	type Error = ();
+	fn try_from(x: VersionedMultiAsset) -> Result<Self, ()> {
+		use VersionedMultiAsset::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -261,6 +373,8 @@ impl TryFrom<VersionedMultiAsset> for v1::MultiAsset {
 pub enum VersionedMultiAssets {
 	V0(Vec<v0::MultiAsset>),
 	V1(v1::MultiAssets),
+	#[codec(index = 3)]
+	V3(v3::MultiAssets),
 }
 
 impl IntoVersion for VersionedMultiAssets {
@@ -268,20 +382,37 @@ impl IntoVersion for VersionedMultiAssets {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
 }
 
+impl IdentifyVersion for VersionedMultiAssets {
+	fn identify_version(&self) -> Version {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+			Self::V3(_) => 3,
+		}
+	}
+}
+
 impl From<Vec<v0::MultiAsset>> for VersionedMultiAssets {
 	fn from(x: Vec<v0::MultiAsset>) -> Self {
 		VersionedMultiAssets::V0(x)
 	}
 }
 
-impl<T: Into<v1::MultiAssets>> From<T> for VersionedMultiAssets {
+impl From<v1::MultiAssets> for VersionedMultiAssets {
+	fn from(x: v1::MultiAssets) -> Self {
+		VersionedMultiAssets::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiAssets>> From<T> for VersionedMultiAssets {
 	fn from(x: T) -> Self {
-		VersionedMultiAssets::V1(x.into())
+		VersionedMultiAssets::V3(x.into())
 	}
 }
 
@@ -292,6 +423,7 @@ impl TryFrom<VersionedMultiAssets> for Vec<v0::MultiAsset> {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -303,6 +435,19 @@ impl TryFrom<VersionedMultiAssets> for v1::MultiAssets {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiAssets> for v3::MultiAssets {
+	type Error = ();
+	fn try_from(x: VersionedMultiAssets) -> Result<Self, ()> {
+		use VersionedMultiAssets::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -317,6 +462,7 @@ pub enum VersionedXcm<Call> {
 	V0(v0::Xcm<Call>),
 	V1(v1::Xcm<Call>),
 	V2(v2::Xcm<Call>),
+	V3(v3::Xcm<Call>),
 }
 
 impl<C> IntoVersion for VersionedXcm<C> {
@@ -325,11 +471,23 @@ impl<C> IntoVersion for VersionedXcm<C> {
 			0 => Self::V0(self.try_into()?),
 			1 => Self::V1(self.try_into()?),
 			2 => Self::V2(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
 }
 
+impl<C> IdentifyVersion for VersionedXcm<C> {
+	fn identify_version(&self) -> Version {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+			Self::V2(_) => 2,
+			Self::V3(_) => 3,
+		}
+	}
+}
+
 impl<Call> From<v0::Xcm<Call>> for VersionedXcm<Call> {
 	fn from(x: v0::Xcm<Call>) -> Self {
 		VersionedXcm::V0(x)
@@ -348,6 +506,12 @@ impl<Call> From<v2::Xcm<Call>> for VersionedXcm<Call> {
 	}
 }
 
+impl<Call> From<v3::Xcm<Call>> for VersionedXcm<Call> {
+	fn from(x: v3::Xcm<Call>) -> Self {
+		VersionedXcm::V3(x)
+	}
+}
+
 impl<Call> TryFrom<VersionedXcm<Call>> for v0::Xcm<Call> {
 	type Error = ();
 	fn try_from(x: VersionedXcm<Call>) -> Result<Self, ()> {
@@ -356,6 +520,7 @@ impl<Call> TryFrom<VersionedXcm<Call>> for v0::Xcm<Call> {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
 			V2(x) => V1(x.try_into()?).try_into(),
+			V3(x) => V1(V2(x.try_into()?).try_into()?).try_into(),
 		}
 	}
 }
@@ -368,6 +533,7 @@ impl<Call> TryFrom<VersionedXcm<Call>> for v1::Xcm<Call> {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
 			V2(x) => x.try_into(),
+			V3(x) => V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -380,6 +546,20 @@ impl<Call> TryFrom<VersionedXcm<Call>> for v2::Xcm<Call> {
 			V0(x) => V1(x.try_into()?).try_into(),
 			V1(x) => x.try_into(),
 			V2(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl<Call> TryFrom<VersionedXcm<Call>> for v3::Xcm<Call> {
+	type Error = ();
+	fn try_from(x: VersionedXcm<Call>) -> Result<Self, ()> {
+		use VersionedXcm::*;
+		match x {
+			V0(x) => V2(V0(x).try_into()?).try_into(),
+			V1(x) => V2(V1(x).try_into()?).try_into(),
+			V2(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -435,17 +615,28 @@ impl WrapVersion for AlwaysV2 {
 	}
 }
 
+/// `WrapVersion` implementation which attempts to always convert the XCM to version 3 before wrapping it.
+pub struct AlwaysV3;
+impl WrapVersion for AlwaysV3 {
+	fn wrap_version<Call>(
+		_: &latest::MultiLocation,
+		xcm: impl Into<VersionedXcm<Call>>,
+	) -> Result<VersionedXcm<Call>, ()> {
+		Ok(VersionedXcm::<Call>::V3(xcm.into().try_into()?))
+	}
+}
+
 /// `WrapVersion` implementation which attempts to always convert the XCM to the latest version before wrapping it.
-pub type AlwaysLatest = AlwaysV1;
+pub type AlwaysLatest = AlwaysV3;
 
 /// `WrapVersion` implementation which attempts to always convert the XCM to the release version before wrapping it.
 pub type AlwaysRelease = AlwaysV0;
 
 pub mod prelude {
 	pub use super::{
-		latest::prelude::*, AlwaysLatest, AlwaysRelease, AlwaysV0, AlwaysV1, AlwaysV2, IntoVersion,
-		Unsupported, Version as XcmVersion, VersionedMultiAsset, VersionedMultiAssets,
-		VersionedMultiLocation, VersionedResponse, VersionedXcm, WrapVersion,
+		latest::prelude::*, AlwaysLatest, AlwaysRelease, AlwaysV0, AlwaysV1, AlwaysV2, AlwaysV3,
+		IdentifyVersion, IntoVersion, Unsupported, Version as XcmVersion, VersionedMultiAsset,
+		VersionedMultiAssets, VersionedMultiLocation, VersionedResponse, VersionedXcm, WrapVersion,
 	};
 }
 
@@ -468,9 +659,15 @@ pub mod opaque {
 		// Then override with the opaque types in v2
 		pub use crate::v2::opaque::{Instruction, Xcm};
 	}
+	pub mod v3 {
+		// Everything from v3
+		pub use crate::v3::*;
+		// Then override with the opaque types in v3
+		pub use crate::v3::opaque::{Instruction, Xcm};
+	}
 
 	pub mod latest {
-		pub use super::v2::*;
+		pub use super::v3::*;
 	}
 
 	/// The basic `VersionedXcm` type which just uses the `Vec<u8>` as an encoded call.
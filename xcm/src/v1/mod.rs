@@ -500,6 +500,10 @@ impl<Call> TryFrom<NewXcm<Call>> for Xcm<Call> {
 			Instruction::SubscribeVersion { query_id, max_response_weight } =>
 				SubscribeVersion { query_id, max_response_weight },
 			Instruction::UnsubscribeVersion => UnsubscribeVersion,
+			Instruction::DescendOrigin(who) => RelayedFrom {
+				who,
+				message: alloc::boxed::Box::new(NewXcm(iter.collect()).try_into()?),
+			},
 			_ => return Err(()),
 		})
 	}
@@ -42,8 +42,8 @@
 //!   nested XCMs, i.e. if the `instructions` field in the `BuyExecution` enum struct variant is
 //!   not empty, then the conversion will fail. To address this, rewrite the XCM using
 //!   `Instruction`s in chronological order.
-//! - During conversion from `Xcm` to `Instruction`, we do not handle `RelayedFrom` messages at
-//!   all.
+//! - `RelayedFrom` is replaced by a leading `DescendOrigin` instruction followed by the relayed
+//!   message's instructions, and vice versa.
 //!
 //! ### XCM Pallet
 //! - The `Weigher` configuration item must have sensible weights defined for `BuyExecution` and
@@ -822,8 +822,10 @@ impl<Call> TryFrom<OldXcm<Call>> for Xcm<Call> {
 				vec![HrmpChannelClosing { initiator, sender, recipient }],
 			OldXcm::Transact { origin_type, require_weight_at_most, call } =>
 				vec![Transact { origin_type, require_weight_at_most, call }],
-			// We don't handle this one at all due to nested XCM.
-			OldXcm::RelayedFrom { .. } => return Err(()),
+			OldXcm::RelayedFrom { who, message } => Some(DescendOrigin(who))
+				.into_iter()
+				.chain(Xcm::<Call>::try_from(*message)?.0.into_iter())
+				.collect(),
 			OldXcm::SubscribeVersion { query_id, max_response_weight } =>
 				vec![SubscribeVersion { query_id, max_response_weight }],
 			OldXcm::UnsubscribeVersion => vec![UnsubscribeVersion],
@@ -895,6 +897,24 @@ mod tests {
 		assert_eq!(new_xcm, xcm);
 	}
 
+	#[test]
+	fn relayed_from_roundtrip_works() {
+		let xcm = Xcm::<()>(vec![
+			DescendOrigin(X1(AccountIndex64 { network: Any, index: 1 })),
+			TransferAsset { assets: (Here, 1).into(), beneficiary: Here.into() },
+		]);
+		let old_xcm = OldXcm::<()>::RelayedFrom {
+			who: X1(AccountIndex64 { network: Any, index: 1 }),
+			message: alloc::boxed::Box::new(OldXcm::<()>::TransferAsset {
+				assets: (Here, 1).into(),
+				beneficiary: Here.into(),
+			}),
+		};
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
 	#[test]
 	fn teleport_roundtrip_works() {
 		let xcm = Xcm::<()>(vec![
@@ -0,0 +1,1298 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # XCM Version 3
+//! Version 3 of the Cross-Consensus Message format data structures.
+//!
+//! ## Changes to be aware of
+//! Version 3 only adds to version 2: no existing instruction, response or error changes its
+//! meaning or its encoding. Messages which use none of the additions below convert losslessly
+//! in both directions via the `try_from` and `from` conversion functions.
+//!
+//! ### Junction
+//! - `GlobalConsensus` junction added, naming a global network capable of externalizing its own
+//!   consensus. A location containing it cannot be expressed in version 2.
+//!
+//! ### `MultiLocation`
+//! - `reanchor` now takes the universal location of the local chain (`context`) rather than its
+//!   location relative to the relay chain (`ancestry`).
+//!
+//! ### `MultiAsset`
+//! - No special attention necessary
+//!
+//! ### XCM and Instruction
+//! - New instructions: `UnpaidExecution`, `BurnAsset`, `ExpectOrigin`, `ExpectError`,
+//!   `QueryPallet`, `ExpectPallet`, `ReportTransactStatus`, `ClearTransactStatus`,
+//!   `ExportMessage`, `SetTopic` and `UniversalOrigin`. A message containing any of them cannot
+//!   be converted to version 2.
+//! - New responses: `PalletsInfo` and `DispatchResult`.
+//! - New errors: `ExpectationFalse`, `PalletNotFound`, `NameMismatch` and `VersionIncompatible`
+//!   at the end of the specified error codes, and the implementation-specific
+//!   `TeleportAmountExceeded` and `ProvenanceMismatch`.
+//!
+//! ### XCM Pallet
+//! - No special attention necessary
+
+use super::v2::{Instruction as OldInstruction, Response as OldResponse, Xcm as OldXcm};
+use crate::{DoubleEncoded, GetWeight};
+use alloc::{vec, vec::Vec};
+use core::{
+	convert::{TryFrom, TryInto},
+	fmt::Debug,
+	result,
+};
+use derivative::Derivative;
+use parity_scale_codec::{self, Decode, Encode};
+use scale_info::TypeInfo;
+
+mod junction;
+mod multiasset;
+mod multilocation;
+mod traits;
+
+pub use junction::Junction;
+pub use multiasset::{
+	AssetId, AssetInstance, Fungibility, MultiAsset, MultiAssetFilter, MultiAssets,
+	WildFungibility, WildMultiAsset,
+};
+pub use multilocation::{
+	Ancestor, AncestorThen, InteriorMultiLocation, Junctions, MultiLocation, Parent, ParentThen,
+};
+pub use traits::{
+	send_xcm, validate_send, Error, ExecuteXcm, ExecutionReceipt, InspectMessageQueues, Outcome,
+	PreparedMessage, Result, SendError, SendResult, SendXcm, Weightless, XcmContext, XcmHash,
+};
+// These parts of XCM v2 have been unchanged in XCM v3, and are re-imported here.
+pub use super::v2::{BodyId, BodyPart, NetworkId, OriginKind, WeightLimit};
+
+/// This module's XCM version.
+pub const VERSION: super::Version = 3;
+
+/// An identifier for a query.
+pub type QueryId = u64;
+
+#[derive(Derivative, Default, Encode, Decode, TypeInfo)]
+#[derivative(Clone(bound = ""), Eq(bound = ""), PartialEq(bound = ""), Debug(bound = ""))]
+#[codec(encode_bound())]
+#[codec(decode_bound())]
+#[scale_info(bounds(), skip_type_params(Call))]
+pub struct Xcm<Call>(pub Vec<Instruction<Call>>);
+
+impl<Call> Xcm<Call> {
+	/// Create an empty instance.
+	pub fn new() -> Self {
+		Self(vec![])
+	}
+
+	/// Return `true` if no instructions are held in `self`.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Return the number of instructions held in `self`.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Consume and either return `self` if it contains some instructions, or if it's empty, then
+	/// instead return the result of `f`.
+	pub fn or_else(self, f: impl FnOnce() -> Self) -> Self {
+		if self.0.is_empty() {
+			f()
+		} else {
+			self
+		}
+	}
+
+	/// Return the first instruction, if any.
+	pub fn first(&self) -> Option<&Instruction<Call>> {
+		self.0.first()
+	}
+
+	/// Return the last instruction, if any.
+	pub fn last(&self) -> Option<&Instruction<Call>> {
+		self.0.last()
+	}
+
+	/// Return the only instruction, contained in `Self`, iff only one exists (`None` otherwise).
+	pub fn only(&self) -> Option<&Instruction<Call>> {
+		if self.0.len() == 1 {
+			self.0.first()
+		} else {
+			None
+		}
+	}
+
+	/// Return the only instruction, contained in `Self`, iff only one exists (returns `self`
+	/// otherwise).
+	pub fn into_only(mut self) -> core::result::Result<Instruction<Call>, Self> {
+		if self.0.len() == 1 {
+			self.0.pop().ok_or(self)
+		} else {
+			Err(self)
+		}
+	}
+}
+
+/// A prelude for importing all types typically used when interacting with XCM messages.
+pub mod prelude {
+	mod contents {
+		pub use super::super::{
+			send_xcm, validate_send, Ancestor, AncestorThen,
+			AssetId::{self, *},
+			AssetInstance::{self, *},
+			BodyId, BodyPart, Error as XcmError, ExecuteXcm, ExecutionReceipt,
+			Fungibility::{self, *},
+			InspectMessageQueues,
+			Instruction::*,
+			InteriorMultiLocation,
+			Junction::{self, *},
+			Junctions::{self, *},
+			MaybeErrorCode, MultiAsset,
+			MultiAssetFilter::{self, *},
+			MultiAssets, MultiLocation,
+			NetworkId::{self, *},
+			OriginKind, Outcome, PalletInfo, Parent, ParentThen, PreparedMessage, QueryId,
+			QueryResponseInfo, Response, Result as XcmResult, SendError, SendResult, SendXcm,
+			WeightLimit::{self, *},
+			WildFungibility::{self, Fungible as WildFungible, NonFungible as WildNonFungible},
+			WildMultiAsset::{self, *},
+			XcmContext, XcmHash, XcmWeightInfo, VERSION as XCM_VERSION,
+		};
+	}
+	pub use super::{Instruction, Xcm};
+	pub use contents::*;
+	pub mod opaque {
+		pub use super::{
+			super::opaque::{Instruction, Xcm},
+			contents::*,
+		};
+	}
+}
+
+/// Response data to a query.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub enum Response {
+	/// No response. Serves as a neutral default.
+	Null,
+	/// Some assets.
+	Assets(MultiAssets),
+	/// The outcome of an XCM instruction.
+	ExecutionResult(Option<(u32, Error)>),
+	/// An XCM version.
+	Version(super::Version),
+	/// The index, instance name, pallet name and version of some pallets.
+	PalletsInfo(Vec<PalletInfo>),
+	/// The status of a dispatch attempt using `Transact`.
+	DispatchResult(MaybeErrorCode),
+}
+
+impl Default for Response {
+	fn default() -> Self {
+		Self::Null
+	}
+}
+
+/// Information about a pallet of a chain, as reported by `QueryPallet`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct PalletInfo {
+	/// The index of the pallet in the runtime.
+	#[codec(compact)]
+	pub index: u32,
+	/// The name of the pallet instance in the runtime.
+	pub name: Vec<u8>,
+	/// The name of the crate of the pallet.
+	pub module_name: Vec<u8>,
+	/// The major version of the crate of the pallet.
+	#[codec(compact)]
+	pub major: u32,
+	/// The minor version of the crate of the pallet.
+	#[codec(compact)]
+	pub minor: u32,
+	/// The patch version of the crate of the pallet.
+	#[codec(compact)]
+	pub patch: u32,
+}
+
+/// The maximum length of the encoded error of a dispatch kept in `MaybeErrorCode`.
+pub const MAX_DISPATCH_ERROR_LEN: usize = 128;
+
+/// The result of the most recent dispatch, as reported by `ReportTransactStatus`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub enum MaybeErrorCode {
+	/// There was no dispatch, or it succeeded.
+	Success,
+	/// The dispatch failed with the given encoded error.
+	Error(Vec<u8>),
+	/// The dispatch failed with an encoded error longer than `MAX_DISPATCH_ERROR_LEN`, of which
+	/// only the first `MAX_DISPATCH_ERROR_LEN` bytes are kept.
+	TruncatedError(Vec<u8>),
+}
+
+impl From<Vec<u8>> for MaybeErrorCode {
+	fn from(mut error: Vec<u8>) -> Self {
+		if error.len() > MAX_DISPATCH_ERROR_LEN {
+			error.truncate(MAX_DISPATCH_ERROR_LEN);
+			Self::TruncatedError(error)
+		} else {
+			Self::Error(error)
+		}
+	}
+}
+
+impl Default for MaybeErrorCode {
+	fn default() -> Self {
+		Self::Success
+	}
+}
+
+/// Where and how to send the `QueryResponse` which reports on an instruction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct QueryResponseInfo {
+	/// The destination to which the `QueryResponse` message should be sent.
+	pub destination: MultiLocation,
+	/// The `query_id` field of the `QueryResponse` message.
+	#[codec(compact)]
+	pub query_id: QueryId,
+	/// The `max_weight` field of the `QueryResponse` message.
+	#[codec(compact)]
+	pub max_weight: Weight,
+}
+
+/// Local weight type; execution time in picoseconds.
+pub type Weight = u64;
+
+/// Cross-Consensus Message: A message from one consensus system to another.
+///
+/// Consensus systems that may send and receive messages include blockchains and smart contracts.
+///
+/// All messages are delivered from a known *origin*, expressed as a `MultiLocation`.
+///
+/// This is the inner XCM format and is version-sensitive. Messages are typically passed using the outer
+/// XCM format, known as `VersionedXcm`.
+#[derive(Derivative, Encode, Decode, TypeInfo, xcm_procedural::XcmWeightInfoTrait)]
+#[derivative(Clone(bound = ""), Eq(bound = ""), PartialEq(bound = ""), Debug(bound = ""))]
+#[codec(encode_bound())]
+#[codec(decode_bound())]
+#[scale_info(bounds(), skip_type_params(Call))]
+pub enum Instruction<Call> {
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place them into the Holding
+	/// Register.
+	///
+	/// - `assets`: The asset(s) to be withdrawn into holding.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	WithdrawAsset(MultiAssets),
+
+	/// Asset(s) (`assets`) have been received into the ownership of this system on the `origin`
+	/// system and equivalent derivatives should be placed into the Holding Register.
+	///
+	/// - `assets`: The asset(s) that are minted into holding.
+	///
+	/// Safety: `origin` must be trusted to have received and be storing `assets` such that they
+	/// may later be withdrawn should this system send a corresponding message.
+	///
+	/// Kind: *Trusted Indication*.
+	///
+	/// Errors:
+	ReserveAssetDeposited(MultiAssets),
+
+	/// Asset(s) (`assets`) have been destroyed on the `origin` system and equivalent assets should
+	/// be created and placed into the Holding Register.
+	///
+	/// - `assets`: The asset(s) that are minted into the Holding Register.
+	///
+	/// Safety: `origin` must be trusted to have irrevocably destroyed the corresponding `assets`
+	/// prior as a consequence of sending this message.
+	///
+	/// Kind: *Trusted Indication*.
+	///
+	/// Errors:
+	ReceiveTeleportedAsset(MultiAssets),
+
+	/// Respond with information that the local system is expecting.
+	///
+	/// - `query_id`: The identifier of the query that resulted in this message being sent.
+	/// - `response`: The message content.
+	/// - `max_weight`: The maximum weight that handling this response should take.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Information*.
+	///
+	/// Errors:
+	QueryResponse {
+		#[codec(compact)]
+		query_id: QueryId,
+		response: Response,
+		#[codec(compact)]
+		max_weight: u64,
+	},
+
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place equivalent assets
+	/// under the ownership of `beneficiary`.
+	///
+	/// - `assets`: The asset(s) to be withdrawn.
+	/// - `beneficiary`: The new owner for the assets.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	TransferAsset { assets: MultiAssets, beneficiary: MultiLocation },
+
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place equivalent assets
+	/// under the ownership of `dest` within this consensus system (i.e. its sovereign account).
+	///
+	/// Send an onward XCM message to `dest` of `ReserveAssetDeposited` with the given
+	/// `xcm`.
+	///
+	/// - `assets`: The asset(s) to be withdrawn.
+	/// - `dest`: The location whose sovereign account will own the assets and thus the effective
+	///   beneficiary for the assets and the notification target for the reserve asset deposit
+	///   message.
+	/// - `xcm`: The instructions that should follow the `ReserveAssetDeposited`
+	///   instruction, which is sent onwards to `dest`.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	TransferReserveAsset { assets: MultiAssets, dest: MultiLocation, xcm: Xcm<()> },
+
+	/// Apply the encoded transaction `call`, whose dispatch-origin should be `origin` as expressed
+	/// by the kind of origin `origin_type`.
+	///
+	/// - `origin_type`: The means of expressing the message origin as a dispatch origin.
+	/// - `max_weight`: The weight of `call`; this should be at least the chain's calculated weight
+	///   and will be used in the weight determination arithmetic.
+	/// - `call`: The encoded transaction to be applied.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	Transact {
+		origin_type: OriginKind,
+		#[codec(compact)]
+		require_weight_at_most: u64,
+		call: DoubleEncoded<Call>,
+	},
+
+	/// A message to notify about a new incoming HRMP channel. This message is meant to be sent by the
+	/// relay-chain to a para.
+	///
+	/// - `sender`: The sender in the to-be opened channel. Also, the initiator of the channel opening.
+	/// - `max_message_size`: The maximum size of a message proposed by the sender.
+	/// - `max_capacity`: The maximum number of messages that can be queued in the channel.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	HrmpNewChannelOpenRequest {
+		#[codec(compact)]
+		sender: u32,
+		#[codec(compact)]
+		max_message_size: u32,
+		#[codec(compact)]
+		max_capacity: u32,
+	},
+
+	/// A message to notify about that a previously sent open channel request has been accepted by
+	/// the recipient. That means that the channel will be opened during the next relay-chain session
+	/// change. This message is meant to be sent by the relay-chain to a para.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	///
+	/// Errors:
+	HrmpChannelAccepted {
+		// NOTE: We keep this as a structured item to a) keep it consistent with the other Hrmp
+		// items; and b) because the field's meaning is not obvious/mentioned from the item name.
+		#[codec(compact)]
+		recipient: u32,
+	},
+
+	/// A message to notify that the other party in an open channel decided to close it. In particular,
+	/// `initiator` is going to close the channel opened from `sender` to the `recipient`. The close
+	/// will be enacted at the next relay-chain session change. This message is meant to be sent by
+	/// the relay-chain to a para.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	///
+	/// Errors:
+	HrmpChannelClosing {
+		#[codec(compact)]
+		initiator: u32,
+		#[codec(compact)]
+		sender: u32,
+		#[codec(compact)]
+		recipient: u32,
+	},
+
+	/// Clear the origin.
+	///
+	/// This may be used by the XCM author to ensure that later instructions cannot command the
+	/// authority of the origin (e.g. if they are being relayed from an untrusted source, as often
+	/// the case with `ReserveAssetDeposited`).
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	ClearOrigin,
+
+	/// Mutate the origin to some interior location.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DescendOrigin(InteriorMultiLocation),
+
+	/// Immediately report the contents of the Error Register to the given destination via XCM.
+	///
+	/// A `QueryResponse` message of type `ExecutionOutcome` is sent to `dest` with the given
+	/// `query_id` and the outcome of the XCM.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ReportError {
+		#[codec(compact)]
+		query_id: QueryId,
+		dest: MultiLocation,
+		#[codec(compact)]
+		max_response_weight: u64,
+	},
+
+	/// Remove the asset(s) (`assets`) from the Holding Register and place equivalent assets under
+	/// the ownership of `beneficiary` within this consensus system.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `max_assets`: The maximum number of unique assets/asset instances to remove from holding.
+	///   Only the first `max_assets` assets/instances of those matched by `assets` will be removed,
+	///   prioritized under standard asset ordering. Any others will remain in holding.
+	/// - `beneficiary`: The new owner for the assets.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DepositAsset {
+		assets: MultiAssetFilter,
+		#[codec(compact)]
+		max_assets: u32,
+		beneficiary: MultiLocation,
+	},
+
+	/// Remove the asset(s) (`assets`) from the Holding Register and place equivalent assets under
+	/// the ownership of `dest` within this consensus system (i.e. deposit them into its sovereign
+	/// account).
+	///
+	/// Send an onward XCM message to `dest` of `ReserveAssetDeposited` with the given `effects`.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `max_assets`: The maximum number of unique assets/asset instances to remove from holding.
+	///   Only the first `max_assets` assets/instances of those matched by `assets` will be removed,
+	///   prioritized under standard asset ordering. Any others will remain in holding.
+	/// - `dest`: The location whose sovereign account will own the assets and thus the effective
+	///   beneficiary for the assets and the notification target for the reserve asset deposit
+	///   message.
+	/// - `xcm`: The orders that should follow the `ReserveAssetDeposited` instruction
+	///   which is sent onwards to `dest`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DepositReserveAsset {
+		assets: MultiAssetFilter,
+		#[codec(compact)]
+		max_assets: u32,
+		dest: MultiLocation,
+		xcm: Xcm<()>,
+	},
+
+	/// Remove the asset(s) (`give`) from the Holding Register and replace them with alternative
+	/// assets.
+	///
+	/// The minimum amount of assets to be received into the Holding Register for the order not to
+	/// fail may be stated.
+	///
+	/// - `give`: The asset(s) to remove from holding.
+	/// - `receive`: The minimum amount of assets(s) which `give` should be exchanged for.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ExchangeAsset { give: MultiAssetFilter, receive: MultiAssets },
+
+	/// Remove the asset(s) (`assets`) from holding and send a `WithdrawAsset` XCM message to a
+	/// reserve location.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `reserve`: A valid location that acts as a reserve for all asset(s) in `assets`. The
+	///   sovereign account of this consensus system *on the reserve location* will have appropriate
+	///   assets withdrawn and `effects` will be executed on them. There will typically be only one
+	///   valid location on any given asset/chain combination.
+	/// - `xcm`: The instructions to execute on the assets once withdrawn *on the reserve
+	///   location*.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	InitiateReserveWithdraw { assets: MultiAssetFilter, reserve: MultiLocation, xcm: Xcm<()> },
+
+	/// Remove the asset(s) (`assets`) from holding and send a `ReceiveTeleportedAsset` XCM message
+	/// to a `dest` location.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `dest`: A valid location that respects teleports coming from this location.
+	/// - `xcm`: The instructions to execute on the assets once arrived *on the destination
+	///   location*.
+	///
+	/// NOTE: The `dest` location *MUST* respect this origin as a valid teleportation origin for all
+	/// `assets`. If it does not, then the assets may be lost.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	InitiateTeleport { assets: MultiAssetFilter, dest: MultiLocation, xcm: Xcm<()> },
+
+	/// Send a `Balances` XCM message with the `assets` value equal to the holding contents, or a
+	/// portion thereof.
+	///
+	/// - `query_id`: An identifier that will be replicated into the returned XCM message.
+	/// - `dest`: A valid destination for the returned XCM message. This may be limited to the
+	///   current origin.
+	/// - `assets`: A filter for the assets that should be reported back. The assets reported back
+	///   will be, asset-wise, *the lesser of this value and the holding register*. No wildcards
+	///   will be used when reporting assets back.
+	/// - `max_response_weight`: The maximum amount of weight that the `QueryResponse` item which
+	///   is sent as a reply may take to execute. NOTE: If this is unexpectedly large then the
+	///   response may not execute at all.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	QueryHolding {
+		#[codec(compact)]
+		query_id: QueryId,
+		dest: MultiLocation,
+		assets: MultiAssetFilter,
+		#[codec(compact)]
+		max_response_weight: u64,
+	},
+
+	/// Pay for the execution of some XCM `xcm` and `orders` with up to `weight`
+	/// picoseconds of execution time, paying for this with up to `fees` from the Holding Register.
+	///
+	/// - `fees`: The asset(s) to remove from the Holding Register to pay for fees.
+	/// - `weight_limit`: The maximum amount of weight to purchase; this must be at least the
+	///   expected maximum weight of the total XCM to be executed for the
+	///   `AllowTopLevelPaidExecutionFrom` barrier to allow the XCM be executed.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	BuyExecution { fees: MultiAsset, weight_limit: WeightLimit },
+
+	/// Refund any surplus weight previously bought with `BuyExecution`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	RefundSurplus,
+
+	/// Set the Error Handler Register. This is code that should be called in the case of an error
+	/// happening.
+	///
+	/// An error occurring within execution of this code will _NOT_ result in the error register
+	/// being set, nor will an error handler be called due to it. The error handler and appendix
+	/// may each still be set.
+	///
+	/// The apparent weight of this instruction is inclusive of the inner `Xcm`; the executing
+	/// weight however includes only the difference between the previous handler and the new
+	/// handler, which can reasonably be negative, which would result in a surplus.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	SetErrorHandler(Xcm<Call>),
+
+	/// Set the Appendix Register. This is code that should be called after code execution
+	/// (including the error handler if any) is finished. This will be called regardless of whether
+	/// an error occurred.
+	///
+	/// Any error occurring due to execution of this code will result in the error register being
+	/// set, and the error handler (if set) firing.
+	///
+	/// The apparent weight of this instruction is inclusive of the inner `Xcm`; the executing
+	/// weight however includes only the difference between the previous appendix and the new
+	/// appendix, which can reasonably be negative, which would result in a surplus.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	SetAppendix(Xcm<Call>),
+
+	/// Clear the Error Register.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	ClearError,
+
+	/// Create some assets which are being held on behalf of the origin.
+	///
+	/// - `assets`: The assets which are to be claimed. This must match exactly with the assets
+	///   claimable by the origin of the ticket.
+	/// - `ticket`: The ticket of the asset; this is an abstract identifier to help locate the
+	///   asset.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ClaimAsset { assets: MultiAssets, ticket: MultiLocation },
+
+	/// Always throws an error of type `Trap`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `Trap`: All circumstances, whose inner value is the same as this item's inner value.
+	Trap(#[codec(compact)] u64),
+
+	/// Ask the destination system to respond with the most recent version of XCM that they
+	/// support in a `QueryResponse` instruction. Any changes to this should also elicit similar
+	/// responses when they happen.
+	///
+	/// Kind: *Instruction*
+	SubscribeVersion {
+		#[codec(compact)]
+		query_id: QueryId,
+		#[codec(compact)]
+		max_response_weight: u64,
+	},
+
+	/// Cancel the effect of a previous `SubscribeVersion` instruction.
+	///
+	/// Kind: *Instruction*
+	UnsubscribeVersion,
+
+	/// A directive to indicate that the origin expects free execution of the message.
+	///
+	/// At execution time, this instruction just does a check on the Origin register. However, at
+	/// the barrier stage messages starting with this instruction can be disregarded if the origin
+	/// is not acceptable for free execution or the `weight_limit` is `Limited` and insufficient.
+	///
+	/// - `weight_limit`: The most weight the origin expects the message to take.
+	/// - `check_origin`: If `Some`, the location which the Origin register must be equal to.
+	///
+	/// Kind: *Indication*
+	///
+	/// Errors:
+	/// - `BadOrigin`: `check_origin` is `Some` and not equal to the Origin register.
+	UnpaidExecution { weight_limit: WeightLimit, check_origin: Option<MultiLocation> },
+
+	/// Reduce Holding by up to the given assets.
+	///
+	/// Holding is reduced by as much as possible up to the assets in the parameter. It is not an
+	/// error if the Holding does not contain the assets.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Infallible*
+	BurnAsset(MultiAssets),
+
+	/// Ensure that the Origin Register equals some given value and throw an error if not.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `ExpectationFalse`: If Origin Register is not equal to the parameter.
+	ExpectOrigin(Option<MultiLocation>),
+
+	/// Ensure that the Error Register equals some given value and throw an error if not.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `ExpectationFalse`: If the value of the Error Register is not equal to the parameter.
+	ExpectError(Option<(u32, Error)>),
+
+	/// Query the existence of a particular pallet type.
+	///
+	/// - `module_name`: The module name of the pallet to query.
+	/// - `response_info`: Information for making the response.
+	///
+	/// Sends a `QueryResponse` to Origin whose data field `PalletsInfo` containing the information
+	/// of all pallets on the local chain whose name is equal to `name`. This is empty in the case
+	/// that the local chain is not based on Substrate Frame.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Fallible*.
+	QueryPallet { module_name: Vec<u8>, response_info: QueryResponseInfo },
+
+	/// Ensure that a particular pallet with a particular version exists.
+	///
+	/// - `index: Compact`: The index which identifies the pallet. An error if no pallet exists at
+	///   this index.
+	/// - `name: Vec<u8>`: Name which must be equal to the name of the pallet.
+	/// - `module_name: Vec<u8>`: Module name which must be equal to the name of the module in
+	///   which the pallet exists.
+	/// - `crate_major: Compact`: Version number which must be equal to the major version of the
+	///   crate which implements the pallet.
+	/// - `min_crate_minor: Compact`: Version number which must be at most the minor version of the
+	///   crate which implements the pallet.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `PalletNotFound`: If no pallet exists at the given index.
+	/// - `NameMismatch`: If the name or the module name of the pallet are not as expected.
+	/// - `VersionIncompatible`: If the version of the crate of the pallet is not as expected.
+	ExpectPallet {
+		#[codec(compact)]
+		index: u32,
+		name: Vec<u8>,
+		module_name: Vec<u8>,
+		#[codec(compact)]
+		crate_major: u32,
+		#[codec(compact)]
+		min_crate_minor: u32,
+	},
+	/// Send a `QueryResponse` message containing the value of the Transact Status Register to
+	/// some destination.
+	///
+	/// - `query_response_info`: The information needed for constructing and sending the
+	///   `QueryResponse` message.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Fallible*.
+	ReportTransactStatus(QueryResponseInfo),
+
+	/// Set the Transact Status Register to its default, cleared, value.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Infallible*.
+	ClearTransactStatus,
+
+	/// Send a message on to a non-local consensus system.
+	///
+	/// This will tend to utilize some extra-consensus mechanism, the obvious one being a bridge.
+	/// A fee may be charged; this may be determined based on the contents of `xcm`. It will be
+	/// taken from the Holding register.
+	///
+	/// - `network`: The remote consensus system to which the message should be exported.
+	/// - `destination`: The location relative to the remote consensus system to which the message
+	///   should be sent on arrival.
+	/// - `xcm`: The message to be exported.
+	///
+	/// As an example, to export a message for execution on Statemine (parachain #1000 in the
+	/// Kusama network), you would call with `network: NetworkId::Kusama` and
+	/// `destination: X1(Parachain(1000))`. Alternatively, to export a message for execution on
+	/// Polkadot, you would call with `network: NetworkId::Polkadot` and `destination: Here`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Fallible*.
+	ExportMessage { network: NetworkId, destination: InteriorMultiLocation, xcm: Xcm<()> },
+
+	/// Set the Topic Register.
+	///
+	/// The topic is an identifier of the message which is kept as it travels across chains and
+	/// is, by convention, given in a trailing `SetTopic` instruction. It allows a message to be
+	/// traced across multiple hops and deduplicated should it be retried.
+	///
+	/// - `topic`: The new value of the Topic Register.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Infallible*.
+	SetTopic([u8; 32]),
+
+	/// Set the Origin Register to be some child of the Universal Ancestor.
+	///
+	/// The `Junction` parameter should generally be a `GlobalConsensus` variant, since it is only
+	/// these which are children of the Universal Ancestor. A bridge uses this to make the messages
+	/// which it dispatches come from the remote consensus system which sent them.
+	///
+	/// Safety: Should only be usable if the Origin is trusted to represent the Universal Ancestor
+	/// child in general. No Origin should be able to represent the local consensus system, since
+	/// it would by extension be able to act as any location within it.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: *Fallible*.
+	UniversalOrigin(Junction),
+}
+
+impl<Call> Xcm<Call> {
+	pub fn into<C>(self) -> Xcm<C> {
+		Xcm::from(self)
+	}
+	pub fn from<C>(xcm: Xcm<C>) -> Self {
+		Self(xcm.0.into_iter().map(Instruction::<Call>::from).collect())
+	}
+}
+
+impl<Call> Instruction<Call> {
+	pub fn into<C>(self) -> Instruction<C> {
+		Instruction::from(self)
+	}
+	pub fn from<C>(xcm: Instruction<C>) -> Self {
+		use Instruction::*;
+		match xcm {
+			WithdrawAsset(assets) => WithdrawAsset(assets),
+			ReserveAssetDeposited(assets) => ReserveAssetDeposited(assets),
+			ReceiveTeleportedAsset(assets) => ReceiveTeleportedAsset(assets),
+			QueryResponse { query_id, response, max_weight } =>
+				QueryResponse { query_id, response, max_weight },
+			TransferAsset { assets, beneficiary } => TransferAsset { assets, beneficiary },
+			TransferReserveAsset { assets, dest, xcm } =>
+				TransferReserveAsset { assets, dest, xcm },
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Transact { origin_type, require_weight_at_most, call: call.into() },
+			ReportError { query_id, dest, max_response_weight } =>
+				ReportError { query_id, dest, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } =>
+				DepositAsset { assets, max_assets, beneficiary },
+			DepositReserveAsset { assets, max_assets, dest, xcm } =>
+				DepositReserveAsset { assets, max_assets, dest, xcm },
+			ExchangeAsset { give, receive } => ExchangeAsset { give, receive },
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				InitiateReserveWithdraw { assets, reserve, xcm },
+			InitiateTeleport { assets, dest, xcm } => InitiateTeleport { assets, dest, xcm },
+			QueryHolding { query_id, dest, assets, max_response_weight } =>
+				QueryHolding { query_id, dest, assets, max_response_weight },
+			BuyExecution { fees, weight_limit } => BuyExecution { fees, weight_limit },
+			ClearOrigin => ClearOrigin,
+			DescendOrigin(who) => DescendOrigin(who),
+			RefundSurplus => RefundSurplus,
+			SetErrorHandler(xcm) => SetErrorHandler(xcm.into()),
+			SetAppendix(xcm) => SetAppendix(xcm.into()),
+			ClearError => ClearError,
+			ClaimAsset { assets, ticket } => ClaimAsset { assets, ticket },
+			Trap(code) => Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => UnsubscribeVersion,
+			UnpaidExecution { weight_limit, check_origin } =>
+				UnpaidExecution { weight_limit, check_origin },
+			BurnAsset(assets) => BurnAsset(assets),
+			ExpectOrigin(origin) => ExpectOrigin(origin),
+			ExpectError(error) => ExpectError(error),
+			QueryPallet { module_name, response_info } =>
+				QueryPallet { module_name, response_info },
+			ExpectPallet { index, name, module_name, crate_major, min_crate_minor } =>
+				ExpectPallet { index, name, module_name, crate_major, min_crate_minor },
+			ReportTransactStatus(response_info) => ReportTransactStatus(response_info),
+			ClearTransactStatus => ClearTransactStatus,
+			ExportMessage { network, destination, xcm } =>
+				ExportMessage { network, destination, xcm },
+			SetTopic(topic) => SetTopic(topic),
+			UniversalOrigin(j) => UniversalOrigin(j),
+		}
+	}
+}
+
+// TODO: Automate Generation
+impl<Call, W: XcmWeightInfo<Call>> GetWeight<W> for Instruction<Call> {
+	fn weight(&self) -> Weight {
+		use Instruction::*;
+		match self {
+			WithdrawAsset(assets) => W::withdraw_asset(assets),
+			ReserveAssetDeposited(assets) => W::reserve_asset_deposited(assets),
+			ReceiveTeleportedAsset(assets) => W::receive_teleported_asset(assets),
+			QueryResponse { query_id, response, max_weight } =>
+				W::query_response(query_id, response, max_weight),
+			TransferAsset { assets, beneficiary } => W::transfer_asset(assets, beneficiary),
+			TransferReserveAsset { assets, dest, xcm } =>
+				W::transfer_reserve_asset(&assets, dest, xcm),
+			Transact { origin_type, require_weight_at_most, call } =>
+				W::transact(origin_type, require_weight_at_most, call),
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				W::hrmp_new_channel_open_request(sender, max_message_size, max_capacity),
+			HrmpChannelAccepted { recipient } => W::hrmp_channel_accepted(recipient),
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				W::hrmp_channel_closing(initiator, sender, recipient),
+			ClearOrigin => W::clear_origin(),
+			DescendOrigin(who) => W::descend_origin(who),
+			ReportError { query_id, dest, max_response_weight } =>
+				W::report_error(query_id, dest, max_response_weight),
+			DepositAsset { assets, max_assets, beneficiary } =>
+				W::deposit_asset(assets, max_assets, beneficiary),
+			DepositReserveAsset { assets, max_assets, dest, xcm } =>
+				W::deposit_reserve_asset(assets, max_assets, dest, xcm),
+			ExchangeAsset { give, receive } => W::exchange_asset(give, receive),
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				W::initiate_reserve_withdraw(assets, reserve, xcm),
+			InitiateTeleport { assets, dest, xcm } => W::initiate_teleport(assets, dest, xcm),
+			QueryHolding { query_id, dest, assets, max_response_weight } =>
+				W::query_holding(query_id, dest, assets, max_response_weight),
+			BuyExecution { fees, weight_limit } => W::buy_execution(fees, weight_limit),
+			RefundSurplus => W::refund_surplus(),
+			SetErrorHandler(xcm) => W::set_error_handler(xcm),
+			SetAppendix(xcm) => W::set_appendix(xcm),
+			ClearError => W::clear_error(),
+			ClaimAsset { assets, ticket } => W::claim_asset(assets, ticket),
+			Trap(code) => W::trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				W::subscribe_version(query_id, max_response_weight),
+			UnsubscribeVersion => W::unsubscribe_version(),
+			UnpaidExecution { weight_limit, check_origin } =>
+				W::unpaid_execution(weight_limit, check_origin),
+			BurnAsset(assets) => W::burn_asset(assets),
+			ExpectOrigin(origin) => W::expect_origin(origin),
+			ExpectError(error) => W::expect_error(error),
+			QueryPallet { module_name, response_info } =>
+				W::query_pallet(module_name, response_info),
+			ExpectPallet { index, name, module_name, crate_major, min_crate_minor } =>
+				W::expect_pallet(index, name, module_name, crate_major, min_crate_minor),
+			ReportTransactStatus(response_info) => W::report_transact_status(response_info),
+			ClearTransactStatus => W::clear_transact_status(),
+			ExportMessage { network, destination, xcm } =>
+				W::export_message(network, destination, xcm),
+			SetTopic(topic) => W::set_topic(topic),
+			UniversalOrigin(j) => W::universal_origin(j),
+		}
+	}
+}
+
+pub mod opaque {
+	/// The basic concrete type of `Xcm`, which doesn't make any assumptions about the
+	/// format of a call other than it is pre-encoded.
+	pub type Xcm = super::Xcm<()>;
+
+	/// The basic concrete type of `Instruction`, which doesn't make any assumptions about the
+	/// format of a call other than it is pre-encoded.
+	pub type Instruction = super::Instruction<()>;
+}
+
+// Convert from a v2 response to a v3 response.
+impl TryFrom<OldResponse> for Response {
+	type Error = ();
+	fn try_from(old_response: OldResponse) -> result::Result<Self, ()> {
+		Ok(match old_response {
+			OldResponse::Null => Self::Null,
+			OldResponse::Assets(assets) => Self::Assets(assets.try_into()?),
+			OldResponse::ExecutionResult(error) =>
+				Self::ExecutionResult(error.map(|(i, e)| (i, e.into()))),
+			OldResponse::Version(version) => Self::Version(version),
+		})
+	}
+}
+
+// Convert from a v3 response to a v2 response.
+impl TryFrom<Response> for OldResponse {
+	type Error = ();
+	fn try_from(response: Response) -> result::Result<Self, ()> {
+		Ok(match response {
+			Response::Null => Self::Null,
+			Response::Assets(assets) => Self::Assets(assets.try_into()?),
+			Response::ExecutionResult(error) => Self::ExecutionResult(match error {
+				Some((i, e)) => Some((i, e.try_into()?)),
+				None => None,
+			}),
+			Response::Version(version) => Self::Version(version),
+			Response::PalletsInfo(_) | Response::DispatchResult(_) => return Err(()),
+		})
+	}
+}
+
+impl<Call> TryFrom<OldXcm<Call>> for Xcm<Call> {
+	type Error = ();
+	fn try_from(old_xcm: OldXcm<Call>) -> result::Result<Self, ()> {
+		Ok(Xcm(old_xcm.0.into_iter().map(TryInto::try_into).collect::<result::Result<_, _>>()?))
+	}
+}
+
+impl<Call> TryFrom<Xcm<Call>> for OldXcm<Call> {
+	type Error = ();
+	fn try_from(xcm: Xcm<Call>) -> result::Result<Self, ()> {
+		Ok(OldXcm(xcm.0.into_iter().map(TryInto::try_into).collect::<result::Result<_, _>>()?))
+	}
+}
+
+// Convert from a v2 instruction to a v3 instruction.
+impl<Call> TryFrom<OldInstruction<Call>> for Instruction<Call> {
+	type Error = ();
+	fn try_from(old_instruction: OldInstruction<Call>) -> result::Result<Self, ()> {
+		use OldInstruction::*;
+		Ok(match old_instruction {
+			WithdrawAsset(assets) => Self::WithdrawAsset(assets.try_into()?),
+			ReserveAssetDeposited(assets) => Self::ReserveAssetDeposited(assets.try_into()?),
+			ReceiveTeleportedAsset(assets) => Self::ReceiveTeleportedAsset(assets.try_into()?),
+			QueryResponse { query_id, response, max_weight } =>
+				Self::QueryResponse { query_id, response: response.try_into()?, max_weight },
+			TransferAsset { assets, beneficiary } => Self::TransferAsset {
+				assets: assets.try_into()?,
+				beneficiary: beneficiary.try_into()?,
+			},
+			TransferReserveAsset { assets, dest, xcm } => Self::TransferReserveAsset {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				Self::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => Self::HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				Self::HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Self::Transact { origin_type, require_weight_at_most, call },
+			ReportError { query_id, dest, max_response_weight } =>
+				Self::ReportError { query_id, dest: dest.try_into()?, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } => Self::DepositAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				beneficiary: beneficiary.try_into()?,
+			},
+			DepositReserveAsset { assets, max_assets, dest, xcm } => Self::DepositReserveAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			ExchangeAsset { give, receive } =>
+				Self::ExchangeAsset { give: give.try_into()?, receive: receive.try_into()? },
+			InitiateReserveWithdraw { assets, reserve, xcm } => Self::InitiateReserveWithdraw {
+				assets: assets.try_into()?,
+				reserve: reserve.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			InitiateTeleport { assets, dest, xcm } => Self::InitiateTeleport {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			QueryHolding { query_id, dest, assets, max_response_weight } => Self::QueryHolding {
+				query_id,
+				dest: dest.try_into()?,
+				assets: assets.try_into()?,
+				max_response_weight,
+			},
+			BuyExecution { fees, weight_limit } =>
+				Self::BuyExecution { fees: fees.try_into()?, weight_limit },
+			ClearOrigin => Self::ClearOrigin,
+			DescendOrigin(who) => Self::DescendOrigin(who.try_into()?),
+			RefundSurplus => Self::RefundSurplus,
+			SetErrorHandler(xcm) => Self::SetErrorHandler(xcm.try_into()?),
+			SetAppendix(xcm) => Self::SetAppendix(xcm.try_into()?),
+			ClearError => Self::ClearError,
+			ClaimAsset { assets, ticket } =>
+				Self::ClaimAsset { assets: assets.try_into()?, ticket: ticket.try_into()? },
+			Trap(code) => Self::Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				Self::SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => Self::UnsubscribeVersion,
+		})
+	}
+}
+
+// Convert from a v3 instruction to a v2 instruction.
+impl<Call> TryFrom<Instruction<Call>> for OldInstruction<Call> {
+	type Error = ();
+	fn try_from(instruction: Instruction<Call>) -> result::Result<Self, ()> {
+		use Instruction::*;
+		Ok(match instruction {
+			WithdrawAsset(assets) => Self::WithdrawAsset(assets.try_into()?),
+			ReserveAssetDeposited(assets) => Self::ReserveAssetDeposited(assets.try_into()?),
+			ReceiveTeleportedAsset(assets) => Self::ReceiveTeleportedAsset(assets.try_into()?),
+			QueryResponse { query_id, response, max_weight } =>
+				Self::QueryResponse { query_id, response: response.try_into()?, max_weight },
+			TransferAsset { assets, beneficiary } => Self::TransferAsset {
+				assets: assets.try_into()?,
+				beneficiary: beneficiary.try_into()?,
+			},
+			TransferReserveAsset { assets, dest, xcm } => Self::TransferReserveAsset {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				Self::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => Self::HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				Self::HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Self::Transact { origin_type, require_weight_at_most, call },
+			ReportError { query_id, dest, max_response_weight } =>
+				Self::ReportError { query_id, dest: dest.try_into()?, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } => Self::DepositAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				beneficiary: beneficiary.try_into()?,
+			},
+			DepositReserveAsset { assets, max_assets, dest, xcm } => Self::DepositReserveAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			ExchangeAsset { give, receive } =>
+				Self::ExchangeAsset { give: give.try_into()?, receive: receive.try_into()? },
+			InitiateReserveWithdraw { assets, reserve, xcm } => Self::InitiateReserveWithdraw {
+				assets: assets.try_into()?,
+				reserve: reserve.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			InitiateTeleport { assets, dest, xcm } => Self::InitiateTeleport {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			QueryHolding { query_id, dest, assets, max_response_weight } => Self::QueryHolding {
+				query_id,
+				dest: dest.try_into()?,
+				assets: assets.try_into()?,
+				max_response_weight,
+			},
+			BuyExecution { fees, weight_limit } =>
+				Self::BuyExecution { fees: fees.try_into()?, weight_limit },
+			ClearOrigin => Self::ClearOrigin,
+			DescendOrigin(who) => Self::DescendOrigin(who.try_into()?),
+			RefundSurplus => Self::RefundSurplus,
+			SetErrorHandler(xcm) => Self::SetErrorHandler(xcm.try_into()?),
+			SetAppendix(xcm) => Self::SetAppendix(xcm.try_into()?),
+			ClearError => Self::ClearError,
+			ClaimAsset { assets, ticket } =>
+				Self::ClaimAsset { assets: assets.try_into()?, ticket: ticket.try_into()? },
+			Trap(code) => Self::Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				Self::SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => Self::UnsubscribeVersion,
+			UnpaidExecution { .. } |
+			BurnAsset(_) |
+			ExpectOrigin(_) |
+			ExpectError(_) |
+			QueryPallet { .. } |
+			ExpectPallet { .. } |
+			ReportTransactStatus(_) |
+			ClearTransactStatus |
+			ExportMessage { .. } |
+			SetTopic(_) |
+			UniversalOrigin(_) => return Err(()),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{prelude::*, *};
+	use crate::v2::{
+		Junctions::Here as OldHere, MultiAssetFilter as OldMultiAssetFilter,
+		WildMultiAsset as OldWildMultiAsset,
+	};
+
+	#[test]
+	fn basic_roundtrip_works() {
+		let xcm =
+			Xcm::<()>(vec![TransferAsset { assets: (Here, 1).into(), beneficiary: Here.into() }]);
+		let old_xcm = OldXcm::<()>(vec![OldInstruction::TransferAsset {
+			assets: (OldHere, 1).into(),
+			beneficiary: OldHere.into(),
+		}]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
+	#[test]
+	fn teleport_roundtrip_works() {
+		let xcm = Xcm::<()>(vec![
+			ReceiveTeleportedAsset((Here, 1).into()),
+			ClearOrigin,
+			DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: Here.into() },
+		]);
+		let old_xcm: OldXcm<()> = OldXcm::<()>(vec![
+			OldInstruction::ReceiveTeleportedAsset((OldHere, 1).into()),
+			OldInstruction::ClearOrigin,
+			OldInstruction::DepositAsset {
+				assets: OldMultiAssetFilter::Wild(OldWildMultiAsset::All),
+				max_assets: 1,
+				beneficiary: OldHere.into(),
+			},
+		]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
+	#[test]
+	fn query_response_roundtrip_works() {
+		let xcm = Xcm::<()>(vec![QueryResponse {
+			query_id: 1,
+			response: Response::ExecutionResult(Some((2, XcmError::Trap(3)))),
+			max_weight: 4,
+		}]);
+		let old_xcm = OldXcm::<()>(vec![OldInstruction::QueryResponse {
+			query_id: 1,
+			response: OldResponse::ExecutionResult(Some((2, crate::v2::Error::Trap(3)))),
+			max_weight: 4,
+		}]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
+	#[test]
+	fn new_instructions_do_not_convert_to_v2() {
+		let unconvertible = vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+			BurnAsset((Here, 1).into()),
+			ExpectOrigin(None),
+			ExpectError(Some((0, XcmError::ExpectationFalse))),
+			ClearTransactStatus,
+			SetTopic([0; 32]),
+			UniversalOrigin(GlobalConsensus(NetworkId::Kusama)),
+		];
+		for instruction in unconvertible {
+			assert!(OldInstruction::<()>::try_from(instruction).is_err());
+		}
+		let with_global_consensus = DescendOrigin(X1(GlobalConsensus(NetworkId::Polkadot)));
+		assert!(OldInstruction::<()>::try_from(with_global_consensus).is_err());
+		let response = Response::ExecutionResult(Some((0, XcmError::PalletNotFound)));
+		assert!(OldResponse::try_from(response).is_err());
+		assert!(OldResponse::try_from(Response::PalletsInfo(vec![])).is_err());
+	}
+}
@@ -0,0 +1,678 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cross-Consensus Message format data structures.
+
+use crate::v2::Error as OldError;
+use core::{convert::TryFrom, result};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+use super::*;
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	// Errors that happen due to instructions being executed. These alone are defined in the
+	// XCM specification.
+	/// An arithmetic overflow happened.
+	#[codec(index = 0)]
+	Overflow,
+	/// The instruction is intentionally unsupported.
+	#[codec(index = 1)]
+	Unimplemented,
+	/// Origin Register does not contain a value value for a reserve transfer notification.
+	#[codec(index = 2)]
+	UntrustedReserveLocation,
+	/// Origin Register does not contain a value value for a teleport notification.
+	#[codec(index = 3)]
+	UntrustedTeleportLocation,
+	/// `MultiLocation` value too large to descend further.
+	#[codec(index = 4)]
+	MultiLocationFull,
+	/// `MultiLocation` value ascend more parents than known ancestors of local location.
+	#[codec(index = 5)]
+	MultiLocationNotInvertible,
+	/// The Origin Register does not contain a valid value for instruction.
+	#[codec(index = 6)]
+	BadOrigin,
+	/// The location parameter is not a valid value for the instruction.
+	#[codec(index = 7)]
+	InvalidLocation,
+	/// The given asset is not handled.
+	#[codec(index = 8)]
+	AssetNotFound,
+	/// An asset transaction (like withdraw or deposit) failed (typically due to type conversions).
+	#[codec(index = 9)]
+	FailedToTransactAsset(#[codec(skip)] &'static str),
+	/// An asset cannot be withdrawn, potentially due to lack of ownership, availability or rights.
+	#[codec(index = 10)]
+	NotWithdrawable,
+	/// An asset cannot be deposited under the ownership of a particular location.
+	#[codec(index = 11)]
+	LocationCannotHold,
+	/// Attempt to send a message greater than the maximum supported by the transport protocol.
+	#[codec(index = 12)]
+	ExceedsMaxMessageSize,
+	/// The given message cannot be translated into a format supported by the destination.
+	#[codec(index = 13)]
+	DestinationUnsupported,
+	/// Destination is routable, but there is some issue with the transport mechanism.
+	#[codec(index = 14)]
+	Transport(#[codec(skip)] &'static str),
+	/// Destination is known to be unroutable.
+	#[codec(index = 15)]
+	Unroutable,
+	/// Used by `ClaimAsset` when the given claim could not be recognized/found.
+	#[codec(index = 16)]
+	UnknownClaim,
+	/// Used by `Transact` when the functor cannot be decoded.
+	#[codec(index = 17)]
+	FailedToDecode,
+	/// Used by `Transact` to indicate that the given weight limit could be breached by the functor.
+	#[codec(index = 18)]
+	MaxWeightInvalid,
+	/// Used by `BuyExecution` when the Holding Register does not contain payable fees.
+	#[codec(index = 19)]
+	NotHoldingFees,
+	/// Used by `BuyExecution` when the fees declared to purchase weight are insufficient.
+	#[codec(index = 20)]
+	TooExpensive,
+	/// Used by the `Trap` instruction to force an error intentionally. Its code is included.
+	#[codec(index = 21)]
+	Trap(u64),
+	/// Used by `ExpectOrigin` and `ExpectError` when the expectation was not true.
+	#[codec(index = 22)]
+	ExpectationFalse,
+	/// Used by `ExpectPallet` when no pallet exists at the given index.
+	#[codec(index = 23)]
+	PalletNotFound,
+	/// Used by `ExpectPallet` when the name or the module name of the pallet is not as expected.
+	#[codec(index = 24)]
+	NameMismatch,
+	/// Used by `ExpectPallet` when the version of the crate of the pallet is not as expected.
+	#[codec(index = 25)]
+	VersionIncompatible,
+
+	// Errors that happen prior to instructions being executed. These fall outside of the XCM spec.
+	/// XCM version not able to be handled.
+	UnhandledXcmVersion,
+	/// Execution of the XCM would potentially result in a greater weight used than weight limit.
+	WeightLimitReached(Weight),
+	/// The XCM did not pass the barrier condition for execution.
+	///
+	/// The barrier condition differs on different chains and in different circumstances, but
+	/// generally it means that the conditions surrounding the message were not such that the chain
+	/// considers the message worth spending time executing. Since most chains lift the barrier to
+	/// execution on appropriate payment, presentation of an NFT voucher, or based on the message
+	/// origin, it means that none of those were the case.
+	Barrier,
+	/// The weight of an XCM message is not computable ahead of execution.
+	WeightNotComputable,
+
+	// Errors that happen during execution due to local configuration. These fall outside of the XCM
+	// spec.
+	/// Used by `ReceiveTeleportedAsset` and `InitiateTeleport` when the amount of an asset exceeds
+	/// the maximum permitted to be teleported in a single message.
+	TeleportAmountExceeded,
+	/// Used by `DepositReserveAsset` and `InitiateTeleport` when the assets to be forwarded are
+	/// reserve-backed derivatives, which may only be withdrawn back from their reserve.
+	ProvenanceMismatch,
+}
+
+impl From<OldError> for Error {
+	fn from(old_error: OldError) -> Self {
+		use OldError::*;
+		match old_error {
+			Overflow => Self::Overflow,
+			Unimplemented => Self::Unimplemented,
+			UntrustedReserveLocation => Self::UntrustedReserveLocation,
+			UntrustedTeleportLocation => Self::UntrustedTeleportLocation,
+			MultiLocationFull => Self::MultiLocationFull,
+			MultiLocationNotInvertible => Self::MultiLocationNotInvertible,
+			BadOrigin => Self::BadOrigin,
+			InvalidLocation => Self::InvalidLocation,
+			AssetNotFound => Self::AssetNotFound,
+			FailedToTransactAsset(s) => Self::FailedToTransactAsset(s),
+			NotWithdrawable => Self::NotWithdrawable,
+			LocationCannotHold => Self::LocationCannotHold,
+			ExceedsMaxMessageSize => Self::ExceedsMaxMessageSize,
+			DestinationUnsupported => Self::DestinationUnsupported,
+			Transport(s) => Self::Transport(s),
+			Unroutable => Self::Unroutable,
+			UnknownClaim => Self::UnknownClaim,
+			FailedToDecode => Self::FailedToDecode,
+			MaxWeightInvalid => Self::MaxWeightInvalid,
+			NotHoldingFees => Self::NotHoldingFees,
+			TooExpensive => Self::TooExpensive,
+			Trap(i) => Self::Trap(i),
+			UnhandledXcmVersion => Self::UnhandledXcmVersion,
+			WeightLimitReached(w) => Self::WeightLimitReached(w),
+			Barrier => Self::Barrier,
+			WeightNotComputable => Self::WeightNotComputable,
+		}
+	}
+}
+
+impl TryFrom<Error> for OldError {
+	type Error = ();
+	fn try_from(new_error: Error) -> result::Result<Self, ()> {
+		use Error::*;
+		Ok(match new_error {
+			Overflow => Self::Overflow,
+			Unimplemented => Self::Unimplemented,
+			UntrustedReserveLocation => Self::UntrustedReserveLocation,
+			UntrustedTeleportLocation => Self::UntrustedTeleportLocation,
+			MultiLocationFull => Self::MultiLocationFull,
+			MultiLocationNotInvertible => Self::MultiLocationNotInvertible,
+			BadOrigin => Self::BadOrigin,
+			InvalidLocation => Self::InvalidLocation,
+			AssetNotFound => Self::AssetNotFound,
+			FailedToTransactAsset(s) => Self::FailedToTransactAsset(s),
+			NotWithdrawable => Self::NotWithdrawable,
+			LocationCannotHold => Self::LocationCannotHold,
+			ExceedsMaxMessageSize => Self::ExceedsMaxMessageSize,
+			DestinationUnsupported => Self::DestinationUnsupported,
+			Transport(s) => Self::Transport(s),
+			Unroutable => Self::Unroutable,
+			UnknownClaim => Self::UnknownClaim,
+			FailedToDecode => Self::FailedToDecode,
+			MaxWeightInvalid => Self::MaxWeightInvalid,
+			NotHoldingFees => Self::NotHoldingFees,
+			TooExpensive => Self::TooExpensive,
+			Trap(i) => Self::Trap(i),
+			UnhandledXcmVersion => Self::UnhandledXcmVersion,
+			WeightLimitReached(w) => Self::WeightLimitReached(w),
+			Barrier => Self::Barrier,
+			WeightNotComputable => Self::WeightNotComputable,
+			ExpectationFalse |
+			PalletNotFound |
+			NameMismatch |
+			VersionIncompatible |
+			TeleportAmountExceeded |
+			ProvenanceMismatch => return Err(()),
+		})
+	}
+}
+
+impl From<SendError> for Error {
+	fn from(e: SendError) -> Self {
+		match e {
+			SendError::NotApplicable | SendError::Unroutable | SendError::MissingArgument =>
+				Error::Unroutable,
+			SendError::Transport(s) => Error::Transport(s),
+			SendError::DestinationUnsupported => Error::DestinationUnsupported,
+			SendError::ExceedsMaxMessageSize => Error::ExceedsMaxMessageSize,
+			SendError::Fees => Error::TooExpensive,
+		}
+	}
+}
+
+pub type Result = result::Result<(), Error>;
+
+/// Outcome of an XCM execution.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Outcome {
+	/// Execution completed successfully; given weight was used.
+	Complete(Weight),
+	/// Execution started, but did not complete successfully due to the given error; given weight was used.
+	Incomplete(Weight, Error),
+	/// Execution did not start due to the given error.
+	Error(Error),
+}
+
+impl Outcome {
+	pub fn ensure_complete(self) -> Result {
+		match self {
+			Outcome::Complete(_) => Ok(()),
+			Outcome::Incomplete(_, e) => Err(e),
+			Outcome::Error(e) => Err(e),
+		}
+	}
+	pub fn ensure_execution(self) -> result::Result<Weight, Error> {
+		match self {
+			Outcome::Complete(w) => Ok(w),
+			Outcome::Incomplete(w, _) => Ok(w),
+			Outcome::Error(e) => Err(e),
+		}
+	}
+	/// How much weight was used by the XCM execution attempt.
+	pub fn weight_used(&self) -> Weight {
+		match self {
+			Outcome::Complete(w) => *w,
+			Outcome::Incomplete(w, _) => *w,
+			Outcome::Error(_) => 0,
+		}
+	}
+}
+
+/// A summary of how far the execution of an XCM message got, detailed enough for the caller to
+/// resume the message should its execution have been cut short for want of weight.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub struct ExecutionReceipt {
+	/// The number of top-level instructions which were executed, including any which failed.
+	pub executed: u32,
+	/// The number of top-level instructions at the end of the message which were not executed
+	/// since they did not fit into the weight limit. If this is non-zero, all instructions before
+	/// them were executed successfully and they may be executed later as a message of their own.
+	pub skipped: u32,
+	/// The weight which was used.
+	pub used: Weight,
+	/// The weight of the executed instructions which turned out not to be needed.
+	pub surplus: Weight,
+	/// The index of the top-level instruction at which execution stopped short of the end of the
+	/// message: the instruction which failed or, if any were skipped, the first skipped one.
+	/// `None` if execution did not stop early, or if the executor cannot tell where it did.
+	pub error_index: Option<u32>,
+}
+
+/// An XCM message which has been prepared for execution, which in particular means that its
+/// weight is known.
+pub trait PreparedMessage {
+	/// The weight which executing the message may take at most.
+	fn weight_of(&self) -> Weight;
+}
+
+/// A `PreparedMessage` which cannot exist, for implementations of `ExecuteXcm` which never
+/// prepare a message.
+pub enum Weightless {}
+impl PreparedMessage for Weightless {
+	fn weight_of(&self) -> Weight {
+		match *self {}
+	}
+}
+
+/// Type of XCM message executor.
+///
+/// Execution happens in two phases. First, the message is prepared with `prepare`, which decodes
+/// nothing further but weighs the message, allowing e.g. a message queue to decide whether it
+/// wants to process it at all. Then, the `Prepared` message is executed with `execute`.
+pub trait ExecuteXcm<Call> {
+	/// A message which has been prepared for execution.
+	type Prepared: PreparedMessage;
+
+	/// Prepare `message` for execution, returning it back unchanged if it cannot be weighed.
+	fn prepare(message: Xcm<Call>) -> result::Result<Self::Prepared, Xcm<Call>>;
+
+	/// Execute some `Prepared` message from `origin`, identified by `id`.
+	///
+	/// Some amount of `weight_credit` may be provided which, depending on the implementation, may
+	/// allow execution without associated payment.
+	fn execute(
+		origin: impl Into<MultiLocation>,
+		pre: Self::Prepared,
+		id: XcmHash,
+		weight_credit: Weight,
+	) -> Outcome;
+
+	/// Execute some XCM `message` from `origin` using no more than `weight_limit` weight. The weight limit is
+	/// a basic hard-limit and the implementation may place further restrictions or requirements on weight and
+	/// other aspects.
+	fn execute_xcm(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Call>,
+		weight_limit: Weight,
+	) -> Outcome {
+		let origin = origin.into();
+		log::debug!(
+			target: "xcm::execute_xcm",
+			"origin: {:?}, message: {:?}, weight_limit: {:?}",
+			origin,
+			message,
+			weight_limit,
+		);
+		Self::execute_xcm_in_credit(origin, message, weight_limit, 0)
+	}
+
+	/// Execute some XCM `message` from `origin` using no more than `weight_limit` weight.
+	///
+	/// Some amount of `weight_credit` may be provided which, depending on the implementation, may allow
+	/// execution without associated payment.
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Call>,
+		weight_limit: Weight,
+		weight_credit: Weight,
+	) -> Outcome;
+
+	/// Execute some XCM `message` as though it originated from `origin`, granting it a
+	/// pre-established `weight_credit` which is also the most weight it may use.
+	///
+	/// Intended for privileged, runtime-internal callers (e.g. governance) which have already
+	/// accounted for the weight of the message and so need not pay for its execution.
+	fn execute_with_origin(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Call>,
+		weight_credit: Weight,
+	) -> Outcome {
+		Self::execute_xcm_in_credit(origin, message, weight_credit, weight_credit)
+	}
+
+	/// Execute as much of some XCM `message` from `origin` as fits into `weight_limit`, with
+	/// `weight_credit` as in `execute_xcm_in_credit`. The message is identified by `id`.
+	///
+	/// Rather than refusing a message which is too heavy altogether, implementations may execute
+	/// the longest prefix of its instructions which fits and skip the rest, returning
+	/// `Outcome::Incomplete` with `Error::WeightLimitReached`. The returned `ExecutionReceipt`
+	/// then says how many instructions were skipped, so that the caller may execute them later,
+	/// e.g. by re-queueing them. Implementations should only skip instructions which will pass
+	/// their barrier when executed as a message of their own.
+	///
+	/// The default implementation never skips any instructions.
+	fn prepare_and_execute(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Call>,
+		id: XcmHash,
+		weight_limit: Weight,
+		weight_credit: Weight,
+	) -> (Outcome, ExecutionReceipt) {
+		let len = message.0.len() as u32;
+		let pre = match Self::prepare(message) {
+			Ok(x) => x,
+			Err(_) => return (Outcome::Error(Error::WeightNotComputable), Default::default()),
+		};
+		let xcm_weight = pre.weight_of();
+		if xcm_weight > weight_limit {
+			return (Outcome::Error(Error::WeightLimitReached(xcm_weight)), Default::default())
+		}
+		let outcome = Self::execute(origin, pre, id, weight_credit);
+		let executed = match outcome {
+			Outcome::Error(_) => 0,
+			_ => len,
+		};
+		let receipt = ExecutionReceipt {
+			executed,
+			skipped: 0,
+			used: outcome.weight_used(),
+			surplus: 0,
+			error_index: None,
+		};
+		(outcome, receipt)
+	}
+
+	/// Deduct some `fees` to the sovereign account of the given `location` and place them as per
+	/// the convention for fees.
+	fn charge_fees(location: impl Into<MultiLocation>, fees: MultiAssets) -> Result;
+}
+
+impl<C> ExecuteXcm<C> for () {
+	type Prepared = Weightless;
+	fn prepare(message: Xcm<C>) -> result::Result<Self::Prepared, Xcm<C>> {
+		Err(message)
+	}
+	fn execute(
+		_origin: impl Into<MultiLocation>,
+		_pre: Self::Prepared,
+		_id: XcmHash,
+		_weight_credit: Weight,
+	) -> Outcome {
+		Outcome::Error(Error::Unimplemented)
+	}
+	fn execute_xcm_in_credit(
+		_origin: impl Into<MultiLocation>,
+		_message: Xcm<C>,
+		_weight_limit: Weight,
+		_weight_credit: Weight,
+	) -> Outcome {
+		Outcome::Error(Error::Unimplemented)
+	}
+	fn charge_fees(_location: impl Into<MultiLocation>, _fees: MultiAssets) -> Result {
+		Err(Error::Unimplemented)
+	}
+}
+
+/// Error result value when attempting to send an XCM message.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, scale_info::TypeInfo)]
+pub enum SendError {
+	/// The message and destination combination was not recognized as being reachable.
+	///
+	/// This is not considered fatal: if there are alternative transport routes available, then
+	/// they may be attempted.
+	NotApplicable,
+	/// Destination is routable, but there is some issue with the transport mechanism. This is
+	/// considered fatal.
+	/// A human-readable explanation of the specific issue is provided.
+	Transport(#[codec(skip)] &'static str),
+	/// Destination is known to be unroutable. This is considered fatal.
+	Unroutable,
+	/// The given message cannot be translated into a format that the destination can be expected
+	/// to interpret.
+	DestinationUnsupported,
+	/// Message could not be sent due to its size exceeding the maximum allowed by the transport
+	/// layer.
+	ExceedsMaxMessageSize,
+	/// A needed argument is `None` when it should be `Some`.
+	MissingArgument,
+	/// Fees needed to be paid in order to send the message and they were unavailable.
+	Fees,
+}
+
+/// A hash type for identifying messages.
+pub type XcmHash = [u8; 32];
+
+/// The context in which some part of an XCM message is being handled, for handlers which need to
+/// correlate what they do with the message.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct XcmContext {
+	/// The location from which the message originated, if known.
+	pub origin: Option<MultiLocation>,
+	/// The identity of the message, as it was given to the executor.
+	pub message_id: XcmHash,
+	/// The topic of the message, as set by `SetTopic`, if any.
+	pub topic: Option<[u8; 32]>,
+}
+
+impl XcmContext {
+	/// A context of a message with the given `message_id` and no known origin or topic.
+	pub fn with_message_id(message_id: XcmHash) -> XcmContext {
+		XcmContext { origin: None, message_id, topic: None }
+	}
+}
+
+/// Result value when attempting to validate the sending of an XCM message: the ticket with which
+/// the message may be delivered along with the price for delivering it.
+pub type SendResult<T> = result::Result<(T, MultiAssets), SendError>;
+
+/// Utility for sending an XCM message to a given location.
+///
+/// Sending happens in two phases. First, the message is validated with `validate`, which determines
+/// whether it can be delivered and at what price, returning a `Ticket`. Then, typically once the
+/// price has been paid, the message is delivered by passing the `Ticket` to `deliver`.
+///
+/// These can be amalgamated in tuples to form sophisticated routing systems. In tuple format, each
+/// router might return `NotApplicable` to pass the execution to the next sender item. Note that
+/// each `NotApplicable` might alter the destination and the XCM message for to the next router.
+///
+/// # Example
+/// ```rust
+/// # use xcm::v3::{prelude::*, send_xcm};
+/// # use parity_scale_codec::Encode;
+///
+/// /// A sender that only passes the message through and does nothing.
+/// struct Sender1;
+/// impl SendXcm for Sender1 {
+///     type Ticket = ();
+///     fn validate(
+///         _destination: &mut Option<MultiLocation>,
+///         _message: &mut Option<Xcm<()>>,
+///     ) -> SendResult<()> {
+///         Err(SendError::NotApplicable)
+///     }
+///     fn deliver(_: ()) -> Result<XcmHash, SendError> {
+///         unreachable!()
+///     }
+/// }
+///
+/// /// A sender that accepts a message that has an X2 junction, otherwise stops the routing.
+/// struct Sender2;
+/// impl SendXcm for Sender2 {
+///     type Ticket = ();
+///     fn validate(
+///         destination: &mut Option<MultiLocation>,
+///         _message: &mut Option<Xcm<()>>,
+///     ) -> SendResult<()> {
+///         match destination.as_ref().ok_or(SendError::MissingArgument)? {
+///             MultiLocation { parents: 0, interior: X2(..) } => Ok(((), MultiAssets::new())),
+///             _ => Err(SendError::Unroutable),
+///         }
+///     }
+///     fn deliver(_: ()) -> Result<XcmHash, SendError> {
+///         Ok([0; 32])
+///     }
+/// }
+///
+/// /// A sender that accepts a message from a parent, passing through otherwise.
+/// struct Sender3;
+/// impl SendXcm for Sender3 {
+///     type Ticket = ();
+///     fn validate(
+///         destination: &mut Option<MultiLocation>,
+///         _message: &mut Option<Xcm<()>>,
+///     ) -> SendResult<()> {
+///         match destination.as_ref().ok_or(SendError::MissingArgument)? {
+///             MultiLocation { parents: 1, interior: Here } => Ok(((), MultiAssets::new())),
+///             _ => Err(SendError::NotApplicable),
+///         }
+///     }
+///     fn deliver(_: ()) -> Result<XcmHash, SendError> {
+///         Ok([0; 32])
+///     }
+/// }
+///
+/// // A call to send via XCM. We don't really care about this.
+/// # fn main() {
+/// let call: Vec<u8> = ().encode();
+/// let message = Xcm(vec![Instruction::Transact {
+///     origin_type: OriginKind::Superuser,
+///     require_weight_at_most: 0,
+///     call: call.into(),
+/// }]);
+///
+/// // Sender2 will block this.
+/// assert!(send_xcm::<(Sender1, Sender2, Sender3)>(Parent.into(), message.clone()).is_err());
+///
+/// // Sender3 will catch this.
+/// assert!(send_xcm::<(Sender1, Sender3)>(Parent.into(), message.clone()).is_ok());
+/// # }
+/// ```
+pub trait SendXcm {
+	/// Intermediate value which connects the two phases of the send operation.
+	type Ticket;
+
+	/// Check whether the given `message` is deliverable to the given `destination` and if so
+	/// determine the cost which will be paid by this chain to do so, returning a `Ticket` token
+	/// which can be used to enact delivery.
+	///
+	/// The `destination` and `message` must be `Some` (or else an error will be returned) and they
+	/// may only be consumed if the `Err` is not `NotApplicable`.
+	///
+	/// If it is not a destination which can be reached with this type but possibly could by others,
+	/// then this *MUST* return `NotApplicable`. Any other error will cause the tuple implementation
+	/// to exit early without trying other type fields.
+	fn validate(
+		destination: &mut Option<MultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket>;
+
+	/// Actually carry out the delivery operation for a previously validated message sending,
+	/// returning the hash of the delivered message.
+	fn deliver(ticket: Self::Ticket) -> result::Result<XcmHash, SendError>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl SendXcm for Tuple {
+	for_tuples! { type Ticket = ( #( Option<Tuple::Ticket> ),* ); }
+
+	fn validate(
+		destination: &mut Option<MultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let mut maybe_cost: Option<MultiAssets> = None;
+		let one_ticket: Self::Ticket = (for_tuples! { #(
+			if maybe_cost.is_some() {
+				None
+			} else {
+				match Tuple::validate(destination, message) {
+					Err(SendError::NotApplicable) => None,
+					Err(e) => { return Err(e) },
+					Ok((v, c)) => {
+						maybe_cost = Some(c);
+						Some(v)
+					},
+				}
+			}
+		),* });
+		if let Some(cost) = maybe_cost {
+			Ok((one_ticket, cost))
+		} else {
+			Err(SendError::NotApplicable)
+		}
+	}
+
+	fn deliver(one_ticket: Self::Ticket) -> result::Result<XcmHash, SendError> {
+		for_tuples!( #(
+			if let Some(validated) = one_ticket.Tuple {
+				return Tuple::deliver(validated);
+			}
+		)* );
+		Err(SendError::Unroutable)
+	}
+}
+
+/// Convenience function for using a `SendXcm` implementation. Just interprets the `dest` and wraps
+/// both in `Some` before passing them as as mutable references into `T::validate`.
+pub fn validate_send<T: SendXcm>(dest: MultiLocation, msg: Xcm<()>) -> SendResult<T::Ticket> {
+	T::validate(&mut Some(dest), &mut Some(msg))
+}
+
+/// Convenience function for using a `SendXcm` implementation. Just interprets the `dest` and wraps
+/// both in `Some` before passing them as as mutable references into `T::validate`, then delivers
+/// the message without paying the price.
+///
+/// Returns the hash of the delivered message along with the price which was due for its delivery.
+pub fn send_xcm<T: SendXcm>(
+	dest: MultiLocation,
+	msg: Xcm<()>,
+) -> result::Result<(XcmHash, MultiAssets), SendError> {
+	let (ticket, price) = T::validate(&mut Some(dest), &mut Some(msg))?;
+	let hash = T::deliver(ticket)?;
+	Ok((hash, price))
+}
+
+/// Means of inspecting the messages which a router has queued for delivery.
+///
+/// This is intended for dry-running calls and messages, whose state changes are discarded
+/// afterwards; `clear_messages` in particular must never be called from a context whose state
+/// is committed.
+pub trait InspectMessageQueues {
+	/// Remove all messages currently queued for delivery.
+	fn clear_messages();
+
+	/// Return all messages currently queued for delivery, grouped by destination.
+	fn get_messages() -> Vec<(crate::VersionedMultiLocation, Vec<crate::VersionedXcm<()>>)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl InspectMessageQueues for Tuple {
+	fn clear_messages() {
+		for_tuples!( #( Tuple::clear_messages(); )* );
+	}
+
+	fn get_messages() -> Vec<(crate::VersionedMultiLocation, Vec<crate::VersionedXcm<()>>)> {
+		let mut messages = Vec::new();
+		for_tuples!( #( messages.append(&mut Tuple::get_messages()); )* );
+		messages
+	}
+}
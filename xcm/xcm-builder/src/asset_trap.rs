@@ -0,0 +1,40 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for configuring the asset trap.
+
+use frame_support::traits::{Contains, Get};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+use xcm::latest::prelude::*;
+use xcm_executor::Assets;
+
+/// A filter for dropped assets which lets through only those worth trapping, for use with
+/// `FilterAssets`: a value is trapped if it holds any asset for which `Thresholds` gives no
+/// minimum amount, or any fungible asset of at least the minimum amount given for its id.
+///
+/// This ensures that the dust left over from failed executions doesn't bloat the asset trap.
+pub struct MinimumAmountFilter<Thresholds>(PhantomData<Thresholds>);
+impl<Thresholds: Get<BTreeMap<AssetId, u128>>> Contains<Assets>
+	for MinimumAmountFilter<Thresholds>
+{
+	fn contains(assets: &Assets) -> bool {
+		let thresholds = Thresholds::get();
+		assets.assets_iter().any(|asset| match (thresholds.get(&asset.id), &asset.fun) {
+			(Some(minimum), Fungible(amount)) => amount >= minimum,
+			_ => true,
+		})
+	}
+}
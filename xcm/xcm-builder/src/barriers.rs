@@ -20,9 +20,9 @@ use frame_support::{ensure, traits::Contains, weights::Weight};
 use polkadot_parachain::primitives::IsSystem;
 use sp_std::{marker::PhantomData, result::Result};
 use xcm::latest::{Instruction::*, Junction, Junctions, MultiLocation, WeightLimit::*, Xcm};
-use xcm_executor::traits::{OnResponse, ShouldExecute};
+use xcm_executor::traits::{OnResponse, Properties, ShouldExecute};
 
-/// Execution barrier that just takes `max_weight` from `weight_credit`.
+/// Execution barrier that just takes `max_weight` from the weight credit of the `properties`.
 ///
 /// Useful to allow XCM execution by local chain users via extrinsics.
 /// E.g. `pallet_xcm::reserve_asset_transfer` to transfer a reserve asset
@@ -33,14 +33,14 @@ impl ShouldExecute for TakeWeightCredit {
 		_origin: &MultiLocation,
 		_message: &mut Xcm<Call>,
 		max_weight: Weight,
-		weight_credit: &mut Weight,
+		properties: &mut Properties,
 	) -> Result<(), ()> {
 		log::trace!(
 			target: "xcm::barriers",
-			"TakeWeightCredit origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
-			_origin, _message, max_weight, weight_credit,
+			"TakeWeightCredit origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			_origin, _message, max_weight, properties,
 		);
-		*weight_credit = weight_credit.checked_sub(max_weight).ok_or(())?;
+		properties.weight_credit = properties.weight_credit.checked_sub(max_weight).ok_or(())?;
 		Ok(())
 	}
 }
@@ -56,12 +56,12 @@ impl<T: Contains<MultiLocation>> ShouldExecute for AllowTopLevelPaidExecutionFro
 		origin: &MultiLocation,
 		message: &mut Xcm<Call>,
 		max_weight: Weight,
-		_weight_credit: &mut Weight,
+		_properties: &mut Properties,
 	) -> Result<(), ()> {
 		log::trace!(
 			target: "xcm::barriers",
-			"AllowTopLevelPaidExecutionFrom origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
-			origin, message, max_weight, _weight_credit,
+			"AllowTopLevelPaidExecutionFrom origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, message, max_weight, _properties,
 		);
 		ensure!(T::contains(origin), ());
 		let mut iter = message.0.iter_mut();
@@ -93,24 +93,54 @@ impl<T: Contains<MultiLocation>> ShouldExecute for AllowTopLevelPaidExecutionFro
 
 /// Allows execution from any origin that is contained in `T` (i.e. `T::Contains(origin)`) without any payments.
 /// Use only for executions from trusted origin groups.
+///
+/// Prefer `AllowExplicitUnpaidExecutionFrom`, which only lets through messages which ask for free
+/// execution.
 pub struct AllowUnpaidExecutionFrom<T>(PhantomData<T>);
 impl<T: Contains<MultiLocation>> ShouldExecute for AllowUnpaidExecutionFrom<T> {
 	fn should_execute<Call>(
 		origin: &MultiLocation,
 		_message: &mut Xcm<Call>,
 		_max_weight: Weight,
-		_weight_credit: &mut Weight,
+		_properties: &mut Properties,
 	) -> Result<(), ()> {
 		log::trace!(
 			target: "xcm::barriers",
-			"AllowUnpaidExecutionFrom origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
-			origin, _message, _max_weight, _weight_credit,
+			"AllowUnpaidExecutionFrom origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, _message, _max_weight, _properties,
 		);
 		ensure!(T::contains(origin), ());
 		Ok(())
 	}
 }
 
+/// Allows execution from any origin that is contained in `T` (i.e. `T::Contains(origin)`) if the
+/// message explicitly asks for it with a leading `UnpaidExecution` instruction whose weight limit
+/// covers `max_weight`.
+///
+/// Use only for executions from trusted origin groups.
+pub struct AllowExplicitUnpaidExecutionFrom<T>(PhantomData<T>);
+impl<T: Contains<MultiLocation>> ShouldExecute for AllowExplicitUnpaidExecutionFrom<T> {
+	fn should_execute<Call>(
+		origin: &MultiLocation,
+		message: &mut Xcm<Call>,
+		max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ()> {
+		log::trace!(
+			target: "xcm::barriers",
+			"AllowExplicitUnpaidExecutionFrom origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, message, max_weight, _properties,
+		);
+		ensure!(T::contains(origin), ());
+		match message.0.first() {
+			Some(UnpaidExecution { weight_limit: Limited(m), .. }) if *m >= max_weight => Ok(()),
+			Some(UnpaidExecution { weight_limit: Unlimited, .. }) => Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
 /// Allows a message only if it is from a system-level child parachain.
 pub struct IsChildSystemParachain<ParaId>(PhantomData<ParaId>);
 impl<ParaId: IsSystem + From<u32>> Contains<MultiLocation> for IsChildSystemParachain<ParaId> {
@@ -130,12 +160,12 @@ impl<ResponseHandler: OnResponse> ShouldExecute for AllowKnownQueryResponses<Res
 		origin: &MultiLocation,
 		message: &mut Xcm<Call>,
 		_max_weight: Weight,
-		_weight_credit: &mut Weight,
+		_properties: &mut Properties,
 	) -> Result<(), ()> {
 		log::trace!(
 			target: "xcm::barriers",
-			"AllowKnownQueryResponses origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
-			origin, message, _max_weight, _weight_credit,
+			"AllowKnownQueryResponses origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, message, _max_weight, _properties,
 		);
 		match message.0.first() {
 			Some(QueryResponse { query_id, .. })
@@ -154,12 +184,12 @@ impl<T: Contains<MultiLocation>> ShouldExecute for AllowSubscriptionsFrom<T> {
 		origin: &MultiLocation,
 		message: &mut Xcm<Call>,
 		_max_weight: Weight,
-		_weight_credit: &mut Weight,
+		_properties: &mut Properties,
 	) -> Result<(), ()> {
 		log::trace!(
 			target: "xcm::barriers",
-			"AllowSubscriptionsFrom origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
-			origin, message, _max_weight, _weight_credit,
+			"AllowSubscriptionsFrom origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, message, _max_weight, _properties,
 		);
 		ensure!(T::contains(origin), ());
 		match (message.0.len(), message.0.first()) {
@@ -168,3 +198,37 @@ impl<T: Contains<MultiLocation>> ShouldExecute for AllowSubscriptionsFrom<T> {
 		}
 	}
 }
+
+/// Sets the message ID of the `properties` to the `topic` of a trailing `SetTopic(topic)`
+/// instruction, if any, and then defers to `InnerBarrier`.
+///
+/// The trailing `SetTopic` is hidden from `InnerBarrier`, so that barriers which expect a
+/// message of a particular shape also allow it once it has been given a topic.
+pub struct TrailingSetTopicAsId<InnerBarrier>(PhantomData<InnerBarrier>);
+impl<InnerBarrier: ShouldExecute> ShouldExecute for TrailingSetTopicAsId<InnerBarrier> {
+	fn should_execute<Call>(
+		origin: &MultiLocation,
+		message: &mut Xcm<Call>,
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ()> {
+		log::trace!(
+			target: "xcm::barriers",
+			"TrailingSetTopicAsId origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
+			origin, message, max_weight, properties,
+		);
+		let topic = match message.0.last() {
+			Some(SetTopic(topic)) => Some(*topic),
+			_ => None,
+		};
+		if let Some(topic) = topic {
+			properties.message_id = Some(topic);
+			message.0.pop();
+		}
+		let result = InnerBarrier::should_execute(origin, message, max_weight, properties);
+		if let Some(topic) = topic {
+			message.0.push(SetTopic(topic));
+		}
+		result
+	}
+}
@@ -16,6 +16,7 @@
 
 //! Adapters to work with `frame_support::traits::Currency` through XCM.
 
+use super::MintLocation;
 use frame_support::traits::{ExistenceRequirement::AllowDeath, Get, WithdrawReasons};
 use sp_runtime::traits::{CheckedSub, SaturatedConversion};
 use sp_std::{convert::TryInto, marker::PhantomData, result};
@@ -50,13 +51,20 @@ impl From<Error> for XcmError {
 /// Simple adapter to use a currency as asset transactor. This type can be used as `type AssetTransactor` in
 /// `xcm::Config`.
 ///
+/// If `CheckedAccount` is `Some`, then teleports of the currency are tracked in the given account.
+/// When the currency is minted locally, the account holds the total amount which has been
+/// teleported out to other chains and not yet returned, so this amount may be reconciled against
+/// the supply of the currency on those chains. When the currency is minted elsewhere, it holds the
+/// total amount which has been teleported in. In either case, no more may be teleported back than
+/// it holds.
+///
 /// # Example
 /// ```
 /// use parity_scale_codec::Decode;
 /// use frame_support::{parameter_types, PalletId};
 /// use sp_runtime::traits::{AccountIdConversion, TrailingZeroInput};
 /// use xcm::latest::prelude::*;
-/// use xcm_builder::{ParentIsPreset, CurrencyAdapter, IsConcrete};
+/// use xcm_builder::{ParentIsPreset, CurrencyAdapter, IsConcrete, MintLocation};
 ///
 /// /// Our chain's account id.
 /// type AccountId = sp_runtime::AccountId32;
@@ -65,6 +73,8 @@ impl From<Error> for XcmError {
 /// parameter_types! {
 ///     pub RelayChain: MultiLocation = Parent.into();
 ///     pub CheckingAccount: AccountId = PalletId(*b"checking").into_account();
+///     pub LocalCheckingAccount: (AccountId, MintLocation) =
+///         (CheckingAccount::get(), MintLocation::Local);
 /// }
 ///
 /// /// Some items that implement `Convert<MultiLocation, AccountId>`. Can be more, but for now we just assume we accept
@@ -81,20 +91,58 @@ impl From<Error> for XcmError {
 ///     LocationConverter,
 ///     // Our chain's account ID type.
 ///     AccountId,
-///     // The checking account. Can be any deterministic inaccessible account.
-///     CheckingAccount,
+///     // The checking account along with where the currency is minted. The account can be any
+///     // deterministic inaccessible account.
+///     LocalCheckingAccount,
 /// >;
 /// ```
 pub struct CurrencyAdapter<Currency, Matcher, AccountIdConverter, AccountId, CheckedAccount>(
 	PhantomData<(Currency, Matcher, AccountIdConverter, AccountId, CheckedAccount)>,
 );
 
+impl<
+		Currency: frame_support::traits::Currency<AccountId>,
+		Matcher,
+		AccountIdConverter,
+		AccountId: Clone, // can't get away without it since Currency is generic over it.
+		CheckedAccount,
+	> CurrencyAdapter<Currency, Matcher, AccountIdConverter, AccountId, CheckedAccount>
+{
+	fn can_accrue_checked(_checked_account: AccountId, _amount: Currency::Balance) -> Result {
+		Ok(())
+	}
+	fn can_reduce_checked(checked_account: AccountId, amount: Currency::Balance) -> Result {
+		let new_balance = Currency::free_balance(&checked_account)
+			.checked_sub(&amount)
+			.ok_or(XcmError::NotWithdrawable)?;
+		Currency::ensure_can_withdraw(
+			&checked_account,
+			amount,
+			WithdrawReasons::TRANSFER,
+			new_balance,
+		)
+		.map_err(|_| XcmError::NotWithdrawable)
+	}
+	fn accrue_checked(checked_account: AccountId, amount: Currency::Balance) {
+		Currency::deposit_creating(&checked_account, amount);
+	}
+	fn reduce_checked(checked_account: AccountId, amount: Currency::Balance) {
+		let ok =
+			Currency::withdraw(&checked_account, amount, WithdrawReasons::TRANSFER, AllowDeath)
+				.is_ok();
+		debug_assert!(
+			ok,
+			"`can_check_in` or `can_check_out` must have returned `Ok` immediately prior; qed"
+		);
+	}
+}
+
 impl<
 		Matcher: MatchesFungible<Currency::Balance>,
 		AccountIdConverter: Convert<MultiLocation, AccountId>,
 		Currency: frame_support::traits::Currency<AccountId>,
 		AccountId: Clone, // can't get away without it since Currency is generic over it.
-		CheckedAccount: Get<Option<AccountId>>,
+		CheckedAccount: Get<Option<(AccountId, MintLocation)>>,
 	> TransactAsset
 	for CurrencyAdapter<Currency, Matcher, AccountIdConverter, AccountId, CheckedAccount>
 {
@@ -103,45 +151,53 @@ impl<
 		// Check we handle this asset.
 		let amount: Currency::Balance =
 			Matcher::matches_fungible(what).ok_or(Error::AssetNotFound)?;
-		if let Some(checked_account) = CheckedAccount::get() {
-			let new_balance = Currency::free_balance(&checked_account)
-				.checked_sub(&amount)
-				.ok_or(XcmError::NotWithdrawable)?;
-			Currency::ensure_can_withdraw(
-				&checked_account,
-				amount,
-				WithdrawReasons::TRANSFER,
-				new_balance,
-			)
-			.map_err(|_| XcmError::NotWithdrawable)?;
+		match CheckedAccount::get() {
+			// We track this currency's teleports to ensure no more come in than have gone out.
+			Some((checked_account, MintLocation::Local)) =>
+				Self::can_reduce_checked(checked_account, amount),
+			// We track this currency's teleports to ensure no more go out than have come in.
+			Some((checked_account, MintLocation::NonLocal)) =>
+				Self::can_accrue_checked(checked_account, amount),
+			None => Ok(()),
 		}
-		Ok(())
 	}
 
 	fn check_in(_origin: &MultiLocation, what: &MultiAsset) {
 		log::trace!(target: "xcm::currency_adapter", "check_in origin: {:?}, what: {:?}", _origin, what);
 		if let Some(amount) = Matcher::matches_fungible(what) {
-			if let Some(checked_account) = CheckedAccount::get() {
-				let ok = Currency::withdraw(
-					&checked_account,
-					amount,
-					WithdrawReasons::TRANSFER,
-					AllowDeath,
-				)
-				.is_ok();
-				debug_assert!(
-					ok,
-					"`can_check_in` must have returned `true` immediately prior; qed"
-				);
+			match CheckedAccount::get() {
+				Some((checked_account, MintLocation::Local)) =>
+					Self::reduce_checked(checked_account, amount),
+				Some((checked_account, MintLocation::NonLocal)) =>
+					Self::accrue_checked(checked_account, amount),
+				None => (),
 			}
 		}
 	}
 
+	fn can_check_out(_dest: &MultiLocation, what: &MultiAsset) -> Result {
+		log::trace!(target: "xcm::currency_adapter", "can_check_out dest: {:?}, what: {:?}", _dest, what);
+		// Check we handle this asset.
+		let amount: Currency::Balance =
+			Matcher::matches_fungible(what).ok_or(Error::AssetNotFound)?;
+		match CheckedAccount::get() {
+			Some((checked_account, MintLocation::Local)) =>
+				Self::can_accrue_checked(checked_account, amount),
+			Some((checked_account, MintLocation::NonLocal)) =>
+				Self::can_reduce_checked(checked_account, amount),
+			None => Ok(()),
+		}
+	}
+
 	fn check_out(_dest: &MultiLocation, what: &MultiAsset) {
 		log::trace!(target: "xcm::currency_adapter", "check_out dest: {:?}, what: {:?}", _dest, what);
 		if let Some(amount) = Matcher::matches_fungible(what) {
-			if let Some(checked_account) = CheckedAccount::get() {
-				Currency::deposit_creating(&checked_account, amount);
+			match CheckedAccount::get() {
+				Some((checked_account, MintLocation::Local)) =>
+					Self::accrue_checked(checked_account, amount),
+				Some((checked_account, MintLocation::NonLocal)) =>
+					Self::reduce_checked(checked_account, amount),
+				None => (),
 			}
 		}
 	}
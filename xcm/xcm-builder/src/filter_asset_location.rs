@@ -18,8 +18,8 @@
 
 use frame_support::traits::Get;
 use sp_std::marker::PhantomData;
-use xcm::latest::{AssetId::Concrete, MultiAsset, MultiAssetFilter, MultiLocation};
-use xcm_executor::traits::FilterAssetLocation;
+use xcm::latest::{AssetId, AssetId::Concrete, MultiAsset, MultiAssetFilter, MultiLocation};
+use xcm_executor::traits::{FilterAssetLocation, TeleportLimit};
 
 /// Accepts an asset iff it is a native asset.
 pub struct NativeAsset;
@@ -39,3 +39,17 @@ impl<T: Get<(MultiAssetFilter, MultiLocation)>> FilterAssetLocation for Case<T>
 		a.contains(asset) && &o == origin
 	}
 }
+
+/// Limits the amount of the asset given by `T`'s `Get` implementation which may be teleported to or
+/// from its location in a single message. Other assets and locations are left unlimited.
+pub struct MaxTeleportAmount<T>(PhantomData<T>);
+impl<T: Get<(AssetId, MultiLocation, u128)>> TeleportLimit for MaxTeleportAmount<T> {
+	fn max_teleport_amount(id: &AssetId, location: &MultiLocation) -> Option<u128> {
+		let (i, l, limit) = T::get();
+		if &i == id && &l == location {
+			Some(limit)
+		} else {
+			None
+		}
+	}
+}
@@ -17,94 +17,74 @@
 //! Adapters to work with `frame_support::traits::tokens::fungibles` through XCM.
 
 use frame_support::traits::{tokens::fungibles, Contains, Get};
-use sp_std::{borrow::Borrow, marker::PhantomData, prelude::*, result};
-use xcm::latest::{
-	AssetId::{Abstract, Concrete},
-	Error as XcmError,
-	Fungibility::Fungible,
-	Junction, MultiAsset, MultiLocation, Result,
-};
+use sp_std::{marker::PhantomData, result};
+use xcm::latest::{Error as XcmError, MultiAsset, MultiLocation, Result};
 use xcm_executor::traits::{Convert, Error as MatchError, MatchesFungibles, TransactAsset};
 
-/// Converter struct implementing `AssetIdConversion` converting a numeric asset ID (must be `TryFrom/TryInto<u128>`) into
-/// a `GeneralIndex` junction, prefixed by some `MultiLocation` value. The `MultiLocation` value will typically be a
-/// `PalletInstance` junction.
-pub struct AsPrefixedGeneralIndex<Prefix, AssetId, ConvertAssetId>(
-	PhantomData<(Prefix, AssetId, ConvertAssetId)>,
-);
-impl<Prefix: Get<MultiLocation>, AssetId: Clone, ConvertAssetId: Convert<u128, AssetId>>
-	Convert<MultiLocation, AssetId> for AsPrefixedGeneralIndex<Prefix, AssetId, ConvertAssetId>
-{
-	fn convert_ref(id: impl Borrow<MultiLocation>) -> result::Result<AssetId, ()> {
-		let prefix = Prefix::get();
-		let id = id.borrow();
-		if prefix.parent_count() != id.parent_count() ||
-			prefix
-				.interior()
-				.iter()
-				.enumerate()
-				.any(|(index, junction)| id.interior().at(index) != Some(junction))
-		{
-			return Err(())
-		}
-		match id.interior().at(prefix.interior().len()) {
-			Some(Junction::GeneralIndex(id)) => ConvertAssetId::convert_ref(id),
-			_ => Err(()),
-		}
+/// The location at which an asset is minted, as seen from the point of view of this chain.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MintLocation {
+	/// The asset is minted on this chain, so teleporting it out is tracked as an increase in, and
+	/// teleporting it back in as a decrease in, the checking account. The checking account thus
+	/// holds the total amount of the asset which lives on other chains.
+	Local,
+	/// The asset is minted on some other chain, so teleporting it in is tracked as an increase in,
+	/// and teleporting it back out as a decrease in, the checking account. The checking account
+	/// thus holds the total amount of the asset which has been teleported to this chain.
+	NonLocal,
+}
+
+/// Determines whether the teleports of a given asset are tracked in a checking account, and if so
+/// where the asset is minted.
+pub trait AssetChecking<AssetId> {
+	/// Return the mint location of `asset` if its teleports are to be tracked, or `None` if not.
+	fn asset_checking(asset: &AssetId) -> Option<MintLocation>;
+}
+
+/// Track the teleports of no asset.
+pub struct NoChecking;
+impl<AssetId> AssetChecking<AssetId> for NoChecking {
+	fn asset_checking(_: &AssetId) -> Option<MintLocation> {
+		None
 	}
-	fn reverse_ref(what: impl Borrow<AssetId>) -> result::Result<MultiLocation, ()> {
-		let mut location = Prefix::get();
-		let id = ConvertAssetId::reverse_ref(what)?;
-		location.push_interior(Junction::GeneralIndex(id)).map_err(|_| ())?;
-		Ok(location)
+}
+
+/// Track the teleports of the assets in `T`, which are all minted locally.
+pub struct LocalMint<T>(PhantomData<T>);
+impl<AssetId, T: Contains<AssetId>> AssetChecking<AssetId> for LocalMint<T> {
+	fn asset_checking(asset: &AssetId) -> Option<MintLocation> {
+		match T::contains(asset) {
+			true => Some(MintLocation::Local),
+			false => None,
+		}
 	}
 }
 
-pub struct ConvertedConcreteAssetId<AssetId, Balance, ConvertAssetId, ConvertBalance>(
-	PhantomData<(AssetId, Balance, ConvertAssetId, ConvertBalance)>,
-);
-impl<
-		AssetId: Clone,
-		Balance: Clone,
-		ConvertAssetId: Convert<MultiLocation, AssetId>,
-		ConvertBalance: Convert<u128, Balance>,
-	> MatchesFungibles<AssetId, Balance>
-	for ConvertedConcreteAssetId<AssetId, Balance, ConvertAssetId, ConvertBalance>
-{
-	fn matches_fungibles(a: &MultiAsset) -> result::Result<(AssetId, Balance), MatchError> {
-		let (amount, id) = match (&a.fun, &a.id) {
-			(Fungible(ref amount), Concrete(ref id)) => (amount, id),
-			_ => return Err(MatchError::AssetNotFound),
-		};
-		let what =
-			ConvertAssetId::convert_ref(id).map_err(|_| MatchError::AssetIdConversionFailed)?;
-		let amount = ConvertBalance::convert_ref(amount)
-			.map_err(|_| MatchError::AmountToBalanceConversionFailed)?;
-		Ok((what, amount))
+/// Track the teleports of the assets in `T`, which are all minted on some other chain.
+pub struct NonLocalMint<T>(PhantomData<T>);
+impl<AssetId, T: Contains<AssetId>> AssetChecking<AssetId> for NonLocalMint<T> {
+	fn asset_checking(asset: &AssetId) -> Option<MintLocation> {
+		match T::contains(asset) {
+			true => Some(MintLocation::NonLocal),
+			false => None,
+		}
 	}
 }
 
-pub struct ConvertedAbstractAssetId<AssetId, Balance, ConvertAssetId, ConvertBalance>(
-	PhantomData<(AssetId, Balance, ConvertAssetId, ConvertBalance)>,
-);
-impl<
-		AssetId: Clone,
-		Balance: Clone,
-		ConvertAssetId: Convert<Vec<u8>, AssetId>,
-		ConvertBalance: Convert<u128, Balance>,
-	> MatchesFungibles<AssetId, Balance>
-	for ConvertedAbstractAssetId<AssetId, Balance, ConvertAssetId, ConvertBalance>
+/// Track the teleports of the assets in `L`, which are minted locally, and of those in `R`, which
+/// are minted on some other chain. `L` takes precedence should an asset be in both.
+pub struct DualMint<L, R>(PhantomData<(L, R)>);
+impl<AssetId, L: Contains<AssetId>, R: Contains<AssetId>> AssetChecking<AssetId>
+	for DualMint<L, R>
 {
-	fn matches_fungibles(a: &MultiAsset) -> result::Result<(AssetId, Balance), MatchError> {
-		let (amount, id) = match (&a.fun, &a.id) {
-			(Fungible(ref amount), Abstract(ref id)) => (amount, id),
-			_ => return Err(MatchError::AssetNotFound),
-		};
-		let what =
-			ConvertAssetId::convert_ref(id).map_err(|_| MatchError::AssetIdConversionFailed)?;
-		let amount = ConvertBalance::convert_ref(amount)
-			.map_err(|_| MatchError::AmountToBalanceConversionFailed)?;
-		Ok((what, amount))
+	fn asset_checking(asset: &AssetId) -> Option<MintLocation> {
+		if L::contains(asset) {
+			Some(MintLocation::Local)
+		} else if R::contains(asset) {
+			Some(MintLocation::NonLocal)
+		} else {
+			None
+		}
 	}
 }
 
@@ -148,12 +128,47 @@ pub struct FungiblesMutateAdapter<
 	CheckAsset,
 	CheckingAccount,
 >(PhantomData<(Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount)>);
+impl<
+		Assets: fungibles::Mutate<AccountId>,
+		Matcher,
+		AccountIdConverter,
+		AccountId: Clone, // can't get away without it since Currency is generic over it.
+		CheckAsset,
+		CheckingAccount: Get<AccountId>,
+	>
+	FungiblesMutateAdapter<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
+{
+	fn can_accrue_checked(_asset_id: Assets::AssetId, _amount: Assets::Balance) -> Result {
+		Ok(())
+	}
+	fn can_reduce_checked(asset_id: Assets::AssetId, amount: Assets::Balance) -> Result {
+		let checking_account = CheckingAccount::get();
+		Assets::can_withdraw(asset_id, &checking_account, amount)
+			.into_result()
+			.map_err(|_| XcmError::NotWithdrawable)
+			.map(|_| ())
+	}
+	fn accrue_checked(asset_id: Assets::AssetId, amount: Assets::Balance) {
+		let checking_account = CheckingAccount::get();
+		let ok = Assets::mint_into(asset_id, &checking_account, amount).is_ok();
+		debug_assert!(ok, "`mint_into` cannot generally fail; qed");
+	}
+	fn reduce_checked(asset_id: Assets::AssetId, amount: Assets::Balance) {
+		let checking_account = CheckingAccount::get();
+		let ok = Assets::burn_from(asset_id, &checking_account, amount).is_ok();
+		debug_assert!(
+			ok,
+			"`can_check_in` or `can_check_out` must have returned `Ok` immediately prior; qed"
+		);
+	}
+}
+
 impl<
 		Assets: fungibles::Mutate<AccountId>,
 		Matcher: MatchesFungibles<Assets::AssetId, Assets::Balance>,
 		AccountIdConverter: Convert<MultiLocation, AccountId>,
 		AccountId: Clone, // can't get away without it since Currency is generic over it.
-		CheckAsset: Contains<Assets::AssetId>,
+		CheckAsset: AssetChecking<Assets::AssetId>,
 		CheckingAccount: Get<AccountId>,
 	> TransactAsset
 	for FungiblesMutateAdapter<
@@ -173,14 +188,13 @@ impl<
 		);
 		// Check we handle this asset.
 		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
-		if CheckAsset::contains(&asset_id) {
-			// This is an asset whose teleports we track.
-			let checking_account = CheckingAccount::get();
-			Assets::can_withdraw(asset_id, &checking_account, amount)
-				.into_result()
-				.map_err(|_| XcmError::NotWithdrawable)?;
+		match CheckAsset::asset_checking(&asset_id) {
+			// We track this asset's teleports to ensure no more come in than have gone out.
+			Some(MintLocation::Local) => Self::can_reduce_checked(asset_id, amount),
+			// We track this asset's teleports to ensure no more go out than have come in.
+			Some(MintLocation::NonLocal) => Self::can_accrue_checked(asset_id, amount),
+			None => Ok(()),
 		}
-		Ok(())
 	}
 
 	fn check_in(_origin: &MultiLocation, what: &MultiAsset) {
@@ -190,17 +204,29 @@ impl<
 			_origin, what
 		);
 		if let Ok((asset_id, amount)) = Matcher::matches_fungibles(what) {
-			if CheckAsset::contains(&asset_id) {
-				let checking_account = CheckingAccount::get();
-				let ok = Assets::burn_from(asset_id, &checking_account, amount).is_ok();
-				debug_assert!(
-					ok,
-					"`can_check_in` must have returned `true` immediately prior; qed"
-				);
+			match CheckAsset::asset_checking(&asset_id) {
+				Some(MintLocation::Local) => Self::reduce_checked(asset_id, amount),
+				Some(MintLocation::NonLocal) => Self::accrue_checked(asset_id, amount),
+				None => (),
 			}
 		}
 	}
 
+	fn can_check_out(_dest: &MultiLocation, what: &MultiAsset) -> Result {
+		log::trace!(
+			target: "xcm::fungibles_adapter",
+			"can_check_out dest: {:?}, what: {:?}",
+			_dest, what
+		);
+		// Check we handle this asset.
+		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
+		match CheckAsset::asset_checking(&asset_id) {
+			Some(MintLocation::Local) => Self::can_accrue_checked(asset_id, amount),
+			Some(MintLocation::NonLocal) => Self::can_reduce_checked(asset_id, amount),
+			None => Ok(()),
+		}
+	}
+
 	fn check_out(_dest: &MultiLocation, what: &MultiAsset) {
 		log::trace!(
 			target: "xcm::fungibles_adapter",
@@ -208,10 +234,10 @@ impl<
 			_dest, what
 		);
 		if let Ok((asset_id, amount)) = Matcher::matches_fungibles(what) {
-			if CheckAsset::contains(&asset_id) {
-				let checking_account = CheckingAccount::get();
-				let ok = Assets::mint_into(asset_id, &checking_account, amount).is_ok();
-				debug_assert!(ok, "`mint_into` cannot generally fail; qed");
+			match CheckAsset::asset_checking(&asset_id) {
+				Some(MintLocation::Local) => Self::accrue_checked(asset_id, amount),
+				Some(MintLocation::NonLocal) => Self::reduce_checked(asset_id, amount),
+				None => (),
 			}
 		}
 	}
@@ -262,7 +288,7 @@ impl<
 		Matcher: MatchesFungibles<Assets::AssetId, Assets::Balance>,
 		AccountIdConverter: Convert<MultiLocation, AccountId>,
 		AccountId: Clone, // can't get away without it since Currency is generic over it.
-		CheckAsset: Contains<Assets::AssetId>,
+		CheckAsset: AssetChecking<Assets::AssetId>,
 		CheckingAccount: Get<AccountId>,
 	> TransactAsset
 	for FungiblesAdapter<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
@@ -289,6 +315,17 @@ impl<
 		>::check_in(origin, what)
 	}
 
+	fn can_check_out(dest: &MultiLocation, what: &MultiAsset) -> Result {
+		FungiblesMutateAdapter::<
+			Assets,
+			Matcher,
+			AccountIdConverter,
+			AccountId,
+			CheckAsset,
+			CheckingAccount,
+		>::can_check_out(dest, what)
+	}
+
 	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
 		FungiblesMutateAdapter::<
 			Assets,
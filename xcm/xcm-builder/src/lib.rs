@@ -30,42 +30,68 @@ pub mod test_utils;
 
 mod location_conversion;
 pub use location_conversion::{
-	Account32Hash, AccountId32Aliases, AccountKey20Aliases, ChildParachainConvertsVia,
-	LocationInverter, ParentIsPreset, SiblingParachainConvertsVia,
+	location_of_account, Account32Hash, AccountId32Aliases, AccountKey20Aliases,
+	ChildParachainConvertsVia, HashedLocation, ParentIsPreset, SiblingParachainConvertsVia,
 };
 
 mod origin_conversion;
 pub use origin_conversion::{
 	BackingToPlurality, ChildParachainAsNative, ChildSystemParachainAsSuperuser, EnsureXcmOrigin,
-	ParentAsSuperuser, RelayChainAsNative, SiblingParachainAsNative,
-	SiblingSystemParachainAsSuperuser, SignedAccountId32AsNative, SignedAccountKey20AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation,
+	OriginToPluralityVoice, ParentAsSuperuser, PluralityAsOrigin, RelayChainAsNative,
+	SiblingParachainAsNative, SiblingSystemParachainAsSuperuser, SignedAccountId32AsNative,
+	SignedAccountKey20AsNative, SignedToAccountId32, SovereignSignedViaLocation,
 };
 
 mod barriers;
 pub use barriers::{
-	AllowKnownQueryResponses, AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom,
-	AllowUnpaidExecutionFrom, IsChildSystemParachain, TakeWeightCredit,
+	AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, IsChildSystemParachain,
+	TakeWeightCredit, TrailingSetTopicAsId,
 };
 
+mod asset_trap;
+pub use asset_trap::MinimumAmountFilter;
+
 mod currency_adapter;
 pub use currency_adapter::CurrencyAdapter;
 
+mod asset_conversion;
+pub use asset_conversion::{AsPrefixedGeneralIndex, ConvertedAbstractId, ConvertedConcreteId};
+#[allow(deprecated)]
+pub use asset_conversion::{ConvertedAbstractAssetId, ConvertedConcreteAssetId};
+
 mod fungibles_adapter;
 pub use fungibles_adapter::{
-	AsPrefixedGeneralIndex, ConvertedAbstractAssetId, ConvertedConcreteAssetId, FungiblesAdapter,
-	FungiblesMutateAdapter, FungiblesTransferAdapter,
+	AssetChecking, DualMint, FungiblesAdapter, FungiblesMutateAdapter, FungiblesTransferAdapter,
+	LocalMint, MintLocation, NoChecking, NonLocalMint,
 };
 
 mod weight;
 #[allow(deprecated)]
 pub use weight::FixedRateOfConcreteFungible;
 pub use weight::{
-	FixedRateOfFungible, FixedWeightBounds, TakeRevenue, UsingComponents, WeightInfoBounds,
+	FixedRateOfFungible, FixedWeightBounds, SwapFirstAssetTrader, TakeRevenue, UsingComponents,
+	WeightInfoBounds,
 };
 
 mod matches_fungible;
 pub use matches_fungible::{IsAbstract, IsConcrete};
 
 mod filter_asset_location;
-pub use filter_asset_location::{Case, NativeAsset};
+pub use filter_asset_location::{Case, MaxTeleportAmount, NativeAsset};
+
+mod pay;
+pub use pay::{LocatableAssetId, Pay, PayOverXcm, PaymentStatus};
+
+mod queue_sinks;
+pub use queue_sinks::{DmpSink, OnMessageProcessed, XcmpSink};
+
+mod routing;
+pub use routing::{HrmpChannelFeeFactor, WithHrmpDeliveryFee, WithUniqueTopic};
+
+mod universal_exports;
+pub use universal_exports::{
+	ensure_is_remote, BridgeBlobDispatcher, BridgeMessage, DispatchBlob, DispatchBlobError,
+	ExporterFor, HaulBlob, HaulBlobError, HaulBlobExporter, NetworkExportTable,
+	SovereignPaidRemoteExporter, UnpaidLocalExporter,
+};
@@ -16,18 +16,38 @@
 
 use frame_support::traits::Get;
 use parity_scale_codec::{Decode, Encode};
-use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{AccountIdConversion, TrailingZeroInput};
+use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash, TrailingZeroInput};
 use sp_std::{borrow::Borrow, marker::PhantomData};
 use xcm::latest::{Junction::*, Junctions::*, MultiLocation, NetworkId, Parent};
-use xcm_executor::traits::{Convert, InvertLocation};
+use xcm_executor::traits::Convert;
 
+/// Derives an `AccountId` for any [`MultiLocation`] by hashing its SCALE encoding (prefixed with
+/// `b"multiloc"`) using `Hasher`.
+///
+/// The conversion is one-way: the location cannot be recovered from the resulting account.
+pub struct HashedLocation<Hasher, AccountId>(PhantomData<(Hasher, AccountId)>);
+impl<Hasher: Hash, AccountId: From<[u8; 32]> + Clone> Convert<MultiLocation, AccountId>
+	for HashedLocation<Hasher, AccountId>
+where
+	Hasher::Output: Into<[u8; 32]>,
+{
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		Ok(Hasher::hash_of(&("multiloc", location.borrow())).into().into())
+	}
+
+	fn reverse_ref(_: impl Borrow<AccountId>) -> Result<MultiLocation, ()> {
+		Err(())
+	}
+}
+
+/// Derives an `AccountId` for any [`MultiLocation`] by hashing it with `blake2_256`. See
+/// [`HashedLocation`].
 pub struct Account32Hash<Network, AccountId>(PhantomData<(Network, AccountId)>);
 impl<Network: Get<NetworkId>, AccountId: From<[u8; 32]> + Into<[u8; 32]> + Clone>
 	Convert<MultiLocation, AccountId> for Account32Hash<Network, AccountId>
 {
 	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
-		Ok(("multiloc", location.borrow()).using_encoded(blake2_256).into())
+		HashedLocation::<BlakeTwo256, AccountId>::convert_ref(location)
 	}
 
 	fn reverse_ref(_: impl Borrow<AccountId>) -> Result<MultiLocation, ()> {
@@ -63,6 +83,8 @@ impl<AccountId: Decode + Eq + Clone> Convert<MultiLocation, AccountId>
 	}
 }
 
+/// Converts a child parachain [`MultiLocation`] (`Parachain(id)` with no parents) into its
+/// sovereign `AccountId`, as derived by `ParaId`'s [`AccountIdConversion`] implementation.
 pub struct ChildParachainConvertsVia<ParaId, AccountId>(PhantomData<(ParaId, AccountId)>);
 impl<ParaId: From<u32> + Into<u32> + AccountIdConversion<AccountId>, AccountId: Clone>
 	Convert<MultiLocation, AccountId> for ChildParachainConvertsVia<ParaId, AccountId>
@@ -84,6 +106,11 @@ impl<ParaId: From<u32> + Into<u32> + AccountIdConversion<AccountId>, AccountId:
 	}
 }
 
+/// Converts a sibling parachain [`MultiLocation`] (`../Parachain(id)`) into its sovereign
+/// `AccountId`, as derived by `ParaId`'s [`AccountIdConversion`] implementation.
+///
+/// `ParaId` should be a type with a different `TypeId` to the one used for child parachains (e.g.
+/// `Sibling`), so that the two kinds of sovereign account never collide.
 pub struct SiblingParachainConvertsVia<ParaId, AccountId>(PhantomData<(ParaId, AccountId)>);
 impl<ParaId: From<u32> + Into<u32> + AccountIdConversion<AccountId>, AccountId: Clone>
 	Convert<MultiLocation, AccountId> for SiblingParachainConvertsVia<ParaId, AccountId>
@@ -129,6 +156,7 @@ impl<Network: Get<NetworkId>, AccountId: From<[u8; 32]> + Into<[u8; 32]> + Clone
 	}
 }
 
+/// Extracts the `AccountKey20` from the passed `location` if the network matches.
 pub struct AccountKey20Aliases<Network, AccountId>(PhantomData<(Network, AccountId)>);
 impl<Network: Get<NetworkId>, AccountId: From<[u8; 20]> + Into<[u8; 20]> + Clone>
 	Convert<MultiLocation, AccountId> for AccountKey20Aliases<Network, AccountId>
@@ -153,54 +181,17 @@ impl<Network: Get<NetworkId>, AccountId: From<[u8; 20]> + Into<[u8; 20]> + Clone
 	}
 }
 
-/// Simple location inverter; give it this location's ancestry and it'll figure out the inverted
-/// location.
+/// Returns the [`MultiLocation`] which `Conversion` maps onto `who`, if any.
 ///
-/// # Example
-/// ## Network Topology
-/// ```txt
-///                    v Source
-/// Relay -> Para 1 -> Account20
-///       -> Para 2 -> Account32
-///                    ^ Target
-/// ```
-/// ```rust
-/// # use frame_support::parameter_types;
-/// # use xcm::latest::{MultiLocation, Junction::*, Junctions::{self, *}, NetworkId::Any};
-/// # use xcm_builder::LocationInverter;
-/// # use xcm_executor::traits::InvertLocation;
-/// # fn main() {
-/// parameter_types!{
-///     pub Ancestry: MultiLocation = X2(
-///         Parachain(1),
-///         AccountKey20 { network: Any, key: Default::default() },
-///     ).into();
-/// }
-///
-/// let input = MultiLocation::new(2, X2(Parachain(2), AccountId32 { network: Any, id: Default::default() }));
-/// let inverted = LocationInverter::<Ancestry>::invert_location(&input);
-/// assert_eq!(inverted, Ok(MultiLocation::new(
-///     2,
-///     X2(Parachain(1), AccountKey20 { network: Any, key: Default::default() }),
-/// )));
-/// # }
-/// ```
-pub struct LocationInverter<Ancestry>(PhantomData<Ancestry>);
-impl<Ancestry: Get<MultiLocation>> InvertLocation for LocationInverter<Ancestry> {
-	fn ancestry() -> MultiLocation {
-		Ancestry::get()
-	}
-	fn invert_location(location: &MultiLocation) -> Result<MultiLocation, ()> {
-		let mut ancestry = Ancestry::get();
-		let mut junctions = Here;
-		for _ in 0..location.parent_count() {
-			junctions = junctions
-				.pushed_with(ancestry.take_first_interior().unwrap_or(OnlyChild))
-				.map_err(|_| ())?;
-		}
-		let parents = location.interior().len() as u8;
-		Ok(MultiLocation::new(parents, junctions))
-	}
+/// Useful for off-chain tooling such as block explorers which need to show which location a
+/// sovereign or aliased account belongs to. Conversions which hash the location (e.g.
+/// [`Account32Hash`]) cannot be inverted and always yield `None`.
+pub fn location_of_account<Conversion, AccountId>(who: &AccountId) -> Option<MultiLocation>
+where
+	Conversion: Convert<MultiLocation, AccountId>,
+	AccountId: Clone,
+{
+	Conversion::reverse_ref(who).ok()
 }
 
 #[cfg(test)]
@@ -208,7 +199,20 @@ mod tests {
 	use super::*;
 
 	use frame_support::parameter_types;
-	use xcm::latest::{Junction, NetworkId::Any};
+	use polkadot_parachain::primitives::{Id as ParaId, Sibling};
+	use sp_runtime::AccountId32 as AccountId;
+	use xcm::latest::{InteriorMultiLocation, Junction, NetworkId::Any};
+
+	parameter_types! {
+		pub const TestNetwork: NetworkId = NetworkId::Polkadot;
+	}
+
+	fn golden(prefix: &[u8], suffix: &[u8]) -> AccountId {
+		let mut raw = [0u8; 32];
+		raw[..prefix.len()].copy_from_slice(prefix);
+		raw[prefix.len()..prefix.len() + suffix.len()].copy_from_slice(suffix);
+		raw.into()
+	}
 
 	fn account20() -> Junction {
 		AccountKey20 { network: Any, key: Default::default() }
@@ -227,17 +231,17 @@ mod tests {
 	// Inputs and outputs written as file paths:
 	//
 	// input location (source to target): ../../../para_2/account32_default
-	// ancestry (root to source): para_1/account20_default/account20_default
+	// universal location (root to source): para_1/account20_default/account20_default
 	// =>
 	// output (target to source): ../../para_1/account20_default/account20_default
 	#[test]
 	fn inverter_works_in_tree() {
 		parameter_types! {
-			pub Ancestry: MultiLocation = X3(Parachain(1), account20(), account20()).into();
+			pub UniversalLocation: InteriorMultiLocation = X3(Parachain(1), account20(), account20());
 		}
 
 		let input = MultiLocation::new(3, X2(Parachain(2), account32()));
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
+		let inverted = UniversalLocation::get().invert_target(&input).unwrap();
 		assert_eq!(inverted, MultiLocation::new(2, X3(Parachain(1), account20(), account20())));
 	}
 
@@ -246,13 +250,13 @@ mod tests {
 	// Relay -> Para 1 -> SmartContract -> Account
 	//          ^ Target
 	#[test]
-	fn inverter_uses_ancestry_as_inverted_location() {
+	fn inverter_uses_context_as_inverted_location() {
 		parameter_types! {
-			pub Ancestry: MultiLocation = X2(account20(), account20()).into();
+			pub UniversalLocation: InteriorMultiLocation = X2(account20(), account20());
 		}
 
 		let input = MultiLocation::grandparent();
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
+		let inverted = UniversalLocation::get().invert_target(&input).unwrap();
 		assert_eq!(inverted, X2(account20(), account20()).into());
 	}
 
@@ -261,24 +265,126 @@ mod tests {
 	// Relay -> Para 1 -> CollectivePallet -> Plurality
 	//          ^ Target
 	#[test]
-	fn inverter_uses_only_child_on_missing_ancestry() {
+	fn inverter_uses_only_child_on_missing_context() {
 		parameter_types! {
-			pub Ancestry: MultiLocation = X1(PalletInstance(5)).into();
+			pub UniversalLocation: InteriorMultiLocation = X1(PalletInstance(5));
 		}
 
 		let input = MultiLocation::grandparent();
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
-		assert_eq!(inverted, X2(PalletInstance(5), OnlyChild).into());
+		let inverted = UniversalLocation::get().invert_target(&input).unwrap();
+		assert_eq!(inverted, X2(OnlyChild, PalletInstance(5)).into());
 	}
 
 	#[test]
 	fn inverter_errors_when_location_is_too_large() {
 		parameter_types! {
-			pub Ancestry: MultiLocation = Here.into();
+			pub UniversalLocation: InteriorMultiLocation = Here;
 		}
 
 		let input = MultiLocation { parents: 99, interior: X1(Parachain(88)) };
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input);
+		let inverted = UniversalLocation::get().invert_target(&input);
 		assert_eq!(inverted, Err(()));
 	}
+
+	#[test]
+	fn parent_is_preset_matches_golden_account() {
+		let account = ParentIsPreset::<AccountId>::convert(Parent.into()).unwrap();
+		assert_eq!(account, golden(b"Parent", &[]));
+		assert_eq!(ParentIsPreset::<AccountId>::reverse(account), Ok(Parent.into()));
+		assert!(ParentIsPreset::<AccountId>::convert(MultiLocation::grandparent()).is_err());
+		assert!(ParentIsPreset::<AccountId>::convert(Here.into()).is_err());
+	}
+
+	#[test]
+	fn child_parachain_converts_to_golden_account() {
+		type Conv = ChildParachainConvertsVia<ParaId, AccountId>;
+		let account = Conv::convert(Parachain(2000).into()).unwrap();
+		assert_eq!(account, golden(b"para", &[0xd0, 0x07, 0, 0]));
+		assert_eq!(Conv::reverse(account), Ok(Parachain(2000).into()));
+		assert!(Conv::convert(MultiLocation::new(1, X1(Parachain(2000)))).is_err());
+	}
+
+	#[test]
+	fn sibling_parachain_converts_to_golden_account() {
+		type Conv = SiblingParachainConvertsVia<Sibling, AccountId>;
+		let location = MultiLocation::new(1, X1(Parachain(1000)));
+		let account = Conv::convert(location.clone()).unwrap();
+		assert_eq!(account, golden(b"sibl", &[0xe8, 0x03, 0, 0]));
+		assert_eq!(Conv::reverse(account), Ok(location));
+		assert!(Conv::convert(Parachain(1000).into()).is_err());
+		// Child and sibling sovereign accounts of the same para never collide.
+		assert_ne!(
+			ChildParachainConvertsVia::<ParaId, AccountId>::convert(Parachain(1000).into()),
+			Conv::convert(MultiLocation::new(1, X1(Parachain(1000)))),
+		);
+	}
+
+	#[test]
+	fn account_id32_aliases_respects_network() {
+		type Conv = AccountId32Aliases<TestNetwork, AccountId>;
+		let id = [7u8; 32];
+		let any: MultiLocation = X1(AccountId32 { network: Any, id }).into();
+		let matching: MultiLocation = X1(AccountId32 { network: NetworkId::Polkadot, id }).into();
+		let other: MultiLocation = X1(AccountId32 { network: NetworkId::Kusama, id }).into();
+		assert_eq!(Conv::convert(any), Ok(id.into()));
+		assert_eq!(Conv::convert_ref(&matching), Ok(id.into()));
+		assert_eq!(Conv::convert(other.clone()), Err(other));
+		assert_eq!(Conv::reverse(id.into()), Ok(matching));
+	}
+
+	#[test]
+	fn account_key20_aliases_respects_network() {
+		type Conv = AccountKey20Aliases<TestNetwork, [u8; 20]>;
+		let key = [7u8; 20];
+		let any: MultiLocation = X1(AccountKey20 { network: Any, key }).into();
+		let matching: MultiLocation = X1(AccountKey20 { network: NetworkId::Polkadot, key }).into();
+		let other: MultiLocation = X1(AccountKey20 { network: NetworkId::Kusama, key }).into();
+		assert_eq!(Conv::convert(any), Ok(key));
+		assert_eq!(Conv::convert_ref(&matching), Ok(key));
+		assert_eq!(Conv::convert(other.clone()), Err(other));
+		assert_eq!(Conv::reverse(key), Ok(matching));
+	}
+
+	#[test]
+	fn account32_hash_matches_golden_account() {
+		type Conv = Account32Hash<TestNetwork, AccountId>;
+		let account = Conv::convert(MultiLocation::new(1, X1(Parachain(1000)))).unwrap();
+		assert_eq!(
+			account,
+			AccountId::new(hex_literal::hex!(
+				"427a39236419b06ee84530be93771fdcad53159e52219e76eb0a6164fb0c9d79"
+			)),
+		);
+		let account = Conv::convert(Here.into()).unwrap();
+		assert_eq!(
+			account,
+			AccountId::new(hex_literal::hex!(
+				"f6efbb79fed97b42ae24d7c051d9fbec8102a80168a62c93438bd82390248fb2"
+			)),
+		);
+		assert_eq!(
+			HashedLocation::<BlakeTwo256, AccountId>::convert(Here.into()),
+			Ok(account.clone())
+		);
+		assert!(Conv::reverse(account).is_err());
+	}
+
+	#[test]
+	fn location_of_account_inverts_reversible_conversions() {
+		type Conv = (
+			ParentIsPreset<AccountId>,
+			ChildParachainConvertsVia<ParaId, AccountId>,
+			SiblingParachainConvertsVia<Sibling, AccountId>,
+			AccountId32Aliases<TestNetwork, AccountId>,
+		);
+		let locations: Vec<MultiLocation> =
+			vec![Parent.into(), Parachain(2000).into(), MultiLocation::new(1, X1(Parachain(2000)))];
+		for location in locations {
+			let account = Conv::convert_ref(&location).unwrap();
+			assert_eq!(location_of_account::<Conv, _>(&account), Some(location));
+		}
+
+		let hashed = Account32Hash::<TestNetwork, AccountId>::convert(Here.into()).unwrap();
+		assert_eq!(location_of_account::<Account32Hash<TestNetwork, AccountId>, _>(&hashed), None);
+	}
 }
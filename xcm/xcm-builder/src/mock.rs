@@ -17,7 +17,8 @@
 use crate::{barriers::AllowSubscriptionsFrom, test_utils::*};
 pub use crate::{
 	AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
-	FixedRateOfFungible, FixedWeightBounds, LocationInverter, TakeWeightCredit,
+	FixedRateOfFungible, FixedWeightBounds, HaulBlob, HaulBlobError, MaxTeleportAmount,
+	TakeWeightCredit,
 };
 pub use frame_support::{
 	dispatch::{
@@ -25,7 +26,7 @@ pub use frame_support::{
 	},
 	ensure, parameter_types,
 	sp_runtime::DispatchErrorWithPostInfo,
-	traits::{Contains, Get, IsInVec},
+	traits::{Contains, CrateVersion, Get, IsInVec, PalletInfoData, PalletsInfoAccess},
 	weights::{GetDispatchInfo, PostDispatchInfo},
 };
 pub use parity_scale_codec::{Decode, Encode};
@@ -37,7 +38,10 @@ pub use sp_std::{
 };
 pub use xcm::latest::prelude::*;
 pub use xcm_executor::{
-	traits::{ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse, TransactAsset},
+	traits::{
+		ConvertOrigin, ExportXcm, FilterAssetLocation, OnResponse, QueryHandler,
+		QueryResponseStatus, TransactAsset,
+	},
 	Assets, Config,
 };
 
@@ -108,8 +112,64 @@ pub fn sent_xcm() -> Vec<(MultiLocation, opaque::Xcm)> {
 }
 pub struct TestSendXcm;
 impl SendXcm for TestSendXcm {
-	fn send_xcm(dest: impl Into<MultiLocation>, msg: opaque::Xcm) -> SendResult {
-		SENT_XCM.with(|q| q.borrow_mut().push((dest.into(), msg)));
+	type Ticket = (MultiLocation, opaque::Xcm);
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<opaque::Xcm>,
+	) -> SendResult<(MultiLocation, opaque::Xcm)> {
+		let pair = (dest.take().unwrap(), msg.take().unwrap());
+		Ok((pair, DeliveryPrice::get()))
+	}
+	fn deliver(pair: (MultiLocation, opaque::Xcm)) -> Result<XcmHash, SendError> {
+		let hash = pair.1.using_encoded(sp_io::hashing::blake2_256);
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok(hash)
+	}
+}
+
+pub type ExportTicket = (NetworkId, u32, InteriorMultiLocation, InteriorMultiLocation, opaque::Xcm);
+thread_local! {
+	pub static EXPORTED_XCM: RefCell<Vec<ExportTicket>> = RefCell::new(Vec::new());
+}
+pub fn exported_xcm() -> Vec<ExportTicket> {
+	EXPORTED_XCM.with(|q| (*q.borrow()).clone())
+}
+pub struct TestMessageExporter;
+impl ExportXcm for TestMessageExporter {
+	type Ticket = ExportTicket;
+	fn validate(
+		network: NetworkId,
+		channel: u32,
+		universal_source: &mut Option<InteriorMultiLocation>,
+		dest: &mut Option<InteriorMultiLocation>,
+		msg: &mut Option<opaque::Xcm>,
+	) -> SendResult<ExportTicket> {
+		let ticket = (
+			network,
+			channel,
+			universal_source.take().ok_or(SendError::MissingArgument)?,
+			dest.take().ok_or(SendError::MissingArgument)?,
+			msg.take().ok_or(SendError::MissingArgument)?,
+		);
+		Ok((ticket, ExportPrice::get()))
+	}
+	fn deliver(ticket: ExportTicket) -> Result<XcmHash, SendError> {
+		let hash = ticket.4.using_encoded(sp_io::hashing::blake2_256);
+		EXPORTED_XCM.with(|q| q.borrow_mut().push(ticket));
+		Ok(hash)
+	}
+}
+
+thread_local! {
+	pub static HAULED_BLOBS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+}
+pub fn hauled_blobs() -> Vec<Vec<u8>> {
+	HAULED_BLOBS.with(|q| (*q.borrow()).clone())
+}
+pub struct TestBridge;
+impl HaulBlob for TestBridge {
+	fn haul_blob(blob: Vec<u8>) -> Result<(), HaulBlobError> {
+		HAULED_BLOBS.with(|q| q.borrow_mut().push(blob));
 		Ok(())
 	}
 }
@@ -126,6 +186,14 @@ pub fn add_asset<AssetArg: Into<MultiAsset>>(who: u64, what: AssetArg) {
 
 pub struct TestAssetTransactor;
 impl TransactAsset for TestAssetTransactor {
+	fn can_check_in(_origin: &MultiLocation, _what: &MultiAsset) -> Result<(), XcmError> {
+		Ok(())
+	}
+
+	fn can_check_out(_dest: &MultiLocation, _what: &MultiAsset) -> Result<(), XcmError> {
+		Ok(())
+	}
+
 	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> Result<(), XcmError> {
 		let who = to_account(who.clone()).map_err(|_| XcmError::LocationCannotHold)?;
 		add_asset(who, what.clone());
@@ -250,8 +318,50 @@ pub fn response(query_id: u64) -> Option<Response> {
 	})
 }
 
+/// Registers queries with `TestResponseHandler`.
+pub struct TestQueryHandler;
+impl QueryHandler for TestQueryHandler {
+	type BlockNumber = u64;
+
+	fn new_query(responder: impl Into<MultiLocation>, _timeout: u64) -> QueryId {
+		let query_id = QUERIES.with(|q| q.borrow().len() as u64);
+		expect_response(query_id, responder.into());
+		query_id
+	}
+
+	fn report_outcome(
+		message: &mut Xcm<()>,
+		responder: impl Into<MultiLocation>,
+		timeout: u64,
+	) -> Result<QueryId, XcmError> {
+		let responder = responder.into();
+		let dest = TestUniversalLocation::get()
+			.invert_target(&responder)
+			.map_err(|()| XcmError::MultiLocationNotInvertible)?;
+		let query_id = Self::new_query(responder, timeout);
+		let report_error = Xcm(vec![ReportError { dest, query_id, max_response_weight: 0 }]);
+		message.0.insert(0, SetAppendix(report_error));
+		Ok(query_id)
+	}
+
+	fn take_response(query_id: QueryId) -> QueryResponseStatus<u64> {
+		QUERIES.with(|q| {
+			let slot = q.borrow_mut().remove(&query_id);
+			match slot {
+				Some(ResponseSlot::Received(response)) =>
+					QueryResponseStatus::Ready { response, at: 0 },
+				Some(slot @ ResponseSlot::Expecting(..)) => {
+					q.borrow_mut().insert(query_id, slot);
+					QueryResponseStatus::Pending { timeout: 0 }
+				},
+				None => QueryResponseStatus::NotFound,
+			}
+		})
+	}
+}
+
 parameter_types! {
-	pub TestAncestry: MultiLocation = X1(Parachain(42)).into();
+	pub TestUniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(Polkadot), Parachain(42));
 	pub UnitWeightCost: Weight = 10;
 }
 parameter_types! {
@@ -262,6 +372,12 @@ parameter_types! {
 	// 1_000_000_000_000 => 1 unit of asset for 1 unit of Weight.
 	pub static WeightPrice: (AssetId, u128) = (From::from(Here), 1_000_000_000_000);
 	pub static MaxInstructions: u32 = 100;
+	pub static DeliveryPrice: MultiAssets = MultiAssets::new();
+	pub static ExportPrice: MultiAssets = MultiAssets::new();
+	pub static BridgeTable: Vec<(NetworkId, MultiLocation, Option<MultiAsset>)> = vec![];
+	pub static UniversalAliases: Vec<(MultiLocation, Junction)> = vec![];
+	pub static ParentTeleportLimit: (AssetId, MultiLocation, u128) =
+		(From::from(Parent), Parent.into(), u128::max_value());
 }
 
 pub type TestBarrier = (
@@ -272,6 +388,27 @@ pub type TestBarrier = (
 	AllowSubscriptionsFrom<IsInVec<AllowSubsFrom>>,
 );
 
+pub struct TestPalletsInfo;
+impl PalletsInfoAccess for TestPalletsInfo {
+	fn count() -> usize {
+		2
+	}
+	fn accumulate(acc: &mut Vec<PalletInfoData>) {
+		acc.push(PalletInfoData {
+			index: 0,
+			name: "System",
+			module_name: "pallet_system",
+			crate_version: CrateVersion { major: 1, minor: 10, patch: 1 },
+		});
+		acc.push(PalletInfoData {
+			index: 1,
+			name: "Balances",
+			module_name: "pallet_balances",
+			crate_version: CrateVersion { major: 1, minor: 42, patch: 69 },
+		});
+	}
+}
+
 pub struct TestConfig;
 impl Config for TestConfig {
 	type Call = TestCall;
@@ -280,7 +417,9 @@ impl Config for TestConfig {
 	type OriginConverter = TestOriginConverter;
 	type IsReserve = TestIsReserve;
 	type IsTeleporter = TestIsTeleporter;
-	type LocationInverter = LocationInverter<TestAncestry>;
+	type TeleportLimits = MaxTeleportAmount<ParentTeleportLimit>;
+	type UniversalLocation = TestUniversalLocation;
+	type CheckSuspension = ();
 	type Barrier = TestBarrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, TestCall, MaxInstructions>;
 	type Trader = FixedRateOfFungible<WeightPrice, ()>;
@@ -288,4 +427,7 @@ impl Config for TestConfig {
 	type AssetTrap = TestAssetTrap;
 	type AssetClaims = TestAssetTrap;
 	type SubscriptionService = TestSubscriptionService;
+	type PalletInstancesInfo = TestPalletsInfo;
+	type MessageExporter = TestMessageExporter;
+	type UniversalAliases = IsInVec<UniversalAliases>;
 }
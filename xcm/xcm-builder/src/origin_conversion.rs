@@ -230,6 +230,58 @@ where
 	}
 }
 
+/// Converts a `Plurality` origin of the governance body `Body`, located at `Prefix`, into the local
+/// origin `BodyOrigin`.
+///
+/// Only the body's `Voice` or a strict majority of its members are accepted; any smaller part of the
+/// body is rejected. Several of these may be combined in a tuple to form a table mapping bodies
+/// (e.g. `BodyId::Executive`, `BodyId::Technical`) onto the corresponding collective origins:
+///
+/// ```ignore
+/// parameter_types! {
+///     pub const RelayChain: MultiLocation = MultiLocation::parent();
+///     pub const Executive: BodyId = BodyId::Executive;
+///     pub const Technical: BodyId = BodyId::Technical;
+///     pub CouncilOrigin: Origin = pallet_collective::RawOrigin::<AccountId, CouncilInstance>::Members(1, 1).into();
+///     pub TechCommitteeOrigin: Origin = pallet_collective::RawOrigin::<AccountId, TechInstance>::Members(1, 1).into();
+/// }
+/// type GovernanceOrigins = (
+///     PluralityAsOrigin<RelayChain, Executive, CouncilOrigin, Origin>,
+///     PluralityAsOrigin<RelayChain, Technical, TechCommitteeOrigin, Origin>,
+/// );
+/// ```
+pub struct PluralityAsOrigin<Prefix, Body, BodyOrigin, Origin>(
+	PhantomData<(Prefix, Body, BodyOrigin, Origin)>,
+);
+impl<Prefix: Get<MultiLocation>, Body: Get<BodyId>, BodyOrigin: Get<Origin>, Origin>
+	ConvertOrigin<Origin> for PluralityAsOrigin<Prefix, Body, BodyOrigin, Origin>
+{
+	fn convert_origin(
+		origin: impl Into<MultiLocation>,
+		kind: OriginKind,
+	) -> Result<Origin, MultiLocation> {
+		let origin = origin.into();
+		log::trace!(
+			target: "xcm::origin_conversion",
+			"PluralityAsOrigin origin: {:?}, kind: {:?}",
+			origin, kind,
+		);
+		if kind != OriginKind::Native {
+			return Err(origin)
+		}
+		let is_body = match origin.match_and_split(&Prefix::get()) {
+			Some(Junction::Plurality { id, part }) =>
+				*id == Body::get() && (*part == BodyPart::Voice || part.is_majority()),
+			_ => false,
+		};
+		if is_body {
+			Ok(BodyOrigin::get())
+		} else {
+			Err(origin)
+		}
+	}
+}
+
 /// `EnsureOrigin` barrier to convert from dispatch origin to XCM origin, if one exists.
 pub struct EnsureXcmOrigin<Origin, Conversion>(PhantomData<(Origin, Conversion)>);
 impl<Origin: OriginTrait + Clone, Conversion: Convert<Origin, MultiLocation>> EnsureOrigin<Origin>
@@ -306,3 +358,22 @@ where
 		})
 	}
 }
+
+/// `Convert` implementation to convert from an origin which passes the check of an `EnsureOrigin`
+/// into a voice of a given pluralistic `Body`.
+///
+/// Typically used when configuring `pallet-xcm` for allowing a governance origin, such as one of
+/// the track origins of `pallet-referenda`, to dispatch an XCM as the voice of a `Plurality`.
+pub struct OriginToPluralityVoice<Origin, EnsureBodyOrigin, Body>(
+	PhantomData<(Origin, EnsureBodyOrigin, Body)>,
+);
+impl<Origin, EnsureBodyOrigin: EnsureOrigin<Origin>, Body: Get<BodyId>>
+	Convert<Origin, MultiLocation> for OriginToPluralityVoice<Origin, EnsureBodyOrigin, Body>
+{
+	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
+		match EnsureBodyOrigin::try_origin(o) {
+			Ok(_) => Ok(Junction::Plurality { id: Body::get(), part: BodyPart::Voice }.into()),
+			Err(o) => Err(o),
+		}
+	}
+}
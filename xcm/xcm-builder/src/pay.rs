@@ -0,0 +1,178 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `PayOverXcm` struct for paying through XCM and getting the status back.
+
+use frame_support::{traits::Get, weights::Weight};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::{FilterAssetLocation, QueryHandler, QueryResponseStatus};
+
+/// The status of a payment.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PaymentStatus {
+	/// The payment has not yet concluded.
+	InProgress,
+	/// The payment was made.
+	Success,
+	/// The payment failed. Any funds which left the payer are not with the beneficiary.
+	Failure,
+	/// The payment is not known, e.g. because its status was already taken.
+	Unknown,
+}
+
+/// Something which can pay `amount` of some asset to a beneficiary and be asked later whether
+/// the payment went through.
+pub trait Pay {
+	/// The type by which we measure units of the currency in which we make payments.
+	type Balance;
+	/// The type by which we identify the beneficiaries to whom a payment may be made.
+	type Beneficiary;
+	/// The type by which we identify the asset in which a payment is made.
+	type AssetKind;
+	/// An identifier given to an individual payment.
+	type Id;
+	/// An error which could be returned by the `pay` function.
+	type Error;
+
+	/// Make a payment of `amount` of `asset_kind` to `who` and return an identifier by which its
+	/// status may be checked.
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error>;
+
+	/// Check how a payment has proceeded. Once this returns a concluded status the payment may
+	/// be forgotten, and later calls may return `PaymentStatus::Unknown`.
+	fn check_payment(id: Self::Id) -> PaymentStatus;
+}
+
+/// An asset together with the chain on which it should be paid out.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct LocatableAssetId {
+	/// The asset, from the context of the paying chain.
+	pub asset_id: AssetId,
+	/// The chain on which the beneficiary is paid, from the context of the paying chain.
+	pub location: MultiLocation,
+}
+
+/// Pays out from the account at the location `Payer` on this chain to a beneficiary on another
+/// chain.
+///
+/// The payment is withdrawn locally and sent to the chain given by the `LocatableAssetId`, as a
+/// teleport if `Teleporters` allows the asset to be teleported there and as a reserve transfer
+/// otherwise. The fees for executing the message on the destination are bought with the payment
+/// itself, so the beneficiary receives `amount` less those fees.
+///
+/// The message asks the destination to report its outcome back as the response to a query of
+/// `Querier`, which `check_payment` turns into the status of the payment. Should no report arrive
+/// within `Timeout` blocks, the status of the payment becomes unknown.
+///
+/// - `Payer`: The location of the account which makes the payments, e.g. the treasury.
+/// - `Executor`: The XCM executor of this chain, executing `Call`s.
+/// - `Querier`: Registers the queries for the outcome of the payments, e.g. the XCM pallet.
+/// - `Timeout`: The number of blocks for which to wait for the report of a payment.
+/// - `Teleporters`: Which assets may be teleported to which chains.
+/// - `UniversalLocation`: The universal location of this chain.
+/// - `MaxWeight`: The most weight the local part of a payment may use.
+pub struct PayOverXcm<
+	Payer,
+	Executor,
+	Call,
+	Querier,
+	Timeout,
+	Teleporters,
+	UniversalLocation,
+	MaxWeight,
+>(
+	PhantomData<(
+		Payer,
+		Executor,
+		Call,
+		Querier,
+		Timeout,
+		Teleporters,
+		UniversalLocation,
+		MaxWeight,
+	)>,
+);
+impl<
+		Payer: Get<MultiLocation>,
+		Executor: ExecuteXcm<Call>,
+		Call,
+		Querier: QueryHandler,
+		Timeout: Get<Querier::BlockNumber>,
+		Teleporters: FilterAssetLocation,
+		UniversalLocation: Get<InteriorMultiLocation>,
+		MaxWeight: Get<Weight>,
+	> Pay
+	for PayOverXcm<Payer, Executor, Call, Querier, Timeout, Teleporters, UniversalLocation, MaxWeight>
+{
+	type Balance = u128;
+	type Beneficiary = MultiLocation;
+	type AssetKind = LocatableAssetId;
+	type Id = QueryId;
+	type Error = XcmError;
+
+	fn pay(
+		who: &MultiLocation,
+		asset_kind: LocatableAssetId,
+		amount: u128,
+	) -> Result<QueryId, XcmError> {
+		let LocatableAssetId { asset_id, location: dest } = asset_kind;
+		let asset = MultiAsset { id: asset_id, fun: Fungible(amount) };
+		let fees = asset
+			.clone()
+			.reanchored(&dest, &UniversalLocation::get())
+			.map_err(|()| XcmError::MultiLocationNotInvertible)?;
+
+		let mut remote_message =
+			Xcm(vec![DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: who.clone() }]);
+		let query_id = Querier::report_outcome(&mut remote_message, dest.clone(), Timeout::get())?;
+		// The report goes after `BuyExecution`, which the barrier of the destination expects right
+		// after the assets are received.
+		remote_message.0.insert(0, BuyExecution { fees, weight_limit: Unlimited });
+
+		let transfer = if Teleporters::filter_asset_location(&asset, &dest) {
+			InitiateTeleport { assets: Wild(All), dest, xcm: remote_message }
+		} else {
+			DepositReserveAsset { assets: Wild(All), max_assets: 1, dest, xcm: remote_message }
+		};
+		let message = Xcm(vec![WithdrawAsset(asset.into()), transfer]);
+		Executor::execute_with_origin(Payer::get(), message, MaxWeight::get()).ensure_complete()?;
+
+		Ok(query_id)
+	}
+
+	fn check_payment(id: QueryId) -> PaymentStatus {
+		match Querier::take_response(id) {
+			QueryResponseStatus::Ready { response: Response::ExecutionResult(None), .. } =>
+				PaymentStatus::Success,
+			QueryResponseStatus::Ready { response: Response::ExecutionResult(Some(_)), .. } =>
+				PaymentStatus::Failure,
+			QueryResponseStatus::Pending { .. } => PaymentStatus::InProgress,
+			// Without a report the payment may or may not have been made, so it must not be
+			// considered failed and retried.
+			QueryResponseStatus::Ready { .. } |
+			QueryResponseStatus::TimedOut { .. } |
+			QueryResponseStatus::UnexpectedVersion |
+			QueryResponseStatus::NotFound => PaymentStatus::Unknown,
+		}
+	}
+}
@@ -0,0 +1,145 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Handlers for the downward and horizontal message queues of a parachain which process each
+//! message through a [`ProcessMessage`] implementation, such as
+//! [`xcm_executor::ProcessXcmMessage`].
+
+use frame_support::weights::Weight;
+use parity_scale_codec::{Decode, DecodeLimit};
+use polkadot_parachain::primitives::{
+	DmpMessageHandler, Id as ParaId, RelayChainBlockNumber, XcmpMessageFormat, XcmpMessageHandler,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{latest::prelude::*, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::traits::{MessageId, ProcessMessage, ProcessMessageError};
+
+/// Something which is told about every message which a queue handler has taken off its queue.
+pub trait OnMessageProcessed {
+	/// The message `id` from `origin` was processed. `success` is true only if its execution was
+	/// complete, and `weight` is the weight used in processing it.
+	fn on_processed(id: MessageId, origin: MultiLocation, success: bool, weight: Weight);
+
+	/// The message `id` from `origin` could not be processed at all.
+	fn on_failed(id: MessageId, origin: MultiLocation, error: ProcessMessageError);
+}
+
+impl OnMessageProcessed for () {
+	fn on_processed(_: MessageId, _: MultiLocation, _: bool, _: Weight) {}
+	fn on_failed(_: MessageId, _: MultiLocation, _: ProcessMessageError) {}
+}
+
+/// Process a single message, reporting its fate to `OnProcessed` and returning the weight used.
+fn process<Processor, OnProcessed>(
+	data: &[u8],
+	origin: MultiLocation,
+	weight_limit: Weight,
+) -> Weight
+where
+	Processor: ProcessMessage<Origin = MultiLocation>,
+	OnProcessed: OnMessageProcessed,
+{
+	let id = sp_io::hashing::blake2_256(data);
+	match Processor::process_message(data, origin.clone(), weight_limit) {
+		Ok((success, weight)) => {
+			OnProcessed::on_processed(id, origin, success, weight);
+			weight
+		},
+		Err(error) => {
+			OnProcessed::on_failed(id, origin, error);
+			0
+		},
+	}
+}
+
+/// A [`DmpMessageHandler`] which processes each downward message with `Processor`, from the
+/// context of the relay chain.
+///
+/// Messages are processed in order while weight remains. A message which needs more weight than
+/// remains is reported as overweight and dropped.
+pub struct DmpSink<Processor, OnProcessed>(PhantomData<(Processor, OnProcessed)>);
+impl<Processor, OnProcessed> DmpMessageHandler for DmpSink<Processor, OnProcessed>
+where
+	Processor: ProcessMessage<Origin = MultiLocation>,
+	OnProcessed: OnMessageProcessed,
+{
+	fn handle_dmp_messages(
+		iter: impl Iterator<Item = (RelayChainBlockNumber, Vec<u8>)>,
+		max_weight: Weight,
+	) -> Weight {
+		let mut weight_used: Weight = 0;
+		for (_sent_at, data) in iter {
+			let remaining = max_weight.saturating_sub(weight_used);
+			weight_used = weight_used.saturating_add(process::<Processor, OnProcessed>(
+				&data[..],
+				Parent.into(),
+				remaining,
+			));
+		}
+		weight_used
+	}
+}
+
+/// An [`XcmpMessageHandler`] which processes each horizontal message with `Processor`, from the
+/// context of the sibling parachain which sent it.
+///
+/// Only the [`XcmpMessageFormat::ConcatenatedVersionedXcm`] format is supported; blobs of any
+/// other format are dropped. Messages are processed in order while weight remains. A message which
+/// needs more weight than remains is reported as overweight and dropped.
+pub struct XcmpSink<Processor, OnProcessed>(PhantomData<(Processor, OnProcessed)>);
+impl<Processor, OnProcessed> XcmpMessageHandler for XcmpSink<Processor, OnProcessed>
+where
+	Processor: ProcessMessage<Origin = MultiLocation>,
+	OnProcessed: OnMessageProcessed,
+{
+	fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayChainBlockNumber, &'a [u8])>>(
+		iter: I,
+		max_weight: Weight,
+	) -> Weight {
+		let mut weight_used: Weight = 0;
+		for (sender, _sent_at, mut data) in iter {
+			if !matches!(
+				XcmpMessageFormat::decode(&mut data),
+				Ok(XcmpMessageFormat::ConcatenatedVersionedXcm)
+			) {
+				continue
+			}
+			let origin = MultiLocation::new(1, X1(Parachain(sender.into())));
+			while !data.is_empty() {
+				// Split off the next message, which is an encoded `VersionedXcm`.
+				let before = data;
+				if VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data)
+					.is_err()
+				{
+					OnProcessed::on_failed(
+						sp_io::hashing::blake2_256(before),
+						origin.clone(),
+						ProcessMessageError::BadFormat,
+					);
+					break
+				}
+				let message = &before[..before.len() - data.len()];
+				let remaining = max_weight.saturating_sub(weight_used);
+				weight_used = weight_used.saturating_add(process::<Processor, OnProcessed>(
+					message,
+					origin.clone(),
+					remaining,
+				));
+			}
+		}
+		weight_used
+	}
+}
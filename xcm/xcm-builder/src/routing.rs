@@ -0,0 +1,155 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Various implementations for `SendXcm`.
+
+use frame_support::{
+	storage::{storage_prefix, unhashed},
+	traits::Get,
+};
+use parity_scale_codec::Encode;
+use sp_arithmetic::{traits::One, FixedPointNumber, FixedU128};
+use sp_std::{marker::PhantomData, result::Result};
+use xcm::latest::prelude::*;
+
+/// The storage key of the nonce which `WithUniqueTopic` uses to make topics unique. It is prefixed
+/// like the storage of a pallet, so that it cannot clash with any other storage item.
+fn unique_topic_nonce_key() -> [u8; 32] {
+	storage_prefix(b"XcmBuilder", b"UniqueTopicNonce")
+}
+
+/// Return a topic for `message` to `destination` which differs from the topics of all messages
+/// delivered before it by this chain.
+///
+/// The topic is the hash of the destination and message together with a nonce, which is only
+/// incremented when a message is delivered, so that validating a message has no side effects.
+fn unique_topic(destination: &Option<MultiLocation>, message: &Xcm<()>) -> XcmHash {
+	let nonce: u64 = unhashed::get_or_default(&unique_topic_nonce_key());
+	(destination, message, nonce).using_encoded(sp_io::hashing::blake2_256)
+}
+
+/// Make sure that the next topic returned by `unique_topic` differs from those returned so far.
+fn bump_unique_topic_nonce() {
+	let key = unique_topic_nonce_key();
+	let nonce: u64 = unhashed::get_or_default(&key);
+	unhashed::put(&key, &nonce.wrapping_add(1));
+}
+
+/// Wrapper router which, if the message does not already end with a `SetTopic` instruction,
+/// appends one with a unique topic to it before sending it with `Inner`.
+///
+/// The topic, rather than the hash of the message, is returned as the message's ID, so that it
+/// may be used to trace the message on its way and to deduplicate it should it be retried.
+pub struct WithUniqueTopic<Inner>(PhantomData<Inner>);
+impl<Inner: SendXcm> SendXcm for WithUniqueTopic<Inner> {
+	type Ticket = (Inner::Ticket, XcmHash);
+
+	fn validate(
+		destination: &mut Option<MultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let mut xcm = message.take().ok_or(SendError::MissingArgument)?;
+		let (topic, appended) = match xcm.0.last() {
+			Some(SetTopic(topic)) => (*topic, false),
+			_ => {
+				let topic = unique_topic(destination, &xcm);
+				xcm.0.push(SetTopic(topic));
+				(topic, true)
+			},
+		};
+		let mut maybe_xcm = Some(xcm);
+		match Inner::validate(destination, &mut maybe_xcm) {
+			Ok((ticket, price)) => Ok(((ticket, topic), price)),
+			Err(e) => {
+				// Give the message back to later routers as we received it.
+				*message = maybe_xcm.map(|mut xcm| {
+					if appended {
+						xcm.0.pop();
+					}
+					xcm
+				});
+				Err(e)
+			},
+		}
+	}
+
+	fn deliver((ticket, topic): Self::Ticket) -> Result<XcmHash, SendError> {
+		Inner::deliver(ticket)?;
+		bump_unique_topic_nonce();
+		Ok(topic)
+	}
+}
+
+/// Means of finding out how congested the HRMP channel from this parachain to a sibling is.
+///
+/// Parachains would usually implement this by reading the fee factor of the channel from the
+/// relay-chain state proof, where it is stored under the
+/// `polkadot_primitives::v2::well_known_keys::hrmp_channel_fee_factor` key.
+pub trait HrmpChannelFeeFactor {
+	/// The factor by which the delivery fee for messages to the sibling parachain `para` should
+	/// be multiplied. This is one while the channel is not congested.
+	fn fee_factor(para: u32) -> FixedU128;
+}
+
+/// HRMP channels are never considered congested.
+impl HrmpChannelFeeFactor for () {
+	fn fee_factor(_: u32) -> FixedU128 {
+		FixedU128::one()
+	}
+}
+
+/// Wrapper router which charges for the delivery of messages to sibling parachains, on top of
+/// whatever `Inner` charges.
+///
+/// The fee is `(BaseFee + ByteFee * message_size) * factor`, paid in the asset `FeeAssetId`, where
+/// `factor` is the fee factor of the HRMP channel to the sibling as given by `FeeFactor`. Messages
+/// to any other destination are priced by `Inner` alone.
+pub struct WithHrmpDeliveryFee<Inner, FeeAssetId, BaseFee, ByteFee, FeeFactor>(
+	PhantomData<(Inner, FeeAssetId, BaseFee, ByteFee, FeeFactor)>,
+);
+impl<
+		Inner: SendXcm,
+		FeeAssetId: Get<AssetId>,
+		BaseFee: Get<u128>,
+		ByteFee: Get<u128>,
+		FeeFactor: HrmpChannelFeeFactor,
+	> SendXcm for WithHrmpDeliveryFee<Inner, FeeAssetId, BaseFee, ByteFee, FeeFactor>
+{
+	type Ticket = Inner::Ticket;
+
+	fn validate(
+		destination: &mut Option<MultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let fee = match (destination.as_ref(), message.as_ref()) {
+			(Some(MultiLocation { parents: 1, interior: X1(Parachain(id)) }), Some(xcm)) => {
+				let message_fee = (xcm.encoded_size() as u128).saturating_mul(ByteFee::get());
+				let fee_sum = BaseFee::get().saturating_add(message_fee);
+				FeeFactor::fee_factor(*id).saturating_mul_int(fee_sum)
+			},
+			_ => 0,
+		};
+		let (ticket, mut price) = Inner::validate(destination, message)?;
+		if fee > 0 {
+			price.push((FeeAssetId::get(), fee).into());
+		}
+		Ok((ticket, price))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		Inner::deliver(ticket)
+	}
+}
@@ -16,12 +16,12 @@
 
 // Shared test utilities and implementations for the XCM Builder.
 
-use frame_support::{dispatch::Weight, parameter_types};
+use frame_support::{parameter_types, weights::WeightMeter};
 use sp_std::vec::Vec;
 pub use xcm::latest::prelude::*;
 use xcm_executor::traits::{ClaimAssets, DropAssets, VersionChangeNotifier};
 pub use xcm_executor::{
-	traits::{ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse, TransactAsset},
+	traits::{ConvertOrigin, FilterAssetLocation, OnResponse, TransactAsset},
 	Assets, Config,
 };
 
@@ -53,16 +53,27 @@ impl VersionChangeNotifier for TestSubscriptionService {
 
 parameter_types! {
 	pub static TrappedAssets: Vec<(MultiLocation, MultiAssets)> = vec![];
+	pub static TrapContexts: Vec<XcmContext> = vec![];
 }
 
 pub struct TestAssetTrap;
 
 impl DropAssets for TestAssetTrap {
-	fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight {
+	fn drop_assets(
+		origin: &MultiLocation,
+		assets: Assets,
+		context: &XcmContext,
+		meter: &mut WeightMeter,
+	) {
+		if !meter.check_accrue(5) {
+			return
+		}
 		let mut t: Vec<(MultiLocation, MultiAssets)> = TrappedAssets::get();
 		t.push((origin.clone(), assets.into()));
 		TrappedAssets::set(t);
-		5
+		let mut c = TrapContexts::get();
+		c.push(context.clone());
+		TrapContexts::set(c);
 	}
 }
 
@@ -15,7 +15,10 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{mock::*, test_utils::*, *};
-use frame_support::{assert_err, weights::constants::WEIGHT_PER_SECOND};
+use frame_support::{
+	assert_err,
+	weights::{constants::WEIGHT_PER_SECOND, WeightMeter},
+};
 use xcm::latest::prelude::*;
 use xcm_executor::{traits::*, Config, XcmExecutor};
 
@@ -52,18 +55,22 @@ fn weigher_should_work() {
 	assert_eq!(<TestConfig as Config>::Weigher::weight(&mut message), Ok(30));
 }
 
+fn props(weight_credit: Weight) -> Properties {
+	Properties { weight_credit, message_id: None }
+}
+
 #[test]
 fn take_weight_credit_barrier_should_work() {
 	let mut message =
 		Xcm::<()>(vec![TransferAsset { assets: (Parent, 100).into(), beneficiary: Here.into() }]);
-	let mut weight_credit = 10;
-	let r = TakeWeightCredit::should_execute(&Parent.into(), &mut message, 10, &mut weight_credit);
+	let mut properties = Properties { weight_credit: 10, message_id: None };
+	let r = TakeWeightCredit::should_execute(&Parent.into(), &mut message, 10, &mut properties);
 	assert_eq!(r, Ok(()));
-	assert_eq!(weight_credit, 0);
+	assert_eq!(properties.weight_credit, 0);
 
-	let r = TakeWeightCredit::should_execute(&Parent.into(), &mut message, 10, &mut weight_credit);
+	let r = TakeWeightCredit::should_execute(&Parent.into(), &mut message, 10, &mut properties);
 	assert_eq!(r, Err(()));
-	assert_eq!(weight_credit, 0);
+	assert_eq!(properties.weight_credit, 0);
 }
 
 #[test]
@@ -77,7 +84,7 @@ fn allow_unpaid_should_work() {
 		&Parachain(1).into(),
 		&mut message,
 		10,
-		&mut 0,
+		&mut props(0),
 	);
 	assert_eq!(r, Err(()));
 
@@ -85,11 +92,92 @@ fn allow_unpaid_should_work() {
 		&Parent.into(),
 		&mut message,
 		10,
-		&mut 0,
+		&mut props(0),
+	);
+	assert_eq!(r, Ok(()));
+}
+
+#[test]
+fn allow_explicit_unpaid_should_work() {
+	let mut bad_message1 =
+		Xcm::<()>(vec![TransferAsset { assets: (Parent, 100).into(), beneficiary: Here.into() }]);
+	let mut bad_message2 = Xcm::<()>(vec![
+		UnpaidExecution { weight_limit: Limited(10), check_origin: Some(Parent.into()) },
+		TransferAsset { assets: (Parent, 100).into(), beneficiary: Here.into() },
+	]);
+	let mut good_message = Xcm::<()>(vec![
+		UnpaidExecution { weight_limit: Limited(20), check_origin: Some(Parent.into()) },
+		TransferAsset { assets: (Parent, 100).into(), beneficiary: Here.into() },
+	]);
+
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	let r = AllowExplicitUnpaidExecutionFrom::<IsInVec<AllowUnpaidFrom>>::should_execute(
+		&Parachain(1).into(),
+		&mut good_message,
+		20,
+		&mut props(0),
+	);
+	assert_eq!(r, Err(()));
+
+	let r = AllowExplicitUnpaidExecutionFrom::<IsInVec<AllowUnpaidFrom>>::should_execute(
+		&Parent.into(),
+		&mut bad_message1,
+		20,
+		&mut props(0),
+	);
+	assert_eq!(r, Err(()));
+
+	// The weight limit is too low.
+	let r = AllowExplicitUnpaidExecutionFrom::<IsInVec<AllowUnpaidFrom>>::should_execute(
+		&Parent.into(),
+		&mut bad_message2,
+		20,
+		&mut props(0),
+	);
+	assert_eq!(r, Err(()));
+
+	let r = AllowExplicitUnpaidExecutionFrom::<IsInVec<AllowUnpaidFrom>>::should_execute(
+		&Parent.into(),
+		&mut good_message,
+		20,
+		&mut props(0),
 	);
 	assert_eq!(r, Ok(()));
 }
 
+#[test]
+fn unpaid_execution_should_check_origin() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+	let transfer = TransferAsset {
+		assets: (Here, 100).into(),
+		beneficiary: X1(AccountIndex64 { index: 3, network: Any }).into(),
+	};
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: Some(Parachain(2).into()) },
+			transfer.clone(),
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::BadOrigin));
+	assert_eq!(assets(3), vec![]);
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			UnpaidExecution { weight_limit: Unlimited, check_origin: Some(Parachain(1).into()) },
+			transfer,
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(assets(3), vec![(Here, 100).into()]);
+}
+
 #[test]
 fn allow_paid_should_work() {
 	AllowPaidFrom::set(vec![Parent.into()]);
@@ -101,7 +189,7 @@ fn allow_paid_should_work() {
 		&Parachain(1).into(),
 		&mut message,
 		10,
-		&mut 0,
+		&mut props(0),
 	);
 	assert_eq!(r, Err(()));
 
@@ -116,7 +204,7 @@ fn allow_paid_should_work() {
 		&Parent.into(),
 		&mut underpaying_message,
 		30,
-		&mut 0,
+		&mut props(0),
 	);
 	assert_eq!(r, Err(()));
 
@@ -131,7 +219,7 @@ fn allow_paid_should_work() {
 		&Parachain(1).into(),
 		&mut paying_message,
 		30,
-		&mut 0,
+		&mut props(0),
 	);
 	assert_eq!(r, Err(()));
 
@@ -139,11 +227,40 @@ fn allow_paid_should_work() {
 		&Parent.into(),
 		&mut paying_message,
 		30,
-		&mut 0,
+		&mut props(0),
 	);
 	assert_eq!(r, Ok(()));
 }
 
+#[test]
+fn trailing_set_topic_as_id_should_work() {
+	AllowSubsFrom::set(vec![Parent.into()]);
+	let mut message = Xcm::<()>(vec![
+		SubscribeVersion { query_id: 42, max_response_weight: 5000 },
+		SetTopic([1; 32]),
+	]);
+
+	let r = AllowSubscriptionsFrom::<IsInVec<AllowSubsFrom>>::should_execute(
+		&Parent.into(),
+		&mut message,
+		10,
+		&mut props(0),
+	);
+	assert_eq!(r, Err(()));
+
+	let mut properties = props(0);
+	let r = TrailingSetTopicAsId::<AllowSubscriptionsFrom<IsInVec<AllowSubsFrom>>>::should_execute(
+		&Parent.into(),
+		&mut message,
+		10,
+		&mut properties,
+	);
+	assert_eq!(r, Ok(()));
+	assert_eq!(properties.message_id, Some([1; 32]));
+	// The topic is left in place for execution.
+	assert_eq!(message.0.last(), Some(&SetTopic([1; 32])));
+}
+
 #[test]
 fn paying_reserve_deposit_should_work() {
 	AllowPaidFrom::set(vec![Parent.into()]);
@@ -183,6 +300,91 @@ fn transfer_should_work() {
 	assert_eq!(sent_xcm(), vec![]);
 }
 
+#[test]
+fn asset_trap_should_receive_message_context() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+
+	let message = Xcm::<TestCall>(vec![
+		SetTopic([7; 32]),
+		WithdrawAsset((Here, 100).into()),
+		Trap(0),
+		ClearOrigin,
+	]);
+	let pre = <XcmExecutor<TestConfig> as ExecuteXcm<TestCall>>::prepare(message).unwrap();
+	let r =
+		<XcmExecutor<TestConfig> as ExecuteXcm<TestCall>>::execute(Parachain(1), pre, [3; 32], 0);
+	// The trap gets the weight of the instruction which wasn't executed and what it accrued on its
+	// meter is accounted for.
+	assert_eq!(r, Outcome::Incomplete(35, XcmError::Trap(0)));
+	assert_eq!(
+		TrapContexts::get(),
+		vec![XcmContext {
+			origin: Some(Parachain(1).into()),
+			message_id: [3; 32],
+			topic: Some([7; 32]),
+		}],
+	);
+}
+
+#[test]
+fn trailing_topic_should_identify_executed_message() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+
+	let message = Xcm::<TestCall>(vec![WithdrawAsset((Here, 100).into()), SetTopic([7; 32])]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(25));
+	assert_eq!(
+		TrapContexts::get(),
+		vec![XcmContext {
+			origin: Some(Parachain(1).into()),
+			message_id: [7; 32],
+			topic: Some([7; 32]),
+		}],
+	);
+}
+
+#[test]
+fn asset_trap_should_not_exceed_weight_limit() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+	let trapped = TrappedAssets::get().len();
+
+	let message = Xcm::<TestCall>(vec![WithdrawAsset((Here, 100).into()), SetTopic([7; 32])]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 20);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(TrappedAssets::get().len(), trapped);
+}
+
+#[test]
+fn minimum_amount_filter_should_work() {
+	use sp_std::collections::btree_map::BTreeMap;
+	parameter_types! {
+		pub TrapThresholds: BTreeMap<AssetId, u128> =
+			vec![(Concrete(Here.into()), 10)].into_iter().collect();
+	}
+	type Trap = FilterAssets<TestAssetTrap, MinimumAmountFilter<TrapThresholds>>;
+	let context = XcmContext::with_message_id([0; 32]);
+	let mut meter = WeightMeter::max_limit();
+	let trapped = TrappedAssets::get().len();
+
+	// Dust is not trapped...
+	Trap::drop_assets(&Parent.into(), MultiAsset::from((Here, 9)).into(), &context, &mut meter);
+	assert_eq!(TrappedAssets::get().len(), trapped);
+	assert_eq!(meter.consumed, 0);
+
+	// ...but anything at the threshold is...
+	Trap::drop_assets(&Parent.into(), MultiAsset::from((Here, 10)).into(), &context, &mut meter);
+	assert_eq!(TrappedAssets::get().len(), trapped + 1);
+
+	// ...as is any asset without a threshold, along with any dust.
+	let assets: Vec<MultiAsset> = vec![(Here, 1).into(), (Parent, 1).into()];
+	Trap::drop_assets(&Parent.into(), assets.into(), &context, &mut meter);
+	assert_eq!(TrappedAssets::get().len(), trapped + 2);
+	assert_eq!(meter.consumed, 10);
+}
+
 #[test]
 fn basic_asset_trap_should_work() {
 	// we'll let them have message execution for free.
@@ -201,7 +403,7 @@ fn basic_asset_trap_should_work() {
 				beneficiary: AccountIndex64 { index: 3, network: Any }.into(),
 			},
 		]),
-		20,
+		25,
 	);
 	assert_eq!(r, Outcome::Complete(25));
 	assert_eq!(assets(1001), vec![(Here, 900).into()]);
@@ -422,6 +624,158 @@ fn code_registers_should_work() {
 	assert_eq!(sent_xcm(), vec![]);
 }
 
+#[test]
+fn burn_asset_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+	let message = Xcm(vec![
+		WithdrawAsset((Here, 1000).into()),
+		BurnAsset((Here, 100).into()),
+		DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: Parachain(1).into() },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(30));
+	assert_eq!(assets(1001), vec![(Here, 900).into()]);
+
+	// Burning more than is in holding is not an error.
+	let message = Xcm(vec![
+		WithdrawAsset((Here, 900).into()),
+		BurnAsset((Here, 1000).into()),
+		DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: Parachain(1).into() },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(30));
+	assert_eq!(assets(1001), vec![]);
+}
+
+#[test]
+fn expect_origin_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+
+	let message =
+		Xcm(vec![ExpectOrigin(Some(Parachain(1).into())), ClearOrigin, ExpectOrigin(None)]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(30));
+
+	let message = Xcm(vec![ExpectOrigin(Some(Parachain(2).into())), ClearOrigin]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::ExpectationFalse));
+}
+
+#[test]
+fn expect_error_should_work() {
+	AllowUnpaidFrom::set(vec![Here.into()]);
+	let failing_transfer = TransferAsset {
+		assets: (Here, 1).into(),
+		beneficiary: X1(AccountIndex64 { index: 3, network: Any }).into(),
+	};
+
+	// The error handler only clears the error it expects.
+	let message = Xcm(vec![
+		SetErrorHandler(Xcm(vec![ExpectError(Some((1, XcmError::NotWithdrawable))), ClearError])),
+		failing_transfer.clone(),
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Here, message, 50);
+	assert_eq!(r, Outcome::Complete(40));
+
+	let message = Xcm(vec![
+		SetErrorHandler(Xcm(vec![ExpectError(Some((0, XcmError::NotWithdrawable))), ClearError])),
+		failing_transfer,
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Here, message, 50);
+	assert_eq!(r, Outcome::Incomplete(30, XcmError::ExpectationFalse));
+}
+
+#[test]
+fn query_pallet_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	let response_info =
+		QueryResponseInfo { destination: Parachain(1).into(), query_id: 1, max_weight: 50 };
+
+	let message = Xcm(vec![QueryPallet {
+		module_name: "pallet_balances".into(),
+		response_info: response_info.clone(),
+	}]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(10));
+
+	let message = Xcm(vec![QueryPallet {
+		module_name: "pallet_assets".into(),
+		response_info: QueryResponseInfo { query_id: 2, ..response_info },
+	}]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(10));
+
+	let expected_info = PalletInfo {
+		index: 1,
+		name: b"Balances".to_vec(),
+		module_name: b"pallet_balances".to_vec(),
+		major: 1,
+		minor: 42,
+		patch: 69,
+	};
+	assert_eq!(
+		sent_xcm(),
+		vec![
+			(
+				Parachain(1).into(),
+				Xcm::<()>(vec![QueryResponse {
+					query_id: 1,
+					max_weight: 50,
+					response: Response::PalletsInfo(vec![expected_info]),
+				}]),
+			),
+			(
+				Parachain(1).into(),
+				Xcm::<()>(vec![QueryResponse {
+					query_id: 2,
+					max_weight: 50,
+					response: Response::PalletsInfo(vec![]),
+				}]),
+			),
+		]
+	);
+}
+
+#[test]
+fn expect_pallet_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	let expect_pallet = |index, name: &str, module_name: &str, crate_major, min_crate_minor| {
+		let message = Xcm(vec![ExpectPallet {
+			index,
+			name: name.into(),
+			module_name: module_name.into(),
+			crate_major,
+			min_crate_minor,
+		}]);
+		XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50)
+	};
+
+	assert_eq!(expect_pallet(1, "Balances", "pallet_balances", 1, 42), Outcome::Complete(10));
+	// A lower minor version is compatible.
+	assert_eq!(expect_pallet(1, "Balances", "pallet_balances", 1, 41), Outcome::Complete(10));
+	assert_eq!(
+		expect_pallet(1, "Balances", "pallet_balances", 1, 43),
+		Outcome::Incomplete(10, XcmError::VersionIncompatible),
+	);
+	assert_eq!(
+		expect_pallet(1, "Balances", "pallet_balances", 2, 42),
+		Outcome::Incomplete(10, XcmError::VersionIncompatible),
+	);
+	assert_eq!(
+		expect_pallet(1, "System", "pallet_balances", 1, 42),
+		Outcome::Incomplete(10, XcmError::NameMismatch),
+	);
+	assert_eq!(
+		expect_pallet(1, "Balances", "pallet_system", 1, 42),
+		Outcome::Incomplete(10, XcmError::NameMismatch),
+	);
+	assert_eq!(
+		expect_pallet(2, "Balances", "pallet_balances", 1, 42),
+		Outcome::Incomplete(10, XcmError::PalletNotFound),
+	);
+}
+
 #[test]
 fn reserve_transfer_should_work() {
 	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
@@ -461,6 +815,40 @@ fn reserve_transfer_should_work() {
 	);
 }
 
+#[test]
+fn delivery_price_should_be_paid_from_holding() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	// Child parachain #1 owns 1000 tokens held by us in reserve.
+	add_asset(1001, (Here, 1000));
+	DeliveryPrice::set((Here, 10).into());
+	let three: MultiLocation = X1(AccountIndex64 { index: 3, network: Any }).into();
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			InitiateReserveWithdraw {
+				assets: (Here, 50).into(),
+				reserve: Parachain(2).into(),
+				xcm: Xcm(vec![]),
+			},
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: three },
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(30));
+
+	// 10 of the remaining 50 tokens in holding went to pay for the delivery of the message.
+	assert_eq!(assets(3), vec![(Here, 40).into()]);
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parachain(2).into(),
+			Xcm::<()>(vec![WithdrawAsset((Parent, 50).into()), ClearOrigin]),
+		)]
+	);
+}
+
 #[test]
 fn simple_version_subscriptions_should_work() {
 	AllowSubsFrom::set(vec![Parent.into()]);
@@ -487,6 +875,30 @@ fn simple_version_subscriptions_should_work() {
 	assert_eq!(SubscriptionRequests::get(), vec![(Parent.into(), Some((42, 5000)))]);
 }
 
+#[test]
+fn v1_version_subscriptions_should_work() {
+	use xcm::VersionedXcm;
+	AllowSubsFrom::set(vec![Parent.into()]);
+
+	let subscribe = VersionedXcm::<TestCall>::V1(xcm::v1::Xcm::SubscribeVersion {
+		query_id: 42,
+		max_response_weight: 5000,
+	});
+	let message = Xcm::<TestCall>::try_from(subscribe).unwrap();
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 10);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(SubscriptionRequests::get(), vec![(Parent.into(), Some((42, 5000)))]);
+
+	let unsubscribe = VersionedXcm::<TestCall>::V1(xcm::v1::Xcm::UnsubscribeVersion);
+	let message = Xcm::<TestCall>::try_from(unsubscribe).unwrap();
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 10);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(
+		SubscriptionRequests::get(),
+		vec![(Parent.into(), Some((42, 5000))), (Parent.into(), None)]
+	);
+}
+
 #[test]
 fn version_subscription_instruction_should_work() {
 	let origin = Parachain(1000).into();
@@ -501,7 +913,7 @@ fn version_subscription_instruction_should_work() {
 		weight_limit,
 		weight_limit,
 	);
-	assert_eq!(r, Outcome::Incomplete(20, XcmError::BadOrigin));
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::BadOrigin));
 
 	let message = Xcm::<TestCall>(vec![
 		SetAppendix(Xcm(vec![])),
@@ -557,7 +969,7 @@ fn version_unsubscription_instruction_should_work() {
 		weight_limit,
 		weight_limit,
 	);
-	assert_eq!(r, Outcome::Incomplete(20, XcmError::BadOrigin));
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::BadOrigin));
 
 	// Fine to do it when origin is untouched.
 	let message = Xcm::<TestCall>(vec![SetAppendix(Xcm(vec![])), UnsubscribeVersion]);
@@ -573,6 +985,110 @@ fn version_unsubscription_instruction_should_work() {
 	assert_eq!(sent_xcm(), vec![]);
 }
 
+#[test]
+fn teleport_amount_limit_should_work() {
+	AllowUnpaidFrom::set(vec![Parent.into(), Parachain(1).into()]);
+	add_teleporter(Parent.into(), Wild((Parent, WildFungible).into()));
+	ParentTeleportLimit::set((Parent.into(), Parent.into(), 100));
+
+	// Teleports from an untrusted location are rejected before any limit is considered.
+	let message = Xcm::<TestCall>(vec![ReceiveTeleportedAsset((Parent, 1000).into())]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 10);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::UntrustedTeleportLocation));
+
+	// Teleports above the limit from a trusted location are rejected.
+	let message = Xcm::<TestCall>(vec![ReceiveTeleportedAsset((Parent, 101).into())]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 10);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::TeleportAmountExceeded));
+
+	// Teleports up to the limit are accepted.
+	let message = Xcm::<TestCall>(vec![
+		ReceiveTeleportedAsset((Parent, 100).into()),
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 20);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(assets(1001), vec![(Parent, 100).into()]);
+
+	// Outgoing teleports are bounded too and leave the assets in holding on failure.
+	add_asset(1001, (Parent, 1000));
+	let message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Parent, 1000).into()),
+		InitiateTeleport { assets: All.into(), dest: Parent.into(), xcm: Xcm(vec![]) },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 20);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::TeleportAmountExceeded));
+	assert!(sent_xcm().is_empty());
+}
+
+#[test]
+fn reserve_backed_assets_should_not_be_forwarded() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+
+	// Reserve-backed assets cannot be teleported onwards...
+	let message = Xcm::<TestCall>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		InitiateTeleport { assets: All.into(), dest: Parachain(2).into(), xcm: Xcm(vec![]) },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 20);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::ProvenanceMismatch));
+
+	// ...nor deposited elsewhere as though we were their reserve...
+	let message = Xcm::<TestCall>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		DepositReserveAsset {
+			assets: All.into(),
+			max_assets: 1,
+			dest: Parachain(2).into(),
+			xcm: Xcm(vec![]),
+		},
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 20);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::ProvenanceMismatch));
+	assert!(sent_xcm().is_empty());
+	assert!(assets(1002).is_empty());
+
+	// ...even when mixed with locally withdrawn assets of the same kind.
+	add_asset(3001, (Parent, 50));
+	let message = Xcm::<TestCall>(vec![
+		WithdrawAsset((Parent, 50).into()),
+		ReserveAssetDeposited((Parent, 100).into()),
+		InitiateTeleport { assets: All.into(), dest: Parachain(2).into(), xcm: Xcm(vec![]) },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 30);
+	assert_eq!(r, Outcome::Incomplete(30, XcmError::ProvenanceMismatch));
+
+	// They may still be deposited locally.
+	let message = Xcm::<TestCall>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 20);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(assets(1001), vec![(Parent, 100).into()]);
+}
+
+#[test]
+fn provenance_should_be_forgotten_once_assets_leave_holding() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+	add_asset(3001, (Parent, 50));
+
+	// Once the reserve-backed assets are sent back to their reserve, assets of the same kind
+	// withdrawn locally may be teleported.
+	let message = Xcm::<TestCall>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		InitiateReserveWithdraw { assets: All.into(), reserve: Parent.into(), xcm: Xcm(vec![]) },
+		WithdrawAsset((Parent, 50).into()),
+		InitiateTeleport { assets: All.into(), dest: Parachain(2).into(), xcm: Xcm(vec![]) },
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, 40);
+	assert_eq!(r, Outcome::Complete(40));
+	assert_eq!(sent_xcm().len(), 2);
+	assert!(assets(3001).is_empty());
+}
+
 #[test]
 fn transacting_should_work() {
 	AllowUnpaidFrom::set(vec![Parent.into()]);
@@ -587,6 +1103,110 @@ fn transacting_should_work() {
 	assert_eq!(r, Outcome::Complete(60));
 }
 
+#[test]
+fn report_successful_transact_status_should_work() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	let message = Xcm::<TestCall>(vec![
+		Transact {
+			origin_type: OriginKind::Native,
+			require_weight_at_most: 50,
+			call: TestCall::Any(50, None).encode().into(),
+		},
+		ReportTransactStatus(QueryResponseInfo {
+			destination: Parent.into(),
+			query_id: 42,
+			max_weight: 5000,
+		}),
+	]);
+	let weight_limit = 70;
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, weight_limit);
+	assert_eq!(r, Outcome::Complete(70));
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parent.into(),
+			Xcm(vec![QueryResponse {
+				response: Response::DispatchResult(MaybeErrorCode::Success),
+				query_id: 42,
+				max_weight: 5000,
+			}])
+		)]
+	);
+}
+
+#[test]
+fn report_failed_transact_status_should_work() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	let message = Xcm::<TestCall>(vec![
+		Transact {
+			origin_type: OriginKind::Native,
+			require_weight_at_most: 50,
+			call: TestCall::OnlyRoot(50, None).encode().into(),
+		},
+		ReportTransactStatus(QueryResponseInfo {
+			destination: Parent.into(),
+			query_id: 42,
+			max_weight: 5000,
+		}),
+		// The status is kept until cleared.
+		ClearTransactStatus,
+		ReportTransactStatus(QueryResponseInfo {
+			destination: Parent.into(),
+			query_id: 43,
+			max_weight: 5000,
+		}),
+	]);
+	let weight_limit = 90;
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parent, message, weight_limit);
+	assert_eq!(r, Outcome::Complete(90));
+	assert_eq!(
+		sent_xcm(),
+		vec![
+			(
+				Parent.into(),
+				Xcm(vec![QueryResponse {
+					response: Response::DispatchResult(DispatchError::BadOrigin.encode().into()),
+					query_id: 42,
+					max_weight: 5000,
+				}])
+			),
+			(
+				Parent.into(),
+				Xcm(vec![QueryResponse {
+					response: Response::DispatchResult(MaybeErrorCode::Success),
+					query_id: 43,
+					max_weight: 5000,
+				}])
+			),
+		]
+	);
+}
+
+#[test]
+fn transacting_should_use_descended_origin() {
+	let transact = Transact {
+		origin_type: OriginKind::Native,
+		require_weight_at_most: 50,
+		call: TestCall::OnlySigned(50, None, Some(3)).encode().into(),
+	};
+	let message = Xcm::<TestCall>(vec![
+		DescendOrigin(X1(AccountIndex64 { index: 3, network: Any })),
+		transact.clone(),
+	]);
+	let weight_limit = 70;
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm_in_credit(Here, message, weight_limit, weight_limit);
+	assert_eq!(r, Outcome::Complete(70));
+
+	// The original origin alone has no native representation.
+	let message = Xcm::<TestCall>(vec![transact]);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm_in_credit(Here, message, weight_limit, weight_limit);
+	assert!(matches!(r, Outcome::Incomplete(_, XcmError::BadOrigin)));
+}
+
 #[test]
 fn transacting_should_respect_max_weight_requirement() {
 	AllowUnpaidFrom::set(vec![Parent.into()]);
@@ -601,6 +1221,114 @@ fn transacting_should_respect_max_weight_requirement() {
 	assert_eq!(r, Outcome::Incomplete(50, XcmError::MaxWeightInvalid));
 }
 
+#[test]
+fn process_xcm_message_should_work() {
+	use xcm::VersionedXcm;
+	use xcm_executor::ProcessXcmMessage;
+	type Processor = ProcessXcmMessage<MultiLocation, XcmExecutor<TestConfig>, TestCall>;
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	let transact = |require_weight_at_most| {
+		VersionedXcm::from(Xcm::<TestCall>(vec![Transact {
+			origin_type: OriginKind::Native,
+			require_weight_at_most,
+			call: TestCall::Any(50, None).encode().into(),
+		}]))
+		.encode()
+	};
+
+	assert_eq!(
+		Processor::process_message(&[0xff][..], Parent.into(), 60),
+		Err(ProcessMessageError::BadFormat)
+	);
+	assert_eq!(Processor::process_message(&transact(50)[..], Parent.into(), 60), Ok((true, 60)));
+	// Execution was incomplete, so the message was not processed successfully.
+	assert_eq!(Processor::process_message(&transact(40)[..], Parent.into(), 60), Ok((false, 50)));
+	// The error with which it stopped is available from the full outcome.
+	assert_eq!(
+		Processor::process_xcm_message(&transact(40)[..], Parent.into(), 60),
+		Ok(Outcome::Incomplete(50, XcmError::MaxWeightInvalid))
+	);
+	// Overweight messages are not executed at all.
+	assert_eq!(
+		Processor::process_message(&transact(50)[..], Parent.into(), 59),
+		Err(ProcessMessageError::Overweight(60))
+	);
+}
+
+thread_local! {
+	static PROCESSED: RefCell<Vec<(MultiLocation, Result<(bool, Weight), ProcessMessageError>)>> =
+		RefCell::new(Vec::new());
+}
+
+/// Records the fate of each message taken off a queue.
+pub struct RecordProcessed;
+impl OnMessageProcessed for RecordProcessed {
+	fn on_processed(_: MessageId, origin: MultiLocation, success: bool, weight: Weight) {
+		PROCESSED.with(|p| p.borrow_mut().push((origin, Ok((success, weight)))));
+	}
+	fn on_failed(_: MessageId, origin: MultiLocation, error: ProcessMessageError) {
+		PROCESSED.with(|p| p.borrow_mut().push((origin, Err(error))));
+	}
+}
+
+fn take_processed() -> Vec<(MultiLocation, Result<(bool, Weight), ProcessMessageError>)> {
+	PROCESSED.with(|p| p.take())
+}
+
+fn transact_message(require_weight_at_most: Weight) -> Vec<u8> {
+	xcm::VersionedXcm::from(Xcm::<TestCall>(vec![Transact {
+		origin_type: OriginKind::Superuser,
+		require_weight_at_most,
+		call: TestCall::Any(require_weight_at_most, None).encode().into(),
+	}]))
+	.encode()
+}
+
+#[test]
+fn dmp_sink_should_process_downward_messages_within_weight() {
+	use polkadot_parachain::primitives::DmpMessageHandler;
+	type Processor =
+		xcm_executor::ProcessXcmMessage<MultiLocation, XcmExecutor<TestConfig>, TestCall>;
+	type Sink = DmpSink<Processor, RecordProcessed>;
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	let messages = vec![(1, transact_message(50)), (1, vec![0xff]), (1, transact_message(50))];
+	assert_eq!(Sink::handle_dmp_messages(messages.into_iter(), 110), 60);
+	assert_eq!(
+		take_processed(),
+		vec![
+			(Parent.into(), Ok((true, 60))),
+			(Parent.into(), Err(ProcessMessageError::BadFormat)),
+			// Only 50 weight remains, so the last message is not executed.
+			(Parent.into(), Err(ProcessMessageError::Overweight(60))),
+		]
+	);
+}
+
+#[test]
+fn xcmp_sink_should_process_each_concatenated_message() {
+	use polkadot_parachain::primitives::{XcmpMessageFormat, XcmpMessageHandler};
+	type Processor =
+		xcm_executor::ProcessXcmMessage<MultiLocation, XcmExecutor<TestConfig>, TestCall>;
+	type Sink = XcmpSink<Processor, RecordProcessed>;
+	let sibling = MultiLocation::new(1, X1(Parachain(2)));
+	AllowUnpaidFrom::set(vec![sibling.clone()]);
+
+	let mut blob = XcmpMessageFormat::ConcatenatedVersionedXcm.encode();
+	blob.extend(transact_message(50));
+	blob.extend(transact_message(20));
+	let mut unsupported = XcmpMessageFormat::ConcatenatedEncodedBlob.encode();
+	unsupported.extend(transact_message(50));
+
+	let messages = vec![(2.into(), 1, &blob[..]), (3.into(), 1, &unsupported[..])];
+	assert_eq!(Sink::handle_xcmp_messages(messages.into_iter(), 1_000), 90);
+	assert_eq!(
+		take_processed(),
+		vec![(sibling.clone(), Ok((true, 60))), (sibling, Ok((true, 30)))]
+	);
+}
+
 #[test]
 fn transacting_should_refund_weight() {
 	AllowUnpaidFrom::set(vec![Parent.into()]);
@@ -714,3 +1442,663 @@ fn weight_trader_tuple_should_work() {
 	// and no refund
 	assert_eq!(traders.refund_weight(2), None);
 }
+
+#[test]
+fn swap_first_asset_trader_should_work() {
+	use frame_support::traits::Contains;
+	pub const PARA_1: MultiLocation = X1(Parachain(1)).into();
+	pub const PARA_2: MultiLocation = X1(Parachain(2)).into();
+
+	parameter_types! {
+		pub static HereWeightPrice: (AssetId, u128) = (Here.into().into(), WEIGHT_PER_SECOND.into());
+	}
+
+	pub struct Para1Pool;
+	impl Contains<AssetId> for Para1Pool {
+		fn contains(id: &AssetId) -> bool {
+			*id == Concrete(PARA_1)
+		}
+	}
+
+	/// Exchanges the first fungible asset of `give` for that of `want`, with `Here` being worth
+	/// two of anything else.
+	pub struct TestExchange;
+	impl AssetExchange for TestExchange {
+		fn exchange_asset(
+			_origin: Option<&MultiLocation>,
+			mut give: Assets,
+			want: &MultiAssets,
+			maximal: bool,
+		) -> Result<Assets, Assets> {
+			let (want_id, wanted) = match want.get(0) {
+				Some(MultiAsset { id, fun: Fungible(amount) }) => (id.clone(), *amount),
+				_ => return Err(give),
+			};
+			let (give_id, have) = match give.fungible_assets_iter().next() {
+				Some(MultiAsset { id, fun: Fungible(amount) }) => (id, amount),
+				_ => return Err(give),
+			};
+			let buying_here = want_id == Concrete(Here.into());
+			let spend = match (maximal, buying_here) {
+				(true, _) => have,
+				(false, true) => wanted * 2,
+				(false, false) => (wanted + 1) / 2,
+			};
+			let received = if buying_here { spend / 2 } else { spend * 2 };
+			if spend > have || received < wanted {
+				return Err(give)
+			}
+			give.saturating_take(Definite((give_id, spend).into()));
+			give.subsume((want_id, received).into());
+			Ok(give)
+		}
+	}
+
+	type Trader = SwapFirstAssetTrader<HereWeightPrice, Para1Pool, TestExchange, ()>;
+
+	let mut trader = Trader::new();
+	// 10 of the pool asset are swapped for the 5 of the fee asset which the weight costs.
+	assert_eq!(
+		trader.buy_weight(5, fungible_multi_asset(PARA_1, 20).into()),
+		Ok(fungible_multi_asset(PARA_1, 10).into()),
+	);
+	// Refunds are made in the asset which was swapped.
+	assert_eq!(trader.refund_weight(2), Some(fungible_multi_asset(PARA_1, 4)));
+	// Only the asset which was swapped before may be swapped again.
+	let payment: Assets = vec![fungible_multi_asset(PARA_2, 20)].into();
+	assert_err!(trader.buy_weight(5, payment), XcmError::TooExpensive);
+	assert_eq!(trader.refund_weight(5), Some(fungible_multi_asset(PARA_1, 6)));
+	assert_eq!(trader.refund_weight(5), None);
+
+	let mut trader = Trader::new();
+	// Assets outside of the pools are not accepted.
+	assert_err!(
+		trader.buy_weight(5, fungible_multi_asset(PARA_2, 20).into()),
+		XcmError::TooExpensive,
+	);
+	// Nor is too little of an asset within them.
+	assert_err!(
+		trader.buy_weight(5, fungible_multi_asset(PARA_1, 9).into()),
+		XcmError::TooExpensive,
+	);
+	// The fee asset itself is left to other traders.
+	assert_err!(
+		trader.buy_weight(5, fungible_multi_asset(Here.into(), 20).into()),
+		XcmError::TooExpensive,
+	);
+	assert_eq!(trader.refund_weight(2), None);
+
+	/// Exchanges as `TestExchange` does, but gives nothing back for refunds.
+	pub struct OneWayExchange;
+	impl AssetExchange for OneWayExchange {
+		fn exchange_asset(
+			origin: Option<&MultiLocation>,
+			give: Assets,
+			want: &MultiAssets,
+			maximal: bool,
+		) -> Result<Assets, Assets> {
+			if maximal {
+				return Ok(Assets::new())
+			}
+			TestExchange::exchange_asset(origin, give, want, maximal)
+		}
+	}
+
+	let mut trader = SwapFirstAssetTrader::<HereWeightPrice, Para1Pool, OneWayExchange, ()>::new();
+	assert_eq!(
+		trader.buy_weight(5, fungible_multi_asset(PARA_1, 20).into()),
+		Ok(fungible_multi_asset(PARA_1, 10).into()),
+	);
+	// Refunds which cannot be swapped back are made in the fee asset.
+	assert_eq!(trader.refund_weight(2), Some(fungible_multi_asset(Here.into(), 2)));
+}
+
+#[test]
+fn converted_concrete_id_tuple_should_work() {
+	parameter_types! {
+		pub AssetsPalletLocation: MultiLocation = PalletInstance(50).into();
+		pub ForeignAssetsPalletLocation: MultiLocation = PalletInstance(51).into();
+	}
+
+	type Matchers = (
+		ConvertedConcreteId<
+			u32,
+			u128,
+			AsPrefixedGeneralIndex<AssetsPalletLocation, u32, JustTry>,
+			JustTry,
+		>,
+		ConvertedConcreteId<
+			u32,
+			u128,
+			AsPrefixedGeneralIndex<ForeignAssetsPalletLocation, u32, JustTry>,
+			JustTry,
+		>,
+	);
+
+	let asset = |pallet: u8, id: u128, amount: u128| -> MultiAsset {
+		(X2(PalletInstance(pallet), GeneralIndex(id)), amount).into()
+	};
+
+	// first matcher
+	assert_eq!(
+		<Matchers as MatchesFungibles<u32, u128>>::matches_fungibles(&asset(50, 1, 100)),
+		Ok((1, 100)),
+	);
+	// second matcher
+	assert_eq!(
+		<Matchers as MatchesFungibles<u32, u128>>::matches_fungibles(&asset(51, 2, 200)),
+		Ok((2, 200)),
+	);
+	// no matcher accepts an unknown pallet
+	assert_eq!(
+		<Matchers as MatchesFungibles<u32, u128>>::matches_fungibles(&asset(52, 1, 100)),
+		Err(Error::AssetNotFound),
+	);
+	// asset ID not representable as `u32`
+	assert_eq!(
+		<ConvertedConcreteId<
+			u32,
+			u128,
+			AsPrefixedGeneralIndex<AssetsPalletLocation, u32, JustTry>,
+			JustTry,
+		> as MatchesFungibles<u32, u128>>::matches_fungibles(&asset(50, u64::MAX as u128, 1)),
+		Err(Error::AssetIdConversionFailed),
+	);
+	// non-fungible with instance conversion
+	let nft: MultiAsset = (X2(PalletInstance(50), GeneralIndex(7)), AssetInstance::Index(3)).into();
+	assert_eq!(
+		<Matchers as MatchesNonFungibles<u32, AssetInstance>>::matches_nonfungibles(&nft),
+		Err(Error::AssetNotFound),
+	);
+	type NftMatcher = ConvertedConcreteId<
+		u32,
+		AssetInstance,
+		AsPrefixedGeneralIndex<AssetsPalletLocation, u32, JustTry>,
+		Identity,
+	>;
+	assert_eq!(
+		<NftMatcher as MatchesNonFungibles<u32, AssetInstance>>::matches_nonfungibles(&nft),
+		Ok((7, AssetInstance::Index(3))),
+	);
+}
+
+#[test]
+fn plurality_as_origin_should_map_bodies_to_origins() {
+	parameter_types! {
+		pub const RelayChain: MultiLocation = MultiLocation::parent();
+		pub const Executive: BodyId = BodyId::Executive;
+		pub const Technical: BodyId = BodyId::Technical;
+		pub const ExecutiveOrigin: TestOrigin = TestOrigin::Root;
+		pub const TechnicalOrigin: TestOrigin = TestOrigin::Signed(42);
+	}
+	type Bodies = (
+		PluralityAsOrigin<RelayChain, Executive, ExecutiveOrigin, TestOrigin>,
+		PluralityAsOrigin<RelayChain, Technical, TechnicalOrigin, TestOrigin>,
+	);
+	let plurality = |id: BodyId, part: BodyPart| MultiLocation::new(1, X1(Plurality { id, part }));
+
+	let origin =
+		Bodies::convert_origin(plurality(BodyId::Executive, BodyPart::Voice), OriginKind::Native);
+	assert!(matches!(origin, Ok(TestOrigin::Root)));
+	let majority = BodyPart::Fraction { nom: 2, denom: 3 };
+	let origin = Bodies::convert_origin(plurality(BodyId::Technical, majority), OriginKind::Native);
+	assert!(matches!(origin, Ok(TestOrigin::Signed(42))));
+
+	// a minority of the body does not carry its privileges.
+	let minority = BodyPart::Fraction { nom: 1, denom: 3 };
+	let origin =
+		Bodies::convert_origin(plurality(BodyId::Executive, minority.clone()), OriginKind::Native);
+	assert!(matches!(origin, Err(l) if l == plurality(BodyId::Executive, minority)));
+	// unknown bodies, other origin kinds and other locations are rejected.
+	let origin =
+		Bodies::convert_origin(plurality(BodyId::Judicial, BodyPart::Voice), OriginKind::Native);
+	assert!(origin.is_err());
+	let origin = Bodies::convert_origin(
+		plurality(BodyId::Executive, BodyPart::Voice),
+		OriginKind::Superuser,
+	);
+	assert!(origin.is_err());
+	let local =
+		MultiLocation::new(0, X1(Plurality { id: BodyId::Executive, part: BodyPart::Voice }));
+	let origin = Bodies::convert_origin(local, OriginKind::Native);
+	assert!(origin.is_err());
+}
+
+parameter_types! {
+	pub Treasury: MultiLocation = X1(AccountIndex64 { index: 1, network: Any }).into();
+	pub const PaymentTimeout: u64 = 10;
+	pub const MaxPaymentWeight: Weight = 100;
+}
+type TestPaymaster = PayOverXcm<
+	Treasury,
+	XcmExecutor<TestConfig>,
+	TestCall,
+	TestQueryHandler,
+	PaymentTimeout,
+	TestIsTeleporter,
+	TestUniversalLocation,
+	MaxPaymentWeight,
+>;
+
+#[test]
+fn pay_over_xcm_should_teleport_and_track_status() {
+	// Our treasury owns 1000 of our native tokens, which may be teleported to parachain #2.
+	add_asset(1, (Here, 1000));
+	add_teleporter(Parachain(2).into(), Wild(All));
+	let beneficiary: MultiLocation = X1(AccountIndex64 { index: 3, network: Any }).into();
+	let asset_kind = LocatableAssetId { asset_id: Here.into(), location: Parachain(2).into() };
+
+	let query_id = TestPaymaster::pay(&beneficiary, asset_kind, 100).unwrap();
+	assert_eq!(assets(1), vec![(Here, 900).into()]);
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parachain(2).into(),
+			Xcm::<()>(vec![
+				ReceiveTeleportedAsset((Parent, 100).into()),
+				ClearOrigin,
+				BuyExecution { fees: (Parent, 100).into(), weight_limit: Unlimited },
+				SetAppendix(Xcm(vec![ReportError {
+					query_id,
+					dest: Parent.into(),
+					max_response_weight: 0,
+				}])),
+				DepositAsset { assets: All.into(), max_assets: 1, beneficiary },
+			]),
+		)]
+	);
+	assert_eq!(TestPaymaster::check_payment(query_id), PaymentStatus::InProgress);
+
+	// Parachain #2 reports the payment as made.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(2),
+		Xcm(vec![QueryResponse {
+			query_id,
+			response: Response::ExecutionResult(None),
+			max_weight: 0,
+		}]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(TestPaymaster::check_payment(query_id), PaymentStatus::Success);
+	// The status has been taken.
+	assert_eq!(TestPaymaster::check_payment(query_id), PaymentStatus::Unknown);
+}
+
+#[test]
+fn pay_over_xcm_should_reserve_transfer_and_report_failure() {
+	add_asset(1, (Here, 1000));
+	let beneficiary: MultiLocation = X1(AccountIndex64 { index: 3, network: Any }).into();
+	let asset_kind = LocatableAssetId { asset_id: Here.into(), location: Parachain(2).into() };
+
+	let query_id = TestPaymaster::pay(&beneficiary, asset_kind.clone(), 100).unwrap();
+	assert_eq!(assets(1), vec![(Here, 900).into()]);
+	// Without a teleporter, the payment is moved into the sovereign account of parachain #2.
+	assert_eq!(assets(1002), vec![(Here, 100).into()]);
+	let (dest, message) = sent_xcm().pop().unwrap();
+	assert_eq!(dest, Parachain(2).into());
+	assert_eq!(message.0[0], ReserveAssetDeposited((Parent, 100).into()));
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(2),
+		Xcm(vec![QueryResponse {
+			query_id,
+			response: Response::ExecutionResult(Some((2, XcmError::FailedToTransactAsset("")))),
+			max_weight: 0,
+		}]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(TestPaymaster::check_payment(query_id), PaymentStatus::Failure);
+
+	// A payment which cannot be withdrawn is not made.
+	assert_eq!(TestPaymaster::pay(&beneficiary, asset_kind, 1000), Err(XcmError::NotWithdrawable));
+	assert_eq!(assets(1), vec![(Here, 900).into()]);
+}
+
+#[test]
+fn export_message_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	ExportPrice::set((Here, 100).into());
+	add_asset(1001, (Here, 1000));
+	let remote_message = Xcm(vec![Trap(0)]);
+
+	let message = Xcm(vec![
+		WithdrawAsset((Here, 100).into()),
+		ExportMessage {
+			network: Kusama,
+			destination: X1(Parachain(1000)),
+			xcm: remote_message.clone(),
+		},
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(20));
+	// The fee for the export is taken from holding.
+	assert_eq!(assets(1001), vec![(Here, 900).into()]);
+
+	let (network, _channel, universal_source, destination, xcm) = exported_xcm().pop().unwrap();
+	assert_eq!(network, Kusama);
+	assert_eq!(universal_source, X3(GlobalConsensus(Polkadot), Parachain(42), Parachain(1)));
+	assert_eq!(destination, X1(Parachain(1000)));
+	assert_eq!(xcm, remote_message);
+
+	// Without the fee in holding, nothing is exported.
+	let message = Xcm(vec![ExportMessage {
+		network: Kusama,
+		destination: X1(Parachain(1000)),
+		xcm: remote_message,
+	}]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::NotHoldingFees));
+	assert_eq!(exported_xcm().len(), 1);
+}
+
+#[test]
+fn ensure_is_remote_works() {
+	let local = TestUniversalLocation::get();
+	let remote = (Parent, Parent, GlobalConsensus(Kusama), Parachain(1000));
+	assert_eq!(ensure_is_remote(local.clone(), remote), Ok((Kusama, X1(Parachain(1000)))));
+	// Locations within the local consensus system are not remote.
+	let sibling: MultiLocation = (Parent, Parachain(1000)).into();
+	assert_eq!(ensure_is_remote(local.clone(), sibling.clone()), Err(sibling));
+	let ours: MultiLocation = (Parent, Parent, GlobalConsensus(Polkadot)).into();
+	assert_eq!(ensure_is_remote(local.clone(), ours.clone()), Err(ours));
+	// Nor is anything beyond the universal root.
+	let beyond: MultiLocation = (Parent, Parent, Parent).into();
+	assert_eq!(ensure_is_remote(local, beyond.clone()), Err(beyond));
+}
+
+#[test]
+fn unpaid_local_exporter_should_work() {
+	type Router = UnpaidLocalExporter<TestMessageExporter, TestUniversalLocation>;
+	let remote_message = Xcm(vec![Trap(0)]);
+
+	let dest = (Parent, Parent, GlobalConsensus(Kusama), Parachain(1000));
+	assert_eq!(
+		send_xcm::<Router>(dest.into(), remote_message.clone()).map(|(_, p)| p),
+		Ok(MultiAssets::new())
+	);
+	assert_eq!(
+		exported_xcm(),
+		vec![(
+			Kusama,
+			0,
+			TestUniversalLocation::get(),
+			X1(Parachain(1000)),
+			remote_message.clone()
+		)],
+	);
+
+	// Messages for the local consensus system are left to other routers.
+	let dest = (Parent, Parachain(1000));
+	assert_eq!(send_xcm::<Router>(dest.into(), remote_message), Err(SendError::NotApplicable));
+	assert_eq!(exported_xcm().len(), 1);
+}
+
+#[test]
+fn sovereign_paid_remote_exporter_should_work() {
+	type Router = SovereignPaidRemoteExporter<
+		NetworkExportTable<BridgeTable>,
+		TestSendXcm,
+		TestUniversalLocation,
+	>;
+	let bridge: MultiLocation = (Parent, Parachain(1002)).into();
+	BridgeTable::set(vec![(Kusama, bridge.clone(), Some((Parent, 100).into()))]);
+	DeliveryPrice::set((Parent, 10).into());
+	let remote_message = Xcm(vec![Trap(0)]);
+
+	let dest = (Parent, Parent, GlobalConsensus(Kusama), Parachain(1000));
+	let (_, price) = send_xcm::<Router>(dest.into(), remote_message.clone()).unwrap();
+	// Delivery to the bridge and the fee of the bridge are both paid by the sender.
+	assert_eq!(price, (Parent, 110).into());
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			bridge,
+			Xcm(vec![
+				WithdrawAsset((Parent, 100).into()),
+				BuyExecution { fees: (Parent, 100).into(), weight_limit: Unlimited },
+				ExportMessage {
+					network: Kusama,
+					destination: X1(Parachain(1000)),
+					xcm: remote_message.clone(),
+				},
+			]),
+		)],
+	);
+
+	// Networks without a bridge are left to other routers.
+	let dest = (Parent, Parent, GlobalConsensus(Named(b"Rococo".to_vec())), Parachain(1000));
+	assert_eq!(send_xcm::<Router>(dest.into(), remote_message), Err(SendError::NotApplicable));
+	assert_eq!(sent_xcm().len(), 1);
+}
+
+#[test]
+fn haul_blob_exporter_and_bridge_blob_dispatcher_should_work() {
+	parameter_types! {
+		pub const BridgedNetwork: NetworkId = Kusama;
+		pub RemoteUniversalLocation: InteriorMultiLocation =
+			X2(GlobalConsensus(Kusama), Parachain(1002));
+	}
+	type Router = UnpaidLocalExporter<
+		HaulBlobExporter<TestBridge, BridgedNetwork, ExportPrice>,
+		TestUniversalLocation,
+	>;
+	type RemoteDispatcher = BridgeBlobDispatcher<TestSendXcm, RemoteUniversalLocation>;
+	type LocalDispatcher = BridgeBlobDispatcher<TestSendXcm, TestUniversalLocation>;
+	let remote_message = Xcm(vec![Trap(0)]);
+
+	let dest = (Parent, Parent, GlobalConsensus(Kusama), Parachain(1000));
+	assert!(send_xcm::<Router>(dest.into(), remote_message.clone()).is_ok());
+	let blob = hauled_blobs().pop().unwrap();
+
+	// On the other side of the bridge, the message is routed to its destination, coming from the
+	// chain which sent it.
+	assert_eq!(RemoteDispatcher::dispatch_blob(blob.clone()), Ok(()));
+	let dispatched_message = Xcm(vec![
+		UniversalOrigin(GlobalConsensus(Polkadot)),
+		DescendOrigin(X1(Parachain(42))),
+		Trap(0),
+	]);
+	assert_eq!(sent_xcm(), vec![((Parent, Parachain(1000)).into(), dispatched_message)]);
+
+	// Messages from the local global consensus system are never accepted from a bridge.
+	assert_eq!(LocalDispatcher::dispatch_blob(blob), Err(DispatchBlobError::LocalSource));
+	assert_eq!(LocalDispatcher::dispatch_blob(vec![42]), Err(DispatchBlobError::InvalidEncoding));
+
+	// Networks other than the bridged one are left to other exporters.
+	let dest = (Parent, Parent, GlobalConsensus(Named(b"Rococo".to_vec())), Parachain(1000));
+	assert_eq!(send_xcm::<Router>(dest.into(), remote_message), Err(SendError::NotApplicable));
+	assert_eq!(hauled_blobs().len(), 1);
+}
+
+#[test]
+fn universal_origin_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into(), X1(Parachain(2)).into()]);
+	// Parachain 1 may represent Kusama to us.
+	UniversalAliases::set(vec![(X1(Parachain(1)).into(), GlobalConsensus(Kusama))]);
+
+	let message = Xcm(vec![
+		UniversalOrigin(GlobalConsensus(Kusama)),
+		ExpectOrigin(Some((Parent, Parent, GlobalConsensus(Kusama)).into())),
+	]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message.clone(), 50);
+	assert_eq!(r, Outcome::Complete(20));
+	// Other origins may not.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(2), message, 50);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::InvalidLocation));
+
+	// Nothing may represent our own global consensus system.
+	UniversalAliases::set(vec![(X1(Parachain(1)).into(), GlobalConsensus(Polkadot))]);
+	let message = Xcm(vec![UniversalOrigin(GlobalConsensus(Polkadot))]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::InvalidLocation));
+}
+
+#[test]
+fn with_unique_topic_should_work() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		type Router = WithUniqueTopic<TestSendXcm>;
+		let message = Xcm(vec![Trap(0)]);
+
+		// Validating a message doesn't change the topic which it is given.
+		let ((_, validated), _) = validate_send::<Router>(Parent.into(), message.clone()).unwrap();
+		let ((_, revalidated), _) =
+			validate_send::<Router>(Parent.into(), message.clone()).unwrap();
+		assert_eq!(validated, revalidated);
+		assert_eq!(sent_xcm(), vec![]);
+
+		let (first, _) = send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+		let (second, _) = send_xcm::<Router>(Parent.into(), message).unwrap();
+		// Sending the same message twice gives it different topics.
+		assert_eq!(first, validated);
+		assert_ne!(first, second);
+		assert_eq!(
+			sent_xcm(),
+			vec![
+				(Parent.into(), Xcm(vec![Trap(0), SetTopic(first)])),
+				(Parent.into(), Xcm(vec![Trap(0), SetTopic(second)])),
+			],
+		);
+
+		// A message which already has a topic keeps it.
+		let message = Xcm(vec![Trap(0), SetTopic([7; 32])]);
+		let (id, _) = send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+		assert_eq!(id, [7; 32]);
+		assert_eq!(sent_xcm().pop(), Some((Parent.into(), message)));
+	});
+}
+
+#[test]
+fn with_hrmp_delivery_fee_should_work() {
+	use sp_arithmetic::{traits::One, FixedPointNumber, FixedU128};
+
+	parameter_types! {
+		pub const FeeAssetId: AssetId = Concrete(MultiLocation::here());
+		pub const BaseFee: u128 = 1_000;
+		pub const ByteFee: u128 = 10;
+	}
+	struct TestFeeFactor;
+	impl HrmpChannelFeeFactor for TestFeeFactor {
+		fn fee_factor(para: u32) -> FixedU128 {
+			if para == 2000 {
+				FixedU128::saturating_from_integer(2)
+			} else {
+				FixedU128::one()
+			}
+		}
+	}
+	type Router = WithHrmpDeliveryFee<TestSendXcm, FeeAssetId, BaseFee, ByteFee, TestFeeFactor>;
+	DeliveryPrice::set((Parent, 10).into());
+	let message = Xcm(vec![Trap(0)]);
+	let fee = 1_000 + 10 * message.encoded_size() as u128;
+
+	// Siblings are charged for on top of the price of the inner router.
+	let (_, price) = send_xcm::<Router>((Parent, Parachain(1000)).into(), message.clone()).unwrap();
+	assert_eq!(price, vec![(Parent, 10).into(), (Here, fee).into()].into());
+
+	// And congested channels are more expensive.
+	let (_, price) = send_xcm::<Router>((Parent, Parachain(2000)).into(), message.clone()).unwrap();
+	assert_eq!(price, vec![(Parent, 10).into(), (Here, 2 * fee).into()].into());
+
+	// Other destinations are only charged for by the inner router.
+	let (_, price) = send_xcm::<Router>(Parent.into(), message.clone()).unwrap();
+	assert_eq!(price, (Parent, 10).into());
+	assert_eq!(sent_xcm().len(), 3);
+}
+
+#[test]
+fn prepare_and_execute_should_execute_prefix_within_weight_limit() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+	let message = Xcm::<TestCall>(vec![ClearError, ClearTransactStatus, SetTopic([1; 32])]);
+
+	let (outcome, receipt) =
+		XcmExecutor::<TestConfig>::prepare_and_execute(Parent, message.clone(), [0; 32], 50, 0);
+	assert_eq!(outcome, Outcome::Complete(30));
+	assert_eq!(
+		receipt,
+		ExecutionReceipt { executed: 3, skipped: 0, used: 30, surplus: 0, error_index: None }
+	);
+
+	// Only the instructions which fit are executed; the rest may be executed later.
+	let (outcome, receipt) =
+		XcmExecutor::<TestConfig>::prepare_and_execute(Parent, message.clone(), [0; 32], 25, 0);
+	assert_eq!(outcome, Outcome::Incomplete(20, XcmError::WeightLimitReached(30)));
+	assert_eq!(
+		receipt,
+		ExecutionReceipt { executed: 2, skipped: 1, used: 20, surplus: 0, error_index: Some(2) }
+	);
+
+	let (outcome, receipt) =
+		XcmExecutor::<TestConfig>::prepare_and_execute(Parent, message, [0; 32], 5, 0);
+	assert_eq!(outcome, Outcome::Error(XcmError::WeightLimitReached(30)));
+	assert_eq!(
+		receipt,
+		ExecutionReceipt { executed: 0, skipped: 3, used: 0, surplus: 0, error_index: Some(0) }
+	);
+
+	// A failing instruction ends the message, so there is nothing left to resume.
+	let message = Xcm::<TestCall>(vec![ClearError, Trap(1), ClearError]);
+	let (outcome, receipt) =
+		XcmExecutor::<TestConfig>::prepare_and_execute(Parent, message, [0; 32], 50, 0);
+	assert_eq!(outcome, Outcome::Incomplete(20, XcmError::Trap(1)));
+	assert_eq!(
+		receipt,
+		ExecutionReceipt { executed: 2, skipped: 0, used: 20, surplus: 10, error_index: Some(1) }
+	);
+}
+
+#[test]
+fn prepare_and_execute_should_not_skip_instructions_which_the_barrier_blocks() {
+	AllowPaidFrom::set(vec![Parent.into()]);
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+	WeightPrice::set((Parent.into(), 1_000_000_000_000));
+
+	let fees = (Parent, 30).into();
+	let message = Xcm::<TestCall>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		BuyExecution { fees, weight_limit: Limited(30) },
+		DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Here.into() },
+	]);
+	// The deposit would not be allowed to execute without paying for it, so the message is not
+	// split and nothing is executed.
+	let (outcome, receipt) =
+		XcmExecutor::<TestConfig>::prepare_and_execute(Parent, message, [0; 32], 25, 0);
+	assert_eq!(outcome, Outcome::Error(XcmError::WeightLimitReached(30)));
+	assert_eq!(receipt, ExecutionReceipt::default());
+	assert_eq!(assets(3000), vec![]);
+}
+
+#[test]
+fn prepared_message_should_execute_with_its_weight() {
+	type Executor = XcmExecutor<TestConfig>;
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+	let message = Xcm::<TestCall>(vec![ClearError, ClearTransactStatus]);
+
+	let pre = <Executor as ExecuteXcm<TestCall>>::prepare(message.clone()).unwrap();
+	assert_eq!(pre.weight_of(), 20);
+	let outcome = <Executor as ExecuteXcm<TestCall>>::execute(Parent, pre, [0; 32], 0);
+	assert_eq!(outcome, Outcome::Complete(20));
+
+	// A message with too many instructions cannot be weighed, and so cannot be prepared.
+	let message = Xcm::<TestCall>(vec![ClearError; MaxInstructions::get() as usize + 1]);
+	assert!(<Executor as ExecuteXcm<TestCall>>::prepare(message).is_err());
+}
+
+#[test]
+fn asset_checking_should_work() {
+	use frame_support::traits::{Everything, Nothing};
+	assert_eq!(<NoChecking as AssetChecking<u32>>::asset_checking(&0), None);
+	assert_eq!(LocalMint::<Everything>::asset_checking(&0u32), Some(MintLocation::Local));
+	assert_eq!(LocalMint::<Nothing>::asset_checking(&0u32), None);
+	assert_eq!(NonLocalMint::<Everything>::asset_checking(&0u32), Some(MintLocation::NonLocal));
+	assert_eq!(
+		DualMint::<Everything, Everything>::asset_checking(&0u32),
+		Some(MintLocation::Local)
+	);
+	assert_eq!(
+		DualMint::<Nothing, Everything>::asset_checking(&0u32),
+		Some(MintLocation::NonLocal)
+	);
+	assert_eq!(DualMint::<Nothing, Nothing>::asset_checking(&0u32), None);
+}
@@ -0,0 +1,372 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traits and utilities to help with exporting XCM messages to other consensus systems, such as
+//! remote networks reached over a bridge.
+
+use frame_support::{ensure, traits::Get};
+use parity_scale_codec::{Decode, Encode};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+use xcm_executor::traits::{validate_export, ExportXcm};
+
+/// Return the network and the location interior to it of `dest`, given from the point of view of
+/// the chain whose universal location is `universal_local`, if and only if `dest` lies in a
+/// different global consensus system than the local chain.
+///
+/// Returns `Err` with `dest` if it is not in a remote global consensus system, or if either
+/// location cannot be expressed universally.
+pub fn ensure_is_remote(
+	universal_local: impl Into<InteriorMultiLocation>,
+	dest: impl Into<MultiLocation>,
+) -> Result<(NetworkId, InteriorMultiLocation), MultiLocation> {
+	let dest = dest.into();
+	let universal_local = universal_local.into();
+	let local_net = match universal_local.global_consensus() {
+		Ok(network) => network,
+		Err(()) => return Err(dest),
+	};
+	let universal_destination = match universal_local.within_global(dest.clone()) {
+		Ok(location) => location,
+		Err(()) => return Err(dest),
+	};
+	match universal_destination.split_first() {
+		(remote_dest, Some(GlobalConsensus(remote_net))) if remote_net != local_net =>
+			Ok((remote_net, remote_dest)),
+		_ => Err(dest),
+	}
+}
+
+/// Implementation of `SendXcm` which uses the given `ExportXcm` implementation in order to forward
+/// the message over a bridge.
+///
+/// No effort is made to charge for any bridge fees, so this can only be used when it is known
+/// that the message sending cannot be abused in any way.
+///
+/// This is only useful when the local chain has bridging capabilities, i.e. it is itself the
+/// bridge.
+pub struct UnpaidLocalExporter<Exporter, UniversalLocation>(
+	PhantomData<(Exporter, UniversalLocation)>,
+);
+impl<Exporter: ExportXcm, UniversalLocation: Get<InteriorMultiLocation>> SendXcm
+	for UnpaidLocalExporter<Exporter, UniversalLocation>
+{
+	type Ticket = Exporter::Ticket;
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		xcm: &mut Option<Xcm<()>>,
+	) -> SendResult<Exporter::Ticket> {
+		let d = dest.take().ok_or(SendError::MissingArgument)?;
+		let universal_source = UniversalLocation::get();
+		let (network, destination) = match ensure_is_remote(universal_source.clone(), d) {
+			Ok(x) => x,
+			Err(d) => {
+				*dest = Some(d);
+				return Err(SendError::NotApplicable)
+			},
+		};
+		let xcm = xcm.take().ok_or(SendError::MissingArgument)?;
+		validate_export::<Exporter>(network, 0, universal_source, destination, xcm)
+	}
+
+	fn deliver(ticket: Exporter::Ticket) -> Result<XcmHash, SendError> {
+		Exporter::deliver(ticket)
+	}
+}
+
+/// Something which determines the bridge, if any, through which a message may be exported to a
+/// remote network, together with the fee which the bridge charges for doing so.
+///
+/// Can be amalgamated into tuples. The first item to return `Some` short-circuits, else `None` is
+/// returned.
+pub trait ExporterFor {
+	/// Return the location of the bridge through which `message` may be exported to
+	/// `remote_location` on `network`, along with the fee, if any, which must be paid to the bridge
+	/// for it, given from the point of view of the local chain.
+	fn exporter_for(
+		network: &NetworkId,
+		remote_location: &InteriorMultiLocation,
+		message: &Xcm<()>,
+	) -> Option<(MultiLocation, Option<MultiAsset>)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl ExporterFor for Tuple {
+	fn exporter_for(
+		network: &NetworkId,
+		remote_location: &InteriorMultiLocation,
+		message: &Xcm<()>,
+	) -> Option<(MultiLocation, Option<MultiAsset>)> {
+		for_tuples!( #(
+			if let Some(r) = Tuple::exporter_for(network, remote_location, message) {
+				return Some(r);
+			}
+		)* );
+		None
+	}
+}
+
+/// An `ExporterFor` which looks up the bridge for a network in a table of
+/// `(network, bridge location, bridge fee)` entries.
+pub struct NetworkExportTable<T>(PhantomData<T>);
+impl<T: Get<Vec<(NetworkId, MultiLocation, Option<MultiAsset>)>>> ExporterFor
+	for NetworkExportTable<T>
+{
+	fn exporter_for(
+		network: &NetworkId,
+		_remote_location: &InteriorMultiLocation,
+		_message: &Xcm<()>,
+	) -> Option<(MultiLocation, Option<MultiAsset>)> {
+		T::get()
+			.into_iter()
+			.find(|(n, ..)| n == network)
+			.map(|(_, bridge, payment)| (bridge, payment))
+	}
+}
+
+/// Implementation of `SendXcm` which wraps the message inside an `ExportMessage` instruction and
+/// sends it to a destination known to be able to handle it.
+///
+/// The bridge and its fee are determined by `Bridges`. Should the bridge charge a fee, the
+/// message to the bridge begins by withdrawing the fee from the sovereign account of the local
+/// chain on the bridge and buying execution with it. The fee is added to the delivery cost of the
+/// message to the bridge, which is routed by `Router`, so that whoever sends the message pays
+/// for both.
+///
+/// This is only useful if the local chain is not itself a bridge, but relies on one within the
+/// same global consensus system.
+pub struct SovereignPaidRemoteExporter<Bridges, Router, UniversalLocation>(
+	PhantomData<(Bridges, Router, UniversalLocation)>,
+);
+impl<Bridges: ExporterFor, Router: SendXcm, UniversalLocation: Get<InteriorMultiLocation>> SendXcm
+	for SovereignPaidRemoteExporter<Bridges, Router, UniversalLocation>
+{
+	type Ticket = Router::Ticket;
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		xcm: &mut Option<Xcm<()>>,
+	) -> SendResult<Router::Ticket> {
+		let d = dest.as_ref().ok_or(SendError::MissingArgument)?.clone();
+		let (remote_network, remote_location) =
+			ensure_is_remote(UniversalLocation::get(), d).map_err(|_| SendError::NotApplicable)?;
+
+		let message = xcm.as_ref().ok_or(SendError::MissingArgument)?;
+		let (bridge, maybe_payment) =
+			Bridges::exporter_for(&remote_network, &remote_location, message)
+				.ok_or(SendError::NotApplicable)?;
+		// Only consume the message once we know it is ours to route.
+		let message = xcm.take().ok_or(SendError::MissingArgument)?;
+
+		let export_instruction =
+			ExportMessage { network: remote_network, destination: remote_location, xcm: message };
+		let message = Xcm(if let Some(ref payment) = maybe_payment {
+			let fees = payment
+				.clone()
+				.reanchored(&bridge, &UniversalLocation::get())
+				.map_err(|_| SendError::Unroutable)?;
+			vec![
+				WithdrawAsset(fees.clone().into()),
+				BuyExecution { fees, weight_limit: Unlimited },
+				export_instruction,
+			]
+		} else {
+			vec![export_instruction]
+		});
+
+		// We then send a normal message to the bridge asking it to export the prepended message
+		// to the remote chain.
+		let (ticket, mut cost) = validate_send::<Router>(bridge, message)?;
+		if let Some(bridge_payment) = maybe_payment {
+			cost.push(bridge_payment);
+		}
+		Ok((ticket, cost))
+	}
+
+	fn deliver(ticket: Router::Ticket) -> Result<XcmHash, SendError> {
+		Router::deliver(ticket)
+	}
+}
+
+/// The wire format of a message carried over a bridge.
+///
+/// The universal locations are versioned `MultiLocation`s without parents, so that both sides of
+/// the bridge may be upgraded to new XCM versions independently.
+#[derive(Clone, Encode, Decode)]
+pub struct BridgeMessage {
+	/// The universal location of the origin of the message.
+	pub universal_source: VersionedMultiLocation,
+	/// The universal location of the destination of the message.
+	pub universal_dest: VersionedMultiLocation,
+	/// The message to be delivered to the destination.
+	pub message: VersionedXcm<()>,
+}
+
+/// Something which can transport an opaque blob to the other side of a bridge.
+pub trait HaulBlob {
+	/// Send a blob over some bridge to the other side, where it will be given to `DispatchBlob`.
+	fn haul_blob(blob: Vec<u8>) -> Result<(), HaulBlobError>;
+}
+
+/// Error returned by `HaulBlob::haul_blob`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug)]
+pub enum HaulBlobError {
+	/// There was an issue with the bridge transport; a human-readable explanation is provided.
+	Transport(#[codec(skip)] &'static str),
+}
+
+impl From<HaulBlobError> for SendError {
+	fn from(e: HaulBlobError) -> Self {
+		match e {
+			HaulBlobError::Transport(s) => SendError::Transport(s),
+		}
+	}
+}
+
+/// Something which can dispatch a blob received from the other side of a bridge.
+pub trait DispatchBlob {
+	/// Dispatch a blob which was hauled over a bridge by `HaulBlob`.
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError>;
+}
+
+/// Error returned by `DispatchBlob::dispatch_blob`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug)]
+pub enum DispatchBlobError {
+	/// The local chain has no global consensus and so cannot receive bridged messages.
+	Unbridgable,
+	/// The blob is not a `BridgeMessage`.
+	InvalidEncoding,
+	/// A universal location is of a version which cannot be interpreted.
+	UnsupportedLocationVersion,
+	/// The message is of a version which cannot be interpreted.
+	UnsupportedXcmVersion,
+	/// The message could not be routed to its destination.
+	RoutingError,
+	/// The source of the message is not a universal location.
+	NonUniversalSource,
+	/// The destination of the message is not a universal location.
+	NonUniversalDestination,
+	/// The message comes from the local global consensus system, which a bridge never carries.
+	LocalSource,
+	/// The destination of the message is not in the local global consensus system.
+	WrongGlobal,
+}
+
+/// Turn a versioned universal location back into an `InteriorMultiLocation`, mapping failures to
+/// `non_universal`.
+fn universal_location(
+	location: VersionedMultiLocation,
+	non_universal: DispatchBlobError,
+) -> Result<(InteriorMultiLocation, NetworkId), DispatchBlobError> {
+	let location: MultiLocation = location
+		.try_into()
+		.map_err(|()| DispatchBlobError::UnsupportedLocationVersion)?;
+	ensure!(location.parents == 0, non_universal.clone());
+	let network = location.interior.global_consensus().map_err(|()| non_universal)?;
+	Ok((location.interior, network))
+}
+
+/// A `DispatchBlob` which routes the messages in the blobs to their destinations within the local
+/// global consensus system using `Router`.
+///
+/// Each message is prefixed with `UniversalOrigin` and, unless it was sent by the remote relay
+/// chain itself, `DescendOrigin`, so that it executes as the location which sent it. Its
+/// destination must therefore allow the bridge to alias the remote global consensus system, as
+/// configured with `UniversalAliases`.
+///
+/// - `Router`: How to send the message on to its destination.
+/// - `OurPlace`: The universal location of the local chain, i.e. the end of the bridge.
+pub struct BridgeBlobDispatcher<Router, OurPlace>(PhantomData<(Router, OurPlace)>);
+impl<Router: SendXcm, OurPlace: Get<InteriorMultiLocation>> DispatchBlob
+	for BridgeBlobDispatcher<Router, OurPlace>
+{
+	fn dispatch_blob(blob: Vec<u8>) -> Result<(), DispatchBlobError> {
+		let our_universal = OurPlace::get();
+		let our_global =
+			our_universal.global_consensus().map_err(|()| DispatchBlobError::Unbridgable)?;
+		let BridgeMessage { universal_source, universal_dest, message } =
+			Decode::decode(&mut &blob[..]).map_err(|_| DispatchBlobError::InvalidEncoding)?;
+
+		let (universal_source, source_global) =
+			universal_location(universal_source, DispatchBlobError::NonUniversalSource)?;
+		ensure!(source_global != our_global, DispatchBlobError::LocalSource);
+		let (universal_dest, intended_global) =
+			universal_location(universal_dest, DispatchBlobError::NonUniversalDestination)?;
+		ensure!(intended_global == our_global, DispatchBlobError::WrongGlobal);
+
+		let dest = universal_dest.relative_to(&our_universal);
+		let mut message: Xcm<()> =
+			message.try_into().map_err(|()| DispatchBlobError::UnsupportedXcmVersion)?;
+		// Make the message come from its source on the other side of the bridge.
+		let (source_interior, _) = universal_source.split_first();
+		if source_interior != Here {
+			message.0.insert(0, DescendOrigin(source_interior));
+		}
+		message.0.insert(0, UniversalOrigin(GlobalConsensus(source_global)));
+		send_xcm::<Router>(dest, message).map_err(|_| DispatchBlobError::RoutingError)?;
+		Ok(())
+	}
+}
+
+/// An `ExportXcm` which frames messages for `BridgedNetwork` as `BridgeMessage`s and hauls them
+/// over the bridge with `Bridge`, charging `Price` for each.
+///
+/// The `channel` of the export is not used, since the bridge delivers all messages in order.
+pub struct HaulBlobExporter<Bridge, BridgedNetwork, Price>(
+	PhantomData<(Bridge, BridgedNetwork, Price)>,
+);
+impl<Bridge: HaulBlob, BridgedNetwork: Get<NetworkId>, Price: Get<MultiAssets>> ExportXcm
+	for HaulBlobExporter<Bridge, BridgedNetwork, Price>
+{
+	type Ticket = (Vec<u8>, XcmHash);
+
+	fn validate(
+		network: NetworkId,
+		_channel: u32,
+		universal_source: &mut Option<InteriorMultiLocation>,
+		destination: &mut Option<InteriorMultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<(Vec<u8>, XcmHash)> {
+		let bridged_network = BridgedNetwork::get();
+		ensure!(network == bridged_network, SendError::NotApplicable);
+		let dest = destination.take().ok_or(SendError::MissingArgument)?;
+		let universal_dest: MultiLocation =
+			match dest.pushed_front_with(GlobalConsensus(bridged_network)) {
+				Ok(d) => d.into(),
+				Err((dest, _)) => {
+					*destination = Some(dest);
+					return Err(SendError::NotApplicable)
+				},
+			};
+		let universal_source: MultiLocation =
+			universal_source.take().ok_or(SendError::MissingArgument)?.into();
+		let message = VersionedXcm::from(message.take().ok_or(SendError::MissingArgument)?);
+		let hash = message.using_encoded(sp_io::hashing::blake2_256);
+		let blob = BridgeMessage {
+			universal_source: universal_source.into(),
+			universal_dest: universal_dest.into(),
+			message,
+		}
+		.encode();
+		Ok(((blob, hash), Price::get()))
+	}
+
+	fn deliver((blob, hash): (Vec<u8>, XcmHash)) -> Result<XcmHash, SendError> {
+		Bridge::haul_blob(blob)?;
+		Ok(hash)
+	}
+}
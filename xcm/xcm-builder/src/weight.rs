@@ -15,7 +15,9 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use frame_support::{
-	traits::{tokens::currency::Currency as CurrencyT, Get, OnUnbalanced as OnUnbalancedT},
+	traits::{
+		tokens::currency::Currency as CurrencyT, Contains, Get, OnUnbalanced as OnUnbalancedT,
+	},
 	weights::{constants::WEIGHT_PER_SECOND, GetDispatchInfo, Weight, WeightToFeePolynomial},
 };
 use parity_scale_codec::Decode;
@@ -23,7 +25,7 @@ use sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
 use sp_std::{convert::TryInto, marker::PhantomData, result::Result};
 use xcm::latest::prelude::*;
 use xcm_executor::{
-	traits::{WeightBounds, WeightTrader},
+	traits::{AssetExchange, WeightBounds, WeightTrader},
 	Assets,
 };
 
@@ -238,6 +240,115 @@ impl<T: Get<(AssetId, u128)>, R: TakeRevenue> Drop for FixedRateOfFungible<T, R>
 	}
 }
 
+/// Fee calculator which requires payment in a single fungible at a fixed rate, but which accepts
+/// payment in any other fungible asset for which `Exchange` can provide it, so that the payer need
+/// not hold the fee asset.
+///
+/// The constant `Get` type parameter `Target` should be the ID of the fee asset and the amount of
+/// it required for one second of weight. The first fungible asset of the payment which is in
+/// `Pools` is swapped into the fee asset through `Exchange`; only a single such asset is used by
+/// each trader. Refunds are swapped back into it where possible, and are otherwise made in the fee
+/// asset.
+pub struct SwapFirstAssetTrader<
+	Target: Get<(AssetId, u128)>,
+	Pools: Contains<AssetId>,
+	Exchange: AssetExchange,
+	R: TakeRevenue,
+> {
+	/// The weight bought so far.
+	weight: Weight,
+	/// The amount of the fee asset paid for it.
+	fee: u128,
+	/// The asset which was swapped into the fee asset, if any has been.
+	swapped: Option<AssetId>,
+	_phantom: PhantomData<(Target, Pools, Exchange, R)>,
+}
+impl<
+		Target: Get<(AssetId, u128)>,
+		Pools: Contains<AssetId>,
+		Exchange: AssetExchange,
+		R: TakeRevenue,
+	> WeightTrader for SwapFirstAssetTrader<Target, Pools, Exchange, R>
+{
+	fn new() -> Self {
+		Self { weight: 0, fee: 0, swapped: None, _phantom: PhantomData }
+	}
+
+	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, XcmError> {
+		log::trace!(
+			target: "xcm::weight",
+			"SwapFirstAssetTrader::buy_weight weight: {:?}, payment: {:?}",
+			weight, payment,
+		);
+		let (target, units_per_second) = Target::get();
+		let amount = units_per_second * (weight as u128) / (WEIGHT_PER_SECOND as u128);
+		if amount == 0 {
+			return Ok(payment)
+		}
+		let give = payment
+			.fungible_assets_iter()
+			.find(|asset| {
+				asset.id != target &&
+					Pools::contains(&asset.id) &&
+					self.swapped.as_ref().map_or(true, |id| *id == asset.id)
+			})
+			.ok_or(XcmError::TooExpensive)?;
+		let id = give.id.clone();
+		let mut unused = payment;
+		let give = unused
+			.try_take(MultiAssetFilter::Definite(give.into()))
+			.map_err(|_| XcmError::TooExpensive)?;
+		let want: MultiAssets = vec![(target.clone(), amount).into()].into();
+		let swapped = Exchange::exchange_asset(None, give, &want, false)
+			.map_err(|_| XcmError::TooExpensive)?;
+		let change = swapped
+			.checked_sub((target, amount).into())
+			.map_err(|_| XcmError::TooExpensive)?;
+		unused.subsume_assets(change);
+		self.weight = self.weight.saturating_add(weight);
+		self.fee = self.fee.saturating_add(amount);
+		self.swapped = Some(id);
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		log::trace!(target: "xcm::weight", "SwapFirstAssetTrader::refund_weight weight: {:?}", weight);
+		let (target, units_per_second) = Target::get();
+		let weight = weight.min(self.weight);
+		let amount = units_per_second * (weight as u128) / (WEIGHT_PER_SECOND as u128);
+		self.weight -= weight;
+		self.fee = self.fee.saturating_sub(amount);
+		if amount == 0 {
+			return None
+		}
+		let refund: MultiAsset = (target, amount).into();
+		let id = match self.swapped.clone() {
+			Some(id) => id,
+			None => return Some(refund),
+		};
+		let want: MultiAssets = vec![(id.clone(), 1).into()].into();
+		// Refund in the fee asset if it cannot be swapped back.
+		Exchange::exchange_asset(None, refund.clone().into(), &want, true)
+			.ok()
+			.and_then(|swapped| swapped.fungible_assets_iter().find(|asset| asset.id == id))
+			.or(Some(refund))
+	}
+}
+
+impl<
+		Target: Get<(AssetId, u128)>,
+		Pools: Contains<AssetId>,
+		Exchange: AssetExchange,
+		R: TakeRevenue,
+	> Drop for SwapFirstAssetTrader<Target, Pools, Exchange, R>
+{
+	fn drop(&mut self) {
+		if self.fee > 0 {
+			R::take_revenue((Target::get().0, self.fee).into());
+		}
+	}
+}
+
 /// Weight trader which uses the `TransactionPayment` pallet to set the right price for weight and then
 /// places any weight bought into the right account.
 pub struct UsingComponents<
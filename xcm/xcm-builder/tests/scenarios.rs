@@ -17,7 +17,8 @@
 mod mock;
 
 use mock::{
-	kusama_like_with_balances, AccountId, Balance, Balances, BaseXcmWeight, XcmConfig, CENTS,
+	kusama_like_with_balances, AccountId, Balance, Balances, BaseXcmWeight, CheckAccount,
+	XcmConfig, CENTS,
 };
 use polkadot_parachain::primitives::Id as ParaId;
 use sp_runtime::traits::AccountIdConversion;
@@ -247,6 +248,71 @@ fn teleport_to_statemine_works() {
 	});
 }
 
+/// Scenario:
+/// A parachain teleports KSM to Statemine, which later teleports some of it back.
+///
+/// Asserts that the checking account holds exactly the amount which lives on other chains, that
+/// total issuance is preserved and that no more can be teleported back than went out.
+#[test]
+fn teleport_checking_account_tracks_teleported_amount() {
+	let para_acc: AccountId = ParaId::from(PARA_ID).into_account();
+	let balances = vec![(ALICE, INITIAL_BALANCE), (para_acc.clone(), INITIAL_BALANCE)];
+	kusama_like_with_balances(balances).execute_with(|| {
+		let statemine_id = 1000;
+		let amount = REGISTER_AMOUNT;
+		let weight = 3 * BaseXcmWeight::get();
+		let check_account = CheckAccount::get();
+		let total_issuance = Balances::total_issuance();
+
+		let r = XcmExecutor::<XcmConfig>::execute_xcm(
+			Parachain(PARA_ID).into(),
+			Xcm(vec![
+				WithdrawAsset((Here, 2 * amount).into()),
+				buy_execution(),
+				InitiateTeleport {
+					assets: All.into(),
+					dest: Parachain(statemine_id).into(),
+					xcm: Xcm(vec![]),
+				},
+			]),
+			weight,
+		);
+		assert_eq!(r, Outcome::Complete(weight));
+		assert_eq!(Balances::free_balance(&check_account), 2 * amount);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+
+		let teleport_in = |amount: Balance| {
+			let weight = 4 * BaseXcmWeight::get();
+			let message = Xcm(vec![
+				ReceiveTeleportedAsset((Here, amount).into()),
+				ClearOrigin,
+				buy_execution(),
+				DepositAsset {
+					assets: All.into(),
+					max_assets: 1,
+					beneficiary: Parachain(PARA_ID).into(),
+				},
+			]);
+			XcmExecutor::<XcmConfig>::execute_xcm(Parachain(statemine_id), message, weight)
+		};
+
+		// Half of it comes back.
+		assert_eq!(teleport_in(amount), Outcome::Complete(4 * BaseXcmWeight::get()));
+		assert_eq!(Balances::free_balance(&check_account), amount);
+		assert_eq!(Balances::free_balance(&para_acc), INITIAL_BALANCE - amount);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+
+		// No more can come back than is still out.
+		assert!(matches!(
+			teleport_in(2 * amount),
+			Outcome::Incomplete(_, XcmError::NotWithdrawable)
+		));
+		assert_eq!(Balances::free_balance(&check_account), amount);
+		assert_eq!(Balances::free_balance(&para_acc), INITIAL_BALANCE - amount);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+	});
+}
+
 /// Scenario:
 /// A parachain wants to move KSM from Kusama to the parachain.
 /// It withdraws funds and then deposits them into the reserve account of the destination chain.
@@ -298,3 +364,37 @@ fn reserve_based_transfer_works() {
 		);
 	});
 }
+
+mod local_asset_transactor_conformance {
+	use super::*;
+	use mock::{LocalAssetTransactor, SovereignAccountOf};
+	use xcm_executor::traits::Convert;
+
+	xcm_executor_test_suite::impl_transact_asset_tests! {
+		transactor: LocalAssetTransactor,
+		new_ext: kusama_like_with_balances(vec![(ALICE, INITIAL_BALANCE)]),
+		asset_id: Here,
+		amount: REGISTER_AMOUNT,
+		funded: AccountId32 { network: Any, id: ALICE.into() },
+		funded_balance: INITIAL_BALANCE,
+		unfunded: Parachain(PARA_ID),
+		teleport_location: Parachain(1000),
+		balance_of: |who: &MultiLocation| {
+			let account = SovereignAccountOf::convert_ref(who).expect("location is convertible");
+			Balances::free_balance(account)
+		},
+	}
+}
+
+mod trader_conformance {
+	use super::*;
+	use mock::XcmConfig;
+
+	xcm_executor_test_suite::impl_weight_trader_tests! {
+		trader: <XcmConfig as xcm_executor::Config>::Trader,
+		new_ext: kusama_like_with_balances(vec![]),
+		payment: (Here, 10),
+		// Three whole units at one unit per second, plus a remainder which rounds down.
+		weight: 3 * frame_support::weights::constants::WEIGHT_PER_SECOND + 1,
+	}
+}
@@ -23,7 +23,7 @@ use sp_std::{
 use xcm::latest::{
 	AssetId, AssetInstance,
 	Fungibility::{Fungible, NonFungible},
-	MultiAsset, MultiAssetFilter, MultiAssets, MultiLocation,
+	InteriorMultiLocation, MultiAsset, MultiAssetFilter, MultiAssets, MultiLocation,
 	WildFungibility::{Fungible as WildFungible, NonFungible as WildNonFungible},
 	WildMultiAsset::{All, AllOf},
 };
@@ -76,6 +76,33 @@ impl From<Assets> for MultiAssets {
 	}
 }
 
+/// The means by which an asset came to be in the Holding Register.
+#[derive(Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+pub enum Provenance {
+	/// Withdrawn from a local account.
+	Local,
+	/// Checked in after being teleported from a trusted location.
+	Teleported,
+	/// A derivative of an asset held in reserve by the location which deposited it. Such assets
+	/// may only be deposited locally or withdrawn back from their reserve.
+	ReserveBacked,
+}
+
+impl Provenance {
+	/// The provenance of an asset placed into Holding by both `self` and `other`.
+	///
+	/// Since Holding does not distinguish between amounts of the same asset, any asset which was
+	/// at least partially reserve-backed is considered to be entirely so.
+	pub fn combine(self, other: Provenance) -> Provenance {
+		match (self, other) {
+			(Provenance::ReserveBacked, _) | (_, Provenance::ReserveBacked) =>
+				Provenance::ReserveBacked,
+			(a, b) if a == b => a,
+			_ => Provenance::Local,
+		}
+	}
+}
+
 /// An error emitted by `take` operations.
 #[derive(Debug)]
 pub enum TakeError {
@@ -99,6 +126,11 @@ impl Assets {
 		self.fungible.is_empty() && self.non_fungible.is_empty()
 	}
 
+	/// Returns `true` if `self` contains any asset, fungible or not, with the given `id`.
+	pub fn contains_id(&self, id: &AssetId) -> bool {
+		self.fungible.contains_key(id) || self.non_fungible.iter().any(|(i, _)| i == id)
+	}
+
 	/// A borrowing iterator over the fungible assets.
 	pub fn fungible_assets_iter<'a>(&'a self) -> impl Iterator<Item = MultiAsset> + 'a {
 		self.fungible
@@ -210,20 +242,20 @@ impl Assets {
 	}
 
 	/// Mutate the assets to be interpreted as the same assets from the perspective of a `target`
-	/// chain. The local chain's `ancestry` is provided.
+	/// chain. The universal location of the local chain is provided as `context`.
 	///
 	/// Any assets which were unable to be reanchored are introduced into `failed_bin`.
 	pub fn reanchor(
 		&mut self,
 		target: &MultiLocation,
-		ancestry: &MultiLocation,
+		context: &InteriorMultiLocation,
 		mut maybe_failed_bin: Option<&mut Self>,
 	) {
 		let mut fungible = Default::default();
 		mem::swap(&mut self.fungible, &mut fungible);
 		self.fungible = fungible
 			.into_iter()
-			.filter_map(|(mut id, amount)| match id.reanchor(target, ancestry) {
+			.filter_map(|(mut id, amount)| match id.reanchor(target, context) {
 				Ok(()) => Some((id, amount)),
 				Err(()) => {
 					maybe_failed_bin.as_mut().map(|f| f.fungible.insert(id, amount));
@@ -235,7 +267,7 @@ impl Assets {
 		mem::swap(&mut self.non_fungible, &mut non_fungible);
 		self.non_fungible = non_fungible
 			.into_iter()
-			.filter_map(|(mut class, inst)| match class.reanchor(target, ancestry) {
+			.filter_map(|(mut class, inst)| match class.reanchor(target, context) {
 				Ok(()) => Some((class, inst)),
 				Err(()) => {
 					maybe_failed_bin.as_mut().map(|f| f.non_fungible.insert((class, inst)));
@@ -703,4 +735,15 @@ mod tests {
 		let assets = assets1.into_assets_iter().collect::<Vec<_>>();
 		assert_eq!(assets, vec![AF(1, 50), ANF(2, 20)]);
 	}
+
+	#[test]
+	fn provenance_combine_works() {
+		use Provenance::*;
+		assert_eq!(Local.combine(Local), Local);
+		assert_eq!(Teleported.combine(Teleported), Teleported);
+		assert_eq!(Local.combine(Teleported), Local);
+		assert_eq!(Teleported.combine(Local), Local);
+		assert_eq!(Local.combine(ReserveBacked), ReserveBacked);
+		assert_eq!(ReserveBacked.combine(Teleported), ReserveBacked);
+	}
 }
@@ -15,14 +15,16 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::traits::{
-	ClaimAssets, ConvertOrigin, DropAssets, FilterAssetLocation, InvertLocation, OnResponse,
-	ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
+	CheckSuspension, ClaimAssets, ConvertOrigin, DropAssets, ExportXcm, FilterAssetLocation,
+	OnResponse, ShouldExecute, TeleportLimit, TransactAsset, VersionChangeNotifier, WeightBounds,
+	WeightTrader,
 };
 use frame_support::{
 	dispatch::{Dispatchable, Parameter},
+	traits::{Contains, Get, PalletsInfoAccess},
 	weights::{GetDispatchInfo, PostDispatchInfo},
 };
-use xcm::latest::SendXcm;
+use xcm::latest::{InteriorMultiLocation, Junction, MultiLocation, SendXcm};
 
 /// The trait to parameterize the `XcmExecutor`.
 pub trait Config {
@@ -44,8 +46,16 @@ pub trait Config {
 	/// Combinations of (Location, Asset) pairs which we trust as teleporters.
 	type IsTeleporter: FilterAssetLocation;
 
-	/// Means of inverting a location.
-	type LocationInverter: InvertLocation;
+	/// The maximum amounts of assets which may be teleported to or from a location in one message.
+	type TeleportLimits: TeleportLimit;
+
+	/// This chain's universal location: its location from the point of view of the universal
+	/// root, including its `GlobalConsensus`. Used to reanchor locations and assets for other
+	/// chains.
+	type UniversalLocation: Get<InteriorMultiLocation>;
+
+	/// Whether the execution of XCM is currently suspended. This is checked before `Barrier`.
+	type CheckSuspension: CheckSuspension;
 
 	/// Whether we should execute the given XCM at all.
 	type Barrier: ShouldExecute;
@@ -68,4 +78,14 @@ pub trait Config {
 
 	/// How we handle version subscription requests.
 	type SubscriptionService: VersionChangeNotifier;
+
+	/// Information on all pallets, as reported by `QueryPallet` and checked by `ExpectPallet`.
+	type PalletInstancesInfo: PalletsInfoAccess;
+
+	/// How we handle messages to be exported to non-local consensus systems, e.g. over a bridge.
+	type MessageExporter: ExportXcm;
+
+	/// The origins which may stand in for the given children of the Universal Ancestor through
+	/// `UniversalOrigin`, typically the bridges to the global consensus systems they name.
+	type UniversalAliases: Contains<(MultiLocation, Junction)>;
 }
@@ -19,31 +19,49 @@
 use frame_support::{
 	dispatch::{Dispatchable, Weight},
 	ensure,
-	weights::GetDispatchInfo,
+	traits::{Contains, Get, PalletsInfoAccess},
+	weights::{GetDispatchInfo, WeightMeter},
 };
+use parity_scale_codec::{Decode, Encode};
 use sp_runtime::traits::Saturating;
-use sp_std::{marker::PhantomData, prelude::*};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
 use xcm::latest::{
-	Error as XcmError, ExecuteXcm,
+	validate_send, AssetId, Error as XcmError, ExecuteXcm, ExecutionReceipt,
+	Fungibility::Fungible,
 	Instruction::{self, *},
-	MultiAssets, MultiLocation, Outcome, Response, SendXcm, Xcm,
+	Junctions, MaybeErrorCode, MultiAsset, MultiAssets, MultiLocation, Outcome, PalletInfo,
+	PreparedMessage, QueryResponseInfo, Response, Result as XcmResult, SendXcm, Xcm, XcmContext,
+	XcmHash,
 };
 
 pub mod traits;
 use traits::{
-	ClaimAssets, ConvertOrigin, DropAssets, FilterAssetLocation, InvertLocation, OnResponse,
-	ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
+	validate_export, CheckSuspension, ClaimAssets, ConvertOrigin, DropAssets, ExportXcm,
+	FilterAssetLocation, OnResponse, Properties, ShouldExecute, TeleportLimit, TransactAsset,
+	VersionChangeNotifier, WeightBounds, WeightTrader,
 };
 
 mod assets;
-pub use assets::Assets;
+pub use assets::{Assets, Provenance};
 mod config;
 pub use config::Config;
+mod process_message;
+pub use process_message::ProcessXcmMessage;
 
 /// The XCM executor.
 pub struct XcmExecutor<Config: config::Config> {
 	pub holding: Assets,
+	/// How each asset which has entered `holding` during execution got there. An entry is kept
+	/// for as long as any of its asset remains in `holding`, so an asset which was reserve-backed
+	/// at any point retains that provenance until it has all been deposited, sent or burnt.
+	/// Assets without an entry are considered to be `Local`.
+	pub holding_provenance: BTreeMap<AssetId, Provenance>,
+	/// The location on whose behalf the message is being executed: the `original_origin` with any
+	/// interior locations appended by `DescendOrigin`, or `None` once cleared by `ClearOrigin`.
+	///
+	/// This is the location used by `Transact` for origin conversion.
 	pub origin: Option<MultiLocation>,
+	/// The location from which the message was received; unaffected by origin mutation.
 	pub original_origin: MultiLocation,
 	pub trader: Config::Trader,
 	/// The most recent error result and instruction index into the fragment in which it occurred,
@@ -59,18 +77,52 @@ pub struct XcmExecutor<Config: config::Config> {
 	pub error_handler_weight: u64,
 	pub appendix: Xcm<Config::Call>,
 	pub appendix_weight: u64,
+	/// The result of the most recent `Transact` dispatch, as reported by `ReportTransactStatus`.
+	pub transact_status: MaybeErrorCode,
+	/// The identifier of the message, as set by `SetTopic`.
+	pub topic: Option<[u8; 32]>,
+	/// The identity of the message being executed, as given to the executor.
+	pub message_id: XcmHash,
 	_config: PhantomData<Config>,
 }
 
 /// The maximum recursion limit for `execute_xcm` and `execute_effects`.
 pub const MAX_RECURSION_LIMIT: u32 = 8;
 
+/// An XCM message which has been weighed by `Config::Weigher` and so is ready for execution.
+pub struct WeighedMessage<Call>(Weight, Xcm<Call>);
+impl<C> PreparedMessage for WeighedMessage<C> {
+	fn weight_of(&self) -> Weight {
+		self.0
+	}
+}
+
 impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
+	type Prepared = WeighedMessage<Config::Call>;
+
+	fn prepare(
+		mut message: Xcm<Config::Call>,
+	) -> Result<WeighedMessage<Config::Call>, Xcm<Config::Call>> {
+		match Config::Weigher::weight(&mut message) {
+			Ok(weight) => Ok(WeighedMessage(weight, message)),
+			Err(()) => Err(message),
+		}
+	}
+
+	fn execute(
+		origin: impl Into<MultiLocation>,
+		WeighedMessage(xcm_weight, message): WeighedMessage<Config::Call>,
+		id: XcmHash,
+		weight_credit: Weight,
+	) -> Outcome {
+		Self::execute_weighed(origin.into(), message, xcm_weight, xcm_weight, id, weight_credit).0
+	}
+
 	fn execute_xcm_in_credit(
 		origin: impl Into<MultiLocation>,
-		mut message: Xcm<Config::Call>,
+		message: Xcm<Config::Call>,
 		weight_limit: Weight,
-		mut weight_credit: Weight,
+		weight_credit: Weight,
 	) -> Outcome {
 		let origin = origin.into();
 		log::trace!(
@@ -81,9 +133,15 @@ impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
 			weight_limit,
 			weight_credit,
 		);
-		let xcm_weight = match Config::Weigher::weight(&mut message) {
+		// A message which names itself through a trailing `SetTopic` needs no hashing to be
+		// identified; only messages without a topic pay for the hashing of their encoding.
+		let id = match message.0.last() {
+			Some(SetTopic(topic)) => *topic,
+			_ => message.using_encoded(sp_io::hashing::blake2_256),
+		};
+		let WeighedMessage(xcm_weight, message) = match Self::prepare(message) {
 			Ok(x) => x,
-			Err(()) => {
+			Err(message) => {
 				log::debug!(
 					target: "xcm::execute_xcm_in_credit",
 					"Weight not computable! (origin: {:?}, message: {:?}, weight_limit: {:?}, weight_credit: {:?})",
@@ -108,38 +166,87 @@ impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
 			);
 			return Outcome::Error(XcmError::WeightLimitReached(xcm_weight))
 		}
+		Self::execute_weighed(origin, message, xcm_weight, weight_limit, id, weight_credit).0
+	}
 
-		if let Err(e) =
-			Config::Barrier::should_execute(&origin, &mut message, xcm_weight, &mut weight_credit)
-		{
-			log::debug!(
-				target: "xcm::execute_xcm_in_credit",
-				"Barrier blocked execution! Error: {:?}. (origin: {:?}, message: {:?}, weight_limit: {:?}, weight_credit: {:?})",
-				e,
+	fn prepare_and_execute(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Config::Call>,
+		id: XcmHash,
+		weight_limit: Weight,
+		weight_credit: Weight,
+	) -> (Outcome, ExecutionReceipt) {
+		let origin = origin.into();
+		let WeighedMessage(xcm_weight, mut message) = match Self::prepare(message) {
+			Ok(x) => x,
+			Err(_) => return (Outcome::Error(XcmError::WeightNotComputable), Default::default()),
+		};
+		if xcm_weight <= weight_limit {
+			return Self::execute_weighed(
 				origin,
 				message,
+				xcm_weight,
 				weight_limit,
+				id,
 				weight_credit,
-			);
-			return Outcome::Error(XcmError::Barrier)
+			)
 		}
 
-		let mut vm = Self::new(origin);
-
-		while !message.0.is_empty() {
-			let result = vm.execute(message);
-			log::trace!(target: "xcm::execute_xcm_in_credit", "result: {:?}", result);
-			message = if let Err(error) = result {
-				vm.total_surplus.saturating_accrue(error.weight);
-				vm.error = Some((error.index, error.xcm_error));
-				vm.take_error_handler().or_else(|| vm.take_appendix())
-			} else {
-				vm.drop_error_handler();
-				vm.take_appendix()
+		// Find the longest prefix of the message which fits into the weight limit.
+		let mut prefix_weight: Weight = 0;
+		let mut prefix_len = 0;
+		for instruction in message.0.iter() {
+			match Config::Weigher::instr_weight(instruction) {
+				Ok(w) if prefix_weight.saturating_add(w) <= weight_limit =>
+					prefix_weight.saturating_accrue(w),
+				_ => break,
 			}
+			prefix_len += 1;
+		}
+		let skipped = (message.0.len() - prefix_len) as u32;
+		let error = XcmError::WeightLimitReached(xcm_weight);
+		if prefix_len == 0 {
+			let receipt = ExecutionReceipt { skipped, error_index: Some(0), ..Default::default() };
+			return (Outcome::Error(error), receipt)
+		}
+
+		// The skipped instructions will be executed as a message of their own, so they must pass
+		// the barrier as such. If they would not, nothing is executed, since nothing could be
+		// resumed after the prefix.
+		let mut suffix = Xcm(message.0.split_off(prefix_len));
+		let suffix_weight = xcm_weight.saturating_sub(prefix_weight);
+		let mut properties = Properties { weight_credit: 0, message_id: Some(id) };
+		if let Err(()) =
+			Config::Barrier::should_execute(&origin, &mut suffix, suffix_weight, &mut properties)
+		{
+			log::debug!(
+				target: "xcm::execute_xcm_in_credit",
+				"Barrier blocked the suffix of a partial execution. (origin: {:?}, suffix: {:?}, suffix_weight: {:?})",
+				origin,
+				suffix,
+				suffix_weight,
+			);
+			return (Outcome::Error(error), Default::default())
 		}
 
-		vm.post_execute(xcm_weight)
+		match Self::execute_weighed(origin, message, prefix_weight, weight_limit, id, weight_credit)
+		{
+			// Only a prefix which was executed successfully allows the rest of the message to be
+			// executed later.
+			(Outcome::Complete(used), receipt) => (
+				Outcome::Incomplete(used, error),
+				ExecutionReceipt { skipped, error_index: Some(prefix_len as u32), ..receipt },
+			),
+			r => r,
+		}
+	}
+
+	fn charge_fees(origin: impl Into<MultiLocation>, fees: MultiAssets) -> XcmResult {
+		let origin = origin.into();
+		for asset in fees.inner() {
+			Config::AssetTransactor::withdraw_asset(asset, &origin)?;
+		}
+		Ok(())
 	}
 }
 
@@ -164,10 +271,88 @@ impl From<ExecutorError> for frame_benchmarking::BenchmarkError {
 }
 
 impl<Config: config::Config> XcmExecutor<Config> {
-	pub fn new(origin: impl Into<MultiLocation>) -> Self {
+	/// Execute `message` from `origin`, which has been weighed to need at most `xcm_weight`,
+	/// returning the outcome along with the receipt of its execution. No more than
+	/// `weight_limit` is used in total.
+	fn execute_weighed(
+		origin: MultiLocation,
+		mut message: Xcm<Config::Call>,
+		xcm_weight: Weight,
+		weight_limit: Weight,
+		id: XcmHash,
+		weight_credit: Weight,
+	) -> (Outcome, ExecutionReceipt) {
+		if Config::CheckSuspension::is_suspended(&origin, &message, xcm_weight) {
+			log::debug!(
+				target: "xcm::execute_xcm_in_credit",
+				"Execution suspended! (origin: {:?}, message: {:?}, xcm_weight: {:?}, weight_credit: {:?})",
+				origin,
+				message,
+				xcm_weight,
+				weight_credit,
+			);
+			return (Outcome::Error(XcmError::Barrier), Default::default())
+		}
+
+		let mut properties = Properties { weight_credit, message_id: Some(id) };
+		if let Err(e) =
+			Config::Barrier::should_execute(&origin, &mut message, xcm_weight, &mut properties)
+		{
+			log::debug!(
+				target: "xcm::execute_xcm_in_credit",
+				"Barrier blocked execution! Error: {:?}. (origin: {:?}, message: {:?}, xcm_weight: {:?}, properties: {:?})",
+				e,
+				origin,
+				message,
+				xcm_weight,
+				properties,
+			);
+			return (Outcome::Error(XcmError::Barrier), Default::default())
+		}
+
+		let mut vm = Self::new(origin, id);
+
+		// Only the instructions of the message itself count as executed, not those of its error
+		// handler or appendix.
+		let mut top_level = None;
+		while !message.0.is_empty() {
+			let len = message.0.len() as u32;
+			let result = vm.execute(message);
+			log::trace!(target: "xcm::execute_xcm_in_credit", "result: {:?}", result);
+			if top_level.is_none() {
+				top_level = Some(match result {
+					Ok(()) => (len, None),
+					Err(ref error) => (error.index + 1, Some(error.index)),
+				});
+			}
+			message = if let Err(error) = result {
+				vm.total_surplus.saturating_accrue(error.weight);
+				vm.error = Some((error.index, error.xcm_error));
+				vm.take_error_handler().or_else(|| vm.take_appendix())
+			} else {
+				vm.drop_error_handler();
+				vm.take_appendix()
+			}
+		}
+
+		let outcome = vm.post_execute(xcm_weight, weight_limit);
+		let used = outcome.weight_used();
+		let (executed, error_index) = top_level.unwrap_or((0, None));
+		let receipt = ExecutionReceipt {
+			executed,
+			skipped: 0,
+			used,
+			surplus: xcm_weight.saturating_sub(used),
+			error_index,
+		};
+		(outcome, receipt)
+	}
+
+	pub fn new(origin: impl Into<MultiLocation>, message_id: XcmHash) -> Self {
 		let origin = origin.into();
 		Self {
 			holding: Assets::new(),
+			holding_provenance: BTreeMap::new(),
 			origin: Some(origin.clone()),
 			original_origin: origin,
 			trader: Config::Trader::new(),
@@ -178,6 +363,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			error_handler_weight: 0,
 			appendix: Xcm(vec![]),
 			appendix_weight: 0,
+			transact_status: Default::default(),
+			topic: None,
+			message_id,
 			_config: PhantomData,
 		}
 	}
@@ -211,7 +399,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 	/// Execute any final operations after having executed the XCM message.
 	/// This includes refunding surplus weight, trapping extra holding funds, and returning any errors during execution.
-	pub fn post_execute(mut self, xcm_weight: Weight) -> Outcome {
+	/// Trapping the funds may use whatever is left of `weight_limit`.
+	pub fn post_execute(mut self, xcm_weight: Weight, weight_limit: Weight) -> Outcome {
 		self.refund_surplus();
 		drop(self.trader);
 
@@ -219,8 +408,15 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 		if !self.holding.is_empty() {
 			log::trace!(target: "xcm::execute_xcm_in_credit", "Trapping assets in holding register: {:?} (original_origin: {:?})", self.holding, self.original_origin);
-			let trap_weight = Config::AssetTrap::drop_assets(&self.original_origin, self.holding);
-			weight_used.saturating_accrue(trap_weight);
+			let context = self.context();
+			let mut meter = WeightMeter::from_limit(weight_limit.saturating_sub(weight_used));
+			Config::AssetTrap::drop_assets(
+				&self.original_origin,
+				self.holding,
+				&context,
+				&mut meter,
+			);
+			weight_used.saturating_accrue(meter.consumed);
 		};
 
 		match self.error {
@@ -234,6 +430,16 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		}
 	}
 
+	/// The context of the message being executed, for handlers which need to correlate what they
+	/// do with it.
+	pub fn context(&self) -> XcmContext {
+		XcmContext {
+			origin: Some(self.original_origin.clone()),
+			message_id: self.message_id,
+			topic: self.topic,
+		}
+	}
+
 	/// Remove the registered error handler and return it. Do not refund its weight.
 	fn take_error_handler(&mut self) -> Xcm<Config::Call> {
 		let mut r = Xcm::<Config::Call>(vec![]);
@@ -276,7 +482,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
 				for asset in assets.drain().into_iter() {
 					Config::AssetTransactor::withdraw_asset(&asset, origin)?;
-					self.holding.subsume(asset);
+					self.subsume_with_provenance(asset, Provenance::Local);
 				}
 				Ok(())
 			},
@@ -289,7 +495,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 						Config::IsReserve::filter_asset_location(&asset, origin),
 						XcmError::UntrustedReserveLocation
 					);
-					self.holding.subsume(asset);
+					self.subsume_with_provenance(asset, Provenance::ReserveBacked);
 				}
 				Ok(())
 			},
@@ -307,11 +513,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				for asset in assets.inner() {
 					Config::AssetTransactor::beam_asset(asset, origin, &dest)?;
 				}
-				let ancestry = Config::LocationInverter::ancestry();
-				assets.reanchor(&dest, &ancestry).map_err(|()| XcmError::MultiLocationFull)?;
+				let context = Config::UniversalLocation::get();
+				assets.reanchor(&dest, &context).map_err(|()| XcmError::MultiLocationFull)?;
 				let mut message = vec![ReserveAssetDeposited(assets), ClearOrigin];
 				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send(dest, Xcm(message)).map(|_| ())
 			},
 			ReceiveTeleportedAsset(assets) => {
 				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
@@ -323,6 +529,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 						Config::IsTeleporter::filter_asset_location(asset, origin),
 						XcmError::UntrustedTeleportLocation
 					);
+					Self::ensure_teleport_limit(asset, origin)?;
 					// We should check that the asset can actually be teleported in (for this to be in error, there
 					// would need to be an accounting violation by one of the trusted chains, so it's unlikely, but we
 					// don't want to punish a possibly innocent chain/user).
@@ -330,7 +537,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				}
 				for asset in assets.drain().into_iter() {
 					Config::AssetTransactor::check_in(origin, &asset);
-					self.holding.subsume(asset);
+					self.subsume_with_provenance(asset, Provenance::Teleported);
 				}
 				Ok(())
 			},
@@ -345,10 +552,14 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let weight = message_call.get_dispatch_info().weight;
 				ensure!(weight <= require_weight_at_most, XcmError::MaxWeightInvalid);
 				let actual_weight = match message_call.dispatch(dispatch_origin) {
-					Ok(post_info) => post_info.actual_weight,
+					Ok(post_info) => {
+						self.transact_status = MaybeErrorCode::Success;
+						post_info.actual_weight
+					},
 					Err(error_and_info) => {
 						// Not much to do with the result as it is. It's up to the parachain to ensure that the
-						// message makes sense.
+						// message makes sense; the error is kept for `ReportTransactStatus`.
+						self.transact_status = error_and_info.error.encode().into();
 						error_and_info.post_info.actual_weight
 					},
 				}
@@ -385,11 +596,12 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				// destination if one was registered.
 				let response = Response::ExecutionResult(self.error);
 				let message = QueryResponse { query_id, response, max_weight };
-				Config::XcmSender::send_xcm(dest, Xcm(vec![message]))?;
+				self.send(dest, Xcm(vec![message]))?;
 				Ok(())
 			},
 			DepositAsset { assets, max_assets, beneficiary } => {
 				let deposited = self.holding.limited_saturating_take(assets, max_assets as usize);
+				self.prune_provenance();
 				for asset in deposited.into_assets_iter() {
 					Config::AssetTransactor::deposit_asset(&asset, &beneficiary)?;
 				}
@@ -397,6 +609,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			},
 			DepositReserveAsset { assets, max_assets, dest, xcm } => {
 				let deposited = self.holding.limited_saturating_take(assets, max_assets as usize);
+				if let Err(e) = self.ensure_not_reserve_backed(&deposited) {
+					self.holding.subsume_assets(deposited);
+					return Err(e)
+				}
+				self.prune_provenance();
 				for asset in deposited.assets_iter() {
 					Config::AssetTransactor::deposit_asset(&asset, &dest)?;
 				}
@@ -405,7 +622,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let assets = Self::reanchored(deposited, &dest, None);
 				let mut message = vec![ReserveAssetDeposited(assets), ClearOrigin];
 				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send(dest, Xcm(message)).map(|_| ())
 			},
 			InitiateReserveWithdraw { assets, reserve, xcm } => {
 				// Note that here we are able to place any assets which could not be reanchored
@@ -415,13 +632,30 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					&reserve,
 					Some(&mut self.holding),
 				);
+				// Assets sent back to their reserve no longer need their provenance.
+				self.prune_provenance();
 				let mut message = vec![WithdrawAsset(assets), ClearOrigin];
 				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(reserve, Xcm(message)).map_err(Into::into)
+				self.send(reserve, Xcm(message)).map(|_| ())
 			},
 			InitiateTeleport { assets, dest, xcm } => {
 				// We must do this first in order to resolve wildcards.
 				let assets = self.holding.saturating_take(assets);
+				if let Err(e) = self.ensure_not_reserve_backed(&assets).and_then(|()| {
+					assets.assets_iter().try_for_each(|a| {
+						Self::ensure_teleport_limit(&a, &dest)?;
+						// We should check that the asset can actually be teleported out (for this to
+						// be in error, there would need to be an accounting violation by ourselves,
+						// so it's unlikely, but we don't want to allow that kind of bug to leak into
+						// a trusted chain).
+						Config::AssetTransactor::can_check_out(&dest, &a)
+					})
+				}) {
+					// Nothing has been checked out yet, so the assets may safely go back to Holding.
+					self.holding.subsume_assets(assets);
+					return Err(e)
+				}
+				self.prune_provenance();
 				for asset in assets.assets_iter() {
 					Config::AssetTransactor::check_out(&dest, &asset);
 				}
@@ -430,7 +664,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let assets = Self::reanchored(assets, &dest, None);
 				let mut message = vec![ReceiveTeleportedAsset(assets), ClearOrigin];
 				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send(dest, Xcm(message)).map(|_| ())
 			},
 			QueryHolding { query_id, dest, assets, max_response_weight } => {
 				// Note that we pass `None` as `maybe_failed_bin` since no assets were ever removed
@@ -439,7 +673,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let max_weight = max_response_weight;
 				let response = Response::Assets(assets);
 				let instruction = QueryResponse { query_id, response, max_weight };
-				Config::XcmSender::send_xcm(dest, Xcm(vec![instruction])).map_err(Into::into)
+				self.send(dest, Xcm(vec![instruction])).map(|_| ())
 			},
 			BuyExecution { fees, weight_limit } => {
 				// There is no need to buy any weight is `weight_limit` is `Unlimited` since it
@@ -483,8 +717,10 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
 				let ok = Config::AssetClaims::claim_assets(origin, &ticket, &assets);
 				ensure!(ok, XcmError::UnknownClaim);
+				// We cannot know how trapped assets originally arrived, so assume the most
+				// restrictive provenance.
 				for asset in assets.drain().into_iter() {
-					self.holding.subsume(asset);
+					self.subsume_with_provenance(asset, Provenance::ReserveBacked);
 				}
 				Ok(())
 			},
@@ -501,6 +737,99 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				ensure!(&self.original_origin == origin, XcmError::BadOrigin);
 				Config::SubscriptionService::stop(origin)
 			},
+			UnpaidExecution { check_origin, .. } => {
+				ensure!(check_origin.is_none() || self.origin == check_origin, XcmError::BadOrigin);
+				Ok(())
+			},
+			BurnAsset(assets) => {
+				self.holding.saturating_take(assets.into());
+				self.prune_provenance();
+				Ok(())
+			},
+			ExpectOrigin(origin) => {
+				ensure!(self.origin == origin, XcmError::ExpectationFalse);
+				Ok(())
+			},
+			ExpectError(error) => {
+				ensure!(self.error == error, XcmError::ExpectationFalse);
+				Ok(())
+			},
+			QueryPallet { module_name, response_info } => {
+				let pallets = Config::PalletInstancesInfo::infos()
+					.into_iter()
+					.filter(|x| x.module_name.as_bytes() == &module_name[..])
+					.map(|x| PalletInfo {
+						index: x.index as u32,
+						name: x.name.as_bytes().into(),
+						module_name: x.module_name.as_bytes().into(),
+						major: x.crate_version.major as u32,
+						minor: x.crate_version.minor as u32,
+						patch: x.crate_version.patch as u32,
+					})
+					.collect::<Vec<_>>();
+				self.respond(Response::PalletsInfo(pallets), response_info)
+			},
+			ExpectPallet { index, name, module_name, crate_major, min_crate_minor } => {
+				let pallet = Config::PalletInstancesInfo::infos()
+					.into_iter()
+					.find(|x| x.index == index as usize)
+					.ok_or(XcmError::PalletNotFound)?;
+				ensure!(pallet.name.as_bytes() == &name[..], XcmError::NameMismatch);
+				ensure!(pallet.module_name.as_bytes() == &module_name[..], XcmError::NameMismatch);
+				let major = pallet.crate_version.major as u32;
+				ensure!(major == crate_major, XcmError::VersionIncompatible);
+				let minor = pallet.crate_version.minor as u32;
+				ensure!(minor >= min_crate_minor, XcmError::VersionIncompatible);
+				Ok(())
+			},
+			ReportTransactStatus(response_info) =>
+				self.respond(Response::DispatchResult(self.transact_status.clone()), response_info),
+			ClearTransactStatus => {
+				self.transact_status = Default::default();
+				Ok(())
+			},
+			ExportMessage { network, destination, xcm } => {
+				// Messages from the same origin to the same destination share a channel, so that
+				// they arrive in order.
+				let hash = (&self.origin, &destination).using_encoded(sp_io::hashing::blake2_128);
+				let channel = u32::decode(&mut hash.as_ref()).unwrap_or(0);
+				// The message is exported with the universal location of its origin, so that it
+				// may be prefixed with it on the remote side.
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				let universal_source = Config::UniversalLocation::get()
+					.within_global(origin)
+					.map_err(|()| XcmError::MultiLocationNotInvertible)?;
+				let (ticket, fee) = validate_export::<Config::MessageExporter>(
+					network,
+					channel,
+					universal_source,
+					destination,
+					xcm,
+				)?;
+				if !fee.is_none() {
+					self.holding.try_take(fee.into()).map_err(|_| XcmError::NotHoldingFees)?;
+				}
+				Config::MessageExporter::deliver(ticket)?;
+				Ok(())
+			},
+			SetTopic(topic) => {
+				self.topic = Some(topic);
+				Ok(())
+			},
+			UniversalOrigin(new_global) => {
+				let universal_location = Config::UniversalLocation::get();
+				// Nothing may act as the root of our own consensus system.
+				ensure!(universal_location.first() != Some(&new_global), XcmError::InvalidLocation);
+				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?.clone();
+				let origin_xform = (origin, new_global);
+				ensure!(
+					Config::UniversalAliases::contains(&origin_xform),
+					XcmError::InvalidLocation
+				);
+				let (_, new_global) = origin_xform;
+				self.origin = Some(Junctions::X1(new_global).relative_to(&universal_location));
+				Ok(())
+			},
 			ExchangeAsset { .. } => Err(XcmError::Unimplemented),
 			HrmpNewChannelOpenRequest { .. } => Err(XcmError::Unimplemented),
 			HrmpChannelAccepted { .. } => Err(XcmError::Unimplemented),
@@ -508,13 +837,69 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		}
 	}
 
+	/// Send `message` to `dest`, paying the price for its delivery out of the Holding Register.
+	///
+	/// NOTE: Any assets paid for delivery are simply burnt.
+	fn send(&mut self, dest: MultiLocation, message: Xcm<()>) -> Result<XcmHash, XcmError> {
+		let (ticket, price) = validate_send::<Config::XcmSender>(dest, message)?;
+		if !price.is_none() {
+			self.holding.try_take(price.into()).map_err(|_| XcmError::NotHoldingFees)?;
+		}
+		Config::XcmSender::deliver(ticket).map_err(Into::into)
+	}
+
+	/// Send `response` as a `QueryResponse` as described by `response_info`.
+	fn respond(&mut self, response: Response, response_info: QueryResponseInfo) -> XcmResult {
+		let QueryResponseInfo { destination, query_id, max_weight } = response_info;
+		let instruction = QueryResponse { query_id, response, max_weight };
+		self.send(destination, Xcm(vec![instruction])).map(|_| ())
+	}
+
+	/// Place `asset` into Holding, noting that it arrived by way of `provenance`.
+	fn subsume_with_provenance(&mut self, asset: MultiAsset, provenance: Provenance) {
+		self.holding_provenance
+			.entry(asset.id.clone())
+			.and_modify(|p| *p = p.combine(provenance))
+			.or_insert(provenance);
+		self.holding.subsume(asset);
+	}
+
+	/// Forget the provenance of any asset of which nothing remains in Holding.
+	fn prune_provenance(&mut self) {
+		let holding = &self.holding;
+		self.holding_provenance.retain(|id, _| holding.contains_id(id));
+	}
+
+	/// Ensure that none of `assets` are reserve-backed, so that they may be forwarded elsewhere
+	/// by way of a teleport or with the local chain acting as their reserve.
+	fn ensure_not_reserve_backed(&self, assets: &Assets) -> Result<(), XcmError> {
+		for asset in assets.assets_iter() {
+			ensure!(
+				self.holding_provenance.get(&asset.id) != Some(&Provenance::ReserveBacked),
+				XcmError::ProvenanceMismatch
+			);
+		}
+		Ok(())
+	}
+
+	/// Ensure that `asset` does not exceed the amount which may be teleported between the local
+	/// chain and `location` in a single message.
+	fn ensure_teleport_limit(asset: &MultiAsset, location: &MultiLocation) -> Result<(), XcmError> {
+		if let Fungible(amount) = asset.fun {
+			if let Some(limit) = Config::TeleportLimits::max_teleport_amount(&asset.id, location) {
+				ensure!(amount <= limit, XcmError::TeleportAmountExceeded);
+			}
+		}
+		Ok(())
+	}
+
 	/// NOTE: Any assets which were unable to be reanchored are introduced into `failed_bin`.
 	fn reanchored(
 		mut assets: Assets,
 		dest: &MultiLocation,
 		maybe_failed_bin: Option<&mut Assets>,
 	) -> MultiAssets {
-		assets.reanchor(dest, &Config::LocationInverter::ancestry(), maybe_failed_bin);
+		assets.reanchor(dest, &Config::UniversalLocation::get(), maybe_failed_bin);
 		assets.into_assets_iter().collect::<Vec<_>>().into()
 	}
 }
@@ -0,0 +1,77 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::traits::{ProcessMessage, ProcessMessageError};
+use frame_support::weights::Weight;
+use parity_scale_codec::DecodeLimit;
+use sp_std::marker::PhantomData;
+use xcm::{
+	latest::{Error as XcmError, ExecuteXcm, MultiLocation, Outcome, PreparedMessage, Xcm},
+	VersionedXcm, MAX_XCM_DECODE_DEPTH,
+};
+
+/// A message processor which decodes versioned XCMs and executes them with `XcmExecutor`.
+///
+/// A message is deemed to be successfully processed only if its execution was complete. Messages
+/// which would need more weight than the limit given are reported as overweight and not executed.
+pub struct ProcessXcmMessage<MessageOrigin, XcmExecutor, Call>(
+	PhantomData<(MessageOrigin, XcmExecutor, Call)>,
+);
+impl<MessageOrigin: Into<MultiLocation>, XcmExecutor: ExecuteXcm<Call>, Call>
+	ProcessXcmMessage<MessageOrigin, XcmExecutor, Call>
+{
+	/// Process the given `message` as [`ProcessMessage::process_message`] does, but return the
+	/// full outcome of its execution, including any error, rather than just whether it succeeded.
+	pub fn process_xcm_message(
+		message: &[u8],
+		origin: MessageOrigin,
+		weight_limit: Weight,
+	) -> Result<Outcome, ProcessMessageError> {
+		let id = sp_io::hashing::blake2_256(message);
+		let mut data = message;
+		let versioned =
+			VersionedXcm::<Call>::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data)
+				.map_err(|_| ProcessMessageError::BadFormat)?;
+		let message =
+			Xcm::<Call>::try_from(versioned).map_err(|()| ProcessMessageError::Unsupported)?;
+		let pre = match XcmExecutor::prepare(message) {
+			Ok(pre) => pre,
+			// A message which cannot be weighed is processed, unsuccessfully, at no cost.
+			Err(_) => return Ok(Outcome::Error(XcmError::WeightNotComputable)),
+		};
+		let required = pre.weight_of();
+		if required > weight_limit {
+			return Err(ProcessMessageError::Overweight(required))
+		}
+		Ok(XcmExecutor::execute(origin, pre, id, 0))
+	}
+}
+
+impl<MessageOrigin: Into<MultiLocation>, XcmExecutor: ExecuteXcm<Call>, Call> ProcessMessage
+	for ProcessXcmMessage<MessageOrigin, XcmExecutor, Call>
+{
+	type Origin = MessageOrigin;
+
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		weight_limit: Weight,
+	) -> Result<(bool, Weight), ProcessMessageError> {
+		let outcome = Self::process_xcm_message(message, origin, weight_limit)?;
+		let success = matches!(outcome, Outcome::Complete(_));
+		Ok((success, outcome.weight_used()))
+	}
+}
@@ -16,18 +16,28 @@
 
 use crate::Assets;
 use core::marker::PhantomData;
-use frame_support::{traits::Contains, weights::Weight};
-use xcm::latest::{MultiAssets, MultiLocation};
+use frame_support::{traits::Contains, weights::WeightMeter};
+use xcm::{
+	latest::{
+		Junction::GeneralIndex, Junctions::*, MultiAssets, MultiLocation, XcmContext, VERSION,
+	},
+	Version,
+};
 
 /// Define a handler for when some non-empty `Assets` value should be dropped.
 pub trait DropAssets {
-	/// Handler for receiving dropped assets. Returns the weight consumed by this operation.
-	fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight;
+	/// Handler for receiving dropped assets from `origin` in the course of handling the message
+	/// given by `context`. Any weight consumed by this operation is accrued on `meter`, whose limit
+	/// is what remains of the weight the message may use; nothing should be done which does not fit.
+	fn drop_assets(
+		origin: &MultiLocation,
+		assets: Assets,
+		context: &XcmContext,
+		meter: &mut WeightMeter,
+	);
 }
 impl DropAssets for () {
-	fn drop_assets(_origin: &MultiLocation, _assets: Assets) -> Weight {
-		0
-	}
+	fn drop_assets(_: &MultiLocation, _: Assets, _: &XcmContext, _: &mut WeightMeter) {}
 }
 
 /// Morph a given `DropAssets` implementation into one which can filter based on assets. This can
@@ -35,11 +45,14 @@ impl DropAssets for () {
 pub struct FilterAssets<D, A>(PhantomData<(D, A)>);
 
 impl<D: DropAssets, A: Contains<Assets>> DropAssets for FilterAssets<D, A> {
-	fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight {
+	fn drop_assets(
+		origin: &MultiLocation,
+		assets: Assets,
+		context: &XcmContext,
+		meter: &mut WeightMeter,
+	) {
 		if A::contains(&assets) {
-			D::drop_assets(origin, assets)
-		} else {
-			0
+			D::drop_assets(origin, assets, context, meter)
 		}
 	}
 }
@@ -50,11 +63,14 @@ impl<D: DropAssets, A: Contains<Assets>> DropAssets for FilterAssets<D, A> {
 pub struct FilterOrigin<D, O>(PhantomData<(D, O)>);
 
 impl<D: DropAssets, O: Contains<MultiLocation>> DropAssets for FilterOrigin<D, O> {
-	fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight {
+	fn drop_assets(
+		origin: &MultiLocation,
+		assets: Assets,
+		context: &XcmContext,
+		meter: &mut WeightMeter,
+	) {
 		if O::contains(origin) {
-			D::drop_assets(origin, assets)
-		} else {
-			0
+			D::drop_assets(origin, assets, context, meter)
 		}
 	}
 }
@@ -77,3 +93,44 @@ impl ClaimAssets for Tuple {
 		false
 	}
 }
+
+/// The XCM version in which the assets claimed with `ticket` are given, according to the
+/// canonical interpretation of claim tickets: `Here` stands for the latest version and
+/// `GeneralIndex(v)` for version `v`. Returns `None` for any other ticket.
+pub fn ticket_version(ticket: &MultiLocation) -> Option<Version> {
+	match (ticket.parents, &ticket.interior) {
+		(0, Here) => Some(VERSION),
+		(0, X1(GeneralIndex(version))) => (*version).try_into().ok(),
+		_ => None,
+	}
+}
+
+/// The canonical ticket with which to claim assets given in XCM `version`.
+pub fn version_ticket(version: Version) -> MultiLocation {
+	if version >= VERSION {
+		Here.into()
+	} else {
+		X1(GeneralIndex(version as u128)).into()
+	}
+}
+
+/// Morph a given `ClaimAssets` implementation into one which does not depend on the XCM version
+/// which a claimant's ticket was written against: should a claim with a canonical ticket fail,
+/// then it is attempted again with the ticket of every other version, latest first.
+pub struct AnyVersionTicket<C>(PhantomData<C>);
+
+impl<C: ClaimAssets> ClaimAssets for AnyVersionTicket<C> {
+	fn claim_assets(origin: &MultiLocation, ticket: &MultiLocation, what: &MultiAssets) -> bool {
+		if C::claim_assets(origin, ticket, what) {
+			return true
+		}
+		let given = match ticket_version(ticket) {
+			Some(version) => version.min(VERSION),
+			None => return false,
+		};
+		(0..=VERSION)
+			.rev()
+			.filter(|version| *version != given)
+			.any(|version| C::claim_assets(origin, &version_ticket(version), what))
+	}
+}
@@ -0,0 +1,143 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::prelude::*;
+
+/// Utility for sending an XCM message to a non-local consensus system, such as a remote network
+/// reached over a bridge.
+///
+/// This is the counterpart of `SendXcm` for the `ExportMessage` instruction. Unlike `SendXcm`, the
+/// destination is given as a `NetworkId` together with a location interior to that network, and
+/// the universal location of the sender of the message is provided so that the message may be
+/// prefixed with it on the remote side.
+///
+/// Can be amalgamated into tuples. The first item to not return `NotApplicable` is used, and any
+/// other error short-circuits.
+pub trait ExportXcm {
+	/// Intermediate value which connects the two phases of the export operation.
+	type Ticket;
+
+	/// Check whether the given `message` is deliverable to the given `destination` on `network`,
+	/// and if so determine the cost which will be paid by this chain to do so, returning a
+	/// `Ticket` token which can be used to enact delivery.
+	///
+	/// The `channel` identifies the message stream, so that messages from different origins need
+	/// not be ordered with respect to each other. The `universal_source` is the universal location
+	/// of the sender of the message.
+	///
+	/// The `destination` and `message` must be `Some` (or else an error will be returned) and they
+	/// may only be consumed if the `Err` is not `NotApplicable`.
+	///
+	/// If it is not a destination which can be reached with this type but possibly could by others,
+	/// then this *MUST* return `NotApplicable`. Any other error will cause the tuple implementation
+	/// to exit early without trying other type fields.
+	fn validate(
+		network: NetworkId,
+		channel: u32,
+		universal_source: &mut Option<InteriorMultiLocation>,
+		destination: &mut Option<InteriorMultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket>;
+
+	/// Actually carry out the delivery operation for a previously validated message export,
+	/// returning the hash of the exported message.
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl ExportXcm for Tuple {
+	for_tuples! { type Ticket = (#( Option<Tuple::Ticket> ),* ); }
+
+	fn validate(
+		network: NetworkId,
+		channel: u32,
+		universal_source: &mut Option<InteriorMultiLocation>,
+		destination: &mut Option<InteriorMultiLocation>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let mut maybe_cost: Option<MultiAssets> = None;
+		let one_ticket: Self::Ticket = (for_tuples! { #(
+			if maybe_cost.is_some() {
+				None
+			} else {
+				match Tuple::validate(network.clone(), channel, universal_source, destination, message) {
+					Err(SendError::NotApplicable) => None,
+					Err(e) => { return Err(e) },
+					Ok((v, c)) => {
+						maybe_cost = Some(c);
+						Some(v)
+					},
+				}
+			}
+		),* });
+		if let Some(cost) = maybe_cost {
+			Ok((one_ticket, cost))
+		} else {
+			Err(SendError::NotApplicable)
+		}
+	}
+
+	fn deliver(one_ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		for_tuples!( #(
+			if let Some(validated) = one_ticket.Tuple {
+				return Tuple::deliver(validated);
+			}
+		)* );
+		Err(SendError::Unroutable)
+	}
+}
+
+/// Convenience function for using a `ExportXcm` implementation. Just interprets the `network`,
+/// `channel`, `universal_source`, `destination` and `message` and calls `validate` on the
+/// exporter.
+pub fn validate_export<T: ExportXcm>(
+	network: NetworkId,
+	channel: u32,
+	universal_source: InteriorMultiLocation,
+	destination: InteriorMultiLocation,
+	message: Xcm<()>,
+) -> SendResult<T::Ticket> {
+	T::validate(
+		network,
+		channel,
+		&mut Some(universal_source),
+		&mut Some(destination),
+		&mut Some(message),
+	)
+}
+
+/// Convenience function for using a `ExportXcm` implementation. Validates and then delivers the
+/// message in one go, returning the hash of the exported message and the cost of delivery.
+///
+/// Note that this function does not pay the cost of delivery, so it should only be used in places
+/// where the cost is paid for by other means.
+pub fn export_xcm<T: ExportXcm>(
+	network: NetworkId,
+	channel: u32,
+	universal_source: InteriorMultiLocation,
+	destination: InteriorMultiLocation,
+	message: Xcm<()>,
+) -> Result<(XcmHash, MultiAssets), SendError> {
+	let (ticket, price) = T::validate(
+		network,
+		channel,
+		&mut Some(universal_source),
+		&mut Some(destination),
+		&mut Some(message),
+	)?;
+	let hash = T::deliver(ticket)?;
+	Ok((hash, price))
+}
@@ -16,20 +16,31 @@
 
 //! Various traits used in configuring the executor.
 
+mod asset_exchange;
+pub use asset_exchange::AssetExchange;
 mod conversion;
-pub use conversion::{Convert, ConvertOrigin, Decoded, Encoded, Identity, InvertLocation, JustTry};
+pub use conversion::{Convert, ConvertOrigin, Decoded, Encoded, Identity, JustTry};
 mod drop_assets;
-pub use drop_assets::{ClaimAssets, DropAssets};
+pub use drop_assets::{
+	ticket_version, version_ticket, AnyVersionTicket, ClaimAssets, DropAssets, FilterAssets,
+	FilterOrigin,
+};
+mod export;
+pub use export::{export_xcm, validate_export, ExportXcm};
 mod filter_asset_location;
 pub use filter_asset_location::FilterAssetLocation;
-mod matches_fungible;
-pub use matches_fungible::MatchesFungible;
-mod matches_fungibles;
-pub use matches_fungibles::{Error, MatchesFungibles};
 mod on_response;
-pub use on_response::{OnResponse, VersionChangeNotifier};
+pub use on_response::{OnResponse, QueryHandler, QueryResponseStatus, VersionChangeNotifier};
+mod process_message;
+pub use process_message::{MessageId, ProcessMessage, ProcessMessageError};
 mod should_execute;
-pub use should_execute::ShouldExecute;
+pub use should_execute::{CheckSuspension, Properties, ShouldExecute};
+mod teleport_limit;
+pub use teleport_limit::TeleportLimit;
+mod token_matching;
+pub use token_matching::{
+	Error, MatchesFungible, MatchesFungibles, MatchesNonFungible, MatchesNonFungibles,
+};
 mod transact_asset;
 pub use transact_asset::TransactAsset;
 mod weight;
@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use frame_support::weights::Weight;
-use xcm::latest::{Error as XcmError, MultiLocation, QueryId, Response, Result as XcmResult};
+use xcm::latest::{Error as XcmError, MultiLocation, QueryId, Response, Result as XcmResult, Xcm};
 
 /// Define what needs to be done upon receiving a query response.
 pub trait OnResponse {
@@ -74,3 +74,42 @@ impl VersionChangeNotifier for () {
 		false
 	}
 }
+
+/// The status of a query, as returned by `QueryHandler::take_response`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum QueryResponseStatus<BlockNumber> {
+	/// The response has arrived and the query has been removed.
+	Ready { response: Response, at: BlockNumber },
+	/// The response has not yet arrived; the query is still pending.
+	Pending { timeout: BlockNumber },
+	/// No response arrived before the query's timeout, which was noted at block `at`. The query
+	/// has been removed.
+	TimedOut { at: BlockNumber },
+	/// The response could not be converted into the latest XCM version.
+	UnexpectedVersion,
+	/// There is no query with the given ID.
+	NotFound,
+}
+
+/// Provides a way to register queries and to take their responses once they arrive.
+pub trait QueryHandler {
+	type BlockNumber;
+
+	/// Register a new query which is expected to be answered by `responder` before `timeout`.
+	fn new_query(responder: impl Into<MultiLocation>, timeout: Self::BlockNumber) -> QueryId;
+
+	/// Alter `message` so that it reports back its outcome to this chain as the response of a
+	/// new query from `responder`, whose ID is returned.
+	///
+	/// The report is placed at the beginning of the message as an appendix, so it will be sent
+	/// regardless of whether the rest of the message succeeds.
+	fn report_outcome(
+		message: &mut Xcm<()>,
+		responder: impl Into<MultiLocation>,
+		timeout: Self::BlockNumber,
+	) -> Result<QueryId, XcmError>;
+
+	/// Take the response of the query `query_id`, if it is ready or has timed out. Taking a
+	/// response removes the query.
+	fn take_response(query_id: QueryId) -> QueryResponseStatus<Self::BlockNumber>;
+}
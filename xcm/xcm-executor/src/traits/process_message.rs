@@ -0,0 +1,50 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::weights::Weight;
+
+/// The identifier of a message being processed; by convention the blake2-256 hash of its encoding.
+pub type MessageId = [u8; 32];
+
+/// Errors which may be returned when processing a message.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ProcessMessageError {
+	/// The message data could not be decoded.
+	BadFormat,
+	/// The message data is in a format or version which is not supported.
+	Unsupported,
+	/// The message needs more weight than the limit given. The required weight is included.
+	Overweight(Weight),
+}
+
+/// A type which can process raw messages arriving from a queue (e.g. UMP, DMP or HRMP).
+///
+/// This allows message sinks to share the decoding, execution and weight accounting of messages
+/// rather than each doing so in an ad-hoc manner.
+pub trait ProcessMessage {
+	/// The origin of the messages which this type can process.
+	type Origin;
+
+	/// Process the given `message` from `origin`, using no more than `weight_limit`.
+	///
+	/// Returns whether the message was processed successfully along with the weight which was used
+	/// in processing it. An `Err` means that the message was not processed at all.
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		weight_limit: Weight,
+	) -> Result<(bool, Weight), ProcessMessageError>;
+}
@@ -16,7 +16,20 @@
 
 use frame_support::weights::Weight;
 use sp_std::result::Result;
-use xcm::latest::{MultiLocation, Xcm};
+use xcm::latest::{MultiLocation, Xcm, XcmHash};
+
+/// Properties of an XCM message and its imminent execution, which barriers may inspect and
+/// alter.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Properties {
+	/// The amount of weight that the system has determined this message may utilize in its
+	/// execution. Typically non-zero only because of prior fee payment, but could in principle be
+	/// due to other factors. Barriers which allow execution because of it should consume it.
+	pub weight_credit: Weight,
+	/// The identity of the message, if one is known. Useful to correlate the decisions of
+	/// barriers with the message in logs.
+	pub message_id: Option<XcmHash>,
+}
 
 /// Trait to determine whether the execution engine should actually execute a given XCM.
 ///
@@ -28,14 +41,13 @@ pub trait ShouldExecute {
 	/// - `origin`: The origin (sender) of the message.
 	/// - `message`: The message itself.
 	/// - `max_weight`: The (possibly over-) estimation of the weight of execution of the message.
-	/// - `weight_credit`: The pre-established amount of weight that the system has determined this
-	///   message may utilize in its execution. Typically non-zero only because of prior fee
-	///   payment, but could in principle be due to other factors.
+	/// - `properties`: Various pre-established properties of the message which may be mutated by
+	///   this API.
 	fn should_execute<Call>(
 		origin: &MultiLocation,
 		message: &mut Xcm<Call>,
 		max_weight: Weight,
-		weight_credit: &mut Weight,
+		properties: &mut Properties,
 	) -> Result<(), ()>;
 }
 
@@ -45,22 +57,48 @@ impl ShouldExecute for Tuple {
 		origin: &MultiLocation,
 		message: &mut Xcm<Call>,
 		max_weight: Weight,
-		weight_credit: &mut Weight,
+		properties: &mut Properties,
 	) -> Result<(), ()> {
 		for_tuples!( #(
-			match Tuple::should_execute(origin, message, max_weight, weight_credit) {
+			match Tuple::should_execute(origin, message, max_weight, properties) {
 				Ok(()) => return Ok(()),
 				_ => (),
 			}
 		)* );
 		log::trace!(
 			target: "xcm::should_execute",
-			"did not pass barrier: origin: {:?}, message: {:?}, max_weight: {:?}, weight_credit: {:?}",
+			"did not pass barrier: origin: {:?}, message: {:?}, max_weight: {:?}, properties: {:?}",
 			origin,
 			message,
 			max_weight,
-			weight_credit,
+			properties,
 		);
 		Err(())
 	}
 }
+
+/// Trait to determine whether the execution engine is suspended from executing a given XCM.
+///
+/// This is checked before the `Barrier`, so that a suspension cannot be lifted by any barrier
+/// granting execution (e.g. by way of fee payment).
+///
+/// Can be amalgamated into a tuple to have multiple trials. If any of the tuple elements returns
+/// `true`, execution is suspended. Else, `false` is returned if all elements allow execution.
+pub trait CheckSuspension {
+	/// Returns `true` if the execution of the given `message` must not happen at present.
+	///
+	/// - `origin`: The origin (sender) of the message.
+	/// - `message`: The message itself.
+	/// - `max_weight`: The (possibly over-) estimation of the weight of execution of the message.
+	fn is_suspended<Call>(origin: &MultiLocation, message: &Xcm<Call>, max_weight: Weight) -> bool;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl CheckSuspension for Tuple {
+	fn is_suspended<Call>(origin: &MultiLocation, message: &Xcm<Call>, max_weight: Weight) -> bool {
+		for_tuples!( #(
+			if Tuple::is_suspended(origin, message, max_weight) { return true }
+		)* );
+		false
+	}
+}
@@ -0,0 +1,41 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::{AssetId, MultiLocation};
+
+/// Bounds the amount of a fungible asset which may be teleported to or from a location within a
+/// single message.
+///
+/// This complements `Config::IsTeleporter`, which only decides whether a location is trusted to
+/// teleport a given asset at all.
+///
+/// Can be amalgamated into tuples. The first item to return `Some` short-circuits, else `None` (i.e.
+/// no limit) is returned.
+pub trait TeleportLimit {
+	/// Return the maximum amount of the asset `id` which may be teleported between the local chain
+	/// and `location` in a single message, or `None` if there is no limit.
+	fn max_teleport_amount(id: &AssetId, location: &MultiLocation) -> Option<u128>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl TeleportLimit for Tuple {
+	fn max_teleport_amount(id: &AssetId, location: &MultiLocation) -> Option<u128> {
+		for_tuples!( #(
+			if let Some(limit) = Tuple::max_teleport_amount(id, location) { return Some(limit) }
+		)* );
+		None
+	}
+}
@@ -48,8 +48,18 @@ pub trait TransactAsset {
 	/// value for `_what` which can cause side-effects for more than one of the type-items.
 	fn check_in(_origin: &MultiLocation, _what: &MultiAsset) {}
 
+	/// Ensure that `check_out` will result in `Ok`.
+	///
+	/// When composed as a tuple, all type-items are called and at least one must result in `Ok`.
+	fn can_check_out(_dest: &MultiLocation, _what: &MultiAsset) -> XcmResult {
+		Err(XcmError::Unimplemented)
+	}
+
 	/// An asset has been teleported out to the given destination. This should do whatever housekeeping is needed.
 	///
+	/// NOTE: This will make only a best-effort at bookkeeping. The caller should ensure that `can_check_out` has
+	/// returned with `Ok` in order to guarantee that this operation proceeds properly.
+	///
 	/// Implementation note: In general this will do one of two things: On chains where the asset is native,
 	/// it will increase the assets in a special "teleported" account so that a) total-issuance is preserved; and
 	/// b) to ensure that no more assets can be teleported in than were teleported out overall (this should not
@@ -130,6 +140,22 @@ impl TransactAsset for Tuple {
 		)* );
 	}
 
+	fn can_check_out(dest: &MultiLocation, what: &MultiAsset) -> XcmResult {
+		for_tuples!( #(
+			match Tuple::can_check_out(dest, what) {
+				Err(XcmError::AssetNotFound) | Err(XcmError::Unimplemented) => (),
+				r => return r,
+			}
+		)* );
+		log::trace!(
+			target: "xcm::TransactAsset::can_check_out",
+			"asset not found: what: {:?}, dest: {:?}",
+			what,
+			dest,
+		);
+		Err(XcmError::AssetNotFound)
+	}
+
 	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
 		for_tuples!( #(
 			Tuple::check_out(dest, what);
@@ -0,0 +1,298 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reusable conformance tests for implementations of the XCM executor's `TransactAsset` and
+//! `WeightTrader` traits.
+//!
+//! Each macro expands to a set of `#[test]` functions in the module in which it is invoked, so
+//! it should be invoked at most once per module:
+//!
+//! ```ignore
+//! mod local_asset_transactor {
+//! 	use super::*;
+//! 	xcm_executor_test_suite::impl_transact_asset_tests! {
+//! 		transactor: LocalAssetTransactor,
+//! 		new_ext: new_test_ext(vec![(ALICE, 1_000)]),
+//! 		asset_id: Here,
+//! 		amount: 100,
+//! 		funded: AccountId32 { network: Any, id: ALICE.into() },
+//! 		funded_balance: 1_000,
+//! 		unfunded: AccountId32 { network: Any, id: BOB.into() },
+//! 		teleport_location: Parachain(1000),
+//! 		balance_of: |who: &MultiLocation| balance_of(who),
+//! 	}
+//! }
+//! ```
+//!
+//! Expressions passed to the macros are resolved in the invoking module.
+
+pub use xcm;
+pub use xcm_executor;
+
+/// Sum the fungible amount of the asset `id` held in `assets`.
+pub fn fungible_amount(assets: &xcm_executor::Assets, id: &xcm::latest::AssetId) -> u128 {
+	assets.fungible.get(id).copied().unwrap_or(0)
+}
+
+/// Generate conformance tests for a fungible `TransactAsset` implementation.
+///
+/// Arguments:
+/// - `transactor`: the type implementing `TransactAsset`.
+/// - `new_ext`: an expression evaluating to fresh externalities, on which `execute_with` is
+///   called once per test.
+/// - `asset_id`: the `AssetId` (or anything convertible into it) handled by the transactor.
+/// - `amount`: the amount moved by the tests. It must be at least any existential deposit and
+///   at most half of `funded_balance`.
+/// - `funded`: a location holding exactly `funded_balance` of the asset in `new_ext`.
+/// - `unfunded`: a location holding none of the asset in `new_ext`.
+/// - `teleport_location`: a location to and from which the asset may be teleported.
+/// - `balance_of`: a function returning the balance of the asset held by a location.
+#[macro_export]
+macro_rules! impl_transact_asset_tests {
+	(
+		transactor: $transactor:ty,
+		new_ext: $new_ext:expr,
+		asset_id: $asset_id:expr,
+		amount: $amount:expr,
+		funded: $funded:expr,
+		funded_balance: $funded_balance:expr,
+		unfunded: $unfunded:expr,
+		teleport_location: $teleport_location:expr,
+		balance_of: $balance_of:expr $(,)?
+	) => {
+		fn transact_asset_test_asset(amount: u128) -> $crate::xcm::latest::MultiAsset {
+			$crate::xcm::latest::MultiAsset {
+				id: $asset_id.into(),
+				fun: $crate::xcm::latest::Fungibility::Fungible(amount),
+			}
+		}
+
+		fn transact_asset_test_balance(who: &$crate::xcm::latest::MultiLocation) -> u128 {
+			let balance_of = $balance_of;
+			balance_of(who)
+		}
+
+		#[test]
+		fn transact_asset_deposit_credits_beneficiary() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let unfunded: $crate::xcm::latest::MultiLocation = $unfunded.into();
+				let before = transact_asset_test_balance(&unfunded);
+				assert!(<$transactor as TransactAsset>::deposit_asset(
+					&transact_asset_test_asset($amount),
+					&unfunded,
+				)
+				.is_ok());
+				assert_eq!(transact_asset_test_balance(&unfunded), before + $amount);
+			});
+		}
+
+		#[test]
+		fn transact_asset_withdraw_debits_owner() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let funded: $crate::xcm::latest::MultiLocation = $funded.into();
+				let withdrawn = <$transactor as TransactAsset>::withdraw_asset(
+					&transact_asset_test_asset($amount),
+					&funded,
+				)
+				.expect("funded location holds enough of the asset; qed");
+				assert_eq!(withdrawn, transact_asset_test_asset($amount).into());
+				assert_eq!(transact_asset_test_balance(&funded), $funded_balance - $amount);
+			});
+		}
+
+		#[test]
+		fn transact_asset_withdraw_beyond_balance_fails() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let funded: $crate::xcm::latest::MultiLocation = $funded.into();
+				assert!(<$transactor as TransactAsset>::withdraw_asset(
+					&transact_asset_test_asset($funded_balance + 1),
+					&funded,
+				)
+				.is_err());
+				assert_eq!(transact_asset_test_balance(&funded), $funded_balance);
+			});
+		}
+
+		#[test]
+		fn transact_asset_withdraw_then_deposit_round_trips() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let funded: $crate::xcm::latest::MultiLocation = $funded.into();
+				let withdrawn = <$transactor as TransactAsset>::withdraw_asset(
+					&transact_asset_test_asset($amount),
+					&funded,
+				)
+				.expect("funded location holds enough of the asset; qed");
+				for asset in withdrawn.fungible_assets_iter() {
+					assert!(<$transactor as TransactAsset>::deposit_asset(&asset, &funded).is_ok());
+				}
+				assert_eq!(transact_asset_test_balance(&funded), $funded_balance);
+			});
+		}
+
+		#[test]
+		fn transact_asset_transfer_moves_exact_amount() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let funded: $crate::xcm::latest::MultiLocation = $funded.into();
+				let unfunded: $crate::xcm::latest::MultiLocation = $unfunded.into();
+				let before = transact_asset_test_balance(&unfunded);
+				let transferred = <$transactor as TransactAsset>::transfer_asset(
+					&transact_asset_test_asset($amount),
+					&funded,
+					&unfunded,
+				)
+				.expect("funded location holds enough of the asset; qed");
+				assert_eq!(transferred, transact_asset_test_asset($amount).into());
+				assert_eq!(transact_asset_test_balance(&funded), $funded_balance - $amount);
+				assert_eq!(transact_asset_test_balance(&unfunded), before + $amount);
+			});
+		}
+
+		#[test]
+		fn transact_asset_unknown_asset_is_rejected() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let funded: $crate::xcm::latest::MultiLocation = $funded.into();
+				let unknown = $crate::xcm::latest::MultiAsset {
+					id: $crate::xcm::latest::AssetId::Abstract(b"xcm-executor-test-suite".to_vec()),
+					fun: $crate::xcm::latest::Fungibility::Fungible($amount),
+				};
+				assert!(<$transactor as TransactAsset>::deposit_asset(&unknown, &funded).is_err());
+				assert!(<$transactor as TransactAsset>::withdraw_asset(&unknown, &funded).is_err());
+				assert_eq!(transact_asset_test_balance(&funded), $funded_balance);
+			});
+		}
+
+		#[test]
+		fn transact_asset_checked_out_asset_can_be_checked_back_in() {
+			use $crate::xcm_executor::traits::TransactAsset;
+			$new_ext.execute_with(|| {
+				let location: $crate::xcm::latest::MultiLocation = $teleport_location.into();
+				let asset = transact_asset_test_asset($amount);
+				<$transactor as TransactAsset>::check_out(&location, &asset);
+				assert!(<$transactor as TransactAsset>::can_check_in(&location, &asset).is_ok());
+				<$transactor as TransactAsset>::check_in(&location, &asset);
+			});
+		}
+	};
+}
+
+/// Generate conformance tests for a `WeightTrader` implementation.
+///
+/// Arguments:
+/// - `trader`: the type implementing `WeightTrader`.
+/// - `new_ext`: an expression evaluating to fresh externalities, on which `execute_with` is
+///   called once per test.
+/// - `payment`: a fungible `MultiAsset` sufficient to buy `weight`.
+/// - `weight`: the weight bought by the tests.
+#[macro_export]
+macro_rules! impl_weight_trader_tests {
+	(
+		trader: $trader:ty,
+		new_ext: $new_ext:expr,
+		payment: $payment:expr,
+		weight: $weight:expr $(,)?
+	) => {
+		fn weight_trader_test_weight() -> $crate::xcm::latest::Weight {
+			$weight
+		}
+
+		fn weight_trader_test_payment() -> $crate::xcm::latest::MultiAsset {
+			$payment.into()
+		}
+
+		fn weight_trader_test_payment_amount() -> ($crate::xcm::latest::AssetId, u128) {
+			match weight_trader_test_payment() {
+				$crate::xcm::latest::MultiAsset {
+					id,
+					fun: $crate::xcm::latest::Fungibility::Fungible(amount),
+				} => (id, amount),
+				_ => panic!("`payment` must be fungible"),
+			}
+		}
+
+		#[test]
+		fn weight_trader_change_never_exceeds_payment() {
+			use $crate::xcm_executor::traits::WeightTrader;
+			$new_ext.execute_with(|| {
+				let (id, amount) = weight_trader_test_payment_amount();
+				let mut trader = <$trader as WeightTrader>::new();
+				let unused = trader
+					.buy_weight(weight_trader_test_weight(), weight_trader_test_payment().into())
+					.expect("`payment` is sufficient to buy `weight`; qed");
+				assert!($crate::fungible_amount(&unused, &id) <= amount);
+				assert!(unused
+					.fungible
+					.keys()
+					.chain(unused.non_fungible.iter().map(|(unused_id, _)| unused_id))
+					.all(|unused_id| *unused_id == id));
+			});
+		}
+
+		#[test]
+		fn weight_trader_refund_never_exceeds_payment() {
+			use $crate::xcm_executor::traits::WeightTrader;
+			$new_ext.execute_with(|| {
+				let (id, amount) = weight_trader_test_payment_amount();
+				let mut trader = <$trader as WeightTrader>::new();
+				let unused = trader
+					.buy_weight(weight_trader_test_weight(), weight_trader_test_payment().into())
+					.expect("`payment` is sufficient to buy `weight`; qed");
+				let paid = amount - $crate::fungible_amount(&unused, &id);
+				let refunded = trader
+					.refund_weight(weight_trader_test_weight().saturating_mul(2))
+					.map_or(0, |asset| $crate::fungible_amount(&asset.into(), &id));
+				assert!(refunded <= paid);
+				assert_eq!(trader.refund_weight(weight_trader_test_weight()), None);
+			});
+		}
+
+		#[test]
+		fn weight_trader_partial_refunds_never_exceed_payment() {
+			use $crate::xcm_executor::traits::WeightTrader;
+			$new_ext.execute_with(|| {
+				let (id, amount) = weight_trader_test_payment_amount();
+				let mut trader = <$trader as WeightTrader>::new();
+				let unused = trader
+					.buy_weight(weight_trader_test_weight(), weight_trader_test_payment().into())
+					.expect("`payment` is sufficient to buy `weight`; qed");
+				let paid = amount - $crate::fungible_amount(&unused, &id);
+				// Refunding in uneven parts must not accumulate rounding in the payer's favour.
+				let weight = weight_trader_test_weight();
+				let third = weight / 3;
+				let refunded: u128 = [third, third, weight - 2 * third]
+					.into_iter()
+					.filter_map(|part| trader.refund_weight(part))
+					.map(|asset| $crate::fungible_amount(&asset.into(), &id))
+					.sum();
+				assert!(refunded <= paid);
+			});
+		}
+
+		#[test]
+		fn weight_trader_refund_without_purchase_is_empty() {
+			use $crate::xcm_executor::traits::WeightTrader;
+			$new_ext.execute_with(|| {
+				let mut trader = <$trader as WeightTrader>::new();
+				assert_eq!(trader.refund_weight(weight_trader_test_weight()), None);
+			});
+		}
+	};
+}
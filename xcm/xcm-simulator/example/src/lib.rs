@@ -57,9 +57,59 @@ decl_test_network! {
 			(1, ParaA),
 			(2, ParaB),
 		],
+		bridge_key = b"mock",
+		bridged_relay_chains = vec![
+			(b"bridged", bridged::BridgedRelay),
+		],
 	}
 }
 
+/// A second network, bridged to `MockNet`, consisting of just a relay chain.
+///
+/// Its relay chain is built from the same source as `Relay`, but as a distinct runtime whose
+/// messages are routed by `BridgedNet`, so that it may reply over the bridge.
+pub mod bridged {
+	use super::*;
+
+	#[path = "../relay_chain.rs"]
+	pub mod relay_chain;
+
+	decl_test_relay_chain! {
+		pub struct BridgedRelay {
+			Runtime = relay_chain::Runtime,
+			XcmConfig = relay_chain::XcmConfig,
+			new_ext = bridged_relay_ext(),
+		}
+	}
+
+	decl_test_network! {
+		pub struct BridgedNet {
+			relay_chain = BridgedRelay,
+			parachains = vec![],
+			bridge_key = b"bridged",
+			bridged_relay_chains = vec![
+				(b"mock", super::Relay),
+			],
+		}
+	}
+
+	pub fn bridged_relay_ext() -> sp_io::TestExternalities {
+		use relay_chain::{Runtime, System};
+
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, INITIAL_BALANCE)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	pub type BridgedRelayPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
+}
+
 pub fn para_account_id(id: u32) -> relay_chain::AccountId {
 	ParaId::from(id).into_account()
 }
@@ -315,4 +365,65 @@ mod tests {
 			);
 		});
 	}
+
+	#[test]
+	fn bridged_messages_are_traced() {
+		MockNet::reset();
+		bridged::BridgedNet::reset();
+
+		let bridged_relay = MultiLocation::new(1, X1(GeneralKey(b"bridged".to_vec())));
+		let message = Xcm(vec![ClearOrigin]);
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, bridged_relay.clone(), message.clone()));
+		});
+
+		let trace = xcm_simulator::take_message_trace();
+		assert_eq!(trace.len(), 1);
+		assert_eq!(trace[0].network, "MockNet");
+		assert_eq!(trace[0].sender, Here.into());
+		assert_eq!(trace[0].channel, xcm_simulator::MessageKind::Bridge);
+		assert_eq!(trace[0].destination, bridged_relay);
+		assert_eq!(trace[0].encoded, xcm::VersionedXcm::<()>::from(message).encode());
+		assert!(matches!(trace[0].outcome, Outcome::Complete(_)));
+	}
+
+	#[test]
+	fn bridged_relay_chains_can_reply() {
+		MockNet::reset();
+		bridged::BridgedNet::reset();
+
+		// The bridged relay chain answers a query about its pallets back over the bridge.
+		let bridged_relay = MultiLocation::new(1, X1(GeneralKey(b"bridged".to_vec())));
+		let mock_relay = MultiLocation::new(1, X1(GeneralKey(b"mock".to_vec())));
+		let response_info =
+			QueryResponseInfo { destination: mock_relay.clone(), query_id: 1, max_weight: 0 };
+		let message =
+			Xcm(vec![QueryPallet { module_name: b"pallet_balances".to_vec(), response_info }]);
+		Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, bridged_relay.clone(), message));
+		});
+
+		let trace = xcm_simulator::take_message_trace();
+		assert_eq!(trace.len(), 2);
+		let query = trace.iter().find(|hop| hop.network == "MockNet").unwrap();
+		assert_eq!(query.destination, bridged_relay);
+		assert!(matches!(query.outcome, Outcome::Complete(_)));
+		let reply = trace.iter().find(|hop| hop.network == "BridgedNet").unwrap();
+		assert_eq!(reply.channel, xcm_simulator::MessageKind::Bridge);
+		assert_eq!(reply.destination, mock_relay);
+		assert!(matches!(reply.outcome, Outcome::Complete(_)));
+
+		// Messages may equally be sent from the bridged network to begin with.
+		bridged::BridgedRelay::execute_with(|| {
+			assert_ok!(bridged::BridgedRelayPalletXcm::send_xcm(
+				Here,
+				mock_relay.clone(),
+				Xcm(vec![ClearOrigin]),
+			));
+		});
+		let trace = xcm_simulator::take_message_trace();
+		assert_eq!(trace.len(), 1);
+		assert_eq!(trace[0].network, "BridgedNet");
+		assert!(matches!(trace[0].outcome, Outcome::Complete(_)));
+	}
 }
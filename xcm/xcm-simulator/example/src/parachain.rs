@@ -16,33 +16,32 @@
 
 //! Parachain runtime mock.
 
-use codec::{Decode, Encode};
+use codec::Decode;
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{Everything, Nothing},
 	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
 use sp_core::H256;
-use sp_runtime::{
-	testing::Header,
-	traits::{Hash, IdentityLookup},
-	AccountId32,
-};
+use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
 use sp_std::{convert::TryFrom, prelude::*};
 
 use pallet_xcm::XcmPassthrough;
 use polkadot_core_primitives::BlockNumber as RelayBlockNumber;
 use polkadot_parachain::primitives::{
-	DmpMessageHandler, Id as ParaId, Sibling, XcmpMessageFormat, XcmpMessageHandler,
+	DmpMessageHandler, Id as ParaId, Sibling, XcmpMessageHandler,
 };
 use xcm::{latest::prelude::*, VersionedXcm};
 use xcm_builder::{
-	AccountId32Aliases, AllowUnpaidExecutionFrom, CurrencyAdapter as XcmCurrencyAdapter,
-	EnsureXcmOrigin, FixedRateOfFungible, FixedWeightBounds, IsConcrete, LocationInverter,
-	NativeAsset, ParentIsPreset, SiblingParachainConvertsVia, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation,
+	AccountId32Aliases, AllowUnpaidExecutionFrom, CurrencyAdapter as XcmCurrencyAdapter, DmpSink,
+	EnsureXcmOrigin, FixedRateOfFungible, FixedWeightBounds, IsConcrete, NativeAsset,
+	OnMessageProcessed, ParentIsPreset, SiblingParachainConvertsVia, SignedAccountId32AsNative,
+	SignedToAccountId32, SovereignSignedViaLocation, XcmpSink,
+};
+use xcm_executor::{
+	traits::{MessageId, ProcessMessageError},
+	Config, ProcessXcmMessage, XcmExecutor,
 };
-use xcm_executor::{Config, XcmExecutor};
 
 pub type AccountId = AccountId32;
 pub type Balance = u128;
@@ -104,7 +103,8 @@ parameter_types! {
 parameter_types! {
 	pub const KsmLocation: MultiLocation = MultiLocation::parent();
 	pub const RelayNetwork: NetworkId = NetworkId::Kusama;
-	pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
+	pub UniversalLocation: InteriorMultiLocation =
+		X2(GlobalConsensus(RelayNetwork::get()), Parachain(MsgQueue::parachain_id().into()));
 }
 
 pub type LocationToAccountId = (
@@ -139,7 +139,9 @@ impl Config for XcmConfig {
 	type OriginConverter = XcmOriginToCallOrigin;
 	type IsReserve = NativeAsset;
 	type IsTeleporter = ();
-	type LocationInverter = LocationInverter<Ancestry>;
+	type TeleportLimits = ();
+	type UniversalLocation = UniversalLocation;
+	type CheckSuspension = ();
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type Trader = FixedRateOfFungible<KsmPerSecond, ()>;
@@ -147,6 +149,9 @@ impl Config for XcmConfig {
 	type AssetTrap = ();
 	type AssetClaims = ();
 	type SubscriptionService = ();
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 #[frame_support::pallet]
@@ -183,80 +188,51 @@ pub mod mock_msg_queue {
 		}
 	}
 
-	pub type MessageId = [u8; 32];
-
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		// XCMP
-		/// Some XCM was executed OK.
-		Success(Option<T::Hash>),
-		/// Some XCM failed.
-		Fail(Option<T::Hash>, XcmError),
-		/// Bad XCM version used.
-		BadVersion(Option<T::Hash>),
-		/// Bad XCM format used.
-		BadFormat(Option<T::Hash>),
-
-		// DMP
-		/// Downward message is invalid XCM.
-		InvalidFormat(MessageId),
-		/// Downward message is unsupported version of XCM.
-		UnsupportedVersion(MessageId),
-		/// Downward message executed with the given outcome.
-		ExecutedDownward(MessageId, Outcome),
+		/// Some message is invalid XCM.
+		InvalidFormat { id: MessageId },
+		/// Some message is an unsupported version of XCM.
+		UnsupportedVersion { id: MessageId },
+		/// Some message was processed; `success` is true only if its execution was complete.
+		/// Overweight messages are not executed and reported as unsuccessful with no weight used.
+		Processed { id: MessageId, origin: MultiLocation, success: bool, weight: Weight },
 	}
 
 	impl<T: Config> Pallet<T> {
 		pub fn set_para_id(para_id: ParaId) {
 			ParachainId::<T>::put(para_id);
 		}
+	}
 
-		fn handle_xcmp_message(
-			sender: ParaId,
-			_sent_at: RelayBlockNumber,
-			xcm: VersionedXcm<T::Call>,
-			max_weight: Weight,
-		) -> Result<Weight, XcmError> {
-			let hash = Encode::using_encoded(&xcm, T::Hashing::hash);
-			let (result, event) = match Xcm::<T::Call>::try_from(xcm) {
-				Ok(xcm) => {
-					let location = (1, Parachain(sender.into()));
-					match T::XcmExecutor::execute_xcm(location, xcm, max_weight) {
-						Outcome::Error(e) => (Err(e.clone()), Event::Fail(Some(hash), e)),
-						Outcome::Complete(w) => (Ok(w), Event::Success(Some(hash))),
-						// As far as the caller is concerned, this was dispatched without error, so
-						// we just report the weight used.
-						Outcome::Incomplete(w, e) => (Ok(w), Event::Fail(Some(hash), e)),
-					}
-				},
-				Err(()) => (Err(XcmError::UnhandledXcmVersion), Event::BadVersion(Some(hash))),
-			};
-			Self::deposit_event(event);
-			result
+	impl<T: Config> OnMessageProcessed for Pallet<T> {
+		fn on_processed(id: MessageId, origin: MultiLocation, success: bool, weight: Weight) {
+			Self::deposit_event(Event::Processed { id, origin, success, weight });
+		}
+
+		fn on_failed(id: MessageId, origin: MultiLocation, error: ProcessMessageError) {
+			Self::deposit_event(match error {
+				ProcessMessageError::BadFormat => Event::InvalidFormat { id },
+				ProcessMessageError::Unsupported => Event::UnsupportedVersion { id },
+				ProcessMessageError::Overweight(_) =>
+					Event::Processed { id, origin, success: false, weight: 0 },
+			});
 		}
 	}
 
+	type Processor<T> = ProcessXcmMessage<
+		MultiLocation,
+		<T as Config>::XcmExecutor,
+		<T as frame_system::Config>::Call,
+	>;
+
 	impl<T: Config> XcmpMessageHandler for Pallet<T> {
 		fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
 			iter: I,
 			max_weight: Weight,
 		) -> Weight {
-			for (sender, sent_at, data) in iter {
-				let mut data_ref = data;
-				let _ = XcmpMessageFormat::decode(&mut data_ref)
-					.expect("Simulator encodes with versioned xcm format; qed");
-
-				let mut remaining_fragments = &data_ref[..];
-				while !remaining_fragments.is_empty() {
-					if let Ok(xcm) = VersionedXcm::<T::Call>::decode(&mut remaining_fragments) {
-						let _ = Self::handle_xcmp_message(sender, sent_at, xcm, max_weight);
-					} else {
-						debug_assert!(false, "Invalid incoming XCMP message data");
-					}
-				}
-			}
-			max_weight
+			XcmpSink::<Processor<T>, Self>::handle_xcmp_messages(iter, max_weight)
 		}
 	}
 
@@ -265,25 +241,14 @@ pub mod mock_msg_queue {
 			iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
 			limit: Weight,
 		) -> Weight {
-			for (_i, (_sent_at, data)) in iter.enumerate() {
-				let id = sp_io::hashing::blake2_256(&data[..]);
+			let iter = iter.inspect(|(_sent_at, data)| {
 				let maybe_msg =
 					VersionedXcm::<T::Call>::decode(&mut &data[..]).map(Xcm::<T::Call>::try_from);
-				match maybe_msg {
-					Err(_) => {
-						Self::deposit_event(Event::InvalidFormat(id));
-					},
-					Ok(Err(())) => {
-						Self::deposit_event(Event::UnsupportedVersion(id));
-					},
-					Ok(Ok(x)) => {
-						let outcome = T::XcmExecutor::execute_xcm(Parent, x.clone(), limit);
-						<ReceivedDmp<T>>::append(x);
-						Self::deposit_event(Event::ExecutedDownward(id, outcome));
-					},
+				if let Ok(Ok(x)) = maybe_msg {
+					<ReceivedDmp<T>>::append(x);
 				}
-			}
-			limit
+			});
+			DmpSink::<Processor<T>, Self>::handle_dmp_messages(iter, limit)
 		}
 	}
 }
@@ -305,11 +270,19 @@ impl pallet_xcm::Config for Runtime {
 	type XcmTeleportFilter = Nothing;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Origin = Origin;
 	type Call = Call;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type TrappedAssetsExpiry = frame_support::traits::ConstU64<100>;
+	type TreasuryLocation = ();
+	type Currency = Balances;
+	type TrapDeposit = ();
+	type SovereignAccountOf = LocationToAccountId;
+	type XcmSendFilter = Everything;
+	type SendRateLimit = ();
+	type WeightInfo = pallet_xcm::TestWeightInfo;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
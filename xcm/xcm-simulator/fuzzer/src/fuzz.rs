@@ -22,7 +22,9 @@ use polkadot_parachain::primitives::Id as ParaId;
 use sp_runtime::traits::AccountIdConversion;
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
 
-use frame_support::assert_ok;
+use frame_support::{assert_ok, traits::Currency};
+use sp_core::H256;
+use sp_runtime::traits::{Saturating, Zero};
 use xcm::{latest::prelude::*, MAX_XCM_DECODE_DEPTH};
 
 pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([0u8; 32]);
@@ -104,17 +106,84 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
 pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
 
+/// Send `message` over the route selected by `selector`, processing it and any messages which
+/// result from it.
+fn send_message(selector: u8, message: Xcm<()>) {
+	match selector % 3 {
+		0 => ParaA::execute_with(|| {
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, Parent, message));
+		}),
+		1 => ParaA::execute_with(|| {
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, (Parent, Parachain(2)), message));
+		}),
+		_ => Relay::execute_with(|| {
+			assert_ok!(RelayChainPalletXcm::send_xcm(Here, Parachain(1), message));
+		}),
+	}
+}
+
+/// Check the invariants which must hold on a chain whatever messages it has executed:
+///
+/// - its total issuance is exactly the sum of all balances, so no funds were created or destroyed
+///   without being accounted for;
+/// - every asset trap it has reported is either fully recorded or fully removed.
+fn check_invariants<T: pallet_balances::Config + pallet_xcm::Config>(
+	trapped_hash: impl Fn(<T as frame_system::Config>::Event) -> Option<H256>,
+) {
+	let total =
+		frame_system::Account::<T>::iter_keys().fold(Zero::zero(), |sum: T::Balance, who| {
+			sum.saturating_add(pallet_balances::Pallet::<T>::total_balance(&who))
+		});
+	assert_eq!(
+		total,
+		pallet_balances::Pallet::<T>::total_issuance(),
+		"Total issuance must be the sum of all balances",
+	);
+
+	for hash in frame_system::Pallet::<T>::events()
+		.into_iter()
+		.filter_map(|record| trapped_hash(record.event))
+	{
+		assert_eq!(
+			pallet_xcm::Pallet::<T>::asset_trap(hash) > 0,
+			pallet_xcm::Pallet::<T>::asset_trap_details(hash).is_some(),
+			"Asset trap {:?} must be recorded along with its details",
+			hash,
+		);
+	}
+}
+
+fn check_all_invariants() {
+	let para_trapped_hash = |event| match event {
+		parachain::Event::PolkadotXcm(pallet_xcm::Event::AssetsTrapped(hash, ..)) => Some(hash),
+		_ => None,
+	};
+	ParaA::execute_with(|| check_invariants::<parachain::Runtime>(para_trapped_hash));
+	ParaB::execute_with(|| check_invariants::<parachain::Runtime>(para_trapped_hash));
+	Relay::execute_with(|| {
+		check_invariants::<relay_chain::Runtime>(|event| match event {
+			relay_chain::Event::XcmPallet(pallet_xcm::Event::AssetsTrapped(hash, ..)) => Some(hash),
+			_ => None,
+		})
+	});
+}
+
+/// Execute a sequence of messages, each preceded by a byte selecting its route, checking the
+/// invariants of every chain after each message. Decoding stops at the first malformed message.
 fn run_one_input(mut data: &[u8]) {
 	MockNet::reset();
-	if let Ok(m) = Xcm::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data) {
+	while let Some((&selector, rest)) = data.split_first() {
+		data = rest;
+		let message = match Xcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data) {
+			Ok(message) => message,
+			Err(_) => break,
+		};
 		#[cfg(not(fuzzing))]
 		{
-			println!("Executing message {:?}", m);
+			println!("Executing message {:?} over route {}", message, selector % 3);
 		}
-		ParaA::execute_with(|| {
-			assert_ok!(ParachainPalletXcm::send_xcm(Here, Parent, m));
-		});
-		Relay::execute_with(|| {});
+		send_message(selector, message);
+		check_all_invariants();
 	}
 }
 
@@ -129,7 +198,10 @@ fn main() {
 	}
 	#[cfg(not(fuzzing))]
 	{
-		//This code path can be used to generate a line-code coverage report in HTML
+		//This code path replays inputs, printing each message as it is executed. Failing inputs
+		//found by the fuzzer may first be shrunk with honggfuzz's `--minimize` mode.
+		//
+		//This code path can also be used to generate a line-code coverage report in HTML
 		//that depicts which lines are executed by at least one input in the current fuzzing queue.
 		//To generate this code coverage report, run the following commands:
 		/*
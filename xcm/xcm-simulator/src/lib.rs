@@ -35,6 +35,11 @@ pub use polkadot_runtime_parachains::{
 pub use xcm::{latest::prelude::*, VersionedXcm};
 pub use xcm_executor::XcmExecutor;
 
+/// Returns the blake2-256 hash of the encoded `message`, used to identify messages in the buses.
+pub fn fake_message_hash<Call>(message: &Xcm<Call>) -> XcmHash {
+	message.using_encoded(sp_io::hashing::blake2_256)
+}
+
 pub trait TestExt {
 	/// Initialize the test environment.
 	fn new_ext() -> sp_io::TestExternalities;
@@ -56,15 +61,19 @@ pub trait TestExt {
 	}
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum MessageKind {
 	Ump,
 	Dmp,
 	Xcmp,
+	/// A message between the relay chains of two networks joined by a simulated bridge.
+	Bridge,
 }
 
 pub fn encode_xcm(message: Xcm<()>, message_kind: MessageKind) -> Vec<u8> {
 	match message_kind {
-		MessageKind::Ump | MessageKind::Dmp => VersionedXcm::<()>::from(message).encode(),
+		MessageKind::Ump | MessageKind::Dmp | MessageKind::Bridge =>
+			VersionedXcm::<()>::from(message).encode(),
 		MessageKind::Xcmp => {
 			let fmt = XcmpMessageFormat::ConcatenatedVersionedXcm;
 			let mut outbound = fmt.encode();
@@ -76,6 +85,54 @@ pub fn encode_xcm(message: Xcm<()>, message_kind: MessageKind) -> Vec<u8> {
 	}
 }
 
+/// A relay chain which may receive messages from another network over a simulated bridge.
+pub trait BridgeMessageHandlerT {
+	/// Execute `message`, which was sent over the bridge by `origin`.
+	fn handle_bridged_message(origin: MultiLocation, message: Xcm<()>) -> Outcome;
+}
+
+/// A message which was carried over a single hop of a simulated network.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TracedMessage {
+	/// The name of the network in which the message was sent.
+	pub network: &'static str,
+	/// The sender of the message, relative to the relay chain of `network`.
+	pub sender: MultiLocation,
+	/// The channel over which the message was carried.
+	pub channel: MessageKind,
+	/// The destination of the message, relative to `sender`.
+	pub destination: MultiLocation,
+	/// The message, as it was encoded for delivery.
+	pub encoded: Vec<u8>,
+	/// The outcome of delivering the message. For channels whose handlers only report the weight
+	/// used, this is `Outcome::Complete` with that weight.
+	pub outcome: Outcome,
+}
+
+thread_local! {
+	/// Every hop taken by a message in any network, in the order in which they were processed.
+	pub static MESSAGE_TRACE: RefCell<Vec<TracedMessage>> = RefCell::new(Vec::new());
+}
+
+/// Record a hop taken by a message.
+pub fn trace_message(message: TracedMessage) {
+	MESSAGE_TRACE.with(|t| t.borrow_mut().push(message));
+}
+
+/// Remove and return every hop recorded since the trace was last taken or reset.
+pub fn take_message_trace() -> Vec<TracedMessage> {
+	MESSAGE_TRACE.with(|t| t.take())
+}
+
+thread_local! {
+	#[deprecated(note = "each network declared with `decl_test_network` has its own message buses")]
+	pub static PARA_MESSAGE_BUS: RefCell<VecDeque<(ParaId, MultiLocation, Xcm<()>)>>
+		= RefCell::new(VecDeque::new());
+	#[deprecated(note = "each network declared with `decl_test_network` has its own message buses")]
+	pub static RELAY_MESSAGE_BUS: RefCell<VecDeque<(MultiLocation, Xcm<()>)>>
+		= RefCell::new(VecDeque::new());
+}
+
 #[macro_export]
 #[rustfmt::skip]
 macro_rules! decl_test_relay_chain {
@@ -105,6 +162,21 @@ macro_rules! decl_test_relay_chain {
 				})
 			}
 		}
+
+		impl $crate::BridgeMessageHandlerT for $name {
+			fn handle_bridged_message(
+				origin: $crate::MultiLocation,
+				message: $crate::Xcm<()>,
+			) -> $crate::Outcome {
+				use $crate::{ExecuteXcm, TestExt};
+
+				Self::execute_with(|| {
+					$crate::XcmExecutor::<$xcm_config>::execute_xcm(
+						origin, message.into(), $crate::Weight::max_value(),
+					)
+				})
+			}
+		}
 	};
 }
 
@@ -189,104 +261,195 @@ macro_rules! __impl_ext {
 					if let Err(xcm_error) = process_para_messages() {
 						panic!("Parachain XCM execution failure: {:?}", xcm_error);
 					}
+					if let Err(xcm_error) = process_bridge_messages() {
+						panic!("Bridged XCM execution failure: {:?}", xcm_error);
+					}
 				}
 			}
 		}
 	};
 }
 
-thread_local! {
-	pub static PARA_MESSAGE_BUS: RefCell<VecDeque<(ParaId, MultiLocation, Xcm<()>)>>
-		= RefCell::new(VecDeque::new());
-	pub static RELAY_MESSAGE_BUS: RefCell<VecDeque<(MultiLocation, Xcm<()>)>>
-		= RefCell::new(VecDeque::new());
-}
-
+/// Declare a network of a relay chain and its parachains, along with the message buses and routers
+/// which connect them.
+///
+/// The relay chain may optionally be bridged to the relay chains of other networks, each given by
+/// a key under which it is reached at `(Parent, GeneralKey(key))`. Messages arriving over the
+/// bridge originate from `(Parent, GeneralKey(bridge_key))`.
+///
+/// Each network must be declared in its own module, along with its chains.
 #[macro_export]
 macro_rules! decl_test_network {
 	(
 		pub struct $name:ident {
 			relay_chain = $relay_chain:ty,
 			parachains = vec![ $( ($para_id:expr, $parachain:ty), )* ],
+			$(
+				bridge_key = $bridge_key:expr,
+				bridged_relay_chains = vec![ $( ($remote_key:expr, $remote_relay:ty), )* ],
+			)?
 		}
 	) => {
 		pub struct $name;
 
+		thread_local! {
+			pub static PARA_MESSAGE_BUS: $crate::RefCell<
+				$crate::VecDeque<($crate::ParaId, $crate::MultiLocation, $crate::Xcm<()>)>
+			> = $crate::RefCell::new($crate::VecDeque::new());
+			pub static RELAY_MESSAGE_BUS: $crate::RefCell<
+				$crate::VecDeque<($crate::MultiLocation, $crate::Xcm<()>)>
+			> = $crate::RefCell::new($crate::VecDeque::new());
+			pub static BRIDGE_MESSAGE_BUS: $crate::RefCell<
+				$crate::VecDeque<($crate::MultiLocation, $crate::Xcm<()>)>
+			> = $crate::RefCell::new($crate::VecDeque::new());
+		}
+
 		impl $name {
 			pub fn reset() {
 				use $crate::{TestExt, VecDeque};
 				// Reset relay chain message bus
-				$crate::RELAY_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				RELAY_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
 				// Reset parachain message bus
-				$crate::PARA_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				PARA_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				// Reset bridge message bus
+				BRIDGE_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				$crate::take_message_trace();
 				<$relay_chain>::reset_ext();
 				$( <$parachain>::reset_ext(); )*
 			}
 		}
 
-		/// Check if any messages exist in either message bus
+		/// Check if any messages exist in any message bus
 		fn exists_messages_in_any_bus() -> bool {
-			use $crate::{RELAY_MESSAGE_BUS, PARA_MESSAGE_BUS};
 			let no_relay_messages_left = RELAY_MESSAGE_BUS.with(|b| b.borrow().is_empty());
 			let no_parachain_messages_left = PARA_MESSAGE_BUS.with(|b| b.borrow().is_empty());
-			!(no_relay_messages_left && no_parachain_messages_left)
+			let no_bridge_messages_left = BRIDGE_MESSAGE_BUS.with(|b| b.borrow().is_empty());
+			!(no_relay_messages_left && no_parachain_messages_left && no_bridge_messages_left)
 		}
 
 		/// Process all messages originating from parachains.
+		// Unreachable when there are no chains to route to.
+		#[allow(unreachable_code)]
 		fn process_para_messages() -> $crate::XcmResult {
 			use $crate::{UmpSink, XcmpMessageHandlerT};
 
-			while let Some((para_id, destination, message)) = $crate::PARA_MESSAGE_BUS.with(
+			while let Some((para_id, destination, message)) = PARA_MESSAGE_BUS.with(
 				|b| b.borrow_mut().pop_front()) {
-				match destination.interior() {
+				let sender = $crate::MultiLocation::new(0, $crate::X1($crate::Parachain(para_id.into())));
+				let (channel, encoded, outcome) = match destination.interior() {
 					$crate::Junctions::Here if destination.parent_count() == 1 => {
 						let encoded = $crate::encode_xcm(message, $crate::MessageKind::Ump);
 						let r = <$relay_chain>::process_upward_message(
 							para_id, &encoded[..],
 							$crate::Weight::max_value(),
 						);
-						if let Err((id, required)) = r {
-							return Err($crate::XcmError::WeightLimitReached(required));
+						match r {
+							Ok(weight) => ($crate::MessageKind::Ump, encoded, $crate::Outcome::Complete(weight)),
+							Err((_id, required)) => {
+								let error = $crate::XcmError::WeightLimitReached(required);
+								$crate::trace_message($crate::TracedMessage {
+									network: stringify!($name),
+									sender,
+									channel: $crate::MessageKind::Ump,
+									destination,
+									encoded,
+									outcome: $crate::Outcome::Error(error.clone()),
+								});
+								return Err(error);
+							},
 						}
 					},
 					$(
 						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 1 => {
 							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Xcmp);
 							let messages = vec![(para_id, 1, &encoded[..])];
-							let _weight = <$parachain>::handle_xcmp_messages(
+							let weight = <$parachain>::handle_xcmp_messages(
 								messages.into_iter(),
 								$crate::Weight::max_value(),
 							);
+							($crate::MessageKind::Xcmp, encoded, $crate::Outcome::Complete(weight))
 						},
 					)*
 					_ => {
 						return Err($crate::XcmError::Unroutable);
 					}
-				}
+				};
+				$crate::trace_message($crate::TracedMessage {
+					network: stringify!($name),
+					sender,
+					channel,
+					destination,
+					encoded,
+					outcome,
+				});
 			}
 
 			Ok(())
 		}
 
 		/// Process all messages originating from the relay chain.
+		// Unreachable when there are no chains to route to.
+		#[allow(unreachable_code)]
 		fn process_relay_messages() -> $crate::XcmResult {
 			use $crate::DmpMessageHandlerT;
 
-			while let Some((destination, message)) = $crate::RELAY_MESSAGE_BUS.with(
+			while let Some((destination, message)) = RELAY_MESSAGE_BUS.with(
 				|b| b.borrow_mut().pop_front()) {
-				match destination.interior() {
+				let (encoded, weight) = match destination.interior() {
 					$(
 						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 0 => {
 							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Dmp);
 							// NOTE: RelayChainBlockNumber is hard-coded to 1
-							let messages = vec![(1, encoded)];
-							let _weight = <$parachain>::handle_dmp_messages(
+							let messages = vec![(1, encoded.clone())];
+							let weight = <$parachain>::handle_dmp_messages(
 								messages.into_iter(), $crate::Weight::max_value(),
 							);
+							(encoded, weight)
 						},
 					)*
 					_ => return Err($crate::XcmError::Transport("Only sends to children parachain.")),
-				}
+				};
+				$crate::trace_message($crate::TracedMessage {
+					network: stringify!($name),
+					sender: $crate::MultiLocation::here(),
+					channel: $crate::MessageKind::Dmp,
+					destination,
+					encoded,
+					outcome: $crate::Outcome::Complete(weight),
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Process all messages sent by the relay chain over a bridge.
+		// Unreachable when there are no chains to route to.
+		#[allow(unreachable_code)]
+		fn process_bridge_messages() -> $crate::XcmResult {
+			while let Some((destination, message)) = BRIDGE_MESSAGE_BUS.with(
+				|b| b.borrow_mut().pop_front()) {
+				let encoded = $crate::encode_xcm(message.clone(), $crate::MessageKind::Bridge);
+				let outcome = match (destination.parent_count(), destination.interior()) {
+					$($(
+						(1, $crate::X1($crate::GeneralKey(key))) if &key[..] == &$remote_key[..] => {
+							use $crate::BridgeMessageHandlerT;
+							let origin = $crate::MultiLocation::new(
+								1,
+								$crate::X1($crate::GeneralKey($bridge_key.to_vec())),
+							);
+							<$remote_relay>::handle_bridged_message(origin, message)
+						},
+					)*)?
+					_ => return Err($crate::XcmError::Unroutable),
+				};
+				$crate::trace_message($crate::TracedMessage {
+					network: stringify!($name),
+					sender: $crate::MultiLocation::here(),
+					channel: $crate::MessageKind::Bridge,
+					destination,
+					encoded,
+					outcome,
+				});
 			}
 
 			Ok(())
@@ -296,45 +459,68 @@ macro_rules! decl_test_network {
 		pub struct ParachainXcmRouter<T>($crate::PhantomData<T>);
 
 		impl<T: $crate::Get<$crate::ParaId>> $crate::SendXcm for ParachainXcmRouter<T> {
-			fn send_xcm(destination: impl Into<$crate::MultiLocation>, message: $crate::Xcm<()>) -> $crate::SendResult {
-				use $crate::{UmpSink, XcmpMessageHandlerT};
-
-				let destination = destination.into();
-				match destination.interior() {
-					$crate::Junctions::Here if destination.parent_count() == 1 => {
-						$crate::PARA_MESSAGE_BUS.with(
-							|b| b.borrow_mut().push_back((T::get(), destination, message)));
-						Ok(())
-					},
+			type Ticket = ($crate::ParaId, $crate::MultiLocation, $crate::Xcm<()>);
+			fn validate(
+				destination: &mut Option<$crate::MultiLocation>,
+				message: &mut Option<$crate::Xcm<()>>,
+			) -> $crate::SendResult<($crate::ParaId, $crate::MultiLocation, $crate::Xcm<()>)> {
+				let d = destination.take().ok_or($crate::SendError::MissingArgument)?;
+				match (d.parent_count(), d.interior()) {
+					(1, $crate::Junctions::Here) => {},
 					$(
-						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 1 => {
-							$crate::PARA_MESSAGE_BUS.with(
-								|b| b.borrow_mut().push_back((T::get(), destination, message)));
-							Ok(())
-						},
+						(1, $crate::X1($crate::Parachain(id))) if *id == $para_id => {},
 					)*
-					_ => Err($crate::SendError::CannotReachDestination(destination, message)),
+					_ => {
+						*destination = Some(d);
+						return Err($crate::SendError::NotApplicable)
+					},
 				}
+				let m = message.take().ok_or($crate::SendError::MissingArgument)?;
+				Ok(((T::get(), d, m), $crate::MultiAssets::new()))
+			}
+			fn deliver(
+				triple: ($crate::ParaId, $crate::MultiLocation, $crate::Xcm<()>),
+			) -> Result<$crate::XcmHash, $crate::SendError> {
+				let hash = $crate::fake_message_hash(&triple.2);
+				PARA_MESSAGE_BUS.with(|b| b.borrow_mut().push_back(triple));
+				Ok(hash)
 			}
 		}
 
-		/// XCM router for relay chain.
+		/// XCM router for relay chain, which routes both to its parachains and over its bridges.
 		pub struct RelayChainXcmRouter;
 		impl $crate::SendXcm for RelayChainXcmRouter {
-			fn send_xcm(destination: impl Into<$crate::MultiLocation>, message: $crate::Xcm<()>) -> $crate::SendResult {
-				use $crate::DmpMessageHandlerT;
-
-				let destination = destination.into();
-				match destination.interior() {
+			type Ticket = ($crate::MultiLocation, $crate::Xcm<()>);
+			fn validate(
+				destination: &mut Option<$crate::MultiLocation>,
+				message: &mut Option<$crate::Xcm<()>>,
+			) -> $crate::SendResult<($crate::MultiLocation, $crate::Xcm<()>)> {
+				let d = destination.take().ok_or($crate::SendError::MissingArgument)?;
+				match (d.parent_count(), d.interior()) {
 					$(
-						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 0 => {
-							$crate::RELAY_MESSAGE_BUS.with(
-								|b| b.borrow_mut().push_back((destination, message)));
-							Ok(())
-						},
+						(0, $crate::X1($crate::Parachain(id))) if *id == $para_id => {},
 					)*
-					_ => Err($crate::SendError::Unroutable),
+					$($(
+						(1, $crate::X1($crate::GeneralKey(key))) if &key[..] == &$remote_key[..] => {},
+					)*)?
+					_ => {
+						*destination = Some(d);
+						return Err($crate::SendError::NotApplicable)
+					},
+				}
+				let m = message.take().ok_or($crate::SendError::MissingArgument)?;
+				Ok(((d, m), $crate::MultiAssets::new()))
+			}
+			fn deliver(
+				pair: ($crate::MultiLocation, $crate::Xcm<()>),
+			) -> Result<$crate::XcmHash, $crate::SendError> {
+				let hash = $crate::fake_message_hash(&pair.1);
+				if pair.0.parent_count() == 0 {
+					RELAY_MESSAGE_BUS.with(|b| b.borrow_mut().push_back(pair));
+				} else {
+					BRIDGE_MESSAGE_BUS.with(|b| b.borrow_mut().push_back(pair));
 				}
+				Ok(hash)
 			}
 		}
 	};